@@ -13,6 +13,9 @@ locals {
     Region = var.region
     creationDate = time_static.on_cluster_create.rfc3339
     QoveryProduct = "EKS"
+    {% for key, value in cost_allocation_tags %}
+    {{ key }} = "{{ value }}"
+    {% endfor %}
     {% if resource_expiration_in_seconds > -1 %}ttl = var.resource_expiration_in_seconds{% endif %}
   }
 
@@ -0,0 +1,85 @@
+{%- if cluster_backup_enabled %}
+resource "aws_iam_role" "iam_eks_velero" {
+  name        = "qovery-velero-${var.kubernetes_cluster_id}"
+  tags        = local.tags_eks
+
+  assume_role_policy = <<POLICY
+{
+  "Version": "2012-10-17",
+  "Statement": [
+    {
+      "Effect": "Allow",
+      "Principal": {
+        "Federated": "${aws_iam_openid_connect_provider.oidc.arn}"
+      },
+      "Action": ["sts:AssumeRoleWithWebIdentity"],
+      "Condition": {
+        "StringEquals": {
+          "${replace(aws_iam_openid_connect_provider.oidc.url, "https://", "")}:sub": "system:serviceaccount:velero:velero"
+        }
+      }
+    }
+  ]
+}
+POLICY
+}
+
+resource "aws_iam_policy" "velero_policy" {
+  name = aws_iam_role.iam_eks_velero.name
+  description = "Policy for cluster backups"
+
+  policy = <<POLICY
+{
+    "Version": "2012-10-17",
+    "Statement": [
+        {
+            "Sid": "backupsS3Velero",
+            "Effect": "Allow",
+            "Action": [
+                "s3:GetObject",
+                "s3:PutObject",
+                "s3:DeleteObject",
+                "s3:AbortMultipartUpload",
+                "s3:ListMultipartUploadParts",
+                "s3:ListBucket"
+            ],
+            "Resource": [
+                "arn:aws:s3:::{{ cluster_backups_bucket_name }}",
+                "arn:aws:s3:::{{ cluster_backups_bucket_name }}/*"
+            ]
+        },
+        {
+            "Sid": "restoreS3Velero",
+            "Effect": "Allow",
+            "Action": [
+                "s3:GetObject",
+                "s3:ListBucket"
+            ],
+            "Resource": [
+                "arn:aws:s3:::qovery-backups-*",
+                "arn:aws:s3:::qovery-backups-*/*"
+            ]
+        },
+        {
+            "Sid": "snapshotsVelero",
+            "Effect": "Allow",
+            "Action": [
+                "ec2:DescribeVolumes",
+                "ec2:DescribeSnapshots",
+                "ec2:CreateTags",
+                "ec2:CreateVolume",
+                "ec2:CreateSnapshot",
+                "ec2:DeleteSnapshot"
+            ],
+            "Resource": "*"
+        }
+    ]
+}
+POLICY
+}
+
+resource "aws_iam_role_policy_attachment" "velero_attachment" {
+  role       = aws_iam_role.iam_eks_velero.name
+  policy_arn = aws_iam_policy.velero_policy.arn
+}
+{%- endif %}
@@ -10,6 +10,7 @@ output "cluster_security_group_id" { value = aws_eks_cluster.eks_cluster.vpc_con
 output "aws_iam_alb_controller_arn" { value = aws_iam_role.aws_load_balancer_controller.arn }
 output "aws_iam_eks_prometheus_role_arn" { value = aws_iam_role.iam_eks_prometheus.arn }
 output "aws_s3_prometheus_bucket_name" { value = aws_s3_bucket.prometheus_bucket.id }
+output "aws_iam_velero_role_arn" { value = {% if cluster_backup_enabled %}aws_iam_role.iam_eks_velero.arn{% else %}""{% endif %} }
 output "cluster_name" { value = aws_eks_cluster.eks_cluster.name }
 output "cluster_arn" { value = aws_eks_cluster.eks_cluster.arn } // or _long_id
 output "cluster_id" { value = aws_eks_cluster.eks_cluster.id }
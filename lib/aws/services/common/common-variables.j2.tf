@@ -49,6 +49,9 @@ variable "database_tags" {
     "q_client_id"                                                                    = "{{ owner_id }}"
     "q_environment_id"                                                               = "{{ environment_id }}"
     "q_project_id"                                                                   = "{{ project_id }}"
+    {% for key, value in cost_allocation_tags %}
+    "{{ key }}"                                                                      = "{{ value }}"
+    {% endfor %}
     {% if resource_expiration_in_seconds > -1 %}
     "ttl"                                                                            = "{{ resource_expiration_in_seconds }}"
     {% endif %}
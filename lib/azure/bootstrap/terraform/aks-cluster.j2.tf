@@ -14,7 +14,13 @@ resource "azurerm_kubernetes_cluster" "primary" {
   default_node_pool {
     name    = "{{ node_group_default.name }}"
     vm_size = "{{ node_group_default.instance_type }}"
+    {% if node_groups_paused %}
+    # cluster is paused: tainting the nodes with CriticalAddonsOnly=true:NoSchedule so workloads of the user node pools
+    # don't move to the system node pool, and it doesn't scale up for them
+    only_critical_addons_enabled = true
+    {% else %}
     # only_critical_addons_enabled = true # tainting the nodes with CriticalAddonsOnly=true:NoSchedule to avoid scheduling workloads on the system node pool
+    {% endif %}
     zones                  = ["{{ node_group_default.zone }}"]
     {% if node_group_default.zone == "1" %}
     vnet_subnet_id         = azurerm_subnet.node_cidr_zone_1.id
@@ -26,9 +32,14 @@ resource "azurerm_kubernetes_cluster" "primary" {
     # this is an error and should fail
     vnet_subnet_id         = "unsupported"
     {% endif %}
+    {% if node_groups_paused %}
+    auto_scaling_enabled   = false
+    node_count             = {{ node_group_default.min_nodes }}
+    {% else %}
     auto_scaling_enabled   = true
     min_count              = {{ node_group_default.min_nodes }}
     max_count              = {{ node_group_default.max_nodes }}
+    {% endif %}
     node_public_ip_enabled = false
     os_disk_size_gb        = {{ node_group_default.disk_size_in_gib }}
     orchestrator_version   = var.kubernetes_version # Keep nodes up to date with control plane
@@ -56,13 +67,34 @@ resource "azurerm_kubernetes_cluster" "primary" {
     dns_service_ip      = local.dns_service_ip
   }
 
-  # TODO(benjaminch): Azure integration, to be variabilized
+  {% if maintenance_window_enabled %}
+  maintenance_window {
+    {% for allowed in maintenance_window_allowed %}
+    allowed {
+      day   = "{{ allowed.day }}"
+      hours = [{{ allowed.hours | join(sep=", ") }}]
+    }
+    {% endfor %}
+  }
+
+  maintenance_window_node_os {
+    frequency   = "{{ maintenance_window_node_os_frequency }}"
+    interval    = 1
+    {% if maintenance_window_node_os_frequency == "Weekly" %}
+    day_of_week = "{{ maintenance_window_node_os_day_of_week }}"
+    {% endif %}
+    start_time  = "{{ maintenance_window_start_time }}"
+    duration    = {{ maintenance_window_duration_in_hours }}
+    utc_offset  = "+00:00"
+  }
+  {% else %}
   maintenance_window {
     allowed {
       day   = "Tuesday"
       hours = [21, 22, 23]
     }
   }
+  {% endif %}
 
   tags = local.tags_aks
 
@@ -90,9 +122,19 @@ resource "azurerm_kubernetes_cluster_node_pool" "node_pool_zone_{{ node_group.zo
   # this is an error and should fail
   vnet_subnet_id         = "unsupported"
   {% endif %}
+  {% if node_groups_paused %}
+  # cluster is paused: user node pools are scaled to zero, the system node pool is kept
+  auto_scaling_enabled   = false
+  node_count             = 0
+  {% else %}
   auto_scaling_enabled   = true
   min_count              = {{ node_group.min_nodes }}
   max_count              = {{ node_group.max_nodes }}
+  {% if node_group.name in node_groups_restored_nodes %}
+  # cluster is resumed: restore the node count recorded at pause time
+  node_count             = {{ node_groups_restored_nodes[node_group.name] }}
+  {% endif %}
+  {% endif %}
   os_disk_size_gb        = {{ node_group.disk_size_in_gib }}
   node_public_ip_enabled = false
   orchestrator_version   = var.kubernetes_version # Keep nodes up to date with control plane
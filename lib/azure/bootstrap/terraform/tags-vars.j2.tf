@@ -8,6 +8,9 @@ locals {
       region                                          = "{{ azure_location }}",
       creation_date                                   = time_static.on_cluster_create.unix,
       qovery_product                                  = "aks",
+      {% for key, value in cost_allocation_tags %}
+      {{ key }} = "{{ value }}",
+      {% endfor %}
       {% if resource_expiration_in_seconds > -1 %}ttl = {{ resource_expiration_in_seconds }} {% endif %}
   }
 }
\ No newline at end of file
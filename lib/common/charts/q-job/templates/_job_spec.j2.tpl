@@ -0,0 +1,221 @@
+    {%- if service.advanced_settings.job_backoff_limit_per_index is number %}
+    backoffLimitPerIndex: {{ service.advanced_settings.job_backoff_limit_per_index }}
+    {%- if service.advanced_settings.job_max_failed_indexes is number %}
+    maxFailedIndexes: {{ service.advanced_settings.job_max_failed_indexes }}
+    {%- endif %}
+    {%- else %}
+    backoffLimit: {{ service.max_nb_restart }}
+    {%- endif %}
+    activeDeadlineSeconds: {{ service.max_duration_in_sec }}
+    {%- if service.advanced_settings.job_delete_ttl_seconds_after_finished %}
+    ttlSecondsAfterFinished: {{ service.advanced_settings.job_delete_ttl_seconds_after_finished }}
+    {%- endif %}
+    parallelism: {{ service.parallelism }}
+    completions: {{ service.completions }}
+    completionMode: {{ service.advanced_settings.job_completion_mode }}
+    {%- if service.advanced_settings.job_pod_failure_policy_ignore_exit_codes or service.advanced_settings.job_pod_failure_policy_fail_job_exit_codes %}
+    podFailurePolicy:
+      rules:
+        {%- if service.advanced_settings.job_pod_failure_policy_fail_job_exit_codes %}
+        - action: FailJob
+          onExitCodes:
+            containerName: {{ service.name }}
+            operator: In
+            values: {{ service.advanced_settings.job_pod_failure_policy_fail_job_exit_codes | json_encode() }}
+        {%- endif %}
+        {%- if service.advanced_settings.job_pod_failure_policy_ignore_exit_codes %}
+        - action: Ignore
+          onExitCodes:
+            containerName: {{ service.name }}
+            operator: In
+            values: {{ service.advanced_settings.job_pod_failure_policy_ignore_exit_codes | json_encode() }}
+        {%- endif %}
+    {%- endif %}
+    template:
+      metadata:
+        labels:
+          envId: {{ environment_short_id }}
+          qovery.com/service-id: {{ service.long_id }}
+          qovery.com/service-type: job
+          qovery.com/environment-id: {{ environment_long_id }}
+          qovery.com/project-id: {{ project_long_id }}
+          qovery.com/deployment-id: {{ deployment_id }}
+          {%- for key, value in labels_group.common %}
+          {{ key }}: |-
+             {{ value }}
+          {%- endfor %}
+        annotations:
+          qovery.com/service-version: {{ service.version }}
+          {%- for key, value in annotations_group.pods %}
+          {{ key }}: |-
+             {{ value }}
+          {%- endfor %}
+      spec:
+        {%- if service.tolerations %}
+        tolerations:
+        {%- for toleration in service.tolerations %}
+          - key: "{{ toleration.key }}"
+            operator: "Exists"
+            effect: "{{ toleration.effect }}"
+        {%- endfor %}
+        {%- endif %}
+        affinity:
+          {%- if service.advanced_settings.deployment_affinity_node_required %}
+          nodeAffinity:
+            requiredDuringSchedulingIgnoredDuringExecution:
+              nodeSelectorTerms:
+              - matchExpressions:
+          {%- for key, value in service.advanced_settings.deployment_affinity_node_required %}
+                  - key: "{{ key }}"
+                    operator: In
+                    values:
+                    - {{ value }}
+          {%- endfor %}
+          {%- endif %}
+        restartPolicy: {{ service.restart_policy }}
+        #shareProcessNamespace: true
+        {%- if service.advanced_settings.security_service_account_name != "" %}
+        automountServiceAccountToken: true
+        serviceAccountName: {{ service.advanced_settings.security_service_account_name }}
+        {%- elif service.with_rbac %}
+        automountServiceAccountToken: true
+        serviceAccountName: {{ service.name }}
+        {%- else %}
+        automountServiceAccountToken: {{ service.advanced_settings.security_automount_service_account_token }}
+        {%- endif %}
+        terminationGracePeriodSeconds: {{ service.advanced_settings.deployment_termination_grace_period_seconds }}
+        {%- if registry %}
+        imagePullSecrets:
+          - name: {{ registry.secret_name }}
+        {%- endif %}
+        volumes:
+          {%- if not service.scaled_job and not service.cronjob_schedule %}
+          - name: output
+            emptyDir: {}
+          {%- endif %}
+          {%- for mounted_file in mounted_files %}
+          - name: {{ mounted_file.id }}-{{ service.short_id }}
+            secret:
+              secretName: {{ mounted_file.id }}-{{ service.short_id }}
+          {%- endfor %}
+        {%- if not service.scaled_job and not service.cronjob_schedule %}
+        initContainers:
+          - name: qovery-wait-container-output
+            # https://kubernetes.io/docs/concepts/workloads/pods/sidecar-containers/#pod-sidecar-containers
+            restartPolicy: Always # this tells that the initContainer is a sidecar
+            image: public.ecr.aws/r3m4q3r9/qovery-job-output-waiter:2025-07-15T14-06-16
+            command: ["/qovery-job-output-waiter", "--watch"]
+            volumeMounts:
+              - name: output
+                mountPath: /qovery-output
+            resources:
+              limits:
+                cpu: "100m"
+                memory: "128Mi"
+              requests:
+                cpu: "100m"
+                memory: "128Mi"
+        {%- endif %}
+        containers:
+          - name: {{ service.name }}
+            image: "{{ service.image_full }}"
+            {%- if service.entrypoint %}
+            command:
+              - |-
+                {{ service.entrypoint }}
+            {%- endif %}
+            args:
+              {%- for arg in service.command_args %}
+              - |-
+                {{ arg }}
+              {%- endfor %}
+            volumeMounts:
+              {%- if not service.scaled_job and not service.cronjob_schedule %}
+              - name: output
+                mountPath: /qovery-output
+              {%- endif %}
+              {%- for mounted_file in mounted_files %}
+              - mountPath: "{{ mounted_file.mount_path }}"
+                subPath: content
+                name: {{ mounted_file.id }}-{{ service.short_id }}
+                readOnly: true
+              {%- endfor %}
+            env:
+              {%- for ev in environment_variables %}
+              - name: "{{ ev.key }}"
+                valueFrom:
+                  secretKeyRef:
+                    name: {{ service.name }}
+                    key: {{ ev.key }}
+              {%- endfor %}
+            {%- if service.default_port %}
+            ports:
+              - containerPort: {{ service.default_port }}
+                name: "p{{ service.default_port }}"
+                protocol: TCP
+            {%- endif %}
+            {%- if service.readiness_probe %}
+            readinessProbe:
+              {%- if service.readiness_probe.type.tcp %}
+              tcpSocket:
+                port: {{ service.readiness_probe.port }}
+                host: {{ service.readiness_probe.type.tcp.host }}
+              {%- endif %}
+              {%- if service.readiness_probe.type.http %}
+              httpGet:
+                port: {{ service.readiness_probe.port }}
+                path: {{ service.readiness_probe.type.http.path }}
+                scheme: {{ service.readiness_probe.type.http.scheme }}
+              {%- endif %}
+              {%- if service.readiness_probe.type.exec %}
+              exec:
+                command: {{ service.readiness_probe.type.exec.commands }}
+              {%- endif %}
+              {%- if service.readiness_probe.type.grpc %}
+              grpc:
+                port: {{ service.readiness_probe.port }}
+                service: {{ service.readiness_probe.type.grpc.service }}
+              {%- endif %}
+              initialDelaySeconds: {{ service.readiness_probe.initial_delay_seconds }}
+              periodSeconds: {{ service.readiness_probe.period_seconds }}
+              timeoutSeconds: {{ service.readiness_probe.timeout_seconds }}
+              successThreshold: {{ service.readiness_probe.success_threshold }}
+              failureThreshold: {{ service.readiness_probe.failure_threshold }}
+            {%- endif %}
+            {%- if service.liveness_probe %}
+            livenessProbe:
+              {%- if service.liveness_probe.type.tcp %}
+              tcpSocket:
+                port: {{ service.liveness_probe.port }}
+                host: {{ service.liveness_probe.type.tcp.host }}
+              {%- endif %}
+              {%- if service.liveness_probe.type.http %}
+              httpGet:
+                port: {{ service.liveness_probe.port }}
+                path: {{ service.liveness_probe.type.http.path }}
+                scheme: {{ service.liveness_probe.type.http.scheme }}
+              {%- endif %}
+              {%- if service.liveness_probe.type.exec %}
+              exec:
+                command: {{ service.liveness_probe.type.exec.commands }}
+              {%- endif %}
+              {%- if service.liveness_probe.type.grpc %}
+              grpc:
+                port: {{ service.liveness_probe.port }}
+                service: {{ service.liveness_probe.type.grpc.service }}
+              {%- endif %}
+              initialDelaySeconds: {{ service.liveness_probe.initial_delay_seconds }}
+              periodSeconds: {{ service.liveness_probe.period_seconds }}
+              timeoutSeconds: {{ service.liveness_probe.timeout_seconds }}
+              successThreshold: {{ service.liveness_probe.success_threshold }}
+              failureThreshold: {{ service.liveness_probe.failure_threshold }}
+            {%- endif %}
+            securityContext:
+              readOnlyRootFilesystem: {{ service.advanced_settings.security_read_only_root_filesystem }}
+            resources:
+              limits:
+                cpu: {{ service.cpu_limit_in_milli }}
+                memory: {{ service.ram_limit_in_mib }}
+              requests:
+                cpu: {{ service.cpu_request_in_milli }}
+                memory: {{ service.ram_request_in_mib }}
@@ -8,6 +8,9 @@ locals {
       region                                          = "{{ gcp_region }}",
       creation_date                                   = time_static.on_cluster_create.unix,
       qovery_product                                  = "gke",
+      {% for key, value in cost_allocation_tags %}
+      {{ key }} = "{{ value }}",
+      {% endfor %}
       {% if resource_expiration_in_seconds > -1 %}ttl = {{ resource_expiration_in_seconds }} {% endif %}
   }
 
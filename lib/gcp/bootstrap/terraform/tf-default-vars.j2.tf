@@ -207,7 +207,7 @@ variable "maintenance_exclusions" {
 variable "maintenance_recurrence" {
   type        = string
   description = "Frequency of the recurring maintenance window in RFC5545 format."
-  default     = ""
+  default     = "{{ cluster_maintenance_recurrence }}"
 }
 
 variable "stack_type" {
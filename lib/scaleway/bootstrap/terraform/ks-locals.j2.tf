@@ -7,6 +7,9 @@ locals {
     Region             = var.region
     creationDate       = time_static.on_cluster_create.rfc3339
     QoveryProduct      = "Kapsule"
+    {% for key, value in cost_allocation_tags %}
+    {{ key }} = "{{ value }}"
+    {% endfor %}
     {% if resource_expiration_in_seconds > -1 %}ttl = var.resource_expiration_in_seconds{% endif %}
   }
   tags_ks_list = [for i, v in local.tags_ks : "${i}=${v}"] # NOTE: Scaleway doesn't support KV style tags
@@ -29,8 +29,8 @@ resource "scaleway_k8s_cluster" "kubernetes_cluster"  {
 
   auto_upgrade {
     enable = true
-    maintenance_window_day = "any"
-    maintenance_window_start_hour = 3
+    maintenance_window_day = "{{ maintenance_window_day }}"
+    maintenance_window_start_hour = {{ maintenance_window_start_hour }}
   }
 
   timeouts {
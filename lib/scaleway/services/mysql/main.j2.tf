@@ -6,6 +6,9 @@ locals {
     q_environment_id = var.q_environment_id
     q_project_id = var.q_project_id
     database_identifier = var.mysql_identifier
+    {% for key, value in cost_allocation_tags %}
+    {{ key }} = "{{ value }}"
+    {% endfor %}
     {% if resource_expiration_in_seconds > -1 %}ttl = var.resource_expiration_in_seconds{% endif %}
     {% if snapshot is defined and snapshot["snapshot_id"] %}meta_last_restored_from = var.snapshot_identifier{% endif %}
   }
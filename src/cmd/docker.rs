@@ -1,9 +1,12 @@
 use crate::cmd::command::{CommandError, CommandKiller, ExecutableCommand, QoveryCommand};
 use crate::io_models::models::CpuArchitecture;
+use derivative::Derivative;
 use itertools::Itertools;
 use once_cell::sync::Lazy;
 use retry::{Error, OperationResult};
+use serde::{Deserialize, Serialize};
 use std::cmp::max;
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
 use std::io::Write;
 use std::num::NonZeroUsize;
@@ -33,6 +36,9 @@ pub enum DockerError {
 
     #[error("Docker command terminated due to timeout: {raw_error_message:?}")]
     Timeout { raw_error_message: String },
+
+    #[error("Docker image manifest cannot be read: {raw_error_message:?}")]
+    InvalidManifest { raw_error_message: String },
 }
 
 impl DockerError {
@@ -46,8 +52,9 @@ impl DockerError {
 // Mostly use for CI/Test when all test start in parallel and it the login phase at the same time
 static LOGIN_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 static DEFAULT_BUILDER_NAME: &str = "qovery-engine";
+static BUILD_SECRET_ENV_PREFIX: &str = "QOVERY_BUILD_SECRET_";
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum Architecture {
     AMD64,
     ARM64,
@@ -83,6 +90,15 @@ impl From<&CpuArchitecture> for Architecture {
     }
 }
 
+impl From<Architecture> for CpuArchitecture {
+    fn from(value: Architecture) -> Self {
+        match value {
+            Architecture::AMD64 => CpuArchitecture::AMD64,
+            Architecture::ARM64 => CpuArchitecture::ARM64,
+        }
+    }
+}
+
 impl Display for Architecture {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -94,7 +110,6 @@ impl Display for Architecture {
 
 #[derive(Debug, Clone)]
 enum ImageId {
-    #[allow(dead_code)]
     Digest(String),
     Tags(Vec<String>),
 }
@@ -117,7 +132,7 @@ impl ContainerImage {
         }
     }
 
-    fn _new_for_digest(registry: Url, name: String, digest: String) -> Self {
+    pub fn new_for_digest(registry: Url, name: String, digest: String) -> Self {
         ContainerImage {
             registry,
             name,
@@ -151,6 +166,220 @@ impl ContainerImage {
     pub fn repository_with_host(&self) -> String {
         format!("{}/{}", self.host_with_credentials_and_port(), self.name)
     }
+
+    /// Parse an image reference as used by kubernetes, i.e: `registry.io/repository/name:tag`, `name@sha256:...`
+    /// Like docker, the first component is only a registry if it looks like a host, otherwise the image is on the docker hub
+    pub fn from_reference(reference: &str) -> Option<ContainerImage> {
+        let (name_with_tag, digest) = match reference.split_once('@') {
+            Some((name_with_tag, digest)) => (name_with_tag, Some(digest)),
+            None => (reference, None),
+        };
+        if name_with_tag.is_empty() {
+            return None;
+        }
+        let (host, path) = match name_with_tag.split_once('/') {
+            Some((host, path)) if host.contains(['.', ':']) || host == "localhost" => (host, path.to_string()),
+            Some(_) => ("docker.io", name_with_tag.to_string()),
+            None => ("docker.io", format!("library/{name_with_tag}")),
+        };
+        // A port of the registry is never in the path, so a `:` after the last `/` is a tag
+        let (name, tag) = match path.rsplit_once(':') {
+            Some((name, tag)) if !tag.contains('/') => (name.to_string(), tag.to_string()),
+            _ => (path, "latest".to_string()),
+        };
+        if name.is_empty() || tag.is_empty() {
+            return None;
+        }
+        let registry = Url::parse(&format!("https://{host}")).ok()?;
+
+        Some(match digest {
+            Some(digest) if digest.starts_with("sha256:") => {
+                ContainerImage::new_for_digest(registry, name, digest.to_string())
+            }
+            Some(_) => return None,
+            None => ContainerImage::new(registry, name, vec![tag]),
+        })
+    }
+}
+
+#[derive(Derivative, Clone)]
+#[derivative(Debug)]
+pub enum BuildCacheObjectStorage {
+    // Any S3 compatible storage (AWS, Scaleway, GCS through its interoperability endpoint)
+    S3 {
+        bucket: String,
+        region: String,
+        endpoint_url: Option<String>,
+        access_key_id: String,
+        #[derivative(Debug = "ignore")]
+        secret_access_key: String,
+        #[derivative(Debug = "ignore")]
+        session_token: Option<String>,
+    },
+    // Buildkit authenticates with the identity of the builder, buildx has no way to forward an account key
+    // without putting it in the command line
+    AzureBlob {
+        account_url: String,
+        container: String,
+    },
+}
+
+/// Backend buildkit imports the build cache from and exports it to
+#[derive(Debug, Clone)]
+pub enum BuildCache {
+    Registry(ContainerImage),
+    ObjectStorage {
+        storage: BuildCacheObjectStorage,
+        // prefix of the cache blobs/manifests in the bucket
+        name: String,
+    },
+    LocalDirectory(PathBuf),
+}
+
+impl BuildCache {
+    /// Cache of a single architecture build, so parallel builds of an image don't overwrite each other cache
+    pub fn for_architecture(&self, arch: Architecture) -> BuildCache {
+        match self {
+            BuildCache::Registry(image) => BuildCache::Registry(ContainerImage::new(
+                image.registry.clone(),
+                image.name.clone(),
+                match &image.id {
+                    ImageId::Tags(tags) => tags.iter().map(|tag| format!("{tag}-{arch}")).collect(),
+                    ImageId::Digest(_) => vec![format!("cache-{arch}")],
+                },
+            )),
+            BuildCache::ObjectStorage { storage, name } => BuildCache::ObjectStorage {
+                storage: storage.clone(),
+                name: format!("{name}-{arch}"),
+            },
+            BuildCache::LocalDirectory(path) => BuildCache::LocalDirectory(path.join(arch.to_string())),
+        }
+    }
+
+    fn cache_from_arg(&self) -> String {
+        match self {
+            BuildCache::Registry(image) => format!("type=registry,ref={}", image.image_name()),
+            BuildCache::ObjectStorage { storage, name } => Self::object_storage_attributes(storage, name),
+            BuildCache::LocalDirectory(path) => format!("type=local,src={}", path.to_string_lossy()),
+        }
+    }
+
+    fn cache_to_arg(&self) -> String {
+        match self {
+            BuildCache::Registry(image) => format!(
+                "type=registry,mode=max,image-manifest=true,oci-mediatypes=true,ref={}",
+                image.image_name()
+            ),
+            BuildCache::ObjectStorage { storage, name } => {
+                format!("{},mode=max", Self::object_storage_attributes(storage, name))
+            }
+            BuildCache::LocalDirectory(path) => format!("type=local,mode=max,dest={}", path.to_string_lossy()),
+        }
+    }
+
+    // Credentials are never part of the attributes, as the command line is logged and visible to other processes
+    fn object_storage_attributes(storage: &BuildCacheObjectStorage, name: &str) -> String {
+        match storage {
+            BuildCacheObjectStorage::S3 {
+                bucket,
+                region,
+                endpoint_url,
+                ..
+            } => {
+                let mut attributes = vec![
+                    "type=s3".to_string(),
+                    format!("region={region}"),
+                    format!("bucket={bucket}"),
+                    format!("name={name}"),
+                ];
+                if let Some(endpoint_url) = endpoint_url {
+                    attributes.push(format!("endpoint_url={endpoint_url}"));
+                    attributes.push("use_path_style=true".to_string());
+                }
+                attributes.join(",")
+            }
+            BuildCacheObjectStorage::AzureBlob { account_url, container } => [
+                "type=azblob".to_string(),
+                format!("account_url={account_url}"),
+                format!("container={container}"),
+                format!("name={name}"),
+            ]
+            .join(","),
+        }
+    }
+
+    /// Environment of the buildx command, buildx reads the AWS credentials from it and forwards them to the builder
+    /// for the s3 cache, through the build session
+    fn credentials_envs(&self) -> Vec<(&str, &str)> {
+        match self {
+            BuildCache::ObjectStorage {
+                storage:
+                    BuildCacheObjectStorage::S3 {
+                        access_key_id,
+                        secret_access_key,
+                        session_token,
+                        ..
+                    },
+                ..
+            } => {
+                let mut envs = vec![
+                    ("AWS_ACCESS_KEY_ID", access_key_id.as_str()),
+                    ("AWS_SECRET_ACCESS_KEY", secret_access_key.as_str()),
+                ];
+                if let Some(session_token) = session_token {
+                    envs.push(("AWS_SESSION_TOKEN", session_token.as_str()));
+                }
+                envs
+            }
+            BuildCache::ObjectStorage { .. } | BuildCache::Registry(_) | BuildCache::LocalDirectory(_) => vec![],
+        }
+    }
+}
+
+/// SLSA provenance attestation attached by buildkit to the pushed images
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum BuildProvenance {
+    // Let buildx decide, it attaches a minimal provenance when the builder supports attestations
+    #[default]
+    Min,
+    // Records the build args values too, so only for builds not passing secrets as build args
+    Max,
+    Disabled,
+}
+
+/// Where buildkit sends the built image
+#[derive(Debug, Clone, Copy)]
+enum BuildOutput<'a> {
+    // Loaded into the docker daemon
+    Docker,
+    // Pushed under the tags of the image
+    Registry,
+    // Pushed without any tag, its digest is written into the metadata file
+    RegistryByDigest { metadata_file: &'a Path },
+}
+
+impl BuildOutput<'_> {
+    fn is_pushed(&self) -> bool {
+        match self {
+            BuildOutput::Docker => false,
+            BuildOutput::Registry | BuildOutput::RegistryByDigest { .. } => true,
+        }
+    }
+}
+
+// Errors of the builder itself, unrelated to what is built (i.e: builder pod restarted, network issue)
+const TRANSIENT_BUILD_ERRORS: [&str; 6] = [
+    "ERROR: listing workers for Build",
+    "use of closed network connection",
+    "i/o timeout",
+    "connection reset by peer",
+    "error reading from server: EOF",
+    "code = Unavailable",
+];
+
+pub fn is_transient_build_error(line: &str) -> bool {
+    TRANSIENT_BUILD_ERRORS.iter().any(|error| line.contains(error))
 }
 
 #[derive(Debug, Clone)]
@@ -650,7 +879,10 @@ impl Docker {
         context: &Path,
         image_to_build: &ContainerImage,
         build_args: &[(&str, &str)],
-        cache: &ContainerImage,
+        secrets: &[(&str, &str)],
+        labels: &[(&str, &str)],
+        provenance: BuildProvenance,
+        cache: &BuildCache,
         push_after_build: bool,
         architectures: &[Architecture],
         stdout_output: &mut Stdout,
@@ -681,8 +913,15 @@ impl Docker {
             context,
             image_to_build,
             build_args,
+            secrets,
+            labels,
+            provenance,
             cache,
-            push_after_build,
+            if push_after_build {
+                BuildOutput::Registry
+            } else {
+                BuildOutput::Docker
+            },
             architectures,
             stdout_output,
             stderr_output,
@@ -691,6 +930,64 @@ impl Docker {
         )
     }
 
+    /// Build the image for a single architecture and push it without any tag, so it is only reachable by the returned
+    /// digest. i.e: to assemble a multi-architecture image without leaving per architecture tags in the registry
+    pub fn build_and_push_by_digest<Stdout, Stderr>(
+        &self,
+        builder_name: &Option<&str>,
+        dockerfile: &Path,
+        context: &Path,
+        image_to_build: &ContainerImage,
+        build_args: &[(&str, &str)],
+        secrets: &[(&str, &str)],
+        labels: &[(&str, &str)],
+        provenance: BuildProvenance,
+        cache: &BuildCache,
+        architecture: Architecture,
+        stdout_output: &mut Stdout,
+        stderr_output: &mut Stderr,
+        should_abort: &CommandKiller,
+        target_build_stage: Option<&String>,
+    ) -> Result<ContainerImage, DockerError>
+    where
+        Stdout: FnMut(String),
+        Stderr: FnMut(String),
+    {
+        let metadata_dir = TempDir::with_prefix("buildx-metadata-").map_err(|err| DockerError::InvalidConfig {
+            raw_error_message: format!("Cannot create buildx metadata directory: {err}"),
+        })?;
+        let metadata_file = metadata_dir.path().join("metadata.json");
+
+        self.build_with_buildkit(
+            builder_name,
+            dockerfile,
+            context,
+            image_to_build,
+            build_args,
+            secrets,
+            labels,
+            provenance,
+            cache,
+            BuildOutput::RegistryByDigest {
+                metadata_file: &metadata_file,
+            },
+            &[architecture],
+            stdout_output,
+            stderr_output,
+            should_abort,
+            target_build_stage,
+        )?;
+
+        let metadata = fs::read_to_string(&metadata_file).map_err(|err| DockerError::InvalidManifest {
+            raw_error_message: format!("Cannot read buildx metadata file: {err}"),
+        })?;
+        Ok(ContainerImage::new_for_digest(
+            image_to_build.registry.clone(),
+            image_to_build.name.clone(),
+            parse_build_metadata_digest(&metadata)?,
+        ))
+    }
+
     fn build_with_buildkit<Stdout, Stderr>(
         &self,
         builder_name: &Option<&str>,
@@ -698,8 +995,11 @@ impl Docker {
         context: &Path,
         image_to_build: &ContainerImage,
         build_args: &[(&str, &str)],
-        cache: &ContainerImage,
-        push_after_build: bool,
+        secrets: &[(&str, &str)],
+        labels: &[(&str, &str)],
+        provenance: BuildProvenance,
+        cache: &BuildCache,
+        output: BuildOutput,
         architectures: &[Architecture],
         stdout_output: &mut Stdout,
         stderr_output: &mut Stderr,
@@ -711,6 +1011,7 @@ impl Docker {
         Stderr: FnMut(String),
     {
         info!("Docker buildkit build {:?}", image_to_build.image_name());
+        let push_after_build = output.is_pushed();
 
         let mut args_string: Vec<String> = vec![
             "--config".to_string(),
@@ -723,13 +1024,16 @@ impl Docker {
                 format!("--builder={DEFAULT_BUILDER_NAME}",)
             },
             "--progress=plain".to_string(),
-            if push_after_build {
-                "--output=type=registry".to_string() // tell buildkit to push image to registry
-            } else {
-                "--output=type=docker".to_string() // tell buildkit to load the image into docker after build
+            match output {
+                BuildOutput::Docker => "--output=type=docker".to_string(), // tell buildkit to load the image into docker after build
+                BuildOutput::Registry => "--output=type=registry".to_string(), // tell buildkit to push image to registry
+                BuildOutput::RegistryByDigest { .. } => format!(
+                    "--output=type=image,name={},push-by-digest=true,name-canonical=true,push=true",
+                    image_to_build.repository_with_host()
+                ),
             },
             "--cache-from".to_string(),
-            format!("type=registry,ref={}", cache.image_name()),
+            cache.cache_from_arg(),
             "-f".to_string(),
             dockerfile.to_str().unwrap_or_default().to_string(),
         ];
@@ -741,10 +1045,7 @@ impl Docker {
 
         if push_after_build {
             args_string.push("--cache-to".to_string());
-            args_string.push(format!(
-                "type=registry,mode=max,image-manifest=true,oci-mediatypes=true,ref={}",
-                cache.image_name()
-            ));
+            args_string.push(cache.cache_to_arg());
         }
 
         // Build for all requested architectures, if empty build for the current architecture the engine is running on
@@ -755,15 +1056,43 @@ impl Docker {
             ));
         };
 
-        for image_name in image_to_build.image_names() {
-            args_string.push("--tag".to_string());
-            args_string.push(image_name.to_string())
+        match output {
+            BuildOutput::Docker | BuildOutput::Registry => {
+                for image_name in image_to_build.image_names() {
+                    args_string.push("--tag".to_string());
+                    args_string.push(image_name.to_string())
+                }
+            }
+            BuildOutput::RegistryByDigest { metadata_file } => {
+                args_string.push("--metadata-file".to_string());
+                args_string.push(metadata_file.to_string_lossy().to_string());
+            }
         }
 
         for (k, v) in build_args {
             args_string.push("--build-arg".to_string());
             args_string.push(format!("{k}={v}"));
         }
+
+        // Provenance attestations can only be attached to images pushed by buildkit
+        args_string.extend(image_metadata_args(labels, provenance, push_after_build));
+
+        // Secret values are read by buildx from its own environment and forwarded to the builder through the build session.
+        // So they never appear in the command line nor in the image, whether the builder runs locally or in kubernetes
+        let secret_env_names: Vec<String> = secrets
+            .iter()
+            .map(|(id, _)| format!("{BUILD_SECRET_ENV_PREFIX}{id}"))
+            .collect();
+        let mut secret_envs: Vec<(&str, &str)> = secrets
+            .iter()
+            .zip(&secret_env_names)
+            .map(|((_, value), env_name)| (env_name.as_str(), *value))
+            .collect();
+        secret_envs.extend(cache.credentials_envs());
+        for ((id, _), env_name) in secrets.iter().zip(&secret_env_names) {
+            args_string.push("--secret".to_string());
+            args_string.push(format!("id={id},env={env_name}"));
+        }
         args_string.push(context.to_str().unwrap_or_default().to_string());
 
         // Hack
@@ -775,10 +1104,7 @@ impl Docker {
             let mut transient_error = false;
             let ret = {
                 let mut stderr_output = |line: String| {
-                    if line.contains("ERROR: listing workers for Build")
-                        || line.contains("use of closed network connection")
-                        || line.contains("i/o timeout")
-                    {
+                    if is_transient_build_error(&line) {
                         transient_error = true;
                     }
 
@@ -786,7 +1112,7 @@ impl Docker {
                 };
                 docker_exec(
                     &args_string.iter().map(|x| x.as_str()).collect::<Vec<&str>>(),
-                    &self.get_all_envs(&[]),
+                    &self.get_all_envs(&secret_envs),
                     stdout_output,
                     &mut stderr_output,
                     should_abort,
@@ -858,6 +1184,62 @@ impl Docker {
         docker_exec(&args, &self.get_all_envs(&[]), stdout_output, stderr_output, should_abort)
     }
 
+    /// Load an image archive (docker-archive or oci layout tarball) and push it under every tag of `dest_image`
+    pub fn load_archive<Stdout, Stderr>(
+        &self,
+        archive: &Path,
+        dest_image: &ContainerImage,
+        stdout_output: &mut Stdout,
+        stderr_output: &mut Stderr,
+        should_abort: &CommandKiller,
+    ) -> Result<(), DockerError>
+    where
+        Stdout: FnMut(String),
+        Stderr: FnMut(String),
+    {
+        info!("Docker load {:?} into {:?}", archive, dest_image);
+        let archive_path = archive.to_str().unwrap_or_default();
+        let args = vec![
+            "--config",
+            self.config_path.path().to_str().unwrap_or(""),
+            "load",
+            "--input",
+            archive_path,
+        ];
+        let mut loaded_images = Vec::new();
+        docker_exec(
+            &args,
+            &self.get_all_envs(&[]),
+            &mut |line| {
+                if let Some(image) = parse_loaded_image(&line) {
+                    loaded_images.push(image);
+                }
+                stdout_output(line)
+            },
+            stderr_output,
+            should_abort,
+        )?;
+
+        let Some(loaded_image) = loaded_images.first() else {
+            return Err(DockerError::InvalidManifest {
+                raw_error_message: format!("Archive {archive_path} does not contain any image"),
+            });
+        };
+
+        for image_name in dest_image.image_names() {
+            let args = vec![
+                "--config",
+                self.config_path.path().to_str().unwrap_or(""),
+                "tag",
+                loaded_image.as_str(),
+                image_name.as_str(),
+            ];
+            docker_exec(&args, &self.get_all_envs(&[]), stdout_output, stderr_output, should_abort)?;
+        }
+
+        self.push(dest_image, stdout_output, stderr_output, should_abort)
+    }
+
     pub fn mirror<Stdout, Stderr>(
         &self,
         source_image: &ContainerImage,
@@ -880,6 +1262,83 @@ impl Docker {
         )
     }
 
+    /// Assemble the single architecture images into a manifest list, pushed under every tag of `image`
+    pub fn create_multi_arch_manifest<Stdout, Stderr>(
+        &self,
+        image: &ContainerImage,
+        arch_images: &[ContainerImage],
+        stdout_output: &mut Stdout,
+        stderr_output: &mut Stderr,
+        should_abort: &CommandKiller,
+    ) -> Result<(), DockerError>
+    where
+        Stdout: FnMut(String),
+        Stderr: FnMut(String),
+    {
+        info!("Docker create multi arch manifest {:?} from {:?}", image, arch_images);
+        let sources: Vec<String> = arch_images.iter().map(|arch_image| arch_image.image_name()).collect();
+        let sources: Vec<&str> = sources.iter().map(|source| source.as_str()).collect();
+        for image_name in image.image_names() {
+            // create_manifest only pushes the first tag of the image
+            let tag = image_name.rsplit_once(':').map(|(_, tag)| tag).unwrap_or_default();
+            let image = ContainerImage::new(image.registry.clone(), image.name.clone(), vec![tag.to_string()]);
+            self.create_manifest(&image, &sources, stdout_output, stderr_output, should_abort)?;
+        }
+
+        Ok(())
+    }
+
+    /// Architectures an image is available for in the registry
+    pub fn image_architectures(&self, image: &ContainerImage) -> Result<Vec<Architecture>, DockerError> {
+        info!("Docker inspect image architectures {:?}", image);
+
+        let raw_manifest = self.imagetools_inspect(image, &["--raw"])?;
+        if let Some(architectures) = parse_manifest_list_architectures(&raw_manifest)? {
+            return Ok(architectures);
+        }
+
+        // Not a manifest list, the architecture is only written in the image config
+        let image_config = self.imagetools_inspect(image, &["--format", "{{json .Image}}"])?;
+        Ok(parse_image_config_architecture(&image_config)?.into_iter().collect())
+    }
+
+    /// Labels of an image in the registry, of its first platform for a multi architecture image
+    pub fn image_labels(&self, image: &ContainerImage) -> Result<BTreeMap<String, String>, DockerError> {
+        info!("Docker inspect image labels {:?}", image);
+
+        let image_config = self.imagetools_inspect(image, &["--format", "{{json .Image}}"])?;
+        parse_image_config_labels(&image_config)
+    }
+
+    fn imagetools_inspect(&self, image: &ContainerImage, extra_args: &[&str]) -> Result<String, DockerError> {
+        let builder = self.configure_builder_for_http_registries(image);
+        let image_name = image.image_name();
+        let mut args = vec![
+            "--config",
+            self.config_path.path().to_str().unwrap_or(""),
+            "buildx",
+            "imagetools",
+            "inspect",
+            &image_name,
+        ];
+        args.extend_from_slice(extra_args);
+        if let Some(builder_name) = &builder.as_ref().and_then(|b| b.builder_name.as_deref()) {
+            args.push("--builder");
+            args.push(builder_name)
+        }
+
+        let mut output = String::new();
+        docker_exec(
+            &args,
+            &self.get_all_envs(&[]),
+            &mut |line| output.push_str(&line),
+            &mut |line| warn!("{}", line),
+            &CommandKiller::never(),
+        )?;
+
+        Ok(output)
+    }
+
     fn create_manifest<Stdout, Stderr>(
         &self,
         image: &ContainerImage,
@@ -951,6 +1410,110 @@ impl Docker {
     }
 }
 
+// Architectures of a manifest list (oci index or docker manifest list), None if it is a single image manifest.
+// Entries without a known platform (i.e: attestations) are ignored
+fn parse_manifest_list_architectures(raw_manifest: &str) -> Result<Option<Vec<Architecture>>, DockerError> {
+    let manifest: serde_json::Value =
+        serde_json::from_str(raw_manifest).map_err(|err| DockerError::InvalidManifest {
+            raw_error_message: err.to_string(),
+        })?;
+    let Some(manifests) = manifest.get("manifests").and_then(|manifests| manifests.as_array()) else {
+        return Ok(None);
+    };
+
+    let architectures = manifests
+        .iter()
+        .filter_map(|manifest| manifest.pointer("/platform/architecture")?.as_str())
+        .filter_map(|arch| Architecture::from_str(arch).ok())
+        .sorted()
+        .dedup()
+        .collect();
+
+    Ok(Some(architectures))
+}
+
+fn parse_image_config_architecture(image_config: &str) -> Result<Option<Architecture>, DockerError> {
+    let config: serde_json::Value = serde_json::from_str(image_config).map_err(|err| DockerError::InvalidManifest {
+        raw_error_message: err.to_string(),
+    })?;
+
+    Ok(config
+        .get("architecture")
+        .and_then(|arch| arch.as_str())
+        .and_then(|arch| Architecture::from_str(arch).ok()))
+}
+
+// For a multi architecture image, the config is given per platform
+fn parse_image_config_labels(image_config: &str) -> Result<BTreeMap<String, String>, DockerError> {
+    let config: serde_json::Value = serde_json::from_str(image_config).map_err(|err| DockerError::InvalidManifest {
+        raw_error_message: err.to_string(),
+    })?;
+    let config = match config.get("config") {
+        Some(_) => &config,
+        None => config
+            .as_object()
+            .and_then(|platforms| platforms.values().next())
+            .unwrap_or(&config),
+    };
+
+    Ok(config
+        .pointer("/config/Labels")
+        .and_then(|labels| labels.as_object())
+        .map(|labels| {
+            labels
+                .iter()
+                .filter_map(|(k, v)| Some((k.clone(), v.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+// Labels are set in the image config, and as OCI annotations on the manifest (and index) when the image is pushed,
+// with the SLSA provenance of the build if asked for
+fn image_metadata_args(labels: &[(&str, &str)], provenance: BuildProvenance, push_after_build: bool) -> Vec<String> {
+    let mut args = Vec::with_capacity(labels.len() * 6 + 1);
+    for (k, v) in labels {
+        args.push("--label".to_string());
+        args.push(format!("{k}={v}"));
+    }
+    if !push_after_build {
+        return args;
+    }
+
+    for (k, v) in labels {
+        args.push("--annotation".to_string());
+        args.push(format!("manifest:{k}={v}"));
+        args.push("--annotation".to_string());
+        args.push(format!("index:{k}={v}"));
+    }
+    match provenance {
+        BuildProvenance::Min => {}
+        BuildProvenance::Max => args.push("--provenance=mode=max".to_string()),
+        BuildProvenance::Disabled => args.push("--provenance=false".to_string()),
+    }
+
+    args
+}
+
+// Digest of the image pushed by buildx, from the file written with `--metadata-file`
+fn parse_build_metadata_digest(metadata: &str) -> Result<String, DockerError> {
+    serde_json::from_str::<serde_json::Value>(metadata)
+        .ok()
+        .and_then(|metadata| metadata.get("containerimage.digest")?.as_str().map(str::to_string))
+        .ok_or_else(|| DockerError::InvalidManifest {
+            raw_error_message: format!("No image digest in buildx metadata: {metadata}"),
+        })
+}
+
+// Image reference printed by `docker load`, either `Loaded image: name:tag` or `Loaded image ID: sha256:...`
+fn parse_loaded_image(line: &str) -> Option<String> {
+    line.trim()
+        .strip_prefix("Loaded image ID:")
+        .or_else(|| line.trim().strip_prefix("Loaded image:"))
+        .map(|image| image.trim().to_string())
+        .filter(|image| !image.is_empty())
+}
+
 fn docker_exec<F, X>(
     args: &[&str],
     envs: &[(&str, &str)],
@@ -981,9 +1544,14 @@ where
 #[cfg(test)]
 mod tests {
     use crate::cmd::command::CommandKiller;
-    use crate::cmd::docker::{Architecture, ContainerImage, Docker, DockerError};
+    use crate::cmd::docker::{
+        Architecture, BuildCache, BuildCacheObjectStorage, BuildOutput, BuildProvenance, ContainerImage, Docker,
+        DockerError, image_metadata_args, is_transient_build_error, parse_build_metadata_digest,
+        parse_image_config_architecture, parse_image_config_labels, parse_loaded_image,
+        parse_manifest_list_architectures,
+    };
     use std::num::NonZeroUsize;
-    use std::path::Path;
+    use std::path::{Path, PathBuf};
     use std::time::Duration;
     use url::Url;
     use uuid::Uuid;
@@ -1050,11 +1618,11 @@ mod tests {
             "local-repo/alpine".to_string(),
             vec!["3.15".to_string()],
         );
-        let image_cache = ContainerImage::new(
+        let image_cache = BuildCache::Registry(ContainerImage::new(
             private_registry_url(),
             "local-repo/alpine".to_string(),
             vec!["cache".to_string()],
-        );
+        ));
 
         // It should work
         let ret = docker.build_with_buildkit(
@@ -1063,8 +1631,11 @@ mod tests {
             Path::new("tests/docker/multi_stage_simple/"),
             &image_to_build,
             &[],
+            &[],
+            &[],
+            BuildProvenance::default(),
             &image_cache,
-            false,
+            BuildOutput::Docker,
             CPU_ARCHITECTURE,
             &mut |msg| println!("{msg}"),
             &mut |msg| eprintln!("{msg}"),
@@ -1080,8 +1651,11 @@ mod tests {
             Path::new("tests/docker/multi_stage_simple/"),
             &image_to_build,
             &[],
+            &[],
+            &[],
+            BuildProvenance::default(),
             &image_cache,
-            false,
+            BuildOutput::Docker,
             CPU_ARCHITECTURE,
             &mut |msg| println!("{msg}"),
             &mut |msg| eprintln!("{msg}"),
@@ -1102,11 +1676,11 @@ mod tests {
             "local-repo/alpine".to_string(),
             vec!["v42.42".to_string()],
         );
-        let image_cache = ContainerImage::new(
+        let image_cache = BuildCache::Registry(ContainerImage::new(
             private_registry_url(),
             "local-repo/alpine".to_string(),
             vec!["cache".to_string()],
-        );
+        ));
 
         // It should work
         let ret = docker.build_with_buildkit(
@@ -1115,8 +1689,11 @@ mod tests {
             Path::new("tests/docker/multi_stage_simple/"),
             &image_to_build,
             &[],
+            &[],
+            &[],
+            BuildProvenance::default(),
             &image_cache,
-            false,
+            BuildOutput::Docker,
             CPU_ARCHITECTURE,
             &mut |msg| println!("{msg}"),
             &mut |msg| eprintln!("{msg}"),
@@ -1238,11 +1815,11 @@ mod tests {
             "local-repo/alpine".to_string(),
             vec!["3.15".to_string()],
         );
-        let image_cache = ContainerImage::new(
+        let image_cache = BuildCache::Registry(ContainerImage::new(
             private_registry_url(),
             "local-repo/alpine".to_string(),
             vec!["cache".to_string()],
-        );
+        ));
 
         // It should work
         let ret = docker.build_with_buildkit(
@@ -1251,8 +1828,11 @@ mod tests {
             Path::new("tests/docker/multi_stage_simple/"),
             &image_to_build,
             &[],
+            &[],
+            &[],
+            BuildProvenance::default(),
             &image_cache,
-            false,
+            BuildOutput::Docker,
             &[Architecture::AMD64],
             &mut |msg| println!("{msg}"),
             &mut |msg| eprintln!("{msg}"),
@@ -1273,11 +1853,11 @@ mod tests {
             "local-repo/alpine".to_string(),
             vec!["3.15".to_string()],
         );
-        let image_cache = ContainerImage::new(
+        let image_cache = BuildCache::Registry(ContainerImage::new(
             private_registry_url(),
             "local-repo/alpine".to_string(),
             vec!["cache".to_string()],
-        );
+        ));
 
         // It should work
         let ret = docker.build_with_buildkit(
@@ -1286,8 +1866,11 @@ mod tests {
             Path::new("tests/docker/multi_stage_simple/"),
             &image_to_build,
             &[],
+            &[],
+            &[],
+            BuildProvenance::default(),
             &image_cache,
-            false,
+            BuildOutput::Docker,
             CPU_ARCHITECTURE,
             &mut |msg| println!("{msg}"),
             &mut |msg| eprintln!("{msg}"),
@@ -1303,8 +1886,11 @@ mod tests {
             Path::new("tests/docker/multi_stage_simple/"),
             &image_to_build,
             &[],
+            &[],
+            &[],
+            BuildProvenance::default(),
             &image_cache,
-            false,
+            BuildOutput::Docker,
             CPU_ARCHITECTURE,
             &mut |msg| println!("{msg}"),
             &mut |msg| eprintln!("{msg}"),
@@ -1314,4 +1900,198 @@ mod tests {
 
         assert!(ret.is_ok());
     }
+
+    #[test]
+    fn test_build_cache_args() {
+        let registry = BuildCache::Registry(ContainerImage::new(
+            private_registry_url(),
+            "my-app-cache".to_string(),
+            vec!["cache".to_string()],
+        ));
+        assert_eq!(registry.cache_from_arg(), "type=registry,ref=localhost:5000/my-app-cache:cache");
+        assert_eq!(
+            registry.cache_to_arg(),
+            "type=registry,mode=max,image-manifest=true,oci-mediatypes=true,ref=localhost:5000/my-app-cache:cache"
+        );
+
+        let s3 = BuildCache::ObjectStorage {
+            storage: BuildCacheObjectStorage::S3 {
+                bucket: "build-cache".to_string(),
+                region: "fr-par".to_string(),
+                endpoint_url: Some("https://s3.fr-par.scw.cloud".to_string()),
+                access_key_id: "access".to_string(),
+                secret_access_key: "secret".to_string(),
+                session_token: None,
+            },
+            name: "my-app".to_string(),
+        };
+        assert_eq!(
+            s3.cache_to_arg(),
+            "type=s3,region=fr-par,bucket=build-cache,name=my-app,endpoint_url=https://s3.fr-par.scw.cloud,use_path_style=true,mode=max"
+        );
+        assert_eq!(
+            s3.credentials_envs(),
+            vec![("AWS_ACCESS_KEY_ID", "access"), ("AWS_SECRET_ACCESS_KEY", "secret")]
+        );
+
+        let azure = BuildCache::ObjectStorage {
+            storage: BuildCacheObjectStorage::AzureBlob {
+                account_url: "https://account.blob.core.windows.net".to_string(),
+                container: "build-cache".to_string(),
+            },
+            name: "my-app".to_string(),
+        };
+        assert_eq!(
+            azure.cache_from_arg(),
+            "type=azblob,account_url=https://account.blob.core.windows.net,container=build-cache,name=my-app"
+        );
+
+        assert!(azure.credentials_envs().is_empty());
+
+        let local = BuildCache::LocalDirectory(PathBuf::from("/var/cache/buildkit/my-app"));
+        assert_eq!(local.cache_from_arg(), "type=local,src=/var/cache/buildkit/my-app");
+        assert_eq!(local.cache_to_arg(), "type=local,mode=max,dest=/var/cache/buildkit/my-app");
+    }
+
+    #[test]
+    fn test_parse_build_metadata_digest() {
+        let metadata = r#"{
+            "buildx.build.ref": "qovery-engine/qovery-engine0/abc",
+            "containerimage.descriptor": { "mediaType": "application/vnd.oci.image.index.v1+json" },
+            "containerimage.digest": "sha256:8b5c7b47d8c0d1f3ef1b5e83d33c1a0e2a9f0d8f2c7b1a0e9d8c7b6a5f4e3d2c1",
+            "image.name": "localhost:5000/my-app"
+        }"#;
+
+        assert_eq!(
+            parse_build_metadata_digest(metadata).unwrap(),
+            "sha256:8b5c7b47d8c0d1f3ef1b5e83d33c1a0e2a9f0d8f2c7b1a0e9d8c7b6a5f4e3d2c1"
+        );
+        assert!(parse_build_metadata_digest(r#"{ "buildx.build.ref": "abc" }"#).is_err());
+        assert!(parse_build_metadata_digest("").is_err());
+    }
+
+    #[test]
+    fn test_is_transient_build_error() {
+        assert!(is_transient_build_error(
+            "ERROR: failed to receive status: rpc error: code = Unavailable desc = error reading from server: EOF"
+        ));
+        assert!(is_transient_build_error("read tcp 10.0.0.1:1234: i/o timeout"));
+        assert!(!is_transient_build_error(
+            "ERROR: failed to solve: process \"/bin/sh -c npm run build\" did not complete successfully: exit code: 1"
+        ));
+    }
+
+    #[test]
+    fn test_parse_manifest_architectures() {
+        let index = r#"{
+            "mediaType": "application/vnd.oci.image.index.v1+json",
+            "manifests": [
+                {"digest": "sha256:1", "platform": {"architecture": "arm64", "os": "linux"}},
+                {"digest": "sha256:2", "platform": {"architecture": "amd64", "os": "linux"}},
+                {"digest": "sha256:3", "platform": {"architecture": "unknown", "os": "unknown"}}
+            ]
+        }"#;
+        assert_eq!(
+            parse_manifest_list_architectures(index).unwrap(),
+            Some(vec![Architecture::AMD64, Architecture::ARM64])
+        );
+
+        let single = r#"{"mediaType": "application/vnd.oci.image.manifest.v1+json", "layers": []}"#;
+        assert_eq!(parse_manifest_list_architectures(single).unwrap(), None);
+        assert_eq!(
+            parse_image_config_architecture(r#"{"architecture": "arm64", "os": "linux"}"#).unwrap(),
+            Some(Architecture::ARM64)
+        );
+        assert!(parse_manifest_list_architectures("not json").is_err());
+    }
+
+    #[test]
+    fn test_image_metadata_args() {
+        let labels = [("org.opencontainers.image.revision", "abc123")];
+        assert_eq!(
+            image_metadata_args(&labels, BuildProvenance::Max, false),
+            vec!["--label", "org.opencontainers.image.revision=abc123"]
+        );
+        assert_eq!(
+            image_metadata_args(&labels, BuildProvenance::Min, true),
+            vec![
+                "--label",
+                "org.opencontainers.image.revision=abc123",
+                "--annotation",
+                "manifest:org.opencontainers.image.revision=abc123",
+                "--annotation",
+                "index:org.opencontainers.image.revision=abc123",
+            ]
+        );
+        assert_eq!(
+            image_metadata_args(&labels, BuildProvenance::Max, true),
+            vec![
+                "--label",
+                "org.opencontainers.image.revision=abc123",
+                "--annotation",
+                "manifest:org.opencontainers.image.revision=abc123",
+                "--annotation",
+                "index:org.opencontainers.image.revision=abc123",
+                "--provenance=mode=max",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_image_config_labels() {
+        let single =
+            r#"{"architecture": "amd64", "config": {"Labels": {"org.opencontainers.image.revision": "abc123"}}}"#;
+        assert_eq!(
+            parse_image_config_labels(single)
+                .unwrap()
+                .get("org.opencontainers.image.revision"),
+            Some(&"abc123".to_string())
+        );
+
+        let multi = r#"{"linux/amd64": {"config": {"Labels": {"org.opencontainers.image.revision": "abc123"}}}}"#;
+        assert_eq!(parse_image_config_labels(multi).unwrap().len(), 1);
+        assert!(parse_image_config_labels(r#"{"config": {}}"#).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_container_image_from_reference() {
+        let image = ContainerImage::from_reference("registry.io:5000/qovery/app:v1").unwrap();
+        assert_eq!(image.image_name(), "registry.io:5000/qovery/app:v1");
+
+        let image = ContainerImage::from_reference("localhost:5000/app").unwrap();
+        assert_eq!(image.image_name(), "localhost:5000/app:latest");
+
+        // digest references
+        let digest = "sha256:d35dfc2fe3ef66bcc085ca00d3152b482e6cafb23cdda1864154caf3b19094ba";
+        let image = ContainerImage::from_reference(&format!("registry.io/qovery/app@{digest}")).unwrap();
+        assert_eq!(image.name, "qovery/app");
+        assert_eq!(image.image_name(), format!("registry.io/qovery/app@{digest}"));
+        let image = ContainerImage::from_reference(&format!("registry.io/qovery/app:v1@{digest}")).unwrap();
+        assert_eq!(image.image_name(), format!("registry.io/qovery/app@{digest}"));
+
+        // references without host are on the docker hub
+        let image = ContainerImage::from_reference("nginx").unwrap();
+        assert_eq!(image.image_name(), "docker.io/library/nginx:latest");
+        let image = ContainerImage::from_reference("bitnami/postgresql:16").unwrap();
+        assert_eq!(image.image_name(), "docker.io/bitnami/postgresql:16");
+        let image = ContainerImage::from_reference(&format!("nginx@{digest}")).unwrap();
+        assert_eq!(image.image_name(), format!("docker.io/library/nginx@{digest}"));
+
+        assert!(ContainerImage::from_reference("").is_none());
+        assert!(ContainerImage::from_reference("registry.io/app@latest").is_none());
+    }
+
+    #[test]
+    fn test_parse_loaded_image() {
+        assert_eq!(
+            parse_loaded_image("Loaded image: registry.io/app:1.0"),
+            Some("registry.io/app:1.0".to_string())
+        );
+        assert_eq!(
+            parse_loaded_image("Loaded image ID: sha256:d35dfc2fe3ef"),
+            Some("sha256:d35dfc2fe3ef".to_string())
+        );
+        assert_eq!(parse_loaded_image("Loaded image: "), None);
+        assert_eq!(parse_loaded_image("a3ed95caeb02: Loading layer"), None);
+    }
 }
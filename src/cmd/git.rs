@@ -1,13 +1,15 @@
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 use std::time::Duration;
 
+use crate::cmd::git_mirror::{GitMirrorCache, GitMirrorLease, git_mirror_cache};
 use crate::infrastructure::models::build_platform::{BuildError, GitCmd};
+use chrono::{DateTime, Utc};
 use git2::ErrorCode::Auth;
 use git2::ResetType::Hard;
 use git2::build::CheckoutBuilder;
 use git2::{
-    AutotagOption, CertificateCheckStatus, Cred, CredentialType, Error, FetchOptions, Object, RemoteCallbacks,
-    Repository, SubmoduleUpdateOptions, opts,
+    AutotagOption, CertificateCheckStatus, Cred, CredentialType, Error, ErrorCode, FetchOptions, Object, ObjectType,
+    Oid, RemoteCallbacks, Repository, Submodule, SubmoduleUpdateOptions, opts,
 };
 use tracing::field::debug;
 use url::Url;
@@ -30,16 +32,28 @@ pub fn git_initialize_opts(
     }
 }
 
+/// Clone the repository at the given commit, with its submodules.
+/// When the git mirror cache is enabled, the returned leases must be kept as long as the clone is used.
+#[must_use = "mirrors the clone reads from can be evicted as soon as the leases are dropped"]
 pub fn clone_at_commit<P>(
     repository_url: &Url,
     commit_id: &str,
     into_dir: P,
     get_credentials: &impl Fn(&str) -> Vec<(CredentialType, Cred)>,
-) -> Result<(), BuildError>
+) -> Result<Vec<GitMirrorLease>, BuildError>
 where
     P: AsRef<Path>,
 {
-    let repo = fetch(repository_url, into_dir, get_credentials, commit_id).map_err(|error| BuildError::GitError {
+    let mut mirror_leases = vec![];
+    let repo = match git_mirror_cache() {
+        Some(mirror_cache) => fetch_from_mirror(mirror_cache, repository_url, into_dir, get_credentials, commit_id)
+            .map(|(repo, mirror_lease)| {
+                mirror_leases.push(mirror_lease);
+                repo
+            }),
+        None => fetch(repository_url, into_dir, get_credentials, commit_id),
+    }
+    .map_err(|error| BuildError::GitError {
         application: "".to_string(),
         git_cmd: GitCmd::Fetch,
         context: format!("url: {repository_url}/ commit id: {commit_id}"),
@@ -74,25 +88,148 @@ where
 
             for mut submodule in submodules {
                 info!("getting submodule {:?} from {:?}", submodule.name(), submodule.url());
-                submodule
-                    .update(true, Some(&mut opts))
-                    .map_err(|error| BuildError::GitError {
-                        application: "".to_string(),
-                        git_cmd: GitCmd::SubmoduleUpdate,
-                        context: submodule.name().unwrap_or("").to_string(),
-                        raw_error: error,
-                    })?
+                let update = match (git_mirror_cache(), submodule_url(repository_url, &submodule)) {
+                    (Some(mirror_cache), Some(submodule_url)) => {
+                        update_submodule_from_mirror(mirror_cache, &mut submodule, &submodule_url, get_credentials)
+                            .map(|mirror_lease| mirror_leases.push(mirror_lease))
+                    }
+                    _ => submodule.update(true, Some(&mut opts)),
+                };
+                update.map_err(|error| BuildError::GitError {
+                    application: "".to_string(),
+                    git_cmd: GitCmd::SubmoduleUpdate,
+                    context: submodule.name().unwrap_or("").to_string(),
+                    raw_error: error,
+                })?
             }
         }
     }
 
-    Ok(())
+    Ok(mirror_leases)
+}
+
+// Url of the submodule repository, if it can be mirrored.
+// Relative urls (i.e: ../lib.git) are resolved from the url of the parent repository, like git does.
+fn submodule_url(repository_url: &Url, submodule: &Submodule) -> Option<Url> {
+    let url = submodule.url()?;
+    let url = if url.starts_with("./") || url.starts_with("../") {
+        Url::parse(&format!("{}/", repository_url.as_str().trim_end_matches('/')))
+            .ok()?
+            .join(url)
+            .ok()?
+    } else {
+        Url::parse(url).ok()?
+    };
+    check_repository_url(&url).ok()?;
+
+    Some(url)
+}
+
+// Same as `Submodule::update`, but the commit of the submodule is fetched through the mirror of its repository
+fn update_submodule_from_mirror(
+    mirror_cache: &GitMirrorCache,
+    submodule: &mut Submodule,
+    submodule_url: &Url,
+    get_credentials: &impl Fn(&str) -> Vec<(CredentialType, Cred)>,
+) -> Result<GitMirrorLease, Error> {
+    let commit_id = submodule
+        .index_id()
+        .or_else(|| submodule.head_id())
+        .ok_or_else(|| Error::from_str("Submodule is not pointing to any commit"))?
+        .to_string();
+    submodule.init(false)?;
+
+    let (repo, mirror_lease) =
+        mirror_cache.with_commit(submodule_url, &commit_id, get_credentials, |mirror_path, mirror_ref| {
+            let repo = submodule.repo_init(true)?;
+            let _ = repo.remote_delete("origin");
+            fetch_worktree_from_mirror(&repo, submodule_url, mirror_path, mirror_ref)?;
+            Ok(repo)
+        })?;
+    checkout(&repo, &commit_id)?;
+
+    Ok(mirror_lease)
+}
+
+/// Hash of the content of the given paths (relative to the repository root) at a commit.
+/// It only relies on git object ids (sha1 of the content), so it is stable across engine versions and
+/// does not change between commits that don't touch those paths.
+/// Paths are normalized (`./api/` is `api`), and a path missing at this commit is an error.
+pub fn paths_content_hash<P>(repository_path: P, commit_id: &str, paths: &[PathBuf]) -> Result<String, Error>
+where
+    P: AsRef<Path>,
+{
+    let repo = Repository::open(repository_path)?;
+    let tree = repo.revparse_single(commit_id)?.peel_to_tree()?;
+
+    let mut paths = paths
+        .iter()
+        .map(|path| normalize_repository_path(path))
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.sort();
+    paths.dedup();
+
+    let mut content = String::new();
+    for path in paths {
+        let object_id = if path.as_os_str().is_empty() {
+            tree.id().to_string()
+        } else {
+            match tree.get_path(&path) {
+                Ok(entry) => entry.id().to_string(),
+                Err(err) if err.code() == ErrorCode::NotFound => {
+                    let mut error = Error::from_str(&format!(
+                        "Path `{}` does not exist in the repository at commit {commit_id}",
+                        path.to_string_lossy()
+                    ));
+                    error.set_code(ErrorCode::NotFound);
+                    return Err(error);
+                }
+                Err(err) => return Err(err),
+            }
+        };
+        content.push_str(&format!("{} {}\n", path.to_string_lossy(), object_id));
+    }
+
+    Ok(Oid::hash_object(ObjectType::Blob, content.as_bytes())?.to_string())
+}
+
+/// Time the commit was made at, so what is built from it does not depend on when it is built
+pub fn commit_timestamp<P>(repository_path: P, commit_id: &str) -> Result<DateTime<Utc>, Error>
+where
+    P: AsRef<Path>,
+{
+    let repo = Repository::open(repository_path)?;
+    let commit = repo.revparse_single(commit_id)?.peel_to_commit()?;
+    let seconds = commit.committer().when().seconds();
+
+    DateTime::from_timestamp(seconds, 0)
+        .ok_or_else(|| Error::from_str(&format!("Invalid time {seconds} of commit {commit_id}")))
+}
+
+/// Path relative to the repository root, without `.` components nor leading/trailing separators.
+/// The repository root itself is an empty path
+fn normalize_repository_path(path: &Path) -> Result<PathBuf, Error> {
+    let mut normalized_path = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => normalized_path.push(name),
+            Component::CurDir | Component::RootDir | Component::Prefix(_) => {}
+            Component::ParentDir => {
+                return Err(Error::from_str(&format!(
+                    "Path `{}` must not go outside of the repository",
+                    path.to_string_lossy()
+                )));
+            }
+        }
+    }
+
+    Ok(normalized_path)
 }
 
 // Credentials callback is called endlessly until the server return Auth Ok (or a definitive error)
 // If auth is denied, it up to us to return a new credential to try different auth method
 // or an error to specify that we have exhausted everything we are able to provide
-fn authentication_callback(
+pub(crate) fn authentication_callback(
     get_credentials: &impl Fn(&str) -> Vec<(CredentialType, Cred)>,
 ) -> impl FnMut(&str, Option<&str>, CredentialType) -> Result<Cred, Error> + '_ {
     let mut current_credentials: (String, Vec<(CredentialType, Cred)>) = ("".into(), vec![]);
@@ -146,15 +283,7 @@ fn checkout<'a>(repo: &'a Repository, commit_id: &'a str) -> Result<Object<'a>,
     Ok(obj)
 }
 
-fn fetch<P>(
-    repository_url: &Url,
-    into_dir: P,
-    get_credentials: &impl Fn(&str) -> Vec<(CredentialType, Cred)>,
-    commit_id: &str,
-) -> Result<Repository, Error>
-where
-    P: AsRef<Path>,
-{
+fn check_repository_url(repository_url: &Url) -> Result<(), Error> {
     #[cfg(not(feature = "test-git-container"))]
     {
         if repository_url.scheme() != "https" {
@@ -170,6 +299,65 @@ where
         }
     }
 
+    Ok(())
+}
+
+// Fetch the commit into the shared mirror of the repository, then fetch the worktree locally from this mirror
+fn fetch_from_mirror<P>(
+    mirror_cache: &GitMirrorCache,
+    repository_url: &Url,
+    into_dir: P,
+    get_credentials: &impl Fn(&str) -> Vec<(CredentialType, Cred)>,
+    commit_id: &str,
+) -> Result<(Repository, GitMirrorLease), Error>
+where
+    P: AsRef<Path>,
+{
+    check_repository_url(repository_url)?;
+
+    if into_dir.as_ref().exists() {
+        let _ = std::fs::remove_dir_all(into_dir.as_ref());
+    }
+
+    mirror_cache.with_commit(repository_url, commit_id, get_credentials, |mirror_path, mirror_ref| {
+        let repo = Repository::init(into_dir.as_ref())?;
+        fetch_worktree_from_mirror(&repo, repository_url, mirror_path, mirror_ref)?;
+        Ok(repo)
+    })
+}
+
+fn fetch_worktree_from_mirror(
+    repo: &Repository,
+    repository_url: &Url,
+    mirror_path: &Path,
+    mirror_ref: &str,
+) -> Result<(), Error> {
+    // origin keeps pointing to the real repository, relative submodule urls and git-lfs are resolved from it
+    repo.remote("origin", repository_url.as_str())?;
+    // git-lfs objects are stored along the mirror, to not download them again on the next checkout
+    repo.config()?
+        .set_str("lfs.storage", &mirror_path.join("lfs").to_string_lossy())?;
+
+    let mut fo = FetchOptions::new();
+    fo.update_fetchhead(false);
+    fo.download_tags(AutotagOption::None);
+    let mut mirror = repo.remote_anonymous(&mirror_path.to_string_lossy())?;
+    mirror.fetch(&[format!("+{mirror_ref}:{mirror_ref}")], Some(&mut fo), None)?;
+
+    Ok(())
+}
+
+fn fetch<P>(
+    repository_url: &Url,
+    into_dir: P,
+    get_credentials: &impl Fn(&str) -> Vec<(CredentialType, Cred)>,
+    commit_id: &str,
+) -> Result<Repository, Error>
+where
+    P: AsRef<Path>,
+{
+    check_repository_url(repository_url)?;
+
     // Prepare authentication callbacks.
     let mut callbacks = RemoteCallbacks::new();
     callbacks.credentials(authentication_callback(&get_credentials));
@@ -223,10 +411,10 @@ fn remote_fetch(
 
 #[cfg(test)]
 mod tests {
-    use crate::cmd::git::{checkout, clone_at_commit, fetch};
+    use crate::cmd::git::{checkout, clone_at_commit, fetch, paths_content_hash, submodule_url};
     use base64::Engine;
     use base64::engine::general_purpose;
-    use git2::{Cred, CredentialType, Repository};
+    use git2::{Cred, CredentialType, Oid, Repository, Signature};
     use std::path::{Path, PathBuf};
     use url::Url;
     use uuid::Uuid;
@@ -395,4 +583,137 @@ mod tests {
         assert!(repo.is_ok());
         assert_eq!(repo.unwrap().head().unwrap().target().unwrap().to_string(), commit_id);
     }
+
+    fn commit_files(repo: &Repository, files: &[(&str, &str)]) -> Oid {
+        let workdir = repo.workdir().unwrap().to_path_buf();
+        let mut index = repo.index().unwrap();
+        for (path, content) in files {
+            let file_path = workdir.join(path);
+            std::fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+            std::fs::write(&file_path, content).unwrap();
+            index.add_path(Path::new(path)).unwrap();
+        }
+        index.write().unwrap();
+
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("test", "test@qovery.com").unwrap();
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents = parent.iter().collect::<Vec<_>>();
+        repo.commit(Some("HEAD"), &signature, &signature, "test", &tree, &parents)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_paths_content_hash() {
+        // setup:
+        let repo_dir = DirectoryForTests::new_with_random_suffix("/tmp/engine_test_content_hash".to_string());
+        let repo = Repository::init(repo_dir.path()).unwrap();
+        let commit_1 = commit_files(
+            &repo,
+            &[
+                ("api/main.rs", "fn main() {}"),
+                ("libs/lib.rs", "pub fn lib() {}"),
+                ("front/index.html", "<html></html>"),
+            ],
+        )
+        .to_string();
+        let commit_2 = commit_files(&repo, &[("front/index.html", "<html>v2</html>")]).to_string();
+        let commit_3 = commit_files(&repo, &[("api/main.rs", "fn main() { v2() }")]).to_string();
+        let api_paths = vec![PathBuf::from("api"), PathBuf::from("libs")];
+
+        // execute:
+        let hash_1 = paths_content_hash(repo_dir.path(), &commit_1, &api_paths).unwrap();
+        let hash_2 = paths_content_hash(repo_dir.path(), &commit_2, &api_paths).unwrap();
+        let hash_3 = paths_content_hash(repo_dir.path(), &commit_3, &api_paths).unwrap();
+        let root_hash_1 = paths_content_hash(repo_dir.path(), &commit_1, &[PathBuf::from("")]).unwrap();
+        let root_hash_2 = paths_content_hash(repo_dir.path(), &commit_2, &[PathBuf::from("")]).unwrap();
+
+        // verify:
+        assert_eq!(hash_1, hash_2, "front changes must not change the api hash");
+        assert_ne!(hash_2, hash_3);
+        assert_ne!(root_hash_1, root_hash_2);
+        assert_eq!(
+            hash_1,
+            paths_content_hash(
+                repo_dir.path(),
+                &commit_1,
+                &[PathBuf::from("libs"), PathBuf::from("api"), PathBuf::from("api")]
+            )
+            .unwrap(),
+            "order and duplicates of paths must not matter"
+        );
+        assert_eq!(
+            hash_1,
+            paths_content_hash(
+                repo_dir.path(),
+                &commit_1,
+                &[PathBuf::from("./api/"), PathBuf::from("/libs"), PathBuf::from("api/.")]
+            )
+            .unwrap(),
+            "paths must be normalized"
+        );
+        assert_eq!(
+            root_hash_1,
+            paths_content_hash(repo_dir.path(), &commit_1, &[PathBuf::from("./")]).unwrap()
+        );
+        assert!(paths_content_hash(repo_dir.path(), "c2c2101f8e4c4ffadb326dc440ba8afb4aeb1310", &api_paths).is_err());
+        assert!(paths_content_hash(repo_dir.path(), &commit_1, &[PathBuf::from("missing")]).is_err());
+        assert!(paths_content_hash(repo_dir.path(), &commit_1, &[PathBuf::from("api/../front")]).is_err());
+    }
+
+    #[test]
+    fn test_commit_timestamp() {
+        // setup:
+        let repo_dir = DirectoryForTests::new_with_random_suffix("/tmp/engine_test_commit_timestamp".to_string());
+        let repo = Repository::init(repo_dir.path()).unwrap();
+        let tree = {
+            let mut index = repo.index().unwrap();
+            repo.find_tree(index.write_tree().unwrap()).unwrap()
+        };
+        let signature = Signature::new("test", "test@qovery.com", &git2::Time::new(1_700_000_000, 60)).unwrap();
+        let commit_id = repo
+            .commit(Some("HEAD"), &signature, &signature, "test", &tree, &[])
+            .unwrap()
+            .to_string();
+
+        // execute & verify:
+        assert_eq!(
+            commit_timestamp(repo_dir.path(), &commit_id).unwrap(),
+            DateTime::from_timestamp(1_700_000_000, 0).unwrap()
+        );
+        assert!(commit_timestamp(repo_dir.path(), "c2c2101f8e4c4ffadb326dc440ba8afb4aeb1310").is_err());
+    }
+
+    #[test]
+    fn test_submodule_url() {
+        // setup:
+        let repo_dir = DirectoryForTests::new_with_random_suffix("/tmp/engine_test_submodule_url".to_string());
+        let repo = Repository::init(repo_dir.path()).unwrap();
+        std::fs::write(
+            Path::new(&repo_dir.path()).join(".gitmodules"),
+            r#"[submodule "relative"]
+    path = relative
+    url = ../lib.git
+[submodule "absolute"]
+    path = absolute
+    url = https://github.com/Qovery/other.git
+[submodule "ssh"]
+    path = ssh
+    url = git@github.com:Qovery/other.git
+"#,
+        )
+        .unwrap();
+        let repository_url = Url::parse("https://github.com/Qovery/engine.git").unwrap();
+
+        // execute:
+        let url = |name: &str| submodule_url(&repository_url, &repo.find_submodule(name).unwrap());
+
+        // verify:
+        assert_eq!(url("relative"), Some(Url::parse("https://github.com/Qovery/lib.git").unwrap()));
+        assert_eq!(
+            url("absolute"),
+            Some(Url::parse("https://github.com/Qovery/other.git").unwrap())
+        );
+        assert_eq!(url("ssh"), None, "ssh submodules are not mirrored");
+    }
 }
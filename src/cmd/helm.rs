@@ -1303,6 +1303,64 @@ impl Helm {
         }
     }
 
+    // Used by helmchart service dry run, to diff the live release against the chart with the user raw arguments
+    pub fn upgrade_diff_raw<STDOUT>(
+        &self,
+        release_name: &str,
+        chart_path: &Path,
+        namespace: &str,
+        args: &[&str],
+        envs: &[(&str, &str)],
+        cmd_killer: &CommandKiller,
+        stdout_output: &mut STDOUT,
+    ) -> Result<(), HelmError>
+    where
+        STDOUT: FnMut(String),
+    {
+        let chart_path = chart_path.to_string_lossy();
+        let args: Vec<&str> = [
+            "diff",
+            "--output",
+            "dyff",
+            "upgrade",
+            "--install",
+            release_name,
+            chart_path.as_ref(),
+            "--namespace",
+            namespace,
+        ]
+        .into_iter()
+        .chain(args.iter().copied())
+        .collect();
+
+        let mut stderr_msg = String::new();
+        let helm_ret = helm_exec_with_output(
+            &args,
+            &self.get_all_envs(envs),
+            stdout_output,
+            &mut |line| {
+                stderr_msg.push_str(&line);
+                warn!("chart {}: {}", release_name, line);
+            },
+            cmd_killer,
+        );
+
+        match helm_ret {
+            // Ok is ok
+            Ok(_) => Ok(()),
+            Err(err) => match err {
+                CommandError::TimeoutError(_) => {
+                    Err(HelmError::Timeout(release_name.to_string(), HelmCommand::DIFF, stderr_msg))
+                }
+                CommandError::Killed(_) => Err(HelmError::Killed(release_name.to_string(), HelmCommand::DIFF)),
+                _ => {
+                    error!("Helm error: {:?}", err);
+                    Err(CmdError(release_name.to_string(), HelmCommand::DIFF, err.into()))
+                }
+            },
+        }
+    }
+
     // Used by helmchart service that takes its argument raw, as it users that control them
     pub fn upgrade_raw<STDOUT, STDERR>(
         &self,
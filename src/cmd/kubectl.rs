@@ -785,6 +785,14 @@ where
     kubectl_exec::<P, PDB>(cmd_args, kubernetes_config, envs)
 }
 
+pub fn kubectl_exec_get_raw<P, T>(kubernetes_config: P, envs: Vec<(&str, &str)>, path: &str) -> Result<T, CommandError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    kubectl_exec::<P, T>(vec!["get", "--raw", path], kubernetes_config, envs)
+}
+
 pub fn kubernetes_is_metrics_server_working<P>(
     kubernetes_config: P,
     envs: Vec<(&str, &str)>,
@@ -2,10 +2,10 @@ pub mod command;
 pub mod docker;
 pub mod git;
 pub mod git_lfs;
+pub mod git_mirror;
 pub mod helm;
 pub mod helm_utils;
 pub mod kubectl;
-pub mod kubent;
 pub mod skopeo;
 pub mod structs;
 pub mod terraform;
@@ -1217,6 +1217,30 @@ pub fn terraform_state_list(
     }
 }
 
+pub fn terraform_state_mv(
+    root_dir: &str,
+    source: &str,
+    destination: &str,
+    envs: &[(&str, &str)],
+    validators: &TerraformValidators,
+) -> Result<TerraformOutput, TerraformError> {
+    let terraform_args = vec!["state", "mv", source, destination];
+    let result = retry::retry(Fixed::from_millis(3000).take(5), || {
+        match terraform_exec(root_dir, terraform_args.clone(), envs, validators) {
+            Ok(out) => OperationResult::Ok(out),
+            Err(err) => {
+                // Error while trying to run terraform state mv, retrying...
+                OperationResult::Retry(err)
+            }
+        }
+    });
+
+    match result {
+        Ok(output) => Ok(output),
+        Err(retry::Error { error, .. }) => Err(error),
+    }
+}
+
 pub fn terraform_plan_internal(
     root_dir: &str,
     envs: &[(&str, &str)],
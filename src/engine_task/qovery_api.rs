@@ -1,6 +1,8 @@
 use crate::infrastructure::action::cluster_outputs_helper::ClusterOutputsRequest;
 use crate::infrastructure::models::cloud_provider::service::ServiceType;
 use crate::io_models::application::GitCredentials;
+use crate::io_models::environment::EnvironmentRequest;
+use crate::services::cost_allocation_service::EnvironmentCost;
 use anyhow::anyhow;
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -16,6 +18,14 @@ pub trait QoveryApi: Send + Sync {
     fn service_version(&self, service_type: EngineServiceType) -> anyhow::Result<String>;
     fn git_token(&self, service_type: ServiceType, service_id: &Uuid) -> anyhow::Result<GitCredentials>;
     fn update_cluster_outputs(&self, cluster_state_request: &ClusterOutputsRequest) -> anyhow::Result<()>;
+    /// Current definition of the environment, as it would be sent for its next deployment
+    fn environment_request(&self, organization_id: &Uuid, environment_id: &Uuid) -> anyhow::Result<EnvironmentRequest>;
+    /// Stores the costs of the environments of a cluster, as computed by OpenCost
+    fn update_environments_costs(
+        &self,
+        cluster_id: &Uuid,
+        environments_costs: &[EnvironmentCost],
+    ) -> anyhow::Result<()>;
 }
 
 pub struct FakeQoveryApi {}
@@ -32,6 +42,22 @@ impl QoveryApi for FakeQoveryApi {
     fn update_cluster_outputs(&self, _cluster_outputs_request: &ClusterOutputsRequest) -> anyhow::Result<()> {
         Ok(())
     }
+
+    fn environment_request(
+        &self,
+        _organization_id: &Uuid,
+        _environment_id: &Uuid,
+    ) -> anyhow::Result<EnvironmentRequest> {
+        Err(anyhow!("not implemented"))
+    }
+
+    fn update_environments_costs(
+        &self,
+        _cluster_id: &Uuid,
+        _environments_costs: &[EnvironmentCost],
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
 }
 
 pub struct StaticQoveryApi {
@@ -54,4 +80,20 @@ impl QoveryApi for StaticQoveryApi {
     fn update_cluster_outputs(&self, _cluster_outputs_request: &ClusterOutputsRequest) -> anyhow::Result<()> {
         Ok(())
     }
+
+    fn environment_request(
+        &self,
+        _organization_id: &Uuid,
+        _environment_id: &Uuid,
+    ) -> anyhow::Result<EnvironmentRequest> {
+        Err(anyhow!("not implemented"))
+    }
+
+    fn update_environments_costs(
+        &self,
+        _cluster_id: &Uuid,
+        _environments_costs: &[EnvironmentCost],
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
 }
@@ -0,0 +1,300 @@
+use crate::environment::models::environment::{AdmissionPolicies, AdmissionPolicyMode, AdmissionPolicyViolation};
+use crate::errors::{CommandError, EngineError};
+use crate::events::{EngineEvent, EventDetails, EventMessage};
+use crate::infrastructure::models::cloud_provider::DeploymentTarget;
+use serde::Deserialize;
+
+/// Rules of a policy whose messages are violations, messages of other rules are only reported as warnings
+const DENY_RULES: &[&str] = &["deny", "violation"];
+const WARN_RULES: &[&str] = &["warn"];
+
+#[derive(thiserror::Error, Clone, Debug, PartialEq)]
+pub enum AdmissionPolicyError {
+    #[error("Admission policy `{policy_name}` is not a valid Rego module: {raw_error}")]
+    InvalidPolicy { policy_name: String, raw_error: String },
+    #[error("Manifests cannot be deserialized: {raw_error}")]
+    InvalidManifests { raw_error: String },
+    #[error("Admission policy `{policy_name}` cannot be evaluated against `{object}`: {raw_error}")]
+    EvaluationError {
+        policy_name: String,
+        object: String,
+        raw_error: String,
+    },
+}
+
+/// Evaluates rendered manifests against the organization admission policies, before they are applied.
+/// In `Enforce` mode, any violation fails the deployment. Otherwise, violations are only reported as warnings.
+pub(super) fn check_manifests_comply_with_admission_policies(
+    target: &DeploymentTarget,
+    manifests: &str,
+    event_details: &EventDetails,
+) -> Result<(), Box<EngineError>> {
+    let Some(admission_policies) = &target.environment.admission_policies else {
+        return Ok(());
+    };
+
+    let (failures, warnings) = evaluate_admission_policies(admission_policies, manifests).map_err(|err| {
+        Box::new(EngineError::new_admission_policy_evaluation_error(
+            event_details.clone(),
+            CommandError::new_from_safe_message(err.to_string()),
+        ))
+    })?;
+    let (failures, warnings) = match admission_policies.mode {
+        AdmissionPolicyMode::Enforce => (failures, warnings),
+        AdmissionPolicyMode::Warn => (vec![], failures.into_iter().chain(warnings).collect()),
+    };
+
+    for warning in &warnings {
+        target.kubernetes.logger().log(EngineEvent::Warning(
+            event_details.clone(),
+            EventMessage::new_from_safe(format!("⚠️ Admission policy: {warning}")),
+        ));
+    }
+
+    if !failures.is_empty() {
+        return Err(Box::new(EngineError::new_admission_policy_violation(
+            event_details.clone(),
+            &failures,
+        )));
+    }
+
+    Ok(())
+}
+
+/// Evaluates every object of the manifests against every policy, and returns violations and warnings
+fn evaluate_admission_policies(
+    admission_policies: &AdmissionPolicies,
+    manifests: &str,
+) -> Result<(Vec<AdmissionPolicyViolation>, Vec<AdmissionPolicyViolation>), AdmissionPolicyError> {
+    let objects = parse_manifests(manifests)?;
+    let mut failures = vec![];
+    let mut warnings = vec![];
+    if objects.is_empty() {
+        return Ok((failures, warnings));
+    }
+
+    for policy in &admission_policies.policies {
+        let invalid_policy = |raw_error: String| AdmissionPolicyError::InvalidPolicy {
+            policy_name: policy.name.clone(),
+            raw_error,
+        };
+        let package = rego_package(&policy.rego).ok_or_else(|| invalid_policy("missing package".to_string()))?;
+        let mut engine = regorus::Engine::new();
+        engine
+            .add_policy(format!("{}.rego", policy.name), policy.rego.clone())
+            .map_err(|e| invalid_policy(e.to_string()))?;
+
+        for (object, manifest) in &objects {
+            let to_evaluation_error = |raw_error: String| AdmissionPolicyError::EvaluationError {
+                policy_name: policy.name.clone(),
+                object: object.clone(),
+                raw_error,
+            };
+            let to_violation = |message: String| AdmissionPolicyViolation {
+                object: object.clone(),
+                policy_name: policy.name.clone(),
+                message,
+            };
+
+            engine.set_input(
+                regorus::Value::from_json_str(&manifest.to_string()).map_err(|e| to_evaluation_error(e.to_string()))?,
+            );
+            for rule in DENY_RULES {
+                let messages = rule_messages(&mut engine, &package, rule).map_err(to_evaluation_error)?;
+                failures.extend(messages.into_iter().map(to_violation));
+            }
+            for rule in WARN_RULES {
+                let messages = rule_messages(&mut engine, &package, rule).map_err(to_evaluation_error)?;
+                warnings.extend(messages.into_iter().map(to_violation));
+            }
+        }
+    }
+
+    Ok((failures, warnings))
+}
+
+fn rego_package(rego: &str) -> Option<String> {
+    rego.lines()
+        .map(|line| line.trim())
+        .find_map(|line| line.strip_prefix("package "))
+        .map(|package| package.trim().to_string())
+}
+
+/// Returns the `Kind/name` of every object of the manifests, with the object itself
+fn parse_manifests(manifests: &str) -> Result<Vec<(String, serde_json::Value)>, AdmissionPolicyError> {
+    let mut objects = vec![];
+    for document in serde_yaml::Deserializer::from_str(manifests) {
+        let object = serde_json::Value::deserialize(document).map_err(|e| AdmissionPolicyError::InvalidManifests {
+            raw_error: e.to_string(),
+        })?;
+        if object.is_null() {
+            continue;
+        }
+
+        let kind = object["kind"].as_str().unwrap_or("Unknown");
+        let name = object["metadata"]["name"].as_str().unwrap_or("unnamed");
+        objects.push((format!("{kind}/{name}"), object));
+    }
+
+    Ok(objects)
+}
+
+/// Messages produced by a rule of the package, an undefined rule produces none
+fn rule_messages(engine: &mut regorus::Engine, package: &str, rule: &str) -> Result<Vec<String>, String> {
+    let results = engine
+        .eval_query(format!("data.{package}.{rule}"), false)
+        .map_err(|e| e.to_string())?;
+
+    let mut messages = vec![];
+    for expression in results.result.into_iter().flat_map(|result| result.expressions) {
+        let value: serde_json::Value = expression
+            .value
+            .to_json_str()
+            .and_then(|json| Ok(serde_json::from_str(&json)?))
+            .map_err(|e| e.to_string())?;
+        messages.extend(to_messages(rule, &value));
+    }
+
+    Ok(messages)
+}
+
+/// Rules are sets of messages, either strings or objects with a `msg` field, or booleans
+fn to_messages(rule: &str, value: &serde_json::Value) -> Vec<String> {
+    match value {
+        serde_json::Value::Array(items) => items
+            .iter()
+            .filter_map(|item| match item {
+                serde_json::Value::String(message) => Some(message.clone()),
+                serde_json::Value::Object(_) => item["msg"].as_str().map(|message| message.to_string()),
+                _ => None,
+            })
+            .collect(),
+        serde_json::Value::Bool(true) => vec![format!("`{rule}` rule matched")],
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environment::models::environment::AdmissionPolicy;
+
+    const MANIFESTS: &str = r#"
+---
+# Source: app/templates/empty.yaml
+---
+apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: app
+spec:
+  template:
+    spec:
+      containers:
+        - name: app
+          image: nginx:latest
+---
+apiVersion: v1
+kind: Service
+metadata:
+  name: app-svc
+"#;
+
+    fn admission_policies(rego: &str) -> AdmissionPolicies {
+        AdmissionPolicies {
+            mode: AdmissionPolicyMode::Enforce,
+            policies: vec![AdmissionPolicy {
+                name: "no-latest-tag".to_string(),
+                rego: rego.to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_parse_manifests_names_every_object() {
+        let objects = parse_manifests(MANIFESTS).unwrap();
+
+        assert_eq!(
+            objects.iter().map(|(object, _)| object.as_str()).collect::<Vec<_>>(),
+            vec!["Deployment/app", "Service/app-svc"]
+        );
+        assert!(matches!(
+            parse_manifests("kind: [Deployment"),
+            Err(AdmissionPolicyError::InvalidManifests { .. })
+        ));
+    }
+
+    #[test]
+    fn test_evaluate_admission_policies_names_object_and_policy() {
+        let policies = admission_policies(
+            r#"
+# images must be pinned
+package qovery.images
+
+import rego.v1
+
+deny contains msg if {
+    some container in input.spec.template.spec.containers
+    endswith(container.image, ":latest")
+    msg := sprintf("image `%s` uses the latest tag", [container.image])
+}
+
+warn contains {"msg": "services should be labelled"} if {
+    input.kind == "Service"
+    not input.metadata.labels
+}
+"#,
+        );
+
+        let (failures, warnings) = evaluate_admission_policies(&policies, MANIFESTS).unwrap();
+
+        assert_eq!(
+            failures,
+            vec![AdmissionPolicyViolation {
+                object: "Deployment/app".to_string(),
+                policy_name: "no-latest-tag".to_string(),
+                message: "image `nginx:latest` uses the latest tag".to_string(),
+            }]
+        );
+        assert_eq!(
+            warnings,
+            vec![AdmissionPolicyViolation {
+                object: "Service/app-svc".to_string(),
+                policy_name: "no-latest-tag".to_string(),
+                message: "services should be labelled".to_string(),
+            }]
+        );
+        assert_eq!(
+            failures[0].to_string(),
+            "`Deployment/app` violates policy `no-latest-tag`: image `nginx:latest` uses the latest tag"
+        );
+    }
+
+    #[test]
+    fn test_evaluate_admission_policies_invalid_policy() {
+        assert!(matches!(
+            evaluate_admission_policies(&admission_policies("deny contains msg if {"), MANIFESTS),
+            Err(AdmissionPolicyError::InvalidPolicy { .. })
+        ));
+        assert!(matches!(
+            evaluate_admission_policies(
+                &admission_policies("package qovery.images\n\ndeny contains msg if {"),
+                MANIFESTS
+            ),
+            Err(AdmissionPolicyError::InvalidPolicy { .. })
+        ));
+    }
+
+    #[test]
+    fn test_to_messages() {
+        assert_eq!(
+            to_messages("deny", &serde_json::json!(["a", {"msg": "b"}, 1])),
+            vec!["a".to_string(), "b".to_string()]
+        );
+        assert_eq!(
+            to_messages("deny", &serde_json::json!(true)),
+            vec!["`deny` rule matched".to_string()]
+        );
+        assert!(to_messages("deny", &serde_json::json!(false)).is_empty());
+    }
+}
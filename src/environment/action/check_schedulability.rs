@@ -0,0 +1,311 @@
+use crate::environment::models::environment::Environment;
+use crate::errors::EngineError;
+use crate::events::{EngineEvent, EnvironmentStep, EventDetails, EventMessage, Stage};
+use crate::infrastructure::models::cloud_provider::DeploymentTarget;
+use crate::infrastructure::models::cloud_provider::service::{Action, Service, ServiceResourcesRequest};
+use crate::infrastructure::models::kubernetes::instance_capacity::{NodeAllocatable, NodeCapacities};
+use crate::logger::Logger;
+use crate::runtime::block_on;
+use k8s_openapi::api::core::v1::Pod;
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use kube::Api;
+use kube::api::ListParams;
+
+fn services_resources_requests(environment: &Environment) -> Vec<(&dyn Service, ServiceResourcesRequest)> {
+    std::iter::empty()
+        .chain(environment.applications.iter().map(|s| s.as_service()))
+        .chain(environment.containers.iter().map(|s| s.as_service()))
+        .chain(environment.jobs.iter().map(|s| s.as_service()))
+        .filter(|service| service.action() == &Action::Create)
+        .filter_map(|service| service.resources_request().map(|request| (service, request)))
+        .collect()
+}
+
+/// Rejects services requesting more resources than any node of the cluster can offer, as their pods would stay pending forever.
+/// When the capacity of some instance types of the cluster is unknown, the service may still fit, so it is only a warning.
+pub(super) fn check_services_fit_on_nodes(
+    target: &DeploymentTarget,
+    logger: &dyn Logger,
+) -> Result<(), Box<EngineError>> {
+    for (service, request) in services_resources_requests(target.environment) {
+        let node_capacities = target.kubernetes.node_capacities(request.node_pool.as_deref());
+        let event_details = service.get_event_details(Stage::Environment(EnvironmentStep::Deploy));
+        match fit_on_nodes(service.name(), &request, &node_capacities) {
+            NodesFit::Fits => {}
+            NodesFit::MayNotFit(warning) => {
+                logger.log(EngineEvent::Warning(event_details, EventMessage::new_from_safe(warning)));
+            }
+            NodesFit::CannotFit {
+                max_node_cpu_in_milli,
+                max_node_ram_in_mib,
+            } => {
+                return Err(Box::new(EngineError::new_service_cannot_fit_on_any_node(
+                    event_details,
+                    service.name(),
+                    request.cpu_in_milli,
+                    request.ram_in_mib,
+                    max_node_cpu_in_milli,
+                    max_node_ram_in_mib,
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum NodesFit {
+    /// A known instance type can run the service, or nodes are not known by the engine
+    Fits,
+    /// No known instance type can run the service, but the capacity of some instance types is unknown
+    MayNotFit(String),
+    /// Every instance type is known and none can run the service
+    CannotFit {
+        max_node_cpu_in_milli: u32,
+        max_node_ram_in_mib: u32,
+    },
+}
+
+fn fit_on_nodes(service_name: &str, request: &ServiceResourcesRequest, node_capacities: &NodeCapacities) -> NodesFit {
+    let allocatables = node_capacities
+        .capacities
+        .iter()
+        .map(|capacity| capacity.allocatable())
+        .collect::<Vec<_>>();
+    if allocatables
+        .iter()
+        .any(|allocatable| allocatable.can_fit(request.cpu_in_milli, request.ram_in_mib))
+    {
+        return NodesFit::Fits;
+    }
+
+    let requested = format!(
+        "⚠️ Service `{service_name}` requests {}m CPU and {}mib RAM",
+        request.cpu_in_milli, request.ram_in_mib
+    );
+    let max_node_cpu_in_milli = allocatables.iter().map(|it| it.cpu_in_milli).max().unwrap_or_default();
+    let max_node_ram_in_mib = allocatables.iter().map(|it| it.memory_in_mib).max().unwrap_or_default();
+    match (allocatables.is_empty(), node_capacities.unknown_instance_types.is_empty()) {
+        // nodes are not known by the engine
+        (true, true) => NodesFit::Fits,
+        (false, true) => NodesFit::CannotFit {
+            max_node_cpu_in_milli,
+            max_node_ram_in_mib,
+        },
+        (true, false) => NodesFit::MayNotFit(format!(
+            "{requested}, capacity of the cluster instance types ({}) is unknown, make sure they can run it or its pods would stay pending.",
+            node_capacities.unknown_instance_types.join(", ")
+        )),
+        (false, false) => NodesFit::MayNotFit(format!(
+            "{requested}, known instance types of the cluster offer about {max_node_cpu_in_milli}m CPU and {max_node_ram_in_mib}mib RAM at most to services and capacity of the others ({}) is unknown, make sure they can run it or its pods would stay pending.",
+            node_capacities.unknown_instance_types.join(", ")
+        )),
+    }
+}
+
+/// Estimates how many nodes the cluster may have to start to run the environment, and warns the user when some are expected.
+/// Only the environment namespace is looked at, so the estimate assumes no free resources are left on the nodes.
+/// It is a best effort estimation, it is skipped when the environment pods cannot be listed.
+pub(super) fn warn_about_extra_nodes(target: &DeploymentTarget, event_details: &EventDetails, logger: &dyn Logger) {
+    let requests = services_resources_requests(target.environment);
+    let requested_cpu_in_milli: u32 = requests.iter().map(|(_, it)| it.cpu_in_milli * it.instances).sum();
+    let requested_ram_in_mib: u32 = requests.iter().map(|(_, it)| it.ram_in_mib * it.instances).sum();
+    if requested_cpu_in_milli == 0 && requested_ram_in_mib == 0 {
+        return;
+    }
+
+    // new nodes are expected to be of the biggest instance type available for the environment
+    let Some(new_node) = target
+        .kubernetes
+        .node_capacities(None)
+        .capacities
+        .iter()
+        .map(|capacity| capacity.allocatable())
+        .max()
+    else {
+        return;
+    };
+
+    // pods of the environment are going to be replaced by the deployment, their resources are released
+    let Some((running_cpu_in_milli, running_ram_in_mib)) =
+        block_on(get_environment_requests(&target.kube.client(), target.environment.namespace()))
+    else {
+        return;
+    };
+
+    let extra_nodes = extra_nodes_estimate(
+        requested_cpu_in_milli,
+        requested_ram_in_mib,
+        running_cpu_in_milli,
+        running_ram_in_mib,
+        &new_node,
+    );
+    if extra_nodes > 0 {
+        logger.log(EngineEvent::Warning(
+            event_details.clone(),
+            EventMessage::new_from_safe(format!(
+                "⚠️ This environment requests more resources than currently running ({requested_cpu_in_milli}m CPU and {requested_ram_in_mib}mib RAM requested), up to {extra_nodes} additional node(s) may be started if cluster nodes lack free resources"
+            )),
+        ));
+    }
+}
+
+fn extra_nodes_estimate(
+    requested_cpu_in_milli: u32,
+    requested_ram_in_mib: u32,
+    running_cpu_in_milli: u32,
+    running_ram_in_mib: u32,
+    new_node: &NodeAllocatable,
+) -> u32 {
+    let nodes_for = |requested: u32, running: u32, per_node: u32| match requested.saturating_sub(running) {
+        0 => 0,
+        _ if per_node == 0 => 0,
+        missing => missing.div_ceil(per_node),
+    };
+
+    nodes_for(requested_cpu_in_milli, running_cpu_in_milli, new_node.cpu_in_milli).max(nodes_for(
+        requested_ram_in_mib,
+        running_ram_in_mib,
+        new_node.memory_in_mib,
+    ))
+}
+
+/// CPU in milli and RAM in mib requested by the running pods of the environment namespace
+async fn get_environment_requests(client: &kube::Client, environment_namespace: &str) -> Option<(u32, u32)> {
+    let pods = Api::<Pod>::namespaced(client.clone(), environment_namespace)
+        .list(&ListParams::default().fields("status.phase!=Succeeded,status.phase!=Failed"))
+        .await
+        .ok()?;
+
+    let (cpu, ram) = pods
+        .items
+        .iter()
+        .filter_map(|pod| pod.spec.as_ref())
+        .flat_map(|spec| spec.containers.iter())
+        .filter_map(|container| container.resources.as_ref()?.requests.as_ref())
+        .fold((0.0, 0.0), |(cpu, ram), requests| {
+            (
+                cpu + requests.get("cpu").and_then(parse_quantity).unwrap_or_default(),
+                ram + requests.get("memory").and_then(parse_quantity).unwrap_or_default(),
+            )
+        });
+
+    Some(((cpu * 1000.0) as u32, (ram / 1_048_576.0) as u32))
+}
+
+/// Parses a kubernetes quantity (i.e `500m`, `2`, `512Mi`, `1G`) into its base unit
+fn parse_quantity(quantity: &Quantity) -> Option<f64> {
+    let value = quantity.0.trim();
+    let (number, suffix) = value.split_at(
+        value
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(value.len()),
+    );
+    let multiplier = match suffix {
+        "" => 1.0,
+        "m" => 0.001,
+        "k" => 1e3,
+        "M" => 1e6,
+        "G" => 1e9,
+        "T" => 1e12,
+        "Ki" => 1024.0,
+        "Mi" => 1_048_576.0,
+        "Gi" => 1_073_741_824.0,
+        "Ti" => 1_099_511_627_776.0,
+        _ => return None,
+    };
+
+    number.parse::<f64>().ok().map(|number| number * multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::models::kubernetes::instance_capacity::InstanceCapacity;
+
+    #[test]
+    fn test_parse_quantity() {
+        assert_eq!(parse_quantity(&Quantity("500m".to_string())), Some(0.5));
+        assert_eq!(parse_quantity(&Quantity("2".to_string())), Some(2.0));
+        assert_eq!(parse_quantity(&Quantity("512Mi".to_string())), Some(536_870_912.0));
+        assert_eq!(parse_quantity(&Quantity("1G".to_string())), Some(1e9));
+        assert_eq!(parse_quantity(&Quantity("3860956Ki".to_string())), Some(3_953_618_944.0));
+        assert_eq!(parse_quantity(&Quantity("1e3".to_string())), None);
+    }
+
+    #[test]
+    fn test_extra_nodes_estimate() {
+        let new_node = NodeAllocatable {
+            cpu_in_milli: 1930,
+            memory_in_mib: 3554,
+            pods: 17,
+        };
+
+        // environment does not grow
+        assert_eq!(extra_nodes_estimate(1000, 2048, 1500, 4096, &new_node), 0);
+        // cpu is missing
+        assert_eq!(extra_nodes_estimate(5000, 2048, 1000, 4096, &new_node), 3);
+        // ram is missing
+        assert_eq!(extra_nodes_estimate(1000, 8192, 1500, 1024, &new_node), 3);
+        // the most missing resource drives the estimate
+        assert_eq!(extra_nodes_estimate(3000, 8192, 1000, 0, &new_node), 3);
+    }
+
+    #[test]
+    fn test_fit_on_nodes() {
+        let request = |cpu_in_milli, ram_in_mib| ServiceResourcesRequest {
+            cpu_in_milli,
+            ram_in_mib,
+            instances: 1,
+            node_pool: None,
+        };
+        let node_capacities = |unknown_instance_types: Vec<&str>| NodeCapacities {
+            // 1930m CPU and 3554mib RAM allocatable
+            capacities: vec![InstanceCapacity {
+                vcpus: 2,
+                memory_in_mib: 4096,
+                max_pods: 17,
+            }],
+            unknown_instance_types: unknown_instance_types.into_iter().map(str::to_string).collect(),
+        };
+
+        // fits on a node
+        assert_eq!(
+            fit_on_nodes("app", &request(1000, 2048), &node_capacities(vec![])),
+            NodesFit::Fits
+        );
+        // nodes are not known by the engine
+        assert_eq!(
+            fit_on_nodes("app", &request(4000, 2048), &NodeCapacities::default()),
+            NodesFit::Fits
+        );
+        // too big for every instance type of the cluster
+        assert_eq!(
+            fit_on_nodes("app", &request(4000, 2048), &node_capacities(vec![])),
+            NodesFit::CannotFit {
+                max_node_cpu_in_milli: 1930,
+                max_node_ram_in_mib: 3554,
+            }
+        );
+        // too big for known nodes, unknown instance types may fit it
+        let NodesFit::MayNotFit(warning) =
+            fit_on_nodes("app", &request(1000, 8192), &node_capacities(vec!["m5.metal"]))
+        else {
+            panic!("a warning should be returned");
+        };
+        assert!(warning.contains("(m5.metal) is unknown"));
+        // only unknown instance types
+        let NodesFit::MayNotFit(warning) = fit_on_nodes(
+            "app",
+            &request(1000, 2048),
+            &NodeCapacities {
+                capacities: vec![],
+                unknown_instance_types: vec!["m5.metal".to_string()],
+            },
+        ) else {
+            panic!("a warning should be returned");
+        };
+        assert!(warning.contains("(m5.metal) is unknown"));
+    }
+}
@@ -1,9 +1,11 @@
 use crate::environment::action::DeploymentAction;
 use crate::environment::action::deploy_helm::HelmDeployment;
+use crate::environment::action::dry_run::ServiceDiff;
 use crate::environment::action::pause_service::PauseServiceAction;
 use crate::environment::models::application::{
     Application, ApplicationService, get_application_with_invalid_storage_size,
 };
+use crate::environment::models::container::get_mirror_repository_name;
 use crate::environment::models::types::{CloudProvider, ToTeraContext};
 use crate::environment::report::application::reporter::ApplicationDeploymentReporter;
 use crate::environment::report::execute_long_deployment;
@@ -11,12 +13,16 @@ use crate::errors::{CommandError, EngineError};
 use crate::events::{EnvironmentStep, Stage};
 use crate::helm::{ChartInfo, HelmAction, HelmChartNamespaces};
 use crate::infrastructure::models::cloud_provider::DeploymentTarget;
+use crate::infrastructure::models::cloud_provider::io::RegistryMirroringMode;
 use crate::infrastructure::models::cloud_provider::service::{Action, Service};
 use crate::kubers_utils::{KubeDeleteMode, kube_delete_all_from_selector};
 use crate::runtime::block_on;
 use k8s_openapi::api::core::v1::PersistentVolumeClaim;
 
-use super::utils::{delete_nlb_or_alb_service, update_pvcs};
+use super::utils::{
+    KubeObjectKind, delete_nlb_or_alb_service, get_deployed_image_source, get_last_deployed_image,
+    mirror_image_if_necessary, update_pvcs,
+};
 use crate::environment::action::restart_service::RestartServiceAction;
 use crate::environment::report::logger::EnvProgressLogger;
 use crate::infrastructure::models::kubernetes::Kind;
@@ -31,6 +37,18 @@ where
     fn on_create(&self, target: &DeploymentTarget) -> Result<(), Box<EngineError>> {
         let long_task = |logger: &EnvProgressLogger| -> Result<(), Box<EngineError>> {
             let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Deploy));
+            // The application image is built into the cluster registry, only init/sidecar images need mirroring
+            for additional_container in &self.additional_containers {
+                mirror_image_if_necessary(
+                    self.long_id(),
+                    &additional_container.source,
+                    target,
+                    logger,
+                    event_details.clone(),
+                    target.metrics_registry.clone(),
+                )?;
+            }
+
             // If the service have been paused, we must ensure we un-pause it first as hpa will not kick in
             let _ = PauseServiceAction::new(
                 self.kube_label_selector(),
@@ -41,6 +59,24 @@ where
             )
             .unpause_if_needed(target);
 
+            if let Some(source) = block_on(get_last_deployed_image(
+                target.kube.client(),
+                &self.kube_label_selector(),
+                if self.is_stateful() {
+                    KubeObjectKind::Statefulset
+                } else {
+                    KubeObjectKind::Deployment
+                },
+                target.environment.namespace(),
+            ))
+            .and_then(|image| get_deployed_image_source(target.docker, &image))
+            {
+                logger.info(format!(
+                    "📌 Currently deployed version was built from {} at commit {}",
+                    source.url, source.revision
+                ));
+            }
+
             match get_application_with_invalid_storage_size(
                 self,
                 &target.kube,
@@ -200,6 +236,25 @@ where
                     return Err(Box::new(user_error));
                 }
 
+                // Images of init/sidecar containers are mirrored in a repository dedicated to the application.
+                // It may outlive them if they have been removed before the application, so it is always deleted.
+                if target.kubernetes.advanced_settings().registry_mirroring_mode == RegistryMirroringMode::Service {
+                    let mirror_repo_name =
+                        target
+                            .container_registry
+                            .registry_info()
+                            .get_repository_name(&get_mirror_repository_name(
+                                self.long_id(),
+                                target.kubernetes.long_id(),
+                                &RegistryMirroringMode::Service,
+                            ));
+                    if let Err(err) = target.container_registry.delete_repository(&mirror_repo_name) {
+                        logger.warning(format!(
+                            "⚠️ Cannot delete mirror registry of the application {mirror_repo_name}: {err}"
+                        ));
+                    }
+                }
+
                 Ok(())
             },
         )
@@ -218,4 +273,30 @@ where
             },
         )
     }
+
+    fn on_dry_run(
+        &self,
+        target: &DeploymentTarget,
+        action: Action,
+        logger: &EnvProgressLogger,
+    ) -> Result<ServiceDiff, Box<EngineError>> {
+        let event_details = self.get_event_details(Stage::Environment(action.to_environment_step()));
+        let chart = ChartInfo {
+            name: self.helm_release_name(),
+            path: self.workspace_directory().to_string(),
+            namespace: HelmChartNamespaces::Custom(target.environment.namespace().to_string()),
+            timeout_in_seconds: self.startup_timeout().as_secs() as i64,
+            k8s_selector: Some(self.kube_label_selector()),
+            ..Default::default()
+        };
+        let helm = HelmDeployment::new(
+            event_details,
+            self.to_tera_context(target)?,
+            PathBuf::from(self.helm_chart_dir()),
+            None,
+            chart,
+        );
+
+        helm.on_dry_run(target, action, logger)
+    }
 }
@@ -1,5 +1,6 @@
 use crate::environment::action::DeploymentAction;
 use crate::environment::action::deploy_helm::HelmDeployment;
+use crate::environment::action::dry_run::ServiceDiff;
 use crate::environment::action::pause_service::PauseServiceAction;
 use crate::environment::models::container::{Container, ContainerService, get_container_with_invalid_storage_size};
 use crate::environment::models::types::{CloudProvider, ToTeraContext};
@@ -35,7 +36,7 @@ where
             last_deployed_image: Option<String>,
         }
 
-        // We first mirror the image if needed
+        // We first mirror the images if needed
         let pre_task = |logger: &EnvProgressLogger| -> Result<TaskContext, Box<EngineError>> {
             mirror_image_if_necessary(
                 self.long_id(),
@@ -45,6 +46,16 @@ where
                 event_details.clone(),
                 metrics_registry.clone(),
             )?;
+            for additional_container in &self.additional_containers {
+                mirror_image_if_necessary(
+                    self.long_id(),
+                    &additional_container.source,
+                    target,
+                    logger,
+                    event_details.clone(),
+                    metrics_registry.clone(),
+                )?;
+            }
 
             let last_image = block_on(get_last_deployed_image(
                 target.kube.client(),
@@ -293,4 +304,30 @@ where
             },
         )
     }
+
+    fn on_dry_run(
+        &self,
+        target: &DeploymentTarget,
+        action: Action,
+        logger: &EnvProgressLogger,
+    ) -> Result<ServiceDiff, Box<EngineError>> {
+        let event_details = self.get_event_details(Stage::Environment(action.to_environment_step()));
+        let chart = ChartInfo {
+            name: self.helm_release_name(),
+            path: self.workspace_directory().to_string(),
+            namespace: HelmChartNamespaces::Custom(target.environment.namespace().to_string()),
+            timeout_in_seconds: self.startup_timeout().as_secs() as i64,
+            k8s_selector: Some(self.kube_label_selector()),
+            ..Default::default()
+        };
+        let helm = HelmDeployment::new(
+            event_details,
+            self.to_tera_context(target)?,
+            PathBuf::from(self.helm_chart_dir()),
+            None,
+            chart,
+        );
+
+        helm.on_dry_run(target, action, logger)
+    }
 }
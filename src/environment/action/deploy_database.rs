@@ -5,6 +5,7 @@ use crate::environment::action::DeploymentAction;
 use crate::environment::action::check_dns::CheckDnsForDomains;
 use crate::environment::action::deploy_helm::HelmDeployment;
 use crate::environment::action::deploy_terraform::TerraformDeployment;
+use crate::environment::action::dry_run::ServiceDiff;
 use crate::environment::action::pause_service::PauseServiceAction;
 use crate::environment::models::database::{
     Container, Database, DatabaseError, DatabaseService, DatabaseType, Managed, get_database_with_invalid_storage_size,
@@ -701,6 +702,25 @@ where
             command_error,
         )))
     }
+
+    fn on_dry_run(
+        &self,
+        target: &DeploymentTarget,
+        action: Action,
+        logger: &EnvProgressLogger,
+    ) -> Result<ServiceDiff, Box<EngineError>> {
+        // The cloud provider side of the database is only known by terraform, so we ask it for a plan
+        let event_details = self.get_event_details(Stage::Environment(action.to_environment_step()));
+        TerraformDeployment::new(
+            self.to_tera_context(target)?,
+            PathBuf::from(self.terraform_common_resource_dir_path()),
+            PathBuf::from(self.terraform_resource_dir_path()),
+            PathBuf::from(self.workspace_directory()),
+            event_details,
+            true,
+        )
+        .on_dry_run(target, action, logger)
+    }
 }
 
 // For Container database
@@ -881,4 +901,30 @@ where
             },
         )
     }
+
+    fn on_dry_run(
+        &self,
+        target: &DeploymentTarget,
+        action: Action,
+        logger: &EnvProgressLogger,
+    ) -> Result<ServiceDiff, Box<EngineError>> {
+        let event_details = self.get_event_details(Stage::Environment(action.to_environment_step()));
+        let chart = ChartInfo {
+            name: self.helm_release_name(),
+            path: self.workspace_directory().to_string(),
+            namespace: HelmChartNamespaces::Custom(target.environment.namespace().to_string()),
+            k8s_selector: Some(self.kube_label_selector()),
+            values_files: vec![format!("{}/qovery-values.yaml", self.workspace_directory())],
+            ..Default::default()
+        };
+        let helm = HelmDeployment::new(
+            event_details,
+            self.to_tera_context(target)?,
+            PathBuf::from(self.helm_chart_dir()),
+            Some(PathBuf::from(format!("{}/qovery-values.j2.yaml", self.helm_chart_values_dir()))),
+            chart,
+        );
+
+        helm.on_dry_run(target, action, logger)
+    }
 }
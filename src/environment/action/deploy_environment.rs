@@ -1,4 +1,5 @@
 use crate::environment::action::DeploymentAction;
+use crate::environment::action::check_schedulability::{check_services_fit_on_nodes, warn_about_extra_nodes};
 use crate::environment::action::deploy_namespace::NamespaceDeployment;
 use crate::environment::models::abort::Abort;
 use crate::environment::models::environment::Environment;
@@ -127,6 +128,10 @@ impl<'a> EnvironmentDeployment<'a> {
         let should_abort = Self::should_abort_wrapper(target, &event_details);
         should_abort()?;
 
+        // reject services that would never be scheduled before deploying anything
+        check_services_fit_on_nodes(target, &**self.logger)?;
+        warn_about_extra_nodes(target, &event_details, &**self.logger);
+
         // deploy namespace first
         let ns = NamespaceDeployment {
             resource_expiration,
@@ -1,9 +1,14 @@
 use crate::cmd::command::CommandKiller;
+use crate::cmd::helm::HelmError;
 use crate::environment::action::DeploymentAction;
+use crate::environment::action::check_admission_policies::check_manifests_comply_with_admission_policies;
+use crate::environment::action::dry_run::{ServiceChange, ServiceDiff};
+use crate::environment::report::logger::EnvProgressLogger;
 use crate::errors::{CommandError, EngineError};
 use crate::events::{EnvironmentStep, EventDetails, Stage};
 use crate::helm::{ChartInfo, HelmChart, ServiceChart};
 use crate::infrastructure::models::cloud_provider::DeploymentTarget;
+use crate::infrastructure::models::cloud_provider::service::Action;
 use crate::template::generate_and_copy_all_files_into_dir;
 use std::env;
 use std::path::{Path, PathBuf};
@@ -78,11 +83,33 @@ impl HelmDeployment {
 
         Ok(())
     }
+
+    fn check_admission_policies(&self, target: &DeploymentTarget) -> Result<(), Box<EngineError>> {
+        if target.environment.admission_policies.is_none() {
+            return Ok(());
+        }
+
+        let manifests = target
+            .helm
+            .template_raw(
+                &self.helm_chart.name,
+                Path::new(&self.helm_chart.path),
+                target.environment.namespace(),
+                &[],
+                target.cloud_provider.credentials_environment_variables().as_slice(),
+                &CommandKiller::from_cancelable(target.abort),
+                &mut |_| {},
+            )
+            .map_err(|e| EngineError::new_helm_error(self.event_details.clone(), e))?;
+
+        check_manifests_comply_with_admission_policies(target, &manifests, &self.event_details)
+    }
 }
 
 impl DeploymentAction for HelmDeployment {
     fn on_create(&self, target: &DeploymentTarget) -> Result<(), Box<EngineError>> {
         self.prepare_helm_chart()?;
+        self.check_admission_policies(target)?;
 
         let service_chart = ServiceChart::new(target.helm.clone(), self.helm_chart.clone());
         let chart: Box<dyn HelmChart> = Box::new(service_chart);
@@ -132,6 +159,42 @@ impl DeploymentAction for HelmDeployment {
             command_error,
         )))
     }
+
+    fn on_dry_run(
+        &self,
+        target: &DeploymentTarget,
+        action: Action,
+        _logger: &EnvProgressLogger,
+    ) -> Result<ServiceDiff, Box<EngineError>> {
+        let release_exists = match target.helm.check_release_exist(&self.helm_chart, &[]) {
+            Ok(_) => true,
+            Err(HelmError::ReleaseDoesNotExist(_)) => false,
+            Err(err) => return Err(Box::new(EngineError::new_helm_error(self.event_details.clone(), err))),
+        };
+
+        match action {
+            Action::Create => {
+                // Only the workspace is written, the diff is computed against the live release
+                self.prepare_helm_chart()?;
+                let mut diff = vec![];
+                target
+                    .helm
+                    .upgrade_diff(&self.helm_chart, &[], &mut |line| diff.push(line))
+                    .map_err(|e| EngineError::new_helm_error(self.event_details.clone(), e))?;
+
+                let change = match (release_exists, diff.iter().all(|line| line.trim().is_empty())) {
+                    (false, _) => ServiceChange::Create,
+                    (true, true) => ServiceChange::Unchanged,
+                    (true, false) => ServiceChange::Update,
+                };
+                Ok(ServiceDiff::new(change, diff))
+            }
+            Action::Pause | Action::Delete | Action::Restart if !release_exists => {
+                Ok(ServiceDiff::new(ServiceChange::Unchanged, vec![]))
+            }
+            Action::Pause | Action::Delete | Action::Restart => Ok(ServiceDiff::from_action(action)),
+        }
+    }
 }
 
 #[cfg(feature = "test-local-kube")]
@@ -2,6 +2,9 @@ use crate::infrastructure::models::build_platform::{Credentials, SshKey};
 
 use crate::cmd::command::CommandKiller;
 use crate::cmd::git;
+use crate::cmd::helm::HelmError;
+use crate::environment::action::check_admission_policies::check_manifests_comply_with_admission_policies;
+use crate::environment::action::dry_run::{ServiceChange, ServiceDiff};
 use crate::environment::action::pause_service::PauseServiceAction;
 use crate::environment::action::restart_service::RestartServiceAction;
 use crate::environment::action::{DeploymentAction, K8sResourceType};
@@ -44,8 +47,13 @@ impl<T: CloudProvider> DeploymentAction for HelmChart<T> {
             prepare_helm_chart_directory(self, target, event_details.clone(), logger)?;
             // Now the chart is ready at self.chart_workspace_directory()
 
-            // Check users does not bypass restrictions (i.e: install cluster wide resources, or not in the correct namespace)
-            check_resources_are_allowed_to_install(self, target, event_details.clone(), logger)?;
+            // The chart is rendered once, for every check of its resources
+            if !self.is_cluster_wide_resources_allowed() || target.environment.admission_policies.is_some() {
+                let template = render_helm_chart(self, target, event_details.clone(), logger)?;
+                // Check users does not bypass restrictions (i.e: install cluster wide resources, or not in the correct namespace)
+                check_resources_are_allowed_to_install(self, target, &template, event_details.clone(), logger)?;
+                check_resources_comply_with_admission_policies(target, &template, event_details.clone(), logger)?;
+            }
 
             // Create config map for qovery-webhook-admission-controller to inject labels / annotations
             create_config_map_for_webhook_admission_controller_if_not_exists(self, target, event_details.clone())?;
@@ -231,6 +239,59 @@ impl<T: CloudProvider> DeploymentAction for HelmChart<T> {
 
         execute_long_deployment(HelmChartDeploymentReporter::new(self, target, Action::Restart), task)
     }
+
+    fn on_dry_run(
+        &self,
+        target: &DeploymentTarget,
+        action: Action,
+        logger: &EnvProgressLogger,
+    ) -> Result<ServiceDiff, Box<EngineError>> {
+        let event_details = self.get_event_details(Stage::Environment(action.to_environment_step()));
+        let chart = ChartInfo::new_from_release_name(self.helm_release_name(), target.environment.namespace());
+        let release_exists = match target.helm.check_release_exist(&chart, &[]) {
+            Ok(_) => true,
+            Err(HelmError::ReleaseDoesNotExist(_)) => false,
+            Err(err) => return Err((event_details, err).into()),
+        };
+
+        match action {
+            Action::Create => {
+                prepare_helm_chart_directory(self, target, event_details.clone(), logger)?;
+                // Dry runs go through the same checks of the chart resources as deployments
+                if !self.is_cluster_wide_resources_allowed() || target.environment.admission_policies.is_some() {
+                    let template = render_helm_chart(self, target, event_details.clone(), logger)?;
+                    check_resources_are_allowed_to_install(self, target, &template, event_details.clone(), logger)?;
+                    check_resources_comply_with_admission_policies(target, &template, event_details.clone(), logger)?;
+                }
+
+                let args = self.helm_template_arguments().collect::<Vec<_>>();
+                let mut diff = vec![];
+                target
+                    .helm
+                    .upgrade_diff_raw(
+                        self.helm_release_name(),
+                        self.chart_workspace_directory(),
+                        target.environment.namespace(),
+                        &args.iter().map(|x| x.as_ref()).collect::<Vec<_>>(),
+                        &[],
+                        &CommandKiller::from(self.helm_timeout(), target.abort),
+                        &mut |line| diff.push(line),
+                    )
+                    .map_err(|err| (event_details.clone(), err))?;
+
+                let change = match (release_exists, diff.iter().all(|line| line.trim().is_empty())) {
+                    (false, _) => ServiceChange::Create,
+                    (true, true) => ServiceChange::Unchanged,
+                    (true, false) => ServiceChange::Update,
+                };
+                Ok(ServiceDiff::new(change, diff))
+            }
+            Action::Pause | Action::Delete | Action::Restart if !release_exists => {
+                Ok(ServiceDiff::new(ServiceChange::Unchanged, vec![]))
+            }
+            Action::Pause | Action::Delete | Action::Restart => Ok(ServiceDiff::from_action(action)),
+        }
+    }
 }
 
 fn write_helm_value_with_replacement<'a>(
@@ -482,8 +543,10 @@ fn prepare_helm_chart_directory<T: CloudProvider>(
             let git_creds =
                 get_credentials().map_err(|e| to_error(format!("Cannot get git credentials due to {e}")))?;
 
-            git::clone_at_commit(git_url, commit_id, &tmpdir, &git_credentials_callback(&git_creds, ssh_keys))
-                .map_err(|e| to_error(format!("Cannot clone helm chart git repository due to {e}")))?;
+            // leases are released before tmpdir is deleted, at the end of the scope
+            let _mirror_leases =
+                git::clone_at_commit(git_url, commit_id, &tmpdir, &git_credentials_callback(&git_creds, ssh_keys))
+                    .map_err(|e| to_error(format!("Cannot clone helm chart git repository due to {e}")))?;
 
             fs::rename(tmpdir.path().join(root_path), this.chart_workspace_directory())
                 .map_err(|e| to_error(format!("Cannot move helm chart directory due to {e}")))?;
@@ -548,8 +611,10 @@ fn prepare_helm_chart_directory<T: CloudProvider>(
             let git_creds =
                 get_credentials().map_err(|e| to_error(format!("Cannot get git credentials due to {e}")))?;
 
-            git::clone_at_commit(git_url, commit_id, &tmpdir, &git_credentials_callback(&git_creds, ssh_keys))
-                .map_err(|e| to_error(format!("Cannot clone helm values git repository due to {e}")))?;
+            // leases are released before tmpdir is deleted, at the end of the scope
+            let _mirror_leases =
+                git::clone_at_commit(git_url, commit_id, &tmpdir, &git_credentials_callback(&git_creds, ssh_keys))
+                    .map_err(|e| to_error(format!("Cannot clone helm values git repository due to {e}")))?;
 
             for value in values_path {
                 let Some(filename) = value.file_name() else {
@@ -589,6 +654,7 @@ fn prepare_helm_chart_directory<T: CloudProvider>(
 fn check_resources_are_allowed_to_install<T: CloudProvider>(
     this: &HelmChart<T>,
     target: &DeploymentTarget,
+    template: &str,
     event_details: EventDetails,
     logger: &EnvProgressLogger,
 ) -> Result<(), Box<EngineError>> {
@@ -597,21 +663,7 @@ fn check_resources_are_allowed_to_install<T: CloudProvider>(
     }
 
     logger.info("🔬 Checking deployed resources do not cross namespace boundary".to_string());
-    let template_args: Vec<_> = this.helm_template_arguments().collect();
-    let template = target
-        .helm
-        .template_raw(
-            this.helm_release_name(),
-            this.chart_workspace_directory(),
-            target.environment.namespace(),
-            &template_args.iter().map(|x| x.as_ref()).collect::<Vec<_>>(),
-            &[],
-            &CommandKiller::from(HELM_CHART_DOWNLOAD_TIMEOUT, target.abort),
-            &mut |line| logger.warning(line),
-        )
-        .map_err(|e| (event_details.clone(), e))?;
-
-    for document in serde_yaml::Deserializer::from_str(&template) {
+    for document in serde_yaml::Deserializer::from_str(template) {
         let kube_obj: PartialObjectMeta<()> = PartialObjectMeta::deserialize(document).map_err(|err| {
             error!("Cannot deserialize yaml into kube resource {:?}", err);
             (
@@ -639,6 +691,43 @@ fn check_resources_are_allowed_to_install<T: CloudProvider>(
     Ok(())
 }
 
+fn render_helm_chart<T: CloudProvider>(
+    this: &HelmChart<T>,
+    target: &DeploymentTarget,
+    event_details: EventDetails,
+    logger: &EnvProgressLogger,
+) -> Result<String, Box<EngineError>> {
+    let template_args: Vec<_> = this.helm_template_arguments().collect();
+    let template = target
+        .helm
+        .template_raw(
+            this.helm_release_name(),
+            this.chart_workspace_directory(),
+            target.environment.namespace(),
+            &template_args.iter().map(|x| x.as_ref()).collect::<Vec<_>>(),
+            &[],
+            &CommandKiller::from(HELM_CHART_DOWNLOAD_TIMEOUT, target.abort),
+            &mut |line| logger.warning(line),
+        )
+        .map_err(|e| (event_details, e))?;
+
+    Ok(template)
+}
+
+fn check_resources_comply_with_admission_policies(
+    target: &DeploymentTarget,
+    template: &str,
+    event_details: EventDetails,
+    logger: &EnvProgressLogger,
+) -> Result<(), Box<EngineError>> {
+    if target.environment.admission_policies.is_none() {
+        return Ok(());
+    }
+
+    logger.info("🔬 Checking deployed resources comply with the organization admission policies".to_string());
+    check_manifests_comply_with_admission_policies(target, template, &event_details)
+}
+
 // * Cannot install CRDS
 // * Cannot install in another namespaces
 // * Cannot install cluster wide resources (i.e: ClusterIssuer)
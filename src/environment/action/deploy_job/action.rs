@@ -1,9 +1,13 @@
-use crate::environment::action::DeploymentAction;
 use crate::environment::action::deploy_helm::HelmDeployment;
 use crate::environment::action::deploy_job::common::{mk_deploy_post_run, mk_deploy_pre_run};
 use crate::environment::action::deploy_job::cronjob::run_cronjob;
 use crate::environment::action::deploy_job::job::mk_deploy_job_run;
+use crate::environment::action::deploy_job::scaled_job::run_scaled_job;
+use crate::environment::action::dry_run::ServiceDiff;
+use crate::environment::action::pause_service::PauseServiceAction;
 use crate::environment::action::utils::{KubeObjectKind, delete_cached_image, get_last_deployed_image};
+use crate::environment::action::{DeploymentAction, K8sResourceType};
+use crate::environment::models::autoscaling::check_event_driven_autoscaling_is_supported;
 use crate::environment::models::job::{ImageSource, Job, JobService};
 use crate::environment::models::types::{CloudProvider, ToTeraContext};
 use crate::environment::report::job::reporter::JobDeploymentReporter;
@@ -14,9 +18,11 @@ use crate::events::{EnvironmentStep, EventDetails, Stage};
 use crate::helm::{ChartInfo, HelmChartNamespaces};
 use crate::infrastructure::models::cloud_provider::DeploymentTarget;
 use crate::infrastructure::models::cloud_provider::service::{Action, Service};
+use crate::io_models::autoscaling::Scaler;
 use crate::io_models::job::JobSchedule;
 use crate::runtime::block_on;
 use std::path::PathBuf;
+use std::time::Duration;
 
 pub(super) struct TaskContext {
     pub last_deployed_image: Option<String>,
@@ -56,6 +62,28 @@ where
 
                 execute_long_deployment(JobDeploymentReporter::new(self, target, Action::Create), task)
             }
+            JobSchedule::Event { scalers, .. } => {
+                check_event_driven_autoscaling_is_supported(target, scalers, event_details.clone())?;
+                if scalers
+                    .iter()
+                    .any(|scaler| matches!(scaler, Scaler::HttpRequestRate { .. }))
+                {
+                    return Err(Box::new(EngineError::new_invalid_engine_payload(
+                        event_details,
+                        "Http request rate scaler cannot be used to trigger a job",
+                        None,
+                    )));
+                }
+
+                let (pre_run, run, post_run) = run_scaled_job(self, target, &event_details);
+                let task = DeploymentTaskImpl {
+                    pre_run: &pre_run,
+                    run: &run,
+                    post_run_success: &post_run,
+                };
+
+                execute_long_deployment(JobDeploymentReporter::new(self, target, Action::Create), task)
+            }
             JobSchedule::OnPause { .. } | JobSchedule::OnDelete { .. } => {
                 let job_reporter = JobDeploymentReporter::new(self, target, Action::Create);
                 execute_long_deployment(job_reporter, |_logger: &EnvProgressLogger| -> Result<(), Box<EngineError>> {
@@ -77,6 +105,20 @@ where
                 };
                 execute_long_deployment(JobDeploymentReporter::new(self, target, Action::Pause), task)
             }
+            JobSchedule::Event { .. } => {
+                let pause_service = PauseServiceAction::new_with_resource_type(
+                    self.kube_label_selector(),
+                    K8sResourceType::ScaledJob,
+                    Duration::from_secs(5 * 60),
+                    event_details.clone(),
+                    false,
+                    false,
+                );
+                let job_reporter = JobDeploymentReporter::new(self, target, Action::Pause);
+                execute_long_deployment(job_reporter, |_logger: &EnvProgressLogger| -> Result<(), Box<EngineError>> {
+                    pause_service.on_pause(target)
+                })
+            }
             JobSchedule::OnPause { .. } => {
                 let event_details = &event_details;
                 let pre_run = mk_deploy_pre_run(self, target, event_details);
@@ -120,7 +162,10 @@ where
                     task,
                 )
             }
-            JobSchedule::Cron { .. } | JobSchedule::OnStart { .. } | JobSchedule::OnPause { .. } => Ok(()),
+            JobSchedule::Cron { .. }
+            | JobSchedule::OnStart { .. }
+            | JobSchedule::OnPause { .. }
+            | JobSchedule::Event { .. } => Ok(()),
         }?;
 
         let (pre_run, run, post_run) = delete_job(self, target, &event_details);
@@ -141,6 +186,32 @@ where
             command_error,
         )))
     }
+
+    fn on_dry_run(
+        &self,
+        target: &DeploymentTarget,
+        action: Action,
+        logger: &EnvProgressLogger,
+    ) -> Result<ServiceDiff, Box<EngineError>> {
+        let event_details = self.get_event_details(Stage::Environment(action.to_environment_step()));
+        let chart = ChartInfo {
+            name: self.helm_release_name(),
+            path: self.workspace_directory().to_string(),
+            namespace: HelmChartNamespaces::Custom(target.environment.namespace().to_string()),
+            timeout_in_seconds: self.startup_timeout().as_secs() as i64,
+            k8s_selector: Some(self.kube_label_selector()),
+            ..Default::default()
+        };
+        let helm = HelmDeployment::new(
+            event_details,
+            self.to_tera_context(target)?,
+            PathBuf::from(self.helm_chart_dir()),
+            None,
+            chart,
+        );
+
+        helm.on_dry_run(target, action, logger)
+    }
 }
 
 pub(super) fn delete_job<'a, T: CloudProvider>(
@@ -1,6 +1,6 @@
 use crate::environment::action::deploy_job::action::{JobPostRun, JobPreRun, TaskContext};
 use crate::environment::action::utils::{
-    KubeObjectKind, delete_cached_image, get_last_deployed_image, mirror_image_if_necessary,
+    KubeObjectKind, delete_cached_image, get_deployed_image_source, get_last_deployed_image, mirror_image_if_necessary,
 };
 use crate::environment::models::job::{ImageSource, Job, JobService};
 use crate::environment::models::types::CloudProvider;
@@ -46,6 +46,15 @@ where
             },
             target.environment.namespace(),
         ));
+        if let Some(source) = last_image
+            .as_deref()
+            .and_then(|image| get_deployed_image_source(target.docker, image))
+        {
+            logger.info(format!(
+                "📌 Currently deployed version was built from {} at commit {}",
+                source.url, source.revision
+            ));
+        }
 
         Ok(TaskContext {
             last_deployed_image: last_image,
@@ -106,7 +106,7 @@ where
         let max_execution_duration = Duration::from_secs(60) + job.max_duration * (job.max_nb_restart + 1);
         let job = block_on(super::job::await_job_to_complete(
             &job_name,
-            max_execution_duration,
+            &super::job::ExecutionDeadline::from_now(max_execution_duration),
             target.environment.namespace(),
             target.kube.client(),
             target.abort,
@@ -2,7 +2,7 @@ use crate::environment::action::DeploymentAction;
 use crate::environment::action::deploy_helm::HelmDeployment;
 use crate::environment::action::deploy_job::action::{JobRun, TaskContext};
 use crate::environment::action::deploy_job::job_output::{
-    JobOutputSerializationError, JobOutputVariable, serialize_job_output,
+    JobOutputSerializationError, JobOutputVariable, merge_job_outputs, serialize_job_output,
 };
 use crate::environment::models::abort::Abort;
 use crate::environment::models::job::{Job, JobService};
@@ -17,12 +17,13 @@ use crate::infrastructure::models::cloud_provider::service::Service;
 use crate::runtime::block_on;
 use anyhow::{Context, anyhow};
 use futures::pin_mut;
+use itertools::Itertools;
 use k8s_openapi::api::batch::v1::Job as K8sJob;
 use k8s_openapi::api::core::v1::Pod;
 use kube::Api;
 use kube::api::{AttachParams, DeleteParams, ListParams};
 use kube::runtime::wait::await_condition;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::pin::Pin;
 use std::time::Duration;
@@ -91,54 +92,90 @@ where
     helm.on_create(target)?;
 
     let job_name = job.kube_name();
-    let max_execution_duration = Duration::from_secs(60) + job.max_duration * (job.max_nb_restart + 1);
-    let pod = block_on(await_job_pod_to_terminate(
-        job.kube_name(),
-        max_execution_duration,
-        target.environment.namespace(),
-        target.kube.client(),
-        target.abort,
-    ));
-    let pod_name = match pod {
-        Ok(pod) => pod.metadata.name.unwrap_or_default(),
+    // Pods run by waves of `parallelism`, and every restart of a failed pod (up to the backoff limit) adds a run.
+    // A single deadline is shared by all the waits of the job.
+    let nb_pod_runs = job.completions().div_ceil(job.parallelism.max(1)) + job.max_nb_restart;
+    let execution_deadline = ExecutionDeadline::from_now(Duration::from_secs(60) + job.max_duration * nb_pod_runs);
+
+    // Every pod of the job waits for us to retrieve its output before terminating.
+    // The job is complete once `completions` of them succeeded, whatever the completion mode is.
+    let job_outputs = collect_job_pods_outputs(
+        job.completions(),
+        |processed_pods| {
+            block_on(await_next_job_pod_to_terminate(
+                job_name,
+                &execution_deadline,
+                target.environment.namespace(),
+                target.kube.client(),
+                target.abort,
+                processed_pods,
+            ))
+            .map(|pod| pod.metadata.name.unwrap_or_default())
+        },
+        |pod_name| {
+            info!("Targeting job pod name: {}", pod_name);
+
+            // Fetch Qovery Json output if any
+            match block_on(retrieve_output_and_terminate_pod(
+                target.kube.client(),
+                target.environment.namespace(),
+                pod_name,
+                job.output_variable_validation_pattern.as_str(),
+            )) {
+                Ok(output) => output,
+                Err(err) => {
+                    logger.log(EngineEvent::Warning(
+                        event_details.clone(),
+                        EventMessage::from(if err.to_string().contains("Validation error") {
+                            EngineError::new_invalid_job_output_variable_validation_failed(
+                                event_details.clone(),
+                                err.to_string(),
+                            )
+                        } else {
+                            EngineError::new_invalid_job_output_cannot_be_serialized(
+                                event_details.clone(),
+                                err.to_string(),
+                            )
+                        }),
+                    ));
+                    None
+                }
+            }
+        },
+    );
+    let job_outputs = match job_outputs {
+        Ok(job_outputs) => job_outputs,
         Err(JobRunError::Aborted) => {
             let _ = block_on(kill_job(target.kube.client(), target.environment.namespace(), job_name));
             return Err(Box::new(EngineError::new_task_cancellation_requested(event_details.clone())));
         }
         Err(err) => return Err(Box::new(EngineError::new_job_error(event_details.clone(), err.to_string()))),
     };
-    info!("Targeting job pod name: {}", pod_name);
 
-    // Fetch Qovery Json output if any, and transmit it to the core for next deployment stage
-    match block_on(retrieve_output_and_terminate_pod(
-        target.kube.client(),
-        target.environment.namespace(),
-        &pod_name,
-        job.output_variable_validation_pattern.as_str(),
-    )) {
-        Ok(None) => {}
-        Ok(Some(output)) => logger.core_configuration_for_job(
-            "Job output succeeded. Environment variables will be synchronized.".to_string(),
-            serde_json::to_string(&output).unwrap_or_else(|_| "{}".to_string()),
-        ),
-        Err(err) => {
+    // Transmit the output of all the pods to the core for next deployment stage
+    if !job_outputs.is_empty() {
+        let (output, dropped_variables) = merge_job_outputs(job_outputs);
+        if !dropped_variables.is_empty() {
             logger.log(EngineEvent::Warning(
                 event_details.clone(),
-                EventMessage::from(if err.to_string().contains("Validation error") {
-                    EngineError::new_invalid_job_output_variable_validation_failed(
-                        event_details.clone(),
-                        err.to_string(),
-                    )
-                } else {
-                    EngineError::new_invalid_job_output_cannot_be_serialized(event_details.clone(), err.to_string())
-                }),
+                EventMessage::from(EngineError::new_invalid_job_output_variable_validation_failed(
+                    event_details.clone(),
+                    format!(
+                        "Job output variables {} have different values across the pods of the job, they are ignored",
+                        dropped_variables.join(", ")
+                    ),
+                )),
             ));
         }
+        logger.core_configuration_for_job(
+            "Job output succeeded. Environment variables will be synchronized.".to_string(),
+            serde_json::to_string(&output).unwrap_or_else(|_| "{}".to_string()),
+        );
     }
 
     let job = block_on(await_job_to_complete(
         job.kube_name(),
-        max_execution_duration,
+        &execution_deadline,
         target.environment.namespace(),
         target.kube.client(),
         target.abort,
@@ -156,6 +193,41 @@ where
     Ok(state)
 }
 
+/// Waits for `completions` pods of the job to terminate, each pod being processed once, and returns their outputs
+fn collect_job_pods_outputs(
+    completions: u32,
+    mut await_next_pod_to_terminate: impl FnMut(&HashSet<String>) -> Result<String, JobRunError>,
+    mut retrieve_pod_output: impl FnMut(&str) -> Option<HashMap<String, JobOutputVariable>>,
+) -> Result<Vec<HashMap<String, JobOutputVariable>>, JobRunError> {
+    let mut processed_pods: HashSet<String> = HashSet::with_capacity(completions as usize);
+    let mut job_outputs: Vec<HashMap<String, JobOutputVariable>> = Vec::with_capacity(completions as usize);
+    while processed_pods.len() < completions as usize {
+        let pod_name = await_next_pod_to_terminate(&processed_pods)?;
+        if let Some(output) = retrieve_pod_output(&pod_name) {
+            job_outputs.push(output);
+        }
+        processed_pods.insert(pod_name);
+    }
+
+    Ok(job_outputs)
+}
+
+/// Instant by which a job must have terminated, shared by all the waits of the job
+#[derive(Clone, Copy, Debug)]
+pub struct ExecutionDeadline {
+    instant: tokio::time::Instant,
+    max_duration: Duration,
+}
+
+impl ExecutionDeadline {
+    pub fn from_now(max_duration: Duration) -> Self {
+        ExecutionDeadline {
+            instant: tokio::time::Instant::now() + max_duration,
+            max_duration,
+        }
+    }
+}
+
 pub async fn retrieve_output_and_terminate_pod(
     kube_client: kube::Client,
     namespace: &str,
@@ -225,13 +297,14 @@ pub async fn kill_job(kube_client: kube::Client, namespace: &str, job_name: &str
 
 pub async fn await_job_to_complete(
     job_name: &str,
-    max_execution_duration: Duration,
+    execution_deadline: &ExecutionDeadline,
     namespace: &str,
     kube_client: kube::Client,
     abort_handle: impl Abort,
 ) -> Result<K8sJob, JobRunError> {
     let job_api: Api<K8sJob> = Api::namespaced(kube_client.clone(), namespace);
-    let execution_deadline = tokio::time::sleep_until(tokio::time::Instant::now() + max_execution_duration);
+    let max_execution_duration = execution_deadline.max_duration;
+    let execution_deadline = tokio::time::sleep_until(execution_deadline.instant);
     pin_mut!(execution_deadline);
 
     let should_force_cancel = async || {
@@ -285,12 +358,13 @@ pub async fn await_job_pod_to_start(
     let should_skip_pod = |_: &Pod| false;
     await_job_pod_to(
         job_name,
-        max_execution_duration,
+        &ExecutionDeadline::from_now(max_execution_duration),
         namespace,
         kube_client,
         abort_handle,
         should_process_pod,
         should_skip_pod,
+        &HashSet::new(),
     )
     .await
 }
@@ -301,6 +375,27 @@ pub async fn await_job_pod_to_terminate(
     namespace: &str,
     kube_client: kube::Client,
     abort_handle: impl Abort,
+) -> Result<Pod, JobRunError> {
+    await_next_job_pod_to_terminate(
+        job_name,
+        &ExecutionDeadline::from_now(max_execution_duration),
+        namespace,
+        kube_client,
+        abort_handle,
+        &HashSet::new(),
+    )
+    .await
+}
+
+/// Same as `await_job_pod_to_terminate`, for jobs running several pods. `processed_pods` are the ones already handled.
+/// The deadline is the one of the whole job, not of the pod.
+pub async fn await_next_job_pod_to_terminate(
+    job_name: &str,
+    execution_deadline: &ExecutionDeadline,
+    namespace: &str,
+    kube_client: kube::Client,
+    abort_handle: impl Abort,
+    processed_pods: &HashSet<String>,
 ) -> Result<Pod, JobRunError> {
     let should_process_pod = |pod: Option<&Pod>| -> bool {
         // if the pod is not present anymore, we want to unstuck the loop
@@ -322,29 +417,32 @@ pub async fn await_job_pod_to_terminate(
 
     await_job_pod_to(
         job_name,
-        max_execution_duration,
+        execution_deadline,
         namespace,
         kube_client,
         abort_handle,
         should_process_pod,
         should_skip_pod,
+        processed_pods,
     )
     .await
 }
 
 async fn await_job_pod_to(
     job_name: &str,
-    max_execution_duration: Duration,
+    execution_deadline: &ExecutionDeadline,
     namespace: &str,
     kube_client: kube::Client,
     abort_handle: impl Abort,
     should_process_pod: impl Fn(Option<&Pod>) -> bool,
     should_skip_pod: impl Fn(&Pod) -> bool,
+    processed_pods: &HashSet<String>,
 ) -> Result<Pod, JobRunError> {
     let job_api: Api<K8sJob> = Api::namespaced(kube_client.clone(), namespace);
     let pod_api: Api<Pod> = Api::namespaced(kube_client.clone(), namespace);
     let pod_selector = format!("job-name={job_name}");
-    let execution_deadline = tokio::time::sleep_until(tokio::time::Instant::now() + max_execution_duration);
+    let max_execution_duration = execution_deadline.max_duration;
+    let execution_deadline = tokio::time::sleep_until(execution_deadline.instant);
     pin_mut!(execution_deadline);
 
     let should_force_cancel = async || {
@@ -393,7 +491,7 @@ async fn await_job_pod_to(
         }
 
         // fetch the correct pod
-        let Ok(pod_name) = get_active_pod_of_job(pod_api.clone(), &pod_selector).await else {
+        let Ok(pod_name) = get_active_pod_of_job(pod_api.clone(), &pod_selector, processed_pods).await else {
             continue;
         };
 
@@ -495,33 +593,111 @@ fn user_job_terminated_exit_code(pod: &Pod) -> Option<u32> {
         .map(|st| st.exit_code as u32)
 }
 
-async fn get_active_pod_of_job(pod_api: Api<Pod>, selector: &str) -> anyhow::Result<String> {
+async fn get_active_pod_of_job(
+    pod_api: Api<Pod>,
+    selector: &str,
+    processed_pods: &HashSet<String>,
+) -> anyhow::Result<String> {
     let pods = pod_api.list(&ListParams::default().labels(selector)).await?;
     if pods.items.is_empty() {
         return Err(anyhow!("No pod found with this label selector {}", selector));
     }
 
-    // Take only pods that are running or pending
-    let mut active_job_pods: Vec<String> = pods
+    // Take only pods that are running or pending, and not already processed
+    let active_job_pods: Vec<Pod> = pods
         .items
         .into_iter()
-        .filter_map(|pod| {
-            if is_pod_running_or_pending(&pod) {
-                Some(pod.metadata.name?)
-            } else {
-                None
-            }
+        .filter(|pod| {
+            is_pod_running_or_pending(pod)
+                && pod
+                    .metadata
+                    .name
+                    .as_ref()
+                    .is_some_and(|name| !processed_pods.contains(name))
         })
+        .sorted_by(|a, b| a.metadata.name.cmp(&b.metadata.name))
         .collect();
 
-    match *active_job_pods.as_slice() {
-        [] => Err(anyhow!("No pod found with this label selector {}", selector)),
-        [_] => Ok(()),
-        _ => Err(anyhow!(
-            "More than one active pod found with this label selector {}. It should not be possible",
-            selector
-        )),
-    }?;
+    // With parallelism, several pods can be active at the same time.
+    // Prefer the ones whose user code already succeeded, they are waiting for us to retrieve their output
+    active_job_pods
+        .iter()
+        .find(|pod| user_job_terminated_exit_code(pod) == Some(0))
+        .or_else(|| active_job_pods.first())
+        .and_then(|pod| pod.metadata.name.clone())
+        .ok_or_else(|| anyhow!("No pod found with this label selector {}", selector))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(key: &str, value: &str) -> HashMap<String, JobOutputVariable> {
+        HashMap::from([(
+            key.to_string(),
+            JobOutputVariable {
+                value: value.to_string(),
+                sensitive: false,
+                description: "".to_string(),
+            },
+        )])
+    }
 
-    Ok(active_job_pods.remove(0))
+    #[test]
+    fn test_collect_job_pods_outputs_processes_each_pod_once() {
+        // setup:
+        // pod-1 failed and has been replaced by pod-3, pod-0 keeps being reported until it is processed
+        let terminated_pods = ["pod-0", "pod-2", "pod-0", "pod-3"];
+        let mut awaited_pods = vec![];
+        let mut retrieved_pods = vec![];
+
+        // execute:
+        let outputs = collect_job_pods_outputs(
+            3,
+            |processed_pods| {
+                awaited_pods.push(processed_pods.len());
+                let pod_name = terminated_pods
+                    .iter()
+                    .find(|pod_name| !processed_pods.contains(**pod_name))
+                    .ok_or_else(|| JobRunError::Unknown(anyhow!("no more pods")))?;
+                Ok(pod_name.to_string())
+            },
+            |pod_name| {
+                retrieved_pods.push(pod_name.to_string());
+                match pod_name {
+                    "pod-2" => None,
+                    _ => Some(output("POD_NAME", pod_name)),
+                }
+            },
+        );
+
+        // verify:
+        let outputs = outputs.unwrap();
+        assert_eq!(awaited_pods, vec![0, 1, 2]);
+        assert_eq!(retrieved_pods, vec!["pod-0", "pod-2", "pod-3"]);
+        assert_eq!(outputs, vec![output("POD_NAME", "pod-0"), output("POD_NAME", "pod-3")]);
+    }
+
+    #[test]
+    fn test_collect_job_pods_outputs_stops_on_error() {
+        // setup:
+        let mut retrieved_pods = vec![];
+
+        // execute:
+        let outputs = collect_job_pods_outputs(
+            3,
+            |processed_pods| match processed_pods.len() {
+                0 => Ok("pod-0".to_string()),
+                _ => Err(JobRunError::Aborted),
+            },
+            |pod_name| {
+                retrieved_pods.push(pod_name.to_string());
+                Some(output("POD_NAME", pod_name))
+            },
+        );
+
+        // verify:
+        assert!(matches!(outputs, Err(JobRunError::Aborted)));
+        assert_eq!(retrieved_pods, vec!["pod-0"]);
+    }
 }
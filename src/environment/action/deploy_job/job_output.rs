@@ -1,6 +1,7 @@
 use serde_derive::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeSet, HashMap};
 
 // Used to validate the job json output format with serde
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
@@ -87,6 +88,36 @@ pub fn serialize_job_output(
     Ok(job_output_variables)
 }
 
+/// Merges the outputs of the pods of a job into a single one, and returns the variables dropped from it.
+/// A variable output by several pods with different values is dropped, as there is no way to tell which one to keep.
+pub fn merge_job_outputs(
+    outputs: impl IntoIterator<Item = HashMap<String, JobOutputVariable>>,
+) -> (HashMap<String, JobOutputVariable>, Vec<String>) {
+    let mut job_output_variables: HashMap<String, JobOutputVariable> = HashMap::new();
+    let mut conflicting_variables: BTreeSet<String> = BTreeSet::new();
+    for output in outputs {
+        for (key, variable) in output {
+            match job_output_variables.entry(key) {
+                Entry::Vacant(entry) => {
+                    entry.insert(variable);
+                }
+                Entry::Occupied(mut entry) if entry.get().value == variable.value => {
+                    // a single pod flagging the variable as sensitive is enough to hide it
+                    entry.get_mut().sensitive |= variable.sensitive;
+                }
+                Entry::Occupied(entry) => {
+                    conflicting_variables.insert(entry.key().clone());
+                }
+            }
+        }
+    }
+
+    for key in &conflicting_variables {
+        job_output_variables.remove(key);
+    }
+    (job_output_variables, conflicting_variables.into_iter().collect())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -203,4 +234,50 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn should_merge_job_outputs_of_all_pods() {
+        // given
+        let shard_0 = serialize_job_output(
+            r#"{"SHARD_0_ROWS": {"value": 10}, "BUCKET": {"value": "s3://results", "sensitive": false}}"#.as_bytes(),
+            "^[a-zA-Z_][a-zA-Z0-9_]*$",
+        )
+        .unwrap();
+        let shard_1 = serialize_job_output(
+            r#"{"SHARD_1_ROWS": {"value": 12}, "BUCKET": {"value": "s3://results", "sensitive": true}}"#.as_bytes(),
+            "^[a-zA-Z_][a-zA-Z0-9_]*$",
+        )
+        .unwrap();
+
+        // when
+        let (hashmap, dropped_variables) = merge_job_outputs([shard_0.clone(), shard_1]);
+
+        // then
+        assert!(dropped_variables.is_empty());
+        assert_eq!(hashmap.len(), 3);
+        assert_eq!(hashmap.get("SHARD_0_ROWS").unwrap().value, "10");
+        assert_eq!(hashmap.get("SHARD_1_ROWS").unwrap().value, "12");
+        assert_eq!(
+            hashmap.get("BUCKET").unwrap(),
+            &JobOutputVariable {
+                value: "s3://results".to_string(),
+                sensitive: true,
+                description: "".to_string(),
+            }
+        );
+
+        // when
+        let conflicting_shard = serialize_job_output(
+            r#"{"BUCKET": {"value": "s3://other-results"}, "SHARD_2_ROWS": {"value": 8}}"#.as_bytes(),
+            "^[a-zA-Z_][a-zA-Z0-9_]*$",
+        )
+        .unwrap();
+        let (hashmap, dropped_variables) = merge_job_outputs([shard_0.clone(), conflicting_shard, shard_0]);
+
+        // then
+        assert_eq!(dropped_variables, vec!["BUCKET".to_string()]);
+        assert_eq!(hashmap.len(), 2);
+        assert_eq!(hashmap.get("SHARD_0_ROWS").unwrap().value, "10");
+        assert_eq!(hashmap.get("SHARD_2_ROWS").unwrap().value, "8");
+    }
 }
@@ -3,3 +3,4 @@ mod common;
 mod cronjob;
 pub(super) mod job;
 pub mod job_output;
+mod scaled_job;
@@ -0,0 +1,63 @@
+use crate::environment::action::deploy_helm::HelmDeployment;
+use crate::environment::action::deploy_job::action::{JobPostRun, JobPreRun, JobRun, TaskContext};
+use crate::environment::action::deploy_job::common::{mk_deploy_post_run, mk_deploy_pre_run};
+use crate::environment::action::pause_service::PauseServiceAction;
+use crate::environment::action::{DeploymentAction, K8sResourceType};
+use crate::environment::models::job::{Job, JobService};
+use crate::environment::models::types::{CloudProvider, ToTeraContext};
+use crate::environment::report::logger::EnvProgressLogger;
+use crate::errors::EngineError;
+use crate::events::EventDetails;
+use crate::helm::{ChartInfo, HelmChartNamespaces};
+use crate::infrastructure::models::cloud_provider::DeploymentTarget;
+use crate::infrastructure::models::cloud_provider::service::Service;
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub(super) fn run_scaled_job<'a, T: CloudProvider>(
+    job: &'a Job<T>,
+    target: &'a DeploymentTarget,
+    event_details: &'a EventDetails,
+) -> (JobPreRun<'a>, JobRun<'a>, JobPostRun<'a>)
+where
+    Job<T>: JobService,
+{
+    let task = move |_logger: &EnvProgressLogger, state: TaskContext| -> Result<TaskContext, Box<EngineError>> {
+        let chart = ChartInfo {
+            name: job.helm_release_name(),
+            path: job.workspace_directory().to_string(),
+            namespace: HelmChartNamespaces::Custom(target.environment.namespace().to_string()),
+            timeout_in_seconds: job.startup_timeout().as_secs() as i64,
+            k8s_selector: Some(job.kube_label_selector()),
+            ..Default::default()
+        };
+
+        let helm = HelmDeployment::new(
+            event_details.clone(),
+            job.to_tera_context(target)?,
+            PathBuf::from(job.helm_chart_dir()),
+            None,
+            chart,
+        );
+
+        // Jobs are spawned by KEDA according to the ScaledJob triggers, there is nothing to wait for
+        helm.on_create(target)?;
+
+        // helm keeps annotations it doesn't manage, so the one set when the environment was paused must be removed
+        PauseServiceAction::new_with_resource_type(
+            job.kube_label_selector(),
+            K8sResourceType::ScaledJob,
+            Duration::from_secs(5 * 60),
+            event_details.clone(),
+            false,
+            false,
+        )
+        .unpause_if_needed(target)?;
+
+        Ok(state)
+    };
+
+    let pre_run = mk_deploy_pre_run(job, target, event_details);
+    let post_run = mk_deploy_post_run(job, target);
+    (pre_run, Box::new(task), post_run)
+}
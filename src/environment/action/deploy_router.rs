@@ -1,6 +1,7 @@
 use crate::environment::action::DeploymentAction;
 use crate::environment::action::check_dns::CheckDnsForDomains;
 use crate::environment::action::deploy_helm::HelmDeployment;
+use crate::environment::action::dry_run::ServiceDiff;
 use crate::environment::models::router::Router;
 use crate::environment::models::types::{CloudProvider, ToTeraContext};
 use crate::environment::report::router::reporter::RouterDeploymentReporter;
@@ -107,4 +108,28 @@ where
             |_logger: &EnvProgressLogger| -> Result<(), Box<EngineError>> { Ok(()) },
         )
     }
+
+    fn on_dry_run(
+        &self,
+        target: &DeploymentTarget,
+        action: Action,
+        logger: &EnvProgressLogger,
+    ) -> Result<ServiceDiff, Box<EngineError>> {
+        let event_details = self.get_event_details(Stage::Environment(action.to_environment_step()));
+        let chart = ChartInfo {
+            name: self.helm_release_name(),
+            path: self.workspace_directory().to_string(),
+            namespace: HelmChartNamespaces::Custom(target.environment.namespace().to_string()),
+            ..Default::default()
+        };
+        let helm = HelmDeployment::new(
+            event_details,
+            self.to_tera_context(target)?,
+            PathBuf::from(self.helm_chart_dir()),
+            None,
+            chart,
+        );
+
+        helm.on_dry_run(target, action, logger)
+    }
 }
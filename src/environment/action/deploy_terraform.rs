@@ -2,8 +2,11 @@ use crate::cmd;
 use crate::cmd::kubectl::kubectl_exec_delete_secret;
 use crate::cmd::terraform_validators::TerraformValidators;
 use crate::environment::action::DeploymentAction;
+use crate::environment::action::dry_run::ServiceDiff;
+use crate::environment::report::logger::EnvProgressLogger;
 use crate::errors::{CommandError, EngineError};
 use crate::events::{EnvironmentStep, EventDetails, Stage};
+use crate::infrastructure::models::cloud_provider::service::Action;
 use crate::infrastructure::models::cloud_provider::{CloudProvider, DeploymentTarget};
 use crate::infrastructure::models::kubernetes::Kubernetes;
 use crate::template::generate_and_copy_all_files_into_dir;
@@ -147,4 +150,28 @@ impl DeploymentAction for TerraformDeployment {
             command_error,
         )))
     }
+
+    fn on_dry_run(
+        &self,
+        target: &DeploymentTarget,
+        action: Action,
+        _logger: &EnvProgressLogger,
+    ) -> Result<ServiceDiff, Box<EngineError>> {
+        if !matches!(action, Action::Create | Action::Delete) {
+            return Ok(ServiceDiff::from_action(action));
+        }
+
+        self.prepare_terraform_files()?;
+        let root_dir = self.destination_folder.to_string_lossy();
+        let envs = target.cloud_provider.credentials_environment_variables();
+        let validators = match action {
+            Action::Delete => TerraformValidators::None,
+            _ => TerraformValidators::Default,
+        };
+        let plan = cmd::terraform::terraform_init_validate(&root_dir, envs.as_slice(), &validators)
+            .and_then(|_| cmd::terraform::terraform_plan(&root_dir, envs.as_slice(), action == Action::Delete))
+            .map_err(|e| EngineError::new_terraform_error(self.event_details.clone(), e))?;
+
+        Ok(ServiceDiff::from_terraform_plan(plan.raw_std_output))
+    }
 }
@@ -1,9 +1,11 @@
 #![allow(unused_imports, unused_variables, dead_code)]
 
+use crate::cmd::helm::HelmError;
 use crate::environment::action::DeploymentAction;
 use crate::environment::action::deploy_helm::HelmDeployment;
 use crate::environment::action::deploy_job::job::JobRunError;
 use crate::environment::action::deploy_job::job_output::JobOutputSerializationError;
+use crate::environment::action::dry_run::{ServiceChange, ServiceDiff};
 use crate::environment::action::utils::{
     KubeObjectKind, delete_cached_image, get_last_deployed_image, mirror_image_if_necessary,
 };
@@ -115,6 +117,33 @@ where
             command_error,
         )))
     }
+
+    fn on_dry_run(
+        &self,
+        target: &DeploymentTarget,
+        action: Action,
+        _logger: &EnvProgressLogger,
+    ) -> Result<ServiceDiff, Box<EngineError>> {
+        // Terraform commands are executed by a job inside the cluster, so the plan cannot be computed without deploying it.
+        // We can only tell if the service already exists
+        let event_details = self.get_event_details(Stage::Environment(action.to_environment_step()));
+        let chart = ChartInfo::new_from_release_name(&self.helm_release_name(), target.environment.namespace());
+        let release_exists = match target.helm.check_release_exist(&chart, &[]) {
+            Ok(_) => true,
+            Err(HelmError::ReleaseDoesNotExist(_)) => false,
+            Err(err) => return Err(Box::new(EngineError::new_helm_error(event_details, err))),
+        };
+
+        let change = match (action, release_exists) {
+            (Action::Create, false) => ServiceChange::Create,
+            (Action::Create, true) => ServiceChange::Update,
+            (Action::Delete, true) => ServiceChange::Delete,
+            (Action::Delete, false) => ServiceChange::Unchanged,
+            (Action::Pause | Action::Restart, _) => return Ok(ServiceDiff::from_action(action)),
+        };
+
+        Ok(ServiceDiff::new(change, vec![]))
+    }
 }
 
 impl<T: CloudProvider> TerraformService<T>
@@ -236,7 +265,7 @@ where
 
         let job = block_on(crate::environment::action::deploy_job::job::await_job_to_complete(
             self.kube_name(),
-            max_execution_duration,
+            &crate::environment::action::deploy_job::job::ExecutionDeadline::from_now(max_execution_duration),
             target.environment.namespace(),
             target.kube.client(),
             target.abort,
@@ -0,0 +1,228 @@
+use crate::environment::action::DeploymentAction;
+use crate::environment::report::logger::{EnvLogger, EnvProgressLogger};
+use crate::errors::EngineError;
+use crate::events::{EngineEvent, EnvironmentStep, EventMessage, Stage};
+use crate::infrastructure::models::cloud_provider::DeploymentTarget;
+use crate::infrastructure::models::cloud_provider::service::{Action, Service};
+use crate::logger::Logger;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_derive::Serialize;
+use std::sync::Arc;
+
+/// `Plan: 1 to add, 2 to change, 0 to destroy.` line ending every terraform plan having changes
+static TERRAFORM_PLAN_SUMMARY_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"Plan: (?P<add>\d+) to add, (?P<change>\d+) to change, (?P<destroy>\d+) to destroy")
+        .expect("Invalid terraform plan summary regex")
+});
+
+/// What the deployment of a service would do
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceChange {
+    Create,
+    Update,
+    Delete,
+    Pause,
+    Restart,
+    Unchanged,
+}
+
+impl ServiceChange {
+    fn to_message(self) -> &'static str {
+        match self {
+            ServiceChange::Create => "would be created",
+            ServiceChange::Update => "would be updated",
+            ServiceChange::Delete => "would be deleted",
+            ServiceChange::Pause => "would be paused",
+            ServiceChange::Restart => "would be restarted",
+            ServiceChange::Unchanged => "would not change",
+        }
+    }
+}
+
+/// Result of a service dry run, computed without building images nor touching the cluster
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ServiceDiff {
+    pub change: ServiceChange,
+    /// Diff of the rendered manifests against the live release, or terraform plan
+    pub diff: Vec<String>,
+}
+
+impl ServiceDiff {
+    pub fn new(change: ServiceChange, diff: Vec<String>) -> ServiceDiff {
+        ServiceDiff { change, diff }
+    }
+
+    /// When the live state of the service cannot be inspected, the change can only be inferred from the action
+    pub fn from_action(action: Action) -> ServiceDiff {
+        let change = match action {
+            Action::Create => ServiceChange::Create,
+            Action::Pause => ServiceChange::Pause,
+            Action::Delete => ServiceChange::Delete,
+            Action::Restart => ServiceChange::Restart,
+        };
+
+        ServiceDiff::new(change, vec![])
+    }
+
+    pub fn from_terraform_plan(plan: Vec<String>) -> ServiceDiff {
+        let summary = plan.iter().find_map(|line| TERRAFORM_PLAN_SUMMARY_REGEX.captures(line));
+        let Some(summary) = summary else {
+            // `No changes. Your infrastructure matches the configuration.`
+            return ServiceDiff::new(ServiceChange::Unchanged, plan);
+        };
+
+        let count = |name: &str| summary[name].parse::<u32>().unwrap_or_default();
+        let change = match (count("add"), count("change"), count("destroy")) {
+            (0, 0, 0) => ServiceChange::Unchanged,
+            (0, 0, _) => ServiceChange::Delete,
+            (_, 0, 0) => ServiceChange::Create,
+            _ => ServiceChange::Update,
+        };
+
+        ServiceDiff::new(change, plan)
+    }
+}
+
+/// Computes the diff of every service of the environment and streams it as events.
+/// Nothing is built, nor applied on the cluster.
+pub fn dry_run_environment(target: &DeploymentTarget, logger: Arc<Box<dyn Logger>>) -> Result<(), Box<EngineError>> {
+    let environment = target.environment;
+    let services = std::iter::empty()
+        .chain(
+            environment
+                .databases
+                .iter()
+                .map(|s| (s.as_service(), s.as_deployment_action())),
+        )
+        .chain(
+            environment
+                .jobs
+                .iter()
+                .map(|s| (s.as_service(), s.as_deployment_action())),
+        )
+        .chain(
+            environment
+                .containers
+                .iter()
+                .map(|s| (s.as_service(), s.as_deployment_action())),
+        )
+        .chain(
+            environment
+                .applications
+                .iter()
+                .map(|s| (s.as_service(), s.as_deployment_action())),
+        )
+        .chain(
+            environment
+                .helm_charts
+                .iter()
+                .map(|s| (s.as_service(), s.as_deployment_action())),
+        )
+        .chain(
+            environment
+                .terraform_services
+                .iter()
+                .map(|s| (s.as_service(), s.as_deployment_action())),
+        )
+        .chain(
+            environment
+                .routers
+                .iter()
+                .map(|s| (s.as_service(), s.as_deployment_action())),
+        );
+
+    for (service, deployment_action) in services {
+        if target.abort.status().should_cancel() {
+            return Err(Box::new(EngineError::new_task_cancellation_requested(
+                environment.event_details().clone(),
+            )));
+        }
+
+        dry_run_service(service, deployment_action, target, logger.clone())?;
+    }
+
+    Ok(())
+}
+
+fn dry_run_service(
+    service: &dyn Service,
+    deployment_action: &dyn DeploymentAction,
+    target: &DeploymentTarget,
+    logger: Arc<Box<dyn Logger>>,
+) -> Result<(), Box<EngineError>> {
+    let action = *service.action();
+    let env_logger = EnvLogger::new(service, EnvironmentStep::DryRun, logger);
+
+    let service_diff = match deployment_action.on_dry_run(target, action, &EnvProgressLogger::new(&env_logger)) {
+        Ok(service_diff) => service_diff,
+        Err(err) => {
+            env_logger.send_error(err.clone_engine_error_with_stage(Stage::Environment(EnvironmentStep::DryRunError)));
+            return Err(err);
+        }
+    };
+
+    for line in &service_diff.diff {
+        env_logger.send_progress(line.clone());
+    }
+
+    let summary = format!(
+        "👻 Dry run: {} `{}` {}",
+        service.service_type(),
+        service.name(),
+        service_diff.change.to_message()
+    );
+    // The structured diff is kept in the event details, for the console to render it
+    env_logger.log(EngineEvent::Info(
+        service.get_event_details(Stage::Environment(EnvironmentStep::DryRun)),
+        EventMessage::new(summary, serde_json::to_string(&service_diff).ok()),
+    ));
+    env_logger.send_success(format!("✅ Dry run of {} succeeded", service.service_type()));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_service_diff_from_terraform_plan() {
+        // setup:
+        let test_cases = vec![
+            (
+                vec!["No changes. Your infrastructure matches the configuration."],
+                ServiceChange::Unchanged,
+            ),
+            (
+                vec![
+                    "  + resource \"aws_db_instance\" {",
+                    "Plan: 3 to add, 0 to change, 0 to destroy.",
+                ],
+                ServiceChange::Create,
+            ),
+            (vec!["Plan: 0 to add, 1 to change, 0 to destroy."], ServiceChange::Update),
+            (vec!["Plan: 1 to add, 0 to change, 1 to destroy."], ServiceChange::Update),
+            (vec!["Plan: 0 to add, 0 to change, 4 to destroy."], ServiceChange::Delete),
+        ];
+
+        for (plan, expected_change) in test_cases {
+            // execute:
+            let plan: Vec<String> = plan.into_iter().map(str::to_string).collect();
+            let service_diff = ServiceDiff::from_terraform_plan(plan.clone());
+
+            // verify:
+            assert_eq!(service_diff.change, expected_change, "plan: {plan:?}");
+            assert_eq!(service_diff.diff, plan);
+        }
+    }
+
+    #[test]
+    fn test_service_diff_from_action() {
+        assert_eq!(ServiceDiff::from_action(Action::Create).change, ServiceChange::Create);
+        assert_eq!(ServiceDiff::from_action(Action::Pause).change, ServiceChange::Pause);
+        assert_eq!(ServiceDiff::from_action(Action::Delete).change, ServiceChange::Delete);
+        assert_eq!(ServiceDiff::from_action(Action::Restart).change, ServiceChange::Restart);
+    }
+}
@@ -1,8 +1,12 @@
+use crate::environment::action::dry_run::ServiceDiff;
+use crate::environment::report::logger::EnvProgressLogger;
 use crate::errors::EngineError;
 use crate::infrastructure::models::cloud_provider::DeploymentTarget;
 use crate::infrastructure::models::cloud_provider::service::Action;
 
+mod check_admission_policies;
 mod check_dns;
+mod check_schedulability;
 mod deploy_application;
 mod deploy_container;
 mod deploy_database;
@@ -14,6 +18,7 @@ pub mod deploy_namespace;
 mod deploy_router;
 mod deploy_terraform;
 mod deploy_terraform_service;
+pub mod dry_run;
 mod pause_service;
 mod restart_service;
 #[cfg(test)]
@@ -35,6 +40,15 @@ pub trait DeploymentAction: Send + Sync {
             Action::Restart => self.on_restart(deployment_target),
         }
     }
+    /// Computes what `exec_action` would change, without building images nor mutating the cluster
+    fn on_dry_run(
+        &self,
+        _target: &DeploymentTarget,
+        action: Action,
+        _logger: &EnvProgressLogger,
+    ) -> Result<ServiceDiff, Box<EngineError>> {
+        Ok(ServiceDiff::from_action(action))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -44,4 +58,5 @@ pub enum K8sResourceType {
     DaemonSet,
     Job,
     CronJob,
+    ScaledJob,
 }
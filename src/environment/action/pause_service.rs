@@ -9,10 +9,11 @@ use k8s_openapi::api::apps::v1::{DaemonSet, Deployment, StatefulSet};
 use k8s_openapi::api::autoscaling::v1::{Scale, ScaleSpec};
 use k8s_openapi::api::batch::v1::CronJob;
 use k8s_openapi::api::core::v1::Pod;
-use kube::api::{ListParams, Patch, PatchParams};
+use kube::api::{ApiResource, DynamicObject, ListParams, Patch, PatchParams};
+use kube::core::GroupVersionKind;
 use kube::runtime::wait::{Condition, await_condition};
 use kube::{Api, Client};
-use serde_json::Value;
+use serde_json::{Value, json};
 use std::time::Duration;
 
 fn has_deployment_ready_replicas(nb_ready_replicas: usize) -> impl Condition<Deployment> {
@@ -68,6 +69,49 @@ fn has_not_daemonset_node_selector(selector_key: String) -> impl Condition<Daemo
 const PAUSE_SELECTOR_KEY: &str = "qovery-pause";
 const PAUSE_SELECTOR_VALUE: &str = "true";
 
+// KEDA keeps scaling its targets, it needs to be told explicitly to stop doing so while the service is paused
+// https://keda.sh/docs/latest/concepts/scaling-deployments/#pause-autoscaling
+const KEDA_SCALED_OBJECT_KIND: &str = "ScaledObject";
+const KEDA_SCALED_JOB_KIND: &str = "ScaledJob";
+const KEDA_PAUSED_REPLICAS_ANNOTATION: &str = "autoscaling.keda.sh/paused-replicas";
+const KEDA_PAUSED_ANNOTATION: &str = "autoscaling.keda.sh/paused";
+
+async fn set_keda_pause_annotation(
+    kube: &kube::Client,
+    namespace: &str,
+    selector: &str,
+    kind: &str,
+    annotation: &str,
+    value: Option<&str>, // None removes the annotation
+    is_cluster_wide_resources_allowed: bool,
+) -> Result<(), kube::Error> {
+    let api_resource = ApiResource::from_gvk(&GroupVersionKind::gvk("keda.sh", "v1alpha1", kind));
+    let keda_objects: Api<DynamicObject> = if is_cluster_wide_resources_allowed {
+        Api::all_with(kube.clone(), &api_resource)
+    } else {
+        Api::namespaced_with(kube.clone(), namespace, &api_resource)
+    };
+
+    let keda_objects = match keda_objects.list(&ListParams::default().labels(selector)).await {
+        Ok(keda_objects) => keda_objects,
+        // KEDA CRDs are not installed on the cluster, nothing to pause
+        Err(kube::Error::Api(api_err)) if api_err.code == 404 => return Ok(()),
+        Err(err) => return Err(err),
+    };
+
+    for keda_object in keda_objects {
+        if let (Some(namespace), Some(name)) = (keda_object.metadata.namespace, keda_object.metadata.name) {
+            let keda_objects: Api<DynamicObject> = Api::namespaced_with(kube.clone(), &namespace, &api_resource);
+            let patch = json!({ "metadata": { "annotations": { annotation: value } } });
+            keda_objects
+                .patch(&name, &PatchParams::default(), &Patch::Merge(&patch))
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
 async fn pause_service(
     kube: &kube::Client,
     namespace: &str,
@@ -82,6 +126,16 @@ async fn pause_service(
 
     match k8s_resource_type {
         K8sResourceType::StateFulSet => {
+            set_keda_pause_annotation(
+                kube,
+                namespace,
+                selector,
+                KEDA_SCALED_OBJECT_KIND,
+                KEDA_PAUSED_REPLICAS_ANNOTATION,
+                Some(&desired_size.to_string()),
+                is_cluster_wide_resources_allowed,
+            )
+            .await?;
             let (list_params, patch_params, patch) = get_patch_merge(selector, desired_size);
             let statefulsets: Api<StatefulSet> = if is_cluster_wide_resources_allowed {
                 Api::all(kube.clone())
@@ -107,6 +161,16 @@ async fn pause_service(
             }
         }
         K8sResourceType::Deployment => {
+            set_keda_pause_annotation(
+                kube,
+                namespace,
+                selector,
+                KEDA_SCALED_OBJECT_KIND,
+                KEDA_PAUSED_REPLICAS_ANNOTATION,
+                Some(&desired_size.to_string()),
+                is_cluster_wide_resources_allowed,
+            )
+            .await?;
             let (list_params, patch_params, patch) = get_patch_merge(selector, desired_size);
             let deployments: Api<Deployment> = if is_cluster_wide_resources_allowed {
                 Api::all(kube.clone())
@@ -190,6 +254,18 @@ async fn pause_service(
                 .await;
             }
         }
+        K8sResourceType::ScaledJob => {
+            set_keda_pause_annotation(
+                kube,
+                namespace,
+                selector,
+                KEDA_SCALED_JOB_KIND,
+                KEDA_PAUSED_ANNOTATION,
+                Some(PAUSE_SELECTOR_VALUE),
+                is_cluster_wide_resources_allowed,
+            )
+            .await?;
+        }
         K8sResourceType::Job => {}
     };
 
@@ -205,6 +281,16 @@ async fn unpause_service_if_needed(
 ) -> Result<(), kube::Error> {
     match k8s_resource_type {
         K8sResourceType::StateFulSet => {
+            set_keda_pause_annotation(
+                kube,
+                namespace,
+                selector,
+                KEDA_SCALED_OBJECT_KIND,
+                KEDA_PAUSED_REPLICAS_ANNOTATION,
+                None,
+                is_cluster_wide_resources_allowed,
+            )
+            .await?;
             let (list_params, patch_params, patch) = get_patch_merge(selector, 1);
             let statefulsets: Api<StatefulSet> = if is_cluster_wide_resources_allowed {
                 Api::all(kube.clone())
@@ -221,6 +307,16 @@ async fn unpause_service_if_needed(
             }
         }
         K8sResourceType::Deployment => {
+            set_keda_pause_annotation(
+                kube,
+                namespace,
+                selector,
+                KEDA_SCALED_OBJECT_KIND,
+                KEDA_PAUSED_REPLICAS_ANNOTATION,
+                None,
+                is_cluster_wide_resources_allowed,
+            )
+            .await?;
             let (list_params, patch_params, patch) = get_patch_merge(selector, 1);
             let deployments: Api<Deployment> = if is_cluster_wide_resources_allowed {
                 Api::all(kube.clone())
@@ -280,6 +376,18 @@ async fn unpause_service_if_needed(
                 }
             }
         }
+        K8sResourceType::ScaledJob => {
+            set_keda_pause_annotation(
+                kube,
+                namespace,
+                selector,
+                KEDA_SCALED_JOB_KIND,
+                KEDA_PAUSED_ANNOTATION,
+                None,
+                is_cluster_wide_resources_allowed,
+            )
+            .await?;
+        }
         K8sResourceType::Job => {}
     }
 
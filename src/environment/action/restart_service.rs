@@ -153,6 +153,7 @@ async fn restart_service(
         }
         K8sResourceType::CronJob => {}
         K8sResourceType::Job => {}
+        K8sResourceType::ScaledJob => {}
     };
 
     Ok(())
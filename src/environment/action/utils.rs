@@ -1,13 +1,14 @@
 use crate::cmd::command::CommandKiller;
-use crate::cmd::docker::ContainerImage;
+use crate::cmd::docker::{Architecture, ContainerImage, Docker};
 use crate::environment::report::logger::EnvProgressLogger;
 use crate::errors::{CommandError, EngineError};
 use crate::events::EventDetails;
-use crate::infrastructure::models::build_platform::Image;
+use crate::infrastructure::models::build_platform::{IMAGE_REVISION_LABEL, IMAGE_SOURCE_LABEL, Image};
 use crate::infrastructure::models::cloud_provider::DeploymentTarget;
 use crate::infrastructure::models::cloud_provider::io::RegistryMirroringMode;
 use crate::infrastructure::models::container_registry::RegistryTags;
 use crate::infrastructure::models::container_registry::errors::ContainerRegistryError;
+use crate::infrastructure::models::cost_allocation::CostAllocationTags;
 
 use crate::environment::models::container::get_mirror_repository_name;
 use crate::environment::models::kubernetes::K8sObject;
@@ -20,17 +21,23 @@ use k8s_openapi::api::apps::v1::{Deployment, StatefulSet};
 use k8s_openapi::api::batch::v1::CronJob;
 
 use crate::infrastructure::models::cloud_provider::service::{Service, increase_storage_size};
-use crate::io_models::models::InvalidStatefulsetStorage;
+use crate::io_models::container::ImageArtifact;
+use crate::io_models::models::{CpuArchitecture, InvalidStatefulsetStorage};
 use crate::kubers_utils::kube_get_resources_by_selector;
 use k8s_openapi::api::core::v1::PersistentVolumeClaim;
 use kube::Api;
 use kube::api::ListParams;
+use once_cell::sync::Lazy;
 use retry::OperationResult;
 use retry::delay::{Fibonacci, Fixed};
-use std::sync::Arc;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::Write;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
+use tempfile::NamedTempFile;
 use uuid::Uuid;
 
 // specific to AWS
@@ -193,7 +200,7 @@ pub fn mirror_image_if_necessary(
         };
         logger.info(skip_image_mirroring_message);
         mirror_record.stop(StepStatus::Skip);
-        Ok(())
+        check_image_architectures(&dest_image, target, logger, event_details)
     } else {
         let result = mirror_image(
             service_id,
@@ -207,6 +214,19 @@ pub fn mirror_image_if_necessary(
                 environment_id: Some(target.environment.id.clone()),
                 project_id: Some(target.environment.project_id.clone()),
                 resource_ttl: target.kubernetes.advanced_settings().resource_ttl(),
+                cost_allocation_tags: {
+                    let tags = CostAllocationTags::for_environment(
+                        target.environment.organization_long_id,
+                        *target.kubernetes.long_id(),
+                        target.environment.project_long_id,
+                        target.environment.long_id,
+                    );
+                    // the mirror repository is shared by all services of the cluster otherwise
+                    match target.kubernetes.advanced_settings().registry_mirroring_mode {
+                        RegistryMirroringMode::Service => tags.with_service(*service_id),
+                        RegistryMirroringMode::Cluster => tags,
+                    }
+                },
             },
         );
         mirror_record.stop(if result.is_ok() {
@@ -214,10 +234,47 @@ pub fn mirror_image_if_necessary(
         } else {
             StepStatus::Error
         });
-        result
+        result?;
+        check_image_architectures(&dest_image, target, logger, event_details)
     }
 }
 
+// Refuse to deploy an image that cannot run on some nodes of the cluster
+fn check_image_architectures(
+    image: &ContainerImage,
+    target: &DeploymentTarget,
+    logger: &EnvProgressLogger,
+    event_details: EventDetails,
+) -> Result<(), Box<EngineError>> {
+    let available_architectures = match target.docker.image_architectures(image) {
+        Ok(available_architectures) => available_architectures,
+        Err(err) => {
+            logger.warning(format!(
+                "⚠️ Cannot verify the architectures of image {}: {}",
+                image.image_name(),
+                err
+            ));
+            return Ok(());
+        }
+    };
+
+    let missing_architectures: Vec<CpuArchitecture> = target
+        .kubernetes
+        .cpu_architectures()
+        .into_iter()
+        .filter(|arch| !available_architectures.contains(&Architecture::from(arch)))
+        .collect();
+    if !missing_architectures.is_empty() {
+        return Err(Box::new(EngineError::new_image_missing_architectures(
+            event_details,
+            &image.image_name(),
+            &missing_architectures,
+        )));
+    }
+
+    Ok(())
+}
+
 fn image_already_exist(dest_image: &ContainerImage, target: &DeploymentTarget) -> bool {
     matches!(target.docker.does_image_exist_remotely(dest_image), Ok(true))
 }
@@ -231,37 +288,12 @@ fn mirror_image(
     event_details: EventDetails,
     tags: RegistryTags,
 ) -> Result<(), Box<EngineError>> {
-    // We need to login to the registry to get access to the image
-    let url = source.registry.get_url_with_credentials().map_err(|_| {
-        logger.warning("⚠️Cannot get the registry credentials".to_string());
-        EngineError::new_error_cannot_get_registry_credentials(event_details.clone())
-    })?;
-    if url.password().is_some() {
-        logger.info(format!(
-            "🔓 Login to registry {} as user {}",
-            url.host_str().unwrap_or_default(),
-            url.username()
-        ));
-
-        let login_ret = retry::retry(Fibonacci::from(Duration::from_secs(1)).take(4), || {
-            target.docker.login(&url).inspect_err(|_err| {
-                logger.warning("🔓 Retrying to login to registry due to error...".to_string());
-            })
-        });
-
-        if let Err(err) = login_ret {
-            let err = EngineError::new_docker_error(event_details, err.error);
-            let msg = format!(
-                "❌ Failed to login to registry {} due to {}",
-                url.host_str().unwrap_or_default(),
-                err
-            );
-            let user_err = EngineError::new_engine_error(err, msg, None);
-            return Err(Box::new(user_err));
-        }
+    // Pre-built artifacts are not fetched from the service registry
+    if source.artifact.is_none() {
+        login_to_source_registry(source, target, logger, event_details.clone())?;
     }
 
-    // Once we are logged to the registry, we mirror the user image into our cluster private registry
+    // Once we are logged to the registry, we mirror the user image (or its artifact) into our cluster private registry
     // This is required only to avoid to manage rotating credentials
     logger.info("🪞 Mirroring image to private cluster registry to ensure reproducibility".to_string());
     let mirror_repo_name = get_mirror_repository_name(
@@ -309,6 +341,10 @@ fn mirror_image(
         let docker_mirror_thread = scope.spawn(|| {
             // making sure to pass the current span to the new thread not to lose any tracing info
             let _span = current_span.enter();
+            if let Some(artifact) = &source.artifact {
+                return load_image_artifact(artifact, dest_image, target, logger, event_details);
+            }
+
             let mut err_logs = Vec::new();
             if let Err(err) = retry::retry(Fixed::from_millis(1000).take(3), || {
                 err_logs.clear();
@@ -359,6 +395,169 @@ fn mirror_image(
     })
 }
 
+fn login_to_source_registry(
+    source: &RegistryImageSource,
+    target: &DeploymentTarget,
+    logger: &EnvProgressLogger,
+    event_details: EventDetails,
+) -> Result<(), Box<EngineError>> {
+    // We need to login to the registry to get access to the image
+    let url = source.registry.get_url_with_credentials().map_err(|_| {
+        logger.warning("⚠️Cannot get the registry credentials".to_string());
+        EngineError::new_error_cannot_get_registry_credentials(event_details.clone())
+    })?;
+    if url.password().is_some() {
+        logger.info(format!(
+            "🔓 Login to registry {} as user {}",
+            url.host_str().unwrap_or_default(),
+            url.username()
+        ));
+
+        let login_ret = retry::retry(Fibonacci::from(Duration::from_secs(1)).take(4), || {
+            target.docker.login(&url).inspect_err(|_err| {
+                logger.warning("🔓 Retrying to login to registry due to error...".to_string());
+            })
+        });
+
+        if let Err(err) = login_ret {
+            let err = EngineError::new_docker_error(event_details, err.error);
+            let msg = format!(
+                "❌ Failed to login to registry {} due to {}",
+                url.host_str().unwrap_or_default(),
+                err
+            );
+            let user_err = EngineError::new_engine_error(err, msg, None);
+            return Err(Box::new(user_err));
+        }
+    }
+
+    Ok(())
+}
+
+// Push a pre-built image artifact (docker-archive or oci layout tarball) into the cluster registry
+fn load_image_artifact(
+    artifact: &ImageArtifact,
+    dest_image: &ContainerImage,
+    target: &DeploymentTarget,
+    logger: &EnvProgressLogger,
+    event_details: EventDetails,
+) -> Result<(), Box<EngineError>> {
+    logger.info(format!("📦 Fetching image artifact {}", artifact.location()));
+    let archive = fetch_image_artifact(artifact, target).map_err(|err| {
+        let err = EngineError::new_cannot_fetch_image_artifact(event_details.clone(), &artifact.location(), err);
+        logger.warning(format!("❌ {}", err.user_log_message()));
+        Box::new(err)
+    })?;
+
+    let mut err_logs = Vec::new();
+    target
+        .docker
+        .load_archive(
+            archive.path(),
+            dest_image,
+            &mut |line| info!("{}", line),
+            &mut |line| {
+                warn!("{}", &line);
+                err_logs.push(line);
+            },
+            &CommandKiller::from(Duration::from_secs(60 * 30), target.abort),
+        )
+        .map_err(|err| {
+            let msg = format!(
+                "❌ Failed to load image artifact {} due to {}\n{}",
+                artifact.location(),
+                err,
+                err_logs.join("\n")
+            );
+            logger.warning(msg.clone());
+            let user_err = EngineError::new_docker_error(event_details, err);
+            Box::new(EngineError::new_engine_error(user_err, msg, None))
+        })
+}
+
+fn fetch_image_artifact(artifact: &ImageArtifact, target: &DeploymentTarget) -> Result<NamedTempFile, CommandError> {
+    let mut archive = NamedTempFile::new().map_err(|err| {
+        CommandError::new("Cannot create image artifact file".to_string(), Some(err.to_string()), None)
+    })?;
+
+    // The artifact is streamed to the file, its checksum being computed along the way
+    let mut writer = Sha256Writer::new(archive.as_file_mut());
+    match artifact {
+        ImageArtifact::ObjectStorage {
+            bucket_name,
+            object_key,
+            ..
+        } => {
+            let object_storage = target.kubernetes.object_storage().ok_or_else(|| {
+                CommandError::new_from_safe_message("The cluster does not have any object storage".to_string())
+            })?;
+            object_storage.get_object_to_writer(bucket_name, object_key, &mut writer)?;
+        }
+        ImageArtifact::Http { url, .. } => {
+            let download_error = |err: reqwest::Error| {
+                CommandError::new("Cannot download image artifact".to_string(), Some(err.to_string()), None)
+            };
+            reqwest::blocking::Client::builder()
+                .connect_timeout(Duration::from_secs(30))
+                .build()
+                .map_err(download_error)?
+                .get(url.clone())
+                // Set timeout at 30min, same as for the mirroring of big images
+                .timeout(Duration::from_secs(60 * 30))
+                .send()
+                .and_then(|response| response.error_for_status())
+                .and_then(|mut response| response.copy_to(&mut writer))
+                .map_err(download_error)?;
+        }
+    }
+
+    check_image_artifact_checksum(artifact, &writer.checksum())?;
+    Ok(archive)
+}
+
+fn check_image_artifact_checksum(artifact: &ImageArtifact, checksum: &str) -> Result<(), CommandError> {
+    let expected_checksum = artifact.sha256_checksum();
+    if checksum != expected_checksum {
+        return Err(CommandError::new_from_safe_message(format!(
+            "Image artifact checksum mismatch, expected sha256 `{expected_checksum}` but got `{checksum}`"
+        )));
+    }
+
+    Ok(())
+}
+
+// Forward the content to the inner writer, while computing its sha256 checksum
+struct Sha256Writer<W: Write> {
+    inner: W,
+    hasher: Sha256,
+}
+
+impl<W: Write> Sha256Writer<W> {
+    fn new(inner: W) -> Self {
+        Sha256Writer {
+            inner,
+            hasher: Sha256::new(),
+        }
+    }
+
+    // Lowercase hex checksum of everything written so far
+    fn checksum(&self) -> String {
+        format!("{:x}", self.hasher.clone().finalize())
+    }
+}
+
+impl<W: Write> Write for Sha256Writer<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
 pub enum KubeObjectKind {
     Deployment,
     Statefulset,
@@ -461,6 +660,45 @@ pub async fn get_last_deployed_image(
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeployedImageSource {
+    pub url: String,
+    pub revision: String,
+}
+
+// Sources of the images already inspected, by image reference. Tags of built images are derived from their content,
+// so the registry is only asked once per image instead of on every deployment
+static DEPLOYED_IMAGE_SOURCES: Lazy<Mutex<HashMap<String, Option<DeployedImageSource>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+const MAX_DEPLOYED_IMAGE_SOURCES: usize = 1000;
+
+// Read back the source labels added at build time by LocalDocker, to know which commit is currently running
+pub fn get_deployed_image_source(docker: &Docker, image: &str) -> Option<DeployedImageSource> {
+    if let Some(source) = DEPLOYED_IMAGE_SOURCES
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .get(image)
+    {
+        return source.clone();
+    }
+
+    let container_image = ContainerImage::from_reference(image)?;
+    // Registry errors are not cached, the image can be readable on the next deployment
+    let mut labels = docker.image_labels(&container_image).ok()?;
+    let source = match (labels.remove(IMAGE_SOURCE_LABEL), labels.remove(IMAGE_REVISION_LABEL)) {
+        (Some(url), Some(revision)) => Some(DeployedImageSource { url, revision }),
+        _ => None,
+    };
+
+    let mut sources = DEPLOYED_IMAGE_SOURCES.lock().unwrap_or_else(|err| err.into_inner());
+    if sources.len() >= MAX_DEPLOYED_IMAGE_SOURCES {
+        sources.clear();
+    }
+    sources.insert(image.to_string(), source.clone());
+
+    source
+}
+
 pub fn are_pvcs_bound(
     service: &dyn Service,
     namespace: &str,
@@ -507,3 +745,52 @@ pub fn update_pvcs(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha256_writer() {
+        // setup:
+        let mut content = Vec::new();
+        let mut writer = Sha256Writer::new(&mut content);
+
+        // execute:
+        writer.write_all(b"te").unwrap();
+        writer.write_all(b"st").unwrap();
+
+        // verify:
+        assert_eq!(
+            writer.checksum(),
+            "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+        );
+        assert_eq!(content, b"test");
+    }
+
+    #[test]
+    fn test_check_image_artifact_checksum() {
+        let checksum = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+        let artifact = |sha256_checksum: &str| ImageArtifact::ObjectStorage {
+            bucket_name: "artifacts".to_string(),
+            object_key: "api.tar".to_string(),
+            sha256_checksum: sha256_checksum.to_string(),
+        };
+
+        assert!(check_image_artifact_checksum(&artifact(checksum), checksum).is_ok());
+        assert!(
+            check_image_artifact_checksum(
+                &artifact("sha256:9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08"),
+                checksum
+            )
+            .is_ok()
+        );
+        assert!(
+            check_image_artifact_checksum(
+                &artifact("60303ae22b998861bce3b28f33eec1be758a213c86c93c076dbe9f558c11c752"),
+                checksum
+            )
+            .is_err()
+        );
+    }
+}
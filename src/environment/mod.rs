@@ -1,4 +1,5 @@
 pub mod action;
 pub mod models;
 pub mod report;
+pub mod sweeper_task;
 pub mod task;
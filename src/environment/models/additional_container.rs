@@ -0,0 +1,256 @@
+use crate::environment::models::probe::Probe;
+use crate::environment::models::registry_image_source::RegistryImageSource;
+use crate::infrastructure::models::cloud_provider::DeploymentTarget;
+use crate::io_models::models::{EnvironmentVariable, KubernetesCpuResourceUnit, KubernetesMemoryResourceUnit};
+use serde_derive::Serialize;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AdditionalContainerKind {
+    Init,
+    Sidecar,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct SharedVolume {
+    pub name: String,
+    pub mount_path: String,
+    /// Path the volume is mounted at in the service container, not mounted in it when `None`
+    pub service_mount_path: Option<String>,
+}
+
+/// Init step or sidecar, defined by the user, running in the pods of an application or a container
+pub struct AdditionalContainer {
+    pub name: String,
+    pub kind: AdditionalContainerKind,
+    pub source: RegistryImageSource,
+    pub command_args: Vec<String>,
+    pub entrypoint: Option<String>,
+    pub environment_variables: Vec<EnvironmentVariable>,
+    pub cpu_request_in_milli: KubernetesCpuResourceUnit,
+    pub cpu_limit_in_milli: KubernetesCpuResourceUnit,
+    pub ram_request_in_mib: KubernetesMemoryResourceUnit,
+    pub ram_limit_in_mib: KubernetesMemoryResourceUnit,
+    pub readiness_probe: Option<Probe>,
+    pub liveness_probe: Option<Probe>,
+    pub shared_volumes: Vec<SharedVolume>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct AdditionalContainerTeraContext {
+    pub(crate) name: String,
+    pub(crate) is_sidecar: bool,
+    pub(crate) image_full: String,
+    pub(crate) command_args: Vec<String>,
+    pub(crate) entrypoint: Option<String>,
+    /// Secret holding the environment variables of the container, distinct from the service one
+    pub(crate) secret_name: String,
+    pub(crate) environment_variables: Vec<EnvironmentVariable>,
+    pub(crate) cpu_request_in_milli: String,
+    pub(crate) cpu_limit_in_milli: String,
+    pub(crate) ram_request_in_mib: String,
+    pub(crate) ram_limit_in_mib: String,
+    pub(crate) readiness_probe: Option<Probe>,
+    pub(crate) liveness_probe: Option<Probe>,
+    pub(crate) shared_volumes: Vec<SharedVolume>,
+}
+
+fn is_dns_label(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 63
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !name.starts_with('-')
+        && !name.ends_with('-')
+}
+
+/// Checks additional containers can live in the same pod as the service container named `service_kube_name`
+pub fn validate_additional_containers(
+    additional_containers: &[AdditionalContainer],
+    service_kube_name: &str,
+) -> Result<(), String> {
+    let mut names = HashSet::with_capacity(additional_containers.len());
+    let mut service_mount_paths: HashMap<&str, &str> = HashMap::new();
+    for container in additional_containers {
+        if !is_dns_label(&container.name) {
+            return Err(format!(
+                "additional container name `{}` must be a valid DNS label (lowercase alphanumeric characters or '-', at most 63 characters)",
+                container.name
+            ));
+        }
+        if container.name == service_kube_name || !names.insert(container.name.as_str()) {
+            return Err(format!("additional container name `{}` is already used", container.name));
+        }
+        if container.kind == AdditionalContainerKind::Init
+            && (container.readiness_probe.is_some() || container.liveness_probe.is_some())
+        {
+            return Err(format!(
+                "init container `{}` cannot have probes, only sidecars can",
+                container.name
+            ));
+        }
+
+        let mut volume_names = HashSet::with_capacity(container.shared_volumes.len());
+        for volume in &container.shared_volumes {
+            if !is_dns_label(&volume.name) || !volume_names.insert(volume.name.as_str()) {
+                return Err(format!(
+                    "shared volume name `{}` of additional container `{}` must be a unique and valid DNS label",
+                    volume.name, container.name
+                ));
+            }
+            let service_mount_path = volume.service_mount_path.as_deref();
+            if !volume.mount_path.starts_with('/') || service_mount_path.is_some_and(|path| !path.starts_with('/')) {
+                return Err(format!(
+                    "shared volume `{}` of additional container `{}` must be mounted on an absolute path",
+                    volume.name, container.name
+                ));
+            }
+            if let Some(service_mount_path) = service_mount_path {
+                match service_mount_paths.insert(volume.name.as_str(), service_mount_path) {
+                    Some(other_path) if other_path != service_mount_path => {
+                        return Err(format!(
+                            "shared volume `{}` is mounted in the service container at both `{other_path}` and `{service_mount_path}`",
+                            volume.name
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the tera context of each additional container, along with the volumes shared by the pod containers.
+/// A volume declared by several containers is mounted in the service container if any of them sets a service mount path.
+pub(crate) fn to_additional_containers_tera_context(
+    additional_containers: &[AdditionalContainer],
+    service_id: &Uuid,
+    service_kube_name: &str,
+    target: &DeploymentTarget,
+) -> (Vec<AdditionalContainerTeraContext>, Vec<SharedVolume>) {
+    let mut shared_volumes: Vec<SharedVolume> = vec![];
+    for volume in additional_containers.iter().flat_map(|c| c.shared_volumes.iter()) {
+        match shared_volumes.iter_mut().find(|v| v.name == volume.name) {
+            Some(shared_volume) => {
+                if shared_volume.service_mount_path.is_none() {
+                    shared_volume.service_mount_path = volume.service_mount_path.clone();
+                }
+            }
+            None => shared_volumes.push(volume.clone()),
+        }
+    }
+
+    let containers = additional_containers
+        .iter()
+        .map(|container| AdditionalContainerTeraContext {
+            name: container.name.clone(),
+            is_sidecar: container.kind == AdditionalContainerKind::Sidecar,
+            image_full: container.source.cluster_image_full(service_id, target).0,
+            command_args: container.command_args.clone(),
+            entrypoint: container.entrypoint.clone(),
+            secret_name: format!("{}-{}", service_kube_name, container.name),
+            environment_variables: container.environment_variables.clone(),
+            cpu_request_in_milli: container.cpu_request_in_milli.to_string(),
+            cpu_limit_in_milli: container.cpu_limit_in_milli.to_string(),
+            ram_request_in_mib: container.ram_request_in_mib.to_string(),
+            ram_limit_in_mib: container.ram_limit_in_mib.to_string(),
+            readiness_probe: container.readiness_probe.clone(),
+            liveness_probe: container.liveness_probe.clone(),
+            shared_volumes: container.shared_volumes.clone(),
+        })
+        .collect();
+
+    (containers, shared_volumes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::models::cloud_provider::io::RegistryMirroringMode;
+    use crate::io_models::container::Registry;
+    use url::Url;
+
+    fn additional_container(name: &str, kind: AdditionalContainerKind) -> AdditionalContainer {
+        AdditionalContainer {
+            name: name.to_string(),
+            kind,
+            source: RegistryImageSource {
+                registry: Registry::PublicEcr {
+                    long_id: Uuid::new_v4(),
+                    url: Url::parse("https://public.ecr.aws").unwrap(),
+                },
+                image: "fluent/fluent-bit".to_string(),
+                tag: "3.0".to_string(),
+                artifact: None,
+                registry_mirroring_mode: RegistryMirroringMode::Service,
+            },
+            command_args: vec![],
+            entrypoint: None,
+            environment_variables: vec![],
+            cpu_request_in_milli: KubernetesCpuResourceUnit::MilliCpu(100),
+            cpu_limit_in_milli: KubernetesCpuResourceUnit::MilliCpu(100),
+            ram_request_in_mib: KubernetesMemoryResourceUnit::MebiByte(64),
+            ram_limit_in_mib: KubernetesMemoryResourceUnit::MebiByte(64),
+            readiness_probe: None,
+            liveness_probe: None,
+            shared_volumes: vec![SharedVolume {
+                name: "logs".to_string(),
+                mount_path: "/var/log/app".to_string(),
+                service_mount_path: Some("/var/log/app".to_string()),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_validate_additional_containers() {
+        let log_shipper = additional_container("log-shipper", AdditionalContainerKind::Sidecar);
+        let migrations = additional_container("migrations", AdditionalContainerKind::Init);
+        assert!(validate_additional_containers(&[log_shipper, migrations], "app-z1234").is_ok());
+
+        let same_as_service = additional_container("app-z1234", AdditionalContainerKind::Sidecar);
+        assert!(validate_additional_containers(&[same_as_service], "app-z1234").is_err());
+
+        let duplicates = [
+            additional_container("proxy", AdditionalContainerKind::Sidecar),
+            additional_container("proxy", AdditionalContainerKind::Init),
+        ];
+        assert!(validate_additional_containers(&duplicates, "app-z1234").is_err());
+
+        let invalid_name = additional_container("Log_Shipper", AdditionalContainerKind::Sidecar);
+        assert!(validate_additional_containers(&[invalid_name], "app-z1234").is_err());
+
+        let mut init_with_probe = additional_container("migrations", AdditionalContainerKind::Init);
+        init_with_probe.liveness_probe = Some(Probe {
+            r#type: crate::environment::models::probe::ProbeType::Tcp { host: None },
+            port: 8080,
+            initial_delay_seconds: 10,
+            period_seconds: 10,
+            timeout_seconds: 5,
+            success_threshold: 1,
+            failure_threshold: 3,
+        });
+        assert!(validate_additional_containers(&[init_with_probe], "app-z1234").is_err());
+
+        let mut relative_mount = additional_container("log-shipper", AdditionalContainerKind::Sidecar);
+        relative_mount.shared_volumes[0].mount_path = "var/log".to_string();
+        assert!(validate_additional_containers(&[relative_mount], "app-z1234").is_err());
+
+        let mut relative_service_mount = additional_container("log-shipper", AdditionalContainerKind::Sidecar);
+        relative_service_mount.shared_volumes[0].service_mount_path = Some("var/log".to_string());
+        assert!(validate_additional_containers(&[relative_service_mount], "app-z1234").is_err());
+
+        let mut other_service_mount = additional_container("migrations", AdditionalContainerKind::Init);
+        other_service_mount.shared_volumes[0].service_mount_path = Some("/logs".to_string());
+        let log_shipper = additional_container("log-shipper", AdditionalContainerKind::Sidecar);
+        assert!(validate_additional_containers(&[log_shipper, other_service_mount], "app-z1234").is_err());
+
+        let mut not_in_service = additional_container("migrations", AdditionalContainerKind::Init);
+        not_in_service.shared_volumes[0].service_mount_path = None;
+        let log_shipper = additional_container("log-shipper", AdditionalContainerKind::Sidecar);
+        assert!(validate_additional_containers(&[log_shipper, not_in_service], "app-z1234").is_ok());
+    }
+}
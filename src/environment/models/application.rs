@@ -1,4 +1,4 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::BTreeSet;
 use std::marker::PhantomData;
 use std::path::PathBuf;
 use std::time::Duration;
@@ -8,7 +8,11 @@ use k8s_openapi::api::core::v1::PersistentVolumeClaim;
 use uuid::Uuid;
 
 use crate::environment::action::DeploymentAction;
+use crate::environment::models::additional_container::{
+    AdditionalContainer, to_additional_containers_tera_context, validate_additional_containers,
+};
 use crate::environment::models::annotations_group::AnnotationsGroupTeraContext;
+use crate::environment::models::autoscaling::{EventDrivenAutoscaling, check_event_driven_autoscaling_is_supported};
 use crate::environment::models::container::{
     ClusterTeraContext, ContainerTeraContext, RegistryTeraContext, ServiceTeraContext, to_public_l4_ports,
 };
@@ -22,7 +26,7 @@ use crate::infrastructure::models::build_platform::Build;
 use crate::infrastructure::models::cloud_provider::DeploymentTarget;
 use crate::infrastructure::models::cloud_provider::Kind::Scw;
 use crate::infrastructure::models::cloud_provider::service::{
-    Action, Service, ServiceType, get_service_statefulset_name_and_volumes,
+    Action, Service, ServiceResourcesRequest, ServiceType, get_service_statefulset_name_and_volumes,
 };
 use crate::io_models::annotations_group::AnnotationsGroup;
 use crate::io_models::application::Protocol::{TCP, UDP};
@@ -69,6 +73,8 @@ pub struct Application<T: CloudProvider> {
     pub(crate) readiness_probe: Option<Probe>,
     pub(crate) liveness_probe: Option<Probe>,
     pub(crate) advanced_settings: ApplicationAdvancedSettings,
+    pub(crate) event_driven_autoscaling: Option<EventDrivenAutoscaling>,
+    pub(crate) additional_containers: Vec<AdditionalContainer>,
     pub(crate) _extra_settings: T::AppExtraSettings,
     pub(crate) workspace_directory: PathBuf,
     pub(crate) lib_root_directory: String,
@@ -98,6 +104,8 @@ impl<T: CloudProvider> Application<T> {
         readiness_probe: Option<Probe>,
         liveness_probe: Option<Probe>,
         advanced_settings: ApplicationAdvancedSettings,
+        event_driven_autoscaling: Option<EventDrivenAutoscaling>,
+        additional_containers: Vec<AdditionalContainer>,
         extra_settings: T::AppExtraSettings,
         mk_event_details: impl Fn(Transmitter) -> EventDetails,
         annotations_groups: Vec<AnnotationsGroup>,
@@ -109,6 +117,7 @@ impl<T: CloudProvider> Application<T> {
         should_delete_shared_registry: bool,
     ) -> Result<Self, ApplicationError> {
         // TODO: Check that the information provided are coherent
+        validate_additional_containers(&additional_containers, &kube_name).map_err(ApplicationError::InvalidConfig)?;
 
         let workspace_directory = crate::fs::workspace_directory(
             context.workspace_root_dir(),
@@ -144,6 +153,8 @@ impl<T: CloudProvider> Application<T> {
             readiness_probe,
             liveness_probe,
             advanced_settings,
+            event_driven_autoscaling,
+            additional_containers,
             _extra_settings: extra_settings,
             workspace_directory,
             lib_root_directory: context.lib_root_dir().to_string(),
@@ -173,15 +184,24 @@ impl<T: CloudProvider> Application<T> {
             &target.kubernetes.cpu_architectures(),
         );
 
-        let mut tolerations = BTreeMap::<String, String>::new();
+        let mut tolerations = Vec::<utils::Toleration>::new();
         let is_stateful_set = !self.storages.is_empty();
-        if utils::need_target_stable_node_pool(kubernetes, self.min_instances, is_stateful_set) {
+        if let Some(node_pool) = self
+            .advanced_settings
+            .deployment_node_pool
+            .as_deref()
+            .and_then(|node_pool| kubernetes.karpenter_user_node_pool(node_pool))
+        {
+            utils::target_user_node_pool(node_pool, &mut deployment_affinity_node_required, &mut tolerations);
+        } else if utils::need_target_stable_node_pool(kubernetes, self.min_instances, is_stateful_set) {
             utils::target_stable_node_pool(&mut deployment_affinity_node_required, &mut tolerations, is_stateful_set);
         }
 
         let mut advanced_settings = self.advanced_settings.clone();
         advanced_settings.deployment_affinity_node_required = deployment_affinity_node_required;
         let registry_info = target.container_registry.registry_info();
+        let (additional_containers, shared_volumes) =
+            to_additional_containers_tera_context(&self.additional_containers, &self.long_id, self.kube_name(), target);
 
         ContainerTeraContext {
             organization_long_id: environment.organization_long_id,
@@ -236,6 +256,12 @@ impl<T: CloudProvider> Application<T> {
                 readiness_probe: self.readiness_probe.clone(),
                 liveness_probe: self.liveness_probe.clone(),
                 advanced_settings: advanced_settings.to_container_advanced_settings(),
+                event_driven_autoscaling: self
+                    .event_driven_autoscaling
+                    .as_ref()
+                    .map(|autoscaling| autoscaling.to_tera_context(environment.namespace(), self.kube_name())),
+                additional_containers,
+                shared_volumes,
                 legacy_deployment_matchlabels: true,
                 legacy_volumeclaim_template: true,
                 legacy_deployment_from_scaleway: T::cloud_provider() == Scw,
@@ -398,6 +424,15 @@ impl<T: CloudProvider> Service for Application<T> {
     fn get_environment_variables(&self) -> Vec<EnvironmentVariable> {
         self.environment_variables.clone()
     }
+
+    fn resources_request(&self) -> Option<ServiceResourcesRequest> {
+        Some(ServiceResourcesRequest {
+            cpu_in_milli: self.cpu_request_in_milli.clone().into(),
+            ram_in_mib: self.ram_request_in_mib.to_mib(),
+            instances: self.min_instances,
+            node_pool: self.advanced_settings.deployment_node_pool.clone(),
+        })
+    }
 }
 
 pub trait ApplicationService: Service + DeploymentAction + ToTeraContext + Send {
@@ -414,6 +449,18 @@ use tera::Context as TeraContext;
 
 impl<T: CloudProvider> ToTeraContext for Application<T> {
     fn to_tera_context(&self, target: &DeploymentTarget) -> Result<TeraContext, Box<EngineError>> {
+        if let Some(event_driven_autoscaling) = &self.event_driven_autoscaling {
+            check_event_driven_autoscaling_is_supported(
+                target,
+                &event_driven_autoscaling.scalers,
+                self.get_event_details(Stage::Environment(self.action().to_environment_step())),
+            )?;
+        }
+        utils::check_node_pool_exists(
+            target.kubernetes,
+            self.advanced_settings.deployment_node_pool.as_deref(),
+            self.get_event_details(Stage::Environment(self.action().to_environment_step())),
+        )?;
         let context = self.default_tera_context(target);
         Ok(TeraContext::from_serialize(context).unwrap_or_default())
     }
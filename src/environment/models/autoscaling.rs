@@ -0,0 +1,292 @@
+use crate::errors::EngineError;
+use crate::events::EventDetails;
+use crate::infrastructure::models::cloud_provider::DeploymentTarget;
+use crate::infrastructure::models::cloud_provider::io::IngressImplementation;
+use serde_derive::Serialize;
+use std::collections::BTreeMap;
+
+/// In-cluster Prometheus queried by `Prometheus` scalers without explicit server address and by `HttpRequestRate` scalers
+pub const DEFAULT_PROMETHEUS_SERVER_ADDRESS: &str = "http://prometheus-operated.prometheus.svc:9090";
+
+#[derive(Clone, Debug)]
+pub enum Scaler {
+    Queue {
+        scaler_type: String,
+        metadata: BTreeMap<String, String>,
+        authentication: BTreeMap<String, String>,
+    },
+    Prometheus {
+        server_address: Option<String>,
+        query: String,
+        threshold: String,
+        activation_threshold: Option<String>,
+    },
+    Cron {
+        timezone: String,
+        start: String,
+        end: String,
+        desired_replicas: u32,
+    },
+    HttpRequestRate {
+        requests_per_second: u32,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct EventDrivenAutoscaling {
+    pub scalers: Vec<Scaler>,
+    pub polling_interval_seconds: u32,
+    pub cooldown_period_seconds: u32,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ScalerTriggerTeraContext {
+    pub(crate) r#type: String,
+    pub(crate) metadata: BTreeMap<String, String>,
+    /// KEDA trigger authentication parameter -> key of the service secret
+    pub(crate) authentication: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub(crate) struct ScaledJobTeraContext {
+    pub(crate) max_parallel_jobs: u32,
+    pub(crate) polling_interval_seconds: u32,
+    pub(crate) triggers: Vec<ScalerTriggerTeraContext>,
+    pub(crate) has_authentication: bool,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub(crate) struct EventDrivenAutoscalingTeraContext {
+    pub(crate) polling_interval_seconds: u32,
+    pub(crate) cooldown_period_seconds: u32,
+    pub(crate) triggers: Vec<ScalerTriggerTeraContext>,
+    pub(crate) has_authentication: bool,
+}
+
+impl Scaler {
+    fn to_trigger(&self, namespace: &str, service_name: &str) -> ScalerTriggerTeraContext {
+        match self {
+            Scaler::Queue {
+                scaler_type,
+                metadata,
+                authentication,
+            } => ScalerTriggerTeraContext {
+                r#type: scaler_type.clone(),
+                metadata: metadata.clone(),
+                authentication: authentication.clone(),
+            },
+            Scaler::Prometheus {
+                server_address,
+                query,
+                threshold,
+                activation_threshold,
+            } => {
+                let mut metadata = BTreeMap::from([
+                    (
+                        "serverAddress".to_string(),
+                        server_address
+                            .clone()
+                            .unwrap_or_else(|| DEFAULT_PROMETHEUS_SERVER_ADDRESS.to_string()),
+                    ),
+                    ("query".to_string(), query.clone()),
+                    ("threshold".to_string(), threshold.clone()),
+                ]);
+                if let Some(activation_threshold) = activation_threshold {
+                    metadata.insert("activationThreshold".to_string(), activation_threshold.clone());
+                }
+
+                ScalerTriggerTeraContext {
+                    r#type: "prometheus".to_string(),
+                    metadata,
+                    authentication: BTreeMap::new(),
+                }
+            }
+            Scaler::Cron {
+                timezone,
+                start,
+                end,
+                desired_replicas,
+            } => ScalerTriggerTeraContext {
+                r#type: "cron".to_string(),
+                metadata: BTreeMap::from([
+                    ("timezone".to_string(), timezone.clone()),
+                    ("start".to_string(), start.clone()),
+                    ("end".to_string(), end.clone()),
+                    ("desiredReplicas".to_string(), desired_replicas.to_string()),
+                ]),
+                authentication: BTreeMap::new(),
+            },
+            // Requests are counted by nginx ingress controller, which is scrapped by the cluster Prometheus
+            Scaler::HttpRequestRate { requests_per_second } => ScalerTriggerTeraContext {
+                r#type: "prometheus".to_string(),
+                metadata: BTreeMap::from([
+                    ("serverAddress".to_string(), DEFAULT_PROMETHEUS_SERVER_ADDRESS.to_string()),
+                    (
+                        "query".to_string(),
+                        format!(
+                            "sum(rate(nginx_ingress_controller_requests{{exported_namespace=\"{namespace}\",exported_service=\"{service_name}\"}}[2m]))"
+                        ),
+                    ),
+                    ("threshold".to_string(), requests_per_second.to_string()),
+                    ("activationThreshold".to_string(), "0".to_string()),
+                ]),
+                authentication: BTreeMap::new(),
+            },
+        }
+    }
+}
+
+impl EventDrivenAutoscaling {
+    pub(crate) fn to_tera_context(&self, namespace: &str, service_name: &str) -> EventDrivenAutoscalingTeraContext {
+        let triggers: Vec<ScalerTriggerTeraContext> = self
+            .scalers
+            .iter()
+            .map(|scaler| scaler.to_trigger(namespace, service_name))
+            .collect();
+
+        EventDrivenAutoscalingTeraContext {
+            polling_interval_seconds: self.polling_interval_seconds,
+            cooldown_period_seconds: self.cooldown_period_seconds,
+            has_authentication: triggers.iter().any(|t| !t.authentication.is_empty()),
+            triggers,
+        }
+    }
+
+    pub(crate) fn to_scaled_job_tera_context(
+        &self,
+        max_parallel_jobs: u32,
+        namespace: &str,
+        service_name: &str,
+    ) -> ScaledJobTeraContext {
+        let autoscaling = self.to_tera_context(namespace, service_name);
+
+        ScaledJobTeraContext {
+            max_parallel_jobs,
+            polling_interval_seconds: autoscaling.polling_interval_seconds,
+            triggers: autoscaling.triggers,
+            has_authentication: autoscaling.has_authentication,
+        }
+    }
+}
+
+/// KEDA CRDs (ScaledObject / ScaledJob) are only available if the operator has been installed on the cluster
+pub(crate) fn check_event_driven_autoscaling_is_supported(
+    target: &DeploymentTarget,
+    scalers: &[Scaler],
+    event_details: EventDetails,
+) -> Result<(), Box<EngineError>> {
+    if !target.kubernetes.advanced_settings().keda_enabled {
+        return Err(Box::new(EngineError::new_invalid_engine_payload(
+            event_details,
+            "Event driven autoscaling requires KEDA to be installed on the cluster, set `keda.enabled` cluster advanced setting",
+            None,
+        )));
+    }
+
+    check_scalers_are_supported(scalers, &target.kubernetes.advanced_settings().ingress_implementation)
+        .map_err(|message| Box::new(EngineError::new_invalid_engine_payload(event_details, &message, None)))
+}
+
+/// `HttpRequestRate` scalers read the requests counted by nginx ingress controller, other ingresses do not expose them
+fn check_scalers_are_supported(
+    scalers: &[Scaler],
+    ingress_implementation: &IngressImplementation,
+) -> Result<(), String> {
+    let has_http_request_rate_scaler = scalers
+        .iter()
+        .any(|scaler| matches!(scaler, Scaler::HttpRequestRate { .. }));
+    if has_http_request_rate_scaler && *ingress_implementation != IngressImplementation::Nginx {
+        return Err(format!(
+            "Http request rate scaler requires nginx ingress, the cluster uses `{ingress_implementation:?}` ingress implementation. Use a Prometheus scaler instead"
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_request_rate_scaler_is_converted_to_prometheus_trigger() {
+        // setup:
+        let autoscaling = EventDrivenAutoscaling {
+            scalers: vec![Scaler::HttpRequestRate {
+                requests_per_second: 50,
+            }],
+            polling_interval_seconds: 30,
+            cooldown_period_seconds: 300,
+        };
+
+        // execute:
+        let context = autoscaling.to_tera_context("z-my-env", "app-z1234");
+
+        // verify:
+        assert!(!context.has_authentication);
+        assert_eq!(context.triggers.len(), 1);
+        assert_eq!(context.triggers[0].r#type, "prometheus");
+        assert_eq!(context.triggers[0].metadata["threshold"], "50");
+        assert_eq!(
+            context.triggers[0].metadata["query"],
+            "sum(rate(nginx_ingress_controller_requests{exported_namespace=\"z-my-env\",exported_service=\"app-z1234\"}[2m]))"
+        );
+    }
+
+    #[test]
+    fn test_queue_scaler_authentication_is_kept() {
+        // setup:
+        let autoscaling = EventDrivenAutoscaling {
+            scalers: vec![
+                Scaler::Queue {
+                    scaler_type: "rabbitmq".to_string(),
+                    metadata: BTreeMap::from([
+                        ("queueName".to_string(), "tasks".to_string()),
+                        ("value".to_string(), "20".to_string()),
+                    ]),
+                    authentication: BTreeMap::from([("host".to_string(), "RABBITMQ_URL".to_string())]),
+                },
+                Scaler::Prometheus {
+                    server_address: None,
+                    query: "sum(my_metric)".to_string(),
+                    threshold: "10".to_string(),
+                    activation_threshold: None,
+                },
+            ],
+            polling_interval_seconds: 10,
+            cooldown_period_seconds: 60,
+        };
+
+        // execute:
+        let context = autoscaling.to_tera_context("z-my-env", "app-z1234");
+
+        // verify:
+        assert!(context.has_authentication);
+        assert_eq!(context.triggers[0].r#type, "rabbitmq");
+        assert_eq!(context.triggers[0].authentication["host"], "RABBITMQ_URL");
+        assert_eq!(context.triggers[1].metadata["serverAddress"], DEFAULT_PROMETHEUS_SERVER_ADDRESS);
+        assert!(!context.triggers[1].metadata.contains_key("activationThreshold"));
+    }
+
+    #[test]
+    fn test_http_request_rate_scaler_requires_nginx_ingress() {
+        // setup:
+        let scalers = vec![
+            Scaler::Cron {
+                timezone: "Europe/Paris".to_string(),
+                start: "0 8 * * *".to_string(),
+                end: "0 20 * * *".to_string(),
+                desired_replicas: 2,
+            },
+            Scaler::HttpRequestRate {
+                requests_per_second: 50,
+            },
+        ];
+
+        // execute & verify:
+        assert!(check_scalers_are_supported(&scalers, &IngressImplementation::Nginx).is_ok());
+        assert!(check_scalers_are_supported(&scalers, &IngressImplementation::EnvoyGateway).is_err());
+        assert!(check_scalers_are_supported(&scalers, &IngressImplementation::Cilium).is_err());
+        assert!(check_scalers_are_supported(&scalers[..1], &IngressImplementation::EnvoyGateway).is_ok());
+    }
+}
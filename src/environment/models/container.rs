@@ -1,5 +1,4 @@
-use std::borrow::Cow;
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::BTreeSet;
 use std::marker::PhantomData;
 use std::path::PathBuf;
 use std::time::Duration;
@@ -10,7 +9,14 @@ use serde::Serialize;
 use uuid::Uuid;
 
 use crate::environment::action::DeploymentAction;
+use crate::environment::models::additional_container::{
+    AdditionalContainer, AdditionalContainerTeraContext, SharedVolume, to_additional_containers_tera_context,
+    validate_additional_containers,
+};
 use crate::environment::models::annotations_group::AnnotationsGroupTeraContext;
+use crate::environment::models::autoscaling::{
+    EventDrivenAutoscaling, EventDrivenAutoscalingTeraContext, check_event_driven_autoscaling_is_supported,
+};
 use crate::environment::models::labels_group::LabelsGroupTeraContext;
 use crate::environment::models::probe::Probe;
 use crate::environment::models::registry_image_source::RegistryImageSource;
@@ -22,7 +28,7 @@ use crate::infrastructure::models::build_platform::Build;
 use crate::infrastructure::models::cloud_provider::DeploymentTarget;
 use crate::infrastructure::models::cloud_provider::io::RegistryMirroringMode;
 use crate::infrastructure::models::cloud_provider::service::{
-    Action, Service, ServiceType, get_service_statefulset_name_and_volumes,
+    Action, Service, ServiceResourcesRequest, ServiceType, get_service_statefulset_name_and_volumes,
 };
 use crate::infrastructure::models::container_registry::DockerRegistryInfo;
 use crate::infrastructure::models::kubernetes::Kubernetes;
@@ -72,6 +78,8 @@ pub struct Container<T: CloudProvider> {
     pub(crate) readiness_probe: Option<Probe>,
     pub(crate) liveness_probe: Option<Probe>,
     pub(crate) advanced_settings: ContainerAdvancedSettings,
+    pub(crate) event_driven_autoscaling: Option<EventDrivenAutoscaling>,
+    pub(crate) additional_containers: Vec<AdditionalContainer>,
     pub(crate) _extra_settings: T::AppExtraSettings,
     pub(crate) workspace_directory: PathBuf,
     pub(crate) lib_root_directory: String,
@@ -135,6 +143,8 @@ impl<T: CloudProvider> Container<T> {
         readiness_probe: Option<Probe>,
         liveness_probe: Option<Probe>,
         advanced_settings: ContainerAdvancedSettings,
+        event_driven_autoscaling: Option<EventDrivenAutoscaling>,
+        additional_containers: Vec<AdditionalContainer>,
         extra_settings: T::AppExtraSettings,
         mk_event_details: impl Fn(Transmitter) -> EventDetails,
         annotations_groups: Vec<AnnotationsGroup>,
@@ -146,12 +156,15 @@ impl<T: CloudProvider> Container<T> {
             ));
         }
 
-        if min_instances == 0 {
+        // scale to zero is only possible when the service is scaled by events
+        if min_instances == 0 && event_driven_autoscaling.is_none() {
             return Err(ContainerError::InvalidConfig(
                 "min_instances must be greater than 0".to_string(),
             ));
         }
 
+        validate_additional_containers(&additional_containers, &kube_name).map_err(ContainerError::InvalidConfig)?;
+
         let workspace_directory = crate::fs::workspace_directory(
             context.workspace_root_dir(),
             context.execution_id(),
@@ -186,6 +199,8 @@ impl<T: CloudProvider> Container<T> {
             readiness_probe,
             liveness_probe,
             advanced_settings,
+            event_driven_autoscaling,
+            additional_containers,
             _extra_settings: extra_settings,
             workspace_directory,
             lib_root_directory: context.lib_root_dir().to_string(),
@@ -222,9 +237,16 @@ impl<T: CloudProvider> Container<T> {
             &target.kubernetes.cpu_architectures(),
         );
 
-        let mut tolerations = BTreeMap::<String, String>::new();
+        let mut tolerations = Vec::<utils::Toleration>::new();
         let is_stateful_set = !self.storages.is_empty();
-        if utils::need_target_stable_node_pool(kubernetes, self.min_instances, is_stateful_set) {
+        if let Some(node_pool) = self
+            .advanced_settings
+            .deployment_node_pool
+            .as_deref()
+            .and_then(|node_pool| kubernetes.karpenter_user_node_pool(node_pool))
+        {
+            utils::target_user_node_pool(node_pool, &mut deployment_affinity_node_required, &mut tolerations);
+        } else if utils::need_target_stable_node_pool(kubernetes, self.min_instances, is_stateful_set) {
             utils::target_stable_node_pool(&mut deployment_affinity_node_required, &mut tolerations, is_stateful_set);
         }
 
@@ -232,23 +254,13 @@ impl<T: CloudProvider> Container<T> {
         advanced_settings.deployment_affinity_node_required = deployment_affinity_node_required;
 
         let registry_info = target.container_registry.registry_info();
-        let registry_endpoint = registry_info.get_registry_endpoint(Some(target.kubernetes.cluster_name().as_str()));
-        let registry_endpoint_host = registry_endpoint.host_str().unwrap_or_default();
-        let repository: Cow<str> = if let Some(port) = registry_endpoint.port() {
-            format!("{registry_endpoint_host}:{port}").into()
-        } else {
-            registry_endpoint_host.into()
-        };
-
-        let (_, image_name, image_tag, _) = self
-            .source
-            .compute_cluster_container_registry_url_with_image_name_and_image_tag(
-                self.long_id(),
-                target.kubernetes.long_id(),
-                &target.kubernetes.advanced_settings().registry_mirroring_mode,
-                target.container_registry.registry_info(),
-            );
-        let image_full = format!("{repository}/{image_name}:{image_tag}");
+        let (image_full, image_tag) = self.source.cluster_image_full(self.long_id(), target);
+        let (additional_containers, shared_volumes) = to_additional_containers_tera_context(
+            &self.additional_containers,
+            self.long_id(),
+            self.kube_name(),
+            target,
+        );
 
         ContainerTeraContext {
             organization_long_id: environment.organization_long_id,
@@ -304,6 +316,12 @@ impl<T: CloudProvider> Container<T> {
                 readiness_probe: self.readiness_probe.clone(),
                 liveness_probe: self.liveness_probe.clone(),
                 advanced_settings,
+                event_driven_autoscaling: self
+                    .event_driven_autoscaling
+                    .as_ref()
+                    .map(|autoscaling| autoscaling.to_tera_context(environment.namespace(), self.kube_name())),
+                additional_containers,
+                shared_volumes,
                 legacy_deployment_matchlabels: false,
                 legacy_volumeclaim_template: false,
                 legacy_deployment_from_scaleway: false,
@@ -426,6 +444,15 @@ impl<T: CloudProvider> Service for Container<T> {
     fn get_environment_variables(&self) -> Vec<EnvironmentVariable> {
         self.environment_variables.clone()
     }
+
+    fn resources_request(&self) -> Option<ServiceResourcesRequest> {
+        Some(ServiceResourcesRequest {
+            cpu_in_milli: self.cpu_request_in_milli.clone().into(),
+            ram_in_mib: self.ram_request_in_mib.to_mib(),
+            instances: self.min_instances,
+            node_pool: self.advanced_settings.deployment_node_pool.clone(),
+        })
+    }
 }
 
 pub trait ContainerService: Service + DeploymentAction + ToTeraContext + Send {
@@ -440,6 +467,18 @@ use tera::Context as TeraContext;
 
 impl<T: CloudProvider> ToTeraContext for Container<T> {
     fn to_tera_context(&self, target: &DeploymentTarget) -> Result<TeraContext, Box<EngineError>> {
+        if let Some(event_driven_autoscaling) = &self.event_driven_autoscaling {
+            check_event_driven_autoscaling_is_supported(
+                target,
+                &event_driven_autoscaling.scalers,
+                self.get_event_details(Stage::Environment(self.action().to_environment_step())),
+            )?;
+        }
+        utils::check_node_pool_exists(
+            target.kubernetes,
+            self.advanced_settings.deployment_node_pool.as_deref(),
+            self.get_event_details(Stage::Environment(self.action().to_environment_step())),
+        )?;
         let context = self.default_tera_context(target);
         Ok(TeraContext::from_serialize(context).unwrap_or_default())
     }
@@ -540,10 +579,13 @@ pub(crate) struct ServiceTeraContext {
     pub(crate) readiness_probe: Option<Probe>,
     pub(crate) liveness_probe: Option<Probe>,
     pub(crate) advanced_settings: ContainerAdvancedSettings,
+    pub(crate) event_driven_autoscaling: Option<EventDrivenAutoscalingTeraContext>,
+    pub(crate) additional_containers: Vec<AdditionalContainerTeraContext>,
+    pub(crate) shared_volumes: Vec<SharedVolume>,
     pub(crate) legacy_deployment_matchlabels: bool,
     pub(crate) legacy_volumeclaim_template: bool,
     pub(crate) legacy_deployment_from_scaleway: bool,
-    pub(crate) tolerations: BTreeMap<String, String>,
+    pub(crate) tolerations: Vec<utils::Toleration>,
 }
 
 #[derive(Serialize, Debug, Clone)]
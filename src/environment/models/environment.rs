@@ -11,6 +11,8 @@ use crate::environment::models::job::JobService;
 use crate::environment::models::router::RouterService;
 use crate::environment::models::terraform_service::TerraformServiceTrait;
 use crate::utilities::to_short_id;
+use serde_derive::Serialize;
+use std::fmt::{Display, Formatter};
 use uuid::Uuid;
 
 pub struct Environment {
@@ -33,6 +35,53 @@ pub struct Environment {
     pub jobs: Vec<Box<dyn JobService>>,
     pub helm_charts: Vec<Box<dyn HelmChartService>>,
     pub terraform_services: Vec<Box<dyn TerraformServiceTrait>>,
+    /// Set when the environment is deployed to several clusters and routers records must be routed between them
+    pub dns_routing: Option<DnsRouting>,
+    pub admission_policies: Option<AdmissionPolicies>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AdmissionPolicyMode {
+    /// Violations are reported, manifests are applied anyway
+    Warn,
+    /// Any violation aborts the deployment of the service
+    Enforce,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdmissionPolicies {
+    pub mode: AdmissionPolicyMode,
+    pub policies: Vec<AdmissionPolicy>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdmissionPolicy {
+    pub name: String,
+    pub rego: String,
+}
+
+/// A manifest object rejected by an admission policy
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdmissionPolicyViolation {
+    /// `Kind/name` of the offending object
+    pub object: String,
+    pub policy_name: String,
+    pub message: String,
+}
+
+impl Display for AdmissionPolicyViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}` violates policy `{}`: {}", self.object, self.policy_name, self.message)
+    }
+}
+
+/// Routing policy of the DNS records of a cluster, when the same hostname is served by several clusters
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct DnsRouting {
+    /// Distinguishes the records of the same hostname pointing to different clusters
+    pub set_identifier: String,
+    pub weight: Option<u32>,
+    pub region: Option<String>,
 }
 
 impl Environment {
@@ -81,6 +130,8 @@ impl Environment {
             jobs,
             helm_charts,
             terraform_services,
+            dns_routing: None,
+            admission_policies: None,
         }
     }
 
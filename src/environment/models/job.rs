@@ -1,19 +1,21 @@
 use crate::environment::action::DeploymentAction;
 use crate::environment::models::annotations_group::AnnotationsGroupTeraContext;
+use crate::environment::models::autoscaling::{EventDrivenAutoscaling, ScaledJobTeraContext};
 use crate::environment::models::container::{ClusterTeraContext, RegistryTeraContext};
 use crate::environment::models::labels_group::LabelsGroupTeraContext;
 use crate::environment::models::probe::Probe;
 use crate::environment::models::registry_image_source::RegistryImageSource;
 use crate::environment::models::types::{CloudProvider, ToTeraContext};
 use crate::environment::models::utils;
+use crate::errors::EngineError;
 use crate::events::{EventDetails, Stage, Transmitter};
 use crate::infrastructure::models::build_platform::Build;
 use crate::infrastructure::models::cloud_provider::DeploymentTarget;
-use crate::infrastructure::models::cloud_provider::service::{Action, Service, ServiceType};
+use crate::infrastructure::models::cloud_provider::service::{Action, Service, ServiceResourcesRequest, ServiceType};
 use crate::infrastructure::models::container_registry::DockerRegistryInfo;
 use crate::io_models::annotations_group::AnnotationsGroup;
 use crate::io_models::context::Context;
-use crate::io_models::job::{JobAdvancedSettings, JobSchedule, LifecycleType};
+use crate::io_models::job::{JobAdvancedSettings, JobCompletionMode, JobSchedule, LifecycleType};
 use crate::io_models::labels_group::LabelsGroup;
 use crate::io_models::models::{
     EnvironmentVariable, KubernetesCpuResourceUnit, KubernetesMemoryResourceUnit, MountedFile,
@@ -46,6 +48,8 @@ pub struct Job<T: CloudProvider> {
     pub(crate) schedule: JobSchedule,
     pub(crate) max_nb_restart: u32,
     pub(crate) max_duration: Duration,
+    pub(crate) parallelism: u32,
+    pub(crate) completions: u32,
     pub(crate) default_port: Option<u16>,
     // for probes
     pub(crate) command_args: Vec<String>,
@@ -81,6 +85,8 @@ impl<T: CloudProvider> Job<T> {
         schedule: JobSchedule,
         max_nb_restart: u32,
         max_duration_in_sec: Duration,
+        parallelism: u32,
+        completions: u32,
         default_port: Option<u16>, // for probes
         command_args: Vec<String>,
         entrypoint: Option<String>,
@@ -101,6 +107,8 @@ impl<T: CloudProvider> Job<T> {
         should_delete_shared_registry: bool,
         output_variable_validation_pattern: String,
     ) -> Result<Self, JobError> {
+        validate_job_pods_settings(parallelism, completions, &advanced_settings).map_err(JobError::InvalidConfig)?;
+
         let workspace_directory = crate::fs::workspace_directory(
             context.workspace_root_dir(),
             context.execution_id(),
@@ -125,6 +133,8 @@ impl<T: CloudProvider> Job<T> {
             schedule,
             max_nb_restart,
             max_duration: max_duration_in_sec,
+            parallelism,
+            completions,
             name,
             kube_name,
             command_args,
@@ -174,13 +184,27 @@ impl<T: CloudProvider> Job<T> {
         self.max_nb_restart
     }
 
+    /// Number of pods that must succeed for the job to be complete
+    pub fn completions(&self) -> u32 {
+        self.completions
+    }
+
     pub(crate) fn default_tera_context(&self, target: &DeploymentTarget) -> JobTeraContext {
         let environment = target.environment;
         let kubernetes = target.kubernetes;
-        let deployment_affinity_node_required = utils::add_arch_to_deployment_affinity_node(
+        let mut deployment_affinity_node_required = utils::add_arch_to_deployment_affinity_node(
             &self.advanced_settings.deployment_affinity_node_required,
             &target.kubernetes.cpu_architectures(),
         );
+        let mut tolerations = Vec::<utils::Toleration>::new();
+        if let Some(node_pool) = self
+            .advanced_settings
+            .deployment_node_pool
+            .as_deref()
+            .and_then(|node_pool| kubernetes.karpenter_user_node_pool(node_pool))
+        {
+            utils::target_user_node_pool(node_pool, &mut deployment_affinity_node_required, &mut tolerations);
+        }
         let mut advanced_settings = self.advanced_settings.clone();
         advanced_settings.deployment_affinity_node_required = deployment_affinity_node_required;
 
@@ -236,18 +260,50 @@ impl<T: CloudProvider> Job<T> {
                 default_port: self.default_port,
                 max_nb_restart: self.max_nb_restart,
                 max_duration_in_sec: self.max_duration.as_secs(),
+                parallelism: self.parallelism,
+                completions: self.completions,
+                restart_policy: restart_policy(&self.advanced_settings),
                 with_rbac: matches!(self.schedule.lifecycle_type(), Some(LifecycleType::TERRAFORM)),
                 cronjob_schedule: match &self.schedule {
-                    JobSchedule::OnStart { .. } | JobSchedule::OnPause { .. } | JobSchedule::OnDelete { .. } => None,
+                    JobSchedule::OnStart { .. }
+                    | JobSchedule::OnPause { .. }
+                    | JobSchedule::OnDelete { .. }
+                    | JobSchedule::Event { .. } => None,
                     JobSchedule::Cron { schedule, .. } => Some(schedule.clone()),
                 },
                 cronjob_timezone: match &self.schedule {
-                    JobSchedule::OnStart { .. } | JobSchedule::OnPause { .. } | JobSchedule::OnDelete { .. } => None,
+                    JobSchedule::OnStart { .. }
+                    | JobSchedule::OnPause { .. }
+                    | JobSchedule::OnDelete { .. }
+                    | JobSchedule::Event { .. } => None,
                     JobSchedule::Cron { timezone, .. } => Some(timezone.clone()),
                 },
+                scaled_job: match &self.schedule {
+                    JobSchedule::OnStart { .. }
+                    | JobSchedule::OnPause { .. }
+                    | JobSchedule::OnDelete { .. }
+                    | JobSchedule::Cron { .. } => None,
+                    JobSchedule::Event {
+                        scalers,
+                        max_parallel_jobs,
+                        polling_interval_seconds,
+                    } => Some(
+                        EventDrivenAutoscaling {
+                            scalers: scalers.iter().map(|s| s.to_domain()).collect(),
+                            polling_interval_seconds: *polling_interval_seconds,
+                            cooldown_period_seconds: 0,
+                        }
+                        .to_scaled_job_tera_context(
+                            *max_parallel_jobs,
+                            environment.namespace(),
+                            self.kube_name(),
+                        ),
+                    ),
+                },
                 readiness_probe: self.readiness_probe.clone(),
                 liveness_probe: self.liveness_probe.clone(),
                 advanced_settings,
+                tolerations,
             },
             registry: match &self.image_source {
                 ImageSource::Registry { source } => registry_info.get_registry_docker_json_config(DockerRegistryInfo {
@@ -290,6 +346,15 @@ impl<T: CloudProvider> Job<T> {
         &self.action
     }
 
+    /// Make sure the node pool the job is placed on exists on the cluster
+    pub(crate) fn check_node_pool_exists(&self, target: &DeploymentTarget) -> Result<(), Box<EngineError>> {
+        utils::check_node_pool_exists(
+            target.kubernetes,
+            self.advanced_settings.deployment_node_pool.as_deref(),
+            (self.mk_event_details)(Stage::Environment(self.action.to_environment_step())),
+        )
+    }
+
     fn service_version(&self) -> String {
         match &self.image_source {
             ImageSource::Registry { source: registry } => {
@@ -303,6 +368,10 @@ impl<T: CloudProvider> Job<T> {
         matches!(self.schedule, JobSchedule::Cron { .. })
     }
 
+    pub fn is_scaled_job(&self) -> bool {
+        matches!(self.schedule, JobSchedule::Event { .. })
+    }
+
     pub fn kube_label_selector(&self) -> String {
         format!("qovery.com/service-id={}", self.long_id)
     }
@@ -370,6 +439,7 @@ impl<T: CloudProvider> Service for Job<T> {
                 JobSchedule::OnPause { .. } if self.action == Action::Pause => Some(build),
                 JobSchedule::OnDelete { .. } if self.action == Action::Delete => Some(build),
                 JobSchedule::Cron { .. } if self.action == Action::Create => Some(build),
+                JobSchedule::Event { .. } if self.action == Action::Create => Some(build),
                 _ => None,
             },
         }
@@ -388,6 +458,7 @@ impl<T: CloudProvider> Service for Job<T> {
                     JobSchedule::OnPause { .. } if self.action == Action::Pause => Some(build),
                     JobSchedule::OnDelete { .. } if self.action == Action::Delete => Some(build),
                     JobSchedule::Cron { .. } if self.action == Action::Create => Some(build),
+                    JobSchedule::Event { .. } if self.action == Action::Create => Some(build),
                     _ => None,
                 }
             }
@@ -397,6 +468,67 @@ impl<T: CloudProvider> Service for Job<T> {
     fn get_environment_variables(&self) -> Vec<EnvironmentVariable> {
         self.environment_variables.clone()
     }
+
+    fn resources_request(&self) -> Option<ServiceResourcesRequest> {
+        Some(ServiceResourcesRequest {
+            cpu_in_milli: self.cpu_request_in_milli.clone().into(),
+            ram_in_mib: self.ram_request_in_mib.to_mib(),
+            // jobs do not run permanently, they only need to fit on a node
+            instances: 0,
+            node_pool: self.advanced_settings.deployment_node_pool.clone(),
+        })
+    }
+}
+
+/// Checks the settings spreading the job over several pods are consistent, the kubernetes api would reject them otherwise
+fn validate_job_pods_settings(
+    parallelism: u32,
+    completions: u32,
+    advanced_settings: &JobAdvancedSettings,
+) -> Result<(), String> {
+    if parallelism == 0 || completions == 0 {
+        return Err(format!(
+            "parallelism ({parallelism}) and completions ({completions}) must be at least 1"
+        ));
+    }
+
+    if advanced_settings.job_completion_mode != JobCompletionMode::Indexed
+        && (advanced_settings.job_backoff_limit_per_index.is_some()
+            || advanced_settings.job_max_failed_indexes.is_some())
+    {
+        return Err(
+            "job.backoff_limit_per_index and job.max_failed_indexes require the Indexed completion mode".to_string(),
+        );
+    }
+
+    if advanced_settings.job_max_failed_indexes.is_some() && advanced_settings.job_backoff_limit_per_index.is_none() {
+        return Err("job.max_failed_indexes requires job.backoff_limit_per_index to be set".to_string());
+    }
+
+    let ignore_exit_codes = &advanced_settings.job_pod_failure_policy_ignore_exit_codes;
+    let fail_job_exit_codes = &advanced_settings.job_pod_failure_policy_fail_job_exit_codes;
+    if ignore_exit_codes.contains(&0) || fail_job_exit_codes.contains(&0) {
+        return Err("exit code 0 means success, it cannot be part of a pod failure policy".to_string());
+    }
+    if let Some(exit_code) = ignore_exit_codes.iter().find(|code| fail_job_exit_codes.contains(code)) {
+        return Err(format!(
+            "exit code {exit_code} cannot be both ignored and failing the job in the pod failure policy"
+        ));
+    }
+
+    Ok(())
+}
+
+/// Pod failure policies and per index backoff limits are only allowed by kubernetes when failed pods are not restarted in place
+fn restart_policy(advanced_settings: &JobAdvancedSettings) -> &'static str {
+    if advanced_settings.job_backoff_limit_per_index.is_some()
+        || !advanced_settings.job_pod_failure_policy_ignore_exit_codes.is_empty()
+        || !advanced_settings.job_pod_failure_policy_fail_job_exit_codes.is_empty()
+    {
+        "Never"
+    } else {
+        "OnFailure"
+    }
 }
 
 pub trait JobService: Service + DeploymentAction + ToTeraContext + Send {
@@ -494,12 +626,17 @@ pub(crate) struct ServiceTeraContext {
     pub(crate) default_port: Option<u16>,
     pub(crate) max_nb_restart: u32,
     pub(crate) max_duration_in_sec: u64,
+    pub(crate) parallelism: u32,
+    pub(crate) completions: u32,
+    pub(crate) restart_policy: &'static str,
     pub(crate) with_rbac: bool,
     pub(crate) cronjob_schedule: Option<String>,
     pub(crate) cronjob_timezone: Option<String>,
+    pub(crate) scaled_job: Option<ScaledJobTeraContext>,
     pub(crate) readiness_probe: Option<Probe>,
     pub(crate) liveness_probe: Option<Probe>,
     pub(crate) advanced_settings: JobAdvancedSettings,
+    pub(crate) tolerations: Vec<utils::Toleration>,
 }
 
 #[derive(Serialize, Debug, Clone)]
@@ -519,3 +656,38 @@ pub(crate) struct JobTeraContext {
     pub(crate) annotations_group: AnnotationsGroupTeraContext,
     pub(crate) labels_group: LabelsGroupTeraContext,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_job_pods_settings() {
+        let mut settings = JobAdvancedSettings::default();
+        assert!(validate_job_pods_settings(4, 10, &settings).is_ok());
+        assert!(validate_job_pods_settings(0, 10, &settings).is_err());
+        assert!(validate_job_pods_settings(4, 0, &settings).is_err());
+        assert_eq!(restart_policy(&settings), "OnFailure");
+
+        settings.job_backoff_limit_per_index = Some(2);
+        assert!(validate_job_pods_settings(4, 10, &settings).is_err());
+        settings.job_completion_mode = JobCompletionMode::Indexed;
+        assert!(validate_job_pods_settings(4, 10, &settings).is_ok());
+        assert_eq!(restart_policy(&settings), "Never");
+
+        settings.job_backoff_limit_per_index = None;
+        settings.job_max_failed_indexes = Some(3);
+        assert!(validate_job_pods_settings(4, 10, &settings).is_err());
+        settings.job_max_failed_indexes = None;
+
+        settings.job_pod_failure_policy_ignore_exit_codes = vec![137];
+        settings.job_pod_failure_policy_fail_job_exit_codes = vec![42];
+        assert!(validate_job_pods_settings(4, 10, &settings).is_ok());
+        assert_eq!(restart_policy(&settings), "Never");
+
+        settings.job_pod_failure_policy_fail_job_exit_codes = vec![0];
+        assert!(validate_job_pods_settings(4, 10, &settings).is_err());
+        settings.job_pod_failure_policy_fail_job_exit_codes = vec![137];
+        assert!(validate_job_pods_settings(4, 10, &settings).is_err());
+    }
+}
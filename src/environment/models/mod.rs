@@ -1,6 +1,8 @@
 pub mod abort;
+pub mod additional_container;
 mod annotations_group;
 pub mod application;
+pub mod autoscaling;
 pub mod aws;
 pub mod azure;
 pub mod container;
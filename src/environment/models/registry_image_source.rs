@@ -1,10 +1,12 @@
 use crate::infrastructure::models::cloud_provider::io::RegistryMirroringMode;
 
 use crate::environment::models::container::get_mirror_repository_name;
+use crate::infrastructure::models::cloud_provider::DeploymentTarget;
 use crate::infrastructure::models::container_registry::ContainerRegistryInfo;
 use crate::io_models::QoveryIdentifier;
-use crate::io_models::container::Registry;
+use crate::io_models::container::{ImageArtifact, Registry};
 use crate::string::cut;
+use std::borrow::Cow;
 use url::Url;
 use uuid::Uuid;
 
@@ -12,24 +14,38 @@ pub struct RegistryImageSource {
     pub registry: Registry,
     pub image: String,
     pub tag: String,
+    // When set, the image is loaded from this pre-built artifact instead of the registry
+    pub artifact: Option<ImageArtifact>,
     pub registry_mirroring_mode: RegistryMirroringMode,
 }
 
 impl RegistryImageSource {
     pub fn tag_for_mirror(&self, service_id: &Uuid) -> String {
+        // A new artifact can be provided for the same tag, so its checksum is part of the mirror tag.
+        // Otherwise it would be considered as already mirrored, and the previous artifact would be deployed
+        let tag = match &self.artifact {
+            Some(artifact) => format!(
+                "{}.{}",
+                self.tag,
+                artifact.sha256_checksum().chars().take(16).collect::<String>()
+            ),
+            None => self.tag.clone(),
+        };
+
         // A tag name must be valid ASCII and may contain lowercase and uppercase letters, digits, underscores, periods and dashes.
         // A tag name may not start with a period or a dash and may contain a maximum of 128 characters.
         match self.registry_mirroring_mode {
             RegistryMirroringMode::Service => {
-                cut(format!("{}.{}.{}", self.image.replace('/', "."), self.tag, service_id), 128)
+                cut(format!("{}.{}.{}", self.image.replace('/', "."), tag, service_id), 128)
             }
-            RegistryMirroringMode::Cluster => cut(format!("{}.{}", self.image.replace('/', "."), self.tag), 128),
+            RegistryMirroringMode::Cluster => cut(format!("{}.{}", self.image.replace('/', "."), tag), 128),
         }
     }
 
     ///
     /// This method is used to retrieve information about the image used to start the service.
     /// If the service container registry is the same as the cluster container registry url, no mirroring would be done
+    /// unless the image comes from a pre-built artifact
     /// The result of this method contains:
     /// * the cluster container registry url
     /// * the cluster image name
@@ -49,7 +65,7 @@ impl RegistryImageSource {
         let cluster_container_registry_host = cluster_container_registry.host_str().unwrap_or_default();
         let service_container_registry_host = service_container_registry.host_str().unwrap_or_default();
 
-        if cluster_container_registry_host == service_container_registry_host {
+        if cluster_container_registry_host == service_container_registry_host && self.artifact.is_none() {
             (cluster_container_registry, self.image.to_string(), self.tag.clone(), false)
         } else {
             (
@@ -64,4 +80,79 @@ impl RegistryImageSource {
             )
         }
     }
+
+    /// Full name and tag of the image, as pulled by the cluster nodes from the cluster registry
+    pub fn cluster_image_full(&self, service_id: &Uuid, target: &DeploymentTarget) -> (String, String) {
+        let registry_endpoint = target
+            .container_registry
+            .registry_info()
+            .get_registry_endpoint(Some(target.kubernetes.cluster_name().as_str()));
+        let registry_endpoint_host = registry_endpoint.host_str().unwrap_or_default();
+        let repository: Cow<str> = if let Some(port) = registry_endpoint.port() {
+            format!("{registry_endpoint_host}:{port}").into()
+        } else {
+            registry_endpoint_host.into()
+        };
+
+        let (_, image_name, image_tag, _) = self.compute_cluster_container_registry_url_with_image_name_and_image_tag(
+            service_id,
+            target.kubernetes.long_id(),
+            &target.kubernetes.advanced_settings().registry_mirroring_mode,
+            target.container_registry.registry_info(),
+        );
+        (format!("{repository}/{image_name}:{image_tag}"), image_tag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image_source(
+        artifact: Option<ImageArtifact>,
+        registry_mirroring_mode: RegistryMirroringMode,
+    ) -> RegistryImageSource {
+        RegistryImageSource {
+            registry: Registry::DockerHub {
+                long_id: Uuid::new_v4(),
+                url: Url::parse("https://docker.io").unwrap(),
+                credentials: None,
+            },
+            image: "qovery/api".to_string(),
+            tag: "v1".to_string(),
+            artifact,
+            registry_mirroring_mode,
+        }
+    }
+
+    fn artifact(sha256_checksum: &str) -> ImageArtifact {
+        ImageArtifact::ObjectStorage {
+            bucket_name: "artifacts".to_string(),
+            object_key: "api.tar".to_string(),
+            sha256_checksum: sha256_checksum.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_tag_for_mirror_depends_on_artifact_checksum() {
+        // setup:
+        let service_id = Uuid::new_v4();
+        let checksum_1 = "sha256:9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08";
+        let checksum_2 = "60303ae22b998861bce3b28f33eec1be758a213c86c93c076dbe9f558c11c752";
+
+        for registry_mirroring_mode in [RegistryMirroringMode::Service, RegistryMirroringMode::Cluster] {
+            // execute:
+            let without_artifact = image_source(None, registry_mirroring_mode.clone()).tag_for_mirror(&service_id);
+            let with_artifact_1 =
+                image_source(Some(artifact(checksum_1)), registry_mirroring_mode.clone()).tag_for_mirror(&service_id);
+            let with_artifact_2 =
+                image_source(Some(artifact(checksum_2)), registry_mirroring_mode.clone()).tag_for_mirror(&service_id);
+
+            // verify:
+            assert!(with_artifact_1.starts_with("qovery.api.v1.9f86d081884c7d65"));
+            assert!(with_artifact_2.starts_with("qovery.api.v1.60303ae22b998861"));
+            assert_ne!(without_artifact, with_artifact_1);
+            assert_ne!(with_artifact_1, with_artifact_2, "a new artifact must be mirrored again");
+        }
+    }
 }
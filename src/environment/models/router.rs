@@ -7,16 +7,19 @@ use crate::errors::EngineError;
 use crate::events::{EnvironmentStep, EventDetails, Stage, Transmitter};
 use crate::infrastructure::models::build_platform::Build;
 use crate::infrastructure::models::cloud_provider::DeploymentTarget;
+use crate::infrastructure::models::cloud_provider::io::IngressImplementation;
 use crate::infrastructure::models::cloud_provider::service::{Action, Service, ServiceType, default_tera_context};
 use crate::io_models::annotations_group::AnnotationsGroup;
 use crate::io_models::application::{Port, Protocol};
 use crate::io_models::context::Context;
 use crate::io_models::labels_group::LabelsGroup;
 use crate::io_models::models::{
-    CustomDomain, CustomDomainDataTemplate, EnvironmentVariable, HostDataTemplate, KubeService, KubeServicePort, Route,
+    CustomDomain, CustomDomainDataTemplate, EnvironmentVariable, GatewayHttpListenerDataTemplate,
+    GatewayListenerDataTemplate, GatewayRouteDataTemplate, HostDataTemplate, KubeService, KubeServicePort, Route,
 };
 use crate::utilities::to_short_id;
-use std::collections::HashMap;
+use serde_derive::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::iter;
 use std::marker::PhantomData;
 use std::path::PathBuf;
@@ -61,6 +64,29 @@ impl RouterAdvancedSettings {
     pub fn whitelist_source_range_default_value() -> String {
         "0.0.0.0/0".to_string()
     }
+
+    fn has_access_restrictions(&self) -> bool {
+        self.whitelist_source_range.is_some() || self.denylist_source_range.is_some() || self.basic_auth.is_some()
+    }
+}
+
+/// Gateway API objects rendered instead of nginx ingresses when the cluster ingress implementation is a Gateway.
+/// Each router owns a Gateway with one HTTP listener and one HTTPS listener per hostname, so that listeners of
+/// merged Gateways never conflict. Certificates are issued by cert-manager: through the cluster issuer (DNS01) for
+/// domains managed by the cluster and wildcards, and through an HTTP01 challenge served by the Gateway for other
+/// custom domains.
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub(crate) struct GatewayApiDataTemplate {
+    pub(crate) gateway_class_name: String,
+    pub(crate) security_policy_supported: bool,
+    pub(crate) http_listeners: Vec<GatewayHttpListenerDataTemplate>,
+    pub(crate) listeners: Vec<GatewayListenerDataTemplate>,
+    pub(crate) http_routes_per_namespace: BTreeMap<String, Vec<GatewayRouteDataTemplate>>,
+    pub(crate) grpc_routes_per_namespace: BTreeMap<String, Vec<GatewayRouteDataTemplate>>,
+    pub(crate) cluster_issuer_hostnames: Vec<String>,
+    pub(crate) http01_hostnames: Vec<String>,
+    pub(crate) allowed_source_ranges: Vec<String>,
+    pub(crate) denied_source_ranges: Vec<String>,
 }
 
 pub struct Router<T: CloudProvider> {
@@ -320,6 +346,37 @@ impl<T: CloudProvider> Router<T> {
         };
         context.insert("spec_acme_server", lets_encrypt_url);
 
+        if let Some(dns_routing) = &environment.dns_routing {
+            // Support of the policy by the cluster DNS provider is checked when the environment is built
+            context.insert("dns_routing", dns_routing);
+        }
+
+        let ingress_implementation = &target.kubernetes.advanced_settings().ingress_implementation;
+        if let Some(gateway_class_name) = ingress_implementation.gateway_class_name() {
+            // Source ranges and basic auth are not part of Gateway API spec, only Envoy Gateway SecurityPolicy provides them
+            let security_policy_supported = ingress_implementation == &IngressImplementation::EnvoyGateway;
+            if !security_policy_supported && self.advanced_settings.has_access_restrictions() {
+                return Err(Box::new(EngineError::new_invalid_engine_payload(
+                    event_details,
+                    "Source range allow/deny lists and basic auth are not supported by the cluster ingress implementation",
+                    None,
+                )));
+            }
+
+            let gateway_api = to_gateway_api_data_template(
+                &self.id,
+                gateway_class_name,
+                security_policy_supported,
+                &http_hosts_per_namespace,
+                &grpc_hosts_per_namespace,
+                &self.custom_domains,
+                &cluster_domain,
+                &self.advanced_settings,
+            )
+            .map_err(|message| Box::new(EngineError::new_invalid_engine_payload(event_details, &message, None)))?;
+            context.insert("gateway_api", &gateway_api);
+        }
+
         Ok(context)
     }
 
@@ -433,6 +490,140 @@ fn to_host_data_template(
     hosts_per_namespace
 }
 
+enum GatewayCertificateIssuer {
+    ClusterIssuer,
+    Http01,
+}
+
+/// Issuer of the certificate of the hostname, `None` when TLS is terminated by a CDN in front of the Gateway
+fn gateway_certificate_issuer(
+    hostname: &str,
+    custom_domains: &[CustomDomain],
+    cluster_domain: &str,
+) -> Result<Option<GatewayCertificateIssuer>, String> {
+    let custom_domain = custom_domains.iter().find(|cd| {
+        hostname == cd.domain
+            || hostname == cd.domain_without_wildcard()
+            || hostname.ends_with(&format!(".{}", cd.domain_without_wildcard()))
+    });
+
+    match custom_domain {
+        // certificate is handled by the CDN, hostname is only served over HTTP
+        Some(cd) if !cd.generate_certificate && cd.use_cdn => Ok(None),
+        // there is no default certificate on a Gateway, TLS would have to be passed through to the service
+        Some(cd) if !cd.generate_certificate => Err(format!(
+            "Custom domain `{}` has no certificate and is not behind a CDN, TLS passthrough to the service is not supported by Gateway API routers. Enable certificate generation or the CDN option of the domain",
+            cd.domain
+        )),
+        Some(cd) if cd.is_wildcard() || cd.domain.ends_with(cluster_domain) => {
+            Ok(Some(GatewayCertificateIssuer::ClusterIssuer))
+        }
+        Some(_) => Ok(Some(GatewayCertificateIssuer::Http01)),
+        None => Ok(Some(GatewayCertificateIssuer::ClusterIssuer)),
+    }
+}
+
+fn to_gateway_api_data_template(
+    router_id: &str,
+    gateway_class_name: &str,
+    security_policy_supported: bool,
+    http_hosts_per_namespace: &HashMap<String, Vec<HostDataTemplate>>,
+    grpc_hosts_per_namespace: &HashMap<String, Vec<HostDataTemplate>>,
+    custom_domains: &[CustomDomain],
+    cluster_domain: &str,
+    advanced_settings: &RouterAdvancedSettings,
+) -> Result<GatewayApiDataTemplate, String> {
+    let hostnames: BTreeSet<&str> = http_hosts_per_namespace
+        .values()
+        .chain(grpc_hosts_per_namespace.values())
+        .flatten()
+        .map(|host| host.domain_name.as_str())
+        .collect();
+
+    let mut http_listeners = Vec::with_capacity(hostnames.len());
+    let mut listeners = Vec::with_capacity(hostnames.len());
+    let mut cluster_issuer_hostnames = vec![];
+    let mut http01_hostnames = vec![];
+    for hostname in hostnames {
+        let certificate_issuer = gateway_certificate_issuer(hostname, custom_domains, cluster_domain)?;
+        http_listeners.push(GatewayHttpListenerDataTemplate {
+            name: format!("http-{}", http_listeners.len()),
+            hostname: hostname.to_string(),
+            redirect_to_https: certificate_issuer.is_some(),
+            serves_http01_challenges: matches!(certificate_issuer, Some(GatewayCertificateIssuer::Http01)),
+        });
+        let certificate_secret_name = match certificate_issuer {
+            None => continue,
+            Some(GatewayCertificateIssuer::ClusterIssuer) => {
+                cluster_issuer_hostnames.push(hostname.to_string());
+                format!("router-tls-{router_id}-cluster")
+            }
+            Some(GatewayCertificateIssuer::Http01) => {
+                http01_hostnames.push(hostname.to_string());
+                format!("router-tls-{router_id}")
+            }
+        };
+
+        listeners.push(GatewayListenerDataTemplate {
+            name: format!("https-{}", listeners.len()),
+            hostname: hostname.to_string(),
+            certificate_secret_name,
+        });
+    }
+
+    let to_routes = |hosts_per_namespace: &HashMap<String, Vec<HostDataTemplate>>| {
+        hosts_per_namespace
+            .iter()
+            .map(|(namespace, hosts)| {
+                let routes = hosts
+                    .iter()
+                    .map(|host| GatewayRouteDataTemplate {
+                        hostname: host.domain_name.clone(),
+                        service_name: host.service_name.clone(),
+                        service_port: host.service_port,
+                        listener_name: listeners
+                            .iter()
+                            .map(|listener| (&listener.hostname, &listener.name))
+                            .chain(
+                                http_listeners
+                                    .iter()
+                                    .map(|listener| (&listener.hostname, &listener.name)),
+                            )
+                            .find(|(hostname, _)| **hostname == host.domain_name)
+                            .map(|(_, name)| name.clone())
+                            .unwrap_or_default(),
+                    })
+                    .collect();
+                (namespace.clone(), routes)
+            })
+            .collect::<BTreeMap<String, Vec<GatewayRouteDataTemplate>>>()
+    };
+    let http_routes_per_namespace = to_routes(http_hosts_per_namespace);
+    let grpc_routes_per_namespace = to_routes(grpc_hosts_per_namespace);
+
+    let to_source_ranges = |source_range: &Option<String>| {
+        source_range
+            .iter()
+            .flat_map(|range| range.split(','))
+            .map(|range| range.trim().to_string())
+            .filter(|range| !range.is_empty())
+            .collect::<Vec<String>>()
+    };
+
+    Ok(GatewayApiDataTemplate {
+        gateway_class_name: gateway_class_name.to_string(),
+        security_policy_supported,
+        http_listeners,
+        listeners,
+        http_routes_per_namespace,
+        grpc_routes_per_namespace,
+        cluster_issuer_hostnames,
+        http01_hostnames,
+        allowed_source_ranges: to_source_ranges(&advanced_settings.whitelist_source_range),
+        denied_source_ranges: to_source_ranges(&advanced_settings.denylist_source_range),
+    })
+}
+
 fn to_additional_services(ports: Vec<&Port>) -> Vec<KubeService> {
     ports
         .into_iter()
@@ -602,12 +793,16 @@ where
 #[cfg(test)]
 mod tests {
     use super::to_additional_services;
-    use crate::environment::models::router::{generate_certificate_alternative_names, to_host_data_template};
+    use crate::environment::models::router::{
+        RouterAdvancedSettings, generate_certificate_alternative_names, to_gateway_api_data_template,
+        to_host_data_template,
+    };
     use crate::io_models::application::{Port, Protocol};
     use crate::io_models::models::{
         CustomDomain, CustomDomainDataTemplate, HostDataTemplate, KubeService, KubeServicePort,
     };
     use maplit::btreemap;
+    use std::collections::{BTreeSet, HashMap};
 
     #[test]
     pub fn test_certificate_alternative_names() {
@@ -1073,4 +1268,156 @@ mod tests {
             selectors: btreemap![ "a".to_string() => "b".to_string()],
         }));
     }
+
+    #[test]
+    pub fn test_gateway_api_listeners_and_certificates() {
+        let port_http = Port {
+            long_id: Default::default(),
+            name: "http".to_string(),
+            publicly_accessible: true,
+            port: 80,
+            is_default: true,
+            protocol: Protocol::HTTP,
+            service_name: None,
+            namespace: None,
+            additional_service: None,
+        };
+        let custom_domains = vec![
+            CustomDomain {
+                domain: "toto.com".to_string(),
+                target_domain: "".to_string(),
+                generate_certificate: true,
+                use_cdn: false,
+            },
+            CustomDomain {
+                domain: "*.wildcard.com".to_string(),
+                target_domain: "".to_string(),
+                generate_certificate: true,
+                use_cdn: false,
+            },
+            CustomDomain {
+                domain: "cdn.com".to_string(),
+                target_domain: "".to_string(),
+                generate_certificate: false,
+                use_cdn: true,
+            },
+        ];
+        let namespace = "namespace1";
+        let http_hosts = to_host_data_template(
+            "srv",
+            &[&port_http],
+            "srv.cluster.com",
+            &custom_domains,
+            "cluster.com",
+            namespace,
+        );
+
+        let gateway_api = to_gateway_api_data_template(
+            "z1234",
+            "qovery-envoy-gateway",
+            true,
+            &http_hosts,
+            &HashMap::new(),
+            &custom_domains,
+            "cluster.com",
+            &RouterAdvancedSettings::new(Some("10.0.0.0/8, 192.168.0.0/16".to_string()), None, None),
+        )
+        .unwrap();
+
+        // every hostname has its own HTTP listener, so that listeners of merged Gateways do not conflict
+        let hostnames: BTreeSet<&str> = http_hosts[namespace].iter().map(|h| h.domain_name.as_str()).collect();
+        assert_eq!(gateway_api.http_listeners.len(), hostnames.len());
+        assert!(
+            gateway_api
+                .http_listeners
+                .iter()
+                .all(|l| hostnames.contains(l.hostname.as_str()))
+        );
+
+        // custom domain behind a CDN is only served over HTTP
+        assert!(!gateway_api.listeners.iter().any(|l| l.hostname == "cdn.com"));
+        let cdn_listener = gateway_api
+            .http_listeners
+            .iter()
+            .find(|l| l.hostname == "cdn.com")
+            .unwrap();
+        assert!(!cdn_listener.redirect_to_https);
+        let cdn_route = gateway_api.http_routes_per_namespace[namespace]
+            .iter()
+            .find(|r| r.hostname == "cdn.com")
+            .unwrap();
+        assert_eq!(cdn_route.listener_name, cdn_listener.name);
+
+        // custom domains not managed by the cluster are issued through HTTP01 challenges
+        assert_eq!(
+            gateway_api.http01_hostnames,
+            vec!["http.toto.com".to_string(), "toto.com".to_string()]
+        );
+        let listener = gateway_api.listeners.iter().find(|l| l.hostname == "toto.com").unwrap();
+        assert_eq!(listener.certificate_secret_name, "router-tls-z1234");
+        let http_listener = gateway_api
+            .http_listeners
+            .iter()
+            .find(|l| l.hostname == "toto.com")
+            .unwrap();
+        assert!(http_listener.redirect_to_https && http_listener.serves_http01_challenges);
+
+        // default and wildcard domains are issued by the cluster issuer
+        assert!(
+            gateway_api
+                .cluster_issuer_hostnames
+                .contains(&"srv.cluster.com".to_string())
+        );
+        assert!(
+            gateway_api
+                .cluster_issuer_hostnames
+                .contains(&"*.wildcard.com".to_string())
+        );
+        let listener = gateway_api
+            .listeners
+            .iter()
+            .find(|l| l.hostname == "srv.cluster.com")
+            .unwrap();
+        assert_eq!(listener.certificate_secret_name, "router-tls-z1234-cluster");
+        let route = gateway_api.http_routes_per_namespace[namespace]
+            .iter()
+            .find(|r| r.hostname == "srv.cluster.com")
+            .unwrap();
+        assert_eq!(route.listener_name, listener.name);
+
+        assert_eq!(
+            gateway_api.allowed_source_ranges,
+            vec!["10.0.0.0/8".to_string(), "192.168.0.0/16".to_string()]
+        );
+        assert!(gateway_api.denied_source_ranges.is_empty());
+
+        // custom domain without certificate nor CDN would require TLS passthrough
+        let custom_domains = vec![CustomDomain {
+            domain: "passthrough.com".to_string(),
+            target_domain: "".to_string(),
+            generate_certificate: false,
+            use_cdn: false,
+        }];
+        let http_hosts = to_host_data_template(
+            "srv",
+            &[&port_http],
+            "srv.cluster.com",
+            &custom_domains,
+            "cluster.com",
+            namespace,
+        );
+        assert!(
+            to_gateway_api_data_template(
+                "z1234",
+                "qovery-envoy-gateway",
+                true,
+                &http_hosts,
+                &HashMap::new(),
+                &custom_domains,
+                "cluster.com",
+                &RouterAdvancedSettings::new(None, None, None),
+            )
+            .is_err()
+        );
+    }
 }
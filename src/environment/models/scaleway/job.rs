@@ -6,6 +6,7 @@ use tera::Context as TeraContext;
 
 impl ToTeraContext for Job<SCW> {
     fn to_tera_context(&self, target: &DeploymentTarget) -> Result<TeraContext, Box<EngineError>> {
+        self.check_node_pool_exists(target)?;
         Ok(TeraContext::from_serialize(self.default_tera_context(target)).unwrap_or_default())
     }
 }
@@ -1,7 +1,26 @@
+use crate::errors::EngineError;
+use crate::events::EventDetails;
+use crate::infrastructure::models::kubernetes::karpenter::{KarpenterNodePoolTaintEffect, KarpenterUserNodePool};
 use crate::infrastructure::models::kubernetes::{Kind, Kubernetes};
 use crate::io_models::models::CpuArchitecture;
+use serde_derive::Serialize;
 use std::collections::BTreeMap;
 
+/// Toleration of the pods of a service, for any value of the taint
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Toleration {
+    pub key: String,
+    pub effect: KarpenterNodePoolTaintEffect,
+}
+
+// A node can have several taints with the same key and different effects, each one needs its toleration
+fn add_toleration(tolerations: &mut Vec<Toleration>, key: String, effect: KarpenterNodePoolTaintEffect) {
+    let toleration = Toleration { key, effect };
+    if !tolerations.contains(&toleration) {
+        tolerations.push(toleration);
+    }
+}
+
 pub fn add_arch_to_deployment_affinity_node(
     deployment_affinity_node_required: &BTreeMap<String, String>,
     cpu_architectures: &[CpuArchitecture],
@@ -28,7 +47,7 @@ pub fn need_target_stable_node_pool(kubernetes: &dyn Kubernetes, min_instances:
 
 pub fn target_stable_node_pool(
     deployment_affinity_node_required: &mut BTreeMap<String, String>,
-    tolerations: &mut BTreeMap<String, String>,
+    tolerations: &mut Vec<Toleration>,
     is_stateful_set: bool,
 ) {
     deployment_affinity_node_required
@@ -41,14 +60,51 @@ pub fn target_stable_node_pool(
             .or_insert_with(|| "on-demand".to_string());
     }
 
-    tolerations
-        .entry("nodepool/stable".to_string())
-        .or_insert_with(|| "NoSchedule".to_string());
+    add_toleration(
+        tolerations,
+        "nodepool/stable".to_string(),
+        KarpenterNodePoolTaintEffect::NoSchedule,
+    );
+}
+
+/// Make sure the node pool a service is placed on exists on the cluster
+pub fn check_node_pool_exists(
+    kubernetes: &dyn Kubernetes,
+    node_pool: Option<&str>,
+    event_details: EventDetails,
+) -> Result<(), Box<EngineError>> {
+    match node_pool {
+        Some(node_pool) if kubernetes.karpenter_user_node_pool(node_pool).is_none() => {
+            Err(Box::new(EngineError::new_invalid_engine_payload(
+                event_details,
+                &format!(
+                    "Node pool `{node_pool}` doesn't exist on the cluster, check the `deployment.node_pool` advanced setting"
+                ),
+                None,
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+pub fn target_user_node_pool(
+    node_pool: &KarpenterUserNodePool,
+    deployment_affinity_node_required: &mut BTreeMap<String, String>,
+    tolerations: &mut Vec<Toleration>,
+) {
+    deployment_affinity_node_required.insert("karpenter.sh/nodepool".to_string(), node_pool.name.clone());
+
+    for taint in node_pool.all_taints() {
+        add_toleration(tolerations, taint.key, taint.effect);
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::environment::models::utils::add_arch_to_deployment_affinity_node;
+    use crate::environment::models::utils::{Toleration, add_arch_to_deployment_affinity_node, target_user_node_pool};
+    use crate::infrastructure::models::kubernetes::karpenter::{
+        KarpenterNodePoolTaint, KarpenterNodePoolTaintEffect, KarpenterUserNodePool,
+    };
     use crate::io_models::models::CpuArchitecture;
     use std::collections::BTreeMap;
 
@@ -104,4 +160,55 @@ mod tests {
         assert_eq!(result.len(), 1);
         assert_eq!(result.get("kubernetes.io/arch"), Some(&"value".to_string()));
     }
+
+    #[test]
+    fn test_target_user_node_pool() {
+        let node_pool = KarpenterUserNodePool {
+            name: "batch".to_string(),
+            requirements: vec![],
+            taints: vec![
+                KarpenterNodePoolTaint {
+                    key: "workload".to_string(),
+                    value: Some("batch".to_string()),
+                    effect: KarpenterNodePoolTaintEffect::NoExecute,
+                },
+                KarpenterNodePoolTaint {
+                    key: "workload".to_string(),
+                    value: Some("batch".to_string()),
+                    effect: KarpenterNodePoolTaintEffect::NoSchedule,
+                },
+            ],
+            limits: None,
+            budgets: vec![],
+        };
+        let mut deployment_affinity_node_required = BTreeMap::<String, String>::new();
+        deployment_affinity_node_required.insert("kubernetes.io/arch".to_string(), "amd64".to_string());
+        let mut tolerations = vec![];
+
+        target_user_node_pool(&node_pool, &mut deployment_affinity_node_required, &mut tolerations);
+
+        assert_eq!(deployment_affinity_node_required.len(), 2);
+        assert_eq!(
+            deployment_affinity_node_required.get("karpenter.sh/nodepool"),
+            Some(&"batch".to_string())
+        );
+        // taints sharing a key with different effects each get their toleration
+        assert_eq!(
+            tolerations,
+            vec![
+                Toleration {
+                    key: "nodepool/batch".to_string(),
+                    effect: KarpenterNodePoolTaintEffect::NoSchedule,
+                },
+                Toleration {
+                    key: "workload".to_string(),
+                    effect: KarpenterNodePoolTaintEffect::NoExecute,
+                },
+                Toleration {
+                    key: "workload".to_string(),
+                    effect: KarpenterNodePoolTaintEffect::NoSchedule,
+                },
+            ]
+        );
+    }
 }
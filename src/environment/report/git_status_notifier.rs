@@ -0,0 +1,861 @@
+use crate::engine_task::qovery_api::QoveryApi;
+use crate::events::{EngineEvent, EnvironmentStep, Stage, Transmitter};
+use crate::infrastructure::models::cloud_provider::service::ServiceType;
+use crate::io_models::application::GitCredentials;
+use crate::logger::Logger;
+use chrono::{Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use url::Url;
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum GitProviderKind {
+    Github,
+    Gitlab,
+    Bitbucket,
+}
+
+impl GitProviderKind {
+    fn from_host(host: &str) -> Option<GitProviderKind> {
+        match host {
+            "github.com" => Some(GitProviderKind::Github),
+            "gitlab.com" => Some(GitProviderKind::Gitlab),
+            "bitbucket.org" => Some(GitProviderKind::Bitbucket),
+            _ => None,
+        }
+    }
+
+    fn default_api_url(&self) -> Url {
+        let url = match self {
+            GitProviderKind::Github => "https://api.github.com",
+            GitProviderKind::Gitlab => "https://gitlab.com/api/v4",
+            GitProviderKind::Bitbucket => "https://api.bitbucket.org/2.0",
+        };
+        Url::parse(url).expect("invalid git provider api url")
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GitProviderApi {
+    pub kind: GitProviderKind,
+    pub api_url: Url,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GithubNotificationMode {
+    #[default]
+    CommitStatus,
+    // GitHub Deployments, displayed in the Environments of the repository
+    Deployment,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct GitStatusNotifications {
+    // Link displayed by the git provider next to the status, i.e: the deployment page of the console
+    pub target_url: Option<Url>,
+    pub github_mode: GithubNotificationMode,
+    // Self-hosted git providers, by host of the git url
+    pub self_hosted_providers: HashMap<String, GitProviderApi>,
+}
+
+// Commit deployed by a service, its status is reported to the git provider
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NotifiedCommit {
+    pub service_type: ServiceType,
+    pub service_name: String,
+    pub environment_name: String,
+    pub git_url: String,
+    pub commit_id: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CommitState {
+    Building,
+    Deploying,
+    Success,
+    Failure,
+    Cancelled,
+}
+
+impl CommitState {
+    fn from_step(step: &EnvironmentStep) -> Option<CommitState> {
+        match step {
+            EnvironmentStep::Build => Some(CommitState::Building),
+            EnvironmentStep::Built | EnvironmentStep::Deploy => Some(CommitState::Deploying),
+            EnvironmentStep::Deployed => Some(CommitState::Success),
+            EnvironmentStep::BuiltError | EnvironmentStep::DeployedError => Some(CommitState::Failure),
+            EnvironmentStep::Cancelled => Some(CommitState::Cancelled),
+            _ => None,
+        }
+    }
+
+    fn is_final(&self) -> bool {
+        matches!(self, CommitState::Success | CommitState::Failure | CommitState::Cancelled)
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            CommitState::Building => "Building",
+            CommitState::Deploying => "Deploying",
+            CommitState::Success => "Deployed",
+            CommitState::Failure => "Deployment failed",
+            CommitState::Cancelled => "Deployment cancelled",
+        }
+    }
+
+    fn github_status(&self) -> &'static str {
+        match self {
+            CommitState::Building | CommitState::Deploying => "pending",
+            CommitState::Success => "success",
+            CommitState::Failure => "failure",
+            CommitState::Cancelled => "error",
+        }
+    }
+
+    fn github_deployment_status(&self) -> &'static str {
+        match self {
+            CommitState::Building => "queued",
+            CommitState::Deploying => "in_progress",
+            CommitState::Success => "success",
+            CommitState::Failure => "failure",
+            CommitState::Cancelled => "error",
+        }
+    }
+
+    fn gitlab_status(&self) -> &'static str {
+        // Gitlab refuses to transition a status to itself, so each state must map to a different one
+        match self {
+            CommitState::Building => "pending",
+            CommitState::Deploying => "running",
+            CommitState::Success => "success",
+            CommitState::Failure => "failed",
+            CommitState::Cancelled => "canceled",
+        }
+    }
+
+    fn bitbucket_status(&self) -> &'static str {
+        match self {
+            CommitState::Building | CommitState::Deploying => "INPROGRESS",
+            CommitState::Success => "SUCCESSFUL",
+            CommitState::Failure => "FAILED",
+            CommitState::Cancelled => "STOPPED",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct GitRepository {
+    provider: GitProviderApi,
+    web_url: Url,
+    // i.e: owner/repository
+    path: String,
+}
+
+impl GitRepository {
+    fn from_git_url(git_url: &str, self_hosted_providers: &HashMap<String, GitProviderApi>) -> Option<GitRepository> {
+        // scp like syntax of ssh urls, i.e: git@github.com:owner/repository.git
+        let git_url = match git_url.strip_prefix("git@") {
+            Some(ssh_url) => format!("https://{}", ssh_url.replacen(':', "/", 1)),
+            None => git_url.to_string(),
+        };
+        let url = Url::parse(&git_url).ok()?;
+        let host = url.host_str()?;
+        let provider = match self_hosted_providers.get(host) {
+            Some(provider) => provider.clone(),
+            None => {
+                let kind = GitProviderKind::from_host(host)?;
+                GitProviderApi {
+                    kind,
+                    api_url: kind.default_api_url(),
+                }
+            }
+        };
+        let path = url.path().trim_matches('/').trim_end_matches(".git").to_string();
+        if path.is_empty() {
+            return None;
+        }
+
+        Some(GitRepository {
+            provider,
+            web_url: Url::parse(&format!("https://{host}/{path}")).ok()?,
+            path,
+        })
+    }
+
+    fn api_url(&self, endpoint: &str) -> String {
+        format!("{}/{}", self.provider.api_url.as_str().trim_end_matches('/'), endpoint)
+    }
+}
+
+// Logger reporting the build and deployment progress of services to the git provider of their commit,
+// as commit statuses or GitHub deployments. Events are forwarded as is to the underlying logger.
+// Calls to the git providers are made by a background worker, so a slow git provider never slows down a deployment.
+pub struct GitStatusNotifier {
+    logger: Box<dyn Logger>,
+    commits: Arc<HashMap<Uuid, NotifiedCommit>>,
+    // Last state notified of each service
+    states: Arc<Mutex<HashMap<Uuid, CommitState>>>,
+    notifications: Sender<(Uuid, CommitState)>,
+}
+
+impl GitStatusNotifier {
+    pub fn new(
+        logger: Box<dyn Logger>,
+        options: GitStatusNotifications,
+        commits: HashMap<Uuid, NotifiedCommit>,
+        qovery_api: Arc<dyn QoveryApi>,
+    ) -> Self {
+        let commits = Arc::new(commits);
+        let (notifications, receiver) = mpsc::channel();
+        let worker = NotificationWorker::new(options, commits.clone(), qovery_api);
+        thread::spawn(move || worker.run(receiver));
+
+        GitStatusNotifier::new_with_sender(logger, commits, notifications)
+    }
+
+    fn new_with_sender(
+        logger: Box<dyn Logger>,
+        commits: Arc<HashMap<Uuid, NotifiedCommit>>,
+        notifications: Sender<(Uuid, CommitState)>,
+    ) -> Self {
+        GitStatusNotifier {
+            logger,
+            commits,
+            states: Arc::new(Mutex::new(HashMap::new())),
+            notifications,
+        }
+    }
+
+    fn commit_state_of(&self, event: &EngineEvent) -> Option<(Uuid, CommitState)> {
+        let details = event.get_details();
+        let service_id = match details.transmitter() {
+            Transmitter::Application(id, _) | Transmitter::Job(id, _) => id,
+            _ => return None,
+        };
+        if !self.commits.contains_key(&service_id) {
+            return None;
+        }
+        let Stage::Environment(step) = details.stage() else {
+            return None;
+        };
+
+        Some((service_id, CommitState::from_step(step)?))
+    }
+
+    fn notify(&self, service_id: Uuid, state: CommitState) {
+        // Only transitions are sent, and nothing after a final state
+        {
+            let mut states = self.states.lock().unwrap_or_else(|err| err.into_inner());
+            let previous_state = states.get(&service_id);
+            if previous_state == Some(&state) || previous_state.is_some_and(|s| s.is_final()) {
+                return;
+            }
+            states.insert(service_id, state);
+        }
+
+        // The worker only stops once every notifier is dropped
+        let _ = self.notifications.send((service_id, state));
+    }
+}
+
+// Sends the notifications to the git providers, one at a time and in the order of the events.
+// So the GitHub deployment of a service is created only once, before any of its statuses.
+struct NotificationWorker {
+    options: GitStatusNotifications,
+    commits: Arc<HashMap<Uuid, NotifiedCommit>>,
+    qovery_api: Arc<dyn QoveryApi>,
+    // Git tokens of the services, reused until they expire
+    tokens: HashMap<Uuid, GitCredentials>,
+    github_deployment_ids: HashMap<Uuid, u64>,
+}
+
+impl NotificationWorker {
+    fn new(
+        options: GitStatusNotifications,
+        commits: Arc<HashMap<Uuid, NotifiedCommit>>,
+        qovery_api: Arc<dyn QoveryApi>,
+    ) -> Self {
+        NotificationWorker {
+            options,
+            commits,
+            qovery_api,
+            tokens: HashMap::new(),
+            github_deployment_ids: HashMap::new(),
+        }
+    }
+
+    fn run(mut self, notifications: Receiver<(Uuid, CommitState)>) {
+        for (service_id, state) in notifications {
+            self.notify(service_id, state);
+        }
+    }
+
+    fn git_token(&mut self, service_type: ServiceType, service_id: Uuid) -> anyhow::Result<String> {
+        let is_valid = |credentials: &GitCredentials| credentials.expired_at > Utc::now() + ChronoDuration::minutes(1);
+        if let Some(credentials) = self.tokens.get(&service_id).filter(|credentials| is_valid(credentials)) {
+            return Ok(credentials.access_token.clone());
+        }
+
+        let credentials = self.qovery_api.git_token(service_type, &service_id)?;
+        let token = credentials.access_token.clone();
+        self.tokens.insert(service_id, credentials);
+        Ok(token)
+    }
+
+    fn notify(&mut self, service_id: Uuid, state: CommitState) {
+        let commits = self.commits.clone();
+        let Some(commit) = commits.get(&service_id) else {
+            return;
+        };
+        let Some(repository) = GitRepository::from_git_url(&commit.git_url, &self.options.self_hosted_providers) else {
+            debug!("No git provider known for {}, skipping commit status", commit.git_url);
+            return;
+        };
+        let token = match self.git_token(commit.service_type, service_id) {
+            Ok(token) => token,
+            Err(err) => {
+                warn!(
+                    "Cannot get git token to notify commit status of {}: {}",
+                    commit.service_name, err
+                );
+                return;
+            }
+        };
+
+        let ret = match (repository.provider.kind, self.options.github_mode) {
+            (GitProviderKind::Github, GithubNotificationMode::CommitStatus) => {
+                self.post_github_status(&repository, commit, &token, state)
+            }
+            (GitProviderKind::Github, GithubNotificationMode::Deployment) => {
+                self.post_github_deployment_status(service_id, &repository, commit, &token, state)
+            }
+            (GitProviderKind::Gitlab, _) => self.post_gitlab_status(&repository, commit, &token, state),
+            (GitProviderKind::Bitbucket, _) => self.post_bitbucket_status(&repository, commit, &token, state),
+        };
+
+        if let Err(err) = ret {
+            warn!(
+                "Cannot notify {:?} of commit {} status of {}: {}",
+                repository.provider.kind, commit.commit_id, commit.service_name, err
+            );
+        }
+    }
+
+    fn post_github_status(
+        &self,
+        repository: &GitRepository,
+        commit: &NotifiedCommit,
+        token: &str,
+        state: CommitState,
+    ) -> reqwest::Result<serde_json::Value> {
+        let url = repository.api_url(&format!("repos/{}/statuses/{}", repository.path, commit.commit_id));
+        post_json(
+            &url,
+            token,
+            json!({
+                "state": state.github_status(),
+                "description": state.description(),
+                "context": status_name(commit),
+                "target_url": self.options.target_url,
+            }),
+        )
+    }
+
+    fn post_github_deployment_status(
+        &mut self,
+        service_id: Uuid,
+        repository: &GitRepository,
+        commit: &NotifiedCommit,
+        token: &str,
+        state: CommitState,
+    ) -> reqwest::Result<serde_json::Value> {
+        let deployment_id = match self.github_deployment_ids.get(&service_id) {
+            Some(deployment_id) => *deployment_id,
+            None => {
+                let url = repository.api_url(&format!("repos/{}/deployments", repository.path));
+                let deployment = post_json(
+                    &url,
+                    token,
+                    json!({
+                        "ref": commit.commit_id,
+                        "environment": commit.environment_name,
+                        "description": status_name(commit),
+                        "auto_merge": false,
+                        "required_contexts": [],
+                    }),
+                )?;
+                let deployment_id = deployment.get("id").and_then(|id| id.as_u64()).unwrap_or_default();
+                self.github_deployment_ids.insert(service_id, deployment_id);
+                deployment_id
+            }
+        };
+
+        let url = repository.api_url(&format!("repos/{}/deployments/{}/statuses", repository.path, deployment_id));
+        post_json(
+            &url,
+            token,
+            json!({
+                "state": state.github_deployment_status(),
+                "description": state.description(),
+                "environment": commit.environment_name,
+                "log_url": self.options.target_url,
+                // Services of an environment are deployed independently, one must not deactivate the others
+                "auto_inactive": false,
+            }),
+        )
+    }
+
+    fn post_gitlab_status(
+        &self,
+        repository: &GitRepository,
+        commit: &NotifiedCommit,
+        token: &str,
+        state: CommitState,
+    ) -> reqwest::Result<serde_json::Value> {
+        let url = repository.api_url(&format!(
+            "projects/{}/statuses/{}",
+            urlencoding::encode(&repository.path),
+            commit.commit_id
+        ));
+        post_json(
+            &url,
+            token,
+            json!({
+                "state": state.gitlab_status(),
+                "description": state.description(),
+                "name": status_name(commit),
+                "target_url": self.options.target_url,
+            }),
+        )
+    }
+
+    fn post_bitbucket_status(
+        &self,
+        repository: &GitRepository,
+        commit: &NotifiedCommit,
+        token: &str,
+        state: CommitState,
+    ) -> reqwest::Result<serde_json::Value> {
+        let url = repository.api_url(&format!(
+            "repositories/{}/commit/{}/statuses/build",
+            repository.path, commit.commit_id
+        ));
+        post_json(
+            &url,
+            token,
+            json!({
+                "key": status_name(commit),
+                "state": state.bitbucket_status(),
+                "name": status_name(commit),
+                "description": state.description(),
+                // url is mandatory for bitbucket
+                "url": self.options.target_url.as_ref().unwrap_or(&repository.web_url),
+            }),
+        )
+    }
+}
+
+fn status_name(commit: &NotifiedCommit) -> String {
+    format!("qovery/{}/{}", commit.environment_name, commit.service_name)
+}
+
+fn post_json(url: &str, token: &str, body: serde_json::Value) -> reqwest::Result<serde_json::Value> {
+    let response = reqwest::blocking::Client::builder()
+        .connect_timeout(Duration::from_secs(10))
+        .timeout(Duration::from_secs(30))
+        // GitHub rejects requests without user agent
+        .user_agent("qovery-engine")
+        .build()?
+        .post(url)
+        .bearer_auth(token)
+        .json(&body)
+        .send()?
+        .error_for_status()?;
+
+    // Some providers answer without body
+    Ok(response.json().unwrap_or_default())
+}
+
+impl Logger for GitStatusNotifier {
+    fn log(&self, event: EngineEvent) {
+        let notification = self.commit_state_of(&event);
+        self.logger.log(event);
+
+        if let Some((service_id, state)) = notification {
+            self.notify(service_id, state);
+        }
+    }
+
+    fn clone_dyn(&self) -> Box<dyn Logger> {
+        Box::new(GitStatusNotifier {
+            logger: self.logger.clone_dyn(),
+            commits: self.commits.clone(),
+            states: self.states.clone(),
+            notifications: self.notifications.clone(),
+        })
+    }
+
+    fn with_secrets(&self, secrets: Vec<String>) -> Box<dyn Logger> {
+        Box::new(GitStatusNotifier {
+            logger: self.logger.with_secrets(secrets),
+            commits: self.commits.clone(),
+            states: self.states.clone(),
+            notifications: self.notifications.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine_task::qovery_api::EngineServiceType;
+    use crate::events::{EventDetails, EventMessage};
+    use crate::infrastructure::action::cluster_outputs_helper::ClusterOutputsRequest;
+    use crate::io_models::QoveryIdentifier;
+    use crate::io_models::environment::EnvironmentRequest;
+    use crate::logger::StdIoLogger;
+    use crate::services::cost_allocation_service::EnvironmentCost;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct TokenQoveryApi {
+        git_token_calls: AtomicUsize,
+    }
+
+    impl QoveryApi for TokenQoveryApi {
+        fn service_version(&self, _service_type: EngineServiceType) -> anyhow::Result<String> {
+            Ok("".to_string())
+        }
+
+        fn git_token(&self, _service_type: ServiceType, _service_id: &Uuid) -> anyhow::Result<GitCredentials> {
+            self.git_token_calls.fetch_add(1, Ordering::SeqCst);
+            Ok(GitCredentials {
+                login: "x-access-token".to_string(),
+                access_token: "my-token".to_string(),
+                expired_at: Utc::now() + ChronoDuration::hours(1),
+            })
+        }
+
+        fn update_cluster_outputs(&self, _cluster_outputs_request: &ClusterOutputsRequest) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn environment_request(
+            &self,
+            _organization_id: &Uuid,
+            _environment_id: &Uuid,
+        ) -> anyhow::Result<EnvironmentRequest> {
+            Err(anyhow::anyhow!("not implemented"))
+        }
+
+        fn update_environments_costs(
+            &self,
+            _cluster_id: &Uuid,
+            _environments_costs: &[EnvironmentCost],
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[derive(Debug)]
+    struct ReceivedRequest {
+        path: String,
+        authorization: String,
+        body: serde_json::Value,
+    }
+
+    // Minimal http server answering 201 to every request, and recording them
+    fn start_mock_server() -> (Url, Arc<Mutex<Vec<ReceivedRequest>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        let requests = Arc::new(Mutex::new(vec![]));
+        let received = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut content_length = 0;
+                let mut authorization = String::new();
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    let header = header.trim_end();
+                    if header.is_empty() {
+                        break;
+                    }
+                    let (name, value) = header.split_once(':').unwrap();
+                    match name.to_ascii_lowercase().as_str() {
+                        "content-length" => content_length = value.trim().parse().unwrap(),
+                        "authorization" => authorization = value.trim().to_string(),
+                        _ => {}
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                received.lock().unwrap().push(ReceivedRequest {
+                    path: request_line.split_whitespace().nth(1).unwrap_or_default().to_string(),
+                    authorization,
+                    body: serde_json::from_slice(&body).unwrap_or_default(),
+                });
+
+                let response_body = r#"{"id": 42}"#;
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 201 Created\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    response_body.len(),
+                    response_body
+                );
+            }
+        });
+
+        (url, requests)
+    }
+
+    fn event(service_id: Uuid, step: EnvironmentStep) -> EngineEvent {
+        EngineEvent::Info(
+            EventDetails::new(
+                None,
+                QoveryIdentifier::new_random(),
+                QoveryIdentifier::new_random(),
+                Uuid::new_v4().to_string(),
+                Stage::Environment(step),
+                Transmitter::Application(service_id, "my-app".to_string()),
+            ),
+            EventMessage::new_from_safe("message".to_string()),
+        )
+    }
+
+    struct TestNotifier {
+        notifier: GitStatusNotifier,
+        notifications: Receiver<(Uuid, CommitState)>,
+        worker: NotificationWorker,
+        qovery_api: Arc<TokenQoveryApi>,
+        service_id: Uuid,
+    }
+
+    impl TestNotifier {
+        fn new(git_url: &str, provider: GitProviderApi, github_mode: GithubNotificationMode) -> TestNotifier {
+            let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+            let service_id = Uuid::new_v4();
+            let host = Url::parse(git_url).unwrap().host_str().unwrap().to_string();
+            let commits = Arc::new(HashMap::from([(
+                service_id,
+                NotifiedCommit {
+                    service_type: ServiceType::Application,
+                    service_name: "my-app".to_string(),
+                    environment_name: "production".to_string(),
+                    git_url: git_url.to_string(),
+                    commit_id: "abc123".to_string(),
+                },
+            )]));
+            let qovery_api = Arc::new(TokenQoveryApi::default());
+            let worker = NotificationWorker::new(
+                GitStatusNotifications {
+                    target_url: Some(Url::parse("https://console.qovery.com/deployment").unwrap()),
+                    github_mode,
+                    self_hosted_providers: HashMap::from([(host, provider)]),
+                },
+                commits.clone(),
+                qovery_api.clone(),
+            );
+            let (sender, notifications) = mpsc::channel();
+
+            TestNotifier {
+                notifier: GitStatusNotifier::new_with_sender(Box::new(StdIoLogger::new()), commits, sender),
+                notifications,
+                worker,
+                qovery_api,
+                service_id,
+            }
+        }
+
+        // Sends the notifications queued so far, as the background worker would
+        fn log(&mut self, event: EngineEvent) {
+            self.notifier.log(event);
+            for (service_id, state) in self.notifications.try_iter() {
+                self.worker.notify(service_id, state);
+            }
+        }
+    }
+
+    #[test]
+    fn test_git_repository_from_git_url() {
+        let repository = GitRepository::from_git_url("https://github.com/Qovery/engine.git", &HashMap::new()).unwrap();
+        assert_eq!(repository.provider.kind, GitProviderKind::Github);
+        assert_eq!(repository.path, "Qovery/engine");
+        assert_eq!(repository.api_url("repos"), "https://api.github.com/repos");
+
+        let repository = GitRepository::from_git_url("git@gitlab.com:group/sub/project.git", &HashMap::new()).unwrap();
+        assert_eq!(repository.provider.kind, GitProviderKind::Gitlab);
+        assert_eq!(repository.path, "group/sub/project");
+
+        assert!(GitRepository::from_git_url("https://git.mycompany.com/team/app.git", &HashMap::new()).is_none());
+        let self_hosted = HashMap::from([(
+            "git.mycompany.com".to_string(),
+            GitProviderApi {
+                kind: GitProviderKind::Gitlab,
+                api_url: Url::parse("https://git.mycompany.com/api/v4").unwrap(),
+            },
+        )]);
+        let repository = GitRepository::from_git_url("https://git.mycompany.com/team/app.git", &self_hosted).unwrap();
+        assert_eq!(repository.api_url("projects"), "https://git.mycompany.com/api/v4/projects");
+    }
+
+    #[test]
+    fn test_notifier_sends_only_transitions() {
+        // setup:
+        let (sender, notifications) = mpsc::channel();
+        let service_id = Uuid::new_v4();
+        let commits = Arc::new(HashMap::from([(
+            service_id,
+            NotifiedCommit {
+                service_type: ServiceType::Application,
+                service_name: "my-app".to_string(),
+                environment_name: "production".to_string(),
+                git_url: "https://github.com/Qovery/engine.git".to_string(),
+                commit_id: "abc123".to_string(),
+            },
+        )]));
+        let notifier = GitStatusNotifier::new_with_sender(Box::new(StdIoLogger::new()), commits, sender);
+        let cloned_notifier = notifier.clone_dyn();
+
+        // execute:
+        notifier.log(event(service_id, EnvironmentStep::Build));
+        cloned_notifier.log(event(service_id, EnvironmentStep::Build));
+        notifier.log(event(service_id, EnvironmentStep::Built));
+        cloned_notifier.log(event(service_id, EnvironmentStep::Deploy));
+        notifier.log(event(service_id, EnvironmentStep::DeployedError));
+        // Nothing is sent after a final state, nor for unknown services
+        cloned_notifier.log(event(service_id, EnvironmentStep::Deployed));
+        notifier.log(event(Uuid::new_v4(), EnvironmentStep::Build));
+
+        // verify:
+        let states: Vec<CommitState> = notifications.try_iter().map(|(_, state)| state).collect();
+        assert_eq!(
+            states,
+            vec![CommitState::Building, CommitState::Deploying, CommitState::Failure]
+        );
+    }
+
+    #[test]
+    fn test_github_commit_status_transitions() {
+        let (api_url, requests) = start_mock_server();
+        let mut notifier = TestNotifier::new(
+            "https://github.com/Qovery/engine.git",
+            GitProviderApi {
+                kind: GitProviderKind::Github,
+                api_url,
+            },
+            GithubNotificationMode::CommitStatus,
+        );
+        let service_id = notifier.service_id;
+
+        notifier.log(event(service_id, EnvironmentStep::Build));
+        notifier.log(event(service_id, EnvironmentStep::Build));
+        notifier.log(event(service_id, EnvironmentStep::Deploy));
+        notifier.log(event(service_id, EnvironmentStep::Deployed));
+        // Nothing is sent after a final state, nor for unknown services
+        notifier.log(event(service_id, EnvironmentStep::Deploy));
+        notifier.log(event(Uuid::new_v4(), EnvironmentStep::Build));
+
+        // The git token is asked once, and reused until it expires
+        assert_eq!(notifier.qovery_api.git_token_calls.load(Ordering::SeqCst), 1);
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert!(
+            requests
+                .iter()
+                .all(|r| r.path == "/repos/Qovery/engine/statuses/abc123")
+        );
+        assert!(requests.iter().all(|r| r.authorization == "Bearer my-token"));
+        let states: Vec<&str> = requests.iter().map(|r| r.body["state"].as_str().unwrap()).collect();
+        assert_eq!(states, vec!["pending", "pending", "success"]);
+        assert_eq!(requests[0].body["context"], "qovery/production/my-app");
+        assert_eq!(requests[0].body["target_url"], "https://console.qovery.com/deployment");
+    }
+
+    #[test]
+    fn test_github_deployment_statuses() {
+        let (api_url, requests) = start_mock_server();
+        let mut notifier = TestNotifier::new(
+            "https://github.com/Qovery/engine.git",
+            GitProviderApi {
+                kind: GitProviderKind::Github,
+                api_url,
+            },
+            GithubNotificationMode::Deployment,
+        );
+        let service_id = notifier.service_id;
+
+        notifier.log(event(service_id, EnvironmentStep::Build));
+        notifier.log(event(service_id, EnvironmentStep::BuiltError));
+
+        let requests = requests.lock().unwrap();
+        let paths: Vec<&str> = requests.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "/repos/Qovery/engine/deployments",
+                "/repos/Qovery/engine/deployments/42/statuses",
+                "/repos/Qovery/engine/deployments/42/statuses",
+            ]
+        );
+        assert_eq!(requests[0].body["ref"], "abc123");
+        assert_eq!(requests[0].body["environment"], "production");
+        assert_eq!(requests[1].body["state"], "queued");
+        assert_eq!(requests[2].body["state"], "failure");
+    }
+
+    #[test]
+    fn test_gitlab_and_bitbucket_statuses() {
+        let (api_url, requests) = start_mock_server();
+        let mut notifier = TestNotifier::new(
+            "https://gitlab.com/group/project.git",
+            GitProviderApi {
+                kind: GitProviderKind::Gitlab,
+                api_url: api_url.clone(),
+            },
+            GithubNotificationMode::Deployment,
+        );
+        let service_id = notifier.service_id;
+        notifier.log(event(service_id, EnvironmentStep::Build));
+        notifier.log(event(service_id, EnvironmentStep::Cancelled));
+
+        let mut notifier = TestNotifier::new(
+            "https://bitbucket.org/workspace/repository.git",
+            GitProviderApi {
+                kind: GitProviderKind::Bitbucket,
+                api_url,
+            },
+            GithubNotificationMode::CommitStatus,
+        );
+        let service_id = notifier.service_id;
+        notifier.log(event(service_id, EnvironmentStep::Deploy));
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].path, "/projects/group%2Fproject/statuses/abc123");
+        assert_eq!(requests[0].body["state"], "pending");
+        assert_eq!(requests[1].body["state"], "canceled");
+        assert_eq!(requests[1].body["name"], "qovery/production/my-app");
+        assert_eq!(
+            requests[2].path,
+            "/repositories/workspace/repository/commit/abc123/statuses/build"
+        );
+        assert_eq!(requests[2].body["state"], "INPROGRESS");
+        assert_eq!(requests[2].body["url"], "https://console.qovery.com/deployment");
+    }
+}
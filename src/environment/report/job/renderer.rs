@@ -25,7 +25,13 @@ const REPORT_TEMPLATE: &str = r#"
 ┃ 🛰 {{ job_type | capitalize }} at new tag {{ tag }} has {{ pods_current_version.nb_pods }} pods: {{ pods_current_version.pods_running | length }} running, {{ pods_current_version.pods_starting | length }} starting, {{ pods_current_version.pods_terminating | length }} terminating and {{ pods_current_version.pods_failing | length }} in error
 {%- set all_current_version_pods = pods_current_version.pods_failing | concat(with=pods_current_version.pods_starting) -%}
 {%- if job %}
-┃  |__ Job {{ job.name }}
+┃  |__ Job {{ job.name }}: {{ job.succeeded }}/{{ job.completions }} pods succeeded, {{ job.active }} running and {{ job.failed }} failed
+{%- if job.completed_indexes %}
+┃     |__ ✅ Completed indexes: {{ job.completed_indexes }}
+{%- endif -%}
+{%- if job.failed_indexes %}
+┃     |__ ❌ Failed indexes: {{ job.failed_indexes }}
+{%- endif -%}
 {%- for event in job.events %}
 ┃     |__ {{ event.type_ | fmt_event_type }} {{ event.message }}
 {%- endfor -%}
@@ -106,6 +112,12 @@ mod test {
                     message: "Error creating: pods \"job-z5a0dd39e-job-\" is forbidden".to_string(),
                     type_: "Warning".to_string(),
                 }],
+                completions: 4,
+                succeeded: 2,
+                active: 1,
+                failed: 1,
+                completed_indexes: Some("0,2".to_string()),
+                failed_indexes: Some("1".to_string()),
             }),
             pods_old_version: PodsRenderContext {
                 nb_pods: 0,
@@ -154,7 +166,9 @@ mod test {
 ┃
 ┃ 🛰 Job at old version has 0 pods: 0 running, 0 starting, 0 terminating and 0 in error
 ┃ 🛰 Job at new tag public.ecr.aws/r3m4q3r9/pub-mirror-debian:11.6 has 1 pods: 0 running, 0 starting, 0 terminating and 1 in error
-┃  |__ Job job-z5a0dd39e-job
+┃  |__ Job job-z5a0dd39e-job: 2/4 pods succeeded, 1 running and 1 failed
+┃     |__ ✅ Completed indexes: 0,2
+┃     |__ ❌ Failed indexes: 1
 ┃     |__ ⚠️ Error creating: pods "job-z5a0dd39e-job-" is forbidden
 ┃  |__ Pod app-pod-1 is FAILING
 ┃     |__ 💭 Pod have been killed due to lack of/using too much memory resources
@@ -29,6 +29,7 @@ use uuid::Uuid;
 pub(crate) enum JobType {
     CronJob(String),
     Job(Action),
+    ScaledJob,
 }
 
 impl Display for JobType {
@@ -36,6 +37,7 @@ impl Display for JobType {
         match self {
             JobType::CronJob(_) => f.write_str("cron-job"),
             JobType::Job(_) => f.write_str("job"),
+            JobType::ScaledJob => f.write_str("scaled-job"),
         }
     }
 }
@@ -44,6 +46,7 @@ pub struct JobDeploymentReporter<T> {
     long_id: Uuid,
     job_type: JobType,
     is_force_trigger: bool,
+    is_cronjob_suspended: bool,
     max_duration: Duration,
     max_restarts: u32,
     action: Action,
@@ -72,6 +75,7 @@ impl<T> JobDeploymentReporter<T> {
                 schedule,
                 timezone: _timezone,
             } => JobType::CronJob(schedule.to_string()),
+            JobSchedule::Event { .. } => JobType::ScaledJob,
         };
 
         JobDeploymentReporter {
@@ -79,6 +83,7 @@ impl<T> JobDeploymentReporter<T> {
             job_type,
             action,
             is_force_trigger: job.is_force_trigger(),
+            is_cronjob_suspended: job.advanced_settings().cronjob_suspend,
             max_duration: *job.max_duration(),
             max_restarts: job.max_restarts(),
             tag: job.version(),
@@ -151,6 +156,9 @@ impl<T: Send + Sync> DeploymentReporter for JobDeploymentReporter<T> {
                     self.tag,
                     self.max_duration_human_str()
                 )),
+                JobType::ScaledJob => self
+                    .logger
+                    .send_progress(format!("🚀 Deployment of event driven job at tag {} is starting", self.tag)),
             }
 
             return;
@@ -171,10 +179,20 @@ impl<T: Send + Sync> DeploymentReporter for JobDeploymentReporter<T> {
                     ));
                 }
             }
-            JobType::CronJob(schedule) => self.logger.send_progress(format!(
-                "🚀 Deployment of cronjob with schedule `{}` at tag {} is starting",
-                schedule, self.tag
-            )),
+            JobType::CronJob(schedule) => {
+                self.logger.send_progress(format!(
+                    "🚀 Deployment of cronjob with schedule `{}` at tag {} is starting",
+                    schedule, self.tag
+                ));
+                if self.is_cronjob_suspended {
+                    self.logger.send_progress(
+                        "⏸️ Cronjob is suspended, it won't be scheduled until it is resumed".to_string(),
+                    );
+                }
+            }
+            JobType::ScaledJob => self
+                .logger
+                .send_progress(format!("🚀 Deployment of event driven job at tag {} is starting", self.tag)),
         }
     }
 
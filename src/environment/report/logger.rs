@@ -26,6 +26,8 @@ impl EnvLogger {
             EnvironmentStep::Delete => (EnvironmentStep::Delete, EnvironmentStep::Deleted),
             EnvironmentStep::Build => (EnvironmentStep::Build, EnvironmentStep::Built),
             EnvironmentStep::Restart => (EnvironmentStep::Restart, EnvironmentStep::Restarted),
+            // A dry run never changes the status of the service
+            EnvironmentStep::DryRun => (EnvironmentStep::DryRun, EnvironmentStep::DryRun),
             _ => panic!("Invalid environment step for logger"),
         };
         let event_details_progress = service.get_event_details(Stage::Environment(progress_step));
@@ -7,6 +7,7 @@ use std::time::Duration;
 
 pub mod application;
 pub mod database;
+pub mod git_status_notifier;
 pub mod helm_chart;
 pub mod job;
 pub mod logger;
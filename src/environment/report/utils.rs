@@ -8,7 +8,7 @@ use k8s_openapi::api::core::v1::{
 };
 use k8s_openapi::apimachinery::pkg::apis::meta::v1;
 use serde::Serialize;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use tera::Tera;
 
 #[derive(Debug, Serialize)]
@@ -75,6 +75,14 @@ pub struct JobRenderContext {
     pub state: DeploymentState,
     pub message: Option<String>,
     pub events: Vec<EventRenderContext>,
+    /// Number of pods that must succeed for the job to be complete
+    pub completions: i32,
+    pub succeeded: i32,
+    pub active: i32,
+    pub failed: i32,
+    /// Only set for indexed jobs, in the kubernetes compressed format (i.e: 1,3-5)
+    pub completed_indexes: Option<String>,
+    pub failed_indexes: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -238,6 +246,8 @@ pub fn to_job_render_context(job: &Job, events: &[Event]) -> JobRenderContext {
             )
         });
 
+    let status = job.status.as_ref();
+
     JobRenderContext {
         name: job_name.to_string(),
         state,
@@ -246,6 +256,16 @@ pub fn to_job_render_context(job: &Job, events: &[Event]) -> JobRenderContext {
             .into_iter()
             .flat_map(to_event_context)
             .collect(),
+        completions: job.spec.as_ref().and_then(|spec| spec.completions).unwrap_or(1),
+        succeeded: status.and_then(|status| status.succeeded).unwrap_or(0),
+        active: status.and_then(|status| status.active).unwrap_or(0),
+        failed: status.and_then(|status| status.failed).unwrap_or(0),
+        completed_indexes: status
+            .and_then(|status| status.completed_indexes.clone())
+            .filter(|indexes| !indexes.is_empty()),
+        failed_indexes: status
+            .and_then(|status| status.failed_indexes.clone())
+            .filter(|indexes| !indexes.is_empty()),
     }
 }
 
@@ -302,7 +322,7 @@ pub fn to_pods_render_context_by_state(
             pods_starting.push(PodRenderContext {
                 name: pod_name.to_string(),
                 state: DeploymentState::Starting,
-                message: None,
+                message: pod.pending_init_container(),
                 container_states: pod.container_states(),
                 service_version: pod.service_version(),
                 events: get_last_events_for(events.iter(), pod_uid, DEFAULT_MAX_EVENTS, OnlyWarningIfAny)
@@ -505,6 +525,7 @@ pub trait QPodExt {
     fn container_states(&self) -> BTreeMap<String, QContainerState>;
     fn is_starting(&self) -> bool;
     fn is_failing(&self) -> Option<&str>;
+    fn pending_init_container(&self) -> Option<String>;
     fn service_version(&self) -> Option<String>;
 }
 
@@ -513,9 +534,10 @@ impl QPodExt for Pod {
         match &self.status {
             None => BTreeMap::new(),
             Some(status) => status
-                .container_statuses
+                .init_container_statuses
                 .iter()
                 .flatten()
+                .chain(status.container_statuses.iter().flatten())
                 .filter_map(|status| {
                     status.last_state.as_ref().map(|state| {
                         (
@@ -599,10 +621,15 @@ impl QPodExt for Pod {
         // if there is something fishy or not, not really friendly...
         match self.status.as_ref() {
             Some(PodStatus {
-                container_statuses: Some(statuses),
+                init_container_statuses,
+                container_statuses,
                 ..
             }) => {
-                for status in statuses {
+                for status in init_container_statuses
+                    .iter()
+                    .flatten()
+                    .chain(container_statuses.iter().flatten())
+                {
                     match &status.state {
                         Some(ContainerState {
                             waiting: Some(ContainerStateWaiting { reason: Some(r), .. }),
@@ -621,6 +648,40 @@ impl QPodExt for Pod {
         }
     }
 
+    fn pending_init_container(&self) -> Option<String> {
+        // Sidecars are init containers kept running, they only need to be started for the pod to go on
+        let sidecars: HashSet<&str> = self
+            .spec
+            .iter()
+            .flat_map(|spec| spec.init_containers.iter().flatten())
+            .filter(|container| container.restart_policy.as_deref() == Some("Always"))
+            .map(|container| container.name.as_str())
+            .collect();
+        let statuses = self.status.as_ref()?.init_container_statuses.as_ref()?;
+
+        statuses.iter().find_map(|status| {
+            let reason = status
+                .state
+                .as_ref()
+                .and_then(|state| state.waiting.as_ref())
+                .and_then(|waiting| waiting.reason.as_deref())
+                .map(|reason| format!(" ({reason})"))
+                .unwrap_or_default();
+
+            if sidecars.contains(status.name.as_str()) {
+                match status.started {
+                    Some(true) => None,
+                    _ => Some(format!("Waiting for sidecar container {} to start{}", status.name, reason)),
+                }
+            } else {
+                match status.state.as_ref().and_then(|state| state.terminated.as_ref()) {
+                    Some(terminated) if terminated.exit_code == 0 => None,
+                    _ => Some(format!("Waiting for init container {} to complete{}", status.name, reason)),
+                }
+            }
+        })
+    }
+
     fn service_version(&self) -> Option<String> {
         let annotations = &self.metadata.annotations.clone()?;
         annotations.get("qovery.com/service-version").cloned()
@@ -661,3 +722,69 @@ pub fn get_last_events_for<'a>(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pod_with_init_containers(init_container_statuses: serde_json::Value) -> Pod {
+        serde_json::from_value(serde_json::json!({
+            "metadata": { "name": "app-pod-1" },
+            "spec": {
+                "initContainers": [
+                    { "name": "cloud-sql-proxy", "restartPolicy": "Always" },
+                    { "name": "migrations" }
+                ],
+                "containers": [{ "name": "app" }]
+            },
+            "status": {
+                "phase": "Pending",
+                "initContainerStatuses": init_container_statuses,
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_pending_init_container() {
+        let sidecar_not_started = pod_with_init_containers(serde_json::json!([
+            { "name": "cloud-sql-proxy", "image": "proxy", "imageID": "", "ready": false, "restartCount": 0, "started": false,
+              "state": { "waiting": { "reason": "PodInitializing" } } },
+            { "name": "migrations", "image": "migrations", "imageID": "", "ready": false, "restartCount": 0,
+              "state": { "waiting": { "reason": "PodInitializing" } } }
+        ]));
+        assert_eq!(
+            sidecar_not_started.pending_init_container().as_deref(),
+            Some("Waiting for sidecar container cloud-sql-proxy to start (PodInitializing)")
+        );
+
+        let migrations_running = pod_with_init_containers(serde_json::json!([
+            { "name": "cloud-sql-proxy", "image": "proxy", "imageID": "", "ready": false, "restartCount": 0, "started": true,
+              "state": { "running": {} } },
+            { "name": "migrations", "image": "migrations", "imageID": "", "ready": false, "restartCount": 0,
+              "state": { "running": {} } }
+        ]));
+        assert_eq!(
+            migrations_running.pending_init_container().as_deref(),
+            Some("Waiting for init container migrations to complete")
+        );
+
+        let migrations_crashing = pod_with_init_containers(serde_json::json!([
+            { "name": "cloud-sql-proxy", "image": "proxy", "imageID": "", "ready": true, "restartCount": 0, "started": true,
+              "state": { "running": {} } },
+            { "name": "migrations", "image": "migrations", "imageID": "", "ready": false, "restartCount": 3,
+              "lastState": { "terminated": { "exitCode": 1, "reason": "Error" } },
+              "state": { "waiting": { "reason": "CrashLoopBackOff" } } }
+        ]));
+        assert!(migrations_crashing.is_failing().is_some());
+        assert_eq!(migrations_crashing.container_states()["migrations"].restart_count, 3);
+
+        let initialized = pod_with_init_containers(serde_json::json!([
+            { "name": "cloud-sql-proxy", "image": "proxy", "imageID": "", "ready": true, "restartCount": 0, "started": true,
+              "state": { "running": {} } },
+            { "name": "migrations", "image": "migrations", "imageID": "", "ready": false, "restartCount": 0,
+              "state": { "terminated": { "exitCode": 0, "reason": "Completed" } } }
+        ]));
+        assert_eq!(initialized.pending_init_container(), None);
+    }
+}
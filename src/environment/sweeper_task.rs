@@ -0,0 +1,620 @@
+use crate::cmd::docker::Docker;
+use crate::engine_task::Task;
+use crate::engine_task::qovery_api::QoveryApi;
+use crate::environment::models::abort::{Abort, AbortStatus, AtomicAbortStatus};
+use crate::environment::task::EnvironmentTask;
+use crate::errors::{CommandError, EngineError, ErrorMessageVerbosity};
+use crate::events::{EngineEvent, EnvironmentStep, EventDetails, EventMessage, Stage};
+use crate::infrastructure::infrastructure_context::InfrastructureContext;
+use crate::io_models::Action;
+use crate::io_models::context::Context;
+use crate::io_models::engine_request::InfrastructureEngineRequest;
+use crate::io_models::environment::{EnvironmentLifecycle, EnvironmentRequest, ExpirationAction};
+use crate::logger::Logger;
+use crate::metrics_registry::MetricsRegistry;
+use crate::runtime::block_on;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use k8s_openapi::ByteString;
+use k8s_openapi::api::core::v1::{Namespace, Secret};
+use kube::Api;
+use kube::api::{DeleteParams, ListParams, ObjectMeta, Patch, PatchParams};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, RwLock};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+/// RFC 3339 date after which the environment is paused or deleted by the `EnvironmentSweeperTask`
+pub const EXPIRE_AT_ANNOTATION: &str = "qovery.com/expire-at";
+pub const ACTION_ON_EXPIRATION_ANNOTATION: &str = "qovery.com/action-on-expiration";
+pub const EXPIRATION_ACTION_PAUSE: &str = "pause";
+pub const EXPIRATION_ACTION_DELETE: &str = "delete";
+
+/// Identity and expiration of an ephemeral environment, stored in its namespace when it has a lifecycle.
+/// Only ids are kept, the sweeper fetches the up to date environment definition from the core API once it has expired
+const ENVIRONMENT_SNAPSHOT_SECRET_NAME: &str = "qovery-environment-snapshot";
+const ENVIRONMENT_SNAPSHOT_KEY: &str = "snapshot.json";
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct EnvironmentSnapshot {
+    pub organization_long_id: Uuid,
+    pub environment_long_id: Uuid,
+    pub action_on_expiration: Action,
+    pub expire_at: DateTime<Utc>,
+}
+
+impl EnvironmentSnapshot {
+    fn new(environment: &EnvironmentRequest, lifecycle: &EnvironmentLifecycle, now: DateTime<Utc>) -> Self {
+        EnvironmentSnapshot {
+            organization_long_id: environment.organization_long_id,
+            environment_long_id: environment.long_id,
+            action_on_expiration: match lifecycle.action_on_expiration {
+                ExpirationAction::Pause => Action::Pause,
+                ExpirationAction::Delete => Action::Delete,
+            },
+            expire_at: now + ChronoDuration::seconds(lifecycle.ttl_in_seconds as i64),
+        }
+    }
+
+    fn expiration_annotations(&self) -> serde_json::Value {
+        json!({
+            EXPIRE_AT_ANNOTATION: self.expire_at.to_rfc3339(),
+            ACTION_ON_EXPIRATION_ANNOTATION: match self.action_on_expiration {
+                Action::Delete => EXPIRATION_ACTION_DELETE,
+                _ => EXPIRATION_ACTION_PAUSE,
+            },
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) struct ExpiredEnvironment {
+    pub namespace: String,
+    pub expired_at: DateTime<Utc>,
+    pub action: Action,
+}
+
+/// Pushes back the expiration of a successfully deployed environment, or removes it if the environment is not
+/// ephemeral anymore. The snapshot is written before the namespace annotations, so an expiring namespace has always one
+pub(crate) fn save_environment_snapshot(
+    kube: &kube::Client,
+    environment: &EnvironmentRequest,
+    event_details: EventDetails,
+) -> Result<(), Box<EngineError>> {
+    let namespace = environment.kube_name.as_str();
+    let secrets: Api<Secret> = Api::namespaced(kube.clone(), namespace);
+
+    let expiration_annotations = match &environment.lifecycle {
+        Some(lifecycle) => {
+            let snapshot = EnvironmentSnapshot::new(environment, lifecycle, Utc::now());
+            let data = serde_json::to_vec(&snapshot).map_err(|err| {
+                EngineError::new_k8s_patch_secret_error(event_details.clone(), CommandError::from(err))
+            })?;
+            let secret = Secret {
+                metadata: ObjectMeta {
+                    name: Some(ENVIRONMENT_SNAPSHOT_SECRET_NAME.to_string()),
+                    namespace: Some(namespace.to_string()),
+                    labels: Some(BTreeMap::from([(
+                        "qovery.com/environment-id".to_string(),
+                        environment.long_id.to_string(),
+                    )])),
+                    ..Default::default()
+                },
+                data: Some(BTreeMap::from([(ENVIRONMENT_SNAPSHOT_KEY.to_string(), ByteString(data))])),
+                ..Default::default()
+            };
+
+            block_on(secrets.patch(
+                ENVIRONMENT_SNAPSHOT_SECRET_NAME,
+                &PatchParams::apply("qovery").force(),
+                &Patch::Apply(secret),
+            ))
+            .map_err(|err| {
+                EngineError::new_k8s_patch_secret_error(
+                    event_details.clone(),
+                    CommandError::new(
+                        format!("Cannot save environment snapshot in namespace {namespace}"),
+                        Some(err.to_string()),
+                        None,
+                    ),
+                )
+            })?;
+
+            snapshot.expiration_annotations()
+        }
+        // environment is not ephemeral anymore
+        None => {
+            match block_on(secrets.delete(ENVIRONMENT_SNAPSHOT_SECRET_NAME, &DeleteParams::default())) {
+                Ok(_) => {}
+                Err(kube::Error::Api(err)) if err.code == 404 => {}
+                Err(err) => {
+                    return Err(Box::new(EngineError::new_k8s_patch_secret_error(
+                        event_details,
+                        CommandError::new(
+                            format!("Cannot delete environment snapshot in namespace {namespace}"),
+                            Some(err.to_string()),
+                            None,
+                        ),
+                    )));
+                }
+            }
+
+            json!({
+                EXPIRE_AT_ANNOTATION: null,
+                ACTION_ON_EXPIRATION_ANNOTATION: null,
+            })
+        }
+    };
+
+    let namespaces: Api<Namespace> = Api::all(kube.clone());
+    block_on(namespaces.patch(
+        namespace,
+        &PatchParams::default(),
+        &Patch::Merge(json!({ "metadata": { "annotations": expiration_annotations } })),
+    ))
+    .map_err(|err| {
+        EngineError::new_k8s_create_namespace(
+            event_details,
+            namespace.to_string(),
+            CommandError::new(
+                format!("Can't set expiration of namespace {namespace}"),
+                Some(err.to_string()),
+                None,
+            ),
+        )
+    })?;
+
+    Ok(())
+}
+
+/// Snapshot of the environment living in the namespace, if any
+fn load_environment_snapshot(
+    kube: &kube::Client,
+    namespace: &str,
+    event_details: EventDetails,
+) -> Result<Option<EnvironmentSnapshot>, Box<EngineError>> {
+    let api: Api<Secret> = Api::namespaced(kube.clone(), namespace);
+    let secret = block_on(api.get_opt(ENVIRONMENT_SNAPSHOT_SECRET_NAME)).map_err(|err| {
+        EngineError::new_k8s_get_secret_error(
+            event_details.clone(),
+            CommandError::new(
+                format!("Cannot get environment snapshot in namespace {namespace}"),
+                Some(err.to_string()),
+                None,
+            ),
+        )
+    })?;
+    let Some(secret) = secret else {
+        return Ok(None);
+    };
+
+    let snapshot = secret
+        .data
+        .and_then(|mut data| data.remove(ENVIRONMENT_SNAPSHOT_KEY))
+        .ok_or_else(|| {
+            EngineError::new_k8s_get_secret_error(
+                event_details.clone(),
+                CommandError::new_from_safe_message(format!("Environment snapshot of namespace {namespace} is empty")),
+            )
+        })?;
+
+    serde_json::from_slice(&snapshot.0)
+        .map(Some)
+        .map_err(|err| Box::new(EngineError::new_k8s_get_secret_error(event_details, CommandError::from(err))))
+}
+
+/// Namespaces whose expiration date is reached, with the action to run on them
+pub(crate) fn expired_environments(namespaces: &[Namespace], now: DateTime<Utc>) -> Vec<ExpiredEnvironment> {
+    namespaces
+        .iter()
+        .filter_map(|namespace| {
+            let name = namespace.metadata.name.as_ref()?;
+            let annotations = namespace.metadata.annotations.as_ref()?;
+            let expired_at = DateTime::parse_from_rfc3339(annotations.get(EXPIRE_AT_ANNOTATION)?)
+                .ok()?
+                .with_timezone(&Utc);
+            if expired_at > now {
+                return None;
+            }
+
+            let action = match annotations.get(ACTION_ON_EXPIRATION_ANNOTATION).map(|x| x.as_str()) {
+                Some(EXPIRATION_ACTION_DELETE) => Action::Delete,
+                _ => Action::Pause,
+            };
+
+            Some(ExpiredEnvironment {
+                namespace: name.to_string(),
+                expired_at,
+                action,
+            })
+        })
+        .collect()
+}
+
+/// Pause or delete ephemeral environments of the cluster once their lifecycle has expired.
+/// Unlike the raw ttl deletion, it goes through the regular flow (registry cleanup, on-delete jobs, ...)
+pub struct EnvironmentSweeperTask {
+    workspace_root_dir: String,
+    lib_root_dir: String,
+    docker: Arc<Docker>,
+    request: InfrastructureEngineRequest,
+    cancel_requested: Arc<AtomicAbortStatus>,
+    logger: Box<dyn Logger>,
+    metrics_registry: Box<dyn MetricsRegistry>,
+    qovery_api: Arc<dyn QoveryApi>,
+    span: tracing::Span,
+    is_terminated: (RwLock<Option<broadcast::Sender<()>>>, broadcast::Receiver<()>),
+}
+
+impl EnvironmentSweeperTask {
+    pub fn new(
+        request: InfrastructureEngineRequest,
+        workspace_root_dir: String,
+        lib_root_dir: String,
+        docker: Arc<Docker>,
+        logger: Box<dyn Logger>,
+        metrics_registry: Box<dyn MetricsRegistry>,
+        qovery_api: Arc<dyn QoveryApi>,
+    ) -> Self {
+        let span = info_span!(
+            "environment_sweeper_task",
+            organization_id = request.organization_long_id.to_string(),
+            cluster_id = request.kubernetes.long_id.to_string(),
+            execution_id = request.id,
+        );
+
+        EnvironmentSweeperTask {
+            workspace_root_dir,
+            lib_root_dir,
+            docker,
+            request,
+            cancel_requested: Arc::new(AtomicAbortStatus::new(AbortStatus::None)),
+            logger,
+            metrics_registry,
+            qovery_api,
+            span,
+            is_terminated: {
+                let (tx, rx) = broadcast::channel(1);
+                (RwLock::new(Some(tx)), rx)
+            },
+        }
+    }
+
+    fn info_context(&self) -> Context {
+        Context::new(
+            self.request.organization_long_id,
+            self.request.kubernetes.long_id,
+            self.request.id.to_string(),
+            self.workspace_root_dir.to_string(),
+            self.lib_root_dir.to_string(),
+            self.request.test_cluster,
+            self.request.features.clone(),
+            self.request.metadata.clone(),
+            self.docker.clone(),
+            self.qovery_api.clone(),
+            self.request.event_details(),
+        )
+    }
+
+    fn list_expired_environments(
+        &self,
+        infra_ctx: &InfrastructureContext,
+    ) -> Result<Vec<ExpiredEnvironment>, Box<EngineError>> {
+        let kube = infra_ctx.mk_kube_client()?;
+        let api: Api<Namespace> = Api::all(kube.client());
+        let namespaces =
+            block_on(api.list(&ListParams::default().labels("qovery.com/environment-id"))).map_err(|err| {
+                EngineError::new_k8s_service_issue(
+                    self.request.event_details(),
+                    CommandError::new("Cannot list environments namespaces".to_string(), Some(err.to_string()), None),
+                )
+            })?;
+
+        Ok(expired_environments(&namespaces.items, Utc::now()))
+    }
+
+    fn sweep_environment(
+        &self,
+        infra_ctx: &InfrastructureContext,
+        expired_environment: &ExpiredEnvironment,
+    ) -> Result<(), Box<EngineError>> {
+        let kube = infra_ctx.mk_kube_client()?;
+        let Some(snapshot) =
+            load_environment_snapshot(&kube.client(), &expired_environment.namespace, self.request.event_details())?
+        else {
+            // Nothing tells which environment lives there, it must be cleaned up by hand
+            self.logger.log(EngineEvent::Warning(
+                self.request.event_details(),
+                EventMessage::new_from_safe(format!(
+                    "Namespace {} has expired but has no environment snapshot, it is skipped",
+                    expired_environment.namespace
+                )),
+            ));
+            return Ok(());
+        };
+
+        // Fetch the current definition of the environment, the one known at its last deployment may be outdated
+        let environment_request = self
+            .qovery_api
+            .environment_request(&snapshot.organization_long_id, &snapshot.environment_long_id)
+            .map_err(|err| {
+                EngineError::new_unknown(
+                    self.request.event_details(),
+                    format!("Cannot fetch environment {} from Qovery API", snapshot.environment_long_id),
+                    Some(CommandError::new_from_safe_message(err.to_string())),
+                    None,
+                    None,
+                )
+            })?;
+        let mut environment_request = environment_request.with_action(expired_environment.action.clone());
+        environment_request.execution_id = self.request.id.to_string();
+
+        let environment = environment_request
+            .to_environment_domain(
+                infra_ctx.context(),
+                infra_ctx.cloud_provider(),
+                infra_ctx.container_registry(),
+                infra_ctx.kubernetes(),
+            )
+            .map_err(|err| {
+                EngineError::new_invalid_engine_payload(self.request.event_details(), err.to_string().as_str(), None)
+            })?;
+        let event_details = environment.event_details().clone();
+
+        self.logger.log(EngineEvent::Info(
+            event_details.clone(),
+            EventMessage::new_from_safe(format!(
+                "⏳ Environment has expired on {}, it is going to be {}",
+                expired_environment.expired_at.to_rfc3339(),
+                match expired_environment.action {
+                    Action::Delete => "deleted",
+                    _ => "paused",
+                }
+            )),
+        ));
+
+        EnvironmentTask::deploy_environment(environment, infra_ctx, self.cancel_checker().as_ref())?;
+
+        // A paused environment is not expired anymore, the expiration is set again on its next deployment
+        if expired_environment.action == Action::Pause {
+            let api: Api<Namespace> = Api::all(kube.client());
+            block_on(api.patch(
+                &expired_environment.namespace,
+                &PatchParams::default(),
+                &Patch::Merge(json!({
+                    "metadata": { "annotations": { EXPIRE_AT_ANNOTATION: null, ACTION_ON_EXPIRATION_ANNOTATION: null } }
+                })),
+            ))
+            .map_err(|err| {
+                EngineError::new_k8s_create_namespace(
+                    event_details,
+                    expired_environment.namespace.clone(),
+                    CommandError::new(
+                        format!("Can't remove expiration of namespace {}", expired_environment.namespace),
+                        Some(err.to_string()),
+                        None,
+                    ),
+                )
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Task for EnvironmentSweeperTask {
+    fn id(&self) -> &str {
+        self.request.id.as_str()
+    }
+
+    fn run(&self) {
+        let _span = self.span.enter();
+        info!("environment sweeper task {} started", self.id());
+        let _guard = scopeguard::guard((), |_| {
+            let Some(is_terminated_tx) = self.is_terminated.0.write().unwrap().take() else {
+                return;
+            };
+            let _ = is_terminated_tx.send(());
+        });
+
+        let infra_ctx = match self.request.to_infrastructure_context(
+            &self.info_context(),
+            self.request.event_details(),
+            self.logger.clone(),
+            self.metrics_registry.clone(),
+            false,
+        ) {
+            Ok(infra_ctx) => infra_ctx,
+            Err(err) => {
+                self.logger.log(EngineEvent::Error(*err, None));
+                return;
+            }
+        };
+
+        let expired_environments = match self.list_expired_environments(&infra_ctx) {
+            Ok(expired_environments) => expired_environments,
+            Err(err) => {
+                self.logger.log(EngineEvent::Error(*err, None));
+                return;
+            }
+        };
+
+        // Environments are swept one by one, a failing one must not prevent the others to be swept
+        for expired_environment in &expired_environments {
+            if self.cancel_checker().status().should_cancel() {
+                break;
+            }
+
+            info!("sweeping expired environment in namespace {}", expired_environment.namespace);
+            if let Err(err) = self.sweep_environment(&infra_ctx, expired_environment) {
+                let step = match expired_environment.action {
+                    Action::Delete => EnvironmentStep::DeletedError,
+                    _ => EnvironmentStep::PausedError,
+                };
+                error!(
+                    "Cannot sweep expired environment in namespace {}: {}",
+                    expired_environment.namespace,
+                    err.message(ErrorMessageVerbosity::SafeOnly)
+                );
+                self.logger.log(EngineEvent::Error(
+                    err.clone_engine_error_with_stage(Stage::Environment(step)),
+                    None,
+                ));
+            }
+        }
+
+        info!("environment sweeper task {} finished", self.id());
+    }
+
+    fn cancel(&self, force_requested: bool) -> bool {
+        if self.is_terminated() {
+            return false;
+        }
+
+        self.cancel_requested.store(
+            match force_requested {
+                true => AbortStatus::UserForceRequested,
+                false => AbortStatus::Requested,
+            },
+            Ordering::Relaxed,
+        );
+        true
+    }
+
+    fn cancel_checker(&self) -> Box<dyn Abort> {
+        let cancel_requested = self.cancel_requested.clone();
+        Box::new(move || cancel_requested.load(Ordering::Relaxed))
+    }
+
+    fn is_terminated(&self) -> bool {
+        self.is_terminated.0.read().map(|tx| tx.is_none()).unwrap_or(true)
+    }
+
+    fn await_terminated(&self) -> broadcast::Receiver<()> {
+        self.is_terminated.1.resubscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use itertools::Itertools;
+
+    fn namespace(name: &str, annotations: Option<BTreeMap<String, String>>) -> Namespace {
+        Namespace {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                annotations,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_expired_environments() {
+        // setup:
+        let now = Utc::now();
+        let namespaces = vec![
+            namespace("z-not-ephemeral", None),
+            namespace(
+                "z-not-expired",
+                Some(BTreeMap::from([(
+                    EXPIRE_AT_ANNOTATION.to_string(),
+                    (now + Duration::hours(1)).to_rfc3339(),
+                )])),
+            ),
+            namespace(
+                "z-expired-pause",
+                Some(BTreeMap::from([(
+                    EXPIRE_AT_ANNOTATION.to_string(),
+                    (now - Duration::hours(1)).to_rfc3339(),
+                )])),
+            ),
+            namespace(
+                "z-expired-delete",
+                Some(BTreeMap::from([
+                    (EXPIRE_AT_ANNOTATION.to_string(), (now - Duration::minutes(1)).to_rfc3339()),
+                    (
+                        ACTION_ON_EXPIRATION_ANNOTATION.to_string(),
+                        EXPIRATION_ACTION_DELETE.to_string(),
+                    ),
+                ])),
+            ),
+            namespace(
+                "z-invalid-date",
+                Some(BTreeMap::from([(EXPIRE_AT_ANNOTATION.to_string(), "tomorrow".to_string())])),
+            ),
+        ];
+
+        // execute:
+        let expired = expired_environments(&namespaces, now);
+
+        // verify:
+        assert_eq!(
+            expired
+                .iter()
+                .map(|x| (x.namespace.as_str(), x.action == Action::Delete))
+                .collect::<Vec<_>>(),
+            vec![("z-expired-pause", false), ("z-expired-delete", true)]
+        );
+    }
+
+    #[test]
+    fn test_environment_snapshot_keeps_only_ids_and_expiration() {
+        // setup:
+        let now = Utc::now();
+        let environment: EnvironmentRequest = serde_json::from_value(json!({
+            "execution_id": "execution-id",
+            "long_id": Uuid::new_v4(),
+            "name": "preview",
+            "kube_name": "z-preview",
+            "project_long_id": Uuid::new_v4(),
+            "organization_long_id": Uuid::new_v4(),
+            "action": "CREATE",
+            "applications": [],
+            "containers": [],
+            "jobs": [],
+            "routers": [],
+            "databases": [],
+            "lifecycle": { "ttl_in_seconds": 3600, "action_on_expiration": "DELETE" },
+        }))
+        .unwrap();
+        let lifecycle = environment.lifecycle.clone().unwrap();
+
+        // execute:
+        let snapshot = EnvironmentSnapshot::new(&environment, &lifecycle, now);
+
+        // verify:
+        assert_eq!(snapshot.organization_long_id, environment.organization_long_id);
+        assert_eq!(snapshot.environment_long_id, environment.long_id);
+        assert!(snapshot.action_on_expiration == Action::Delete);
+        assert_eq!(snapshot.expire_at, now + Duration::hours(1));
+        assert_eq!(
+            serde_json::to_value(&snapshot)
+                .unwrap()
+                .as_object()
+                .unwrap()
+                .keys()
+                .map(|key| key.as_str())
+                .sorted()
+                .collect::<Vec<_>>(),
+            vec![
+                "action_on_expiration",
+                "environment_long_id",
+                "expire_at",
+                "organization_long_id"
+            ]
+        );
+
+        // the expiration written on the namespace matches the snapshot
+        let annotations: BTreeMap<String, String> = serde_json::from_value(snapshot.expiration_annotations()).unwrap();
+        let expired = expired_environments(&[namespace("z-preview", Some(annotations))], now + Duration::hours(1));
+        assert_eq!(expired.len(), 1);
+        assert!(expired[0].action == Action::Delete);
+        assert_eq!(expired[0].expired_at, snapshot.expire_at);
+    }
+}
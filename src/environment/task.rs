@@ -1,36 +1,45 @@
-use crate::cmd::docker::Docker;
+use crate::cmd::command::CommandKiller;
+use crate::cmd::docker::{ContainerImage, Docker};
 use crate::engine_task;
 use crate::engine_task::Task;
 use crate::engine_task::qovery_api::QoveryApi;
 use crate::environment::action::deploy_environment::EnvironmentDeployment;
+use crate::environment::action::dry_run;
 use crate::environment::models::abort::{Abort, AbortStatus, AtomicAbortStatus};
-use crate::environment::models::environment::Environment;
+use crate::environment::models::environment::{DnsRouting, Environment};
+use crate::environment::report::git_status_notifier::{GitStatusNotifier, NotifiedCommit};
 use crate::environment::report::logger::EnvLogger;
-use crate::errors::{EngineError, ErrorMessageVerbosity};
+use crate::environment::sweeper_task::save_environment_snapshot;
+use crate::errors::{CommandError, EngineError, ErrorMessageVerbosity};
 use crate::events::{EngineEvent, EnvironmentStep, EventDetails, EventMessage, Stage};
 use crate::infrastructure::infrastructure_context::InfrastructureContext;
 use crate::infrastructure::models::build_platform;
-use crate::infrastructure::models::build_platform::{BuildError, BuildPlatform};
+use crate::infrastructure::models::build_platform::{BuildCacheBackendKind, BuildError, BuildPlatform, Image};
+use crate::infrastructure::models::cloud_provider::DeploymentTarget;
 use crate::infrastructure::models::cloud_provider::service;
 use crate::infrastructure::models::cloud_provider::service::Service;
 use crate::infrastructure::models::container_registry::errors::ContainerRegistryError;
 use crate::infrastructure::models::container_registry::{InteractWithRegistry, RegistryTags, to_engine_error};
+use crate::infrastructure::models::cost_allocation::CostAllocationTags;
 use crate::io_models::Action;
 use crate::io_models::context::Context;
-use crate::io_models::engine_request::{CloudProviderOptions, EnvironmentEngineRequest};
+use crate::io_models::engine_request::{
+    CloudProviderOptions, DnsRoutingPolicy, EnvironmentEngineRequest, MultiClusterDeploymentStrategy,
+};
+use crate::io_models::job::JobSource;
 use crate::log_file_writer::LogFileWriter;
 use crate::logger::Logger;
 use crate::metrics_registry::{MetricsRegistry, StepLabel, StepName, StepRecordHandle, StepStatus};
 use base64::Engine;
 use itertools::Itertools;
 use std::cmp::{max, min};
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::num::NonZeroUsize;
 use std::sync::atomic::Ordering;
 use std::sync::{Arc, RwLock};
 use std::thread::ScopedJoinHandle;
 use std::time::Duration;
-use std::{env, fs, thread};
+use std::{env, fs, iter, thread};
 use tokio::sync::broadcast;
 use uuid::Uuid;
 
@@ -73,15 +82,25 @@ impl EnvironmentTask {
         );
 
         let secrets = Self::get_secrets(&request);
+        let qovery_api: Arc<dyn QoveryApi> = Arc::from(qovery_api);
+        let logger: Box<dyn Logger> = match &request.git_status_notifications {
+            Some(options) => Box::new(GitStatusNotifier::new(
+                logger.with_secrets(secrets),
+                options.clone(),
+                Self::notified_commits(&request),
+                qovery_api.clone(),
+            )),
+            None => logger.with_secrets(secrets),
+        };
         EnvironmentTask {
             workspace_root_dir,
             lib_root_dir,
             docker,
             request,
-            logger: logger.with_secrets(secrets),
+            logger,
             metrics_registry,
             cancel_requested: Arc::new(AtomicAbortStatus::new(AbortStatus::None)),
-            qovery_api: Arc::from(qovery_api),
+            qovery_api,
             span,
             is_terminated: {
                 let (tx, rx) = broadcast::channel(1);
@@ -91,19 +110,55 @@ impl EnvironmentTask {
         }
     }
 
+    // Services built from a git commit, whose progress is reported to their git provider
+    fn notified_commits(request: &EnvironmentEngineRequest) -> HashMap<Uuid, NotifiedCommit> {
+        let environment = &request.target_environment;
+        let applications = environment.applications.iter().map(|app| {
+            (
+                app.long_id,
+                NotifiedCommit {
+                    service_type: service::ServiceType::Application,
+                    service_name: app.name.clone(),
+                    environment_name: environment.name.clone(),
+                    git_url: app.git_url.clone(),
+                    commit_id: app.commit_id.clone(),
+                },
+            )
+        });
+        let jobs = environment.jobs.iter().filter_map(|job| match &job.source {
+            JobSource::Docker { git_url, commit_id, .. } => Some((
+                job.long_id,
+                NotifiedCommit {
+                    service_type: service::ServiceType::Job,
+                    service_name: job.name.clone(),
+                    environment_name: environment.name.clone(),
+                    git_url: git_url.clone(),
+                    commit_id: commit_id.clone(),
+                },
+            )),
+            JobSource::Image { .. } => None,
+        });
+
+        applications.chain(jobs).collect()
+    }
+
     fn info_context(&self) -> Context {
+        self.cluster_info_context(&self.request, self.workspace_root_dir.to_string())
+    }
+
+    fn cluster_info_context(&self, request: &EnvironmentEngineRequest, workspace_root_dir: String) -> Context {
         Context::new(
-            self.request.organization_long_id,
-            self.request.kubernetes.long_id,
-            self.request.id.to_string(),
-            self.workspace_root_dir.to_string(),
+            request.organization_long_id,
+            request.kubernetes.long_id,
+            request.id.to_string(),
+            workspace_root_dir,
             self.lib_root_dir.to_string(),
-            self.request.test_cluster,
-            self.request.features.clone(),
-            self.request.metadata.clone(),
+            request.test_cluster,
+            request.features.clone(),
+            request.metadata.clone(),
             self.docker.clone(),
             self.qovery_api.clone(),
-            self.request.event_details(),
+            request.event_details(),
         )
     }
 
@@ -117,6 +172,87 @@ impl EnvironmentTask {
         )
     }
 
+    /// Infrastructure context and environment of each additional cluster of a multi cluster deployment
+    fn additional_clusters_deployments(&self) -> Result<Vec<(InfrastructureContext, Environment)>, Box<EngineError>> {
+        let dns_routing_policy = self
+            .request
+            .multi_cluster
+            .as_ref()
+            .and_then(|multi_cluster| multi_cluster.dns_routing_policy.as_ref());
+
+        self.request
+            .additional_clusters()
+            .iter()
+            .map(|cluster| {
+                let request = self.request.for_cluster(cluster);
+                // Each cluster gets its own workspace, as services files are generated per cluster
+                let workspace_root_dir = format!("{}/{}", self.workspace_root_dir, cluster.kubernetes.long_id);
+                let infra_ctx = request.to_infrastructure_context(
+                    &self.cluster_info_context(&request, workspace_root_dir),
+                    request.event_details(),
+                    self.logger.clone(),
+                    self.metrics_registry.clone(),
+                    false,
+                )?;
+                let mut environment = Self::to_environment(&request, &infra_ctx)?;
+                environment.dns_routing = dns_routing_policy
+                    .map(|policy| Self::dns_routing(policy, &request, &infra_ctx))
+                    .transpose()?;
+
+                Ok((infra_ctx, environment))
+            })
+            .collect()
+    }
+
+    fn dns_routing(
+        policy: &DnsRoutingPolicy,
+        request: &EnvironmentEngineRequest,
+        infra_ctx: &InfrastructureContext,
+    ) -> Result<DnsRouting, Box<EngineError>> {
+        policy
+            .check_cluster_support(&infra_ctx.cloud_provider().kind(), &infra_ctx.dns_provider().kind())
+            .map_err(|err| {
+                Box::new(EngineError::new_invalid_engine_payload(
+                    request.event_details(),
+                    &format!("Cluster {}: {}", request.kubernetes.name, err),
+                    None,
+                ))
+            })?;
+
+        Ok(policy.to_domain(&request.kubernetes.long_id, &request.kubernetes.region))
+    }
+
+    fn to_environment(
+        request: &EnvironmentEngineRequest,
+        infra_ctx: &InfrastructureContext,
+    ) -> Result<Environment, Box<EngineError>> {
+        request
+            .target_environment
+            .to_environment_domain(
+                infra_ctx.context(),
+                infra_ctx.cloud_provider(),
+                infra_ctx.container_registry(),
+                infra_ctx.kubernetes(),
+            )
+            .map_err(|err| {
+                let event_details = EventDetails::clone_changing_stage(
+                    request.event_details(),
+                    Stage::Environment(
+                        request
+                            .target_environment
+                            .action
+                            .to_service_action()
+                            .to_environment_step(),
+                    ),
+                );
+                Box::new(EngineError::new_invalid_engine_payload(
+                    event_details,
+                    err.to_string().as_str(),
+                    None,
+                ))
+            })
+    }
+
     fn _is_canceled(&self) -> bool {
         self.cancel_requested.load(Ordering::Relaxed).should_cancel()
     }
@@ -166,6 +302,8 @@ impl EnvironmentTask {
             .advanced_settings()
             .registry_image_retention_time_sec;
         let resource_ttl = infra_ctx.kubernetes().advanced_settings().resource_ttl();
+        let organization_id = *infra_ctx.context().organization_long_id();
+        let cluster_id = *infra_ctx.kubernetes().long_id();
         let cr_registry = infra_ctx.container_registry();
         let build_platform = infra_ctx.build_platform();
 
@@ -177,7 +315,8 @@ impl EnvironmentTask {
             .into_iter()
             .map(|service| {
                 || {
-                    metrics_registry.stop_record(*service.long_id(), StepName::BuildQueueing, StepStatus::Success);
+                    let service_id = *service.long_id();
+                    metrics_registry.stop_record(service_id, StepName::BuildQueueing, StepStatus::Success);
                     Self::build_and_push_service(
                         service,
                         option,
@@ -189,6 +328,13 @@ impl EnvironmentTask {
                             environment_id: Some(environment_id.to_string()),
                             project_id: Some(project_id.to_string()),
                             resource_ttl,
+                            cost_allocation_tags: CostAllocationTags::for_environment(
+                                organization_id,
+                                cluster_id,
+                                project_id,
+                                environment_id,
+                            )
+                            .with_service(service_id),
                         },
                         cr_to_engine_error,
                         &mk_logger,
@@ -228,10 +374,21 @@ impl EnvironmentTask {
         let image_name = build.image.full_image_name_with_tag();
 
         // If image already exists in the registry, skip the build
+        // unless it lacks an architecture of the cluster nodes (i.e: a node group of a new architecture has been added)
         if !option.force_build && cr_registry.image_exists(&build.image) {
-            let msg = format!("✅ Container image {image_name} already exists and ready to use");
-            logger.send_success(msg);
-            return Ok(());
+            match build_platform.missing_image_architectures(build) {
+                Ok(missing_architectures) if !missing_architectures.is_empty() => {
+                    logger.send_warning(format!(
+                        "⚠️ Container image {image_name} already exists but lacks architectures {}, rebuilding it",
+                        missing_architectures.iter().join(", ")
+                    ));
+                }
+                _ => {
+                    let msg = format!("✅ Container image {image_name} already exists and ready to use");
+                    logger.send_success(msg);
+                    return Ok(());
+                }
+            }
         }
 
         // Be sure that our repository exist before trying to pull/push images from it
@@ -246,25 +403,48 @@ impl EnvironmentTask {
             StepName::RegistryCreateRepository,
         );
 
-        match cr_registry.create_repository(
+        let repository_created = match cr_registry.create_repository(
             Some(build.image.registry_name.as_str()),
             build.image.repository_name(),
             image_retention_time_sec,
-            registry_tags,
+            registry_tags.clone(),
         ) {
             Err(err) => {
                 provision_registry_record.stop(StepStatus::Error);
                 return Err(Box::new(cr_to_engine_error(err)));
             }
-            Ok((_repository, repository_info)) => provision_registry_record.stop(if repository_info.created {
-                StepStatus::Success
-            } else {
-                StepStatus::Skip
-            }),
+            Ok((_repository, repository_info)) => repository_info.created,
+        };
+
+        if build_platform.cache_backend(build) == BuildCacheBackendKind::DedicatedRegistry {
+            let cache_repository_name = build.image.cache_repository_name();
+            logger.send_progress(format!(
+                "🗂️ Provisioning build cache repository {}/{}",
+                build.image.registry_name.as_str(),
+                cache_repository_name
+            ));
+            if let Err(err) = cr_registry.create_repository(
+                Some(build.image.registry_name.as_str()),
+                &cache_repository_name,
+                image_retention_time_sec,
+                registry_tags,
+            ) {
+                provision_registry_record.stop(StepStatus::Error);
+                let engine_error = cr_to_engine_error(err);
+                logger.send_error(engine_error.clone());
+                return Err(Box::new(engine_error));
+            }
         }
+        provision_registry_record.stop(if repository_created {
+            StepStatus::Success
+        } else {
+            StepStatus::Skip
+        });
 
         // Ok now everything is setup, we can try to build the app
         let build_result = build_platform.build(build, &logger, metrics_registry.clone(), abort);
+        // In monorepo mode, the image tag is only known once the repository has been cloned
+        let image_name = build.image.full_image_name_with_tag();
         match build_result {
             Ok(_) => {
                 let msg = format!("✅ Container image {} is built and ready to use", &image_name);
@@ -293,6 +473,17 @@ impl EnvironmentTask {
                 logger.send_error(build_result.clone());
                 Err(Box::new(build_result))
             }
+            Err(err @ BuildError::ArchitectureDockerError { architecture, .. }) => {
+                let msg = format!(
+                    "❌ Container image {} failed to be build for architecture {}: Look at the build logs of this architecture to understand the error",
+                    &image_name, architecture
+                );
+                info!("{}", err);
+                let event_details = service.get_event_details(Stage::Environment(EnvironmentStep::BuiltError));
+                let build_result = build_platform::to_engine_error(event_details, err, msg);
+                logger.send_error(build_result.clone());
+                Err(Box::new(build_result))
+            }
             Err(err @ BuildError::GitError { .. }) => {
                 let msg = format!("❌ Application {} failed to be cloned: {}", &service.name(), err);
                 info!("{}", err);
@@ -374,6 +565,335 @@ impl EnvironmentTask {
         };
 
         // Handle deployment error, send back Cancelled event for all services that were not deployed
+        Self::log_not_deployed_services_as_cancelled(&environment, &deployed_services, infra_ctx);
+
+        Err(deployment_err)
+    }
+
+    /// Streams the diff of every service against what is running on the cluster.
+    /// Images are not built, and nothing is applied on the cluster.
+    pub fn dry_run_environment(
+        environment: &Environment,
+        infra_ctx: &InfrastructureContext,
+        abort: &dyn Abort,
+    ) -> Result<(), Box<EngineError>> {
+        let logger = Arc::new(infra_ctx.kubernetes().logger().clone_dyn());
+        logger.log(EngineEvent::Warning(
+            EventDetails::clone_changing_stage(
+                environment.event_details().clone(),
+                Stage::Environment(EnvironmentStep::DryRun),
+            ),
+            EventMessage::new_from_safe("👻 Dry run mode enabled, services are neither built nor deployed".to_string()),
+        ));
+
+        let target = DeploymentTarget::new(infra_ctx, environment, abort)?;
+        dry_run::dry_run_environment(&target, logger)
+    }
+
+    /// Deploys the environment to several clusters. Images are built once against the registry of the first cluster,
+    /// then replicated to the registry of the other clusters before their deployment.
+    pub fn deploy_environment_to_clusters(
+        mut environment: Environment,
+        infra_ctx: &InfrastructureContext,
+        additional_clusters: Vec<(InfrastructureContext, Environment)>,
+        strategy: MultiClusterDeploymentStrategy,
+        abort: &dyn Abort,
+    ) -> Result<(), Box<EngineError>> {
+        let (additional_infra_ctxs, additional_environments): (Vec<_>, Vec<_>) =
+            additional_clusters.into_iter().unzip();
+
+        let logger = Arc::new(infra_ctx.kubernetes().logger().clone_dyn());
+        let (environment_id, project_id) = (environment.long_id, environment.project_long_id);
+        let max_parallel_build = environment.max_parallel_build as usize;
+        let build_ret = Self::build_and_push_services(
+            environment_id,
+            project_id,
+            Self::services_to_build(&mut environment),
+            &DeploymentOption {
+                force_build: false,
+                force_push: false,
+            },
+            infra_ctx,
+            max_parallel_build,
+            |srv: &dyn Service| EnvLogger::new(srv, EnvironmentStep::Build, logger.clone()),
+            abort,
+        );
+        if let Err(err) = build_ret {
+            Self::log_not_deployed_services_as_cancelled(&environment, &HashSet::new(), infra_ctx);
+            for (cluster_infra_ctx, cluster_environment) in additional_infra_ctxs.iter().zip(&additional_environments) {
+                Self::log_not_deployed_services_as_cancelled(cluster_environment, &HashSet::new(), cluster_infra_ctx);
+            }
+            return Err(err);
+        }
+
+        let built_images: HashMap<Uuid, Image> = environment
+            .applications
+            .iter()
+            .map(|x| x.as_service())
+            .chain(environment.jobs.iter().map(|x| x.as_service()))
+            .chain(environment.terraform_services.iter().map(|x| x.as_service()))
+            .filter_map(|service| service.build().map(|build| (*service.long_id(), build.image.clone())))
+            .collect();
+
+        let event_details = environment.event_details().clone();
+        let clusters = iter::once((infra_ctx, environment))
+            .chain(additional_infra_ctxs.iter().zip(additional_environments))
+            .collect_vec();
+
+        Self::run_on_clusters(
+            clusters,
+            strategy,
+            |(cluster_infra_ctx, cluster_environment)| {
+                Self::deploy_environment_on_cluster(&built_images, cluster_environment, cluster_infra_ctx, abort)
+            },
+            |(cluster_infra_ctx, cluster_environment)| {
+                Self::log_not_deployed_services_as_cancelled(&cluster_environment, &HashSet::new(), cluster_infra_ctx)
+            },
+            event_details,
+        )
+    }
+
+    /// Runs the deployment of each cluster according to the strategy, and returns the first error.
+    /// With the sequential strategy, clusters following a failed one are cancelled instead of being deployed
+    fn run_on_clusters<C: Send>(
+        clusters: Vec<C>,
+        strategy: MultiClusterDeploymentStrategy,
+        deploy: impl Fn(C) -> Result<(), Box<EngineError>> + Sync,
+        cancel: impl Fn(C),
+        event_details: EventDetails,
+    ) -> Result<(), Box<EngineError>> {
+        match strategy {
+            MultiClusterDeploymentStrategy::Sequential => {
+                let mut deployment_ret = Ok(());
+                for cluster in clusters {
+                    // Halt on first failure, remaining clusters are left untouched
+                    if deployment_ret.is_err() {
+                        cancel(cluster);
+                        continue;
+                    }
+                    deployment_ret = deploy(cluster);
+                }
+                deployment_ret
+            }
+            MultiClusterDeploymentStrategy::Parallel => thread::scope(|scope| {
+                let deploy = &deploy;
+                let deployment_threads = clusters
+                    .into_iter()
+                    .map(|cluster| {
+                        let current_span = tracing::Span::current();
+                        scope.spawn(move || {
+                            let _span = current_span.enter();
+                            deploy(cluster)
+                        })
+                    })
+                    .collect_vec();
+
+                // Wait for all clusters, and keep the first error
+                deployment_threads
+                    .into_iter()
+                    .map(|th| {
+                        th.join().unwrap_or_else(|err| {
+                            let err = match err.downcast_ref::<&'static str>() {
+                                None => match err.downcast_ref::<String>() {
+                                    None => "Unable to get error.",
+                                    Some(s) => s.as_str(),
+                                },
+                                Some(s) => *s,
+                            };
+                            Err(Box::new(EngineError::new_unknown(
+                                event_details.clone(),
+                                "Cluster deployment failed unexpectedly".to_string(),
+                                Some(CommandError::new(
+                                    "Thread panicked during parallel clusters deployments.".to_string(),
+                                    Some(err.to_string()),
+                                    None,
+                                )),
+                                None,
+                                None,
+                            )))
+                        })
+                    })
+                    .fold(Ok(()), |ret, cluster_ret| ret.and(cluster_ret))
+            }),
+        }
+    }
+
+    fn deploy_environment_on_cluster(
+        built_images: &HashMap<Uuid, Image>,
+        mut environment: Environment,
+        infra_ctx: &InfrastructureContext,
+        abort: &dyn Abort,
+    ) -> Result<(), Box<EngineError>> {
+        let action = environment.action;
+        let event_details = environment.event_details().clone();
+        let cluster_name = infra_ctx.kubernetes().name().to_string();
+
+        let deployment_ret = match Self::replicate_images(built_images, &mut environment, infra_ctx, abort) {
+            Ok(()) => Self::deploy_environment(environment, infra_ctx, abort),
+            Err(err) => {
+                Self::log_not_deployed_services_as_cancelled(&environment, &HashSet::new(), infra_ctx);
+                Err(err)
+            }
+        };
+
+        // Report the status of this cluster, event details are bound to the cluster the environment is deployed to
+        let (step, message) = match (&deployment_ret, action) {
+            (Ok(()), service::Action::Create) => (EnvironmentStep::Deployed, "✅ Environment deployed"),
+            (Ok(()), service::Action::Pause) => (EnvironmentStep::Paused, "⏸️ Environment paused"),
+            (Ok(()), service::Action::Delete) => (EnvironmentStep::Deleted, "🗑️ Environment deleted"),
+            (Ok(()), service::Action::Restart) => (EnvironmentStep::Restarted, "⟳️ Environment restarted"),
+            (Err(_), service::Action::Create) => {
+                (EnvironmentStep::DeployedError, "💣 Environment failed to be deployed")
+            }
+            (Err(_), service::Action::Pause) => (EnvironmentStep::PausedError, "💣 Environment failed to be paused"),
+            (Err(_), service::Action::Delete) => (EnvironmentStep::DeletedError, "💣 Environment failed to be deleted"),
+            (Err(_), service::Action::Restart) => {
+                (EnvironmentStep::RestartedError, "💣 Environment failed to be restarted")
+            }
+        };
+        infra_ctx.kubernetes().logger().log(EngineEvent::Info(
+            EventDetails::clone_changing_stage(event_details, Stage::Environment(step)),
+            EventMessage::new_from_safe(format!("{message} on cluster {cluster_name}")),
+        ));
+
+        deployment_ret
+    }
+
+    /// Copy images built for the first cluster into the registry of the cluster the environment is deployed to
+    fn replicate_images(
+        built_images: &HashMap<Uuid, Image>,
+        environment: &mut Environment,
+        infra_ctx: &InfrastructureContext,
+        abort: &dyn Abort,
+    ) -> Result<(), Box<EngineError>> {
+        let logger = Arc::new(infra_ctx.kubernetes().logger().clone_dyn());
+        let metrics_registry = infra_ctx.metrics_registry();
+        let cr_registry = infra_ctx.container_registry();
+        let docker = &infra_ctx.context().docker;
+        let advanced_settings = infra_ctx.kubernetes().advanced_settings();
+        let environment_id = environment.long_id;
+        let project_id = environment.project_long_id;
+        let organization_id = environment.organization_long_id;
+
+        for service in Self::services_to_build(environment) {
+            let Some(source_image) = built_images.get(service.long_id()) else {
+                continue;
+            };
+            if abort.status().should_cancel() {
+                return Err(Box::new(EngineError::new_task_cancellation_requested(
+                    service.get_event_details(Stage::Environment(EnvironmentStep::Cancelled)),
+                )));
+            }
+
+            let logger = EnvLogger::new(service, EnvironmentStep::Build, logger.clone());
+            let event_details = service.get_event_details(Stage::Environment(EnvironmentStep::Build));
+            let Some(build) = service.build_mut() else {
+                continue;
+            };
+            // The image tag is computed during the build, so it must be aligned with the one built
+            build.image.tag = source_image.tag.clone();
+            if source_image.full_image_name_with_tag() == build.image.full_image_name_with_tag()
+                || cr_registry.image_exists(&build.image)
+            {
+                logger.send_success(format!(
+                    "✅ Container image {} already exists and ready to use",
+                    build.image.full_image_name_with_tag()
+                ));
+                continue;
+            }
+
+            let record =
+                metrics_registry.start_record(build.image.service_long_id, StepLabel::Service, StepName::MirrorImage);
+            let replicate = || -> Result<(), Box<EngineError>> {
+                cr_registry
+                    .create_repository(
+                        Some(build.image.registry_name.as_str()),
+                        build.image.repository_name(),
+                        advanced_settings.registry_image_retention_time_sec,
+                        RegistryTags {
+                            cluster_id: None,
+                            environment_id: Some(environment_id.to_string()),
+                            project_id: Some(project_id.to_string()),
+                            resource_ttl: advanced_settings.resource_ttl(),
+                            cost_allocation_tags: CostAllocationTags::for_environment(
+                                organization_id,
+                                *infra_ctx.kubernetes().long_id(),
+                                project_id,
+                                environment_id,
+                            )
+                            .with_service(build.image.service_long_id),
+                        },
+                    )
+                    .map_err(|err| Box::new(to_engine_error(event_details.clone(), err)))?;
+
+                for registry_url in [&source_image.registry_url, &build.image.registry_url] {
+                    docker
+                        .login_with_retry(registry_url)
+                        .map_err(|err| Box::new(EngineError::new_docker_error(event_details.clone(), err)))?;
+                }
+
+                logger.send_progress(format!(
+                    "🪞 Replicating container image {} to the cluster registry",
+                    build.image.full_image_name_with_tag()
+                ));
+                docker
+                    .mirror(
+                        &ContainerImage::new(
+                            source_image.registry_url.clone(),
+                            source_image.name(),
+                            vec![source_image.tag.clone()],
+                        ),
+                        &ContainerImage::new(
+                            build.image.registry_url.clone(),
+                            build.image.name(),
+                            vec![build.image.tag.clone()],
+                        ),
+                        &mut |line| info!("{}", line),
+                        &mut |line| info!("{}", line),
+                        &CommandKiller::from(Duration::from_secs(30 * 60), abort),
+                    )
+                    .map_err(|err| Box::new(EngineError::new_docker_error(event_details.clone(), err)))
+            };
+
+            match replicate() {
+                Ok(()) => {
+                    record.stop(StepStatus::Success);
+                    logger.send_success(format!(
+                        "✅ Container image {} is replicated and ready to use",
+                        build.image.full_image_name_with_tag()
+                    ));
+                }
+                Err(err) => {
+                    record.stop(StepStatus::Error);
+                    logger.send_error(*err.clone());
+                    return Err(err);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn services_to_build(environment: &mut Environment) -> Vec<&mut dyn Service> {
+        environment
+            .applications
+            .iter_mut()
+            .map(|app| app.as_service_mut())
+            .chain(environment.jobs.iter_mut().map(|job| job.as_service_mut()))
+            .chain(
+                environment
+                    .terraform_services
+                    .iter_mut()
+                    .map(|terraform_service| terraform_service.as_service_mut()),
+            )
+            .collect()
+    }
+
+    fn log_not_deployed_services_as_cancelled(
+        environment: &Environment,
+        deployed_services: &HashSet<Uuid>,
+        infra_ctx: &InfrastructureContext,
+    ) {
         let services = std::iter::empty()
             .chain(environment.applications.iter().map(|x| x.as_service()))
             .chain(environment.containers.iter().map(|x| x.as_service()))
@@ -391,8 +911,6 @@ impl EnvironmentTask {
                 EventMessage::new_from_safe("".to_string()),
             ));
         }
-
-        Err(deployment_err)
     }
 
     fn get_secrets(request: &EnvironmentEngineRequest) -> Vec<String> {
@@ -435,21 +953,29 @@ impl EnvironmentTask {
         });
         secrets.extend(service_secrets);
 
-        match &request.cloud_provider.options {
-            CloudProviderOptions::Aws { secret_access_key, .. } => {
-                secrets.push(secret_access_key.to_string());
-            }
-            CloudProviderOptions::Scaleway {
-                scaleway_secret_key, ..
-            } => {
-                secrets.push(scaleway_secret_key.to_string());
-            }
-            CloudProviderOptions::Gcp { gcp_credentials } => {
-                secrets.push(gcp_credentials.private_key.to_string());
-            }
-            CloudProviderOptions::Azure { .. } => {}
-            CloudProviderOptions::OnPremise { .. } => {}
-        };
+        let cloud_providers = iter::once(&request.cloud_provider).chain(
+            request
+                .additional_clusters()
+                .iter()
+                .map(|cluster| &cluster.cloud_provider),
+        );
+        for cloud_provider in cloud_providers {
+            match &cloud_provider.options {
+                CloudProviderOptions::Aws { secret_access_key, .. } => {
+                    secrets.push(secret_access_key.to_string());
+                }
+                CloudProviderOptions::Scaleway {
+                    scaleway_secret_key, ..
+                } => {
+                    secrets.push(scaleway_secret_key.to_string());
+                }
+                CloudProviderOptions::Gcp { gcp_credentials } => {
+                    secrets.push(gcp_credentials.private_key.to_string());
+                }
+                CloudProviderOptions::Azure { .. } => {}
+                CloudProviderOptions::OnPremise { .. } => {}
+            };
+        }
 
         secrets
     }
@@ -507,28 +1033,34 @@ impl Task for EnvironmentTask {
                 return;
             }
         };
-        let env_step = self
-            .request
-            .target_environment
-            .action
-            .to_service_action()
-            .to_environment_step();
-        let event_details = self.get_event_details(env_step);
-        let environment = match self.request.target_environment.to_environment_domain(
-            infra_context.context(),
-            infra_context.cloud_provider(),
-            infra_context.container_registry(),
-            infra_context.kubernetes(),
-        ) {
+        let mut environment = match Self::to_environment(&self.request, &infra_context) {
             Ok(env) => env,
             Err(err) => {
-                self.logger.log(EngineEvent::Error(
-                    EngineError::new_invalid_engine_payload(event_details, err.to_string().as_str(), None),
-                    None,
-                ));
+                self.logger.log(EngineEvent::Error(*err, None));
+                return;
+            }
+        };
+        let additional_clusters = match self.additional_clusters_deployments() {
+            Ok(additional_clusters) => additional_clusters,
+            Err(err) => {
+                self.logger.log(EngineEvent::Error(*err, None));
                 return;
             }
         };
+        if let Some(policy) = self
+            .request
+            .multi_cluster
+            .as_ref()
+            .and_then(|multi_cluster| multi_cluster.dns_routing_policy.as_ref())
+        {
+            environment.dns_routing = match Self::dns_routing(policy, &self.request, &infra_context) {
+                Ok(dns_routing) => Some(dns_routing),
+                Err(err) => {
+                    self.logger.log(EngineEvent::Error(*err, None));
+                    return;
+                }
+            };
+        }
 
         // run the actions
 
@@ -548,12 +1080,53 @@ impl Task for EnvironmentTask {
             .map(|service_id| metrics_registry.start_record(*service_id, StepLabel::Service, StepName::Total))
             .collect();
 
-        let deployment_ret =
-            EnvironmentTask::deploy_environment(environment, &infra_context, self.cancel_checker().as_ref());
+        let is_dry_run = infra_context.context().is_dry_run_deploy();
+        let deployment_ret = match &self.request.multi_cluster {
+            _ if is_dry_run => {
+                EnvironmentTask::dry_run_environment(&environment, &infra_context, self.cancel_checker().as_ref())
+            }
+            Some(multi_cluster) if !additional_clusters.is_empty() => EnvironmentTask::deploy_environment_to_clusters(
+                environment,
+                &infra_context,
+                additional_clusters,
+                multi_cluster.strategy,
+                self.cancel_checker().as_ref(),
+            ),
+            _ => EnvironmentTask::deploy_environment(environment, &infra_context, self.cancel_checker().as_ref()),
+        };
+
+        // Expiration of ephemeral environments is only pushed back once they are successfully deployed
+        let deployment_ret = match deployment_ret {
+            Ok(()) if !is_dry_run && self.request.action == Action::Create => {
+                infra_context.mk_kube_client().and_then(|kube| {
+                    save_environment_snapshot(
+                        &kube.client(),
+                        &self.request.target_environment,
+                        self.get_event_details(EnvironmentStep::Deploy),
+                    )
+                })
+            }
+            deployment_ret => deployment_ret,
+        };
 
         Self::stop_total_steps_records(&deployment_ret, record, service_records);
 
         match (&self.request.action, deployment_ret) {
+            // A dry run leaves the environment untouched, its status must not change
+            (_, Ok(())) if is_dry_run => self.logger.log(EngineEvent::Info(
+                self.get_event_details(EnvironmentStep::DryRun),
+                EventMessage::new("👻 Dry run succeeded, nothing has been deployed".to_string(), None),
+            )),
+            (_, Err(err)) if is_dry_run => self.logger.log(EngineEvent::Info(
+                self.get_event_details(EnvironmentStep::DryRunError),
+                EventMessage::new(
+                    match err.tag().is_cancel() {
+                        true => "🚫 Dry run has been canceled at user request 🚫".to_string(),
+                        false => "💣 Dry run failed, nothing has been deployed".to_string(),
+                    },
+                    Some(err.message(ErrorMessageVerbosity::FullDetailsWithoutEnvVars)),
+                ),
+            )),
             (Action::Create, Ok(())) => self.logger.log(EngineEvent::Info(
                 self.get_event_details(EnvironmentStep::Deployed),
                 EventMessage::new("❤️ Deployment succeeded ❤️".to_string(), None),
@@ -772,6 +1345,8 @@ impl BuilderThreadPool {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::events::Transmitter;
+    use crate::io_models::QoveryIdentifier;
     use std::sync::atomic::AtomicUsize;
     use std::time::Duration;
 
@@ -848,4 +1423,103 @@ mod test {
         assert!(ret.is_err());
         assert_ne!(active_taks.load(Ordering::Relaxed), 10);
     }
+
+    fn cluster_event_details() -> EventDetails {
+        EventDetails::new(
+            None,
+            QoveryIdentifier::new_random(),
+            QoveryIdentifier::new_random(),
+            Uuid::new_v4().to_string(),
+            Stage::Environment(EnvironmentStep::Deploy),
+            Transmitter::Environment(Uuid::new_v4(), "environment".to_string()),
+        )
+    }
+
+    fn cluster_deployment(cluster: usize, failing_clusters: &[usize]) -> Result<(), Box<EngineError>> {
+        if cluster == 99 {
+            panic!("cluster {cluster} exploded");
+        }
+        if failing_clusters.contains(&cluster) {
+            return Err(Box::new(EngineError::new_unknown(
+                cluster_event_details(),
+                format!("cluster {cluster} failed"),
+                None,
+                None,
+                None,
+            )));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_run_on_clusters_sequential_halts_on_first_failure() {
+        let deployed = std::sync::Mutex::new(vec![]);
+        let cancelled = std::sync::Mutex::new(vec![]);
+
+        let ret = EnvironmentTask::run_on_clusters(
+            vec![0, 1, 2, 3],
+            MultiClusterDeploymentStrategy::Sequential,
+            |cluster| {
+                deployed.lock().unwrap().push(cluster);
+                cluster_deployment(cluster, &[1])
+            },
+            |cluster| cancelled.lock().unwrap().push(cluster),
+            cluster_event_details(),
+        );
+
+        assert_eq!(ret.unwrap_err().user_log_message(), "cluster 1 failed");
+        assert_eq!(*deployed.lock().unwrap(), vec![0, 1]);
+        assert_eq!(*cancelled.lock().unwrap(), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_run_on_clusters_parallel_deploys_every_cluster() {
+        let deployed = AtomicUsize::new(0);
+        let cancelled = AtomicUsize::new(0);
+
+        let ret = EnvironmentTask::run_on_clusters(
+            vec![0, 1, 2, 3],
+            MultiClusterDeploymentStrategy::Parallel,
+            |cluster| {
+                deployed.fetch_add(1, Ordering::Relaxed);
+                cluster_deployment(cluster, &[1, 3])
+            },
+            |_| {
+                cancelled.fetch_add(1, Ordering::Relaxed);
+            },
+            cluster_event_details(),
+        );
+
+        // Failures do not stop other clusters, the first one is reported
+        assert_eq!(ret.unwrap_err().user_log_message(), "cluster 1 failed");
+        assert_eq!(deployed.load(Ordering::Relaxed), 4);
+        assert_eq!(cancelled.load(Ordering::Relaxed), 0);
+
+        let ret = EnvironmentTask::run_on_clusters(
+            vec![0, 1],
+            MultiClusterDeploymentStrategy::Parallel,
+            |cluster| cluster_deployment(cluster, &[]),
+            |_| {},
+            cluster_event_details(),
+        );
+        assert!(ret.is_ok());
+    }
+
+    #[test]
+    fn test_run_on_clusters_parallel_reports_panics_as_errors() {
+        let ret = EnvironmentTask::run_on_clusters(
+            vec![0, 99, 2],
+            MultiClusterDeploymentStrategy::Parallel,
+            |cluster| cluster_deployment(cluster, &[]),
+            |_| {},
+            cluster_event_details(),
+        );
+
+        let err = ret.unwrap_err();
+        assert_eq!(err.user_log_message(), "Cluster deployment failed unexpectedly");
+        assert_eq!(
+            err.underlying_error().and_then(|err| err.message_raw()),
+            Some("cluster 99 exploded".to_string())
+        );
+    }
 }
@@ -21,6 +21,8 @@ impl From<errors::CommandError> for CommandError {
 #[derive(Deserialize, Serialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Tag {
+    AdmissionPolicyEvaluationError,
+    AdmissionPolicyViolation,
     AwsCloudwatchRetentionConfigurationError,
     AwsSdkDetachEC2Volumes,
     AwsSdkGetClient,
@@ -49,6 +51,7 @@ pub enum Tag {
     CannotDetermineK8sRequestedUpgradeVersion,
     CannotExecuteK8sApiCustomMetrics,
     CannotExecuteK8sVersion,
+    CannotFetchImageArtifact,
     CannotFetchScalewayPrivateNetworks,
     CannotFindRequiredBinary,
     CannotGetAnyAvailableVPC,
@@ -61,6 +64,7 @@ pub enum Tag {
     CannotGetSupportedVersions,
     CannotGetWorkspaceDirectory,
     CannotListClusters,
+    CannotManageClusterPauseState,
     CannotParseString,
     CannotPatchHelmAdmissionControllerConfigMap,
     CannotPauseClusterTasksAreRunning,
@@ -70,6 +74,7 @@ pub enum Tag {
     CannotRetrieveClusterConfigFile,
     CannotUpgradeClusterDeprecatedKubernetesApiCallDetected,
     CannotUninstallHelmChart,
+    CannotUpdateNodeGroup,
     CannotWriteToFile,
     ClientServiceFailedToDeployBeforeStart,
     ClientServiceFailedToStart,
@@ -79,6 +84,8 @@ pub enum Tag {
     CloudProviderGetLoadBalancer,
     CloudProviderGetLoadBalancerTags,
     CloudProviderInformationError,
+    ClusterBackupRestoreError,
+    ClusterBlueGreenUpgradeRolledBack,
     ClusterHasNoWorkerNodes,
     ClusterWorkerNodeNotFound,
     CompressionError,
@@ -114,6 +121,7 @@ pub enum Tag {
     DockerError,
     DockerPullImageError,
     DockerPushImageError,
+    EnvironmentsCostsError,
     HelmChartUninstallError,
     HelmChartsDeployError,
     HelmChartsSetupError,
@@ -122,6 +130,7 @@ pub enum Tag {
     HelmHistoryError,
     HelmReleaseDataNotFound,
     HelmSecretNotFound,
+    ImageMissingArchitectures,
     InvalidEngineApiInputCannotBeDeserialized,
     InvalidEnginePayload,
     InvalidJobOutputCannotBeSerialized,
@@ -149,6 +158,7 @@ pub enum Tag {
     K8sDeleteKarpenterNodesError,
     K8sDeleteStatefulsetError,
     K8sDescribe,
+    K8sDrainNodeError,
     K8sErrorCopySecret,
     K8sGetCrdError,
     K8sGetDeploymentError,
@@ -512,6 +522,16 @@ impl From<errors::Tag> for Tag {
             errors::Tag::CannotUpgradeClusterDeprecatedKubernetesApiCallDetected => {
                 Tag::CannotUpgradeClusterDeprecatedKubernetesApiCallDetected
             }
+            errors::Tag::ImageMissingArchitectures => Tag::ImageMissingArchitectures,
+            errors::Tag::CannotFetchImageArtifact => Tag::CannotFetchImageArtifact,
+            errors::Tag::CannotManageClusterPauseState => Tag::CannotManageClusterPauseState,
+            errors::Tag::K8sDrainNodeError => Tag::K8sDrainNodeError,
+            errors::Tag::ClusterBlueGreenUpgradeRolledBack => Tag::ClusterBlueGreenUpgradeRolledBack,
+            errors::Tag::ClusterBackupRestoreError => Tag::ClusterBackupRestoreError,
+            errors::Tag::AdmissionPolicyViolation => Tag::AdmissionPolicyViolation,
+            errors::Tag::AdmissionPolicyEvaluationError => Tag::AdmissionPolicyEvaluationError,
+            errors::Tag::EnvironmentsCostsError => Tag::EnvironmentsCostsError,
+            errors::Tag::CannotUpdateNodeGroup => Tag::CannotUpdateNodeGroup,
         }
     }
 }
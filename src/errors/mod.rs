@@ -13,12 +13,14 @@ use crate::infrastructure::models::container_registry::errors::ContainerRegistry
 
 use crate::cmd::{command, terraform};
 use crate::environment::models::database::DatabaseError;
+use crate::environment::models::environment::AdmissionPolicyViolation;
 use crate::environment::models::router::RouterError;
 use crate::environment::models::types::VersionsNumber;
 use crate::events::{EventDetails, Stage};
 use crate::infrastructure::models::cloud_provider::io::InputError;
 use crate::infrastructure::models::kubernetes::KubernetesError;
 use crate::infrastructure::models::object_storage::errors::ObjectStorageError;
+use crate::io_models::models::CpuArchitecture;
 use crate::services::kubernetes_api_deprecation_service::KubernetesDeprecationServiceError;
 use aws_sdk_docdb::error::SdkError as DocdbSdkError;
 use aws_sdk_docdb::operation::describe_db_clusters::DescribeDBClustersError;
@@ -31,6 +33,7 @@ use aws_sdk_elasticache::operation::describe_cache_clusters::DescribeCacheCluste
 use aws_sdk_rds::error::SdkError as RdsSdkError;
 use aws_sdk_rds::operation::describe_db_instances::DescribeDBInstancesError;
 use derivative::Derivative;
+use itertools::Itertools;
 use kube::Resource;
 use kube::error::Error as KubeError;
 use serde::de::DeserializeOwned;
@@ -600,6 +603,25 @@ impl From<BuildError> for CommandError {
                 Some(raw_error.to_string()),
                 None,
             ),
+            BuildError::ArchitectureDockerError {
+                application,
+                architecture,
+                raw_error,
+            } => CommandError::new(
+                format!(
+                    "Build error, cannot build application `{application}` for architecture `{architecture}` due to a Docker error"
+                ),
+                Some(raw_error.to_string()),
+                None,
+            ),
+            BuildError::MissingArchitectures {
+                application,
+                image,
+                missing_architectures,
+            } => CommandError::new_from_safe_message(format!(
+                "Build error, image `{image}` of application `{application}` is not available for architectures `{}`",
+                missing_architectures.iter().join(", ")
+            )),
             BuildError::CannotGetCredentials { .. } => {
                 CommandError::new("Build error, cannot get registry credentials".to_string(), None, None)
             }
@@ -632,6 +654,11 @@ impl From<DockerError> for CommandError {
                 Some(raw_error_message),
                 None,
             ),
+            DockerError::InvalidManifest { raw_error_message } => CommandError::new(
+                "Docker error, cannot read image manifest".to_string(),
+                Some(raw_error_message),
+                None,
+            ),
         }
     }
 }
@@ -1166,6 +1193,26 @@ pub enum Tag {
     CannotCreateAwsServiceLinkedRoleForSpotInstance,
     /// CannotUpgradeClusterDeprecatedKubernetesApiCallDetected: represents an error while trying to upgrade a cluster having deprecated Kubernetes API calls
     CannotUpgradeClusterDeprecatedKubernetesApiCallDetected,
+    /// ImageMissingArchitectures: represents an error when an image is not available for all the architectures of the cluster nodes
+    ImageMissingArchitectures,
+    /// CannotFetchImageArtifact: represents an error while trying to download or verify a pre-built image artifact
+    CannotFetchImageArtifact,
+    /// CannotManageClusterPauseState: represents an error while trying to read or write the state recorded when pausing a cluster
+    CannotManageClusterPauseState,
+    /// K8sDrainNodeError: represents an error while trying to cordon a node or evict its pods
+    K8sDrainNodeError,
+    /// ClusterBlueGreenUpgradeRolledBack: represents a blue/green worker nodes upgrade rolled back because workloads were not healthy on new nodes
+    ClusterBlueGreenUpgradeRolledBack,
+    /// ClusterBackupRestoreError: represents an error while restoring namespaces from a cluster backup
+    ClusterBackupRestoreError,
+    /// AdmissionPolicyViolation: represents manifests violating the organization admission policies
+    AdmissionPolicyViolation,
+    /// AdmissionPolicyEvaluationError: represents an error while evaluating manifests against the organization admission policies
+    AdmissionPolicyEvaluationError,
+    /// EnvironmentsCostsError: represents an error while computing or reporting the cluster environments costs
+    EnvironmentsCostsError,
+    /// CannotUpdateNodeGroup: represents an error while trying to update the nodes images of a node group
+    CannotUpdateNodeGroup,
 }
 
 impl Tag {
@@ -1939,6 +1986,38 @@ impl EngineError {
         )
     }
 
+    /// Creates new error for a service requesting more resources than any node of the cluster can offer.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `service_name`: Service name.
+    /// * `requested_cpu_in_milli`: How much CPU in milli is requested by an instance of the service.
+    /// * `requested_ram_in_mib`: How much RAM in mib is requested by an instance of the service.
+    /// * `max_node_cpu_in_milli`: How much CPU in milli nodes can offer at most to services.
+    /// * `max_node_ram_in_mib`: How much RAM in mib nodes can offer at most to services.
+    pub fn new_service_cannot_fit_on_any_node(
+        event_details: EventDetails,
+        service_name: &str,
+        requested_cpu_in_milli: u32,
+        requested_ram_in_mib: u32,
+        max_node_cpu_in_milli: u32,
+        max_node_ram_in_mib: u32,
+    ) -> EngineError {
+        let message = format!(
+            "Service `{service_name}` requests {requested_cpu_in_milli}m CPU and {requested_ram_in_mib}mib RAM, it cannot fit on any node of the cluster: nodes can offer at most {max_node_cpu_in_milli}m CPU and {max_node_ram_in_mib}mib RAM to services."
+        );
+
+        EngineError::new(
+            event_details,
+            Tag::NotEnoughResourcesToDeployEnvironment,
+            message,
+            None,
+            None,
+            Some("Consider to lower the service CPU / RAM requests or to add bigger instance types to your cluster nodes configuration.".to_string()),
+        )
+    }
+
     /// Creates new error for cannot uninstall an helm chart.
     ///
     /// Arguments:
@@ -4041,6 +4120,23 @@ impl EngineError {
         EngineError::new(event_details, Tag::CannotDeleteNodeGroup, message, None, None, None)
     }
 
+    /// Can't update node group nodes to the latest image of their kubernetes version
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `nodegroup_name`: Node group name.
+    /// * `raw_error`: Raw error message.
+    pub fn new_nodegroup_update_error(
+        event_details: EventDetails,
+        nodegroup_name: String,
+        raw_error: CommandError,
+    ) -> EngineError {
+        let message = format!("Error, can't update nodes images of nodegroup '{nodegroup_name}'.");
+
+        EngineError::new(event_details, Tag::CannotUpdateNodeGroup, message, Some(raw_error), None, None)
+    }
+
     /// No cluster found
     ///
     /// Arguments:
@@ -5270,6 +5366,202 @@ impl EngineError {
         )
     }
 
+    /// Creates new error when an image cannot run on every node of the cluster
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `image_name`: Name of the image.
+    /// * `missing_architectures`: Architectures of the cluster nodes the image is not available for.
+    pub fn new_image_missing_architectures(
+        event_details: EventDetails,
+        image_name: &str,
+        missing_architectures: &[CpuArchitecture],
+    ) -> EngineError {
+        let message_safe = format!(
+            "Image `{}` is not available for architectures `{}` required by the cluster node groups",
+            image_name,
+            missing_architectures.iter().join(", ")
+        );
+
+        EngineError::new(
+            event_details,
+            Tag::ImageMissingArchitectures,
+            message_safe,
+            None,
+            None,
+            Some("Build and push a multi-architecture image, or remove the node groups of this architecture from the cluster".to_string()),
+        )
+    }
+
+    /// Creates new error when a pre-built image artifact cannot be downloaded or verified
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `artifact_location`: Object storage key or url of the artifact.
+    /// * `raw_error`: Raw error message.
+    pub fn new_cannot_fetch_image_artifact(
+        event_details: EventDetails,
+        artifact_location: &str,
+        raw_error: CommandError,
+    ) -> EngineError {
+        let message_safe = format!("Cannot fetch image artifact `{artifact_location}`");
+
+        EngineError::new(
+            event_details,
+            Tag::CannotFetchImageArtifact,
+            message_safe,
+            Some(raw_error),
+            None,
+            Some("Make sure the artifact exists, is reachable from the cluster and matches its checksum".to_string()),
+        )
+    }
+
+    /// Creates new error when the state recorded at cluster pause time cannot be read or written
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `raw_error`: Raw error message.
+    pub fn new_cannot_manage_cluster_pause_state(event_details: EventDetails, raw_error: CommandError) -> EngineError {
+        EngineError::new(
+            event_details,
+            Tag::CannotManageClusterPauseState,
+            "Cannot manage the cluster pause state".to_string(),
+            Some(raw_error),
+            None,
+            Some("The node and replica counts recorded at pause time are stored in the `qovery-cluster-pause-state` config map of the `kube-system` namespace".to_string()),
+        )
+    }
+
+    /// Creates new error when a node cannot be cordoned or drained
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `node_name`: Node being drained.
+    /// * `raw_error`: Raw error message.
+    pub fn new_k8s_drain_node_error(
+        event_details: EventDetails,
+        node_name: String,
+        raw_error: CommandError,
+    ) -> EngineError {
+        EngineError::new(
+            event_details,
+            Tag::K8sDrainNodeError,
+            format!("Cannot drain node `{node_name}`"),
+            Some(raw_error),
+            None,
+            Some("Make sure pod disruption budgets allow pods to be evicted from this node".to_string()),
+        )
+    }
+
+    /// Creates new error when a blue/green worker nodes upgrade has been rolled back
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `raw_error`: Raw error message.
+    pub fn new_cluster_blue_green_upgrade_rolled_back(
+        event_details: EventDetails,
+        raw_error: CommandError,
+    ) -> EngineError {
+        EngineError::new(
+            event_details,
+            Tag::ClusterBlueGreenUpgradeRolledBack,
+            "Workloads are not healthy on upgraded nodes, worker nodes upgrade has been rolled back".to_string(),
+            Some(raw_error),
+            None,
+            Some(
+                "Previous worker nodes have been kept, check your workloads health before retrying the upgrade"
+                    .to_string(),
+            ),
+        )
+    }
+
+    /// Creates new error when namespaces cannot be restored from a cluster backup
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `backup_name`: Backup being restored.
+    /// * `raw_error`: Raw error message.
+    pub fn new_cluster_backup_restore_error(
+        event_details: EventDetails,
+        backup_name: String,
+        raw_error: CommandError,
+    ) -> EngineError {
+        EngineError::new(
+            event_details,
+            Tag::ClusterBackupRestoreError,
+            format!("Cannot restore cluster backup `{backup_name}`"),
+            Some(raw_error),
+            None,
+            Some("Make sure cluster backups are enabled on this cluster and the backup exists".to_string()),
+        )
+    }
+
+    /// Creates new error when the cluster environments costs cannot be computed or reported
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `raw_error`: Raw error message.
+    pub fn new_environments_costs_error(event_details: EventDetails, raw_error: CommandError) -> EngineError {
+        EngineError::new(
+            event_details,
+            Tag::EnvironmentsCostsError,
+            "Cannot compute environments costs".to_string(),
+            Some(raw_error),
+            None,
+            Some("Make sure OpenCost is enabled on this cluster and its metrics are available".to_string()),
+        )
+    }
+
+    /// Creates new error for manifests violating the organization admission policies
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `violations`: Offending objects and the policies they violate.
+    pub fn new_admission_policy_violation(
+        event_details: EventDetails,
+        violations: &[AdmissionPolicyViolation],
+    ) -> EngineError {
+        let message = format!(
+            "{} object(s) violate the organization admission policies:\n{}",
+            violations.len(),
+            violations.iter().map(|violation| format!("- {violation}")).join("\n")
+        );
+
+        EngineError::new(
+            event_details,
+            Tag::AdmissionPolicyViolation,
+            message,
+            None,
+            None,
+            Some("Update the service configuration to comply with the policies, or ask your organization administrator to review them".to_string()),
+        )
+    }
+
+    /// Creates new error when manifests cannot be evaluated against the organization admission policies
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `raw_error`: Raw error message.
+    pub fn new_admission_policy_evaluation_error(event_details: EventDetails, raw_error: CommandError) -> EngineError {
+        EngineError::new(
+            event_details,
+            Tag::AdmissionPolicyEvaluationError,
+            "Cannot evaluate manifests against the organization admission policies".to_string(),
+            Some(raw_error),
+            None,
+            Some("Make sure the admission policies are valid Rego modules".to_string()),
+        )
+    }
+
     /// Creates new error when attempting to create the service-linked rome for Spot Instances
     ///
     /// Arguments:
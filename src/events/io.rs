@@ -141,6 +141,16 @@ pub enum InfrastructureStep {
     Restart,
     Restarted,
     RestartedError,
+    Restore,
+    Restored,
+    RestoreError,
+    Maintenance,
+    Maintained,
+    MaintenanceSkipped,
+    MaintenanceError,
+    EnvironmentsCosts,
+    EnvironmentsCostsReported,
+    EnvironmentsCostsError,
     CannotProcessRequest,
     GlobalError,
     InfrastructureDiff,
@@ -171,6 +181,16 @@ impl From<events::InfrastructureStep> for InfrastructureStep {
             events::InfrastructureStep::Restart => InfrastructureStep::Restart,
             events::InfrastructureStep::Restarted => InfrastructureStep::Restarted,
             events::InfrastructureStep::RestartedError => InfrastructureStep::RestartedError,
+            events::InfrastructureStep::Restore => InfrastructureStep::Restore,
+            events::InfrastructureStep::Restored => InfrastructureStep::Restored,
+            events::InfrastructureStep::RestoreError => InfrastructureStep::RestoreError,
+            events::InfrastructureStep::Maintenance => InfrastructureStep::Maintenance,
+            events::InfrastructureStep::Maintained => InfrastructureStep::Maintained,
+            events::InfrastructureStep::MaintenanceSkipped => InfrastructureStep::MaintenanceSkipped,
+            events::InfrastructureStep::MaintenanceError => InfrastructureStep::MaintenanceError,
+            events::InfrastructureStep::EnvironmentsCosts => InfrastructureStep::EnvironmentsCosts,
+            events::InfrastructureStep::EnvironmentsCostsReported => InfrastructureStep::EnvironmentsCostsReported,
+            events::InfrastructureStep::EnvironmentsCostsError => InfrastructureStep::EnvironmentsCostsError,
             events::InfrastructureStep::CannotProcessRequest => InfrastructureStep::CannotProcessRequest,
             events::InfrastructureStep::GlobalError => InfrastructureStep::GlobalError,
             events::InfrastructureStep::InfrastructureDiff(_) => InfrastructureStep::InfrastructureDiff,
@@ -210,6 +230,8 @@ pub enum EnvironmentStep {
     Restarted,
     RestartedError,
     TerraformServiceOutput,
+    DryRun,
+    DryRunError,
 }
 
 impl From<events::EnvironmentStep> for EnvironmentStep {
@@ -245,6 +267,8 @@ impl From<events::EnvironmentStep> for EnvironmentStep {
             events::EnvironmentStep::Recap => EnvironmentStep::Recap,
             events::EnvironmentStep::GlobalError => EnvironmentStep::GlobalError,
             events::EnvironmentStep::TerraformServiceOutput => EnvironmentStep::TerraformServiceOutput,
+            events::EnvironmentStep::DryRun => EnvironmentStep::DryRun,
+            events::EnvironmentStep::DryRunError => EnvironmentStep::DryRunError,
         }
     }
 }
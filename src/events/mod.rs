@@ -347,6 +347,26 @@ pub enum InfrastructureStep {
     Restarted,
     /// RestartedError: error on restarting a cluster.
     RestartedError,
+    /// Restore: restoring namespaces from a cluster backup.
+    Restore,
+    /// Restored: namespaces restore is ok.
+    Restored,
+    /// RestoreError: error on restoring namespaces from a cluster backup.
+    RestoreError,
+    /// Maintenance: applying pending patch upgrades and node images updates during the maintenance window.
+    Maintenance,
+    /// Maintained: cluster maintenance is ok.
+    Maintained,
+    /// MaintenanceSkipped: cluster maintenance didn't run, as the maintenance window is closed.
+    MaintenanceSkipped,
+    /// MaintenanceError: error on applying cluster maintenance.
+    MaintenanceError,
+    /// EnvironmentsCosts: computing the costs of the cluster environments.
+    EnvironmentsCosts,
+    /// EnvironmentsCostsReported: environments costs are computed and reported.
+    EnvironmentsCostsReported,
+    /// EnvironmentsCostsError: error on computing or reporting environments costs.
+    EnvironmentsCostsError,
     /// CannotProcessRequest: error returned if the payload sent is wrong
     CannotProcessRequest,
 }
@@ -379,6 +399,16 @@ impl Display for InfrastructureStep {
                 InfrastructureStep::Restart => "restart",
                 InfrastructureStep::Restarted => "restarted",
                 InfrastructureStep::RestartedError => "restart-error",
+                InfrastructureStep::Restore => "restore",
+                InfrastructureStep::Restored => "restored",
+                InfrastructureStep::RestoreError => "restore-error",
+                InfrastructureStep::Maintenance => "maintenance",
+                InfrastructureStep::Maintained => "maintained",
+                InfrastructureStep::MaintenanceSkipped => "maintenance-skipped",
+                InfrastructureStep::MaintenanceError => "maintenance-error",
+                InfrastructureStep::EnvironmentsCosts => "environments-costs",
+                InfrastructureStep::EnvironmentsCostsReported => "environments-costs-reported",
+                InfrastructureStep::EnvironmentsCostsError => "environments-costs-error",
                 InfrastructureStep::CannotProcessRequest => "cannot-process-request",
                 InfrastructureStep::GlobalError => "global-error",
                 InfrastructureStep::InfrastructureDiff(name) => match name {
@@ -461,6 +491,11 @@ pub enum EnvironmentStep {
 
     /// TerraformServiceOutput: contains the environment variables to upsert
     TerraformServiceOutput,
+
+    /// DryRun: diff of a service or environment computed without deploying it, no status change is implied
+    DryRun,
+    /// DryRunError: Error while computing the diff of a service or environment, no status change is implied
+    DryRunError,
 }
 
 impl EnvironmentStep {
@@ -473,6 +508,7 @@ impl EnvironmentStep {
                 | EnvironmentStep::PausedError
                 | EnvironmentStep::DeletedError
                 | EnvironmentStep::RestartedError
+                | EnvironmentStep::DryRunError
         )
     }
 
@@ -517,6 +553,8 @@ impl Display for EnvironmentStep {
                 EnvironmentStep::Recap => "recap",
                 EnvironmentStep::GlobalError => "global-error",
                 EnvironmentStep::TerraformServiceOutput => "terraform-service-output",
+                EnvironmentStep::DryRun => "dry-run",
+                EnvironmentStep::DryRunError => "dry-run-error",
             },
         )
     }
@@ -652,6 +690,15 @@ impl EventDetails {
                 InfrastructureStep::Restart | InfrastructureStep::Restarted => {
                     Stage::Infrastructure(InfrastructureStep::RestartedError)
                 }
+                InfrastructureStep::Restore | InfrastructureStep::Restored => {
+                    Stage::Infrastructure(InfrastructureStep::RestoreError)
+                }
+                InfrastructureStep::Maintenance
+                | InfrastructureStep::Maintained
+                | InfrastructureStep::MaintenanceSkipped => Stage::Infrastructure(InfrastructureStep::MaintenanceError),
+                InfrastructureStep::EnvironmentsCosts | InfrastructureStep::EnvironmentsCostsReported => {
+                    Stage::Infrastructure(InfrastructureStep::EnvironmentsCostsError)
+                }
                 InfrastructureStep::LoadConfiguration
                 | InfrastructureStep::ValidateApiInput
                 | InfrastructureStep::ValidateSystemRequirements
@@ -665,6 +712,9 @@ impl EventDetails {
                 | InfrastructureStep::UpgradeError
                 | InfrastructureStep::DeleteError
                 | InfrastructureStep::RestartedError
+                | InfrastructureStep::RestoreError
+                | InfrastructureStep::MaintenanceError
+                | InfrastructureStep::EnvironmentsCostsError
                 | InfrastructureStep::InfrastructureDiff(_)
                 | InfrastructureStep::CannotProcessRequest => return,
             },
@@ -678,6 +728,7 @@ impl EventDetails {
                 EnvironmentStep::Restart | EnvironmentStep::Restarted => {
                     Stage::Environment(EnvironmentStep::RestartedError)
                 }
+                EnvironmentStep::DryRun => Stage::Environment(EnvironmentStep::DryRunError),
                 EnvironmentStep::LoadConfiguration
                 | EnvironmentStep::ValidateApiInput
                 | EnvironmentStep::ValidateSystemRequirements
@@ -697,7 +748,8 @@ impl EventDetails {
                 | EnvironmentStep::JobOutput
                 | EnvironmentStep::Recap
                 | EnvironmentStep::DatabaseOutput
-                | EnvironmentStep::TerraformServiceOutput => return,
+                | EnvironmentStep::TerraformServiceOutput
+                | EnvironmentStep::DryRunError => return,
             },
         };
     }
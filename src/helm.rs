@@ -67,6 +67,10 @@ pub enum HelmChartNamespaces {
     CertManager,
     NginxIngress,
     Qovery,
+    Keda,
+    EnvoyGateway,
+    Velero,
+    OpenCost,
     Custom(String),
 }
 
@@ -80,6 +84,10 @@ impl Display for HelmChartNamespaces {
             HelmChartNamespaces::CertManager => "cert-manager",
             HelmChartNamespaces::NginxIngress => "nginx-ingress",
             HelmChartNamespaces::Qovery => "qovery",
+            HelmChartNamespaces::Keda => "keda",
+            HelmChartNamespaces::EnvoyGateway => "envoy-gateway-system",
+            HelmChartNamespaces::Velero => "velero",
+            HelmChartNamespaces::OpenCost => "opencost",
         };
 
         f.write_str(str)
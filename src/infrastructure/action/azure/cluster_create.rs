@@ -3,17 +3,22 @@ use crate::events::Stage::Infrastructure;
 use crate::events::{EventDetails, InfrastructureStep};
 use crate::infrastructure::action::azure::AksQoveryTerraformOutput;
 use crate::infrastructure::action::azure::helm_charts::AksHelmsDeployment;
+use crate::infrastructure::action::cluster_backup::check_cluster_backup_support;
 use crate::infrastructure::action::cluster_outputs_helper::update_cluster_outputs;
+use crate::infrastructure::action::cluster_pause_state::ClusterPauseState;
 use crate::infrastructure::action::deploy_helms::{HelmInfraContext, HelmInfraResources};
 use crate::infrastructure::action::deploy_terraform::TerraformInfraResources;
 use crate::infrastructure::action::kubectl_utils::check_workers_on_create;
 use crate::infrastructure::action::{InfraLogger, ToInfraTeraContext};
 use crate::infrastructure::infrastructure_context::InfrastructureContext;
 use crate::infrastructure::models::container_registry::RegistryTags;
+use crate::infrastructure::models::cost_allocation::CostAllocationTags;
 use crate::infrastructure::models::kubernetes::Kubernetes;
 use crate::infrastructure::models::kubernetes::azure::aks::AKS;
 use crate::infrastructure::models::object_storage::azure_object_storage::StorageAccount;
+use crate::runtime::block_on;
 use crate::utilities::envs_to_string;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 pub(super) fn create_aks_cluster(
@@ -24,11 +29,38 @@ pub(super) fn create_aks_cluster(
     let event_details = cluster.get_event_details(Infrastructure(InfrastructureStep::Create));
 
     logger.info("Preparing AKS cluster deployment.");
+    check_cluster_backup_support(cluster, &event_details)?;
 
     logger.info("Deploying AKS cluster.");
 
     // Terraform deployment dedicated to cloud resources
-    let tera_context = cluster.to_infra_tera_context(infra_ctx)?;
+    let mut tera_context = cluster.to_infra_tera_context(infra_ctx)?;
+
+    // If the cluster has been paused, restore user node pools with the node counts recorded at pause time
+    let pause_state = infra_ctx
+        .mk_kube_client()
+        .ok()
+        .and_then(|kube_client| block_on(ClusterPauseState::load(&kube_client.client(), &event_details)).ok())
+        .flatten();
+    if let Some(pause_state) = &pause_state {
+        logger.info("Resuming paused cluster, restoring node pools as they were before pause.");
+        let restored_nodes: BTreeMap<String, u32> = cluster
+            .node_groups
+            .get_additional_node_groups()
+            .iter()
+            .filter_map(|node_group| {
+                pause_state
+                    .node_pool_count(
+                        &node_group.name,
+                        node_group.min_nodes.max(0) as u32,
+                        node_group.max_nodes.max(0) as u32,
+                    )
+                    .map(|count| (node_group.name.clone(), count))
+            })
+            .collect();
+        tera_context.insert("node_groups_restored_nodes", &restored_nodes);
+    }
+
     let tf_resources = TerraformInfraResources::new(
         tera_context.clone(),
         cluster.template_directory.join("terraform"),
@@ -46,6 +78,10 @@ pub(super) fn create_aks_cluster(
 
     logger.info("Kubernetes nodes have been successfully created");
 
+    if pause_state.is_some() {
+        block_on(ClusterPauseState::clear(&infra_ctx.mk_kube_client()?.client(), &event_details))?;
+    }
+
     // Create Qovery managed blob container
     if let Err(err) = create_object_storage(
         cluster,
@@ -106,6 +142,10 @@ fn create_container_registry(
                 environment_id: None,
                 project_id: None,
                 resource_ttl: kubernetes.advanced_settings().resource_ttl(),
+                cost_allocation_tags: CostAllocationTags::for_cluster(
+                    *kubernetes.context().organization_long_id(),
+                    *kubernetes.long_id(),
+                ),
             },
         )
         .map_err(|e| EngineError::new_container_registry_error(event_details.clone(), e))?;
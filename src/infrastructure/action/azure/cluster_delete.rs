@@ -3,7 +3,7 @@ use crate::events::Stage::Infrastructure;
 use crate::events::{EventDetails, EventMessage, InfrastructureStep};
 use crate::infrastructure::action::azure::AksQoveryTerraformOutput;
 use crate::infrastructure::action::cluster_outputs_helper::update_cluster_outputs;
-use crate::infrastructure::action::delete_kube_apps::{delete_all_pdbs, delete_kube_apps};
+use crate::infrastructure::action::delete_kube_apps::{KubeAppsDeletionScope, delete_all_pdbs, delete_kube_apps};
 use crate::infrastructure::action::deploy_terraform::TerraformInfraResources;
 use crate::infrastructure::action::{InfraLogger, ToInfraTeraContext};
 use crate::infrastructure::infrastructure_context::InfrastructureContext;
@@ -50,7 +50,14 @@ pub(super) fn delete_aks_cluster(
         logger.warn("Cannot delete all PDBs, this is not blocking cluster deletion.");
     }
 
-    delete_kube_apps(cluster, infra_ctx, event_details.clone(), &logger, HashSet::with_capacity(0))?;
+    delete_kube_apps(
+        cluster,
+        infra_ctx,
+        event_details.clone(),
+        &logger,
+        HashSet::with_capacity(0),
+        KubeAppsDeletionScope::AllApps,
+    )?;
 
     // Delete cluster CR before terraform destroy because resource group should be deleted
     delete_container_registry(infra_ctx, event_details.clone())?;
@@ -1,16 +1,86 @@
 use crate::errors::EngineError;
-use crate::infrastructure::action::InfraLogger;
+use crate::events::InfrastructureStep;
+use crate::events::Stage::Infrastructure;
+use crate::infrastructure::action::cluster_pause_state::ClusterPauseState;
+use crate::infrastructure::action::deploy_terraform::TerraformInfraResources;
+use crate::infrastructure::action::kubectl_utils::check_workers_on_pause;
+use crate::infrastructure::action::{InfraLogger, ToInfraTeraContext};
 use crate::infrastructure::infrastructure_context::InfrastructureContext;
+use crate::infrastructure::models::kubernetes::Kubernetes;
 use crate::infrastructure::models::kubernetes::azure::aks::AKS;
+use crate::runtime::block_on;
+use crate::services::kube_client::SelectK8sResourceBy;
+use crate::utilities::envs_to_string;
+use std::collections::BTreeMap;
+
+const AKS_USER_NODES_SELECTOR: &str = "kubernetes.azure.com/mode=user";
+const AKS_NODE_POOL_LABEL: &str = "kubernetes.azure.com/agentpool";
 
 pub(super) fn pause_aks_cluster(
-    _cluster: &AKS,
-    _infra_ctx: &InfrastructureContext,
+    cluster: &AKS,
+    infra_ctx: &InfrastructureContext,
     logger: impl InfraLogger,
 ) -> Result<(), Box<EngineError>> {
-    logger.warn("Pausing a AKS cluster is not supported yet. Skipping this step.");
+    let event_details = cluster.get_event_details(Infrastructure(InfrastructureStep::Pause));
+    logger.info("Preparing cluster pause.");
+
+    // Record how many nodes each user node pool is running, so resume can restore them
+    let kube_client = infra_ctx.mk_kube_client()?;
+    let client = kube_client.client();
+    match block_on(ClusterPauseState::load(&client, &event_details))? {
+        // cluster has already been paused, nodes have already been scaled down so keep the counts recorded at that time
+        Some(_) => logger.info("Node pools counts have already been recorded by a previous pause."),
+        None => {
+            let nodes = block_on(kube_client.get_nodes(
+                event_details.clone(),
+                SelectK8sResourceBy::LabelsSelector(AKS_USER_NODES_SELECTOR.to_string()),
+            ))?;
+            let mut node_pools: BTreeMap<String, u32> = cluster
+                .node_groups
+                .get_additional_node_groups()
+                .iter()
+                .map(|node_group| (node_group.name.clone(), 0))
+                .collect();
+            for node_pool_name in nodes
+                .iter()
+                .filter_map(|node| node.metadata.labels.as_ref()?.get(AKS_NODE_POOL_LABEL))
+            {
+                *node_pools.entry(node_pool_name.to_string()).or_default() += 1;
+            }
+
+            let state = ClusterPauseState {
+                node_pools,
+                ..Default::default()
+            };
+            block_on(state.save(&client, &event_details))?;
+        }
+    }
+
+    // generate terraform files and copy them into temp dir
+    let mut tera_context = cluster.to_infra_tera_context(infra_ctx)?;
+
+    // pause: scale user node pools to zero to reduce the bill but keep the system one to keep all the deployment config, certificates etc...
+    // The system node pool is tainted and stops autoscaling at its minimum size, so the workloads don't move on it
+    tera_context.insert("node_groups_paused", &true);
+    let tf_resources = TerraformInfraResources::new(
+        tera_context,
+        cluster.template_directory.join("terraform"),
+        cluster.temp_dir().join("terraform"),
+        event_details.clone(),
+        envs_to_string(infra_ctx.cloud_provider().credentials_environment_variables()),
+        cluster.context().is_dry_run_deploy(),
+    );
+
+    logger.info("Pausing cluster deployment.");
+    tf_resources.pause(&[
+        "azurerm_kubernetes_cluster.primary",
+        "azurerm_kubernetes_cluster_node_pool.",
+    ])?;
 
-    // TODO(benjaminch): implement cluster pause
+    if let Err(e) = check_workers_on_pause(cluster, infra_ctx.cloud_provider(), Some(AKS_USER_NODES_SELECTOR)) {
+        return Err(Box::new(EngineError::new_k8s_node_not_ready(event_details, e)));
+    };
 
+    logger.info(format!("Kubernetes cluster {} successfully paused", cluster.name()));
     Ok(())
 }
@@ -11,8 +11,11 @@ use crate::infrastructure::action::gen_metrics_charts::{CloudProviderMetricsConf
 use crate::infrastructure::helm_charts::cert_manager_chart::CertManagerChart;
 use crate::infrastructure::helm_charts::cert_manager_config_chart::CertManagerConfigsChart;
 use crate::infrastructure::helm_charts::coredns_config_chart::CoreDNSConfigChart;
+use crate::infrastructure::helm_charts::envoy_gateway_chart::EnvoyGatewayChart;
+use crate::infrastructure::helm_charts::envoy_gateway_config_chart::EnvoyGatewayConfigsChart;
 use crate::infrastructure::helm_charts::external_dns_chart::ExternalDNSChart;
 use crate::infrastructure::helm_charts::k8s_event_logger::K8sEventLoggerChart;
+use crate::infrastructure::helm_charts::keda_chart::KedaChart;
 use crate::infrastructure::helm_charts::kube_prometheus_stack_chart::{
     KubePrometheusStackChart, PrometheusConfiguration,
 };
@@ -21,6 +24,7 @@ use crate::infrastructure::helm_charts::loki_chart::{
     BlobStorageLokiChartConfiguration, LokiChart, LokiObjectBucketConfiguration,
 };
 use crate::infrastructure::helm_charts::nginx_ingress_chart::{NginxIngressChart, NginxOptions};
+use crate::infrastructure::helm_charts::opencost_chart::OpenCostChart;
 use crate::infrastructure::helm_charts::promtail_chart::PromtailChart;
 use crate::infrastructure::helm_charts::qovery_cert_manager_webhook_chart::QoveryCertManagerWebhookChart;
 use crate::infrastructure::helm_charts::qovery_cluster_agent_chart::QoveryClusterAgentChart;
@@ -32,6 +36,7 @@ use crate::infrastructure::helm_charts::{
     HelmChartDirectoryLocation, HelmChartResources, HelmChartResourcesConstraintType, HelmChartTimeout,
     ToCommonHelmChart,
 };
+use crate::infrastructure::models::cloud_provider::io::IngressImplementation;
 use crate::infrastructure::models::cloud_provider::{Kind as CloudProviderKind, Kind};
 use crate::infrastructure::models::dns_provider::DnsProviderConfiguration;
 use crate::infrastructure::models::kubernetes::Kind as KubernetesKind;
@@ -84,6 +89,10 @@ pub(super) fn aks_helm_charts(
         chart_config_prerequisites.cluster_id.to_string(),
         UpdateStrategy::RollingUpdate,
         true,
+        chart_config_prerequisites
+            .cluster_advanced_settings
+            .ingress_implementation
+            .is_gateway_api(),
         HelmChartNamespaces::Qovery,
     )
     .to_common_helm_chart()?;
@@ -146,6 +155,10 @@ pub(super) fn aks_helm_charts(
         UpdateStrategy::RollingUpdate,
         get_chart_override_fn.clone(),
         true,
+        chart_config_prerequisites
+            .cluster_advanced_settings
+            .ingress_implementation
+            .is_gateway_api(),
         HelmChartNamespaces::Qovery,
         HelmChartNamespaces::Qovery,
     )
@@ -376,6 +389,23 @@ pub(super) fn aks_helm_charts(
         chart_config_prerequisites.cluster_long_id.to_string().as_str(),
     )?;
 
+    // OpenCost, workloads costs computed from the metrics stack
+    let opencost: Option<Box<dyn HelmChart>> = match (
+        chart_config_prerequisites.cluster_advanced_settings.opencost_enabled,
+        &metrics_config.metrics_query_url,
+    ) {
+        (true, Some(metrics_query_url)) => Some(Box::new(
+            OpenCostChart::new(
+                chart_prefix_path,
+                chart_config_prerequisites.cluster_long_id.to_string(),
+                metrics_query_url.to_string(),
+                get_chart_override_fn.clone(),
+            )
+            .to_common_helm_chart()?,
+        )),
+        _ => None,
+    };
+
     // Qovery cluster agent
     let qovery_cluster_agent = QoveryClusterAgentChart::new(
         chart_prefix_path,
@@ -407,6 +437,43 @@ pub(super) fn aks_helm_charts(
     )
     .to_common_helm_chart()?;
 
+    // KEDA, event driven autoscaling for services
+    let keda: Option<Box<dyn HelmChart>> = match chart_config_prerequisites.cluster_advanced_settings.keda_enabled {
+        false => None,
+        true => Some(Box::new(
+            KedaChart::new(
+                chart_prefix_path,
+                HelmChartNamespaces::Keda,
+                chart_config_prerequisites.metrics_parameters.is_some(),
+                get_chart_override_fn.clone(),
+            )
+            .to_common_helm_chart()?,
+        )),
+    };
+
+    // Envoy Gateway, routers are exposed through Gateway API instead of nginx ingress
+    let (envoy_gateway, envoy_gateway_configs): (Option<Box<dyn HelmChart>>, Option<Box<dyn HelmChart>>) =
+        match chart_config_prerequisites
+            .cluster_advanced_settings
+            .ingress_implementation
+        {
+            IngressImplementation::EnvoyGateway => (
+                Some(Box::new(
+                    EnvoyGatewayChart::new(
+                        chart_prefix_path,
+                        HelmChartNamespaces::EnvoyGateway,
+                        get_chart_override_fn.clone(),
+                    )
+                    .to_common_helm_chart()?,
+                )),
+                Some(Box::new(
+                    EnvoyGatewayConfigsChart::new(chart_prefix_path, HelmChartNamespaces::EnvoyGateway)
+                        .to_common_helm_chart()?,
+                )),
+            ),
+            IngressImplementation::Nginx | IngressImplementation::Cilium => (None, None),
+        };
+
     // Qovery shell agent
     let qovery_shell_agent = QoveryShellAgentChart::new(
         chart_prefix_path,
@@ -457,6 +524,9 @@ pub(super) fn aks_helm_charts(
     }
     let level_8: Vec<Option<Box<dyn HelmChart>>> = vec![
         Some(Box::new(external_dns_chart)), /*Some(Box::new(metrics_server))*/
+        keda,
+        opencost,
+        envoy_gateway,
     ];
     let level_9: Vec<Option<Box<dyn HelmChart>>> = vec![Some(Box::new(nginx_ingress))];
     let level_10: Vec<Option<Box<dyn HelmChart>>> = vec![
@@ -464,6 +534,7 @@ pub(super) fn aks_helm_charts(
         Some(Box::new(qovery_cluster_agent)),
         Some(Box::new(qovery_shell_agent)),
         Some(Box::new(cert_manager_config)),
+        envoy_gateway_configs,
     ];
 
     Ok(vec![
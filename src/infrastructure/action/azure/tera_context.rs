@@ -7,10 +7,19 @@ use crate::infrastructure::infrastructure_context::InfrastructureContext;
 use crate::infrastructure::models::kubernetes::Kubernetes;
 use crate::infrastructure::models::kubernetes::azure::SkuTier;
 use crate::infrastructure::models::kubernetes::azure::aks::AKS;
+use crate::infrastructure::models::kubernetes::maintenance_window::weekday_name;
 use crate::io_models::context::Features;
 use crate::string::terraform_list_format;
+use serde_derive::Serialize;
+use std::collections::BTreeMap;
 use tera::Context as TeraContext;
 
+#[derive(Serialize)]
+struct AksMaintenanceWindowAllowed {
+    day: &'static str,
+    hours: Vec<u32>,
+}
+
 impl ToInfraTeraContext for AKS {
     fn to_infra_tera_context(&self, infra_ctx: &InfrastructureContext) -> Result<TeraContext, Box<EngineError>> {
         aks_tera_context(self, infra_ctx)
@@ -23,6 +32,7 @@ fn aks_tera_context(cluster: &AKS, infra_ctx: &InfrastructureContext) -> Result<
     // Qovery
     context.insert("organization_id", infra_ctx.context().organization_short_id());
     context.insert("organization_long_id", &infra_ctx.context().organization_long_id().to_string());
+    context.insert("cost_allocation_tags", &cluster.cost_allocation_tags().to_tags());
     context.insert("object_storage_kubeconfig_bucket", &cluster.kubeconfig_bucket_name());
     context.insert("object_storage_logs_bucket", &cluster.logs_bucket_name());
     // Qovery features
@@ -65,9 +75,47 @@ fn aks_tera_context(cluster: &AKS, infra_ctx: &InfrastructureContext) -> Result<
     context.insert("azure_load_balancer_sku_tier", SkuTier::Standard.to_cloud_provider_format());
     context.insert("azure_nat_gateway_sku_tier_name", &SkuTier::Standard.to_string()); // Somehow, for this resource Azure needs Sku to start with a capital letter
 
+    // Maintenance window
+    let maintenance_window = cluster.advanced_settings().maintenance_window();
+    context.insert("maintenance_window_enabled", &maintenance_window.is_some());
+    if let Some(maintenance_window) = &maintenance_window {
+        let allowed = maintenance_window
+            .hours_by_day()
+            .into_iter()
+            .map(|(day, hours)| AksMaintenanceWindowAllowed {
+                day: weekday_name(day),
+                hours,
+            })
+            .collect::<Vec<_>>();
+        context.insert("maintenance_window_allowed", &allowed);
+        // AKS node OS schedules only support a single day per week or every day, other windows are rejected (see AKS::new)
+        context.insert(
+            "maintenance_window_node_os_frequency",
+            if maintenance_window.is_every_day() {
+                "Daily"
+            } else {
+                "Weekly"
+            },
+        );
+        context.insert(
+            "maintenance_window_node_os_day_of_week",
+            weekday_name(maintenance_window.days()[0]),
+        );
+        context.insert(
+            "maintenance_window_start_time",
+            &maintenance_window.start_time().format("%H:%M").to_string(),
+        );
+        context.insert(
+            "maintenance_window_duration_in_hours",
+            &maintenance_window.duration().num_hours(),
+        );
+    }
+
     // Node groups
     context.insert("node_group_default", &cluster.node_groups.get_default_node_group());
     context.insert("node_groups_additional", &cluster.node_groups.get_additional_node_groups());
+    context.insert("node_groups_paused", &false);
+    context.insert("node_groups_restored_nodes", &BTreeMap::<String, u32>::new());
 
     // Credentials
     context.insert("azure_client_id", cluster.credentials.client_id.as_str());
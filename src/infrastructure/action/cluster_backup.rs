@@ -0,0 +1,401 @@
+use crate::errors::{CommandError, EngineError};
+use crate::events::EventDetails;
+use crate::events::InfrastructureStep;
+use crate::events::Stage::Infrastructure;
+use crate::helm::HelmChartNamespaces;
+use crate::infrastructure::action::InfraLogger;
+use crate::infrastructure::helm_charts::velero_chart::VELERO_EXCLUDED_NAMESPACES;
+use crate::infrastructure::infrastructure_context::InfrastructureContext;
+use crate::infrastructure::models::kubernetes::{Kind, Kubernetes, backups_bucket_name};
+use crate::io_models::engine_request::ClusterBackupRestore;
+use crate::runtime::block_on;
+use crate::utilities::to_short_id;
+use kube::Api;
+use kube::api::{ApiResource, DynamicObject, GroupVersionKind, PostParams};
+use serde_json::{Value, json};
+use std::time::Duration;
+
+const RESTORE_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+// velero synchronizes backups of a storage location every minute
+const BACKUP_SYNC_TIMEOUT: Duration = Duration::from_secs(60 * 5);
+const POLLING_INTERVAL: Duration = Duration::from_secs(10);
+
+fn velero_api(client: &kube::Client, kind: &str) -> Api<DynamicObject> {
+    Api::namespaced_with(
+        client.clone(),
+        &HelmChartNamespaces::Velero.to_string(),
+        &ApiResource::from_gvk(&GroupVersionKind::gvk("velero.io", "v1", kind)),
+    )
+}
+
+/// Rejects clusters with backups enabled when velero is not installed on their kind.
+/// Only EKS and Kapsule clusters are supported for now.
+pub(super) fn check_cluster_backup_support(
+    kubernetes: &dyn Kubernetes,
+    event_details: &EventDetails,
+) -> Result<(), Box<EngineError>> {
+    if !kubernetes.advanced_settings().cluster_backup_enabled
+        || matches!(kubernetes.kind(), Kind::Eks | Kind::ScwKapsule)
+    {
+        return Ok(());
+    }
+
+    Err(Box::new(EngineError::new_unsupported_cluster_kind(
+        event_details.clone(),
+        &kubernetes.kind().to_string(),
+        CommandError::new_from_safe_message(format!(
+            "Cluster backups are not supported on {} clusters, please disable them in the cluster advanced settings",
+            kubernetes.kind()
+        )),
+    )))
+}
+
+/// Creates the bucket backups are stored in, when cluster backups are enabled.
+pub(super) fn create_backups_bucket(
+    kubernetes: &dyn Kubernetes,
+    event_details: &EventDetails,
+    logger: &impl InfraLogger,
+) -> Result<(), Box<EngineError>> {
+    if !kubernetes.advanced_settings().cluster_backup_enabled {
+        return Ok(());
+    }
+    check_cluster_backup_support(kubernetes, event_details)?;
+    let Some(object_storage) = kubernetes.object_storage() else {
+        return Ok(());
+    };
+
+    let bucket_name = kubernetes.backups_bucket_name();
+    if object_storage.bucket_exists(&bucket_name) {
+        return Ok(());
+    }
+
+    logger.info(format!("Creating cluster backups bucket `{bucket_name}`."));
+    object_storage
+        .create_bucket(
+            &bucket_name,
+            kubernetes.advanced_settings().resource_ttl(),
+            false,
+            kubernetes.advanced_settings().object_storage_enable_logging,
+        )
+        .map_err(|e| Box::new(EngineError::new_object_storage_error(event_details.clone(), e)))?;
+
+    Ok(())
+}
+
+/// Restores environments namespaces of a backup, including their volumes.
+/// When the backup has been made by another cluster, its bucket is added as a read only location of this cluster.
+pub(super) fn restore_cluster_backup(
+    kubernetes: &dyn Kubernetes,
+    infra_ctx: &InfrastructureContext,
+    restore: &ClusterBackupRestore,
+    logger: &impl InfraLogger,
+) -> Result<(), Box<EngineError>> {
+    let event_details = kubernetes.get_event_details(Infrastructure(InfrastructureStep::Restore));
+    let mk_error = |error: CommandError| {
+        Box::new(EngineError::new_cluster_backup_restore_error(
+            event_details.clone(),
+            restore.backup_name.to_string(),
+            error,
+        ))
+    };
+
+    if !kubernetes.advanced_settings().cluster_backup_enabled {
+        return Err(mk_error(CommandError::new_from_safe_message(
+            "Cluster backups are not enabled on this cluster".to_string(),
+        )));
+    }
+    check_cluster_backup_support(kubernetes, &event_details)?;
+    validate_namespaces_to_restore(restore).map_err(mk_error)?;
+
+    let client = infra_ctx.mk_kube_client()?.client();
+    let mut restore_volumes = true;
+    let storage_location = match restore.source_cluster_long_id {
+        Some(source_cluster_id) if &source_cluster_id != kubernetes.long_id() => {
+            let source_cluster_short_id = to_short_id(&source_cluster_id);
+            let Some(source_cluster_region) = restore.source_cluster_region.as_deref() else {
+                return Err(mk_error(CommandError::new_from_safe_message(format!(
+                    "Region of cluster `{source_cluster_short_id}` is required to restore one of its backups"
+                ))));
+            };
+            // EBS snapshots can't be restored outside of the region they have been taken in
+            if kubernetes.kind() == Kind::Eks && source_cluster_region != kubernetes.region() {
+                logger.warn(format!(
+                    "Backup `{}` has been made in region `{source_cluster_region}`, its volumes snapshots can't be restored in region `{}`. Volumes will be restored empty.",
+                    restore.backup_name,
+                    kubernetes.region()
+                ));
+                restore_volumes = false;
+            }
+            logger.info(format!(
+                "Adding backups of cluster `{source_cluster_short_id}` as a read only backup location."
+            ));
+            block_on(add_source_cluster_storage_location(
+                &client,
+                &source_cluster_short_id,
+                source_cluster_region,
+            ))
+            .map_err(mk_error)?
+        }
+        _ => "default".to_string(),
+    };
+
+    if infra_ctx.context().is_dry_run_deploy() {
+        logger.warn(format!("👻 Dry run mode, backup `{}` won't be restored.", restore.backup_name));
+        return Ok(());
+    }
+
+    logger.info(format!(
+        "Waiting for backup `{}` to be available from `{storage_location}` backup location.",
+        restore.backup_name
+    ));
+    block_on(wait_for_backup(&client, &restore.backup_name)).map_err(mk_error)?;
+
+    logger.info(format!("Restoring backup `{}`.", restore.backup_name));
+    let restore_name = block_on(create_restore(&client, restore, restore_volumes)).map_err(mk_error)?;
+    let warnings = block_on(wait_for_restore(&client, &restore_name)).map_err(mk_error)?;
+    if warnings > 0 {
+        logger.warn(format!(
+            "Backup `{}` has been partially restored, {warnings} resource(s) could not be restored.",
+            restore.backup_name
+        ));
+    } else {
+        logger.info(format!("Backup `{}` has been successfully restored.", restore.backup_name));
+    }
+
+    Ok(())
+}
+
+/// Only environments namespaces can be restored, they must be listed explicitly so a restore never touches
+/// Kubernetes or Qovery components namespaces
+fn validate_namespaces_to_restore(restore: &ClusterBackupRestore) -> Result<(), CommandError> {
+    if restore.namespaces.is_empty() {
+        return Err(CommandError::new_from_safe_message(
+            "Namespaces to restore must be listed explicitly".to_string(),
+        ));
+    }
+
+    let forbidden_namespaces: Vec<&str> = restore
+        .namespaces
+        .iter()
+        .chain(restore.namespace_mapping.values())
+        .map(String::as_str)
+        .filter(|namespace| *namespace == "*" || VELERO_EXCLUDED_NAMESPACES.contains(namespace))
+        .collect();
+    if !forbidden_namespaces.is_empty() {
+        return Err(CommandError::new_from_safe_message(format!(
+            "Only environments namespaces can be restored, `{}` can't be",
+            forbidden_namespaces.join("`, `")
+        )));
+    }
+
+    Ok(())
+}
+
+/// Spec of a read only location on the backups bucket of another cluster. The bucket lives in the same object
+/// storage as this cluster one, so the default location spec is reused, but it may be in another region.
+fn source_cluster_location_spec(
+    default_location_spec: &Value,
+    source_cluster_short_id: &str,
+    source_cluster_region: &str,
+) -> Value {
+    let mut spec = default_location_spec.clone();
+    spec["objectStorage"]["bucket"] = json!(backups_bucket_name(source_cluster_short_id));
+    spec["accessMode"] = json!("ReadOnly");
+    spec["default"] = json!(false);
+
+    // S3 compatible endpoints are regional, i.e: https://s3.fr-par.scw.cloud
+    if let (Some(region), Some(s3_url)) = (
+        default_location_spec["config"]["region"].as_str(),
+        default_location_spec["config"]["s3Url"].as_str(),
+    ) {
+        spec["config"]["s3Url"] = json!(s3_url.replace(&format!(".{region}."), &format!(".{source_cluster_region}.")));
+    }
+    spec["config"]["region"] = json!(source_cluster_region);
+
+    spec
+}
+
+/// Adds the backups bucket of another cluster as a read only location, and returns the location name
+async fn add_source_cluster_storage_location(
+    client: &kube::Client,
+    source_cluster_short_id: &str,
+    source_cluster_region: &str,
+) -> Result<String, CommandError> {
+    let locations = velero_api(client, "BackupStorageLocation");
+    let location_name = format!("qovery-{source_cluster_short_id}");
+    if locations
+        .get_opt(&location_name)
+        .await
+        .map_err(|e| CommandError::new("Cannot get backup location".to_string(), Some(e.to_string()), None))?
+        .is_some()
+    {
+        return Ok(location_name);
+    }
+
+    let default_location = locations
+        .get("default")
+        .await
+        .map_err(|e| CommandError::new("Cannot get default backup location".to_string(), Some(e.to_string()), None))?;
+    let spec =
+        source_cluster_location_spec(&default_location.data["spec"], source_cluster_short_id, source_cluster_region);
+
+    let mut location = DynamicObject::new(
+        &location_name,
+        &ApiResource::from_gvk(&GroupVersionKind::gvk("velero.io", "v1", "BackupStorageLocation")),
+    );
+    location.data = json!({ "spec": spec });
+    locations
+        .create(&PostParams::default(), &location)
+        .await
+        .map_err(|e| CommandError::new("Cannot create backup location".to_string(), Some(e.to_string()), None))?;
+
+    Ok(location_name)
+}
+
+async fn wait_for_backup(client: &kube::Client, backup_name: &str) -> Result<(), CommandError> {
+    let backups = velero_api(client, "Backup");
+    let deadline = tokio::time::Instant::now() + BACKUP_SYNC_TIMEOUT;
+    loop {
+        let backup = backups
+            .get_opt(backup_name)
+            .await
+            .map_err(|e| CommandError::new("Cannot get backup".to_string(), Some(e.to_string()), None))?;
+        match backup.as_ref().and_then(|it| it.data["status"]["phase"].as_str()) {
+            Some("Completed") | Some("PartiallyFailed") => return Ok(()),
+            Some(phase @ ("Failed" | "FailedValidation")) => {
+                return Err(CommandError::new_from_safe_message(format!(
+                    "Backup `{backup_name}` cannot be restored, its phase is `{phase}`"
+                )));
+            }
+            _ if tokio::time::Instant::now() > deadline => {
+                return Err(CommandError::new_from_safe_message(format!(
+                    "Backup `{backup_name}` is not available after {BACKUP_SYNC_TIMEOUT:?}"
+                )));
+            }
+            _ => tokio::time::sleep(POLLING_INTERVAL).await,
+        }
+    }
+}
+
+async fn create_restore(
+    client: &kube::Client,
+    restore: &ClusterBackupRestore,
+    restore_volumes: bool,
+) -> Result<String, CommandError> {
+    let restore_name = format!("{}-{}", restore.backup_name, chrono::Utc::now().format("%Y%m%d%H%M%S"));
+    let mut restore_object = DynamicObject::new(
+        &restore_name,
+        &ApiResource::from_gvk(&GroupVersionKind::gvk("velero.io", "v1", "Restore")),
+    );
+    restore_object.data = json!({
+        "spec": {
+            "backupName": restore.backup_name,
+            "includedNamespaces": restore.namespaces,
+            "namespaceMapping": restore.namespace_mapping,
+            "restorePVs": restore_volumes,
+            // resources already present in the cluster are left untouched
+            "existingResourcePolicy": "none",
+        }
+    });
+
+    velero_api(client, "Restore")
+        .create(&PostParams::default(), &restore_object)
+        .await
+        .map_err(|e| CommandError::new("Cannot create restore".to_string(), Some(e.to_string()), None))?;
+
+    Ok(restore_name)
+}
+
+/// Waits for the restore to end, and returns the number of resources which failed to be restored
+async fn wait_for_restore(client: &kube::Client, restore_name: &str) -> Result<u64, CommandError> {
+    let restores = velero_api(client, "Restore");
+    let deadline = tokio::time::Instant::now() + RESTORE_TIMEOUT;
+    loop {
+        let restore = restores
+            .get(restore_name)
+            .await
+            .map_err(|e| CommandError::new("Cannot get restore".to_string(), Some(e.to_string()), None))?;
+        let status = &restore.data["status"];
+        match status["phase"].as_str() {
+            Some("Completed") => return Ok(0),
+            Some("PartiallyFailed") => return Ok(status["errors"].as_u64().unwrap_or_default()),
+            Some(phase @ ("Failed" | "FailedValidation")) => {
+                return Err(CommandError::new(
+                    format!("Restore `{restore_name}` ended with phase `{phase}`"),
+                    Some(status.to_string()),
+                    None,
+                ));
+            }
+            _ if tokio::time::Instant::now() > deadline => {
+                return Err(CommandError::new_from_safe_message(format!(
+                    "Restore `{restore_name}` is not done after {RESTORE_TIMEOUT:?}"
+                )));
+            }
+            _ => tokio::time::sleep(POLLING_INTERVAL).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn restore(namespaces: &[&str], namespace_mapping: &[(&str, &str)]) -> ClusterBackupRestore {
+        ClusterBackupRestore {
+            backup_name: "qovery-cluster-backup-20260101030000".to_string(),
+            source_cluster_long_id: None,
+            source_cluster_region: None,
+            namespaces: namespaces.iter().map(|it| it.to_string()).collect(),
+            namespace_mapping: namespace_mapping
+                .iter()
+                .map(|(from, to)| (from.to_string(), to.to_string()))
+                .collect::<HashMap<_, _>>(),
+        }
+    }
+
+    #[test]
+    fn test_validate_namespaces_to_restore() {
+        // execute & verify:
+        assert!(validate_namespaces_to_restore(&restore(&["z1234-z5678"], &[])).is_ok());
+        assert!(
+            validate_namespaces_to_restore(&restore(&["z1234-z5678"], &[("z1234-z5678", "z1234-restored")])).is_ok()
+        );
+        assert!(validate_namespaces_to_restore(&restore(&[], &[])).is_err());
+        assert!(validate_namespaces_to_restore(&restore(&["*"], &[])).is_err());
+        assert!(validate_namespaces_to_restore(&restore(&["z1234-z5678", "kube-system"], &[])).is_err());
+        assert!(validate_namespaces_to_restore(&restore(&["qovery"], &[])).is_err());
+        assert!(validate_namespaces_to_restore(&restore(&["z1234-z5678"], &[("z1234-z5678", "qovery")])).is_err());
+    }
+
+    #[test]
+    fn test_source_cluster_location_spec_uses_source_cluster_region() {
+        // setup:
+        let aws_spec = json!({
+            "provider": "aws",
+            "default": true,
+            "objectStorage": { "bucket": "qovery-backups-zcurrent" },
+            "config": { "region": "us-east-2" },
+        });
+        let scaleway_spec = json!({
+            "provider": "aws",
+            "default": true,
+            "objectStorage": { "bucket": "qovery-backups-zcurrent" },
+            "config": { "region": "fr-par", "s3Url": "https://s3.fr-par.scw.cloud", "s3ForcePathStyle": "true" },
+        });
+
+        // execute:
+        let aws_location = source_cluster_location_spec(&aws_spec, "zsource", "eu-west-3");
+        let scaleway_location = source_cluster_location_spec(&scaleway_spec, "zsource", "nl-ams");
+
+        // verify:
+        assert_eq!(aws_location["objectStorage"]["bucket"], json!(backups_bucket_name("zsource")));
+        assert_eq!(aws_location["accessMode"], json!("ReadOnly"));
+        assert_eq!(aws_location["default"], json!(false));
+        assert_eq!(aws_location["config"]["region"], json!("eu-west-3"));
+        assert!(aws_location["config"]["s3Url"].is_null());
+        assert_eq!(scaleway_location["config"]["region"], json!("nl-ams"));
+        assert_eq!(scaleway_location["config"]["s3Url"], json!("https://s3.nl-ams.scw.cloud"));
+        assert_eq!(scaleway_location["config"]["s3ForcePathStyle"], json!("true"));
+    }
+}
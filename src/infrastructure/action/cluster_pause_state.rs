@@ -0,0 +1,183 @@
+use crate::errors::{CommandError, EngineError};
+use crate::events::EventDetails;
+use crate::helm::HelmChartNamespaces;
+use k8s_openapi::api::core::v1::ConfigMap;
+use kube::Api;
+use kube::api::{DeleteParams, Patch, PatchParams};
+use serde_derive::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet};
+
+const CLUSTER_PAUSE_STATE_CONFIG_MAP_NAME: &str = "qovery-cluster-pause-state";
+const CLUSTER_PAUSE_STATE_CONFIG_MAP_KEY: &str = "state";
+
+/// What was running on the cluster when it has been paused, so resuming it can restore it as it was.
+/// It is stored in a config map of the `kube-system` namespace, that stays available while workers are paused.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClusterPauseState {
+    /// Number of nodes per node pool name
+    #[serde(default)]
+    pub node_pools: BTreeMap<String, u32>,
+    /// Number of replicas per workload, see `workload_key`
+    #[serde(default)]
+    pub workloads: BTreeMap<String, u32>,
+    /// KEDA autoscalers paused along their workloads, see `workload_key`
+    #[serde(default)]
+    pub autoscalers: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PausedWorkloadKind {
+    Deployment,
+    Statefulset,
+    ScaledObject,
+    ScaledJob,
+}
+
+impl PausedWorkloadKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            PausedWorkloadKind::Deployment => "deployment",
+            PausedWorkloadKind::Statefulset => "statefulset",
+            PausedWorkloadKind::ScaledObject => "scaledobject",
+            PausedWorkloadKind::ScaledJob => "scaledjob",
+        }
+    }
+}
+
+pub fn workload_key(kind: PausedWorkloadKind, namespace: &str, name: &str) -> String {
+    format!("{}/{}/{}", kind.as_str(), namespace, name)
+}
+
+pub fn parse_workload_key(key: &str) -> Option<(PausedWorkloadKind, &str, &str)> {
+    let mut parts = key.splitn(3, '/');
+    let kind = match parts.next()? {
+        "deployment" => PausedWorkloadKind::Deployment,
+        "statefulset" => PausedWorkloadKind::Statefulset,
+        "scaledobject" => PausedWorkloadKind::ScaledObject,
+        "scaledjob" => PausedWorkloadKind::ScaledJob,
+        _ => return None,
+    };
+
+    Some((kind, parts.next()?, parts.next()?))
+}
+
+impl ClusterPauseState {
+    /// Node count to restore for a node pool, kept within the pool bounds
+    pub fn node_pool_count(&self, node_pool_name: &str, min_nodes: u32, max_nodes: u32) -> Option<u32> {
+        self.node_pools
+            .get(node_pool_name)
+            .map(|count| (*count).clamp(min_nodes, max_nodes.max(min_nodes)))
+    }
+
+    pub async fn load(client: &kube::Client, event_details: &EventDetails) -> Result<Option<Self>, Box<EngineError>> {
+        let api: Api<ConfigMap> = Api::namespaced(client.clone(), HelmChartNamespaces::KubeSystem.to_string().as_str());
+
+        let config_map = api
+            .get_opt(CLUSTER_PAUSE_STATE_CONFIG_MAP_NAME)
+            .await
+            .map_err(|e| Self::error(event_details, "read", e.to_string()))?;
+
+        match config_map
+            .and_then(|config_map| config_map.data)
+            .and_then(|mut data| data.remove(CLUSTER_PAUSE_STATE_CONFIG_MAP_KEY))
+        {
+            Some(state) => serde_json::from_str(&state)
+                .map(Some)
+                .map_err(|e| Self::error(event_details, "read", e.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn save(&self, client: &kube::Client, event_details: &EventDetails) -> Result<(), Box<EngineError>> {
+        let namespace = HelmChartNamespaces::KubeSystem.to_string();
+        let api: Api<ConfigMap> = Api::namespaced(client.clone(), namespace.as_str());
+
+        let state = serde_json::to_string(self).map_err(|e| Self::error(event_details, "write", e.to_string()))?;
+        let config_map = serde_json::from_value::<ConfigMap>(json!({
+            "apiVersion": "v1",
+            "kind": "ConfigMap",
+            "metadata": {
+                "name": CLUSTER_PAUSE_STATE_CONFIG_MAP_NAME,
+                "namespace": namespace,
+            },
+            "data": {
+                CLUSTER_PAUSE_STATE_CONFIG_MAP_KEY: state,
+            },
+        }))
+        .map_err(|e| Self::error(event_details, "write", e.to_string()))?;
+
+        api.patch(
+            CLUSTER_PAUSE_STATE_CONFIG_MAP_NAME,
+            &PatchParams::apply("qovery").force(),
+            &Patch::Apply(config_map),
+        )
+        .await
+        .map_err(|e| Self::error(event_details, "write", e.to_string()))?;
+
+        Ok(())
+    }
+
+    pub async fn clear(client: &kube::Client, event_details: &EventDetails) -> Result<(), Box<EngineError>> {
+        let api: Api<ConfigMap> = Api::namespaced(client.clone(), HelmChartNamespaces::KubeSystem.to_string().as_str());
+
+        match api
+            .delete(CLUSTER_PAUSE_STATE_CONFIG_MAP_NAME, &DeleteParams::default())
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(kube::Error::Api(e)) if e.code == 404 => Ok(()),
+            Err(e) => Err(Self::error(event_details, "delete", e.to_string())),
+        }
+    }
+
+    fn error(event_details: &EventDetails, operation: &str, raw_error: String) -> Box<EngineError> {
+        Box::new(EngineError::new_cannot_manage_cluster_pause_state(
+            event_details.clone(),
+            CommandError::new(
+                format!("Cannot {operation} config map `{CLUSTER_PAUSE_STATE_CONFIG_MAP_NAME}`"),
+                Some(raw_error),
+                None,
+            ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workload_key() {
+        let key = workload_key(PausedWorkloadKind::Statefulset, "z1234-my-env", "app-z5678");
+        assert_eq!(key, "statefulset/z1234-my-env/app-z5678");
+        assert_eq!(
+            parse_workload_key(&key),
+            Some((PausedWorkloadKind::Statefulset, "z1234-my-env", "app-z5678"))
+        );
+        assert_eq!(
+            parse_workload_key("scaledjob/z1234-my-env/job-z5678"),
+            Some((PausedWorkloadKind::ScaledJob, "z1234-my-env", "job-z5678"))
+        );
+        assert_eq!(parse_workload_key("cronjob/ns/name"), None);
+        assert_eq!(parse_workload_key("deployment/ns"), None);
+    }
+
+    #[test]
+    fn test_node_pool_count_is_kept_within_bounds() {
+        let state = ClusterPauseState {
+            node_pools: BTreeMap::from([
+                ("small".to_string(), 1),
+                ("medium".to_string(), 4),
+                ("large".to_string(), 12),
+            ]),
+            workloads: BTreeMap::new(),
+            autoscalers: BTreeSet::new(),
+        };
+
+        assert_eq!(state.node_pool_count("small", 2, 5), Some(2));
+        assert_eq!(state.node_pool_count("medium", 2, 5), Some(4));
+        assert_eq!(state.node_pool_count("large", 2, 5), Some(5));
+        assert_eq!(state.node_pool_count("unknown", 2, 5), None);
+    }
+}
@@ -14,6 +14,7 @@ use crate::runtime::block_on;
 use crate::services::kube_client::SelectK8sResourceBy;
 use k8s_openapi::api::core::v1::Namespace;
 use k8s_openapi::api::policy::v1::PodDisruptionBudget;
+use k8s_openapi::apiextensions_apiserver::pkg::apis::apiextensions::v1::CustomResourceDefinition;
 use kube::Api;
 use kube::api::{DeleteParams, ListParams};
 use std::collections::HashSet;
@@ -21,6 +22,21 @@ use std::time::Duration;
 
 const DELETE_TIMEOUT: Duration = Duration::from_secs(60 * 10);
 
+// Every namespace created by Qovery for an environment carries this label
+const QOVERY_ENVIRONMENT_NAMESPACES_SELECTOR: &str = "qovery.com/environment-id";
+
+/// What has to be removed from the cluster when it is deleted
+pub(super) enum KubeAppsDeletionScope {
+    /// The cluster is managed by Qovery, everything deployed on it is removed
+    AllApps,
+    /// The cluster is owned by the user, only Qovery environments and the given releases installed by Qovery are
+    /// removed, along with the given CRDs installed by Qovery charts that helm doesn't uninstall
+    QoveryStackOnly {
+        releases: Vec<ChartInfo>,
+        crds: &'static [&'static str],
+    },
+}
+
 fn delete_namespace(
     ns_to_delete: &str,
     ns_api: Api<Namespace>,
@@ -47,6 +63,16 @@ fn delete_namespace(
     Ok(())
 }
 
+fn delete_crds(crds: &[&str], crd_api: Api<CustomResourceDefinition>, logger: &impl InfraLogger) {
+    for crd in crds {
+        match block_on(crd_api.delete(crd, &DeleteParams::default())) {
+            Ok(_) => logger.info(format!("Deleted successfully CRD `{crd}`")),
+            Err(kube::Error::Api(err)) if err.code == 404 => {}
+            Err(err) => logger.warn(format!("Can't delete the CRD `{crd}`: {err:?}")),
+        }
+    }
+}
+
 pub(super) fn delete_all_pdbs(
     infra_ctx: &InfrastructureContext,
     event_details: EventDetails,
@@ -99,6 +125,7 @@ pub(super) fn delete_kube_apps(
     event_details: EventDetails,
     logger: &impl InfraLogger,
     skip_helm_releases: HashSet<String>,
+    scope: KubeAppsDeletionScope,
 ) -> Result<(), Box<EngineError>> {
     // should make the diff between all namespaces and qovery managed namespaces
     let message = format!(
@@ -110,7 +137,13 @@ pub(super) fn delete_kube_apps(
 
     let kube = infra_ctx.mk_kube_client()?;
     let ns_api: Api<Namespace> = Api::all(kube.client());
-    let all_namespaces = block_on(ns_api.list_metadata(&Default::default())).map(|ns| {
+    let list_params = match scope {
+        KubeAppsDeletionScope::AllApps => ListParams::default(),
+        KubeAppsDeletionScope::QoveryStackOnly { .. } => {
+            ListParams::default().labels(QOVERY_ENVIRONMENT_NAMESPACES_SELECTOR)
+        }
+    };
+    let all_namespaces = block_on(ns_api.list_metadata(&list_params)).map(|ns| {
         ns.items
             .into_iter()
             .map(|ns| ns.metadata.name.unwrap_or_default())
@@ -119,12 +152,17 @@ pub(super) fn delete_kube_apps(
 
     // Delete cert-manager objects first
     // required to avoid namespace stuck on deletion
-    if let Err(e) = uninstall_cert_manager(
-        cluster.kubeconfig_local_file_path(),
-        infra_ctx.cloud_provider().credentials_environment_variables(),
-        event_details.clone(),
-        cluster.logger(),
-    ) {
+    // on user owned clusters, cert-manager is not ours and its objects are left untouched
+    let cert_manager_uninstall = match scope {
+        KubeAppsDeletionScope::AllApps => uninstall_cert_manager(
+            cluster.kubeconfig_local_file_path(),
+            infra_ctx.cloud_provider().credentials_environment_variables(),
+            event_details.clone(),
+            cluster.logger(),
+        ),
+        KubeAppsDeletionScope::QoveryStackOnly { .. } => Ok(()),
+    };
+    if let Err(e) = cert_manager_uninstall {
         // this error is not blocking, logging a warning and move on
         logger.warn(EventMessage::new(
             "An error occurred while trying to uninstall cert-manager. This is not blocking.".to_string(),
@@ -163,18 +201,46 @@ pub(super) fn delete_kube_apps(
     )
     .map_err(|e| to_engine_error(&event_details, e))?;
     let chart = ChartInfo::new_from_release_name(&MetricsServerChart::chart_name(), "kube-system");
-
-    if let Err(e) = helm.uninstall(
-        &chart,
-        &[],
-        &CommandKiller::from_timeout(DELETE_TIMEOUT),
-        &mut |_| {},
-        &mut |_| {},
-    ) {
+    let metrics_server_uninstall = match scope {
+        KubeAppsDeletionScope::AllApps => helm.uninstall(
+            &chart,
+            &[],
+            &CommandKiller::from_timeout(DELETE_TIMEOUT),
+            &mut |_| {},
+            &mut |_| {},
+        ),
+        KubeAppsDeletionScope::QoveryStackOnly { .. } => Ok(()),
+    };
+    if let Err(e) = metrics_server_uninstall {
         // this error is not blocking
         logger.warn(EventMessage::from(to_engine_error(&event_details, e)));
     }
 
+    if let KubeAppsDeletionScope::QoveryStackOnly { releases, crds } = scope {
+        // other releases of the `qovery` namespace belong to the user, and the engine itself may run there,
+        // so only Qovery releases are uninstalled and the namespace is kept
+        logger.info("Deleting Qovery installed releases");
+        for release in releases {
+            match helm.uninstall(
+                &release,
+                &[],
+                &CommandKiller::from_timeout(DELETE_TIMEOUT),
+                &mut |_| {},
+                &mut |_| {},
+            ) {
+                Ok(_) => logger.info(format!("Chart `{}` deleted", release.name)),
+                Err(e) => {
+                    let message_safe = format!("Can't delete chart `{}`", release.name);
+                    logger.warn(EventMessage::new(message_safe, Some(e.to_string())));
+                }
+            }
+        }
+
+        logger.info("Deleting Qovery installed CRDs");
+        delete_crds(crds, Api::all(kube.client()), logger);
+        return Ok(());
+    }
+
     logger.info("Deleting Qovery managed elements");
     let qovery_namespaces = get_qovery_managed_namespaces();
     for qovery_namespace in qovery_namespaces.iter() {
@@ -3,7 +3,7 @@ use crate::cmd::command::CommandKiller;
 use crate::cmd::helm::Helm;
 use crate::errors::{CommandError, EngineError};
 use crate::events::{EventDetails, InfrastructureDiffType};
-use crate::helm::{HelmAction, HelmChart, HelmChartError};
+use crate::helm::{ChartInfo, HelmAction, HelmChart, HelmChartError};
 use crate::infrastructure::infrastructure_context::InfrastructureContext;
 use crate::io_models::engine_request::{ChartValuesOverrideName, ChartValuesOverrideValues};
 use crate::io_models::models::CustomerHelmChartsOverride;
@@ -111,6 +111,28 @@ pub(super) trait HelmInfraResources {
 
         Ok(())
     }
+
+    /// Releases deployed by `deploy_charts`, in the order they should be uninstalled
+    fn deployed_releases(&self, infra_ctx: &InfrastructureContext) -> Result<Vec<ChartInfo>, Box<EngineError>> {
+        self.charts_context().prepare_helm_files_on_disk()?;
+        let chart_configs = self.new_chart_prerequisite(infra_ctx);
+        let charts = self.gen_charts_to_deploy(infra_ctx, chart_configs)?;
+
+        Ok(releases_in_uninstall_order(&charts))
+    }
+}
+
+/// Charts of the last levels depend on the first ones, so they are uninstalled first.
+/// Charts only there to be destroyed are left aside, they are not installed anymore
+fn releases_in_uninstall_order(charts: &[Vec<Box<dyn HelmChart>>]) -> Vec<ChartInfo> {
+    charts
+        .iter()
+        .rev()
+        .flatten()
+        .map(|chart| chart.get_chart_info())
+        .filter(|chart_info| chart_info.action == HelmAction::Deploy)
+        .cloned()
+        .collect()
 }
 
 fn charts_names_user_str(charts: &[Box<dyn HelmChart>]) -> String {
@@ -300,3 +322,46 @@ fn create_helm_diff_file(dir_path: &Path, chart_name: &str) -> anyhow::Result<Bu
 
     Ok(BufWriter::new(file))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::releases_in_uninstall_order;
+    use crate::helm::{ChartInfo, CommonChart, HelmAction, HelmChart};
+
+    fn chart(name: &str, action: HelmAction) -> Box<dyn HelmChart> {
+        Box::new(CommonChart::new(
+            ChartInfo {
+                name: name.to_string(),
+                action,
+                ..Default::default()
+            },
+            None,
+            None,
+        ))
+    }
+
+    #[test]
+    fn test_releases_in_uninstall_order() {
+        // setup:
+        let charts = vec![
+            vec![chart("qovery-priority-class", HelmAction::Deploy)],
+            vec![
+                chart("cert-manager", HelmAction::Deploy),
+                chart("loki", HelmAction::Destroy),
+            ],
+            vec![
+                chart("cluster-agent", HelmAction::Deploy),
+                chart("shell-agent", HelmAction::Deploy),
+            ],
+        ];
+
+        // execute:
+        let releases = releases_in_uninstall_order(&charts);
+
+        // verify:
+        assert_eq!(
+            vec!["cluster-agent", "shell-agent", "cert-manager", "qovery-priority-class"],
+            releases.iter().map(|release| release.name.as_str()).collect::<Vec<_>>()
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use crate::cmd::terraform::{
     terraform_apply, terraform_apply_with_specific_resources, terraform_destroy, terraform_init_validate,
     terraform_output, terraform_plan, terraform_remove_resource_from_tf_state, terraform_state_list,
+    terraform_state_mv,
 };
 use crate::cmd::terraform_validators::TerraformValidators;
 use crate::errors::EngineError;
@@ -172,6 +173,38 @@ impl TerraformInfraResources {
         Ok(())
     }
 
+    /// Moves resources to new addresses in the terraform state, in the given order, without changing them
+    pub fn move_resources_in_state(
+        &self,
+        moves: &[(String, String)],
+        logger: &impl InfraLogger,
+    ) -> Result<(), Box<EngineError>> {
+        let envs = envs_to_slice(self.envs.as_slice());
+        self.prepare_terraform_files()?;
+        self.terraform_init(&envs)?;
+
+        for (source, destination) in moves {
+            if self.is_dry_run {
+                logger.warn(format!(
+                    "👻 Skipping move of resource {source} to {destination} in terraform state"
+                ));
+                continue;
+            }
+
+            logger.info(format!("Moving resource {source} to {destination} in terraform state"));
+            terraform_state_mv(
+                self.destination_folder.to_string_lossy().as_ref(),
+                source,
+                destination,
+                &envs,
+                &TerraformValidators::None,
+            )
+            .map_err(|e| Box::new(EngineError::new_terraform_error(self.event_details.clone(), e)))?;
+        }
+
+        Ok(())
+    }
+
     fn delete_resources_from_state(&self, resources: &[&str], logger: &impl InfraLogger) {
         for resource in resources {
             if self.is_dry_run {
@@ -0,0 +1,261 @@
+use crate::errors::{CommandError, EngineError, ErrorMessageVerbosity};
+use crate::events::EventDetails;
+use crate::infrastructure::action::InfraLogger;
+use crate::infrastructure::action::deploy_terraform::TerraformInfraResources;
+use crate::infrastructure::action::eks::AwsEksQoveryTerraformOutput;
+use crate::infrastructure::action::kubectl_utils::check_workers_on_create;
+use crate::infrastructure::action::node_drain::{
+    check_workloads_health, cordon_nodes, drain_nodes, get_not_ready_workloads,
+};
+use crate::infrastructure::infrastructure_context::InfrastructureContext;
+use crate::infrastructure::models::kubernetes::aws::eks::EKS;
+use crate::infrastructure::models::kubernetes::{Kubernetes, KubernetesUpgradeStatus};
+use crate::io_models::models::NodeGroupsWithDesiredState;
+use crate::runtime::block_on;
+use crate::services::kube_client::{QubeClient, SelectK8sResourceBy};
+use crate::utilities::envs_to_string;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use tera::Context as TeraContext;
+
+/// Upgrades worker nodes by swapping them with new ones instead of replacing them in place.
+/// Green node groups are started on the requested version next to the current (blue) ones, workloads are moved on
+/// them, then green node groups take the place of the blue ones in the terraform state and blue ones are removed.
+/// If workloads are not healthy on the green nodes, blue nodes are restored and the upgrade is rolled back.
+pub(super) fn upgrade_eks_workers_blue_green(
+    kubernetes: &EKS,
+    infra_ctx: &InfrastructureContext,
+    kubernetes_upgrade_status: &KubernetesUpgradeStatus,
+    nodes_groups: &[NodeGroupsWithDesiredState],
+    mut context: TeraContext,
+    event_details: &EventDetails,
+    logger: &impl InfraLogger,
+) -> Result<(), Box<EngineError>> {
+    let requested_version = kubernetes_upgrade_status.requested_version.to_string();
+    let deployed_version = kubernetes_upgrade_status.deployed_workers_version.to_string();
+    context.insert("eks_green_workers_version", &requested_version);
+
+    if infra_ctx.context().is_dry_run_deploy() {
+        logger.warn("👻 Dry run mode, blue/green worker nodes upgrade is skipped.");
+        context.insert("eks_workers_version", &requested_version);
+        return apply_terraform(kubernetes, infra_ctx, &context, event_details, logger);
+    }
+
+    // Karpenter is not used with blue/green upgrades, so the nodes of the cluster are the ones of the blue node groups.
+    // Workloads already not ready are recorded, so they don't fail the upgrade
+    let kube_client = infra_ctx.mk_kube_client()?;
+    let blue_nodes = list_nodes(&kube_client, event_details)?;
+    let not_ready_before_upgrade = get_not_ready_workloads(&kube_client, event_details)?;
+
+    logger.info("Starting green worker nodes with the requested Kubernetes version.");
+    context.insert("eks_workers_version", &deployed_version);
+    context.insert("eks_green_worker_nodes", &green_node_groups(nodes_groups));
+    apply_terraform(kubernetes, infra_ctx, &context, event_details, logger)?;
+    check_workers_on_create(kubernetes, infra_ctx.cloud_provider(), None)
+        .map_err(|e| EngineError::new_k8s_node_not_ready(event_details.clone(), e))?;
+
+    // Move workloads on green nodes, and roll back if they are not healthy there
+    logger.info("Moving workloads to green worker nodes.");
+    if let Err(e) = move_workloads(
+        kubernetes,
+        infra_ctx,
+        &kube_client,
+        event_details,
+        &blue_nodes,
+        &not_ready_before_upgrade,
+        logger,
+    ) {
+        logger.warn("Workloads are not healthy on green worker nodes, rolling back to blue worker nodes.");
+        cordon_nodes(&kube_client, event_details, &blue_nodes, false)?;
+        context.insert("eks_green_worker_nodes", &Vec::<NodeGroupsWithDesiredState>::new());
+        apply_terraform(kubernetes, infra_ctx, &context, event_details, logger)?;
+
+        return Err(Box::new(EngineError::new_cluster_blue_green_upgrade_rolled_back(
+            event_details.clone(),
+            e.underlying_error()
+                .unwrap_or_else(|| CommandError::new_from_safe_message(e.message(ErrorMessageVerbosity::SafeOnly))),
+        )));
+    }
+
+    // Green node groups become the worker node groups, blue ones are left out of the configuration so they are removed
+    logger.info("Swapping blue and green worker node groups.");
+    terraform_resources(kubernetes, infra_ctx, &context, event_details)
+        .move_resources_in_state(&swap_node_groups_state_moves(nodes_groups.len()), logger)?;
+    logger.info("Removing blue worker nodes.");
+    context.insert("eks_workers_version", &requested_version);
+    context.insert("eks_green_worker_nodes", &Vec::<NodeGroupsWithDesiredState>::new());
+    apply_terraform(kubernetes, infra_ctx, &context, event_details, logger)
+}
+
+/// Green node groups have to host all workloads as soon as blue nodes are drained, so they start at the current size
+fn green_node_groups(nodes_groups: &[NodeGroupsWithDesiredState]) -> Vec<NodeGroupsWithDesiredState> {
+    nodes_groups
+        .iter()
+        .map(|node_group| NodeGroupsWithDesiredState {
+            min_nodes: node_group.desired_size.max(node_group.min_nodes),
+            ..node_group.clone()
+        })
+        .collect()
+}
+
+/// Terraform state moves giving the addresses of blue node groups resources to the green ones.
+/// Blue ones are moved first to addresses absent from the configuration, so the next apply destroys them
+fn swap_node_groups_state_moves(node_groups_count: usize) -> Vec<(String, String)> {
+    let resources = |ix: usize, color: &str| {
+        [
+            format!("aws_eks_node_group.eks_cluster_{color}workers_{ix}"),
+            format!("aws_launch_template.eks_{color}workers_nodes_{ix}"),
+            format!("null_resource.autoscaling_suspend_{color}workers_nodes_{ix}"),
+        ]
+    };
+
+    let retire_blue =
+        (1..=node_groups_count).flat_map(|ix| resources(ix, "").into_iter().zip(resources(ix, "retired_")));
+    let promote_green =
+        (1..=node_groups_count).flat_map(|ix| resources(ix, "green_").into_iter().zip(resources(ix, "")));
+    retire_blue.chain(promote_green).collect()
+}
+
+/// Cordons and drains the given nodes, then waits for workloads to be healthy on the remaining ones
+fn move_workloads(
+    kubernetes: &EKS,
+    infra_ctx: &InfrastructureContext,
+    kube_client: &QubeClient,
+    event_details: &EventDetails,
+    nodes: &[String],
+    not_ready_before_upgrade: &BTreeSet<String>,
+    logger: &impl InfraLogger,
+) -> Result<(), Box<EngineError>> {
+    cordon_nodes(kube_client, event_details, nodes, true)?;
+    drain_nodes(kubernetes as &dyn Kubernetes, infra_ctx, event_details, nodes, logger)?;
+
+    logger.info("Checking workloads health.");
+    check_workloads_health(kube_client, event_details, not_ready_before_upgrade)
+        .map_err(|e| Box::new(EngineError::new_k8s_node_not_ready(event_details.clone(), e)))
+}
+
+fn list_nodes(kube_client: &QubeClient, event_details: &EventDetails) -> Result<Vec<String>, Box<EngineError>> {
+    Ok(
+        block_on(kube_client.get_nodes(event_details.clone(), SelectK8sResourceBy::All))?
+            .into_iter()
+            .filter_map(|node| node.metadata.name)
+            .collect(),
+    )
+}
+
+fn terraform_resources(
+    kubernetes: &EKS,
+    infra_ctx: &InfrastructureContext,
+    context: &TeraContext,
+    event_details: &EventDetails,
+) -> TerraformInfraResources {
+    TerraformInfraResources::new(
+        context.clone(),
+        PathBuf::from(&kubernetes.template_directory).join("terraform"),
+        kubernetes.temp_dir().join("terraform"),
+        event_details.clone(),
+        envs_to_string(infra_ctx.cloud_provider().credentials_environment_variables()),
+        infra_ctx.context().is_dry_run_deploy(),
+    )
+}
+
+fn apply_terraform(
+    kubernetes: &EKS,
+    infra_ctx: &InfrastructureContext,
+    context: &TeraContext,
+    event_details: &EventDetails,
+    logger: &impl InfraLogger,
+) -> Result<(), Box<EngineError>> {
+    let _: AwsEksQoveryTerraformOutput =
+        terraform_resources(kubernetes, infra_ctx, context, event_details).create(logger)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{green_node_groups, swap_node_groups_state_moves};
+    use crate::io_models::models::{CpuArchitecture, NodeGroupsWithDesiredState};
+
+    #[test]
+    fn test_green_node_groups_start_at_current_size() {
+        // setup:
+        let node_group = |min_nodes: i32, desired_size: i32| NodeGroupsWithDesiredState {
+            name: "default".to_string(),
+            id: None,
+            min_nodes,
+            max_nodes: 10,
+            desired_size,
+            enable_desired_size: false,
+            instance_type: "t3a.large".to_string(),
+            disk_size_in_gib: 50,
+            instance_architecture: CpuArchitecture::AMD64,
+        };
+
+        // execute:
+        let green = green_node_groups(&[node_group(3, 5), node_group(3, 2)]);
+
+        // verify:
+        assert_eq!(vec![node_group(5, 5), node_group(3, 2)], green);
+    }
+
+    #[test]
+    fn test_swap_node_groups_state_moves() {
+        // execute:
+        let moves = swap_node_groups_state_moves(2);
+
+        // verify:
+        let expected = [
+            (
+                "aws_eks_node_group.eks_cluster_workers_1",
+                "aws_eks_node_group.eks_cluster_retired_workers_1",
+            ),
+            (
+                "aws_launch_template.eks_workers_nodes_1",
+                "aws_launch_template.eks_retired_workers_nodes_1",
+            ),
+            (
+                "null_resource.autoscaling_suspend_workers_nodes_1",
+                "null_resource.autoscaling_suspend_retired_workers_nodes_1",
+            ),
+            (
+                "aws_eks_node_group.eks_cluster_workers_2",
+                "aws_eks_node_group.eks_cluster_retired_workers_2",
+            ),
+            (
+                "aws_launch_template.eks_workers_nodes_2",
+                "aws_launch_template.eks_retired_workers_nodes_2",
+            ),
+            (
+                "null_resource.autoscaling_suspend_workers_nodes_2",
+                "null_resource.autoscaling_suspend_retired_workers_nodes_2",
+            ),
+            (
+                "aws_eks_node_group.eks_cluster_green_workers_1",
+                "aws_eks_node_group.eks_cluster_workers_1",
+            ),
+            (
+                "aws_launch_template.eks_green_workers_nodes_1",
+                "aws_launch_template.eks_workers_nodes_1",
+            ),
+            (
+                "null_resource.autoscaling_suspend_green_workers_nodes_1",
+                "null_resource.autoscaling_suspend_workers_nodes_1",
+            ),
+            (
+                "aws_eks_node_group.eks_cluster_green_workers_2",
+                "aws_eks_node_group.eks_cluster_workers_2",
+            ),
+            (
+                "aws_launch_template.eks_green_workers_nodes_2",
+                "aws_launch_template.eks_workers_nodes_2",
+            ),
+            (
+                "null_resource.autoscaling_suspend_green_workers_nodes_2",
+                "null_resource.autoscaling_suspend_workers_nodes_2",
+            ),
+        ]
+        .map(|(source, destination)| (source.to_string(), destination.to_string()));
+        assert_eq!(expected.to_vec(), moves);
+    }
+}
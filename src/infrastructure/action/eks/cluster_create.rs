@@ -2,6 +2,7 @@ use crate::environment::models::kubernetes::K8sObject;
 use crate::errors::{CommandError, EngineError, Tag};
 use crate::events::{EventDetails, InfrastructureStep, Stage};
 use crate::infrastructure::action::InfraLogger;
+use crate::infrastructure::action::cluster_backup::create_backups_bucket;
 use crate::infrastructure::action::cluster_outputs_helper::update_cluster_outputs;
 use crate::infrastructure::action::deploy_helms::{HelmInfraContext, HelmInfraResources};
 use crate::infrastructure::action::deploy_terraform::TerraformInfraResources;
@@ -185,6 +186,7 @@ pub fn create_eks_cluster(
     }
 
     patch_kube_proxy_for_custom_vpc(kubernetes, infra_ctx, event_details.clone(), &logger)?;
+    create_backups_bucket(kubernetes, &event_details, &logger)?;
     let alb_already_deployed = is_nginx_migrated_to_alb(kubernetes, infra_ctx, event_details.clone())?;
     let helms_deployments = EksHelmsDeployment::new(
         HelmInfraContext::new(
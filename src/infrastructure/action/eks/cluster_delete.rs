@@ -5,7 +5,7 @@ use crate::errors::EngineError;
 use crate::events::{EventMessage, InfrastructureStep, Stage};
 use crate::infrastructure::action::InfraLogger;
 use crate::infrastructure::action::cluster_outputs_helper::update_cluster_outputs;
-use crate::infrastructure::action::delete_kube_apps::{delete_all_pdbs, delete_kube_apps};
+use crate::infrastructure::action::delete_kube_apps::{KubeAppsDeletionScope, delete_all_pdbs, delete_kube_apps};
 use crate::infrastructure::action::deploy_terraform::TerraformInfraResources;
 use crate::infrastructure::action::eks::karpenter::Karpenter;
 use crate::infrastructure::action::eks::karpenter::node_groups_when_karpenter_is_enabled;
@@ -141,7 +141,14 @@ pub fn delete_eks_cluster(
     } else {
         HashSet::with_capacity(0)
     };
-    delete_kube_apps(kubernetes, infra_ctx, event_details.clone(), &logger, skip_helm_release)?;
+    delete_kube_apps(
+        kubernetes,
+        infra_ctx,
+        event_details.clone(),
+        &logger,
+        skip_helm_release,
+        KubeAppsDeletionScope::AllApps,
+    )?;
 
     logger.info(format!(
         "Deleting Kubernetes cluster {}/{}",
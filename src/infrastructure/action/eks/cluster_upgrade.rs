@@ -6,6 +6,7 @@ use crate::infrastructure::action::InfraLogger;
 use crate::infrastructure::action::delete_kube_apps::prepare_kube_upgrade;
 use crate::infrastructure::action::deploy_terraform::TerraformInfraResources;
 use crate::infrastructure::action::eks::AwsEksQoveryTerraformOutput;
+use crate::infrastructure::action::eks::blue_green_upgrade::upgrade_eks_workers_blue_green;
 use crate::infrastructure::action::eks::nodegroup::should_update_desired_nodes;
 use crate::infrastructure::action::eks::tera_context::eks_tera_context;
 use crate::infrastructure::action::eks::utils::{define_cluster_upgrade_timeout, get_rusoto_eks_client};
@@ -127,7 +128,19 @@ pub fn upgrade_eks_cluster(
         );
     }
 
-    let _: AwsEksQoveryTerraformOutput = tf_resources.create(&logger)?;
+    if kubernetes.advanced_settings.aws_eks_enable_blue_green_upgrade && !kubernetes.is_karpenter_enabled() {
+        upgrade_eks_workers_blue_green(
+            kubernetes,
+            infra_ctx,
+            &kubernetes_upgrade_status,
+            &nodes_groups,
+            context,
+            &event_details,
+            &logger,
+        )?;
+    } else {
+        let _: AwsEksQoveryTerraformOutput = tf_resources.create(&logger)?;
+    }
 
     // In case of karpenter, we don't need to upgrade workers, it will do it by itself
     if !infra_ctx.kubernetes().is_karpenter_enabled() {
@@ -36,17 +36,24 @@ use crate::infrastructure::action::eks::helm_charts::gen_karpenter_charts::gener
 use crate::infrastructure::action::gen_metrics_charts::{CloudProviderMetricsConfig, generate_metrics_config};
 use crate::infrastructure::helm_charts::cert_manager_chart::CertManagerChart;
 use crate::infrastructure::helm_charts::cert_manager_config_chart::CertManagerConfigsChart;
+use crate::infrastructure::helm_charts::envoy_gateway_chart::EnvoyGatewayChart;
+use crate::infrastructure::helm_charts::envoy_gateway_config_chart::EnvoyGatewayConfigsChart;
 use crate::infrastructure::helm_charts::external_dns_chart::ExternalDNSChart;
 use crate::infrastructure::helm_charts::grafana_chart::{
     CloudWatchConfig, GrafanaAdminUser, GrafanaChart, GrafanaDatasources,
 };
+use crate::infrastructure::helm_charts::keda_chart::KedaChart;
 use crate::infrastructure::helm_charts::loki_chart::{
     LokiChart, LokiObjectBucketConfiguration, S3LokiChartConfiguration,
 };
 use crate::infrastructure::helm_charts::metrics_server_chart::MetricsServerChart;
+use crate::infrastructure::helm_charts::opencost_chart::OpenCostChart;
 use crate::infrastructure::helm_charts::qovery_cert_manager_webhook_chart::QoveryCertManagerWebhookChart;
 use crate::infrastructure::helm_charts::qovery_cluster_agent_chart::QoveryClusterAgentChart;
 use crate::infrastructure::helm_charts::qovery_priority_class_chart::QoveryPriorityClassChart;
+use crate::infrastructure::helm_charts::velero_chart::{VeleroChart, VeleroStorageConfiguration};
+use crate::infrastructure::models::cloud_provider::io::IngressImplementation;
+use crate::infrastructure::models::kubernetes::backups_bucket_name;
 use crate::io_models::QoveryIdentifier;
 use chrono::Duration;
 use std::collections::HashSet;
@@ -79,6 +86,23 @@ pub(super) fn eks_helm_charts(
         chart_config_prerequisites.cluster_long_id.to_string().as_str(),
     )?;
 
+    // OpenCost, workloads costs computed from the metrics stack
+    let opencost = match (
+        chart_config_prerequisites.cluster_advanced_settings.opencost_enabled,
+        &metrics_config.metrics_query_url,
+    ) {
+        (true, Some(metrics_query_url)) => Some(
+            OpenCostChart::new(
+                chart_prefix_path,
+                chart_config_prerequisites.cluster_long_id.to_string(),
+                metrics_query_url.to_string(),
+                get_chart_override_fn.clone(),
+            )
+            .to_common_helm_chart()?,
+        ),
+        _ => None,
+    };
+
     // Qovery storage class
     let q_storage_class = QoveryStorageClassChart::new(
         chart_prefix_path,
@@ -250,6 +274,10 @@ pub(super) fn eks_helm_charts(
         chart_config_prerequisites.cluster_id.to_string(),
         UpdateStrategy::RollingUpdate,
         true,
+        chart_config_prerequisites
+            .cluster_advanced_settings
+            .ingress_implementation
+            .is_gateway_api(),
         HelmChartNamespaces::KubeSystem,
     )
     .to_common_helm_chart()?;
@@ -338,6 +366,69 @@ pub(super) fn eks_helm_charts(
     )
     .to_common_helm_chart()?;
 
+    // KEDA, event driven autoscaling for services
+    let keda = match chart_config_prerequisites.cluster_advanced_settings.keda_enabled {
+        false => None,
+        true => Some(
+            KedaChart::new(
+                chart_prefix_path,
+                HelmChartNamespaces::Keda,
+                chart_config_prerequisites.metrics_parameters.is_some(),
+                get_chart_override_fn.clone(),
+            )
+            .to_common_helm_chart()?,
+        ),
+    };
+
+    // Velero, cluster resources and volumes backups
+    let velero = match chart_config_prerequisites
+        .cluster_advanced_settings
+        .cluster_backup_enabled
+    {
+        false => None,
+        true => Some(
+            VeleroChart::new(
+                chart_prefix_path,
+                VeleroStorageConfiguration::AwsS3 {
+                    region: chart_config_prerequisites.region.to_cloud_provider_format().to_string(),
+                    bucket_name: backups_bucket_name(&chart_config_prerequisites.cluster_id),
+                    aws_iam_velero_role_arn: chart_config_prerequisites.aws_iam_velero_role_arn.clone(),
+                },
+                chart_config_prerequisites
+                    .cluster_advanced_settings
+                    .cluster_backup_schedule
+                    .clone(),
+                chart_config_prerequisites
+                    .cluster_advanced_settings
+                    .cluster_backup_retention_in_days,
+                get_chart_override_fn.clone(),
+            )
+            .to_common_helm_chart()?,
+        ),
+    };
+
+    // Envoy Gateway, routers are exposed through Gateway API instead of nginx ingress
+    let (envoy_gateway, envoy_gateway_configs) = match chart_config_prerequisites
+        .cluster_advanced_settings
+        .ingress_implementation
+    {
+        IngressImplementation::EnvoyGateway => (
+            Some(
+                EnvoyGatewayChart::new(
+                    chart_prefix_path,
+                    HelmChartNamespaces::EnvoyGateway,
+                    get_chart_override_fn.clone(),
+                )
+                .to_common_helm_chart()?,
+            ),
+            Some(
+                EnvoyGatewayConfigsChart::new(chart_prefix_path, HelmChartNamespaces::EnvoyGateway)
+                    .to_common_helm_chart()?,
+            ),
+        ),
+        IngressImplementation::Nginx | IngressImplementation::Cilium => (None, None),
+    };
+
     // Grafana chart
     let grafana = match chart_config_prerequisites.ff_grafana_enabled {
         false => None,
@@ -376,6 +467,10 @@ pub(super) fn eks_helm_charts(
         UpdateStrategy::RollingUpdate,
         get_chart_override_fn.clone(),
         true,
+        chart_config_prerequisites
+            .cluster_advanced_settings
+            .ingress_implementation
+            .is_gateway_api(),
         HelmChartNamespaces::CertManager,
         HelmChartNamespaces::KubeSystem,
     )
@@ -712,9 +807,21 @@ pub(super) fn eks_helm_charts(
         level_8.push(Box::new(aws_load_balancer_controller));
     }
 
+    if let Some(keda_chart) = keda {
+        level_8.push(Box::new(keda_chart));
+    }
+
+    if let Some(velero_chart) = velero {
+        level_8.push(Box::new(velero_chart));
+    }
+
+    if let Some(opencost_chart) = opencost {
+        level_8.push(Box::new(opencost_chart));
+    }
+
     let level_9: Vec<Box<dyn HelmChart>> = vec![Box::new(nginx_ingress)];
 
-    let level_10: Vec<Box<dyn HelmChart>> = vec![
+    let mut level_10: Vec<Box<dyn HelmChart>> = vec![
         Box::new(cert_manager_config),
         Box::new(cluster_agent),
         Box::new(qovery_shell_agent),
@@ -742,6 +849,14 @@ pub(super) fn eks_helm_charts(
         level_5.push(Box::new(grafana_chart))
     }
 
+    // gateway API
+    if let Some(envoy_gateway_chart) = envoy_gateway {
+        level_8.push(Box::new(envoy_gateway_chart));
+    }
+    if let Some(envoy_gateway_configs_chart) = envoy_gateway_configs {
+        level_10.push(Box::new(envoy_gateway_configs_chart));
+    }
+
     // karpenter
     if let Some(karpenter_charts) = karpenter_charts {
         level_0.push(Box::new(karpenter_charts.karpenter_crd_chart));
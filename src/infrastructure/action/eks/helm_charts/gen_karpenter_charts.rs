@@ -56,6 +56,9 @@ pub fn generate_karpenter_charts(
         karpenter_parameters,
         chart_config_prerequisites.infra_options.user_provided_network.as_ref(),
         chart_config_prerequisites.cluster_advanced_settings.pleco_resources_ttl,
+        chart_config_prerequisites
+            .cluster_advanced_settings
+            .maintenance_window(),
     )
     .to_common_helm_chart()?;
 
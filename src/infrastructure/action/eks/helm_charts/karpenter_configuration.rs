@@ -9,9 +9,11 @@ use crate::infrastructure::helm_charts::{
 use crate::infrastructure::models::kubernetes::KubernetesVersion;
 use crate::infrastructure::models::kubernetes::aws::UserNetworkConfig;
 use crate::infrastructure::models::kubernetes::karpenter::{
-    KarpenterNodePool, KarpenterNodePoolRequirement, KarpenterNodePoolRequirementKey, KarpenterParameters,
-    KarpenterRequirementOperator,
+    KarpenterNodePool, KarpenterNodePoolDisruptionBudget, KarpenterNodePoolDisruptionReason,
+    KarpenterNodePoolRequirement, KarpenterNodePoolRequirementKey, KarpenterParameters, KarpenterRequirementOperator,
+    KarpenterStableNodePoolOverride, KarpenterUserNodePool,
 };
+use crate::infrastructure::models::kubernetes::maintenance_window::MaintenanceWindow;
 use itertools::Itertools;
 use kube::Client;
 
@@ -30,6 +32,7 @@ pub struct KarpenterConfigurationChart {
     karpenter_parameters: KarpenterParameters,
     explicit_subnet_ids: Vec<String>,
     pleco_resources_ttl: i32,
+    maintenance_window: Option<MaintenanceWindow>,
 }
 
 impl KarpenterConfigurationChart {
@@ -47,6 +50,7 @@ impl KarpenterConfigurationChart {
         karpenter_parameters: KarpenterParameters,
         user_network_config: Option<&UserNetworkConfig>,
         pleco_resources_ttl: i32,
+        maintenance_window: Option<MaintenanceWindow>,
     ) -> Self {
         KarpenterConfigurationChart {
             chart_path: HelmChartPath::new(
@@ -83,6 +87,7 @@ impl KarpenterConfigurationChart {
                 vec![]
             },
             pleco_resources_ttl,
+            maintenance_window,
         }
     }
 
@@ -107,6 +112,99 @@ impl KarpenterConfigurationChart {
 
         requirements
     }
+
+    /// User node pools keep their own capacity type if any, otherwise they follow the cluster one
+    fn enrich_user_node_pool_requirements(
+        spot_enabled: bool,
+        user_node_pool: &KarpenterUserNodePool,
+    ) -> Vec<KarpenterNodePoolRequirement> {
+        if user_node_pool
+            .requirements
+            .iter()
+            .any(|requirement| requirement.key == KarpenterNodePoolRequirementKey::CapacityType)
+        {
+            return user_node_pool.requirements.clone();
+        }
+
+        Self::enrich_karpenter_requirements(
+            spot_enabled,
+            KarpenterNodePool {
+                requirements: user_node_pool.requirements.clone(),
+                stable_override: KarpenterStableNodePoolOverride {
+                    budgets: vec![],
+                    limits: None,
+                },
+                default_override: None,
+            },
+        )
+    }
+
+    fn requirement_values(prefix: &str, requirement: &KarpenterNodePoolRequirement) -> Vec<ChartSetValue> {
+        let formated_values = if requirement.key == KarpenterNodePoolRequirementKey::Arch {
+            // The nodepool support only lowercase value for arch
+            requirement.values.iter().map(|value| value.to_lowercase()).join(",")
+        } else {
+            requirement.values.join(",")
+        };
+
+        vec![
+            ChartSetValue {
+                key: format!("{prefix}.key"),
+                value: requirement.key.to_k8s_label(),
+            },
+            ChartSetValue {
+                key: format!("{prefix}.operator"),
+                value: requirement
+                    .operator
+                    .as_ref()
+                    .unwrap_or(&KarpenterRequirementOperator::In)
+                    .to_string(),
+            },
+            ChartSetValue {
+                key: format!("{prefix}.values"),
+                value: format!("{{{formated_values}}}"),
+            },
+        ]
+    }
+
+    /// Forbids drift disruptions while the maintenance window is closed
+    fn maintenance_drift_budgets(&self) -> Vec<KarpenterNodePoolDisruptionBudget> {
+        let Some(maintenance_window) = &self.maintenance_window else {
+            return vec![];
+        };
+
+        maintenance_window
+            .closed_periods()
+            .into_iter()
+            .map(|(schedule, closed_for)| KarpenterNodePoolDisruptionBudget {
+                nodes: "0".to_string(),
+                reasons: vec![KarpenterNodePoolDisruptionReason::Drifted],
+                duration: closed_for.to_std().unwrap_or_default(),
+                schedule,
+            })
+            .collect()
+    }
+
+    fn budget_values(prefix: &str, budget: &KarpenterNodePoolDisruptionBudget) -> Vec<ChartSetValue> {
+        vec![
+            ChartSetValue {
+                key: format!("{prefix}.nodes"),
+                value: budget.nodes.to_string(),
+            },
+            ChartSetValue {
+                key: format!("{prefix}.reasons"),
+                value: budget.reasons.to_helm_format_string().to_string(),
+            },
+            ChartSetValue {
+                key: format!("{prefix}.duration"),
+                value: budget.get_karpenter_budget_duration_as_string(),
+            },
+            ChartSetValue {
+                key: format!("{prefix}.schedule"),
+                value: budget.schedule.to_string(),
+            },
+        ]
+    }
 }
 
 impl ToCommonHelmChart for KarpenterConfigurationChart {
@@ -179,54 +277,30 @@ impl ToCommonHelmChart for KarpenterConfigurationChart {
             .iter()
             .enumerate()
             .for_each(|(index, requirement)| {
-                let prefix = format!("global_node_pools.requirements[{index}]");
-
-                let formated_values = if requirement.key == KarpenterNodePoolRequirementKey::Arch {
-                    // The nodepool support only lowercase value for arch
-                    requirement.values.iter().map(|value| value.to_lowercase()).join(",")
-                } else {
-                    requirement.values.join(",")
-                };
+                values.extend(Self::requirement_values(
+                    &format!("global_node_pools.requirements[{index}]"),
+                    requirement,
+                ));
+            });
 
-                values.push(ChartSetValue {
-                    key: format!("{prefix}.key"),
-                    value: requirement.key.to_k8s_label(),
-                });
-                values.push(ChartSetValue {
-                    key: format!("{prefix}.operator"),
-                    value: requirement
-                        .operator
-                        .as_ref()
-                        .unwrap_or(&KarpenterRequirementOperator::In)
-                        .to_string(),
-                });
-                values.push(ChartSetValue {
-                    key: format!("{prefix}.values"),
-                    value: format!("{{{formated_values}}}"),
-                });
+        // Nodes drifted by a new AMI are only replaced during the maintenance window, if any
+        self.maintenance_drift_budgets()
+            .iter()
+            .enumerate()
+            .for_each(|(index, it)| {
+                values.extend(Self::budget_values(
+                    &format!("global_node_pools.maintenanceBudgets[{index}]"),
+                    it,
+                ));
             });
 
         // Stable node pool consolidation
         let stable_pool_override = self.karpenter_parameters.qovery_node_pools.stable_override.clone();
         stable_pool_override.budgets.iter().enumerate().for_each(|(index, it)| {
-            let prefix = format!("stableNodePool.consolidation.budgets[{index}]");
-
-            values.push(ChartSetValue {
-                key: format!("{prefix}.nodes"),
-                value: it.nodes.to_string(),
-            });
-            values.push(ChartSetValue {
-                key: format!("{prefix}.reasons"),
-                value: it.reasons.to_helm_format_string().to_string(),
-            });
-            values.push(ChartSetValue {
-                key: format!("{prefix}.duration"),
-                value: it.get_karpenter_budget_duration_as_string(),
-            });
-            values.push(ChartSetValue {
-                key: format!("{prefix}.schedule"),
-                value: it.schedule.to_string(),
-            });
+            values.extend(Self::budget_values(
+                &format!("stableNodePool.consolidation.budgets[{index}]"),
+                it,
+            ));
         });
 
         // Stable node pool limits
@@ -259,6 +333,58 @@ impl ToCommonHelmChart for KarpenterConfigurationChart {
             });
         }
 
+        // User node pools
+        self.karpenter_parameters
+            .user_node_pools
+            .iter()
+            .enumerate()
+            .for_each(|(pool_index, node_pool)| {
+                let prefix = format!("userNodePools[{pool_index}]");
+
+                values.push(ChartSetValue {
+                    key: format!("{prefix}.name"),
+                    value: node_pool.name.clone(),
+                });
+                Self::enrich_user_node_pool_requirements(self.karpenter_parameters.spot_enabled, node_pool)
+                    .iter()
+                    .enumerate()
+                    .for_each(|(index, requirement)| {
+                        values.extend(Self::requirement_values(
+                            &format!("{prefix}.requirements[{index}]"),
+                            requirement,
+                        ));
+                    });
+                node_pool.all_taints().iter().enumerate().for_each(|(index, taint)| {
+                    values.push(ChartSetValue {
+                        key: format!("{prefix}.taints[{index}].key"),
+                        value: taint.key.clone(),
+                    });
+                    if let Some(value) = &taint.value {
+                        values.push(ChartSetValue {
+                            key: format!("{prefix}.taints[{index}].value"),
+                            value: value.clone(),
+                        });
+                    }
+                    values.push(ChartSetValue {
+                        key: format!("{prefix}.taints[{index}].effect"),
+                        value: taint.effect.to_string(),
+                    });
+                });
+                node_pool.budgets.iter().enumerate().for_each(|(index, it)| {
+                    values.extend(Self::budget_values(&format!("{prefix}.budgets[{index}]"), it));
+                });
+                if let Some(limits) = &node_pool.limits {
+                    values.push(ChartSetValue {
+                        key: format!("{prefix}.limits.maxCpu"),
+                        value: limits.max_cpu.to_string(),
+                    });
+                    values.push(ChartSetValue {
+                        key: format!("{prefix}.limits.maxMemory"),
+                        value: limits.max_memory.to_string(),
+                    });
+                }
+            });
+
         let mut values_string: Vec<ChartSetValue> = vec![];
         if self.pleco_resources_ttl > 0 {
             values_string.push(ChartSetValue {
@@ -330,8 +456,8 @@ mod tests {
     use crate::infrastructure::models::kubernetes::karpenter::{
         KarpenterDefaultNodePoolOverride, KarpenterNodePool, KarpenterNodePoolDisruptionBudget,
         KarpenterNodePoolDisruptionReason, KarpenterNodePoolLimits, KarpenterNodePoolRequirement,
-        KarpenterNodePoolRequirementKey, KarpenterParameters, KarpenterRequirementOperator,
-        KarpenterStableNodePoolOverride,
+        KarpenterNodePoolRequirementKey, KarpenterNodePoolTaint, KarpenterNodePoolTaintEffect, KarpenterParameters,
+        KarpenterRequirementOperator, KarpenterStableNodePoolOverride, KarpenterUserNodePool,
     };
     use crate::infrastructure::models::kubernetes::{Kind as KubernetesKind, KubernetesVersion};
     use crate::io_models::models::CpuArchitecture::ARM64;
@@ -605,6 +731,93 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_karpenter_configuration_user_node_pools_values() {
+        // setup:
+        let mut chart = create_chart(
+            KUBERNETES_VERSION,
+            true,
+            KarpenterNodePool {
+                requirements: vec![],
+                stable_override: KarpenterStableNodePoolOverride {
+                    budgets: vec![],
+                    limits: None,
+                },
+                default_override: None,
+            },
+        );
+        chart.karpenter_parameters.user_node_pools = vec![
+            KarpenterUserNodePool {
+                name: "high-memory".to_string(),
+                requirements: vec![KarpenterNodePoolRequirement {
+                    key: KarpenterNodePoolRequirementKey::InstanceCategory,
+                    operator: Some(KarpenterRequirementOperator::In),
+                    values: vec!["r".to_string()],
+                }],
+                taints: vec![KarpenterNodePoolTaint {
+                    key: "tenant".to_string(),
+                    value: Some("noisy".to_string()),
+                    effect: KarpenterNodePoolTaintEffect::NoExecute,
+                }],
+                limits: Some(KarpenterNodePoolLimits {
+                    max_cpu: KubernetesCpuResourceUnit::MilliCpu(16_000),
+                    max_memory: KubernetesMemoryResourceUnit::GibiByte(128),
+                }),
+                budgets: vec![],
+            },
+            KarpenterUserNodePool {
+                name: "batch".to_string(),
+                requirements: vec![KarpenterNodePoolRequirement {
+                    key: KarpenterNodePoolRequirementKey::CapacityType,
+                    operator: Some(KarpenterRequirementOperator::In),
+                    values: vec!["spot".to_string()],
+                }],
+                taints: vec![],
+                limits: None,
+                budgets: vec![],
+            },
+        ];
+
+        // execute:
+        let values = chart.to_common_helm_chart().unwrap().chart_info.values;
+        let value_of = |key: &str| {
+            values
+                .iter()
+                .find(|value| value.key == key)
+                .map(|value| value.value.clone())
+        };
+
+        // verify:
+        assert_eq!(value_of("userNodePools[0].name"), Some("high-memory".to_string()));
+        assert_eq!(
+            value_of("userNodePools[0].requirements[0].key"),
+            Some("karpenter.k8s.aws/instance-category".to_string())
+        );
+        // capacity type follows the cluster one when not set on the node pool
+        assert_eq!(
+            value_of("userNodePools[0].requirements[1].key"),
+            Some("karpenter.sh/capacity-type".to_string())
+        );
+        assert_eq!(
+            value_of("userNodePools[0].requirements[1].values"),
+            Some("{spot,on-demand}".to_string())
+        );
+        assert_eq!(
+            value_of("userNodePools[0].taints[0].key"),
+            Some("nodepool/high-memory".to_string())
+        );
+        assert_eq!(value_of("userNodePools[0].taints[0].value"), None);
+        assert_eq!(value_of("userNodePools[0].taints[0].effect"), Some("NoSchedule".to_string()));
+        assert_eq!(value_of("userNodePools[0].taints[1].key"), Some("tenant".to_string()));
+        assert_eq!(value_of("userNodePools[0].taints[1].value"), Some("noisy".to_string()));
+        assert_eq!(value_of("userNodePools[0].taints[1].effect"), Some("NoExecute".to_string()));
+        assert_eq!(value_of("userNodePools[0].limits.maxCpu"), Some("16000m".to_string()));
+        assert_eq!(value_of("userNodePools[1].name"), Some("batch".to_string()));
+        assert_eq!(value_of("userNodePools[1].requirements[0].values"), Some("{spot}".to_string()));
+        assert_eq!(value_of("userNodePools[1].requirements[1].key"), None);
+        assert_eq!(value_of("userNodePools[1].limits.maxCpu"), None);
+    }
+
     #[derive(Debug)]
     struct TestCase {
         with_spot: bool,
@@ -691,9 +904,11 @@ mod tests {
                 disk_size_in_gib: 50,
                 default_service_architecture: ARM64,
                 qovery_node_pools,
+                user_node_pools: vec![],
             },
             None,
             0,
+            None,
         )
     }
 
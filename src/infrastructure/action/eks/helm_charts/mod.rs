@@ -71,6 +71,7 @@ pub struct EksChartsConfigPrerequisites {
     pub metrics_parameters: Option<MetricsParameters>,
     pub aws_iam_eks_prometheus_role_arn: String,
     pub aws_s3_prometheus_bucket_name: String,
+    pub aws_iam_velero_role_arn: String,
     pub karpenter_controller_aws_role_arn: String,
     pub cluster_security_group_id: String,
     pub aws_iam_alb_controller_arn: String,
@@ -155,6 +156,7 @@ impl HelmInfraResources for EksHelmsDeployment<'_> {
             metrics_parameters: cluster.options.metrics_parameters.clone(),
             aws_iam_eks_prometheus_role_arn: self.terraform_output.aws_iam_eks_prometheus_role_arn.clone(),
             aws_s3_prometheus_bucket_name: self.terraform_output.aws_s3_prometheus_bucket_name.clone(),
+            aws_iam_velero_role_arn: self.terraform_output.aws_iam_velero_role_arn.clone(),
             cluster_creation_date: cluster.created_at,
         }
     }
@@ -338,6 +338,7 @@ impl Karpenter {
             karpenter_parameters,
             options.user_provided_network.as_ref(),
             kubernetes.advanced_settings().pleco_resources_ttl,
+            kubernetes.advanced_settings().maintenance_window(),
         )
         .to_common_helm_chart()
         .map_err(|el| {
@@ -1,3 +1,4 @@
+mod blue_green_upgrade;
 mod cluster_bootstrap;
 mod cluster_create;
 mod cluster_delete;
@@ -12,17 +13,19 @@ mod tera_context;
 mod utils;
 
 use crate::errors::EngineError;
-use crate::events::InfrastructureStep;
+use crate::events::{InfrastructureStep, Stage};
 use crate::infrastructure::action::InfrastructureAction;
 use crate::infrastructure::action::eks::cluster_bootstrap::bootstrap_eks_cluster;
 use crate::infrastructure::action::eks::cluster_create::create_eks_cluster;
 use crate::infrastructure::action::eks::cluster_delete::delete_eks_cluster;
 use crate::infrastructure::action::eks::cluster_pause::pause_eks_cluster;
 use crate::infrastructure::action::eks::cluster_upgrade::upgrade_eks_cluster;
+use crate::infrastructure::action::eks::nodegroup::update_eks_nodegroups_images;
 use crate::infrastructure::infrastructure_context::InfrastructureContext;
 use crate::infrastructure::models::cloud_provider::service::Action;
 use crate::infrastructure::models::kubernetes::aws::eks::EKS;
 use crate::infrastructure::models::kubernetes::{Kubernetes, KubernetesUpgradeStatus, send_progress_on_long_task};
+use crate::runtime::block_on;
 use chrono::Duration as ChronoDuration;
 use serde_derive::{Deserialize, Serialize};
 
@@ -82,6 +85,32 @@ impl InfrastructureAction for EKS {
         })
     }
 
+    fn update_nodes_images(&self, infra_ctx: &InfrastructureContext) -> Result<(), Box<EngineError>> {
+        let logger = mk_logger(infra_ctx.kubernetes(), InfrastructureStep::Maintenance);
+        let event_details = self.get_event_details(Stage::Infrastructure(InfrastructureStep::Maintenance));
+        let aws_conn = infra_ctx
+            .cloud_provider()
+            .downcast_ref()
+            .as_aws()
+            .ok_or_else(|| Box::new(EngineError::new_bad_cast(event_details.clone(), "cloud provider is not aws")))?
+            .aws_sdk_client();
+
+        // Re-applying the cluster configuration does not bump the AMI of managed node groups, it has to be requested
+        logger.info("Updating node groups nodes to the latest images of their kubernetes version");
+        let updated_nodegroups = send_progress_on_long_task(self, Action::Create, || {
+            block_on(update_eks_nodegroups_images(&aws_conn, self.cluster_name(), &event_details))
+        })?;
+        match updated_nodegroups.is_empty() {
+            true => logger.info("Node groups nodes already run the latest images"),
+            false => logger.info(format!(
+                "Node groups {} nodes have been updated to the latest images",
+                updated_nodegroups.join(", ")
+            )),
+        }
+
+        Ok(())
+    }
+
     fn upgrade_node_selector(&self) -> Option<&str> {
         // Exclude fargate nodes from the test in case of karpenter, those will be recreated after helm deploy
         match self.is_karpenter_enabled() {
@@ -119,6 +148,8 @@ pub struct AwsEksQoveryTerraformOutput {
     pub aws_iam_eks_prometheus_role_arn: String,
     #[serde(deserialize_with = "from_terraform_value")]
     pub aws_s3_prometheus_bucket_name: String,
+    #[serde(default, deserialize_with = "from_terraform_value")]
+    pub aws_iam_velero_role_arn: String,
     #[serde(deserialize_with = "from_terraform_value")]
     pub kubeconfig: String,
     #[serde(deserialize_with = "from_terraform_value")]
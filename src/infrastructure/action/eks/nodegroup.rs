@@ -5,10 +5,16 @@ use crate::infrastructure::models::kubernetes::Kubernetes;
 use crate::io_models::models::{KubernetesClusterAction, NodeGroups, NodeGroupsWithDesiredState};
 use crate::runtime::block_on;
 use aws_sdk_eks::operation::describe_nodegroup::DescribeNodegroupOutput;
+use aws_sdk_eks::types::NodegroupStatus;
 use aws_types::SdkConfig;
 use rusoto_eks::{DescribeNodegroupRequest, Eks, EksClient, ListNodegroupsRequest, NodegroupScalingConfig};
+use std::time::Duration;
 use thiserror::Error;
 
+// https://docs.aws.amazon.com/eks/latest/userguide/managed-node-update-behavior.html
+const NODEGROUP_UPDATE_TIMEOUT: Duration = Duration::from_secs(2 * 60 * 60);
+const NODEGROUP_UPDATE_POLLING_INTERVAL: Duration = Duration::from_secs(30);
+
 #[derive(Debug, PartialEq, Eq, Error)]
 pub enum NodeGroupToRemoveFailure {
     #[error("No cluster found")]
@@ -352,6 +358,90 @@ pub async fn delete_eks_nodegroups(
     Ok(())
 }
 
+/// Rolls the nodes of every active Qovery node group on the latest AMI release of their kubernetes version,
+/// and waits for the node groups to be active again. Returns the names of the node groups updated.
+pub async fn update_eks_nodegroups_images(
+    aws_conn: &SdkConfig,
+    cluster_name: String,
+    event_details: &EventDetails,
+) -> Result<Vec<String>, Box<EngineError>> {
+    let all_cluster_nodegroups = aws_conn
+        .list_all_eks_nodegroups(cluster_name.clone())
+        .await
+        .map_err(|e| {
+            Box::new(EngineError::new_nodegroup_list_error(
+                event_details.clone(),
+                CommandError::new_from_safe_message(e.to_string()),
+            ))
+        })?;
+    let all_cluster_nodegroups_described = aws_conn
+        .describe_nodegroups(cluster_name.clone(), all_cluster_nodegroups)
+        .await
+        .map_err(|e| {
+            Box::new(EngineError::new_missing_nodegroup_information_error(
+                event_details.clone(),
+                e.to_string(),
+            ))
+        })?;
+
+    let mut updated_nodegroups = vec![];
+    for nodegroup in all_cluster_nodegroups_described.iter().filter_map(|n| n.nodegroup()) {
+        let is_managed_by_qovery = nodegroup
+            .tags()
+            .is_some_and(|tags| tags.contains_key("QoveryNodeGroupName"));
+        if !is_managed_by_qovery || nodegroup.status() != Some(&NodegroupStatus::Active) {
+            continue;
+        }
+
+        let nodegroup_name = nodegroup.nodegroup_name().unwrap_or_default().to_string();
+        let to_update_error = |raw_error: String| {
+            Box::new(EngineError::new_nodegroup_update_error(
+                event_details.clone(),
+                nodegroup_name.clone(),
+                CommandError::new(
+                    "Error while trying to update nodegroup nodes images".to_string(),
+                    Some(raw_error),
+                    None,
+                ),
+            ))
+        };
+        match aws_conn
+            .update_nodegroup_version(cluster_name.clone(), nodegroup_name.clone())
+            .await
+        {
+            Ok(_) => {}
+            // AWS rejects the update when the node group already runs the latest AMI release
+            Err(e) if e.as_service_error().is_some_and(|e| e.is_invalid_parameter_exception()) => continue,
+            Err(e) => return Err(to_update_error(e.to_string())),
+        }
+
+        let deadline = tokio::time::Instant::now() + NODEGROUP_UPDATE_TIMEOUT;
+        loop {
+            tokio::time::sleep(NODEGROUP_UPDATE_POLLING_INTERVAL).await;
+            let status = aws_conn
+                .describe_nodegroup(cluster_name.clone(), nodegroup_name.clone())
+                .await
+                .map_err(|e| to_update_error(e.to_string()))?
+                .nodegroup()
+                .and_then(|n| n.status().cloned());
+            match status {
+                Some(NodegroupStatus::Active) => break,
+                Some(NodegroupStatus::Updating) if tokio::time::Instant::now() < deadline => continue,
+                Some(NodegroupStatus::Updating) => {
+                    return Err(to_update_error(format!(
+                        "nodegroup is still updating after {} minutes",
+                        NODEGROUP_UPDATE_TIMEOUT.as_secs() / 60
+                    )));
+                }
+                status => return Err(to_update_error(format!("nodegroup is in state {status:?}"))),
+            }
+        }
+        updated_nodegroups.push(nodegroup_name);
+    }
+
+    Ok(updated_nodegroups)
+}
+
 fn check_failed_nodegroups_to_remove(
     nodegroups: Vec<DescribeNodegroupOutput>,
 ) -> Result<Vec<DescribeNodegroupOutput>, NodeGroupToRemoveFailure> {
@@ -4,6 +4,7 @@ use aws_sdk_eks::operation::delete_nodegroup::{DeleteNodegroupError, DeleteNodeg
 use aws_sdk_eks::operation::describe_nodegroup::{DescribeNodegroupError, DescribeNodegroupOutput};
 use aws_sdk_eks::operation::list_clusters::{ListClustersError, ListClustersOutput};
 use aws_sdk_eks::operation::list_nodegroups::{ListNodegroupsError, ListNodegroupsOutput};
+use aws_sdk_eks::operation::update_nodegroup_version::{UpdateNodegroupVersionError, UpdateNodegroupVersionOutput};
 use aws_sdk_iam::operation::create_service_linked_role::{CreateServiceLinkedRoleError, CreateServiceLinkedRoleOutput};
 use aws_sdk_iam::operation::get_role::{GetRoleError, GetRoleOutput};
 use aws_types::SdkConfig;
@@ -30,6 +31,12 @@ pub trait QoveryAwsSdkConfigEks {
         cluster_id: String,
         nodegroup_id: String,
     ) -> Result<DeleteNodegroupOutput, SdkError<DeleteNodegroupError>>;
+    /// Rolls the nodegroup nodes on the latest AMI release of their kubernetes version
+    async fn update_nodegroup_version(
+        &self,
+        cluster_id: String,
+        nodegroup_id: String,
+    ) -> Result<UpdateNodegroupVersionOutput, SdkError<UpdateNodegroupVersionError>>;
 
     async fn get_role(&self, name: &str) -> Result<GetRoleOutput, SdkError<GetRoleError>>;
 
@@ -102,6 +109,21 @@ impl QoveryAwsSdkConfigEks for SdkConfig {
             .await
     }
 
+    async fn update_nodegroup_version(
+        &self,
+        cluster_name: String,
+        nodegroup_name: String,
+    ) -> Result<UpdateNodegroupVersionOutput, SdkError<UpdateNodegroupVersionError>> {
+        // without version nor release version, the latest AMI release of the current version is used
+        let client = aws_sdk_eks::Client::new(self);
+        client
+            .update_nodegroup_version()
+            .cluster_name(cluster_name)
+            .nodegroup_name(nodegroup_name)
+            .send()
+            .await
+    }
+
     async fn get_role(&self, name: &str) -> Result<GetRoleOutput, SdkError<GetRoleError>> {
         let client = aws_sdk_iam::Client::new(self);
         client.get_role().role_name(name).send().await
@@ -233,6 +233,7 @@ pub fn eks_tera_context(
     // Qovery
     context.insert("organization_id", kubernetes.context.organization_short_id());
     context.insert("organization_long_id", &kubernetes.context.organization_long_id().to_string());
+    context.insert("cost_allocation_tags", &kubernetes.cost_allocation_tags().to_tags());
     context.insert("qovery_api_url", &qovery_api_url);
 
     context.insert("test_cluster", &kubernetes.context().is_test_cluster());
@@ -355,6 +356,8 @@ pub fn eks_tera_context(
     context.insert("kubernetes_cluster_long_id", kubernetes.context().cluster_long_id());
     context.insert("eks_region_cluster_id", region_cluster_id.as_str());
     context.insert("eks_worker_nodes", &node_groups);
+    // only set during blue/green upgrades
+    context.insert("eks_green_worker_nodes", &Vec::<NodeGroupsWithDesiredState>::new());
     context.insert("ec2_zone_a_subnet_blocks_private", &ec2_zone_a_subnet_blocks_private);
     context.insert("ec2_zone_b_subnet_blocks_private", &ec2_zone_b_subnet_blocks_private);
     context.insert("ec2_zone_c_subnet_blocks_private", &ec2_zone_c_subnet_blocks_private);
@@ -489,6 +492,10 @@ pub fn eks_tera_context(
         &kubernetes.advanced_settings().loki_log_retention_in_week,
     );
 
+    // Cluster backups
+    context.insert("cluster_backup_enabled", &kubernetes.advanced_settings().cluster_backup_enabled);
+    context.insert("cluster_backups_bucket_name", &kubernetes.backups_bucket_name());
+
     // EKS Addons
     // CNI
     context.insert(
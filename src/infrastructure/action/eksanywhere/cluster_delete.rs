@@ -0,0 +1,66 @@
+use crate::errors::EngineError;
+use crate::events::InfrastructureStep;
+use crate::events::Stage::Infrastructure;
+use crate::infrastructure::action::InfraLogger;
+use crate::infrastructure::action::delete_kube_apps::{KubeAppsDeletionScope, delete_kube_apps};
+use crate::infrastructure::action::deploy_helms::{HelmInfraContext, HelmInfraResources};
+use crate::infrastructure::action::eksanywhere::helm_charts::EksAnywhereHelmsDeployment;
+use crate::infrastructure::action::kubeconfig_helper::write_kubeconfig_on_disk;
+use crate::infrastructure::infrastructure_context::InfrastructureContext;
+use crate::infrastructure::models::kubernetes::Kubernetes;
+use crate::infrastructure::models::kubernetes::eksanywhere::EksAnywhere;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+// VPA CRDs are shipped in the chart `crds` folder, so helm doesn't remove them on uninstall
+const QOVERY_INSTALLED_CRDS: &[&str] = &[
+    "verticalpodautoscalers.autoscaling.k8s.io",
+    "verticalpodautoscalercheckpoints.autoscaling.k8s.io",
+];
+
+pub(super) fn delete_eks_anywhere_charts(
+    cluster: &EksAnywhere,
+    infra_ctx: &InfrastructureContext,
+    logger: impl InfraLogger,
+) -> Result<(), Box<EngineError>> {
+    let event_details = cluster.get_event_details(Infrastructure(InfrastructureStep::Delete));
+
+    logger.info("Uninstalling Qovery charts from Eks Anywhere cluster.");
+
+    write_kubeconfig_on_disk(
+        &cluster.kubeconfig_local_file_path(),
+        &cluster.kubeconfig,
+        cluster.get_event_details(Infrastructure(InfrastructureStep::LoadConfiguration)),
+    )?;
+
+    // Releases to uninstall are the ones the install deploys
+    let helms_deployments = EksAnywhereHelmsDeployment::new(
+        HelmInfraContext::new(
+            tera::Context::new(),
+            PathBuf::from(infra_ctx.context().lib_root_dir()),
+            cluster.template_directory.clone(),
+            cluster.temp_dir().join("helms"),
+            event_details.clone(),
+            vec![],
+            cluster.context().is_dry_run_deploy(),
+        ),
+        cluster,
+    );
+    let releases = helms_deployments.deployed_releases(infra_ctx)?;
+
+    // The cluster itself is owned by the user, only what Qovery installed on it is removed
+    delete_kube_apps(
+        cluster,
+        infra_ctx,
+        event_details,
+        &logger,
+        HashSet::with_capacity(0),
+        KubeAppsDeletionScope::QoveryStackOnly {
+            releases,
+            crds: QOVERY_INSTALLED_CRDS,
+        },
+    )?;
+
+    logger.info("Qovery charts uninstalled successfully.");
+    Ok(())
+}
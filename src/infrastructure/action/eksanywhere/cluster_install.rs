@@ -5,6 +5,7 @@ use crate::infrastructure::action::InfraLogger;
 use crate::infrastructure::action::deploy_helms::{HelmInfraContext, HelmInfraResources};
 use crate::infrastructure::action::eksanywhere::helm_charts::EksAnywhereHelmsDeployment;
 use crate::infrastructure::action::kubeconfig_helper::write_kubeconfig_on_disk;
+use crate::infrastructure::action::qovery_agents_pause::resume_qovery_agents;
 use crate::infrastructure::infrastructure_context::InfrastructureContext;
 use crate::infrastructure::models::kubernetes::Kubernetes;
 use crate::infrastructure::models::kubernetes::eksanywhere::EksAnywhere;
@@ -39,5 +40,8 @@ pub(super) fn install_eks_anywhere_charts(
     );
     helms_deployments.deploy_charts(infra_ctx, &logger)?;
 
+    // If the cluster has been paused, restore Qovery agents
+    resume_qovery_agents(cluster, infra_ctx, &logger)?;
+
     Ok(())
 }
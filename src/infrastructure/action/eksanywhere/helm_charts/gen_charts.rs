@@ -64,6 +64,7 @@ pub(super) fn eks_anywhere_helm_charts(
         chart_config_prerequisites.cluster_id.to_string(),
         UpdateStrategy::RollingUpdate,
         true,
+        false,
         HelmChartNamespaces::Qovery,
     )
     .to_common_helm_chart()?;
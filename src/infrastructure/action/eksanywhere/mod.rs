@@ -1,16 +1,18 @@
+mod cluster_delete;
 mod cluster_install;
 mod helm_charts;
 
 use crate::errors::EngineError;
 use crate::events::InfrastructureStep;
-use crate::events::Stage::Infrastructure;
-use crate::infrastructure::action::InfrastructureAction;
+use crate::infrastructure::action::eksanywhere::cluster_delete::delete_eks_anywhere_charts;
 use crate::infrastructure::action::eksanywhere::cluster_install::install_eks_anywhere_charts;
+use crate::infrastructure::action::qovery_agents_pause::pause_qovery_agents;
 use crate::infrastructure::action::utils::mk_logger;
+use crate::infrastructure::action::{InfraLogger, InfrastructureAction};
 use crate::infrastructure::infrastructure_context::InfrastructureContext;
 use crate::infrastructure::models::cloud_provider::service::Action;
 use crate::infrastructure::models::kubernetes::eksanywhere::EksAnywhere;
-use crate::infrastructure::models::kubernetes::{Kubernetes, KubernetesUpgradeStatus, send_progress_on_long_task};
+use crate::infrastructure::models::kubernetes::{KubernetesUpgradeStatus, send_progress_on_long_task};
 
 impl InfrastructureAction for EksAnywhere {
     fn create_cluster(
@@ -22,25 +24,27 @@ impl InfrastructureAction for EksAnywhere {
         send_progress_on_long_task(self, Action::Create, || install_eks_anywhere_charts(self, infra_ctx, logger))
     }
 
-    fn pause_cluster(&self, _infra_ctx: &InfrastructureContext) -> Result<(), Box<EngineError>> {
-        Err(Box::new(EngineError::new_cannot_restart_kubernetes_cluster(
-            self.get_event_details(Infrastructure(InfrastructureStep::PauseError)),
-        )))
+    fn pause_cluster(&self, infra_ctx: &InfrastructureContext) -> Result<(), Box<EngineError>> {
+        let logger = mk_logger(infra_ctx.kubernetes(), InfrastructureStep::Pause);
+        send_progress_on_long_task(self, Action::Pause, || pause_qovery_agents(self, infra_ctx, &logger))
     }
 
-    fn delete_cluster(&self, _infra_ctx: &InfrastructureContext) -> Result<(), Box<EngineError>> {
-        Err(Box::new(EngineError::new_cannot_restart_kubernetes_cluster(
-            self.get_event_details(Infrastructure(InfrastructureStep::DeleteError)),
-        )))
+    fn delete_cluster(&self, infra_ctx: &InfrastructureContext) -> Result<(), Box<EngineError>> {
+        let logger = mk_logger(infra_ctx.kubernetes(), InfrastructureStep::Delete);
+        send_progress_on_long_task(self, Action::Delete, || delete_eks_anywhere_charts(self, infra_ctx, logger))
     }
 
     fn upgrade_cluster(
         &self,
-        _infra_ctx: &InfrastructureContext,
-        _kubernetes_upgrade_status: KubernetesUpgradeStatus,
+        infra_ctx: &InfrastructureContext,
+        kubernetes_upgrade_status: KubernetesUpgradeStatus,
     ) -> Result<(), Box<EngineError>> {
-        Err(Box::new(EngineError::new_cannot_restart_kubernetes_cluster(
-            self.get_event_details(Infrastructure(InfrastructureStep::UpgradeError)),
-        )))
+        // Kubernetes version is managed by the user, Qovery charts are upgraded when the cluster is created right after
+        let logger = mk_logger(infra_ctx.kubernetes(), InfrastructureStep::Upgrade);
+        logger.warn(format!(
+            "Kubernetes version of this cluster is managed by you, upgrade it to {} on your side.",
+            kubernetes_upgrade_status.requested_version
+        ));
+        Ok(())
     }
 }
@@ -0,0 +1,221 @@
+use crate::engine_task::qovery_api::QoveryApi;
+use crate::errors::{CommandError, EngineError};
+use crate::events::EventDetails;
+use crate::events::InfrastructureStep;
+use crate::events::Stage::Infrastructure;
+use crate::infrastructure::action::InfraLogger;
+use crate::infrastructure::infrastructure_context::InfrastructureContext;
+use crate::infrastructure::models::kubernetes::Kubernetes;
+use crate::io_models::engine_request::EnvironmentsCostsRequest;
+use crate::services::cost_allocation_service::{CostAllocationService, CostAllocationServiceError, EnvironmentCost};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Computes the costs of the cluster environments with OpenCost, and reports them to the Qovery API
+pub(super) fn report_environments_costs(
+    kubernetes: &dyn Kubernetes,
+    infra_ctx: &InfrastructureContext,
+    request: &EnvironmentsCostsRequest,
+    logger: &impl InfraLogger,
+) -> Result<(), Box<EngineError>> {
+    let event_details = kubernetes.get_event_details(Infrastructure(InfrastructureStep::EnvironmentsCosts));
+    if !kubernetes.advanced_settings().opencost_enabled {
+        return Err(Box::new(EngineError::new_environments_costs_error(
+            event_details,
+            CommandError::new_from_safe_message(
+                "OpenCost is not installed on this cluster, enable `cost.opencost.enabled` in its advanced settings"
+                    .to_string(),
+            ),
+        )));
+    }
+
+    let cost_allocation_service = CostAllocationService::new(
+        kubernetes.kubeconfig_local_file_path(),
+        infra_ctx
+            .cloud_provider()
+            .credentials_environment_variables()
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+    );
+    let reported_environments = send_environments_costs(
+        |window| cost_allocation_service.get_environments_costs(window),
+        kubernetes.context().qovery_api.as_ref(),
+        kubernetes.long_id(),
+        request,
+        &event_details,
+    )?;
+    logger.info(format!(
+        "Costs of {reported_environments} environment(s) over the last {}h have been reported.",
+        request.window_in_hours
+    ));
+
+    Ok(())
+}
+
+/// Sends the environments costs to the Qovery API, and returns the number of environments reported
+fn send_environments_costs(
+    get_environments_costs: impl FnOnce(Duration) -> Result<Vec<EnvironmentCost>, CostAllocationServiceError>,
+    qovery_api: &dyn QoveryApi,
+    cluster_id: &Uuid,
+    request: &EnvironmentsCostsRequest,
+    event_details: &EventDetails,
+) -> Result<usize, Box<EngineError>> {
+    let environments_costs = get_environments_costs(Duration::from_secs(u64::from(request.window_in_hours) * 3600))
+        .map_err(|e| {
+            Box::new(EngineError::new_environments_costs_error(
+                event_details.clone(),
+                CommandError::new(
+                    "Cannot get environments costs from OpenCost".to_string(),
+                    Some(e.to_string()),
+                    None,
+                ),
+            ))
+        })?;
+
+    qovery_api
+        .update_environments_costs(cluster_id, &environments_costs)
+        .map_err(|e| {
+            Box::new(EngineError::new_environments_costs_error(
+                event_details.clone(),
+                CommandError::new(
+                    "Cannot send environments costs to Qovery API".to_string(),
+                    Some(e.to_string()),
+                    None,
+                ),
+            ))
+        })?;
+
+    Ok(environments_costs.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine_task::qovery_api::EngineServiceType;
+    use crate::events::{Stage, Transmitter};
+    use crate::infrastructure::action::cluster_outputs_helper::ClusterOutputsRequest;
+    use crate::infrastructure::models::cloud_provider::service::ServiceType;
+    use crate::io_models::QoveryIdentifier;
+    use crate::io_models::application::GitCredentials;
+    use crate::io_models::environment::EnvironmentRequest;
+    use chrono::Utc;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct CostsRecorderQoveryApi {
+        reported_costs: Mutex<Vec<(Uuid, Vec<EnvironmentCost>)>>,
+    }
+
+    impl QoveryApi for CostsRecorderQoveryApi {
+        fn service_version(&self, _service_type: EngineServiceType) -> anyhow::Result<String> {
+            Err(anyhow::anyhow!("not implemented"))
+        }
+
+        fn git_token(&self, _service_type: ServiceType, _service_id: &Uuid) -> anyhow::Result<GitCredentials> {
+            Err(anyhow::anyhow!("not implemented"))
+        }
+
+        fn update_cluster_outputs(&self, _cluster_outputs_request: &ClusterOutputsRequest) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn environment_request(
+            &self,
+            _organization_id: &Uuid,
+            _environment_id: &Uuid,
+        ) -> anyhow::Result<EnvironmentRequest> {
+            Err(anyhow::anyhow!("not implemented"))
+        }
+
+        fn update_environments_costs(
+            &self,
+            cluster_id: &Uuid,
+            environments_costs: &[EnvironmentCost],
+        ) -> anyhow::Result<()> {
+            self.reported_costs
+                .lock()
+                .unwrap()
+                .push((*cluster_id, environments_costs.to_vec()));
+            Ok(())
+        }
+    }
+
+    fn event_details() -> EventDetails {
+        EventDetails::new(
+            None,
+            QoveryIdentifier::default(),
+            QoveryIdentifier::default(),
+            "".to_string(),
+            Stage::Infrastructure(InfrastructureStep::EnvironmentsCosts),
+            Transmitter::Kubernetes(Uuid::new_v4(), "".to_string()),
+        )
+    }
+
+    fn environment_cost(environment_id: Uuid) -> EnvironmentCost {
+        EnvironmentCost {
+            environment_id,
+            window_start: Utc::now(),
+            window_end: Utc::now(),
+            cpu_cost: 1.5,
+            gpu_cost: 0.0,
+            ram_cost: 0.5,
+            pv_cost: 0.0,
+            network_cost: 0.0,
+            load_balancer_cost: 0.0,
+            total_cost: 2.0,
+        }
+    }
+
+    #[test]
+    fn test_send_environments_costs_reports_costs_of_the_requested_window() {
+        // setup:
+        let qovery_api = CostsRecorderQoveryApi::default();
+        let cluster_id = Uuid::new_v4();
+        let environments_costs = vec![environment_cost(Uuid::new_v4()), environment_cost(Uuid::new_v4())];
+        let mut requested_window = None;
+
+        // execute:
+        let reported_environments = send_environments_costs(
+            |window| {
+                requested_window = Some(window);
+                Ok(environments_costs.clone())
+            },
+            &qovery_api,
+            &cluster_id,
+            &EnvironmentsCostsRequest { window_in_hours: 24 },
+            &event_details(),
+        );
+
+        // verify:
+        assert_eq!(reported_environments.unwrap(), 2);
+        assert_eq!(requested_window, Some(Duration::from_secs(24 * 3600)));
+        assert_eq!(
+            *qovery_api.reported_costs.lock().unwrap(),
+            vec![(cluster_id, environments_costs)]
+        );
+    }
+
+    #[test]
+    fn test_send_environments_costs_reports_nothing_when_opencost_fails() {
+        // setup:
+        let qovery_api = CostsRecorderQoveryApi::default();
+
+        // execute:
+        let result = send_environments_costs(
+            |_| {
+                Err(CostAllocationServiceError::CannotGetAllocation {
+                    raw_error_message: "service unavailable".to_string(),
+                })
+            },
+            &qovery_api,
+            &Uuid::new_v4(),
+            &EnvironmentsCostsRequest { window_in_hours: 24 },
+            &event_details(),
+        );
+
+        // verify:
+        assert!(result.is_err());
+        assert!(qovery_api.reported_costs.lock().unwrap().is_empty());
+    }
+}
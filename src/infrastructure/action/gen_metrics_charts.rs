@@ -400,6 +400,7 @@ mod tests {
             metrics_parameters: None,
             aws_iam_eks_prometheus_role_arn: "".to_string(),
             aws_s3_prometheus_bucket_name: "".to_string(),
+            aws_iam_velero_role_arn: "".to_string(),
             karpenter_controller_aws_role_arn: "".to_string(),
             cluster_security_group_id: "".to_string(),
             aws_iam_alb_controller_arn: "".to_string(),
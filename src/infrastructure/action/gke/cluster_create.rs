@@ -1,10 +1,12 @@
 use crate::errors::EngineError;
 use crate::events::Stage::Infrastructure;
 use crate::events::{EventDetails, InfrastructureStep};
+use crate::infrastructure::action::cluster_backup::check_cluster_backup_support;
 use crate::infrastructure::action::cluster_outputs_helper::update_cluster_outputs;
 use crate::infrastructure::action::deploy_helms::{HelmInfraContext, HelmInfraResources};
 use crate::infrastructure::action::deploy_terraform::TerraformInfraResources;
 use crate::infrastructure::action::gke::GkeQoveryTerraformOutput;
+use crate::infrastructure::action::gke::cluster_pause::resume_gke_workloads;
 use crate::infrastructure::action::gke::helm_charts::GkeHelmsDeployment;
 use crate::infrastructure::action::kubectl_utils::check_workers_on_create;
 use crate::infrastructure::action::{InfraLogger, ToInfraTeraContext};
@@ -23,6 +25,7 @@ pub(super) fn create_gke_cluster(
     let event_details = cluster.get_event_details(Infrastructure(InfrastructureStep::Create));
 
     logger.info("Preparing GKE cluster deployment.");
+    check_cluster_backup_support(cluster, &event_details)?;
 
     logger.info("Deploying GKE cluster.");
     if let Err(err) = create_object_storage(cluster, &logger, event_details.clone()) {
@@ -66,6 +69,9 @@ pub(super) fn create_gke_cluster(
     );
     helms_deployments.deploy_charts(infra_ctx, &logger)?;
 
+    // If the cluster has been paused, restore its workloads
+    resume_gke_workloads(cluster, infra_ctx, &logger)?;
+
     Ok(())
 }
 
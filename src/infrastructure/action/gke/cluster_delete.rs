@@ -2,7 +2,7 @@ use crate::errors::EngineError;
 use crate::events::Stage::Infrastructure;
 use crate::events::{EventMessage, InfrastructureStep};
 use crate::infrastructure::action::cluster_outputs_helper::update_cluster_outputs;
-use crate::infrastructure::action::delete_kube_apps::{delete_all_pdbs, delete_kube_apps};
+use crate::infrastructure::action::delete_kube_apps::{KubeAppsDeletionScope, delete_all_pdbs, delete_kube_apps};
 use crate::infrastructure::action::deploy_terraform::TerraformInfraResources;
 use crate::infrastructure::action::gke::GkeQoveryTerraformOutput;
 use crate::infrastructure::action::{InfraLogger, ToInfraTeraContext};
@@ -52,7 +52,14 @@ pub(super) fn delete_gke_cluster(
         logger.warn("Cannot delete all PDBs, this is not blocking cluster deletion.");
     }
 
-    delete_kube_apps(cluster, infra_ctx, event_details.clone(), &logger, HashSet::with_capacity(0))?;
+    delete_kube_apps(
+        cluster,
+        infra_ctx,
+        event_details.clone(),
+        &logger,
+        HashSet::with_capacity(0),
+        KubeAppsDeletionScope::AllApps,
+    )?;
 
     logger.info(format!("Deleting Kubernetes cluster {}/{}", cluster.name(), cluster.short_id()));
     tf_resources.delete(&[], &logger)?;
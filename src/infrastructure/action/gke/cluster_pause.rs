@@ -1,16 +1,254 @@
-use crate::errors::EngineError;
+use crate::errors::{CommandError, EngineError};
+use crate::events::Stage::Infrastructure;
+use crate::events::{EventDetails, InfrastructureStep};
 use crate::infrastructure::action::InfraLogger;
+use crate::infrastructure::action::cluster_pause_state::{
+    ClusterPauseState, PausedWorkloadKind, parse_workload_key, workload_key,
+};
+use crate::infrastructure::action::kubectl_utils::check_workloads_on_pause;
 use crate::infrastructure::infrastructure_context::InfrastructureContext;
+use crate::infrastructure::models::kubernetes::Kubernetes;
 use crate::infrastructure::models::kubernetes::gcp::Gke;
+use crate::runtime::block_on;
+use crate::services::kube_client::{QubeClient, SelectK8sResourceBy};
+use k8s_openapi::api::apps::v1::{Deployment, StatefulSet};
+use kube::Api;
+use kube::api::{ApiResource, DynamicObject, GroupVersionKind, ListParams, Patch, PatchParams};
+use serde_json::json;
+
+// Every workload deployed by Qovery for an environment carries this label
+const QOVERY_WORKLOADS_SELECTOR: &str = "qovery.com/environment-id";
 
 pub(super) fn pause_gke_cluster(
     cluster: &Gke,
     infra_ctx: &InfrastructureContext,
     logger: impl InfraLogger,
 ) -> Result<(), Box<EngineError>> {
-    logger.warn("Pausing a GKE cluster is not supported yet. Skipping this step.");
+    let event_details = cluster.get_event_details(Infrastructure(InfrastructureStep::Pause));
+    logger.info("Preparing cluster pause.");
+
     // Configure kubectl to be able to connect to cluster
     let _ = cluster.configure_gcloud_for_cluster(infra_ctx); // TODO(ENG-1802): properly handle this error
+    let kube_client = infra_ctx.mk_kube_client()?;
+    let client = kube_client.client();
+
+    // GKE autopilot owns the node pools of the cluster, they can't be resized to zero like on other providers.
+    // Autopilot releases the nodes it provisioned for the Qovery workloads once they are scaled to zero,
+    // nodes running system components are kept.
+    let mut state = block_on(ClusterPauseState::load(&client, &event_details))?.unwrap_or_default();
+    let selector = SelectK8sResourceBy::LabelsSelector(QOVERY_WORKLOADS_SELECTOR.to_string());
+    for deployment in block_on(kube_client.get_deployments(event_details.clone(), None, selector.clone()))? {
+        let replicas = deployment.status.and_then(|status| status.replicas).unwrap_or(0);
+        if replicas > 0 {
+            // keep counts recorded by a previous pause attempt, workloads may have already been scaled down
+            state
+                .workloads
+                .entry(workload_key(
+                    PausedWorkloadKind::Deployment,
+                    &deployment.metadata.namespace,
+                    &deployment.metadata.name,
+                ))
+                .or_insert(replicas as u32);
+        }
+    }
+    for statefulset in block_on(kube_client.get_statefulsets(event_details.clone(), None, selector))? {
+        let replicas = statefulset.status.and_then(|status| status.replicas).unwrap_or(0);
+        if replicas > 0 {
+            state
+                .workloads
+                .entry(workload_key(
+                    PausedWorkloadKind::Statefulset,
+                    &statefulset.metadata.namespace,
+                    &statefulset.metadata.name,
+                ))
+                .or_insert(replicas as u32);
+        }
+    }
+
+    // KEDA would scale the paused workloads back up, so their autoscalers are paused too.
+    // Those already paused by the user are left aside, to not resume them with the cluster
+    for kind in [PausedWorkloadKind::ScaledObject, PausedWorkloadKind::ScaledJob] {
+        for autoscaler in block_on(list_keda_autoscalers(&client, kind, &event_details))? {
+            let is_paused_by_user = autoscaler
+                .metadata
+                .annotations
+                .as_ref()
+                .is_some_and(|annotations| annotations.keys().any(|key| key.starts_with(KEDA_PAUSED_ANNOTATION)));
+            let key = workload_key(
+                kind,
+                autoscaler.metadata.namespace.as_deref().unwrap_or_default(),
+                autoscaler.metadata.name.as_deref().unwrap_or_default(),
+            );
+            if !is_paused_by_user || state.autoscalers.contains(&key) {
+                state.autoscalers.insert(key);
+            }
+        }
+    }
+
+    // Record replicas before scaling down, so resume can restore them even if pause fails in the middle
+    block_on(state.save(&client, &event_details))?;
+
+    logger.info("Pausing cluster deployment.");
+    pause_autoscalers(&client, &event_details, &state, true)?;
+    scale_workloads(&kube_client, &event_details, &state, |_| 0)?;
+
+    if let Err(e) = check_workloads_on_pause(
+        cluster,
+        infra_ctx.cloud_provider(),
+        &format!("{QOVERY_WORKLOADS_SELECTOR},!job-name"),
+    ) {
+        return Err(Box::new(EngineError::new_k8s_node_not_ready(event_details, e)));
+    };
+
+    logger.info(format!("Kubernetes cluster {} successfully paused", cluster.name()));
+    Ok(())
+}
+
+/// Restore workloads replicas and their autoscalers as they were when the cluster has been paused
+pub(super) fn resume_gke_workloads(
+    cluster: &Gke,
+    infra_ctx: &InfrastructureContext,
+    logger: &impl InfraLogger,
+) -> Result<(), Box<EngineError>> {
+    let event_details = cluster.get_event_details(Infrastructure(InfrastructureStep::Create));
+    let kube_client = infra_ctx.mk_kube_client()?;
+    let client = kube_client.client();
+
+    let Some(state) = block_on(ClusterPauseState::load(&client, &event_details))? else {
+        return Ok(());
+    };
+
+    logger.info("Resuming paused cluster, restoring workloads as they were before pause.");
+    scale_workloads(&kube_client, &event_details, &state, |replicas| replicas)?;
+    pause_autoscalers(&client, &event_details, &state, false)?;
+
+    block_on(ClusterPauseState::clear(&client, &event_details))
+}
+
+// `autoscaling.keda.sh/paused` and `autoscaling.keda.sh/paused-replicas`
+const KEDA_PAUSED_ANNOTATION: &str = "autoscaling.keda.sh/paused";
+
+fn keda_api_resource(kind: PausedWorkloadKind) -> ApiResource {
+    let kind = match kind {
+        PausedWorkloadKind::ScaledJob => "ScaledJob",
+        _ => "ScaledObject",
+    };
+    ApiResource::from_gvk(&GroupVersionKind::gvk("keda.sh", "v1alpha1", kind))
+}
+
+async fn list_keda_autoscalers(
+    client: &kube::Client,
+    kind: PausedWorkloadKind,
+    event_details: &EventDetails,
+) -> Result<Vec<DynamicObject>, Box<EngineError>> {
+    let api: Api<DynamicObject> = Api::all_with(client.clone(), &keda_api_resource(kind));
+    match api.list(&ListParams::default().labels(QOVERY_WORKLOADS_SELECTOR)).await {
+        Ok(autoscalers) => Ok(autoscalers.items),
+        // KEDA is not installed on the cluster
+        Err(kube::Error::Api(e)) if e.code == 404 => Ok(vec![]),
+        Err(e) => Err(Box::new(EngineError::new_k8s_scale_replicas(
+            event_details.clone(),
+            QOVERY_WORKLOADS_SELECTOR.to_string(),
+            "".to_string(),
+            0,
+            CommandError::new("Cannot list KEDA autoscalers".to_string(), Some(e.to_string()), None),
+        ))),
+    }
+}
+
+fn pause_autoscalers(
+    client: &kube::Client,
+    event_details: &EventDetails,
+    state: &ClusterPauseState,
+    paused: bool,
+) -> Result<(), Box<EngineError>> {
+    for key in &state.autoscalers {
+        let Some((kind, namespace, name)) = parse_workload_key(key) else {
+            continue;
+        };
+
+        // A null annotation removes it. Paused scaled objects are kept at zero replicas, paused scaled jobs create no job
+        let (annotation, value) = match kind {
+            PausedWorkloadKind::ScaledJob => (KEDA_PAUSED_ANNOTATION.to_string(), paused.then_some("true")),
+            _ => (format!("{KEDA_PAUSED_ANNOTATION}-replicas"), paused.then_some("0")),
+        };
+        let api: Api<DynamicObject> = Api::namespaced_with(client.clone(), namespace, &keda_api_resource(kind));
+        let patch = json!({ "metadata": { "annotations": { annotation: value } } });
+        match block_on(api.patch(name, &PatchParams::default(), &Patch::Merge(&patch))) {
+            Ok(_) => {}
+            // Deleted since the cluster has been paused
+            Err(kube::Error::Api(e)) if e.code == 404 => {}
+            Err(e) => {
+                return Err(Box::new(EngineError::new_k8s_scale_replicas(
+                    event_details.clone(),
+                    name.to_string(),
+                    namespace.to_string(),
+                    0,
+                    CommandError::new(
+                        format!("Cannot {} KEDA autoscaler", if paused { "pause" } else { "resume" }),
+                        Some(e.to_string()),
+                        None,
+                    ),
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn scale_workloads(
+    kube_client: &QubeClient,
+    event_details: &EventDetails,
+    state: &ClusterPauseState,
+    replicas: impl Fn(u32) -> u32,
+) -> Result<(), Box<EngineError>> {
+    for (key, recorded_replicas) in &state.workloads {
+        let Some((kind, namespace, name)) = parse_workload_key(key) else {
+            continue;
+        };
+
+        // Workloads deleted since the cluster has been paused are skipped
+        let exists = match kind {
+            PausedWorkloadKind::Deployment => {
+                block_on(Api::<Deployment>::namespaced(kube_client.client(), namespace).get_opt(name))
+                    .map(|deployment| deployment.is_some())
+            }
+            PausedWorkloadKind::Statefulset => {
+                block_on(Api::<StatefulSet>::namespaced(kube_client.client(), namespace).get_opt(name))
+                    .map(|statefulset| statefulset.is_some())
+            }
+            PausedWorkloadKind::ScaledObject | PausedWorkloadKind::ScaledJob => continue,
+        }
+        .map_err(|e| {
+            Box::new(EngineError::new_k8s_scale_replicas(
+                event_details.clone(),
+                name.to_string(),
+                namespace.to_string(),
+                replicas(*recorded_replicas),
+                CommandError::new("Cannot get paused workload".to_string(), Some(e.to_string()), None),
+            ))
+        })?;
+        if !exists {
+            continue;
+        }
+
+        match kind {
+            PausedWorkloadKind::Deployment => block_on(kube_client.set_deployment_replicas_number(
+                event_details.clone(),
+                name,
+                namespace,
+                replicas(*recorded_replicas),
+            ))?,
+            PausedWorkloadKind::Statefulset => block_on(kube_client.set_statefulset_replicas_number(
+                event_details.clone(),
+                name,
+                namespace,
+                replicas(*recorded_replicas),
+            ))?,
+            PausedWorkloadKind::ScaledObject | PausedWorkloadKind::ScaledJob => {}
+        }
+    }
 
     Ok(())
 }
@@ -7,12 +7,16 @@ use crate::infrastructure::action::deploy_helms::mk_customer_chart_override_fn;
 use crate::infrastructure::action::gen_metrics_charts::{CloudProviderMetricsConfig, generate_metrics_config};
 use crate::infrastructure::helm_charts::cert_manager_chart::CertManagerChart;
 use crate::infrastructure::helm_charts::cert_manager_config_chart::CertManagerConfigsChart;
+use crate::infrastructure::helm_charts::envoy_gateway_chart::EnvoyGatewayChart;
+use crate::infrastructure::helm_charts::envoy_gateway_config_chart::EnvoyGatewayConfigsChart;
 use crate::infrastructure::helm_charts::external_dns_chart::ExternalDNSChart;
 use crate::infrastructure::helm_charts::k8s_event_logger::K8sEventLoggerChart;
+use crate::infrastructure::helm_charts::keda_chart::KedaChart;
 use crate::infrastructure::helm_charts::loki_chart::{
     GCSLokiChartConfiguration, LokiChart, LokiObjectBucketConfiguration,
 };
 use crate::infrastructure::helm_charts::nginx_ingress_chart::{NginxIngressChart, NginxOptions};
+use crate::infrastructure::helm_charts::opencost_chart::OpenCostChart;
 use crate::infrastructure::helm_charts::promtail_chart::PromtailChart;
 use crate::infrastructure::helm_charts::qovery_cert_manager_webhook_chart::QoveryCertManagerWebhookChart;
 use crate::infrastructure::helm_charts::qovery_cluster_agent_chart::QoveryClusterAgentChart;
@@ -25,6 +29,7 @@ use crate::infrastructure::helm_charts::{
 };
 use crate::infrastructure::models::cloud_provider::Kind;
 use crate::infrastructure::models::cloud_provider::Kind as CloudProviderKind;
+use crate::infrastructure::models::cloud_provider::io::IngressImplementation;
 use crate::infrastructure::models::dns_provider::DnsProviderConfiguration;
 use crate::infrastructure::models::kubernetes::Kind as KubernetesKind;
 use crate::io_models::QoveryIdentifier;
@@ -56,6 +61,23 @@ pub(super) fn gke_helm_charts(
         chart_config_prerequisites.cluster_long_id.to_string().as_str(),
     )?;
 
+    // OpenCost, workloads costs computed from the metrics stack
+    let opencost: Option<Box<dyn HelmChart>> = match (
+        chart_config_prerequisites.cluster_advanced_settings.opencost_enabled,
+        &metrics_config.metrics_query_url,
+    ) {
+        (true, Some(metrics_query_url)) => Some(Box::new(
+            OpenCostChart::new(
+                chart_prefix_path,
+                chart_config_prerequisites.cluster_long_id.to_string(),
+                metrics_query_url.to_string(),
+                get_chart_override_fn.clone(),
+            )
+            .to_common_helm_chart()?,
+        )),
+        _ => None,
+    };
+
     // Qovery storage class
     let q_storage_class_chart = QoveryStorageClassChart::new(
         chart_prefix_path,
@@ -89,6 +111,10 @@ pub(super) fn gke_helm_charts(
         chart_config_prerequisites.cluster_id.to_string(),
         UpdateStrategy::RollingUpdate,
         true,
+        chart_config_prerequisites
+            .cluster_advanced_settings
+            .ingress_implementation
+            .is_gateway_api(),
         HelmChartNamespaces::Qovery,
     )
     .to_common_helm_chart()?;
@@ -156,6 +182,10 @@ pub(super) fn gke_helm_charts(
         UpdateStrategy::RollingUpdate,
         get_chart_override_fn.clone(),
         true,
+        chart_config_prerequisites
+            .cluster_advanced_settings
+            .ingress_implementation
+            .is_gateway_api(),
         HelmChartNamespaces::Qovery,
         HelmChartNamespaces::Qovery, // Leader election defaults to kube-system which is not permitted on GKE autopilot
     )
@@ -335,6 +365,43 @@ pub(super) fn gke_helm_charts(
     )
     .to_common_helm_chart()?;
 
+    // KEDA, event driven autoscaling for services
+    let keda: Option<Box<dyn HelmChart>> = match chart_config_prerequisites.cluster_advanced_settings.keda_enabled {
+        false => None,
+        true => Some(Box::new(
+            KedaChart::new(
+                chart_prefix_path,
+                HelmChartNamespaces::Keda,
+                chart_config_prerequisites.metrics_parameters.is_some(),
+                get_chart_override_fn.clone(),
+            )
+            .to_common_helm_chart()?,
+        )),
+    };
+
+    // Envoy Gateway, routers are exposed through Gateway API instead of nginx ingress
+    let (envoy_gateway, envoy_gateway_configs): (Option<Box<dyn HelmChart>>, Option<Box<dyn HelmChart>>) =
+        match chart_config_prerequisites
+            .cluster_advanced_settings
+            .ingress_implementation
+        {
+            IngressImplementation::EnvoyGateway => (
+                Some(Box::new(
+                    EnvoyGatewayChart::new(
+                        chart_prefix_path,
+                        HelmChartNamespaces::EnvoyGateway,
+                        get_chart_override_fn.clone(),
+                    )
+                    .to_common_helm_chart()?,
+                )),
+                Some(Box::new(
+                    EnvoyGatewayConfigsChart::new(chart_prefix_path, HelmChartNamespaces::EnvoyGateway)
+                        .to_common_helm_chart()?,
+                )),
+            ),
+            IngressImplementation::Nginx | IngressImplementation::Cilium => (None, None),
+        };
+
     // Qovery shell agent
     let qovery_shell_agent = QoveryShellAgentChart::new(
         chart_prefix_path,
@@ -377,10 +444,12 @@ pub(super) fn gke_helm_charts(
     let level_2: Vec<Option<Box<dyn HelmChart>>> = vec![loki, thanos_chart];
     let level_3: Vec<Option<Box<dyn HelmChart>>> = vec![Some(Box::new(cert_manager))];
     let level_4: Vec<Option<Box<dyn HelmChart>>> = vec![qovery_cert_manager_webhook];
-    let level_5: Vec<Option<Box<dyn HelmChart>>> = vec![Some(Box::new(external_dns_chart))];
+    let level_5: Vec<Option<Box<dyn HelmChart>>> =
+        vec![Some(Box::new(external_dns_chart)), keda, opencost, envoy_gateway];
     let level_6: Vec<Option<Box<dyn HelmChart>>> = vec![Some(Box::new(nginx_ingress))];
     let level_7: Vec<Option<Box<dyn HelmChart>>> = vec![
         Some(Box::new(cert_manager_config)),
+        envoy_gateway_configs,
         Some(Box::new(qovery_cluster_agent)),
         Some(Box::new(qovery_shell_agent)),
         Some(Box::new(k8s_event_logger)),
@@ -24,6 +24,7 @@ fn gke_tera_context(cluster: &Gke, infra_ctx: &InfrastructureContext) -> Result<
     // Qovery
     context.insert("organization_id", infra_ctx.context().organization_short_id());
     context.insert("organization_long_id", &infra_ctx.context().organization_long_id().to_string());
+    context.insert("cost_allocation_tags", &cluster.cost_allocation_tags().to_tags());
     context.insert("object_storage_kubeconfig_bucket", &cluster.kubeconfig_bucket_name());
     context.insert("object_storage_logs_bucket", &cluster.logs_bucket_name());
     // Qovery features
@@ -114,6 +115,17 @@ fn gke_tera_context(cluster: &Gke, infra_ctx: &InfrastructureContext) -> Result<
         None => "".to_string(),
     };
     context.insert("cluster_maintenance_end_time", cluster_maintenance_end_time.as_str()); // RFC3339 https://www.ietf.org/rfc/rfc3339.txt
+    context.insert("cluster_maintenance_recurrence", "");
+    // The cluster maintenance window takes precedence over GKE daily maintenance options
+    if let Some(maintenance_window) = cluster.advanced_settings().maintenance_window() {
+        let first_opening = maintenance_window.first_opening();
+        context.insert("cluster_maintenance_start_time", &first_opening.to_rfc3339());
+        context.insert(
+            "cluster_maintenance_end_time",
+            &(first_opening + maintenance_window.duration()).to_rfc3339(),
+        );
+        context.insert("cluster_maintenance_recurrence", &maintenance_window.to_rrule());
+    }
 
     // Network
     // VPC
@@ -5,7 +5,7 @@ use crate::infrastructure::models::cloud_provider::CloudProvider;
 use crate::infrastructure::models::cloud_provider::service::Action;
 use crate::infrastructure::models::kubernetes::{
     Kubernetes, KubernetesVersion, check_master_version_status, check_workers_pause, check_workers_status,
-    check_workers_upgrade_status, send_progress_on_long_task,
+    check_workers_upgrade_status, check_workloads_pause, send_progress_on_long_task,
 };
 
 pub fn check_workers_on_upgrade(
@@ -66,6 +66,20 @@ pub fn check_workers_on_pause(
     })
 }
 
+pub fn check_workloads_on_pause(
+    kube: &dyn Kubernetes,
+    cloud_provider: &dyn CloudProvider,
+    pod_selector: &str,
+) -> Result<(), CommandError> {
+    send_progress_on_long_task(kube, Action::Pause, || {
+        check_workloads_pause(
+            kube.kubeconfig_local_file_path(),
+            cloud_provider.credentials_environment_variables(),
+            pod_selector,
+        )
+    })
+}
+
 pub fn delete_crashlooping_pods(
     kube: &dyn Kubernetes,
     namespace: Option<&str>,
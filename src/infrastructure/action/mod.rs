@@ -1,14 +1,19 @@
 mod azure;
+mod cluster_backup;
 pub mod cluster_outputs_helper;
+mod cluster_pause_state;
 mod delete_kube_apps;
 mod deploy_helms;
 mod deploy_terraform;
 mod eks;
 mod eksanywhere;
+mod environments_costs;
 mod gen_metrics_charts;
 mod gke;
 pub(super) mod kubeconfig_helper;
 mod kubectl_utils;
+mod node_drain;
+mod qovery_agents_pause;
 mod scaleway;
 mod self_managed;
 mod utils;
@@ -19,9 +24,13 @@ use crate::events::{EngineEvent, EventDetails, EventMessage, InfrastructureDiffT
 use crate::infrastructure::action::utils::mk_logger;
 use crate::infrastructure::infrastructure_context::InfrastructureContext;
 use crate::infrastructure::models::cloud_provider::service::Action;
-use crate::infrastructure::models::kubernetes::{KubernetesUpgradeStatus, is_kubernetes_upgrade_required};
+use crate::infrastructure::models::kubernetes::{
+    KubernetesUpgradeStatus, is_kubernetes_upgrade_required, send_progress_on_long_task,
+};
+use crate::io_models::engine_request::{ClusterBackupRestore, EnvironmentsCostsRequest};
 use crate::logger::Logger;
 use crate::services::kubernetes_api_deprecation_service::KubernetesApiDeprecationServiceGranuality;
+use chrono::Utc;
 use tera::Context as TeraContext;
 
 pub trait InfrastructureAction: Send + Sync {
@@ -52,7 +61,6 @@ pub trait InfrastructureAction: Send + Sync {
         };
         let logger = mk_logger(infra_ctx.kubernetes(), step);
         let kubernetes = infra_ctx.kubernetes();
-        let cloud_provider = infra_ctx.cloud_provider();
         if infra_ctx.context().is_dry_run_deploy() {
             logger.warn("👻 Dry run mode is enabled. No changes will be made to the infrastructure");
         }
@@ -70,32 +78,8 @@ pub trait InfrastructureAction: Send + Sync {
                 if infra_ctx.context().is_first_cluster_deployment() {
                     self.bootstap_cluster(infra_ctx)?;
                 } else if let Some(upgrade_status) = self.is_upgrade_required(infra_ctx) {
-                    let kube_client = infra_ctx.mk_kube_client()?;
-                    let event_details = kubernetes.get_event_details(Infrastructure(InfrastructureStep::Upgrade));
-
-                    logger.info("Check if cluster has no calls to deprecated kubernetes API in next version");
-                    match infra_ctx
-                        .kubernetes_api_deprecation_service()
-                        .is_cluster_fully_compatible_with_kubernetes_version(
-                            kubernetes.kubeconfig_local_file_path().as_path(),
-                            Some(&upgrade_status.requested_version),
-                            &cloud_provider.credentials_environment_variables(),
-                            KubernetesApiDeprecationServiceGranuality::WithQoveryMetadata {
-                                kube_client: kube_client.as_ref(),
-                            },
-                        ) {
-                        Ok(_) => logger.info("Cluster is compatible with the next version"),
-                        Err(e) => {
-                            return Err(Box::new(EngineError::new_k8s_deprecated_api_calls_found_error(
-                                event_details.clone(),
-                                &upgrade_status.requested_version,
-                                e,
-                            )));
-                        }
-                    }
-
                     cluster_has_been_upgraded = true;
-                    self.upgrade_cluster(infra_ctx, upgrade_status)?;
+                    self.upgrade_cluster_if_compatible(infra_ctx, upgrade_status)?;
                 }
 
                 let cluster = self.create_cluster(infra_ctx, cluster_has_been_upgraded);
@@ -113,9 +97,8 @@ pub trait InfrastructureAction: Send + Sync {
                     match infra_ctx
                         .kubernetes_api_deprecation_service()
                         .is_cluster_fully_compatible_with_kubernetes_version(
-                            kubernetes.kubeconfig_local_file_path().as_path(),
+                            kube_client.as_ref(),
                             Some(&target_kubernetes_version),
-                            &cloud_provider.credentials_environment_variables(),
                             KubernetesApiDeprecationServiceGranuality::WithQoveryMetadata {
                                 kube_client: kube_client.as_ref(),
                             },
@@ -145,6 +128,130 @@ pub trait InfrastructureAction: Send + Sync {
         }
     }
 
+    /// Upgrades the cluster, only if it has no calls to kubernetes APIs deprecated in the requested version
+    fn upgrade_cluster_if_compatible(
+        &self,
+        infra_ctx: &InfrastructureContext,
+        upgrade_status: KubernetesUpgradeStatus,
+    ) -> Result<(), Box<EngineError>> {
+        let logger = mk_logger(infra_ctx.kubernetes(), InfrastructureStep::Upgrade);
+        let kube_client = infra_ctx.mk_kube_client()?;
+        let event_details = infra_ctx
+            .kubernetes()
+            .get_event_details(Infrastructure(InfrastructureStep::Upgrade));
+
+        logger.info("Check if cluster has no calls to deprecated kubernetes API in next version");
+        match infra_ctx
+            .kubernetes_api_deprecation_service()
+            .is_cluster_fully_compatible_with_kubernetes_version(
+                kube_client.as_ref(),
+                Some(&upgrade_status.requested_version),
+                KubernetesApiDeprecationServiceGranuality::WithQoveryMetadata {
+                    kube_client: kube_client.as_ref(),
+                },
+            ) {
+            Ok(_) => logger.info("Cluster is compatible with the next version"),
+            Err(e) => {
+                return Err(Box::new(EngineError::new_k8s_deprecated_api_calls_found_error(
+                    event_details,
+                    &upgrade_status.requested_version,
+                    e,
+                )));
+            }
+        }
+
+        self.upgrade_cluster(infra_ctx, upgrade_status)
+    }
+
+    /// Updates the nodes to the latest images of their kubernetes version.
+    /// By default, nothing is done as the cloud provider rolls the node images during the cluster maintenance window.
+    fn update_nodes_images(&self, _infra_ctx: &InfrastructureContext) -> Result<(), Box<EngineError>> {
+        Ok(())
+    }
+
+    /// Restores a backup made by velero, see [ClusterBackupRestore] for what can be restored
+    fn restore_cluster_backup(
+        &self,
+        infra_ctx: &InfrastructureContext,
+        restore: &ClusterBackupRestore,
+    ) -> Result<(), Box<EngineError>> {
+        let logger = mk_logger(infra_ctx.kubernetes(), InfrastructureStep::Restore);
+        logger.info(format!(
+            "Restoring backup `{}` on {} cluster {}",
+            restore.backup_name,
+            infra_ctx.kubernetes().kind(),
+            infra_ctx.kubernetes().name()
+        ));
+
+        send_progress_on_long_task(infra_ctx.kubernetes(), Action::Create, || {
+            cluster_backup::restore_cluster_backup(infra_ctx.kubernetes(), infra_ctx, restore, &logger)
+        })
+    }
+
+    /// Computes the costs of the cluster environments with OpenCost, and reports them to the Qovery API
+    fn report_environments_costs(
+        &self,
+        infra_ctx: &InfrastructureContext,
+        request: &EnvironmentsCostsRequest,
+    ) -> Result<(), Box<EngineError>> {
+        let logger = mk_logger(infra_ctx.kubernetes(), InfrastructureStep::EnvironmentsCosts);
+        environments_costs::report_environments_costs(infra_ctx.kubernetes(), infra_ctx, request, &logger)
+    }
+
+    /// Applies pending patch upgrades and node images updates, only while the cluster maintenance window is open.
+    /// Minor version upgrades are never applied here, as they may require changes on the user side.
+    fn run_cluster_maintenance(
+        &self,
+        infra_ctx: &InfrastructureContext,
+    ) -> Result<ClusterMaintenanceOutcome, Box<EngineError>> {
+        let logger = mk_logger(infra_ctx.kubernetes(), InfrastructureStep::Maintenance);
+        let Some(maintenance_window) = infra_ctx.kubernetes().advanced_settings().maintenance_window() else {
+            logger.info("⏭️ Maintenance skipped, the cluster has no maintenance window");
+            return Ok(ClusterMaintenanceOutcome::Skipped);
+        };
+
+        let now = Utc::now();
+        if !maintenance_window.is_open_at(now) {
+            logger.info(format!(
+                "⏭️ Maintenance skipped, the maintenance window is closed until {}",
+                maintenance_window.next_opening_after(now).to_rfc3339()
+            ));
+            return Ok(ClusterMaintenanceOutcome::Skipped);
+        }
+
+        logger.info(format!(
+            "Applying maintenance on {} cluster {}",
+            infra_ctx.kubernetes().kind(),
+            infra_ctx.kubernetes().name()
+        ));
+
+        let upgrade_status = self.is_upgrade_required(infra_ctx);
+        if let Some(upgrade_status) = &upgrade_status {
+            let requested_version = &upgrade_status.requested_version;
+            let deployed_version = &upgrade_status.deployed_masters_version;
+            if (&requested_version.major, &requested_version.minor)
+                != (&deployed_version.major, &deployed_version.minor)
+            {
+                logger.warn(format!(
+                    "⏭️ Maintenance skipped, upgrading from {deployed_version} to {requested_version} is not a patch upgrade and must be done by updating the cluster"
+                ));
+                return Ok(ClusterMaintenanceOutcome::Skipped);
+            }
+        }
+
+        let has_been_upgraded = match upgrade_status {
+            Some(upgrade_status) => {
+                self.upgrade_cluster_if_compatible(infra_ctx, upgrade_status)?;
+                true
+            }
+            None => false,
+        };
+        self.create_cluster(infra_ctx, has_been_upgraded)?;
+        self.update_nodes_images(infra_ctx)?;
+
+        Ok(ClusterMaintenanceOutcome::Applied)
+    }
+
     // During upgrade check we may want to exclude some node as not pertinent/managed by us
     // I.e: fargate nodes are managed by karpenter, so we don't want to upgrade them
     fn upgrade_node_selector(&self) -> Option<&str> {
@@ -181,6 +288,13 @@ pub trait InfrastructureAction: Send + Sync {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClusterMaintenanceOutcome {
+    Applied,
+    /// Nothing has been applied, i.e: the maintenance window is closed
+    Skipped,
+}
+
 pub trait ToInfraTeraContext {
     fn to_infra_tera_context(&self, target: &InfrastructureContext) -> Result<TeraContext, Box<EngineError>>;
 }
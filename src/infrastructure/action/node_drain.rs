@@ -0,0 +1,417 @@
+use crate::environment::models::kubernetes::{K8sDeployment, K8sStatefulset};
+use crate::errors::{CommandError, EngineError};
+use crate::events::EventDetails;
+use crate::infrastructure::action::InfraLogger;
+use crate::infrastructure::infrastructure_context::InfrastructureContext;
+use crate::infrastructure::models::kubernetes::{Kubernetes, is_kubernetes_upgradable};
+use crate::runtime::block_on;
+use crate::services::kube_client::{QubeClient, SelectK8sResourceBy};
+use itertools::Itertools;
+use k8s_openapi::api::apps::v1::StatefulSet;
+use k8s_openapi::api::core::v1::{Node, Pod};
+use kube::Api;
+use kube::api::{EvictParams, ListParams};
+use retry::OperationResult;
+use retry::delay::Fixed;
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(60 * 15);
+const DRAIN_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+const DRAIN_PROGRESS_INTERVAL: Duration = Duration::from_secs(60);
+
+pub(super) fn cordon_nodes(
+    kube_client: &QubeClient,
+    event_details: &EventDetails,
+    nodes: &[String],
+    unschedulable: bool,
+) -> Result<(), Box<EngineError>> {
+    let nodes_api: Api<Node> = Api::all(kube_client.client());
+    for node in nodes {
+        let result = match unschedulable {
+            true => block_on(nodes_api.cordon(node)),
+            false => block_on(nodes_api.uncordon(node)),
+        };
+        if let Err(e) = result {
+            return Err(Box::new(EngineError::new_k8s_drain_node_error(
+                event_details.clone(),
+                node.to_string(),
+                CommandError::new("Cannot change node schedulability".to_string(), Some(e.to_string()), None),
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Evicts pods of the given nodes, one node after the other, so pod disruption budgets are respected.
+/// Nodes are expected to be cordoned first, so evicted pods are rescheduled elsewhere.
+pub(super) fn drain_nodes(
+    kubernetes: &dyn Kubernetes,
+    infra_ctx: &InfrastructureContext,
+    event_details: &EventDetails,
+    nodes: &[String],
+    logger: &impl InfraLogger,
+) -> Result<(), Box<EngineError>> {
+    // a budget not satisfied yet would block evictions until the drain timeout
+    is_kubernetes_upgradable(
+        kubernetes.kubeconfig_local_file_path(),
+        infra_ctx.cloud_provider().credentials_environment_variables(),
+        event_details.clone(),
+    )?;
+
+    let client = infra_ctx.mk_kube_client()?.client();
+    for (ix, node) in nodes.iter().enumerate() {
+        logger.info(format!("Draining node `{node}` ({}/{}).", ix + 1, nodes.len()));
+        // progress is reported through the given logger, so under the step the drain is part of
+        block_on(async {
+            let drain = drain_node(&client, node);
+            tokio::pin!(drain);
+            let mut progress = tokio::time::interval_at(
+                tokio::time::Instant::now() + DRAIN_PROGRESS_INTERVAL,
+                DRAIN_PROGRESS_INTERVAL,
+            );
+            loop {
+                tokio::select! {
+                    result = &mut drain => break result,
+                    _ = progress.tick() => {
+                        logger.info(format!("Node `{node}` is still being drained ({}/{})...", ix + 1, nodes.len()))
+                    }
+                }
+            }
+        })
+        .map_err(|e| {
+            Box::new(EngineError::new_k8s_drain_node_error(
+                event_details.clone(),
+                node.to_string(),
+                e,
+            ))
+        })?;
+    }
+
+    Ok(())
+}
+
+async fn drain_node(client: &kube::Client, node: &str) -> Result<(), CommandError> {
+    let pods = evictable_pods(client, node).await?;
+
+    // Statefulset pods are evicted one by one, their volume has to be detached before their replacement can start
+    let (stateful_pods, stateless_pods): (Vec<Pod>, Vec<Pod>) =
+        pods.into_iter().partition(|pod| owner_kind(pod) == Some("StatefulSet"));
+    for pod in &stateless_pods {
+        evict_pod(client, pod).await?;
+    }
+    for pod in &stateful_pods {
+        evict_pod(client, pod).await?;
+        wait_for_statefulset_ready(client, pod).await?;
+    }
+
+    let deadline = tokio::time::Instant::now() + DRAIN_TIMEOUT;
+    while !evictable_pods(client, node).await?.is_empty() {
+        if tokio::time::Instant::now() > deadline {
+            return Err(CommandError::new_from_safe_message(format!(
+                "Pods are still running on node `{node}` after {DRAIN_TIMEOUT:?}"
+            )));
+        }
+        tokio::time::sleep(DRAIN_RETRY_INTERVAL).await;
+    }
+
+    Ok(())
+}
+
+/// Pods which are not managed by the node itself (daemonsets and static pods) and are still running
+async fn evictable_pods(client: &kube::Client, node: &str) -> Result<Vec<Pod>, CommandError> {
+    let pods = Api::<Pod>::all(client.clone())
+        .list(&ListParams::default().fields(&format!("spec.nodeName={node}")))
+        .await
+        .map_err(|e| CommandError::new(format!("Cannot list pods of node `{node}`"), Some(e.to_string()), None))?;
+
+    Ok(pods.items.into_iter().filter(is_evictable).collect())
+}
+
+fn is_evictable(pod: &Pod) -> bool {
+    let is_mirror_pod = pod
+        .metadata
+        .annotations
+        .as_ref()
+        .is_some_and(|annotations| annotations.contains_key("kubernetes.io/config.mirror"));
+    let is_terminated = matches!(
+        pod.status.as_ref().and_then(|status| status.phase.as_deref()),
+        Some("Succeeded") | Some("Failed")
+    );
+
+    owner_kind(pod) != Some("DaemonSet") && !is_mirror_pod && !is_terminated
+}
+
+fn owner_kind(pod: &Pod) -> Option<&str> {
+    pod.metadata
+        .owner_references
+        .as_ref()?
+        .iter()
+        .find(|owner| owner.controller == Some(true))
+        .map(|owner| owner.kind.as_str())
+}
+
+/// Evicts a pod, retrying while a pod disruption budget doesn't allow it
+async fn evict_pod(client: &kube::Client, pod: &Pod) -> Result<(), CommandError> {
+    let (Some(namespace), Some(name)) = (pod.metadata.namespace.as_deref(), pod.metadata.name.as_deref()) else {
+        return Ok(());
+    };
+    let pods_api: Api<Pod> = Api::namespaced(client.clone(), namespace);
+
+    let deadline = tokio::time::Instant::now() + DRAIN_TIMEOUT;
+    loop {
+        match pods_api.evict(name, &EvictParams::default()).await {
+            Ok(_) => return Ok(()),
+            Err(kube::Error::Api(e)) if e.code == 404 => return Ok(()),
+            // 429 is returned when the eviction would violate a pod disruption budget
+            Err(kube::Error::Api(e)) if e.code == 429 && tokio::time::Instant::now() < deadline => {
+                tokio::time::sleep(DRAIN_RETRY_INTERVAL).await
+            }
+            Err(e) => {
+                return Err(CommandError::new(
+                    format!("Cannot evict pod `{namespace}/{name}`"),
+                    Some(e.to_string()),
+                    None,
+                ));
+            }
+        }
+    }
+}
+
+async fn wait_for_statefulset_ready(client: &kube::Client, pod: &Pod) -> Result<(), CommandError> {
+    let Some(namespace) = pod.metadata.namespace.as_deref() else {
+        return Ok(());
+    };
+    let Some(statefulset_name) = pod
+        .metadata
+        .owner_references
+        .as_ref()
+        .and_then(|owners| owners.iter().find(|owner| owner.kind == "StatefulSet"))
+        .map(|owner| owner.name.as_str())
+    else {
+        return Ok(());
+    };
+    let statefulsets_api: Api<StatefulSet> = Api::namespaced(client.clone(), namespace);
+
+    let deadline = tokio::time::Instant::now() + DRAIN_TIMEOUT;
+    loop {
+        // give the controller some time to notice the evicted pod
+        tokio::time::sleep(DRAIN_RETRY_INTERVAL).await;
+        let statefulset = statefulsets_api.get_opt(statefulset_name).await.map_err(|e| {
+            CommandError::new(
+                format!("Cannot get statefulset `{namespace}/{statefulset_name}`"),
+                Some(e.to_string()),
+                None,
+            )
+        })?;
+        let Some(statefulset) = statefulset else {
+            return Ok(());
+        };
+
+        let desired_replicas = statefulset.spec.and_then(|spec| spec.replicas).unwrap_or(1);
+        let ready_replicas = statefulset.status.and_then(|status| status.ready_replicas).unwrap_or(0);
+        if ready_replicas >= desired_replicas {
+            return Ok(());
+        }
+        if tokio::time::Instant::now() > deadline {
+            return Err(CommandError::new_from_safe_message(format!(
+                "Statefulset `{namespace}/{statefulset_name}` is not ready after {DRAIN_TIMEOUT:?}"
+            )));
+        }
+    }
+}
+
+/// Deployments and statefulsets not having all their replicas ready
+pub(super) fn get_not_ready_workloads(
+    kube_client: &QubeClient,
+    event_details: &EventDetails,
+) -> Result<BTreeSet<String>, Box<EngineError>> {
+    let deployments = block_on(kube_client.get_deployments(event_details.clone(), None, SelectK8sResourceBy::All))?;
+    let statefulsets = block_on(kube_client.get_statefulsets(event_details.clone(), None, SelectK8sResourceBy::All))?;
+
+    Ok(not_ready_workloads(&deployments, &statefulsets))
+}
+
+fn not_ready_workloads(deployments: &[K8sDeployment], statefulsets: &[K8sStatefulset]) -> BTreeSet<String> {
+    let not_ready_deployments = deployments
+        .iter()
+        .filter(|deployment| {
+            let status = deployment.status.as_ref();
+            status.and_then(|status| status.ready_replicas).unwrap_or(0)
+                < status.and_then(|status| status.replicas).unwrap_or(0)
+        })
+        .map(|deployment| format!("Deployment `{}/{}`", deployment.metadata.namespace, deployment.metadata.name));
+    let not_ready_statefulsets = statefulsets
+        .iter()
+        .filter(|statefulset| {
+            statefulset
+                .status
+                .as_ref()
+                .is_some_and(|status| status.ready_replicas.unwrap_or(0) < status.replicas)
+        })
+        .map(|statefulset| format!("Statefulset `{}/{}`", statefulset.metadata.namespace, statefulset.metadata.name));
+
+    not_ready_deployments.chain(not_ready_statefulsets).collect()
+}
+
+/// Waits for every deployment and statefulset to have all its replicas ready.
+/// Workloads which were already not ready before nodes were drained are not the drain's doing, so they are ignored
+pub(super) fn check_workloads_health(
+    kube_client: &QubeClient,
+    event_details: &EventDetails,
+    not_ready_before_drain: &BTreeSet<String>,
+) -> Result<(), CommandError> {
+    let result = retry::retry(Fixed::from_millis(10000).take(60), || {
+        let deployments = block_on(kube_client.get_deployments(event_details.clone(), None, SelectK8sResourceBy::All));
+        let statefulsets =
+            block_on(kube_client.get_statefulsets(event_details.clone(), None, SelectK8sResourceBy::All));
+        let (deployments, statefulsets) = match (deployments, statefulsets) {
+            (Ok(deployments), Ok(statefulsets)) => (deployments, statefulsets),
+            (Err(e), _) | (_, Err(e)) => {
+                return OperationResult::Retry(CommandError::new_from_safe_message(e.to_string()));
+            }
+        };
+
+        let not_ready = not_ready_workloads(&deployments, &statefulsets);
+        let newly_not_ready: Vec<&String> = not_ready.difference(not_ready_before_drain).collect();
+        if !newly_not_ready.is_empty() {
+            return OperationResult::Retry(CommandError::new_from_safe_message(format!(
+                "{} not ready.",
+                newly_not_ready.into_iter().join(", ")
+            )));
+        }
+
+        OperationResult::Ok(())
+    });
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(retry::Error { error, .. }) => Err(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_evictable, not_ready_workloads};
+    use crate::environment::models::kubernetes::{
+        K8sDeployment, K8sDeploymentStatus, K8sMetadata, K8sStatefulset, K8sStatefulsetStatus,
+    };
+    use k8s_openapi::api::core::v1::Pod;
+    use serde_json::json;
+    use std::collections::BTreeSet;
+
+    fn metadata(name: &str) -> K8sMetadata {
+        K8sMetadata {
+            name: name.to_string(),
+            namespace: "z1234".to_string(),
+            labels: None,
+            annotations: None,
+            termination_grace_period_seconds: None,
+        }
+    }
+
+    fn deployment(name: &str, replicas: i32, ready_replicas: Option<i32>) -> K8sDeployment {
+        K8sDeployment {
+            metadata: metadata(name),
+            status: Some(K8sDeploymentStatus {
+                replicas: Some(replicas),
+                ready_replicas,
+            }),
+        }
+    }
+
+    fn statefulset(name: &str, replicas: i32, ready_replicas: Option<i32>) -> K8sStatefulset {
+        K8sStatefulset {
+            metadata: metadata(name),
+            status: Some(K8sStatefulsetStatus {
+                replicas,
+                ready_replicas,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_not_ready_workloads() {
+        // setup:
+        let deployments = vec![
+            deployment("app-ready", 2, Some(2)),
+            deployment("app-not-ready", 2, Some(1)),
+            deployment("app-scaled-down", 0, None),
+        ];
+        let statefulsets = vec![
+            statefulset("db-ready", 1, Some(1)),
+            statefulset("db-not-ready", 1, None),
+        ];
+
+        // execute:
+        let not_ready = not_ready_workloads(&deployments, &statefulsets);
+
+        // verify:
+        assert_eq!(
+            BTreeSet::from([
+                "Deployment `z1234/app-not-ready`".to_string(),
+                "Statefulset `z1234/db-not-ready`".to_string(),
+            ]),
+            not_ready
+        );
+    }
+
+    #[test]
+    fn test_not_ready_workloads_compared_to_before_drain() {
+        // setup:
+        let before_drain = not_ready_workloads(&[deployment("app-already-broken", 1, Some(0))], &[]);
+
+        // execute:
+        let after_drain = not_ready_workloads(
+            &[
+                deployment("app-already-broken", 1, Some(0)),
+                deployment("app", 1, Some(0)),
+            ],
+            &[],
+        );
+
+        // verify:
+        assert_eq!(
+            vec!["Deployment `z1234/app`"],
+            after_drain.difference(&before_drain).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_is_evictable() {
+        // setup:
+        let pod = |value: serde_json::Value| serde_json::from_value::<Pod>(value).expect("invalid pod");
+        let test_cases = vec![
+            (
+                pod(json!({"metadata": {"name": "app", "ownerReferences": [
+                    {"apiVersion": "apps/v1", "kind": "ReplicaSet", "name": "app", "uid": "1", "controller": true}
+                ]}, "status": {"phase": "Running"}})),
+                true,
+                "pod of a deployment",
+            ),
+            (
+                pod(json!({"metadata": {"name": "logs", "ownerReferences": [
+                    {"apiVersion": "apps/v1", "kind": "DaemonSet", "name": "logs", "uid": "1", "controller": true}
+                ]}})),
+                false,
+                "pod of a daemonset",
+            ),
+            (
+                pod(json!({"metadata": {"name": "proxy", "annotations": {"kubernetes.io/config.mirror": "abc"}}})),
+                false,
+                "static pod",
+            ),
+            (
+                pod(json!({"metadata": {"name": "job"}, "status": {"phase": "Succeeded"}})),
+                false,
+                "completed pod",
+            ),
+        ];
+
+        for (pod, expected, description) in test_cases {
+            // execute & verify:
+            assert_eq!(expected, is_evictable(&pod), "case: {description}");
+        }
+    }
+}
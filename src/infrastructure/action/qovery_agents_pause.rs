@@ -0,0 +1,89 @@
+use crate::errors::EngineError;
+use crate::events::InfrastructureStep;
+use crate::events::Stage::Infrastructure;
+use crate::helm::HelmChartNamespaces;
+use crate::infrastructure::action::InfraLogger;
+use crate::infrastructure::action::cluster_pause_state::{
+    ClusterPauseState, PausedWorkloadKind, parse_workload_key, workload_key,
+};
+use crate::infrastructure::infrastructure_context::InfrastructureContext;
+use crate::infrastructure::models::kubernetes::Kubernetes;
+use crate::runtime::block_on;
+use crate::services::kube_client::SelectK8sResourceBy;
+
+// Agents connecting the cluster to Qovery, the engine is left aside as it may run within the cluster
+const QOVERY_AGENTS_SELECTOR: &str = "app.kubernetes.io/name in (qovery-cluster-agent,qovery-shell-agent)";
+
+/// Scale Qovery agents down on clusters whose nodes are not managed by Qovery.
+/// Their replicas are recorded so resuming the cluster can restore them.
+pub(super) fn pause_qovery_agents(
+    cluster: &dyn Kubernetes,
+    infra_ctx: &InfrastructureContext,
+    logger: &impl InfraLogger,
+) -> Result<(), Box<EngineError>> {
+    let event_details = cluster.get_event_details(Infrastructure(InfrastructureStep::Pause));
+    let kube_client = infra_ctx.mk_kube_client()?;
+    let client = kube_client.client();
+    let namespace = HelmChartNamespaces::Qovery.to_string();
+
+    let mut state = block_on(ClusterPauseState::load(&client, &event_details))?.unwrap_or_default();
+    let agents = block_on(kube_client.get_deployments(
+        event_details.clone(),
+        Some(&namespace),
+        SelectK8sResourceBy::LabelsSelector(QOVERY_AGENTS_SELECTOR.to_string()),
+    ))?;
+    for agent in &agents {
+        let replicas = agent.status.as_ref().and_then(|status| status.replicas).unwrap_or(0);
+        if replicas > 0 {
+            // keep counts recorded by a previous pause attempt, agents may have already been scaled down
+            state
+                .workloads
+                .entry(workload_key(
+                    PausedWorkloadKind::Deployment,
+                    &agent.metadata.namespace,
+                    &agent.metadata.name,
+                ))
+                .or_insert(replicas as u32);
+        }
+    }
+
+    // Record replicas before scaling down, so resume can restore them even if pause fails in the middle
+    block_on(state.save(&client, &event_details))?;
+
+    for agent in &agents {
+        logger.info(format!("Scaling down Qovery agent `{}`.", agent.metadata.name));
+        block_on(kube_client.set_deployment_replicas_number(
+            event_details.clone(),
+            &agent.metadata.name,
+            &agent.metadata.namespace,
+            0,
+        ))?;
+    }
+
+    Ok(())
+}
+
+/// Restore Qovery agents replicas as they were when the cluster has been paused
+pub(super) fn resume_qovery_agents(
+    cluster: &dyn Kubernetes,
+    infra_ctx: &InfrastructureContext,
+    logger: &impl InfraLogger,
+) -> Result<(), Box<EngineError>> {
+    let event_details = cluster.get_event_details(Infrastructure(InfrastructureStep::Create));
+    let kube_client = infra_ctx.mk_kube_client()?;
+    let client = kube_client.client();
+
+    let Some(state) = block_on(ClusterPauseState::load(&client, &event_details))? else {
+        return Ok(());
+    };
+
+    logger.info("Resuming paused cluster, restoring Qovery agents as they were before pause.");
+    for (key, replicas) in &state.workloads {
+        let Some((PausedWorkloadKind::Deployment, namespace, name)) = parse_workload_key(key) else {
+            continue;
+        };
+        block_on(kube_client.set_deployment_replicas_number(event_details.clone(), name, namespace, *replicas))?;
+    }
+
+    block_on(ClusterPauseState::clear(&client, &event_details))
+}
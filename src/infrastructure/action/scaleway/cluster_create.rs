@@ -1,6 +1,7 @@
 use crate::errors::{CommandError, EngineError, ErrorMessageVerbosity};
 use crate::events::Stage::Infrastructure;
 use crate::events::{EventDetails, InfrastructureStep};
+use crate::infrastructure::action::cluster_backup::create_backups_bucket;
 use crate::infrastructure::action::cluster_outputs_helper::update_cluster_outputs;
 use crate::infrastructure::action::deploy_helms::{HelmInfraContext, HelmInfraResources};
 use crate::infrastructure::action::deploy_terraform::TerraformInfraResources;
@@ -54,6 +55,9 @@ pub fn create_kapsule_cluster(
         return Err(Box::new(error));
     }
 
+    // Cluster backups bucket
+    create_backups_bucket(cluster, &event_details, &logger)?;
+
     // terraform deployment dedicated to cloud resources
     let tera_context = cluster.to_infra_tera_context(infra_ctx)?;
     let tf_action = TerraformInfraResources::new(
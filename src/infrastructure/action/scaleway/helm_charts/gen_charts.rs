@@ -2,8 +2,12 @@ use crate::helm::{
     ChartInfo, ChartSetValue, CommonChart, HelmChart, HelmChartNamespaces, PriorityClass, QoveryPriorityClass,
     UpdateStrategy, get_engine_helm_action_from_location,
 };
+use crate::infrastructure::helm_charts::envoy_gateway_chart::EnvoyGatewayChart;
+use crate::infrastructure::helm_charts::envoy_gateway_config_chart::EnvoyGatewayConfigsChart;
 use crate::infrastructure::helm_charts::k8s_event_logger::K8sEventLoggerChart;
+use crate::infrastructure::helm_charts::keda_chart::KedaChart;
 use crate::infrastructure::helm_charts::nginx_ingress_chart::{NginxIngressChart, NginxOptions};
+use crate::infrastructure::helm_charts::opencost_chart::OpenCostChart;
 use crate::infrastructure::helm_charts::promtail_chart::PromtailChart;
 use crate::infrastructure::helm_charts::qovery_shell_agent_chart::QoveryShellAgentChart;
 use crate::infrastructure::helm_charts::qovery_storage_class_chart::{QoveryStorageClassChart, QoveryStorageType};
@@ -13,7 +17,9 @@ use crate::infrastructure::helm_charts::{
     ToCommonHelmChart,
 };
 use crate::infrastructure::models::cloud_provider::Kind;
+use crate::infrastructure::models::cloud_provider::io::IngressImplementation;
 use crate::infrastructure::models::kubernetes::Kind as KubernetesKind;
+use crate::infrastructure::models::kubernetes::backups_bucket_name;
 use crate::io_models::models::{KubernetesCpuResourceUnit, KubernetesMemoryResourceUnit};
 
 use crate::errors::CommandError;
@@ -34,6 +40,7 @@ use crate::infrastructure::helm_charts::loki_chart::{
 use crate::infrastructure::helm_charts::qovery_cert_manager_webhook_chart::QoveryCertManagerWebhookChart;
 use crate::infrastructure::helm_charts::qovery_cluster_agent_chart::QoveryClusterAgentChart;
 use crate::infrastructure::helm_charts::qovery_priority_class_chart::QoveryPriorityClassChart;
+use crate::infrastructure::helm_charts::velero_chart::{VeleroChart, VeleroStorageConfiguration};
 use crate::io_models::QoveryIdentifier;
 // use crate::io_models::metrics::MetricsConfiguration;
 use crate::infrastructure::action::gen_metrics_charts::{CloudProviderMetricsConfig, generate_metrics_config};
@@ -68,6 +75,23 @@ pub fn kapsule_helm_charts(
         chart_config_prerequisites.cluster_long_id.to_string().as_str(),
     )?;
 
+    // OpenCost, workloads costs computed from the metrics stack
+    let opencost = match (
+        chart_config_prerequisites.cluster_advanced_settings.opencost_enabled,
+        &metrics_config.metrics_query_url,
+    ) {
+        (true, Some(metrics_query_url)) => Some(
+            OpenCostChart::new(
+                chart_prefix_path,
+                chart_config_prerequisites.cluster_long_id.to_string(),
+                metrics_query_url.to_string(),
+                get_chart_override_fn.clone(),
+            )
+            .to_common_helm_chart()?,
+        ),
+        _ => None,
+    };
+
     let prometheus_operator_crds_chart = metrics_config
         .prometheus_operator_crds_chart
         .map(|chart| Box::new(chart) as Box<dyn HelmChart>);
@@ -132,6 +156,10 @@ pub fn kapsule_helm_charts(
         chart_config_prerequisites.cluster_id.to_string(),
         UpdateStrategy::RollingUpdate,
         true,
+        chart_config_prerequisites
+            .cluster_advanced_settings
+            .ingress_implementation
+            .is_gateway_api(),
         HelmChartNamespaces::KubeSystem,
     )
     .to_common_helm_chart()?;
@@ -220,6 +248,10 @@ pub fn kapsule_helm_charts(
         UpdateStrategy::RollingUpdate,
         get_chart_override_fn.clone(),
         true,
+        chart_config_prerequisites
+            .cluster_advanced_settings
+            .ingress_implementation
+            .is_gateway_api(),
         HelmChartNamespaces::CertManager,
         HelmChartNamespaces::KubeSystem,
     )
@@ -399,6 +431,71 @@ pub fn kapsule_helm_charts(
     )
     .to_common_helm_chart()?;
 
+    // KEDA, event driven autoscaling for services
+    let keda = match chart_config_prerequisites.cluster_advanced_settings.keda_enabled {
+        false => None,
+        true => Some(
+            KedaChart::new(
+                chart_prefix_path,
+                HelmChartNamespaces::Keda,
+                chart_config_prerequisites.metrics_parameters.is_some(),
+                get_chart_override_fn.clone(),
+            )
+            .to_common_helm_chart()?,
+        ),
+    };
+
+    // Velero, cluster resources and volumes backups
+    let velero = match chart_config_prerequisites
+        .cluster_advanced_settings
+        .cluster_backup_enabled
+    {
+        false => None,
+        true => Some(
+            VeleroChart::new(
+                chart_prefix_path,
+                VeleroStorageConfiguration::ScalewayObjectStorage {
+                    region: chart_config_prerequisites.zone.region().to_string(),
+                    endpoint: chart_config_prerequisites.endpoint.clone(),
+                    bucket_name: backups_bucket_name(&chart_config_prerequisites.cluster_id),
+                    access_key: chart_config_prerequisites.access_key.clone(),
+                    secret_key: chart_config_prerequisites.secret_key.clone(),
+                },
+                chart_config_prerequisites
+                    .cluster_advanced_settings
+                    .cluster_backup_schedule
+                    .clone(),
+                chart_config_prerequisites
+                    .cluster_advanced_settings
+                    .cluster_backup_retention_in_days,
+                get_chart_override_fn.clone(),
+            )
+            .to_common_helm_chart()?,
+        ),
+    };
+
+    // Envoy Gateway, routers are exposed through Gateway API instead of nginx ingress
+    let (envoy_gateway, envoy_gateway_configs) = match chart_config_prerequisites
+        .cluster_advanced_settings
+        .ingress_implementation
+    {
+        IngressImplementation::EnvoyGateway => (
+            Some(
+                EnvoyGatewayChart::new(
+                    chart_prefix_path,
+                    HelmChartNamespaces::EnvoyGateway,
+                    get_chart_override_fn.clone(),
+                )
+                .to_common_helm_chart()?,
+            ),
+            Some(
+                EnvoyGatewayConfigsChart::new(chart_prefix_path, HelmChartNamespaces::EnvoyGateway)
+                    .to_common_helm_chart()?,
+            ),
+        ),
+        IngressImplementation::Nginx | IngressImplementation::Cilium => (None, None),
+    };
+
     // Qovery shell agent
     let qovery_shell_agent = QoveryShellAgentChart::new(
         chart_prefix_path,
@@ -551,11 +648,11 @@ pub fn kapsule_helm_charts(
         vec![]
     };
 
-    let level_5: Vec<Box<dyn HelmChart>> = vec![Box::new(external_dns)];
+    let mut level_5: Vec<Box<dyn HelmChart>> = vec![Box::new(external_dns)];
 
     let level_6: Vec<Box<dyn HelmChart>> = vec![Box::new(nginx_ingress)];
 
-    let level_7: Vec<Box<dyn HelmChart>> = vec![
+    let mut level_7: Vec<Box<dyn HelmChart>> = vec![
         Box::new(cert_manager_config),
         Box::new(qovery_cluster_agent),
         Box::new(qovery_shell_agent),
@@ -583,6 +680,29 @@ pub fn kapsule_helm_charts(
         level_2.push(Box::new(grafana_chart))
     }
 
+    // event driven autoscaling
+    if let Some(keda_chart) = keda {
+        level_5.push(Box::new(keda_chart));
+    }
+
+    // backups
+    if let Some(velero_chart) = velero {
+        level_5.push(Box::new(velero_chart));
+    }
+
+    // cost allocation
+    if let Some(opencost_chart) = opencost {
+        level_5.push(Box::new(opencost_chart));
+    }
+
+    // gateway API
+    if let Some(envoy_gateway_chart) = envoy_gateway {
+        level_5.push(Box::new(envoy_gateway_chart));
+    }
+    if let Some(envoy_gateway_configs_chart) = envoy_gateway_configs {
+        level_7.push(Box::new(envoy_gateway_configs_chart));
+    }
+
     info!("charts configuration preparation finished");
     Ok(vec![level_0, level_1, level_2, level_3, level_4, level_5, level_6, level_7])
 }
@@ -6,9 +6,11 @@ use crate::events::Stage::Infrastructure;
 use crate::infrastructure::action::ToInfraTeraContext;
 use crate::infrastructure::infrastructure_context::InfrastructureContext;
 use crate::infrastructure::models::kubernetes::Kubernetes;
+use crate::infrastructure::models::kubernetes::maintenance_window::weekday_name;
 use crate::infrastructure::models::kubernetes::scaleway::kapsule::Kapsule;
 use crate::io_models::context::Features;
 use crate::string::terraform_list_format;
+use chrono::Timelike;
 use reqwest::header;
 use serde_derive::{Deserialize, Serialize};
 use tera::Context as TeraContext;
@@ -79,10 +81,22 @@ fn kapsule_tera_context(cluster: &Kapsule, infra_ctx: &InfrastructureContext) ->
         "kubernetes_cluster_type",
         &cluster.options.scaleway_kubernetes_type.to_terraform_format_string(),
     );
+    // Kapsule auto upgrades only accept a single day, or any day, and a start hour, other windows are rejected (see Kapsule::new)
+    let (maintenance_window_day, maintenance_window_start_hour) = match cluster.advanced_settings().maintenance_window()
+    {
+        Some(window) if !window.is_every_day() => {
+            (weekday_name(window.days()[0]).to_lowercase(), window.start_time().hour())
+        }
+        Some(window) => ("any".to_string(), window.start_time().hour()),
+        None => ("any".to_string(), 3),
+    };
+    context.insert("maintenance_window_day", &maintenance_window_day);
+    context.insert("maintenance_window_start_hour", &maintenance_window_start_hour);
 
     // Qovery
     context.insert("organization_id", infra_ctx.context().organization_short_id());
     context.insert("organization_long_id", &infra_ctx.context().organization_long_id());
+    context.insert("cost_allocation_tags", &cluster.cost_allocation_tags().to_tags());
     context.insert("object_storage_kubeconfig_bucket", &cluster.kubeconfig_bucket_name());
     context.insert("object_storage_logs_bucket", &cluster.logs_bucket_name());
 
@@ -0,0 +1,54 @@
+use crate::errors::EngineError;
+use crate::events::InfrastructureStep;
+use crate::events::Stage::Infrastructure;
+use crate::infrastructure::action::InfraLogger;
+use crate::infrastructure::action::delete_kube_apps::{KubeAppsDeletionScope, delete_kube_apps};
+use crate::infrastructure::action::deploy_helms::{HelmInfraContext, HelmInfraResources};
+use crate::infrastructure::action::self_managed::check_self_managed_kubeconfig;
+use crate::infrastructure::action::self_managed::helm_charts::SelfManagedHelmsDeployment;
+use crate::infrastructure::infrastructure_context::InfrastructureContext;
+use crate::infrastructure::models::kubernetes::Kubernetes;
+use crate::infrastructure::models::kubernetes::self_managed::on_premise::SelfManaged;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+pub(super) fn delete_self_managed_charts(
+    cluster: &SelfManaged,
+    infra_ctx: &InfrastructureContext,
+    logger: impl InfraLogger,
+) -> Result<(), Box<EngineError>> {
+    let event_details = cluster.get_event_details(Infrastructure(InfrastructureStep::Delete));
+
+    logger.info("Uninstalling Qovery charts from self-managed cluster.");
+
+    check_self_managed_kubeconfig(cluster)?;
+
+    // Releases to uninstall are the ones the install deploys
+    let lib_root_dir = PathBuf::from(infra_ctx.context().lib_root_dir());
+    let helms_deployments = SelfManagedHelmsDeployment::new(
+        HelmInfraContext::new(
+            tera::Context::new(),
+            lib_root_dir.clone(),
+            lib_root_dir.join("common/bootstrap"),
+            cluster.temp_dir().join("helms"),
+            event_details.clone(),
+            vec![],
+            cluster.context().is_dry_run_deploy(),
+        ),
+        cluster,
+    );
+    let releases = helms_deployments.deployed_releases(infra_ctx)?;
+
+    // The cluster itself is owned by the user, only what Qovery installed on it is removed
+    delete_kube_apps(
+        cluster,
+        infra_ctx,
+        event_details,
+        &logger,
+        HashSet::with_capacity(0),
+        KubeAppsDeletionScope::QoveryStackOnly { releases, crds: &[] },
+    )?;
+
+    logger.info("Qovery charts uninstalled successfully.");
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use crate::errors::EngineError;
+use crate::events::InfrastructureStep;
+use crate::events::Stage::Infrastructure;
+use crate::infrastructure::action::InfraLogger;
+use crate::infrastructure::action::deploy_helms::{HelmInfraContext, HelmInfraResources};
+use crate::infrastructure::action::qovery_agents_pause::resume_qovery_agents;
+use crate::infrastructure::action::self_managed::check_self_managed_kubeconfig;
+use crate::infrastructure::action::self_managed::helm_charts::SelfManagedHelmsDeployment;
+use crate::infrastructure::infrastructure_context::InfrastructureContext;
+use crate::infrastructure::models::kubernetes::Kubernetes;
+use crate::infrastructure::models::kubernetes::self_managed::on_premise::SelfManaged;
+use std::path::PathBuf;
+
+pub(super) fn install_self_managed_charts(
+    cluster: &SelfManaged,
+    infra_ctx: &InfrastructureContext,
+    logger: impl InfraLogger,
+) -> Result<(), Box<EngineError>> {
+    let event_details = cluster.get_event_details(Infrastructure(InfrastructureStep::Create));
+
+    logger.info("Deploying Qovery charts for self-managed cluster.");
+
+    check_self_managed_kubeconfig(cluster)?;
+
+    // Self-managed clusters have no provider specific chart to render, only common ones are used
+    let lib_root_dir = PathBuf::from(infra_ctx.context().lib_root_dir());
+    let helms_deployments = SelfManagedHelmsDeployment::new(
+        HelmInfraContext::new(
+            tera::Context::new(),
+            lib_root_dir.clone(),
+            lib_root_dir.join("common/bootstrap"),
+            cluster.temp_dir().join("helms"),
+            event_details.clone(),
+            vec![],
+            cluster.context().is_dry_run_deploy(),
+        ),
+        cluster,
+    );
+    helms_deployments.deploy_charts(infra_ctx, &logger)?;
+
+    // If the cluster has been paused, restore Qovery agents
+    resume_qovery_agents(cluster, infra_ctx, &logger)?;
+
+    Ok(())
+}
@@ -0,0 +1,496 @@
+use crate::engine_task::qovery_api::{EngineServiceType, QoveryApi};
+use crate::environment::models::domain::{Domain, ToHelmString};
+use crate::environment::models::third_parties::LetsEncryptConfig;
+use crate::errors::{CommandError, EngineError};
+use crate::helm::{HelmChart, HelmChartNamespaces, PriorityClass, QoveryPriorityClass, UpdateStrategy};
+use crate::infrastructure::action::deploy_helms::{
+    HelmInfraContext, HelmInfraResources, mk_customer_chart_override_fn,
+};
+use crate::infrastructure::helm_charts::cert_manager_chart::CertManagerChart;
+use crate::infrastructure::helm_charts::cert_manager_config_chart::CertManagerConfigsChart;
+use crate::infrastructure::helm_charts::external_dns_chart::ExternalDNSChart;
+use crate::infrastructure::helm_charts::loki_chart::{LokiChart, LokiObjectBucketConfiguration};
+use crate::infrastructure::helm_charts::metrics_server_chart::MetricsServerChart;
+use crate::infrastructure::helm_charts::nginx_ingress_chart::{NginxIngressChart, NginxOptions};
+use crate::infrastructure::helm_charts::promtail_chart::PromtailChart;
+use crate::infrastructure::helm_charts::qovery_cert_manager_webhook_chart::QoveryCertManagerWebhookChart;
+use crate::infrastructure::helm_charts::qovery_cluster_agent_chart::QoveryClusterAgentChart;
+use crate::infrastructure::helm_charts::qovery_priority_class_chart::QoveryPriorityClassChart;
+use crate::infrastructure::helm_charts::qovery_shell_agent_chart::QoveryShellAgentChart;
+use crate::infrastructure::helm_charts::{
+    HelmChartDirectoryLocation, HelmChartResources, HelmChartResourcesConstraintType, HelmChartTimeout,
+    ToCommonHelmChart,
+};
+use crate::infrastructure::infrastructure_context::InfrastructureContext;
+use crate::infrastructure::models::cloud_provider::Kind;
+use crate::infrastructure::models::cloud_provider::io::ClusterAdvancedSettings;
+use crate::infrastructure::models::dns_provider::DnsProviderConfiguration;
+use crate::infrastructure::models::kubernetes::Kind as KubernetesKind;
+use crate::infrastructure::models::kubernetes::Kubernetes;
+use crate::infrastructure::models::kubernetes::self_managed::on_premise::{SelfManaged, SelfManagedOptions};
+use crate::io_models::QoveryIdentifier;
+use crate::io_models::context::Features;
+use crate::io_models::models::{KubernetesCpuResourceUnit, KubernetesMemoryResourceUnit};
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use std::iter::FromIterator;
+use url::Url;
+
+#[derive(Clone)]
+pub struct SelfManagedChartsConfigPrerequisites {
+    pub organization_id: String,
+    pub organization_long_id: uuid::Uuid,
+    pub cluster_id: String,
+    pub cluster_long_id: uuid::Uuid,
+    pub cluster_kind: KubernetesKind,
+    pub cluster_creation_date: DateTime<Utc>,
+    pub metrics_enabled: bool,
+    pub managed_dns_helm_format: String,
+    pub managed_dns_root_domain_helm_format: String,
+    pub dns_provider_config: DnsProviderConfiguration,
+    pub lets_encrypt_config: LetsEncryptConfig,
+    pub infra_options: SelfManagedOptions,
+    pub cluster_advanced_settings: ClusterAdvancedSettings,
+}
+
+pub struct SelfManagedHelmsDeployment<'a> {
+    context: HelmInfraContext,
+    cluster: &'a SelfManaged,
+}
+
+impl<'a> SelfManagedHelmsDeployment<'a> {
+    pub fn new(context: HelmInfraContext, cluster: &'a SelfManaged) -> Self {
+        Self { context, cluster }
+    }
+}
+
+impl HelmInfraResources for SelfManagedHelmsDeployment<'_> {
+    type ChartPrerequisite = SelfManagedChartsConfigPrerequisites;
+
+    fn charts_context(&self) -> &HelmInfraContext {
+        &self.context
+    }
+
+    fn new_chart_prerequisite(&self, infra_ctx: &InfrastructureContext) -> Self::ChartPrerequisite {
+        SelfManagedChartsConfigPrerequisites {
+            organization_id: infra_ctx.context().organization_short_id().to_string(),
+            organization_long_id: *infra_ctx.context().organization_long_id(),
+            cluster_id: self.cluster.short_id().to_string(),
+            cluster_long_id: *self.cluster.long_id(),
+            cluster_kind: self.cluster.kind(),
+            cluster_creation_date: self.cluster.created_at(),
+            metrics_enabled: self.cluster.context().is_feature_enabled(&Features::MetricsHistory),
+            managed_dns_helm_format: infra_ctx.dns_provider().domain().to_helm_format_string(),
+            managed_dns_root_domain_helm_format: infra_ctx
+                .dns_provider()
+                .domain()
+                .root_domain()
+                .to_helm_format_string(),
+            dns_provider_config: infra_ctx.dns_provider().provider_configuration(),
+            lets_encrypt_config: LetsEncryptConfig::new(
+                self.cluster.options().tls_email_report.to_string(),
+                self.cluster.context().is_test_cluster(),
+            ),
+            infra_options: self.cluster.options().clone(),
+            cluster_advanced_settings: self.cluster.advanced_settings().clone(),
+        }
+    }
+
+    fn gen_charts_to_deploy(
+        &self,
+        infra_ctx: &InfrastructureContext,
+        charts_prerequisites: Self::ChartPrerequisite,
+    ) -> Result<Vec<Vec<Box<dyn HelmChart>>>, Box<EngineError>> {
+        self_managed_helm_charts(
+            &charts_prerequisites,
+            Some(self.context.destination_folder.to_string_lossy().as_ref()),
+            &*infra_ctx.context().qovery_api,
+            infra_ctx.dns_provider().domain(),
+        )
+        .map_err(|e| Box::new(EngineError::new_helm_charts_setup_error(self.context.event_details.clone(), e)))
+    }
+}
+
+/// Charts of the self-managed bootstrap chart (see `byok_chart_gen`).
+/// Qovery agents are always deployed, the other ones only when the user opted in for them in [SelfManagedOptions].
+/// The engine chart is left to the bootstrap chart, as the engine may run this very deployment.
+/// Everything is deployed in the `qovery` namespace, and VPA is not enabled as its CRDs may not be installed.
+fn self_managed_helm_charts(
+    chart_config_prerequisites: &SelfManagedChartsConfigPrerequisites,
+    chart_prefix_path: Option<&str>,
+    qovery_api: &dyn QoveryApi,
+    domain: &Domain,
+) -> Result<Vec<Vec<Box<dyn HelmChart>>>, CommandError> {
+    let services = &chart_config_prerequisites.infra_options.services;
+    let advanced_settings = &chart_config_prerequisites.cluster_advanced_settings;
+    let get_chart_override_fn = mk_customer_chart_override_fn(None);
+    let namespace = HelmChartNamespaces::Qovery;
+
+    // Qovery priority class
+    let q_priority_class_chart = QoveryPriorityClassChart::new(
+        chart_prefix_path,
+        HashSet::from_iter(vec![QoveryPriorityClass::StandardPriority, QoveryPriorityClass::HighPriority]),
+        namespace.clone(),
+    )
+    .to_common_helm_chart()?;
+
+    // Cert manager, its configs and the Qovery DNS webhook
+    let mut cert_manager: Option<Box<dyn HelmChart>> = None;
+    let mut qovery_cert_manager_webhook: Option<Box<dyn HelmChart>> = None;
+    let mut cert_manager_config: Option<Box<dyn HelmChart>> = None;
+    if services.certificates {
+        cert_manager = Some(Box::new(
+            CertManagerChart::new(
+                chart_prefix_path,
+                chart_config_prerequisites.metrics_enabled,
+                HelmChartResourcesConstraintType::ChartDefault,
+                HelmChartResourcesConstraintType::ChartDefault,
+                HelmChartResourcesConstraintType::ChartDefault,
+                UpdateStrategy::RollingUpdate,
+                get_chart_override_fn.clone(),
+                false,
+                advanced_settings.ingress_implementation.is_gateway_api(),
+                namespace.clone(),
+                namespace.clone(),
+            )
+            .to_common_helm_chart()?,
+        ));
+        if let DnsProviderConfiguration::QoveryDns(qovery_dns_config) = &chart_config_prerequisites.dns_provider_config
+        {
+            qovery_cert_manager_webhook = Some(Box::new(
+                QoveryCertManagerWebhookChart::new(
+                    chart_prefix_path,
+                    qovery_dns_config.clone(),
+                    HelmChartResourcesConstraintType::ChartDefault,
+                    UpdateStrategy::RollingUpdate,
+                    namespace.clone(),
+                    namespace.clone(),
+                )
+                .to_common_helm_chart()?,
+            ));
+        }
+        cert_manager_config = Some(Box::new(
+            CertManagerConfigsChart::new(
+                chart_prefix_path,
+                &chart_config_prerequisites.lets_encrypt_config,
+                &chart_config_prerequisites.dns_provider_config,
+                chart_config_prerequisites.managed_dns_helm_format.to_string(),
+                namespace.clone(),
+            )
+            .to_common_helm_chart()?,
+        ));
+    }
+
+    // Promtail & Loki, logs are stored on the cluster
+    let loki_url = format!("loki.{namespace}.svc.cluster.local:3100");
+    let mut promtail: Option<Box<dyn HelmChart>> = None;
+    let mut loki: Option<Box<dyn HelmChart>> = None;
+    if services.logging {
+        promtail = Some(Box::new(
+            PromtailChart::new(
+                chart_prefix_path,
+                HelmChartDirectoryLocation::CommonFolder,
+                loki_url.clone(),
+                get_chart_override_fn.clone(),
+                false,
+                namespace.clone(),
+                PriorityClass::Default,
+                false,
+                chart_config_prerequisites.metrics_enabled,
+            )
+            .to_common_helm_chart()?,
+        ));
+        loki = Some(Box::new(
+            LokiChart::new(
+                chart_prefix_path,
+                namespace.clone(),
+                advanced_settings.loki_log_retention_in_week,
+                LokiObjectBucketConfiguration::Local,
+                get_chart_override_fn.clone(),
+                false,
+                None,
+                HelmChartResourcesConstraintType::ChartDefault,
+                HelmChartTimeout::ChartDefault,
+                false,
+            )
+            .to_common_helm_chart()?,
+        ));
+    }
+
+    // Metrics server
+    let metrics_server: Option<Box<dyn HelmChart>> = match services.observability {
+        false => None,
+        true => Some(Box::new(
+            MetricsServerChart::new(
+                chart_prefix_path,
+                HelmChartResourcesConstraintType::ChartDefault,
+                namespace.clone(),
+                UpdateStrategy::RollingUpdate,
+                false,
+                false,
+            )
+            .to_common_helm_chart()?,
+        )),
+    };
+
+    // External DNS
+    let external_dns: Option<Box<dyn HelmChart>> = match services.dns {
+        false => None,
+        true => Some(Box::new(
+            ExternalDNSChart::new(
+                chart_prefix_path,
+                chart_config_prerequisites.dns_provider_config.clone(),
+                chart_config_prerequisites
+                    .managed_dns_root_domain_helm_format
+                    .to_string(),
+                chart_config_prerequisites.cluster_id.to_string(),
+                UpdateStrategy::RollingUpdate,
+                false,
+                advanced_settings.ingress_implementation.is_gateway_api(),
+                namespace.clone(),
+            )
+            .to_common_helm_chart()?,
+        )),
+    };
+
+    // Nginx ingress
+    let nginx_ingress: Option<Box<dyn HelmChart>> = match services.ingress {
+        false => None,
+        true => Some(Box::new(
+            NginxIngressChart::new(
+                chart_prefix_path,
+                HelmChartResourcesConstraintType::Constrained(HelmChartResources {
+                    request_cpu: KubernetesCpuResourceUnit::MilliCpu(advanced_settings.nginx_vcpu_request_in_milli_cpu),
+                    request_memory: KubernetesMemoryResourceUnit::MebiByte(
+                        advanced_settings.nginx_memory_request_in_mib,
+                    ),
+                    limit_cpu: KubernetesCpuResourceUnit::MilliCpu(advanced_settings.nginx_vcpu_limit_in_milli_cpu),
+                    limit_memory: KubernetesMemoryResourceUnit::MebiByte(advanced_settings.nginx_memory_limit_in_mib),
+                }),
+                HelmChartResourcesConstraintType::ChartDefault,
+                chart_config_prerequisites.metrics_enabled,
+                get_chart_override_fn.clone(),
+                domain.clone(),
+                Kind::OnPremise,
+                chart_config_prerequisites.organization_long_id.to_string(),
+                chart_config_prerequisites.organization_id.clone(),
+                chart_config_prerequisites.cluster_long_id.to_string(),
+                chart_config_prerequisites.cluster_id.clone(),
+                chart_config_prerequisites.cluster_kind,
+                chart_config_prerequisites.cluster_creation_date,
+                NginxOptions {
+                    nginx_hpa_minimum_replicas: Some(advanced_settings.nginx_hpa_min_number_instances),
+                    nginx_hpa_maximum_replicas: Some(advanced_settings.nginx_hpa_max_number_instances),
+                    nginx_hpa_target_cpu_utilization_percentage: Some(
+                        advanced_settings.nginx_hpa_cpu_utilization_percentage_threshold,
+                    ),
+                    namespace: namespace.clone(),
+                    loadbalancer_size: None,
+                    enable_real_ip: advanced_settings.nginx_controller_enable_client_ip,
+                    use_forwarded_headers: advanced_settings.nginx_controller_use_forwarded_headers,
+                    compute_full_forwarded_for: advanced_settings.nginx_controller_compute_full_forwarded_for,
+                    log_format_escaping: advanced_settings.nginx_controller_log_format_escaping.to_model(),
+                    is_alb_enabled: false,
+                    http_snippet: advanced_settings
+                        .nginx_controller_http_snippet
+                        .as_ref()
+                        .map(|nginx_controller_http_snippet_io| nginx_controller_http_snippet_io.to_model()),
+                    server_snippet: advanced_settings
+                        .nginx_controller_server_snippet
+                        .as_ref()
+                        .map(|nginx_controller_server_snippet_io| nginx_controller_server_snippet_io.to_model()),
+                    limit_request_status_code: advanced_settings
+                        .nginx_controller_limit_request_status_code
+                        .as_ref()
+                        .map(|v| v.to_model().map_err(CommandError::from))
+                        .transpose()?,
+                    nginx_controller_custom_http_errors: advanced_settings.nginx_controller_custom_http_errors.clone(),
+                    nginx_default_backend_enabled: advanced_settings.nginx_default_backend_enabled,
+                    nginx_default_backend_image_repository: advanced_settings
+                        .nginx_default_backend_image_repository
+                        .clone(),
+                    nginx_default_backend_image_tag: advanced_settings.nginx_default_backend_image_tag.clone(),
+                    default_ssl_certificate: None,
+                    publish_status_address: None,
+                    replica_count: None,
+                    metal_lb_load_balancer_ip: None,
+                    external_dns_target: None,
+                },
+            )
+            .to_common_helm_chart()?,
+        )),
+    };
+
+    // Qovery cluster agent
+    let cluster_agent = QoveryClusterAgentChart::new(
+        chart_prefix_path,
+        qovery_api
+            .service_version(EngineServiceType::ClusterAgent)
+            .map_err(|e| CommandError::new("cannot get cluster agent version".to_string(), Some(e.to_string()), None))?
+            .as_str(),
+        Url::parse(&chart_config_prerequisites.infra_options.qovery_grpc_url)
+            .map_err(|e| CommandError::new("cannot parse GRPC url".to_string(), Some(e.to_string()), None))?,
+        match services.logging {
+            true => Some(
+                Url::parse(&format!("http://{loki_url}"))
+                    .map_err(|e| CommandError::new("cannot parse Loki url".to_string(), Some(e.to_string()), None))?,
+            ),
+            false => None,
+        },
+        &chart_config_prerequisites.infra_options.jwt_token.clone(),
+        QoveryIdentifier::new(chart_config_prerequisites.cluster_long_id),
+        QoveryIdentifier::new(chart_config_prerequisites.organization_long_id),
+        HelmChartResourcesConstraintType::ChartDefault,
+        UpdateStrategy::RollingUpdate,
+        false,
+        false,
+        None,
+    )
+    .to_common_helm_chart()?;
+
+    // Qovery shell agent
+    let qovery_shell_agent = QoveryShellAgentChart::new(
+        chart_prefix_path,
+        qovery_api
+            .service_version(EngineServiceType::ShellAgent)
+            .map_err(|e| CommandError::new("cannot get shell agent version".to_string(), Some(e.to_string()), None))?
+            .as_str(),
+        chart_config_prerequisites.infra_options.jwt_token.clone(),
+        QoveryIdentifier::new(chart_config_prerequisites.organization_long_id),
+        QoveryIdentifier::new(chart_config_prerequisites.cluster_long_id),
+        chart_config_prerequisites.infra_options.qovery_grpc_url.clone(),
+        HelmChartResourcesConstraintType::ChartDefault,
+        UpdateStrategy::RollingUpdate,
+    )
+    .to_common_helm_chart()?;
+
+    // Set deploying order
+    let level_0: Vec<Option<Box<dyn HelmChart>>> = vec![Some(Box::new(q_priority_class_chart))];
+    let level_1: Vec<Option<Box<dyn HelmChart>>> = vec![cert_manager, loki];
+    let level_2: Vec<Option<Box<dyn HelmChart>>> = vec![qovery_cert_manager_webhook, promtail, metrics_server];
+    let level_3: Vec<Option<Box<dyn HelmChart>>> = vec![external_dns, nginx_ingress];
+    let level_4: Vec<Option<Box<dyn HelmChart>>> = vec![
+        Some(Box::new(cluster_agent)),
+        Some(Box::new(qovery_shell_agent)),
+        cert_manager_config,
+    ];
+
+    info!("charts configuration preparation finished");
+    Ok(vec![
+        level_0.into_iter().flatten().collect(),
+        level_1.into_iter().flatten().collect(),
+        level_2.into_iter().flatten().collect(),
+        level_3.into_iter().flatten().collect(),
+        level_4.into_iter().flatten().collect(),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SelfManagedChartsConfigPrerequisites, self_managed_helm_charts};
+    use crate::engine_task::qovery_api::{EngineServiceType, StaticQoveryApi};
+    use crate::environment::models::domain::Domain;
+    use crate::environment::models::third_parties::LetsEncryptConfig;
+    use crate::infrastructure::models::cloud_provider::io::ClusterAdvancedSettings;
+    use crate::infrastructure::models::dns_provider::DnsProviderConfiguration;
+    use crate::infrastructure::models::dns_provider::qoverydns::QoveryDnsConfig;
+    use crate::infrastructure::models::kubernetes::Kind as KubernetesKind;
+    use crate::infrastructure::models::kubernetes::self_managed::on_premise::{
+        SelfManagedOptions, SelfManagedServices,
+    };
+    use crate::io_models::engine_location::EngineLocation;
+    use std::collections::HashMap;
+    use url::Url;
+
+    fn chart_prerequisites(services: SelfManagedServices) -> SelfManagedChartsConfigPrerequisites {
+        SelfManagedChartsConfigPrerequisites {
+            organization_id: "z1234567".to_string(),
+            organization_long_id: uuid::Uuid::new_v4(),
+            cluster_id: "z7654321".to_string(),
+            cluster_long_id: uuid::Uuid::new_v4(),
+            cluster_kind: KubernetesKind::OnPremiseSelfManaged,
+            cluster_creation_date: Default::default(),
+            metrics_enabled: false,
+            managed_dns_helm_format: "\"whatever.com\"".to_string(),
+            managed_dns_root_domain_helm_format: "\"whatever.com\"".to_string(),
+            dns_provider_config: DnsProviderConfiguration::QoveryDns(QoveryDnsConfig {
+                api_url: Url::parse("https://whatever.com").expect("Unable to parse URL"),
+                api_key: "whatever".to_string(),
+                api_url_scheme_and_domain: "https://whatever.com".to_string(),
+                api_url_port: "443".to_string(),
+            }),
+            lets_encrypt_config: LetsEncryptConfig::new("whatever@whatever.com".to_string(), true),
+            infra_options: SelfManagedOptions {
+                qovery_grpc_url: "https://grpc.whatever.com".to_string(),
+                qovery_engine_url: "https://engine.whatever.com".to_string(),
+                jwt_token: "whatever".to_string(),
+                qovery_engine_location: EngineLocation::ClientSide,
+                tls_email_report: "whatever@whatever.com".to_string(),
+                services,
+            },
+            cluster_advanced_settings: ClusterAdvancedSettings::default(),
+        }
+    }
+
+    #[test]
+    fn test_self_managed_helm_charts_follow_enabled_services() {
+        // setup:
+        let qovery_api = StaticQoveryApi {
+            versions: HashMap::from([
+                (EngineServiceType::ClusterAgent, "1.0.0".to_string()),
+                (EngineServiceType::ShellAgent, "1.0.0".to_string()),
+            ]),
+        };
+        let domain = Domain::new("whatever.com".to_string());
+        let test_cases = vec![
+            (
+                SelfManagedServices::default(),
+                vec![vec!["qovery-priority-class"], vec!["cluster-agent", "shell-agent"]],
+            ),
+            (
+                SelfManagedServices {
+                    ingress: true,
+                    dns: true,
+                    logging: false,
+                    certificates: false,
+                    observability: false,
+                },
+                vec![
+                    vec!["qovery-priority-class"],
+                    vec!["external-dns", "ingress-nginx"],
+                    vec!["cluster-agent", "shell-agent"],
+                ],
+            ),
+            (
+                SelfManagedServices {
+                    ingress: true,
+                    dns: true,
+                    logging: true,
+                    certificates: true,
+                    observability: true,
+                },
+                vec![
+                    vec!["qovery-priority-class"],
+                    vec!["cert-manager", "loki"],
+                    vec!["qovery-cert-manager-webhook", "promtail", "metrics-server"],
+                    vec!["external-dns", "ingress-nginx"],
+                    vec!["cluster-agent", "shell-agent", "cert-manager-configs"],
+                ],
+            ),
+        ];
+
+        for (services, expected_releases) in test_cases {
+            // execute:
+            let charts = self_managed_helm_charts(&chart_prerequisites(services.clone()), None, &qovery_api, &domain)
+                .expect("Unable to generate charts");
+
+            // verify:
+            let releases: Vec<Vec<String>> = charts
+                .iter()
+                .map(|level| level.iter().map(|chart| chart.get_chart_info().name.clone()).collect())
+                .filter(|level: &Vec<String>| !level.is_empty())
+                .collect();
+            assert_eq!(expected_releases, releases, "services: {services:?}");
+        }
+    }
+}
@@ -1,41 +1,64 @@
+mod cluster_delete;
+mod cluster_install;
+mod helm_charts;
+
 use crate::errors::EngineError;
 use crate::events::InfrastructureStep;
 use crate::events::Stage::Infrastructure;
-use crate::infrastructure::action::InfrastructureAction;
+use crate::infrastructure::action::qovery_agents_pause::pause_qovery_agents;
+use crate::infrastructure::action::self_managed::cluster_delete::delete_self_managed_charts;
+use crate::infrastructure::action::self_managed::cluster_install::install_self_managed_charts;
+use crate::infrastructure::action::utils::mk_logger;
+use crate::infrastructure::action::{InfraLogger, InfrastructureAction};
 use crate::infrastructure::infrastructure_context::InfrastructureContext;
+use crate::infrastructure::models::cloud_provider::service::Action;
 use crate::infrastructure::models::kubernetes::self_managed::on_premise::SelfManaged;
-use crate::infrastructure::models::kubernetes::{Kubernetes, KubernetesUpgradeStatus};
+use crate::infrastructure::models::kubernetes::{Kubernetes, KubernetesUpgradeStatus, send_progress_on_long_task};
 
 impl InfrastructureAction for SelfManaged {
     fn create_cluster(
         &self,
-        _infra_ctx: &InfrastructureContext,
+        infra_ctx: &InfrastructureContext,
         _has_been_upgraded: bool,
     ) -> Result<(), Box<EngineError>> {
-        Err(Box::new(EngineError::new_cannot_restart_kubernetes_cluster(
-            self.get_event_details(Infrastructure(InfrastructureStep::CreateError)),
-        )))
+        let logger = mk_logger(infra_ctx.kubernetes(), InfrastructureStep::Create);
+        send_progress_on_long_task(self, Action::Create, || install_self_managed_charts(self, infra_ctx, logger))
     }
 
-    fn pause_cluster(&self, _infra_ctx: &InfrastructureContext) -> Result<(), Box<EngineError>> {
-        Err(Box::new(EngineError::new_cannot_restart_kubernetes_cluster(
-            self.get_event_details(Infrastructure(InfrastructureStep::PauseError)),
-        )))
+    fn pause_cluster(&self, infra_ctx: &InfrastructureContext) -> Result<(), Box<EngineError>> {
+        let logger = mk_logger(infra_ctx.kubernetes(), InfrastructureStep::Pause);
+        send_progress_on_long_task(self, Action::Pause, || {
+            check_self_managed_kubeconfig(self)?;
+            pause_qovery_agents(self, infra_ctx, &logger)
+        })
     }
 
-    fn delete_cluster(&self, _infra_ctx: &InfrastructureContext) -> Result<(), Box<EngineError>> {
-        Err(Box::new(EngineError::new_cannot_restart_kubernetes_cluster(
-            self.get_event_details(Infrastructure(InfrastructureStep::DeleteError)),
-        )))
+    fn delete_cluster(&self, infra_ctx: &InfrastructureContext) -> Result<(), Box<EngineError>> {
+        let logger = mk_logger(infra_ctx.kubernetes(), InfrastructureStep::Delete);
+        send_progress_on_long_task(self, Action::Delete, || delete_self_managed_charts(self, infra_ctx, logger))
     }
 
     fn upgrade_cluster(
         &self,
-        _infra_ctx: &InfrastructureContext,
-        _kubernetes_upgrade_status: KubernetesUpgradeStatus,
+        infra_ctx: &InfrastructureContext,
+        kubernetes_upgrade_status: KubernetesUpgradeStatus,
     ) -> Result<(), Box<EngineError>> {
-        Err(Box::new(EngineError::new_cannot_restart_kubernetes_cluster(
-            self.get_event_details(Infrastructure(InfrastructureStep::UpgradeError)),
-        )))
+        // Kubernetes version is managed by the user, Qovery charts are upgraded when the cluster is created right after
+        let logger = mk_logger(infra_ctx.kubernetes(), InfrastructureStep::Upgrade);
+        logger.warn(format!(
+            "Kubernetes version of this cluster is managed by you, upgrade it to {} on your side.",
+            kubernetes_upgrade_status.requested_version
+        ));
+        Ok(())
+    }
+}
+
+/// Infrastructure actions run from outside the cluster, they require its kubeconfig
+fn check_self_managed_kubeconfig(cluster: &SelfManaged) -> Result<(), Box<EngineError>> {
+    match cluster.kubeconfig() {
+        Some(_) => Ok(()),
+        None => Err(Box::new(EngineError::new_missing_kubeconfig_error(
+            cluster.get_event_details(Infrastructure(InfrastructureStep::LoadConfiguration)),
+        ))),
     }
 }
@@ -26,6 +26,7 @@ pub struct CertManagerChart {
     update_strategy: UpdateStrategy,
     customer_helm_chart_override: Option<CustomerHelmChartsOverride>,
     enable_vpa: bool,
+    gateway_api_enabled: bool,
     namespace: HelmChartNamespaces,
     leader_election_namespace: HelmChartNamespaces,
 }
@@ -40,6 +41,7 @@ impl CertManagerChart {
         update_strategy: UpdateStrategy,
         customer_helm_chart_fn: Arc<dyn Fn(String) -> Option<CustomerHelmChartsOverride>>,
         enable_vpa: bool,
+        gateway_api_enabled: bool,
         namespace: HelmChartNamespaces,
         leader_election_namespace: HelmChartNamespaces,
     ) -> CertManagerChart {
@@ -86,6 +88,7 @@ impl CertManagerChart {
             update_strategy,
             customer_helm_chart_override: customer_helm_chart_fn(Self::chart_name()),
             enable_vpa,
+            gateway_api_enabled,
             namespace,
             leader_election_namespace,
         }
@@ -119,6 +122,11 @@ impl ToCommonHelmChart for CertManagerChart {
                         key: "prometheus.servicemonitor.enabled".to_string(),
                         value: self.ff_metrics_history_enabled.to_string(),
                     },
+                    // required to solve HTTP01 challenges of routers exposed through Gateway API
+                    ChartSetValue {
+                        key: "config.enableGatewayAPI".to_string(),
+                        value: self.gateway_api_enabled.to_string(),
+                    },
                     // resources limits
                     ChartSetValue {
                         key: "resources.limits.cpu".to_string(),
@@ -314,6 +322,7 @@ mod tests {
             UpdateStrategy::RollingUpdate,
             get_cert_manager_chart_override(),
             false,
+            false,
             HelmChartNamespaces::CertManager,
             HelmChartNamespaces::KubeSystem,
         );
@@ -348,6 +357,7 @@ mod tests {
             UpdateStrategy::RollingUpdate,
             get_cert_manager_chart_override(),
             false,
+            false,
             HelmChartNamespaces::CertManager,
             HelmChartNamespaces::KubeSystem,
         );
@@ -386,6 +396,7 @@ mod tests {
             UpdateStrategy::RollingUpdate,
             get_cert_manager_chart_override(),
             false,
+            false,
             HelmChartNamespaces::CertManager,
             HelmChartNamespaces::KubeSystem,
         );
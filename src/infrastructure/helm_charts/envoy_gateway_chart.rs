@@ -0,0 +1,124 @@
+use std::sync::Arc;
+
+use crate::errors::CommandError;
+use crate::helm::{ChartInfo, ChartInstallationChecker, CommonChart, HelmChartError, HelmChartNamespaces};
+use crate::infrastructure::helm_charts::{
+    HelmChartDirectoryLocation, HelmChartPath, HelmChartValuesFilePath, ToCommonHelmChart,
+};
+use crate::io_models::models::CustomerHelmChartsOverride;
+use kube::Client;
+
+pub struct EnvoyGatewayChart {
+    chart_path: HelmChartPath,
+    chart_values_path: HelmChartValuesFilePath,
+    namespace: HelmChartNamespaces,
+    customer_helm_chart_override: Option<CustomerHelmChartsOverride>,
+}
+
+impl EnvoyGatewayChart {
+    pub fn new(
+        chart_prefix_path: Option<&str>,
+        namespace: HelmChartNamespaces,
+        customer_helm_chart_fn: Arc<dyn Fn(String) -> Option<CustomerHelmChartsOverride>>,
+    ) -> EnvoyGatewayChart {
+        EnvoyGatewayChart {
+            chart_path: HelmChartPath::new(
+                chart_prefix_path,
+                HelmChartDirectoryLocation::CommonFolder,
+                EnvoyGatewayChart::chart_name(),
+            ),
+            chart_values_path: HelmChartValuesFilePath::new(
+                chart_prefix_path,
+                HelmChartDirectoryLocation::CommonFolder,
+                EnvoyGatewayChart::chart_name(),
+            ),
+            namespace,
+            customer_helm_chart_override: customer_helm_chart_fn(Self::chart_name()),
+        }
+    }
+
+    pub fn chart_name() -> String {
+        "envoy-gateway".to_string()
+    }
+}
+
+impl ToCommonHelmChart for EnvoyGatewayChart {
+    fn to_common_helm_chart(&self) -> Result<CommonChart, HelmChartError> {
+        Ok(CommonChart {
+            chart_info: ChartInfo {
+                name: EnvoyGatewayChart::chart_name(),
+                path: self.chart_path.to_string(),
+                namespace: self.namespace.clone(),
+                values_files: vec![self.chart_values_path.to_string()],
+                yaml_files_content: match self.customer_helm_chart_override.clone() {
+                    Some(x) => vec![x.to_chart_values_generated()],
+                    None => vec![],
+                },
+                ..Default::default()
+            },
+            chart_installation_checker: Some(Box::new(EnvoyGatewayChartChecker::new())),
+            vertical_pod_autoscaler: None,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct EnvoyGatewayChartChecker {}
+
+impl EnvoyGatewayChartChecker {
+    pub fn new() -> EnvoyGatewayChartChecker {
+        EnvoyGatewayChartChecker {}
+    }
+}
+
+impl Default for EnvoyGatewayChartChecker {
+    fn default() -> Self {
+        EnvoyGatewayChartChecker::new()
+    }
+}
+
+impl ChartInstallationChecker for EnvoyGatewayChartChecker {
+    fn verify_installation(&self, _kube_client: &Client) -> Result<(), CommandError> {
+        // TODO: check Gateway API CRDs are served once the controller is up
+        Ok(())
+    }
+
+    fn clone_dyn(&self) -> Box<dyn ChartInstallationChecker> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helm::HelmChartNamespaces;
+    use crate::infrastructure::helm_charts::envoy_gateway_chart::EnvoyGatewayChart;
+    use crate::infrastructure::helm_charts::{HelmChartType, get_helm_path_kubernetes_provider_sub_folder_name};
+    use std::env;
+    use std::sync::Arc;
+
+    /// Makes sure chart values file exists.
+    #[test]
+    fn envoy_gateway_chart_values_file_exists_test() {
+        // setup:
+        let chart = EnvoyGatewayChart::new(None, HelmChartNamespaces::EnvoyGateway, Arc::new(|_| None));
+
+        let current_directory = env::current_dir().expect("Impossible to get current directory");
+        let chart_values_path = format!(
+            "{}/lib/{}/bootstrap/chart_values/{}.yaml",
+            current_directory
+                .to_str()
+                .expect("Impossible to convert current directory to string"),
+            get_helm_path_kubernetes_provider_sub_folder_name(
+                chart.chart_values_path.helm_path(),
+                HelmChartType::Shared
+            ),
+            EnvoyGatewayChart::chart_name(),
+        );
+
+        // execute
+        let values_file = std::fs::File::open(&chart_values_path);
+
+        // verify:
+        assert!(values_file.is_ok(), "Chart values file should exist: `{chart_values_path}`");
+    }
+}
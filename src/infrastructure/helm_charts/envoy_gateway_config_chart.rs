@@ -0,0 +1,159 @@
+use crate::errors::CommandError;
+use crate::helm::{
+    ChartInfo, ChartInfoUpgradeRetry, ChartInstallationChecker, ChartSetValue, CommonChart, HelmChartError,
+    HelmChartNamespaces,
+};
+use crate::infrastructure::helm_charts::{
+    HelmChartDirectoryLocation, HelmChartPath, HelmChartValuesFilePath, ToCommonHelmChart,
+};
+use crate::infrastructure::models::cloud_provider::io::IngressImplementation;
+use kube::Client;
+
+/// GatewayClass and shared Envoy proxy configuration, routers Gateways are attached to it.
+pub struct EnvoyGatewayConfigsChart {
+    chart_path: HelmChartPath,
+    chart_values_path: HelmChartValuesFilePath,
+    namespace: HelmChartNamespaces,
+}
+
+impl EnvoyGatewayConfigsChart {
+    pub fn new(chart_prefix_path: Option<&str>, namespace: HelmChartNamespaces) -> Self {
+        EnvoyGatewayConfigsChart {
+            chart_path: HelmChartPath::new(
+                chart_prefix_path,
+                HelmChartDirectoryLocation::CommonFolder,
+                EnvoyGatewayConfigsChart::chart_name(),
+            ),
+            chart_values_path: HelmChartValuesFilePath::new(
+                chart_prefix_path,
+                HelmChartDirectoryLocation::CommonFolder,
+                EnvoyGatewayConfigsChart::chart_name(),
+            ),
+            namespace,
+        }
+    }
+
+    pub fn chart_name() -> String {
+        "envoy-gateway-configs".to_string()
+    }
+}
+
+impl ToCommonHelmChart for EnvoyGatewayConfigsChart {
+    fn to_common_helm_chart(&self) -> Result<CommonChart, HelmChartError> {
+        Ok(CommonChart {
+            chart_info: ChartInfo {
+                name: EnvoyGatewayConfigsChart::chart_name(),
+                path: self.chart_path.to_string(),
+                namespace: self.namespace.clone(),
+                values_files: vec![self.chart_values_path.to_string()],
+                values: vec![
+                    ChartSetValue {
+                        key: "gatewayClassName".to_string(),
+                        value: IngressImplementation::ENVOY_GATEWAY_CLASS_NAME.to_string(),
+                    },
+                    ChartSetValue {
+                        key: "namespace".to_string(),
+                        value: self.namespace.to_string(),
+                    },
+                ],
+                // Envoy Gateway webhooks / CRDs may not be ready right after the controller install
+                upgrade_retry: Some(ChartInfoUpgradeRetry {
+                    nb_retry: 10,
+                    delay_in_milli_sec: 30_000,
+                }),
+                ..Default::default()
+            },
+            chart_installation_checker: Some(Box::new(EnvoyGatewayConfigsChartChecker::new())),
+            vertical_pod_autoscaler: None,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct EnvoyGatewayConfigsChartChecker {}
+
+impl EnvoyGatewayConfigsChartChecker {
+    pub fn new() -> EnvoyGatewayConfigsChartChecker {
+        EnvoyGatewayConfigsChartChecker {}
+    }
+}
+
+impl Default for EnvoyGatewayConfigsChartChecker {
+    fn default() -> Self {
+        EnvoyGatewayConfigsChartChecker::new()
+    }
+}
+
+impl ChartInstallationChecker for EnvoyGatewayConfigsChartChecker {
+    fn verify_installation(&self, _kube_client: &Client) -> Result<(), CommandError> {
+        // TODO: check GatewayClass is accepted by the controller
+        Ok(())
+    }
+
+    fn clone_dyn(&self) -> Box<dyn ChartInstallationChecker> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helm::HelmChartNamespaces;
+    use crate::infrastructure::helm_charts::envoy_gateway_config_chart::EnvoyGatewayConfigsChart;
+    use crate::infrastructure::helm_charts::{
+        HelmChartType, ToCommonHelmChart, get_helm_path_kubernetes_provider_sub_folder_name,
+        get_helm_values_set_in_code_but_absent_in_values_file,
+    };
+    use std::env;
+
+    /// Makes sure chart directory containing all YAML files exists.
+    #[test]
+    fn envoy_gateway_configs_chart_directory_exists_test() {
+        // setup:
+        let chart = EnvoyGatewayConfigsChart::new(None, HelmChartNamespaces::EnvoyGateway);
+
+        let current_directory = env::current_dir().expect("Impossible to get current directory");
+        let chart_path = format!(
+            "{}/lib/{}/bootstrap/charts/{}/Chart.yaml",
+            current_directory
+                .to_str()
+                .expect("Impossible to convert current directory to string"),
+            get_helm_path_kubernetes_provider_sub_folder_name(chart.chart_path.helm_path(), HelmChartType::Shared,),
+            EnvoyGatewayConfigsChart::chart_name(),
+        );
+
+        // execute
+        let values_file = std::fs::File::open(&chart_path);
+
+        // verify:
+        assert!(values_file.is_ok(), "Chart directory should exist: `{chart_path}`");
+    }
+
+    /// Make sure rust code doesn't set a value not declared inside values file.
+    /// All values should be declared / set in values file unless it needs to be injected via rust code.
+    #[test]
+    fn envoy_gateway_configs_chart_rust_overridden_values_exists_in_values_yaml_test() {
+        // setup:
+        let chart = EnvoyGatewayConfigsChart::new(None, HelmChartNamespaces::EnvoyGateway);
+        let common_chart = chart.to_common_helm_chart().unwrap();
+
+        // execute:
+        let missing_fields = get_helm_values_set_in_code_but_absent_in_values_file(
+            common_chart,
+            format!(
+                "/lib/{}/bootstrap/chart_values/{}.yaml",
+                get_helm_path_kubernetes_provider_sub_folder_name(
+                    chart.chart_values_path.helm_path(),
+                    HelmChartType::Shared,
+                ),
+                EnvoyGatewayConfigsChart::chart_name()
+            ),
+        );
+
+        // verify:
+        assert!(
+            missing_fields.is_none(),
+            "Some fields are missing in values file, add those (make sure they still exist in chart values), fields: {}",
+            missing_fields.unwrap_or_default().join(",")
+        );
+    }
+}
@@ -20,6 +20,7 @@ pub struct ExternalDNSChart {
     cluster_id: String,
     update_strategy: UpdateStrategy,
     enable_vpa: bool,
+    gateway_api_enabled: bool,
     namespace: HelmChartNamespaces,
 }
 
@@ -31,6 +32,7 @@ impl ExternalDNSChart {
         cluster_id: String,
         update_strategy: UpdateStrategy,
         enable_vpa: bool,
+        gateway_api_enabled: bool,
         namespace: HelmChartNamespaces,
     ) -> ExternalDNSChart {
         ExternalDNSChart {
@@ -50,6 +52,7 @@ impl ExternalDNSChart {
             cluster_id,
             update_strategy,
             enable_vpa,
+            gateway_api_enabled,
             namespace,
         }
     }
@@ -91,6 +94,13 @@ impl ToCommonHelmChart for ExternalDNSChart {
                             .to_string()
                             .replace('.', r"\."), // escape . from domains
                     },
+                    ChartSetValue {
+                        key: "sources".to_string(),
+                        value: match self.gateway_api_enabled {
+                            true => "{service,ingress,gateway-httproute,gateway-grpcroute}".to_string(),
+                            false => "{service,ingress}".to_string(),
+                        },
+                    },
                     ChartSetValue {
                         key: "txtOwnerId".to_string(),
                         value: self.cluster_id.to_string(),
@@ -225,6 +235,7 @@ mod tests {
             "whatever".to_string(),
             UpdateStrategy::RollingUpdate,
             false,
+            false,
             HelmChartNamespaces::KubeSystem,
         );
 
@@ -260,6 +271,7 @@ mod tests {
             "whatever".to_string(),
             UpdateStrategy::RollingUpdate,
             false,
+            false,
             HelmChartNamespaces::KubeSystem,
         );
 
@@ -299,6 +311,7 @@ mod tests {
             "whatever".to_string(),
             UpdateStrategy::RollingUpdate,
             false,
+            false,
             HelmChartNamespaces::KubeSystem,
         );
         let common_chart = chart.to_common_helm_chart().unwrap();
@@ -0,0 +1,175 @@
+use std::sync::Arc;
+
+use crate::errors::CommandError;
+use crate::helm::{
+    ChartInfo, ChartInstallationChecker, ChartSetValue, CommonChart, HelmChartError, HelmChartNamespaces,
+};
+use crate::infrastructure::helm_charts::{
+    HelmChartDirectoryLocation, HelmChartPath, HelmChartValuesFilePath, ToCommonHelmChart,
+};
+use crate::io_models::models::CustomerHelmChartsOverride;
+use kube::Client;
+
+pub struct KedaChart {
+    chart_path: HelmChartPath,
+    chart_values_path: HelmChartValuesFilePath,
+    namespace: HelmChartNamespaces,
+    prometheus_enabled: bool,
+    customer_helm_chart_override: Option<CustomerHelmChartsOverride>,
+}
+
+impl KedaChart {
+    pub fn new(
+        chart_prefix_path: Option<&str>,
+        namespace: HelmChartNamespaces,
+        prometheus_enabled: bool,
+        customer_helm_chart_fn: Arc<dyn Fn(String) -> Option<CustomerHelmChartsOverride>>,
+    ) -> KedaChart {
+        KedaChart {
+            chart_path: HelmChartPath::new(
+                chart_prefix_path,
+                HelmChartDirectoryLocation::CommonFolder,
+                KedaChart::chart_name(),
+            ),
+            chart_values_path: HelmChartValuesFilePath::new(
+                chart_prefix_path,
+                HelmChartDirectoryLocation::CommonFolder,
+                KedaChart::chart_name(),
+            ),
+            namespace,
+            prometheus_enabled,
+            customer_helm_chart_override: customer_helm_chart_fn(Self::chart_name()),
+        }
+    }
+
+    pub fn chart_name() -> String {
+        "keda".to_string()
+    }
+}
+
+impl ToCommonHelmChart for KedaChart {
+    fn to_common_helm_chart(&self) -> Result<CommonChart, HelmChartError> {
+        let mut values = vec![];
+        for key in [
+            "prometheus.operator.enabled",
+            "prometheus.operator.serviceMonitor.enabled",
+            "prometheus.metricServer.enabled",
+            "prometheus.metricServer.serviceMonitor.enabled",
+        ] {
+            values.push(ChartSetValue {
+                key: key.to_string(),
+                value: self.prometheus_enabled.to_string(),
+            });
+        }
+
+        Ok(CommonChart {
+            chart_info: ChartInfo {
+                name: KedaChart::chart_name(),
+                path: self.chart_path.to_string(),
+                namespace: self.namespace.clone(),
+                values_files: vec![self.chart_values_path.to_string()],
+                values,
+                yaml_files_content: match self.customer_helm_chart_override.clone() {
+                    Some(x) => vec![x.to_chart_values_generated()],
+                    None => vec![],
+                },
+                ..Default::default()
+            },
+            chart_installation_checker: Some(Box::new(KedaChartChecker::new())),
+            vertical_pod_autoscaler: None,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct KedaChartChecker {}
+
+impl KedaChartChecker {
+    pub fn new() -> KedaChartChecker {
+        KedaChartChecker {}
+    }
+}
+
+impl Default for KedaChartChecker {
+    fn default() -> Self {
+        KedaChartChecker::new()
+    }
+}
+
+impl ChartInstallationChecker for KedaChartChecker {
+    fn verify_installation(&self, _kube_client: &Client) -> Result<(), CommandError> {
+        // TODO: check ScaledObject / ScaledJob CRDs are served once the operator is up
+        Ok(())
+    }
+
+    fn clone_dyn(&self) -> Box<dyn ChartInstallationChecker> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helm::HelmChartNamespaces;
+    use crate::infrastructure::helm_charts::keda_chart::KedaChart;
+    use crate::infrastructure::helm_charts::{
+        HelmChartType, ToCommonHelmChart, get_helm_path_kubernetes_provider_sub_folder_name,
+        get_helm_values_set_in_code_but_absent_in_values_file,
+    };
+    use std::env;
+    use std::sync::Arc;
+
+    /// Makes sure chart values file exists.
+    #[test]
+    fn keda_chart_values_file_exists_test() {
+        // setup:
+        let chart = KedaChart::new(None, HelmChartNamespaces::Keda, true, Arc::new(|_| None));
+
+        let current_directory = env::current_dir().expect("Impossible to get current directory");
+        let chart_values_path = format!(
+            "{}/lib/{}/bootstrap/chart_values/{}.yaml",
+            current_directory
+                .to_str()
+                .expect("Impossible to convert current directory to string"),
+            get_helm_path_kubernetes_provider_sub_folder_name(
+                chart.chart_values_path.helm_path(),
+                HelmChartType::Shared
+            ),
+            KedaChart::chart_name(),
+        );
+
+        // execute
+        let values_file = std::fs::File::open(&chart_values_path);
+
+        // verify:
+        assert!(values_file.is_ok(), "Chart values file should exist: `{chart_values_path}`");
+    }
+
+    /// Make sure rust code doesn't set a value not declared inside values file.
+    /// All values should be declared / set in values file unless it needs to be injected via rust code.
+    #[test]
+    fn keda_chart_rust_overridden_values_exists_in_values_yaml_test() {
+        // setup:
+        let chart = KedaChart::new(None, HelmChartNamespaces::Keda, true, Arc::new(|_| None));
+        let common_chart = chart.to_common_helm_chart().unwrap();
+
+        // execute:
+        let missing_fields = get_helm_values_set_in_code_but_absent_in_values_file(
+            common_chart,
+            format!(
+                "/lib/{}/bootstrap/chart_values/{}.yaml",
+                get_helm_path_kubernetes_provider_sub_folder_name(
+                    chart.chart_values_path.helm_path(),
+                    HelmChartType::Shared
+                ),
+                KedaChart::chart_name(),
+            ),
+        );
+
+        // verify:
+        assert!(
+            missing_fields.is_none(),
+            "Some fields are missing in values file, add those (make sure they still exist in chart values), fields: {}",
+            missing_fields.unwrap_or_default().join(",")
+        );
+    }
+}
@@ -11,14 +11,18 @@ use time::Duration;
 pub mod cert_manager_chart;
 pub mod cert_manager_config_chart;
 pub mod coredns_config_chart;
+pub mod envoy_gateway_chart;
+pub mod envoy_gateway_config_chart;
 pub mod external_dns_chart;
 pub mod grafana_chart;
 pub mod k8s_event_logger;
+pub mod keda_chart;
 pub mod kube_prometheus_stack_chart;
 pub mod kube_state_metrics;
 pub mod loki_chart;
 pub mod metrics_server_chart;
 pub mod nginx_ingress_chart;
+pub mod opencost_chart;
 pub mod prometheus_adapter_chart;
 pub mod prometheus_operator_crds;
 pub mod promtail_chart;
@@ -28,6 +32,7 @@ pub mod qovery_priority_class_chart;
 pub mod qovery_shell_agent_chart;
 pub mod qovery_storage_class_chart;
 pub mod thanos;
+pub mod velero_chart;
 pub mod vertical_pod_autoscaler;
 
 pub enum HelmChartTimeout {
@@ -0,0 +1,183 @@
+use std::sync::Arc;
+
+use crate::errors::CommandError;
+use crate::helm::{
+    ChartInfo, ChartInstallationChecker, ChartSetValue, CommonChart, HelmChartError, HelmChartNamespaces,
+};
+use crate::infrastructure::helm_charts::{
+    HelmChartDirectoryLocation, HelmChartPath, HelmChartValuesFilePath, ToCommonHelmChart,
+};
+use crate::io_models::models::CustomerHelmChartsOverride;
+use kube::Client;
+
+/// Port of the OpenCost API, exposed by the `opencost` service
+pub const OPENCOST_API_PORT: u16 = 9003;
+
+pub struct OpenCostChart {
+    chart_path: HelmChartPath,
+    chart_values_path: HelmChartValuesFilePath,
+    cluster_id: String,
+    prometheus_url: String,
+    customer_helm_chart_override: Option<CustomerHelmChartsOverride>,
+}
+
+impl OpenCostChart {
+    pub fn new(
+        chart_prefix_path: Option<&str>,
+        cluster_id: String,
+        prometheus_url: String,
+        customer_helm_chart_fn: Arc<dyn Fn(String) -> Option<CustomerHelmChartsOverride>>,
+    ) -> OpenCostChart {
+        OpenCostChart {
+            chart_path: HelmChartPath::new(
+                chart_prefix_path,
+                HelmChartDirectoryLocation::CommonFolder,
+                OpenCostChart::chart_name(),
+            ),
+            chart_values_path: HelmChartValuesFilePath::new(
+                chart_prefix_path,
+                HelmChartDirectoryLocation::CommonFolder,
+                OpenCostChart::chart_name(),
+            ),
+            cluster_id,
+            prometheus_url,
+            customer_helm_chart_override: customer_helm_chart_fn(Self::chart_name()),
+        }
+    }
+
+    pub fn chart_name() -> String {
+        "opencost".to_string()
+    }
+}
+
+impl ToCommonHelmChart for OpenCostChart {
+    fn to_common_helm_chart(&self) -> Result<CommonChart, HelmChartError> {
+        Ok(CommonChart {
+            chart_info: ChartInfo {
+                name: OpenCostChart::chart_name(),
+                path: self.chart_path.to_string(),
+                namespace: HelmChartNamespaces::OpenCost,
+                values_files: vec![self.chart_values_path.to_string()],
+                values: vec![
+                    ChartSetValue {
+                        key: "opencost.exporter.defaultClusterId".to_string(),
+                        value: self.cluster_id.to_string(),
+                    },
+                    // costs are computed from the long term metrics, so they survive prometheus restarts
+                    ChartSetValue {
+                        key: "opencost.prometheus.external.url".to_string(),
+                        value: self.prometheus_url.to_string(),
+                    },
+                ],
+                yaml_files_content: match self.customer_helm_chart_override.clone() {
+                    Some(x) => vec![x.to_chart_values_generated()],
+                    None => vec![],
+                },
+                ..Default::default()
+            },
+            chart_installation_checker: Some(Box::new(OpenCostChartChecker::new())),
+            vertical_pod_autoscaler: None,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct OpenCostChartChecker {}
+
+impl OpenCostChartChecker {
+    pub fn new() -> OpenCostChartChecker {
+        OpenCostChartChecker {}
+    }
+}
+
+impl Default for OpenCostChartChecker {
+    fn default() -> Self {
+        OpenCostChartChecker::new()
+    }
+}
+
+impl ChartInstallationChecker for OpenCostChartChecker {
+    fn verify_installation(&self, _kube_client: &Client) -> Result<(), CommandError> {
+        // TODO: check the allocation API answers once the exporter is up
+        Ok(())
+    }
+
+    fn clone_dyn(&self) -> Box<dyn ChartInstallationChecker> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::infrastructure::helm_charts::opencost_chart::OpenCostChart;
+    use crate::infrastructure::helm_charts::{
+        HelmChartType, ToCommonHelmChart, get_helm_path_kubernetes_provider_sub_folder_name,
+        get_helm_values_set_in_code_but_absent_in_values_file,
+    };
+    use std::env;
+    use std::sync::Arc;
+
+    fn opencost_chart() -> OpenCostChart {
+        OpenCostChart::new(
+            None,
+            "whatever".to_string(),
+            "http://thanos-query.prometheus.svc.cluster.local:9090".to_string(),
+            Arc::new(|_| None),
+        )
+    }
+
+    /// Makes sure chart values file exists.
+    #[test]
+    fn opencost_chart_values_file_exists_test() {
+        // setup:
+        let chart = opencost_chart();
+
+        let current_directory = env::current_dir().expect("Impossible to get current directory");
+        let chart_values_path = format!(
+            "{}/lib/{}/bootstrap/chart_values/{}.yaml",
+            current_directory
+                .to_str()
+                .expect("Impossible to convert current directory to string"),
+            get_helm_path_kubernetes_provider_sub_folder_name(
+                chart.chart_values_path.helm_path(),
+                HelmChartType::Shared
+            ),
+            OpenCostChart::chart_name(),
+        );
+
+        // execute
+        let values_file = std::fs::File::open(&chart_values_path);
+
+        // verify:
+        assert!(values_file.is_ok(), "Chart values file should exist: `{chart_values_path}`");
+    }
+
+    /// Make sure rust code doesn't set a value not declared inside values file.
+    /// All values should be declared / set in values file unless it needs to be injected via rust code.
+    #[test]
+    fn opencost_chart_rust_overridden_values_exists_in_values_yaml_test() {
+        // setup:
+        let chart = opencost_chart();
+        let common_chart = chart.to_common_helm_chart().unwrap();
+
+        // execute:
+        let missing_fields = get_helm_values_set_in_code_but_absent_in_values_file(
+            common_chart,
+            format!(
+                "/lib/{}/bootstrap/chart_values/{}.yaml",
+                get_helm_path_kubernetes_provider_sub_folder_name(
+                    chart.chart_values_path.helm_path(),
+                    HelmChartType::Shared
+                ),
+                OpenCostChart::chart_name(),
+            ),
+        );
+
+        // verify:
+        assert!(
+            missing_fields.is_none(),
+            "Some fields are missing in values file, add those (make sure they still exist in chart values), fields: {}",
+            missing_fields.unwrap_or_default().join(",")
+        );
+    }
+}
@@ -0,0 +1,385 @@
+use std::sync::Arc;
+
+use crate::errors::CommandError;
+use crate::helm::{
+    ChartInfo, ChartInstallationChecker, ChartSetValue, ChartValuesGenerated, CommonChart, HelmChartError,
+    HelmChartNamespaces,
+};
+use crate::infrastructure::helm_charts::{
+    HelmChartDirectoryLocation, HelmChartPath, HelmChartValuesFilePath, ToCommonHelmChart,
+};
+use crate::io_models::models::CustomerHelmChartsOverride;
+use crate::runtime::block_on;
+use kube::api::{ApiResource, DynamicObject, GroupVersionKind};
+use kube::{Api, Client};
+use retry::OperationResult;
+use retry::delay::Fixed;
+use std::time::Duration;
+
+/// Schedule name of the cluster backups, backups created by it are prefixed by this name
+pub const VELERO_BACKUP_SCHEDULE_NAME: &str = "qovery-cluster-backup";
+
+/// Namespaces of Kubernetes and of Qovery components, they are re-created by the engine so only environments
+/// namespaces are backed up and can be restored
+pub const VELERO_EXCLUDED_NAMESPACES: &[&str] = &[
+    "default",
+    "kube-node-lease",
+    "kube-public",
+    "kube-system",
+    "qovery",
+    "logging",
+    "prometheus",
+    "cert-manager",
+    "nginx-ingress",
+    "keda",
+    "envoy-gateway-system",
+    "opencost",
+    "velero",
+];
+
+#[derive(Clone)]
+pub enum VeleroStorageConfiguration {
+    /// Backups are stored in S3 and volumes are snapshotted as EBS snapshots
+    AwsS3 {
+        region: String,
+        bucket_name: String,
+        aws_iam_velero_role_arn: String,
+    },
+    /// Backups are stored in an S3 compatible bucket, volumes content is copied in the bucket by the node agent
+    ScalewayObjectStorage {
+        region: String,
+        endpoint: String,
+        bucket_name: String,
+        access_key: String,
+        secret_key: String,
+    },
+}
+
+pub struct VeleroChart {
+    chart_path: HelmChartPath,
+    chart_values_path: HelmChartValuesFilePath,
+    storage_configuration: VeleroStorageConfiguration,
+    backup_schedule: String,
+    backup_retention_in_days: u32,
+    customer_helm_chart_override: Option<CustomerHelmChartsOverride>,
+}
+
+impl VeleroChart {
+    pub fn new(
+        chart_prefix_path: Option<&str>,
+        storage_configuration: VeleroStorageConfiguration,
+        backup_schedule: String,
+        backup_retention_in_days: u32,
+        customer_helm_chart_fn: Arc<dyn Fn(String) -> Option<CustomerHelmChartsOverride>>,
+    ) -> VeleroChart {
+        VeleroChart {
+            chart_path: HelmChartPath::new(
+                chart_prefix_path,
+                HelmChartDirectoryLocation::CommonFolder,
+                VeleroChart::chart_name(),
+            ),
+            chart_values_path: HelmChartValuesFilePath::new(
+                chart_prefix_path,
+                HelmChartDirectoryLocation::CommonFolder,
+                VeleroChart::chart_name(),
+            ),
+            storage_configuration,
+            backup_schedule,
+            backup_retention_in_days,
+            customer_helm_chart_override: customer_helm_chart_fn(Self::chart_name()),
+        }
+    }
+
+    pub fn chart_name() -> String {
+        "velero".to_string()
+    }
+
+    // Locations are lists in the chart, they can't be partially overridden with set values
+    fn locations_yaml(&self) -> String {
+        match &self.storage_configuration {
+            VeleroStorageConfiguration::AwsS3 {
+                region, bucket_name, ..
+            } => format!(
+                r#"configuration:
+  backupStorageLocation:
+    - name: default
+      provider: aws
+      bucket: {bucket_name}
+      default: true
+      config:
+        region: {region}
+  volumeSnapshotLocation:
+    - name: default
+      provider: aws
+      config:
+        region: {region}
+"#
+            ),
+            VeleroStorageConfiguration::ScalewayObjectStorage {
+                region,
+                endpoint,
+                bucket_name,
+                access_key,
+                secret_key,
+            } => format!(
+                r#"configuration:
+  backupStorageLocation:
+    - name: default
+      provider: aws
+      bucket: {bucket_name}
+      default: true
+      config:
+        region: {region}
+        s3Url: {endpoint}
+        s3ForcePathStyle: "true"
+credentials:
+  secretContents:
+    cloud: |
+      [default]
+      aws_access_key_id={access_key}
+      aws_secret_access_key={secret_key}
+"#
+            ),
+        }
+    }
+}
+
+impl ToCommonHelmChart for VeleroChart {
+    fn to_common_helm_chart(&self) -> Result<CommonChart, HelmChartError> {
+        let mut values = vec![
+            ChartSetValue {
+                key: format!("schedules.{VELERO_BACKUP_SCHEDULE_NAME}.schedule"),
+                value: self.backup_schedule.to_string(),
+            },
+            ChartSetValue {
+                key: format!("schedules.{VELERO_BACKUP_SCHEDULE_NAME}.template.ttl"),
+                value: format!("{}h", self.backup_retention_in_days * 24),
+            },
+            ChartSetValue {
+                key: format!("schedules.{VELERO_BACKUP_SCHEDULE_NAME}.template.excludedNamespaces"),
+                value: format!("{{{}}}", VELERO_EXCLUDED_NAMESPACES.join(",")),
+            },
+        ];
+        match &self.storage_configuration {
+            VeleroStorageConfiguration::AwsS3 {
+                aws_iam_velero_role_arn,
+                ..
+            } => values.extend([
+                ChartSetValue {
+                    key: r"serviceAccount.server.annotations.eks\.amazonaws\.com/role-arn".to_string(),
+                    value: aws_iam_velero_role_arn.to_string(),
+                },
+                ChartSetValue {
+                    key: "snapshotsEnabled".to_string(),
+                    value: "true".to_string(),
+                },
+            ]),
+            // volumes can't be snapshotted through the S3 API, their content is backed up by the node agent
+            VeleroStorageConfiguration::ScalewayObjectStorage { .. } => values.extend([
+                ChartSetValue {
+                    key: "credentials.useSecret".to_string(),
+                    value: "true".to_string(),
+                },
+                ChartSetValue {
+                    key: "snapshotsEnabled".to_string(),
+                    value: "false".to_string(),
+                },
+                ChartSetValue {
+                    key: "deployNodeAgent".to_string(),
+                    value: "true".to_string(),
+                },
+                ChartSetValue {
+                    key: "configuration.defaultVolumesToFsBackup".to_string(),
+                    value: "true".to_string(),
+                },
+            ]),
+        }
+
+        let mut yaml_files_content = vec![ChartValuesGenerated::new(
+            "qovery_velero_locations".to_string(),
+            self.locations_yaml(),
+        )];
+        if let Some(customer_override) = &self.customer_helm_chart_override {
+            yaml_files_content.push(customer_override.to_chart_values_generated());
+        }
+
+        Ok(CommonChart {
+            chart_info: ChartInfo {
+                name: VeleroChart::chart_name(),
+                path: self.chart_path.to_string(),
+                namespace: HelmChartNamespaces::Velero,
+                values_files: vec![self.chart_values_path.to_string()],
+                values,
+                yaml_files_content,
+                ..Default::default()
+            },
+            chart_installation_checker: Some(Box::new(VeleroChartChecker::new())),
+            vertical_pod_autoscaler: None,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct VeleroChartChecker {}
+
+impl VeleroChartChecker {
+    pub fn new() -> VeleroChartChecker {
+        VeleroChartChecker {}
+    }
+}
+
+impl Default for VeleroChartChecker {
+    fn default() -> Self {
+        VeleroChartChecker::new()
+    }
+}
+
+impl ChartInstallationChecker for VeleroChartChecker {
+    fn verify_installation(&self, kube_client: &Client) -> Result<(), CommandError> {
+        // velero validates its locations once started, nothing can be backed up until the default one is available
+        let locations: Api<DynamicObject> = Api::namespaced_with(
+            kube_client.clone(),
+            &HelmChartNamespaces::Velero.to_string(),
+            &ApiResource::from_gvk(&GroupVersionKind::gvk("velero.io", "v1", "BackupStorageLocation")),
+        );
+
+        let result = retry::retry(Fixed::from(Duration::from_secs(10)).take(30), || {
+            let location = match block_on(locations.get_opt("default")) {
+                Ok(location) => location,
+                Err(e) => {
+                    return OperationResult::Retry(CommandError::new(
+                        "Cannot get velero default backup storage location".to_string(),
+                        Some(e.to_string()),
+                        None,
+                    ));
+                }
+            };
+            let status = location.map(|it| it.data["status"].clone()).unwrap_or_default();
+            match status["phase"].as_str() {
+                Some("Available") => OperationResult::Ok(()),
+                phase => OperationResult::Retry(CommandError::new(
+                    format!(
+                        "Velero default backup storage location is not available, its phase is `{}`",
+                        phase.unwrap_or("unknown")
+                    ),
+                    Some(status["message"].to_string()),
+                    None,
+                )),
+            }
+        });
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(retry::Error { error, .. }) => Err(error),
+        }
+    }
+
+    fn clone_dyn(&self) -> Box<dyn ChartInstallationChecker> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::infrastructure::helm_charts::velero_chart::{VeleroChart, VeleroStorageConfiguration};
+    use crate::infrastructure::helm_charts::{
+        HelmChartType, ToCommonHelmChart, get_helm_path_kubernetes_provider_sub_folder_name,
+        get_helm_values_set_in_code_but_absent_in_values_file,
+    };
+    use std::env;
+    use std::sync::Arc;
+
+    fn velero_chart(storage_configuration: VeleroStorageConfiguration) -> VeleroChart {
+        VeleroChart::new(None, storage_configuration, "0 3 * * *".to_string(), 7, Arc::new(|_| None))
+    }
+
+    fn storage_configurations() -> Vec<VeleroStorageConfiguration> {
+        vec![
+            VeleroStorageConfiguration::AwsS3 {
+                region: "whatever".to_string(),
+                bucket_name: "whatever".to_string(),
+                aws_iam_velero_role_arn: "whatever".to_string(),
+            },
+            VeleroStorageConfiguration::ScalewayObjectStorage {
+                region: "whatever".to_string(),
+                endpoint: "whatever".to_string(),
+                bucket_name: "whatever".to_string(),
+                access_key: "whatever".to_string(),
+                secret_key: "whatever".to_string(),
+            },
+        ]
+    }
+
+    /// Makes sure chart values file exists.
+    #[test]
+    fn velero_chart_values_file_exists_test() {
+        // setup:
+        let chart = velero_chart(storage_configurations().remove(0));
+
+        let current_directory = env::current_dir().expect("Impossible to get current directory");
+        let chart_values_path = format!(
+            "{}/lib/{}/bootstrap/chart_values/{}.yaml",
+            current_directory
+                .to_str()
+                .expect("Impossible to convert current directory to string"),
+            get_helm_path_kubernetes_provider_sub_folder_name(
+                chart.chart_values_path.helm_path(),
+                HelmChartType::Shared
+            ),
+            VeleroChart::chart_name(),
+        );
+
+        // execute
+        let values_file = std::fs::File::open(&chart_values_path);
+
+        // verify:
+        assert!(values_file.is_ok(), "Chart values file should exist: `{chart_values_path}`");
+    }
+
+    /// Make sure rust code doesn't set a value not declared inside values file.
+    /// All values should be declared / set in values file unless it needs to be injected via rust code.
+    #[test]
+    fn velero_chart_rust_overridden_values_exists_in_values_yaml_test() {
+        for storage_configuration in storage_configurations() {
+            // setup:
+            let chart = velero_chart(storage_configuration);
+            let common_chart = chart.to_common_helm_chart().unwrap();
+
+            // execute:
+            let missing_fields = get_helm_values_set_in_code_but_absent_in_values_file(
+                common_chart,
+                format!(
+                    "/lib/{}/bootstrap/chart_values/{}.yaml",
+                    get_helm_path_kubernetes_provider_sub_folder_name(
+                        chart.chart_values_path.helm_path(),
+                        HelmChartType::Shared
+                    ),
+                    VeleroChart::chart_name(),
+                ),
+            );
+
+            // verify:
+            assert!(
+                missing_fields.is_none(),
+                "Some fields are missing in values file, add those (make sure they still exist in chart values), fields: {}",
+                missing_fields.unwrap_or_default().join(",")
+            );
+        }
+    }
+
+    /// Make sure generated locations are valid yaml.
+    #[test]
+    fn velero_chart_locations_yaml_is_valid_test() {
+        for storage_configuration in storage_configurations() {
+            let chart = velero_chart(storage_configuration);
+
+            let locations: serde_yaml::Value =
+                serde_yaml::from_str(&chart.locations_yaml()).expect("locations should be valid yaml");
+
+            assert_eq!(
+                locations["configuration"]["backupStorageLocation"][0]["bucket"].as_str(),
+                Some("whatever")
+            );
+        }
+    }
+}
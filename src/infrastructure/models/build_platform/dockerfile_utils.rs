@@ -32,6 +32,90 @@ pub fn extract_dockerfile_args(dockerfile_content: Vec<u8>) -> Result<HashSet<St
     Ok(used_args)
 }
 
+/// Dockerfile instructions, with lines ending with a `\` joined to the following ones.
+/// Comment lines in the middle of an instruction are dropped, as Docker does
+fn dockerfile_instructions(dockerfile_content: &str) -> Vec<String> {
+    let mut instructions = vec![];
+    let mut current_instruction: Option<String> = None;
+
+    for line in dockerfile_content.lines() {
+        let trimmed_line = line.trim();
+        if current_instruction.is_some() && trimmed_line.starts_with('#') {
+            continue;
+        }
+
+        let (content, continued) = match trimmed_line.strip_suffix('\\') {
+            Some(content) => (content, true),
+            None => (trimmed_line, false),
+        };
+        let instruction = match current_instruction.take() {
+            Some(instruction) => format!("{instruction} {content}"),
+            None => content.to_string(),
+        };
+
+        match continued {
+            true => current_instruction = Some(instruction),
+            false => instructions.push(instruction),
+        }
+    }
+    instructions.extend(current_instruction);
+
+    instructions
+}
+
+/// Extract ids of BuildKit secrets mounted by RUN instructions from a Dockerfile content
+/// E.g
+/// ```dockerfile
+/// FROM node
+///
+/// RUN --mount=type=secret,id=NPM_TOKEN npm install
+/// RUN --mount=type=cache,target=/root/.cache --mount=required,id=API_KEY,type=secret ./build.sh
+/// RUN --mount=type=secret,target=/run/secrets/GITHUB_TOKEN \
+///     ./fetch.sh
+/// ...
+/// ```
+///
+/// will return a set of "NPM_TOKEN", "API_KEY" and "GITHUB_TOKEN" strings.
+/// Like BuildKit, the id of a secret mount defaults to the basename of its target
+pub fn extract_dockerfile_secrets(dockerfile_content: Vec<u8>) -> Result<HashSet<String>, Utf8Error> {
+    let content = std::str::from_utf8(dockerfile_content.as_slice())?;
+
+    let used_secrets = dockerfile_instructions(content)
+        .iter()
+        .filter(|instruction| instruction.to_uppercase().starts_with("RUN "))
+        .flat_map(|instruction| {
+            instruction
+                .split_whitespace()
+                .filter_map(|word| word.strip_prefix("--mount="))
+                .filter_map(|mount| {
+                    let options = mount.split(',').collect::<Vec<&str>>();
+                    if !options.contains(&"type=secret") {
+                        return None;
+                    }
+
+                    let id = options.iter().find_map(|option| option.strip_prefix("id="));
+                    let target = || {
+                        options.iter().find_map(|option| {
+                            ["target=", "dst=", "destination="]
+                                .iter()
+                                .find_map(|prefix| option.strip_prefix(prefix))
+                        })
+                    };
+                    match id {
+                        Some(id) => Some(id.to_string()),
+                        None => target()
+                            .and_then(|target| target.trim_end_matches('/').rsplit('/').next())
+                            .filter(|name| !name.is_empty())
+                            .map(|name| name.to_string()),
+                    }
+                })
+                .collect::<Vec<String>>()
+        })
+        .collect::<HashSet<String>>();
+
+    Ok(used_secrets)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -67,6 +151,88 @@ mod tests {
         assert_eq!(res.unwrap().len(), 0);
     }
 
+    #[test]
+    fn test_extract_dockerfile_secrets() {
+        let dockerfile = b"
+        FROM node
+
+        ARG foo
+        RUN --mount=type=secret,id=NPM_TOKEN npm install
+        RUN --mount=type=cache,target=/root/.npm --mount=required,id=API_KEY,type=secret ./build.sh
+        RUN --mount=type=cache,id=cache_id,target=/tmp ls -lh
+        run --mount=type=secret,id=lower_case_run cat /run/secrets/lower_case_run
+        COPY . .
+        ";
+
+        let res = extract_dockerfile_secrets(dockerfile.to_vec()).unwrap();
+        assert_eq!(
+            res,
+            HashSet::from([
+                "NPM_TOKEN".to_string(),
+                "API_KEY".to_string(),
+                "lower_case_run".to_string()
+            ])
+        );
+
+        let dockerfile = b"
+        FROM node
+
+        ARG NPM_TOKEN
+        RUN ls -lh
+        ";
+
+        let res = extract_dockerfile_secrets(dockerfile.to_vec());
+        assert_eq!(res.unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_extract_dockerfile_secrets_multi_lines() {
+        let dockerfile = b"
+        FROM node
+
+        RUN \\
+            --mount=type=secret,id=NPM_TOKEN \\
+            # comments are allowed in the middle of an instruction
+            --mount=type=secret,id=SENTRY_TOKEN \\
+            npm install
+        RUN --mount=type=cache,target=/root/.npm \\
+          --mount=type=secret,id=API_KEY npm run build
+        COPY --mount=type=secret,id=NOT_A_RUN . .
+        ";
+
+        let res = extract_dockerfile_secrets(dockerfile.to_vec()).unwrap();
+        assert_eq!(
+            res,
+            HashSet::from([
+                "NPM_TOKEN".to_string(),
+                "SENTRY_TOKEN".to_string(),
+                "API_KEY".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_extract_dockerfile_secrets_id_from_target() {
+        let dockerfile = b"
+        FROM node
+
+        RUN --mount=type=secret,target=/run/secrets/GITHUB_TOKEN ./fetch.sh
+        RUN --mount=type=secret,dst=/root/.aws/credentials aws s3 ls
+        RUN --mount=type=secret,id=EXPLICIT_ID,target=/run/secrets/ignored ./build.sh
+        RUN --mount=type=secret ./no_id_nor_target.sh
+        ";
+
+        let res = extract_dockerfile_secrets(dockerfile.to_vec()).unwrap();
+        assert_eq!(
+            res,
+            HashSet::from([
+                "GITHUB_TOKEN".to_string(),
+                "credentials".to_string(),
+                "EXPLICIT_ID".to_string()
+            ])
+        );
+    }
+
     #[test]
     fn test_match_used_env_var_args() {
         let dockerfile = b"
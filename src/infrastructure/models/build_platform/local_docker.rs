@@ -9,6 +9,8 @@ use std::time::Duration;
 use std::{fs, thread};
 
 use git2::{Cred, CredentialType, ErrorClass};
+use itertools::Itertools;
+use once_cell::sync::OnceCell;
 use retry::OperationResult;
 use retry::delay::Fibonacci;
 use time::Instant;
@@ -16,17 +18,23 @@ use uuid::Uuid;
 
 use crate::cmd::command::CommandKiller;
 use crate::cmd::docker;
-use crate::cmd::docker::{Architecture, BuilderHandle, ContainerImage};
+use crate::cmd::docker::{Architecture, BuildCache, BuilderHandle, ContainerImage};
 use crate::cmd::git_lfs::{GitLfs, GitLfsError};
+use crate::engine_task::qovery_api::EngineServiceType;
 use crate::environment::report::logger::EnvLogger;
-use crate::infrastructure::models::build_platform::dockerfile_utils::extract_dockerfile_args;
-use crate::infrastructure::models::build_platform::{Build, BuildError, BuildPlatform, Kind, to_build_error};
+use crate::infrastructure::models::build_platform::dockerfile_utils::{
+    extract_dockerfile_args, extract_dockerfile_secrets,
+};
+use crate::infrastructure::models::build_platform::{
+    Build, BuildCacheBackendKind, BuildCacheConfig, BuildError, BuildPlatform, GitCmd, Kind, to_build_error,
+};
 
 use crate::cmd::git;
 use crate::environment::models::abort::Abort;
 use crate::fs::workspace_directory;
 use crate::io_models::container::Registry;
 use crate::io_models::context::Context;
+use crate::io_models::models::CpuArchitecture;
 use crate::metrics_registry::{MetricsRegistry, StepLabel, StepName, StepStatus};
 use crate::utilities::to_short_id;
 
@@ -47,10 +55,14 @@ pub struct LocalDocker {
     name: String,
     builder_counter: AtomicUsize,
     metrics_registry: Box<dyn MetricsRegistry>,
+    build_cache: BuildCacheConfig,
+    // Asked once to the API, for the labels of all the images built
+    engine_version: OnceCell<Option<String>>,
 }
 
 const MAX_GIT_LFS_SIZE_GB: u64 = 5;
 const MAX_GIT_LFS_SIZE_KB: u64 = MAX_GIT_LFS_SIZE_GB * 1024 * 1024; // 5GB
+const MAX_ARCHITECTURE_BUILD_ATTEMPTS: u32 = 2;
 
 impl LocalDocker {
     pub fn new(
@@ -58,6 +70,7 @@ impl LocalDocker {
         long_id: Uuid,
         name: &str,
         metrics_registry: Box<dyn MetricsRegistry>,
+        build_cache: BuildCacheConfig,
     ) -> Result<Self, BuildError> {
         Ok(LocalDocker {
             context,
@@ -66,9 +79,43 @@ impl LocalDocker {
             name: name.to_string(),
             builder_counter: AtomicUsize::new(0),
             metrics_registry,
+            build_cache,
+            engine_version: OnceCell::new(),
         })
     }
 
+    fn build_cache(&self, build: &Build) -> Result<BuildCache, BuildError> {
+        let registry_cache = |image_name: String| {
+            BuildCache::Registry(ContainerImage::new(
+                build.image.registry_url.clone(),
+                image_name,
+                vec!["cache".to_string()],
+            ))
+        };
+
+        match self.cache_backend(build) {
+            BuildCacheBackendKind::Registry => Ok(registry_cache(build.image.name())),
+            BuildCacheBackendKind::DedicatedRegistry => Ok(registry_cache(build.image.cache_image_name())),
+            BuildCacheBackendKind::ObjectStorage => match &self.build_cache.object_storage {
+                Some(storage) => Ok(BuildCache::ObjectStorage {
+                    storage: storage.clone(),
+                    name: build.image.name(),
+                }),
+                None => Err(BuildError::InvalidConfig {
+                    application: build.image.service_id.clone(),
+                    raw_error_message: "Object storage build cache is requested, but no object storage is configured for the build platform".to_string(),
+                }),
+            },
+            BuildCacheBackendKind::LocalDirectory => match &self.build_cache.local_directory {
+                Some(directory) => Ok(BuildCache::LocalDirectory(directory.join(build.image.name()))),
+                None => Err(BuildError::InvalidConfig {
+                    application: build.image.service_id.clone(),
+                    raw_error_message: "Local directory build cache is requested, but no directory is configured for the build platform".to_string(),
+                }),
+            },
+        }
+    }
+
     fn build_image_with_docker(
         &self,
         build: &mut Build,
@@ -87,8 +134,10 @@ impl LocalDocker {
             action_description: "reading dockerfile content".to_string(),
             raw_error: err,
         })?;
-        let dockerfile_args = match extract_dockerfile_args(dockerfile_content) {
-            Ok(dockerfile_args) => dockerfile_args,
+        let (dockerfile_args, dockerfile_secrets) = match extract_dockerfile_args(dockerfile_content.clone())
+            .and_then(|args| extract_dockerfile_secrets(dockerfile_content).map(|secrets| (args, secrets)))
+        {
+            Ok(dockerfile_args_and_secrets) => dockerfile_args_and_secrets,
             Err(err) => {
                 build_record.stop(StepStatus::Error);
                 return Err(BuildError::InvalidConfig {
@@ -98,9 +147,29 @@ impl LocalDocker {
             }
         };
 
-        // Keep only the env variables we want for our build
+        // Secrets only consumed through an ARG are still passed as build args, to not break existing Dockerfiles.
+        // But their value ends up in the image history, so warn the user to mount them instead
+        let secrets_used_as_args: Vec<String> = build
+            .secret_environment_variables
+            .keys()
+            .filter(|k| dockerfile_args.contains(*k) && !dockerfile_secrets.contains(*k))
+            .cloned()
+            .collect();
+        for key in secrets_used_as_args {
+            logger.send_warning(format!(
+                "⚠️ Secret variable `{key}` is passed as a build argument and will be visible in the image history. Use `RUN --mount=type=secret,id={key}` in your Dockerfile instead of `ARG {key}`"
+            ));
+            if let Some(value) = build.secret_environment_variables.remove(&key) {
+                build.environment_variables.insert(key, value);
+            }
+        }
+
+        // Keep only the env variables and secrets we want for our build
         // and force re-compute the image tag
         build.environment_variables.retain(|k, _| dockerfile_args.contains(k));
+        build
+            .secret_environment_variables
+            .retain(|k, _| dockerfile_secrets.contains(k));
         build.compute_image_tag();
 
         // Prepare image we want to build
@@ -110,8 +179,13 @@ impl LocalDocker {
             vec![build.image.tag.clone(), "latest".to_string()],
         );
 
-        let image_cache =
-            ContainerImage::new(build.image.registry_url.clone(), build.image.name(), vec!["cache".to_string()]);
+        let image_cache = match self.build_cache(build) {
+            Ok(image_cache) => image_cache,
+            Err(err) => {
+                build_record.stop(StepStatus::Error);
+                return Err(err);
+            }
+        };
 
         // Login to the registry at repository level if needed
         let login_ret = retry::retry(Fibonacci::from(Duration::from_secs(1)).take(4), || {
@@ -139,10 +213,21 @@ impl LocalDocker {
         let image_name = image_to_build.image_name();
         logger.send_progress(format!("🕵️ Checking if image already exists remotely {image_name}"));
         if let Ok(true) = self.context.docker.does_image_exist_remotely(&image_to_build) {
-            logger.send_progress(format!("🎯 Skipping build. Image already exists in the registry {image_name}"));
-            build_record.stop(StepStatus::Skip);
-            // skip build
-            return Ok(());
+            match self.missing_architectures(build, &image_to_build) {
+                Ok(missing_architectures) if !missing_architectures.is_empty() => {
+                    logger.send_warning(format!(
+                        "⚠️ Image {image_name} already exists but lacks architectures {}, rebuilding it",
+                        missing_architectures.iter().join(", ")
+                    ));
+                }
+                _ => {
+                    logger
+                        .send_progress(format!("🎯 Skipping build. Image already exists in the registry {image_name}"));
+                    build_record.stop(StepStatus::Skip);
+                    // skip build
+                    return Ok(());
+                }
+            }
         }
 
         logger.send_progress(format!("⛏️ Building image. It does not exist remotely {image_name}"));
@@ -200,6 +285,17 @@ impl LocalDocker {
             .iter()
             .map(|(k, v)| (k.as_str(), v.as_str()))
             .collect();
+        let secrets: Vec<(&str, &str)> = build
+            .secret_environment_variables
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+
+        let engine_version = self
+            .engine_version
+            .get_or_init(|| self.context.qovery_api.service_version(EngineServiceType::Engine).ok());
+        let image_labels = build.image_labels(engine_version.as_deref());
+        let labels: Vec<(&str, &str)> = image_labels.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
 
         let arch: Vec<Architecture> = build
             .architectures
@@ -210,29 +306,282 @@ impl LocalDocker {
         let builder_handle =
             self.provision_builder(build, |line| logger.send_progress(line), &CommandKiller::from_cancelable(abort))?;
 
-        let exit_status = self.context.docker.build(
-            &builder_handle.builder_name.as_deref(),
-            Path::new(dockerfile_complete_path),
-            Path::new(into_dir_docker_style),
-            &image_to_build,
-            &env_vars,
-            &image_cache,
-            true,
-            &arch,
-            &mut |line| logger.send_progress(line),
-            &mut |line| logger.send_progress(line),
-            &CommandKiller::from(build.timeout, abort),
-            build.git_repository.docker_target_build_stage.as_ref(),
-        );
+        let cached_steps = AtomicUsize::new(0);
+        let build_ret = if arch.len() > 1 {
+            self.build_per_architecture(
+                build,
+                &builder_handle,
+                Path::new(dockerfile_complete_path),
+                Path::new(into_dir_docker_style),
+                &image_to_build,
+                &env_vars,
+                &secrets,
+                &labels,
+                &image_cache,
+                &arch,
+                &cached_steps,
+                logger,
+                metrics_registry.as_ref(),
+                abort,
+            )
+        } else {
+            let on_output = |line: String| {
+                count_cached_step(&line, &cached_steps);
+                logger.send_progress(line);
+            };
+            self.context
+                .docker
+                .build(
+                    &builder_handle.builder_name.as_deref(),
+                    Path::new(dockerfile_complete_path),
+                    Path::new(into_dir_docker_style),
+                    &image_to_build,
+                    &env_vars,
+                    &secrets,
+                    &labels,
+                    build.provenance,
+                    &image_cache,
+                    true,
+                    &arch,
+                    &mut |line| on_output(line),
+                    &mut |line| on_output(line),
+                    &CommandKiller::from(build.timeout, abort),
+                    build.git_repository.docker_target_build_stage.as_ref(),
+                )
+                .map_err(|err| to_build_error(build.image.service_id.clone(), err))
+        };
 
-        if let Err(err) = exit_status {
+        if let Err(err) = build_ret {
             build_record.stop(StepStatus::Error);
-            return Err(to_build_error(build.image.service_id.clone(), err));
+            return Err(err);
+        }
+
+        // Make sure the pushed manifest contains every requested platform, before anything gets deployed with it
+        if !build.architectures.is_empty() {
+            logger.send_progress(format!(
+                "🔎 Verifying image {image_name} is available for {}",
+                build.architectures.iter().join(", ")
+            ));
+            match self.missing_architectures(build, &image_to_build) {
+                Ok(missing_architectures) if missing_architectures.is_empty() => {}
+                Ok(missing_architectures) => {
+                    build_record.stop(StepStatus::Error);
+                    return Err(BuildError::MissingArchitectures {
+                        application: build.image.service_id.clone(),
+                        image: image_name,
+                        missing_architectures,
+                    });
+                }
+                Err(err) => {
+                    logger.send_warning(format!("⚠️ Cannot verify the architectures of image {image_name}: {err}"))
+                }
+            }
         }
         build_record.stop(StepStatus::Success);
+
+        let cached_steps = cached_steps.load(Ordering::Relaxed);
+        let cache_step_name = if cached_steps > 0 {
+            logger.send_progress(format!("♻️ {cached_steps} build steps restored from the cache"));
+            StepName::BuildCacheHit
+        } else {
+            StepName::BuildCacheMiss
+        };
+        metrics_registry
+            .start_record(build.image.service_long_id, StepLabel::Service, cache_step_name)
+            .stop(StepStatus::Success);
+
         Ok(())
     }
 
+    // Each architecture is built in parallel on its own builder node and pushed by digest, without any tag,
+    // then the manifest list is assembled from them. So a failing architecture is retried and reported on its own
+    fn build_per_architecture(
+        &self,
+        build: &Build,
+        builder_handle: &BuilderHandle,
+        dockerfile: &Path,
+        context: &Path,
+        image_to_build: &ContainerImage,
+        env_vars: &[(&str, &str)],
+        secrets: &[(&str, &str)],
+        labels: &[(&str, &str)],
+        image_cache: &BuildCache,
+        architectures: &[Architecture],
+        cached_steps: &AtomicUsize,
+        logger: &EnvLogger,
+        metrics_registry: &dyn MetricsRegistry,
+        abort: &dyn Abort,
+    ) -> Result<(), BuildError> {
+        let results: Vec<Result<ContainerImage, docker::DockerError>> = thread::scope(|scope| {
+            let handles = architectures
+                .iter()
+                .map(|arch| {
+                    let arch = *arch;
+                    scope.spawn(move || {
+                        self.build_architecture(
+                            build,
+                            builder_handle,
+                            dockerfile,
+                            context,
+                            image_to_build,
+                            env_vars,
+                            secrets,
+                            labels,
+                            &image_cache.for_architecture(arch),
+                            arch,
+                            cached_steps,
+                            logger,
+                            metrics_registry,
+                            abort,
+                        )
+                    })
+                })
+                .collect_vec();
+
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle.join().unwrap_or_else(|_| {
+                        Err(docker::DockerError::InvalidConfig {
+                            raw_error_message: "architecture build thread panicked".to_string(),
+                        })
+                    })
+                })
+                .collect()
+        });
+
+        let mut arch_images = Vec::with_capacity(architectures.len());
+        for (arch, result) in architectures.iter().zip(results) {
+            match result {
+                Ok(arch_image) => {
+                    logger.send_progress(format!("✅ [{arch}] Image built and pushed"));
+                    arch_images.push(arch_image);
+                }
+                Err(err) if err.is_aborted() => {
+                    return Err(BuildError::Aborted {
+                        application: build.image.service_id.clone(),
+                    });
+                }
+                Err(err) => {
+                    return Err(BuildError::ArchitectureDockerError {
+                        application: build.image.service_id.clone(),
+                        architecture: CpuArchitecture::from(*arch),
+                        raw_error: err,
+                    });
+                }
+            }
+        }
+
+        logger.send_progress(format!(
+            "🧩 Assembling multi-architecture image {} for {}",
+            image_to_build.image_name(),
+            architectures.iter().join(", ")
+        ));
+        self.context
+            .docker
+            .create_multi_arch_manifest(
+                image_to_build,
+                &arch_images,
+                &mut |line| logger.send_progress(line),
+                &mut |line| logger.send_progress(line),
+                &CommandKiller::from_cancelable(abort),
+            )
+            .map_err(|err| to_build_error(build.image.service_id.clone(), err))
+    }
+
+    fn build_architecture(
+        &self,
+        build: &Build,
+        builder_handle: &BuilderHandle,
+        dockerfile: &Path,
+        context: &Path,
+        image_to_build: &ContainerImage,
+        env_vars: &[(&str, &str)],
+        secrets: &[(&str, &str)],
+        labels: &[(&str, &str)],
+        arch_cache: &BuildCache,
+        arch: Architecture,
+        cached_steps: &AtomicUsize,
+        logger: &EnvLogger,
+        metrics_registry: &dyn MetricsRegistry,
+        abort: &dyn Abort,
+    ) -> Result<ContainerImage, docker::DockerError> {
+        let arch_record = metrics_registry.start_record(
+            build.image.service_long_id,
+            StepLabel::Service,
+            StepName::BuildArchitecture(CpuArchitecture::from(arch)),
+        );
+        let on_output = |line: String| {
+            count_cached_step(&line, cached_steps);
+            logger.send_progress(format!("[{arch}] {line}"));
+        };
+        let should_abort = CommandKiller::from(build.timeout, abort);
+
+        let mut attempt = 1;
+        let ret = loop {
+            // A build failing because of the Dockerfile or the code fails the same way on every attempt,
+            // so only the failures of the builder itself are retried
+            let mut transient_error = false;
+            let ret = self.context.docker.build_and_push_by_digest(
+                &builder_handle.builder_name.as_deref(),
+                dockerfile,
+                context,
+                image_to_build,
+                env_vars,
+                secrets,
+                labels,
+                build.provenance,
+                arch_cache,
+                arch,
+                &mut |line| on_output(line),
+                &mut |line| {
+                    transient_error |= docker::is_transient_build_error(&line);
+                    on_output(line)
+                },
+                &should_abort,
+                build.git_repository.docker_target_build_stage.as_ref(),
+            );
+
+            match &ret {
+                Err(docker::DockerError::Aborted { .. } | docker::DockerError::Timeout { .. }) => break ret,
+                Err(_) if transient_error && attempt < MAX_ARCHITECTURE_BUILD_ATTEMPTS => {
+                    attempt += 1;
+                    logger.send_warning(format!(
+                        "⚠️ [{arch}] Build failed due to a builder error, retrying (attempt {attempt}/{MAX_ARCHITECTURE_BUILD_ATTEMPTS})"
+                    ));
+                }
+                _ => break ret,
+            }
+        };
+
+        arch_record.stop(match &ret {
+            Ok(_) => StepStatus::Success,
+            Err(err) if err.is_aborted() => StepStatus::Cancel,
+            Err(_) => StepStatus::Error,
+        });
+        ret
+    }
+
+    // Architectures requested by the build the image is not available for in the registry
+    fn missing_architectures(&self, build: &Build, image: &ContainerImage) -> Result<Vec<CpuArchitecture>, BuildError> {
+        if build.architectures.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let available_architectures = self
+            .context
+            .docker
+            .image_architectures(image)
+            .map_err(|err| to_build_error(build.image.service_id.clone(), err))?;
+
+        Ok(build
+            .architectures
+            .iter()
+            .filter(|arch| !available_architectures.contains(&Architecture::from(*arch)))
+            .copied()
+            .collect())
+    }
+
     fn provision_builder(
         &self,
         build: &Build,
@@ -370,6 +719,19 @@ impl BuildPlatform for LocalDocker {
         self.name.as_str()
     }
 
+    fn cache_backend(&self, build: &Build) -> BuildCacheBackendKind {
+        build.cache_backend.unwrap_or(self.build_cache.backend)
+    }
+
+    fn missing_image_architectures(&self, build: &Build) -> Result<Vec<CpuArchitecture>, BuildError> {
+        let image = ContainerImage::new(
+            build.image.registry_url.clone(),
+            build.image.name(),
+            vec![build.image.tag.clone()],
+        );
+        self.missing_architectures(build, &image)
+    }
+
     fn build(
         &self,
         build: &mut Build,
@@ -434,52 +796,60 @@ impl BuildPlatform for LocalDocker {
         // Do the real git clone
         let git_clone_record =
             metrics_registry.start_record(build.image.service_long_id, StepLabel::Service, StepName::GitClone);
-        if let Err(error) = retry::retry(retry::delay::Fixed::from_millis(10_000).take(3), || {
-            if let Err(BuildError::GitError {
-                application: _,
-                git_cmd,
-                context,
-                raw_error,
-            }) = git::clone_at_commit(
+        // Mirrors of the repository (and their git-lfs objects) must not be evicted until the build is done
+        let _git_mirror_leases = match retry::retry(retry::delay::Fixed::from_millis(10_000).take(3), || {
+            let git_mirror_leases = match git::clone_at_commit(
                 &build.git_repository.url,
                 &build.git_repository.commit_id,
                 &repository_root_path,
                 &get_credentials,
             ) {
-                let message = raw_error.message();
-                let git_error_class = raw_error.class();
-                // Some errors can happen "randomly":
-                // - SSL error: syscall failure: Resource temporarily unavailable
-                // - Timeout on git clone
-                debug!("Error on git clone: git_error_class={:?}, message={}", git_error_class, message);
-                return if git_error_class == ErrorClass::Os
-                    || git_error_class == ErrorClass::Ssl
-                    || (git_error_class == ErrorClass::Net && message.contains("timed out"))
-                {
-                    debug!("Retrying git clone...");
-                    logger.send_warning(format!(
-                        "⚠️ Retrying cloning your git repository, due to following error: {message}"
-                    ));
-                    OperationResult::Retry(BuildError::GitError {
-                        application: build.image.service_id.clone(),
-                        git_cmd,
-                        context,
-                        raw_error,
-                    })
-                } else {
-                    OperationResult::Err(BuildError::GitError {
-                        application: build.image.service_id.clone(),
-                        git_cmd,
-                        context,
-                        raw_error,
-                    })
-                };
-            }
-            OperationResult::Ok(())
+                Ok(git_mirror_leases) => git_mirror_leases,
+                Err(BuildError::GitError {
+                    application: _,
+                    git_cmd,
+                    context,
+                    raw_error,
+                }) => {
+                    let message = raw_error.message();
+                    let git_error_class = raw_error.class();
+                    // Some errors can happen "randomly":
+                    // - SSL error: syscall failure: Resource temporarily unavailable
+                    // - Timeout on git clone
+                    debug!("Error on git clone: git_error_class={:?}, message={}", git_error_class, message);
+                    return if git_error_class == ErrorClass::Os
+                        || git_error_class == ErrorClass::Ssl
+                        || (git_error_class == ErrorClass::Net && message.contains("timed out"))
+                    {
+                        debug!("Retrying git clone...");
+                        logger.send_warning(format!(
+                            "⚠️ Retrying cloning your git repository, due to following error: {message}"
+                        ));
+                        OperationResult::Retry(BuildError::GitError {
+                            application: build.image.service_id.clone(),
+                            git_cmd,
+                            context,
+                            raw_error,
+                        })
+                    } else {
+                        OperationResult::Err(BuildError::GitError {
+                            application: build.image.service_id.clone(),
+                            git_cmd,
+                            context,
+                            raw_error,
+                        })
+                    };
+                }
+                Err(_) => vec![],
+            };
+            OperationResult::Ok(git_mirror_leases)
         }) {
-            git_clone_record.stop(StepStatus::Error);
-            return Err(error.error);
-        }
+            Ok(git_mirror_leases) => git_mirror_leases,
+            Err(error) => {
+                git_clone_record.stop(StepStatus::Error);
+                return Err(error.error);
+            }
+        };
         git_clone_record.stop(StepStatus::Success);
 
         let _git_cleanup = scopeguard::guard(&repository_root_path, |path| {
@@ -495,6 +865,40 @@ impl BuildPlatform for LocalDocker {
 
         let app_id = build.image.service_id.clone();
 
+        let commit_timestamp =
+            git::commit_timestamp(&repository_root_path, &build.git_repository.commit_id).map_err(|raw_error| {
+                BuildError::GitError {
+                    application: app_id.clone(),
+                    git_cmd: GitCmd::Log,
+                    context: format!("commit: {}", build.git_repository.commit_id),
+                    raw_error,
+                }
+            })?;
+        build.git_repository.commit_timestamp = Some(commit_timestamp);
+
+        // Monorepo mode, the image is identified by the content of its paths at this commit instead of the commit itself.
+        // So the image built for a previous commit is reused if those paths did not change
+        if let Some(watched_paths) = &build.git_repository.watched_paths {
+            let paths_content_hash =
+                git::paths_content_hash(&repository_root_path, &build.git_repository.commit_id, watched_paths)
+                    .map_err(|raw_error| BuildError::GitError {
+                        application: app_id.clone(),
+                        git_cmd: GitCmd::LsTree,
+                        context: format!("paths: {watched_paths:?}"),
+                        raw_error,
+                    })?;
+            logger.send_progress(format!(
+                "🌳 Monorepo mode enabled, image tag is derived from the content of: {}",
+                watched_paths
+                    .iter()
+                    .map(|path| path.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+            build.git_repository.paths_content_hash = Some(paths_content_hash);
+            build.compute_image_tag();
+        }
+
         // Fetch git-lfs/big files for the repository if necessary
         let git_lfs = if let Some(creds) = git_user_creds {
             GitLfs::new(creds.login, creds.password)
@@ -628,3 +1032,10 @@ impl BuildPlatform for LocalDocker {
         )
     }
 }
+
+// buildkit prints `#5 CACHED` for every step restored from the cache
+fn count_cached_step(line: &str, cached_steps: &AtomicUsize) {
+    if line.starts_with('#') && line.trim_end().ends_with(" CACHED") {
+        cached_steps.fetch_add(1, Ordering::Relaxed);
+    }
+}
@@ -1,7 +1,8 @@
+use chrono::{DateTime, SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
-use crate::cmd::docker::DockerError;
+use crate::cmd::docker::{BuildCacheObjectStorage, BuildProvenance, DockerError};
 use crate::environment::report::logger::EnvLogger;
 use crate::errors::EngineError;
 use crate::events::EventDetails;
@@ -10,7 +11,7 @@ use crate::environment::models::abort::Abort;
 use crate::io_models::container::Registry;
 use crate::io_models::models::CpuArchitecture;
 use crate::metrics_registry::MetricsRegistry;
-use crate::utilities::compute_image_tag;
+use crate::utilities::{compute_content_addressed_image_tag, compute_image_tag};
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::hash::Hash;
 use std::path::PathBuf;
@@ -22,12 +23,24 @@ use uuid::Uuid;
 pub mod dockerfile_utils;
 pub mod local_docker;
 
+// Labels set on built images to trace them back to their source
+// https://github.com/opencontainers/image-spec/blob/main/annotations.md
+pub const IMAGE_SOURCE_LABEL: &str = "org.opencontainers.image.source";
+pub const IMAGE_REVISION_LABEL: &str = "org.opencontainers.image.revision";
+pub const IMAGE_CREATED_LABEL: &str = "org.opencontainers.image.created";
+pub const IMAGE_ROOT_PATH_LABEL: &str = "com.qovery.image.root-path";
+pub const IMAGE_DOCKERFILE_PATH_LABEL: &str = "com.qovery.image.dockerfile-path";
+pub const IMAGE_ENGINE_VERSION_LABEL: &str = "com.qovery.engine.version";
+pub const IMAGE_SERVICE_ID_LABEL: &str = "com.qovery.service.id";
+
 #[derive(Debug)]
 pub enum GitCmd {
     Fetch,
     Checkout,
     Submodule,
     SubmoduleUpdate,
+    LsTree,
+    Log,
 }
 
 impl Display for GitCmd {
@@ -37,6 +50,8 @@ impl Display for GitCmd {
             GitCmd::Checkout => "git checkout",
             GitCmd::Submodule => "git submodule",
             GitCmd::SubmoduleUpdate => "git submodule update",
+            GitCmd::LsTree => "git ls-tree",
+            GitCmd::Log => "git log",
         };
         f.write_str(msg)
     }
@@ -76,6 +91,24 @@ pub enum BuildError {
         raw_error: DockerError,
     },
 
+    #[error(
+        "Cannot build Application {application:?} for architecture {architecture} due to an error with docker: {raw_error:?}"
+    )]
+    ArchitectureDockerError {
+        application: String,
+        architecture: CpuArchitecture,
+        raw_error: DockerError,
+    },
+
+    #[error(
+        "Image {image} of Application {application:?} is not available for architectures {missing_architectures:?}"
+    )]
+    MissingArchitectures {
+        application: String,
+        image: String,
+        missing_architectures: Vec<CpuArchitecture>,
+    },
+
     #[error("Cannot get credentials error.")]
     CannotGetCredentials { raw_error_message: String },
 }
@@ -111,12 +144,44 @@ pub trait BuildPlatform: Send + Sync {
         metrics_registry: Arc<dyn MetricsRegistry>,
         cancellation_requested: &dyn Abort,
     ) -> Result<(), BuildError>;
+    // Cache backend used to build the service, the service setting takes precedence over the build platform one
+    fn cache_backend(&self, build: &Build) -> BuildCacheBackendKind {
+        build.cache_backend.unwrap_or_default()
+    }
+    // Architectures requested by the build that the already pushed image lacks
+    fn missing_image_architectures(&self, _build: &Build) -> Result<Vec<CpuArchitecture>, BuildError> {
+        Ok(vec![])
+    }
+}
+
+/// Where buildkit imports and exports the layers cache of the builds
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BuildCacheBackendKind {
+    // `cache` tag in the repository of the image
+    #[default]
+    Registry,
+    // Dedicated `<repository>-cache` repository, to not mix cache manifests with the images
+    DedicatedRegistry,
+    // Bucket of the cluster object storage (S3, GCS, Azure blob)
+    ObjectStorage,
+    // Persistent directory of the builder, for on-premise builders
+    LocalDirectory,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct BuildCacheConfig {
+    pub backend: BuildCacheBackendKind,
+    pub object_storage: Option<BuildCacheObjectStorage>,
+    pub local_directory: Option<PathBuf>,
 }
 
 pub struct Build {
     pub git_repository: GitRepository,
     pub image: Image,
     pub environment_variables: BTreeMap<String, String>,
+    // secret variables, exposed to the build as BuildKit secret mounts to not end up in the image layers/metadata
+    pub secret_environment_variables: BTreeMap<String, String>,
     pub disable_cache: bool,
     pub timeout: Duration,
     pub architectures: Vec<CpuArchitecture>,
@@ -125,20 +190,73 @@ pub struct Build {
     pub ephemeral_storage_in_gib: Option<u32>,
     // registries used by the build where we need to login to pull image
     pub registries: Vec<Registry>,
+    // Override of the build platform cache backend for this service
+    pub cache_backend: Option<BuildCacheBackendKind>,
+    pub provenance: BuildProvenance,
 }
 
 impl Build {
     pub fn compute_image_tag(&mut self) {
+        if let Some(paths_content_hash) = &self.git_repository.paths_content_hash {
+            self.image.tag = compute_content_addressed_image_tag(
+                paths_content_hash,
+                &self.git_repository.dockerfile_path,
+                &self.git_repository.dockerfile_content,
+                &self.git_repository.extra_files_to_inject,
+                &self.environment_variables,
+                &self.secret_environment_variables,
+                &self.git_repository.docker_target_build_stage,
+            );
+            return;
+        }
+
         self.image.tag = compute_image_tag(
             &self.git_repository.root_path,
             &self.git_repository.dockerfile_path,
             &self.git_repository.dockerfile_content,
             &self.git_repository.extra_files_to_inject,
             &self.environment_variables,
+            &self.secret_environment_variables,
             &self.git_repository.commit_id,
             &self.git_repository.docker_target_build_stage,
         );
     }
+
+    /// Labels tracing the built image back to its source
+    pub fn image_labels(&self, engine_version: Option<&str>) -> BTreeMap<String, String> {
+        // The git url must never leak credentials into the image
+        let mut source_url = self.git_repository.url.clone();
+        let _ = source_url.set_username("");
+        let _ = source_url.set_password(None);
+
+        let mut labels = BTreeMap::from([
+            (IMAGE_SOURCE_LABEL.to_string(), source_url.to_string()),
+            (IMAGE_REVISION_LABEL.to_string(), self.git_repository.commit_id.clone()),
+            (
+                IMAGE_ROOT_PATH_LABEL.to_string(),
+                self.git_repository.root_path.to_string_lossy().to_string(),
+            ),
+            (IMAGE_SERVICE_ID_LABEL.to_string(), self.image.service_long_id.to_string()),
+        ]);
+        // The commit time and not the build time, so building the same commit twice gives the same image
+        if let Some(commit_timestamp) = &self.git_repository.commit_timestamp {
+            labels.insert(
+                IMAGE_CREATED_LABEL.to_string(),
+                commit_timestamp.to_rfc3339_opts(SecondsFormat::Secs, true),
+            );
+        }
+        if let Some(dockerfile_path) = &self.git_repository.dockerfile_path {
+            labels.insert(
+                IMAGE_DOCKERFILE_PATH_LABEL.to_string(),
+                dockerfile_path.to_string_lossy().to_string(),
+            );
+        }
+        if let Some(engine_version) = engine_version {
+            labels.insert(IMAGE_ENGINE_VERSION_LABEL.to_string(), engine_version.to_string());
+        }
+
+        labels
+    }
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
@@ -176,6 +294,13 @@ pub struct GitRepository {
     pub root_path: PathBuf,
     pub extra_files_to_inject: Vec<GitRepositoryExtraFile>,
     pub docker_target_build_stage: Option<String>,
+    // Monorepo mode, when set the image tag is derived from the content of root_path and those extra paths
+    // instead of the commit. So a commit not touching them reuses the image already built
+    pub watched_paths: Option<Vec<PathBuf>>,
+    // Content hash of the watched paths, only known once the repository has been cloned
+    pub paths_content_hash: Option<String>,
+    // Time of the commit, only known once the repository has been cloned
+    pub commit_timestamp: Option<DateTime<Utc>>,
 }
 impl GitRepository {
     fn credentials(&self) -> Option<anyhow::Result<Credentials>> {
@@ -230,6 +355,18 @@ impl Image {
         &self.repository_name
     }
 
+    pub fn cache_repository_name(&self) -> String {
+        format!("{}-cache", self.repository_name())
+    }
+
+    // Image name is prefixed by its repository for some registries (i.e: `namespace/image`)
+    pub fn cache_image_name(&self) -> String {
+        match self.name.strip_prefix(self.repository_name()) {
+            Some(image_name) => format!("{}{}", self.cache_repository_name(), image_name),
+            None => format!("{}-cache", self.name),
+        }
+    }
+
     pub fn full_image_name_with_tag(&self) -> String {
         match self.registry_url.port_or_known_default() {
             None | Some(443) => {
@@ -5,14 +5,17 @@ use crate::infrastructure::helm_charts::nginx_ingress_chart::{
     NginxServerSnippet as NginxServerSnippetModel,
 };
 use crate::infrastructure::models::cloud_provider::Kind as KindModel;
+use crate::infrastructure::models::kubernetes::maintenance_window::MaintenanceWindow;
 use crate::io_models::models::StorageClass as StorageClassModel;
 use crate::{errors::EngineError, events::EventDetails};
 use base64::Engine;
 use base64::engine::general_purpose;
+use chrono::{NaiveTime, Weekday};
 use reqwest::StatusCode;
 use serde_derive::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::str;
+use std::str::FromStr;
 use std::time::Duration;
 use thiserror::Error;
 
@@ -34,6 +37,10 @@ fn default_nginx_controller_log_format_escaping() -> LogFormatEscaping {
     LogFormatEscaping::Default
 }
 
+fn default_ingress_implementation() -> IngressImplementation {
+    IngressImplementation::Nginx
+}
+
 #[derive(Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Kind {
@@ -108,6 +115,36 @@ impl LogFormatEscaping {
     }
 }
 
+/// Implementation used to expose routers of the cluster.
+/// Gateway API implementations render `HTTPRoute` / `GRPCRoute` objects instead of nginx `Ingress` ones.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum IngressImplementation {
+    #[serde(alias = "nginx", alias = "NGINX")]
+    Nginx,
+    #[serde(alias = "envoy_gateway", alias = "ENVOY_GATEWAY")]
+    EnvoyGateway,
+    /// Cilium gateway API support must already be enabled on the cluster, Qovery doesn't install it
+    #[serde(alias = "cilium", alias = "CILIUM")]
+    Cilium,
+}
+
+impl IngressImplementation {
+    pub const ENVOY_GATEWAY_CLASS_NAME: &'static str = "qovery-envoy-gateway";
+    pub const CILIUM_GATEWAY_CLASS_NAME: &'static str = "cilium";
+
+    pub fn is_gateway_api(&self) -> bool {
+        !matches!(self, IngressImplementation::Nginx)
+    }
+
+    pub fn gateway_class_name(&self) -> Option<&'static str> {
+        match self {
+            IngressImplementation::Nginx => None,
+            IngressImplementation::EnvoyGateway => Some(Self::ENVOY_GATEWAY_CLASS_NAME),
+            IngressImplementation::Cilium => Some(Self::CILIUM_GATEWAY_CLASS_NAME),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct NginxHttpSnippet(String);
 
@@ -187,6 +224,8 @@ pub struct ClusterAdvancedSettings {
     pub aws_cloudwatch_eks_logs_retention_days: u32,
     #[serde(alias = "aws.eks.encrypt_secrets_kms_key_arn", default)]
     pub aws_eks_encrypt_secrets_kms_key_arn: String,
+    #[serde(alias = "aws.eks.enable_blue_green_upgrade")]
+    pub aws_eks_enable_blue_green_upgrade: bool,
     #[serde(alias = "cloud_provider.container_registry.tags")]
     pub cloud_provider_container_registry_tags: HashMap<String, String>,
     #[serde(alias = "database.postgresql.deny_any_access")]
@@ -271,6 +310,38 @@ pub struct ClusterAdvancedSettings {
 
     #[serde(alias = "object_storage.enable_logging")]
     pub object_storage_enable_logging: bool,
+
+    #[serde(alias = "keda.enabled")]
+    pub keda_enabled: bool,
+
+    /// Backup cluster resources and volumes on a schedule, in the cluster backups bucket
+    #[serde(alias = "cluster.backup.enabled")]
+    pub cluster_backup_enabled: bool,
+    /// Cron expression of the backups schedule
+    #[serde(alias = "cluster.backup.schedule")]
+    pub cluster_backup_schedule: String,
+    #[serde(alias = "cluster.backup.retention_in_days")]
+    pub cluster_backup_retention_in_days: u32,
+
+    /// Install OpenCost to compute workloads costs, requires metrics to be installed by Qovery
+    #[serde(alias = "cost.opencost.enabled")]
+    pub opencost_enabled: bool,
+
+    /// Weekly window, in UTC, during which patch upgrades and node images updates can be applied to the cluster
+    #[serde(alias = "cluster.maintenance_window.enabled")]
+    pub cluster_maintenance_window_enabled: bool,
+    /// Days of the week the window opens, i.e: `["saturday", "sunday"]`
+    #[serde(alias = "cluster.maintenance_window.days")]
+    pub cluster_maintenance_window_days: Vec<String>,
+    /// Opening time of the window, `HH:MM` in UTC
+    #[serde(alias = "cluster.maintenance_window.start_time")]
+    pub cluster_maintenance_window_start_time: String,
+    /// AKS node OS updates require a window of at least 4 hours, GKE requires 48 hours of maintenance per month
+    #[serde(alias = "cluster.maintenance_window.duration_in_hours")]
+    pub cluster_maintenance_window_duration_in_hours: u32,
+
+    #[serde(alias = "ingress.implementation", default = "default_ingress_implementation")]
+    pub ingress_implementation: IngressImplementation,
 }
 
 impl Default for ClusterAdvancedSettings {
@@ -320,6 +391,7 @@ impl Default for ClusterAdvancedSettings {
             nginx_controller_limit_request_status_code: None,
             scaleway_enable_private_network_migration: false,
             aws_eks_encrypt_secrets_kms_key_arn: "".to_string(),
+            aws_eks_enable_blue_green_upgrade: false,
             gcp_vpc_enable_flow_logs: false,
             gcp_vpc_flow_logs_sampling: None,
             qovery_static_ip_mode: None,
@@ -335,6 +407,16 @@ impl Default for ClusterAdvancedSettings {
             nginx_default_backend_image_repository: None,
             nginx_default_backend_image_tag: None,
             object_storage_enable_logging: false,
+            keda_enabled: false,
+            cluster_backup_enabled: false,
+            cluster_backup_schedule: "0 3 * * *".to_string(),
+            cluster_backup_retention_in_days: 7,
+            opencost_enabled: false,
+            cluster_maintenance_window_enabled: false,
+            cluster_maintenance_window_days: vec!["saturday".to_string(), "sunday".to_string()],
+            cluster_maintenance_window_start_time: "03:00".to_string(),
+            cluster_maintenance_window_duration_in_hours: 4,
+            ingress_implementation: IngressImplementation::Nginx,
         }
     }
 }
@@ -350,9 +432,50 @@ impl ClusterAdvancedSettings {
             )));
         }
 
+        if let Err(input_error) = self.try_maintenance_window() {
+            return Err(Box::new(EngineError::new_invalid_engine_payload_invalid_field_value(
+                event_details,
+                input_error,
+            )));
+        }
+
         Ok(())
     }
 
+    /// Cluster maintenance window, `None` when it is disabled or invalid (see [ClusterAdvancedSettings::validate])
+    pub fn maintenance_window(&self) -> Option<MaintenanceWindow> {
+        self.try_maintenance_window().ok().flatten()
+    }
+
+    fn try_maintenance_window(&self) -> Result<Option<MaintenanceWindow>, InputError> {
+        if !self.cluster_maintenance_window_enabled {
+            return Ok(None);
+        }
+
+        let to_input_error = |field_name: &str, message: String| InputError::InvalidInputFieldValue {
+            field_name: field_name.to_string(),
+            message,
+        };
+        let days = self
+            .cluster_maintenance_window_days
+            .iter()
+            .map(|day| {
+                Weekday::from_str(day)
+                    .map_err(|_| to_input_error("cluster.maintenance_window.days", format!("`{day}` is not a day")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let start_time = NaiveTime::parse_from_str(&self.cluster_maintenance_window_start_time, "%H:%M")
+            .map_err(|e| to_input_error("cluster.maintenance_window.start_time", e.to_string()))?;
+
+        MaintenanceWindow::new(
+            days,
+            start_time,
+            chrono::Duration::hours(self.cluster_maintenance_window_duration_in_hours as i64),
+        )
+        .map(Some)
+        .map_err(|message| to_input_error("cluster.maintenance_window", message))
+    }
+
     pub fn resource_ttl(&self) -> Option<Duration> {
         if self.pleco_resources_ttl >= 0 {
             Some(Duration::new(self.pleco_resources_ttl as u64, 0))
@@ -390,13 +513,14 @@ mod tests {
     use uuid::Uuid;
 
     use crate::infrastructure::models::cloud_provider::io::{
-        ClusterAdvancedSettings, InputError, LogFormatEscaping, RegistryMirroringMode,
+        ClusterAdvancedSettings, IngressImplementation, InputError, LogFormatEscaping, RegistryMirroringMode,
         validate_aws_cloudwatch_eks_logs_retention_days,
     };
     use crate::{
         events::{EventDetails, Stage, Transmitter},
         io_models::QoveryIdentifier,
     };
+    use chrono::{NaiveTime, Weekday};
 
     #[test]
     // avoid human mistakes and check defaults values at compile time
@@ -420,6 +544,30 @@ mod tests {
         assert!(!validate_aws_cloudwatch_eks_logs_retention_days(2));
     }
 
+    #[test]
+    fn test_maintenance_window() {
+        let mut settings = ClusterAdvancedSettings::default();
+        assert_eq!(settings.try_maintenance_window(), Ok(None));
+
+        settings.cluster_maintenance_window_enabled = true;
+        let window = settings
+            .try_maintenance_window()
+            .expect("default maintenance window should be valid")
+            .expect("maintenance window should be enabled");
+        assert_eq!(window.days(), &[Weekday::Sat, Weekday::Sun]);
+        assert_eq!(window.start_time(), NaiveTime::from_hms_opt(3, 0, 0).unwrap());
+
+        settings.cluster_maintenance_window_days = vec!["Mon".to_string(), "someday".to_string()];
+        assert_eq!(
+            settings.try_maintenance_window(),
+            Err(InputError::InvalidInputFieldValue {
+                field_name: "cluster.maintenance_window.days".to_string(),
+                message: "`someday` is not a day".to_string(),
+            })
+        );
+        assert_eq!(settings.maintenance_window(), None);
+    }
+
     #[test]
     fn test_registry_mirroring_mode_deserialization() {
         struct TestCase {
@@ -472,6 +620,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_ingress_implementation_deserialization() {
+        let cluster_advanced_settings: ClusterAdvancedSettings = serde_json::from_str("{}").unwrap();
+        assert_eq!(cluster_advanced_settings.ingress_implementation, IngressImplementation::Nginx);
+        assert!(!cluster_advanced_settings.ingress_implementation.is_gateway_api());
+
+        for (input, expected, gateway_class_name) in [
+            ("nginx", IngressImplementation::Nginx, None),
+            (
+                "EnvoyGateway",
+                IngressImplementation::EnvoyGateway,
+                Some("qovery-envoy-gateway"),
+            ),
+            (
+                "envoy_gateway",
+                IngressImplementation::EnvoyGateway,
+                Some("qovery-envoy-gateway"),
+            ),
+            ("CILIUM", IngressImplementation::Cilium, Some("cilium")),
+        ] {
+            let data = format!(r#"{{ "ingress.implementation": "{input}" }}"#);
+            let cluster_advanced_settings: ClusterAdvancedSettings = serde_json::from_str(data.as_str()).unwrap();
+            assert_eq!(cluster_advanced_settings.ingress_implementation, expected);
+            assert_eq!(
+                cluster_advanced_settings.ingress_implementation.gateway_class_name(),
+                gateway_class_name
+            );
+        }
+    }
+
     #[test]
     fn test_default_values_for_nginx() {
         let data = r#" {}"#;
@@ -19,6 +19,7 @@ use crate::environment::models::database::{Database, DatabaseMode};
 use crate::environment::models::environment::Environment;
 use crate::errors::{CommandError, EngineError};
 use crate::events::{EnvironmentStep, EventDetails, Stage};
+use crate::infrastructure::models::cost_allocation::CostAllocationTags;
 use crate::infrastructure::models::kubernetes::Kubernetes;
 use crate::io_models::models::{EnvironmentVariable, InvalidStatefulsetStorage};
 use crate::kubers_utils::{
@@ -44,6 +45,21 @@ pub trait Service: Send {
     fn build(&self) -> Option<&Build>;
     fn build_mut(&mut self) -> Option<&mut Build>;
     fn get_environment_variables(&self) -> Vec<EnvironmentVariable>;
+    /// Resources the service requests to be scheduled on the cluster nodes, `None` when it does not run on them
+    fn resources_request(&self) -> Option<ServiceResourcesRequest> {
+        None
+    }
+}
+
+/// Resources requested by each instance of a service
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ServiceResourcesRequest {
+    pub cpu_in_milli: u32,
+    pub ram_in_mib: u32,
+    /// Number of instances running once the service is deployed
+    pub instances: u32,
+    /// User node pool the service is placed on, default placement when `None`
+    pub node_pool: Option<String>,
 }
 
 #[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
@@ -152,6 +168,17 @@ pub fn default_tera_context(
     context.insert("sanitized_name", &service.kube_name());
     context.insert("namespace", environment.namespace());
     context.insert("cluster_name", kubernetes.name());
+    context.insert(
+        "cost_allocation_tags",
+        &CostAllocationTags::for_environment(
+            environment.organization_long_id,
+            *kubernetes.long_id(),
+            environment.project_long_id,
+            environment.long_id,
+        )
+        .with_service(*service.long_id())
+        .to_tags(),
+    );
 
     context
 }
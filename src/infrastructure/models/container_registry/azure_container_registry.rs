@@ -154,6 +154,7 @@ impl AzureContainerRegistry {
         if let Some(cluster_id) = registry_tags.cluster_id {
             tags.push(("cluster_id".to_string(), cluster_id));
         }
+        tags.extend(registry_tags.cost_allocation_tags.to_tags());
 
         self.service
             .create_registry(
@@ -219,6 +219,16 @@ impl ECR {
                 value: Some(duration.as_secs().to_string()),
             })
         };
+        tags.extend(
+            registry_tags
+                .cost_allocation_tags
+                .to_tags()
+                .into_iter()
+                .map(|(key, value)| Tag {
+                    key: Some(key),
+                    value: Some(value),
+                }),
+        );
         let crr = CreateRepositoryRequest {
             repository_name: repository_name.to_string(),
             tags: Some(tags),
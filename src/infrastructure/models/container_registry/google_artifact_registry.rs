@@ -140,6 +140,7 @@ impl GoogleArtifactRegistry {
         if let Some(cluster_id) = registry_tags.cluster_id {
             tags.push(("cluster_id".to_string(), cluster_id));
         }
+        tags.extend(registry_tags.cost_allocation_tags.to_tags());
         self.service
             .create_repository(&self.project_id, self.region.clone(), repository_name, HashMap::from_iter(tags))
             .map(|r| (r, RepositoryInfo { created: true }))
@@ -8,6 +8,7 @@ use crate::errors::EngineError;
 use crate::events::{EventDetails, Stage, Transmitter};
 use crate::infrastructure::models::build_platform::Image;
 use crate::infrastructure::models::container_registry::errors::ContainerRegistryError;
+use crate::infrastructure::models::cost_allocation::CostAllocationTags;
 use crate::io_models::QoveryIdentifier;
 use crate::io_models::context::Context;
 
@@ -35,11 +36,13 @@ pub struct DockerImage {
     pub tag: String,
 }
 
+#[derive(Clone)]
 pub struct RegistryTags {
     pub cluster_id: Option<String>,
     pub environment_id: Option<String>,
     pub project_id: Option<String>,
     pub resource_ttl: Option<Duration>,
+    pub cost_allocation_tags: CostAllocationTags,
 }
 
 #[enum_dispatch]
@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// Tags set on every cloud resource created by the engine, so its cost can be allocated to the Qovery
+/// organization, cluster, project, environment or service owning it.
+/// Keys and values only use lowercase characters, digits and underscores/hyphens to be accepted by every cloud provider.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CostAllocationTags {
+    pub organization_id: Option<Uuid>,
+    pub cluster_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    pub environment_id: Option<Uuid>,
+    pub service_id: Option<Uuid>,
+}
+
+impl CostAllocationTags {
+    pub fn for_cluster(organization_id: Uuid, cluster_id: Uuid) -> CostAllocationTags {
+        CostAllocationTags {
+            organization_id: Some(organization_id),
+            cluster_id: Some(cluster_id),
+            ..Default::default()
+        }
+    }
+
+    pub fn for_environment(
+        organization_id: Uuid,
+        cluster_id: Uuid,
+        project_id: Uuid,
+        environment_id: Uuid,
+    ) -> CostAllocationTags {
+        CostAllocationTags {
+            organization_id: Some(organization_id),
+            cluster_id: Some(cluster_id),
+            project_id: Some(project_id),
+            environment_id: Some(environment_id),
+            service_id: None,
+        }
+    }
+
+    pub fn with_service(self, service_id: Uuid) -> CostAllocationTags {
+        CostAllocationTags {
+            service_id: Some(service_id),
+            ..self
+        }
+    }
+
+    pub fn to_tags(&self) -> BTreeMap<String, String> {
+        [
+            ("qovery_organization_id", self.organization_id),
+            ("qovery_cluster_id", self.cluster_id),
+            ("qovery_project_id", self.project_id),
+            ("qovery_environment_id", self.environment_id),
+            ("qovery_service_id", self.service_id),
+        ]
+        .into_iter()
+        .filter_map(|(key, id)| id.map(|id| (key.to_string(), id.to_string())))
+        .collect()
+    }
+
+    /// Same as `to_tags`, for providers not supporting key/value tags (i.e: Scaleway)
+    pub fn to_tags_list(&self) -> Vec<String> {
+        self.to_tags()
+            .into_iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::infrastructure::models::cost_allocation::CostAllocationTags;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_cost_allocation_tags_only_contain_known_ids() {
+        let organization_id = Uuid::new_v4();
+        let cluster_id = Uuid::new_v4();
+        let service_id = Uuid::new_v4();
+
+        let tags = CostAllocationTags::for_cluster(organization_id, cluster_id).with_service(service_id);
+
+        assert_eq!(
+            tags.to_tags().into_iter().collect::<Vec<_>>(),
+            vec![
+                ("qovery_cluster_id".to_string(), cluster_id.to_string()),
+                ("qovery_organization_id".to_string(), organization_id.to_string()),
+                ("qovery_service_id".to_string(), service_id.to_string()),
+            ]
+        );
+        assert_eq!(
+            tags.to_tags_list(),
+            vec![
+                format!("qovery_cluster_id={cluster_id}"),
+                format!("qovery_organization_id={organization_id}"),
+                format!("qovery_service_id={service_id}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cost_allocation_tags_are_valid_for_every_cloud_provider() {
+        let tags = CostAllocationTags::for_environment(Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4())
+            .with_service(Uuid::new_v4());
+
+        // GCP labels are the most restrictive ones
+        let is_valid = |s: &str| {
+            s.len() <= 63
+                && s.chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+        };
+        for (key, value) in tags.to_tags() {
+            assert!(is_valid(&key), "invalid key {key}");
+            assert!(is_valid(&value), "invalid value {value}");
+        }
+    }
+}
@@ -22,6 +22,15 @@ pub enum Kind {
     QoveryDns,
 }
 
+impl Kind {
+    /// external-dns only honours weighted / latency based records (aws-weight, aws-region annotations) with Route53
+    pub fn supports_routing_policies(&self) -> bool {
+        match self {
+            Kind::Cloudflare | Kind::QoveryDns => false,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum DnsProviderConfiguration {
     Cloudflare(CloudflareDnsConfig),
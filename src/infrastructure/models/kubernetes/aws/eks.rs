@@ -7,9 +7,16 @@ use crate::infrastructure::action::kubeconfig_helper::write_kubeconfig_on_disk;
 use crate::infrastructure::models::cloud_provider::CloudProvider;
 use crate::infrastructure::models::cloud_provider::aws::regions::{AwsRegion, AwsZone};
 use crate::infrastructure::models::cloud_provider::io::ClusterAdvancedSettings;
+use crate::infrastructure::models::cost_allocation::CostAllocationTags;
 use crate::infrastructure::models::kubernetes::aws;
+use crate::infrastructure::models::kubernetes::aws::node::AwsInstancesType;
 use crate::infrastructure::models::kubernetes::aws::{KarpenterParameters, Options};
+use crate::infrastructure::models::kubernetes::instance_capacity::{
+    InstanceCapacityCatalog, NodeCapacities, aws_karpenter_instance_capacities,
+};
+use crate::infrastructure::models::kubernetes::karpenter::KarpenterUserNodePool;
 use crate::infrastructure::models::kubernetes::{Kind, Kubernetes, KubernetesVersion, event_details};
+use crate::infrastructure::models::object_storage::ObjectStorage;
 use crate::infrastructure::models::object_storage::s3::S3;
 use crate::io_models::context::Context;
 use crate::io_models::engine_request::{ChartValuesOverrideName, ChartValuesOverrideValues};
@@ -21,6 +28,7 @@ use chrono::{DateTime, Utc};
 use std::borrow::Borrow;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use uuid::Uuid;
 
 /// EKS kubernetes provider allowing to deploy an EKS cluster.
@@ -69,6 +77,14 @@ impl EKS {
 
         let aws_zones = aws::aws_zones(zones, &region, &event_details)?;
         advanced_settings.validate(event_details.clone())?;
+        if let Some(karpenter_parameters) = &options.karpenter_parameters {
+            karpenter_parameters.validate().map_err(|input_error| {
+                Box::new(EngineError::new_invalid_engine_payload_invalid_field_value(
+                    event_details.clone(),
+                    input_error,
+                ))
+            })?;
+        }
 
         let creds = cloud_provider
             .downcast_ref()
@@ -76,7 +92,8 @@ impl EKS {
             .ok_or_else(|| Box::new(EngineError::new_bad_cast(event_details.clone(), "Cloudprovider is not AWS")))?
             .aws_credentials()
             .clone();
-        let s3 = S3::new("s3-temp-id".to_string(), "default-s3".to_string(), creds, region.clone());
+        let s3 = S3::new("s3-temp-id".to_string(), "default-s3".to_string(), creds, region.clone())
+            .with_cost_allocation_tags(CostAllocationTags::for_cluster(*context.organization_long_id(), long_id));
 
         let cluster = EKS {
             context,
@@ -165,6 +182,10 @@ impl Kubernetes for EKS {
         }
     }
 
+    fn object_storage(&self) -> Option<&dyn ObjectStorage> {
+        Some(&self.s3)
+    }
+
     fn temp_dir(&self) -> &Path {
         &self.temp_dir
     }
@@ -177,6 +198,37 @@ impl Kubernetes for EKS {
         self.options.karpenter_parameters.is_some()
     }
 
+    fn karpenter_user_node_pool(&self, name: &str) -> Option<&KarpenterUserNodePool> {
+        self.options
+            .karpenter_parameters
+            .as_ref()
+            .and_then(|karpenter_parameters| karpenter_parameters.get_user_node_pool(name))
+    }
+
+    fn node_capacities(&self, node_pool: Option<&str>) -> NodeCapacities {
+        match &self.options.karpenter_parameters {
+            Some(karpenter_parameters) => {
+                let requirements = match node_pool.and_then(|name| karpenter_parameters.get_user_node_pool(name)) {
+                    Some(user_node_pool) => &user_node_pool.requirements,
+                    None => &karpenter_parameters.qovery_node_pools.requirements,
+                };
+                aws_karpenter_instance_capacities(requirements)
+            }
+            None => self
+                .nodes_groups
+                .iter()
+                .map(|node_group| {
+                    (
+                        node_group.instance_type.clone(),
+                        AwsInstancesType::from_str(&node_group.instance_type)
+                            .ok()
+                            .and_then(|instance_type| instance_type.capacity()),
+                    )
+                })
+                .collect(),
+        }
+    }
+
     fn loadbalancer_l4_annotations(&self, cloud_provider_lb_name: Option<&str>) -> Vec<(String, String)> {
         let lb_name = match cloud_provider_lb_name {
             Some(x) => format!(",QoveryName={x}"),
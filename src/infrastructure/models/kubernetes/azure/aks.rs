@@ -7,10 +7,13 @@ use crate::infrastructure::action::InfrastructureAction;
 use crate::infrastructure::action::kubeconfig_helper::write_kubeconfig_on_disk;
 use crate::infrastructure::models::cloud_provider::CloudProvider;
 use crate::infrastructure::models::cloud_provider::azure::locations::AzureLocation;
-use crate::infrastructure::models::cloud_provider::io::ClusterAdvancedSettings;
+use crate::infrastructure::models::cloud_provider::io::{ClusterAdvancedSettings, InputError};
+use crate::infrastructure::models::cost_allocation::CostAllocationTags;
 use crate::infrastructure::models::kubernetes::azure::AksOptions;
 use crate::infrastructure::models::kubernetes::azure::node_group::AzureNodeGroups;
+use crate::infrastructure::models::kubernetes::instance_capacity::{InstanceCapacityCatalog, NodeCapacities};
 use crate::infrastructure::models::kubernetes::{Kind, Kubernetes, KubernetesVersion, event_details};
+use crate::infrastructure::models::object_storage::ObjectStorage;
 use crate::infrastructure::models::object_storage::azure_object_storage::AzureOS;
 use crate::io_models::context::Context;
 use crate::io_models::engine_request::{ChartValuesOverrideName, ChartValuesOverrideValues};
@@ -70,6 +73,24 @@ impl AKS {
         let template_directory = PathBuf::from(format!("{}/azure/bootstrap", context.lib_root_dir()));
 
         advanced_settings.validate(event_details.clone())?;
+        // AKS node OS upgrade schedules open on a single day per week or every day, for at least 4 hours
+        if let Some(maintenance_window) = advanced_settings.maintenance_window() {
+            let to_input_error = |field_name: &str, message: String| {
+                Box::new(EngineError::new_invalid_engine_payload_invalid_field_value(
+                    event_details.clone(),
+                    InputError::InvalidInputFieldValue {
+                        field_name: field_name.to_string(),
+                        message,
+                    },
+                ))
+            };
+            maintenance_window
+                .check_single_day_or_every_day()
+                .map_err(|message| to_input_error("cluster.maintenance_window.days", message))?;
+            maintenance_window
+                .check_min_duration_in_hours(4)
+                .map_err(|message| to_input_error("cluster.maintenance_window.duration_in_hours", message))?;
+        }
 
         let credentials = cloud_provider
             .downcast_ref()
@@ -101,7 +122,8 @@ impl AKS {
         // Blob storage
         // TODO(benjaminch): Storage location should be deduced from the AKS location
         let blob_storage_service = BlobStorageService::new();
-        let blob_storage = AzureOS::new(short_id.as_str(), long_id, name, Arc::from(blob_storage_service));
+        let blob_storage = AzureOS::new(short_id.as_str(), long_id, name, Arc::from(blob_storage_service))
+            .with_cost_allocation_tags(CostAllocationTags::for_cluster(*context.organization_long_id(), long_id));
 
         let cluster = AKS {
             context,
@@ -195,6 +217,23 @@ impl Kubernetes for AKS {
             .collect()
     }
 
+    fn node_capacities(&self, _node_pool: Option<&str>) -> NodeCapacities {
+        self.node_groups
+            .get_all_node_groups()
+            .iter()
+            .map(|node_group| {
+                (
+                    node_group.instance_type.to_cloud_provider_format(),
+                    node_group.instance_type.capacity(),
+                )
+            })
+            .collect()
+    }
+
+    fn object_storage(&self) -> Option<&dyn ObjectStorage> {
+        Some(&self.blob_storage)
+    }
+
     fn temp_dir(&self) -> &Path {
         &self.temp_dir
     }
@@ -5,6 +5,7 @@ use crate::events::{EventDetails, InfrastructureStep, Transmitter};
 use crate::infrastructure::action::kubeconfig_helper::write_kubeconfig_on_disk;
 use crate::infrastructure::models::cloud_provider::gcp::locations::GcpRegion;
 use crate::infrastructure::models::cloud_provider::io::ClusterAdvancedSettings;
+use crate::infrastructure::models::cost_allocation::CostAllocationTags;
 use crate::infrastructure::models::kubernetes::{Kind, Kubernetes, KubernetesVersion, ProviderOptions};
 use crate::io_models::QoveryIdentifier;
 use crate::io_models::context::Context;
@@ -16,6 +17,7 @@ use crate::logger::Logger;
 use crate::environment::models::ToCloudProviderFormat;
 use crate::infrastructure::infrastructure_context::InfrastructureContext;
 use crate::infrastructure::models::cloud_provider;
+use crate::infrastructure::models::object_storage::ObjectStorage;
 use crate::infrastructure::models::object_storage::errors::ObjectStorageError;
 use crate::infrastructure::models::object_storage::google_object_storage::GoogleOS;
 use crate::services::gcp::auth_service::GoogleAuthService;
@@ -255,7 +257,8 @@ impl Gke {
             &creds.project_id,
             GcpStorageRegion::from(region.clone()),
             Arc::new(object_storage_service_client),
-        );
+        )
+        .with_cost_allocation_tags(CostAllocationTags::for_cluster(*context.organization_long_id(), long_id));
 
         let cluster = Self {
             context: context.clone(),
@@ -379,6 +382,10 @@ impl Kubernetes for Gke {
         vec![CpuArchitecture::AMD64]
     }
 
+    fn object_storage(&self) -> Option<&dyn ObjectStorage> {
+        Some(&self.object_storage)
+    }
+
     fn temp_dir(&self) -> &Path {
         &self.temp_dir
     }
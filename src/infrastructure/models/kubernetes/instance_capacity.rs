@@ -0,0 +1,505 @@
+use crate::infrastructure::models::kubernetes::InstanceType;
+use crate::infrastructure::models::kubernetes::aws::node::AwsInstancesType;
+use crate::infrastructure::models::kubernetes::azure::node::AzureInstancesType;
+use crate::infrastructure::models::kubernetes::karpenter::{
+    KarpenterNodePoolRequirement, KarpenterNodePoolRequirementKey, KarpenterRequirementOperator,
+};
+use crate::infrastructure::models::kubernetes::scaleway::node::ScwInstancesType;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use strum::IntoEnumIterator;
+
+// AKS clusters are created with azure CNI overlay, its default max pods per node is 250
+const AKS_MAX_PODS: u32 = 250;
+const KAPSULE_MAX_PODS: u32 = 110;
+
+/// Hardware capacity of an instance type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InstanceCapacity {
+    pub vcpus: u32,
+    pub memory_in_mib: u32,
+    /// Maximum number of pods the kubelet accepts on a node of this instance type
+    pub max_pods: u32,
+}
+
+/// Resources a node offers to workloads, once kubelet and system reservations are removed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NodeAllocatable {
+    pub cpu_in_milli: u32,
+    pub memory_in_mib: u32,
+    pub pods: u32,
+}
+
+impl NodeAllocatable {
+    pub fn can_fit(&self, cpu_in_milli: u32, memory_in_mib: u32) -> bool {
+        cpu_in_milli <= self.cpu_in_milli && memory_in_mib <= self.memory_in_mib
+    }
+}
+
+impl InstanceCapacity {
+    /// Reservations follow the ones applied by EKS, GKE and AKS node bootstrap:
+    /// * cpu: 6% of the first core, 1% of the second, 0.5% of the next two and 0.25% of the remaining ones
+    /// * memory: 255Mi + 11Mi per pod for the kubelet, plus 100Mi of hard eviction threshold
+    ///
+    /// Daemonsets requests are not taken into account.
+    pub fn allocatable(&self) -> NodeAllocatable {
+        // (upper bound of the tier in milli cpu, reserved part of the tier in 1/10000)
+        const CPU_RESERVATION_TIERS: [(u32, u32); 4] = [(1000, 600), (2000, 100), (4000, 50), (u32::MAX, 25)];
+
+        let cpu_in_milli = self.vcpus * 1000;
+        let mut reserved_cpu_in_milli = 0;
+        let mut tier_lower_bound = 0;
+        for (tier_upper_bound, reserved_part) in CPU_RESERVATION_TIERS {
+            if cpu_in_milli <= tier_lower_bound {
+                break;
+            }
+            reserved_cpu_in_milli += (cpu_in_milli.min(tier_upper_bound) - tier_lower_bound) * reserved_part / 10_000;
+            tier_lower_bound = tier_upper_bound;
+        }
+
+        let reserved_memory_in_mib = 255 + 11 * self.max_pods + 100;
+
+        NodeAllocatable {
+            cpu_in_milli: cpu_in_milli - reserved_cpu_in_milli,
+            memory_in_mib: self.memory_in_mib.saturating_sub(reserved_memory_in_mib),
+            pods: self.max_pods,
+        }
+    }
+}
+
+/// Capacities of the instance types a cluster can start nodes with
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NodeCapacities {
+    pub capacities: Vec<InstanceCapacity>,
+    /// Instance types missing from the catalog, their nodes may offer more resources than the known ones
+    pub unknown_instance_types: Vec<String>,
+}
+
+impl FromIterator<(String, Option<InstanceCapacity>)> for NodeCapacities {
+    fn from_iter<T: IntoIterator<Item = (String, Option<InstanceCapacity>)>>(instance_types: T) -> Self {
+        let mut node_capacities = NodeCapacities::default();
+        for (instance_type, capacity) in instance_types {
+            match capacity {
+                Some(capacity) => node_capacities.capacities.push(capacity),
+                None => node_capacities.unknown_instance_types.push(instance_type),
+            }
+        }
+        node_capacities
+    }
+}
+
+/// Capacity catalog of instance types.
+/// Instance types enums are generated, so their capacity is derived here from providers naming conventions.
+pub trait InstanceCapacityCatalog: InstanceType {
+    /// Returns `None` when the instance type family is not known by the catalog
+    fn capacity(&self) -> Option<InstanceCapacity>;
+}
+
+impl InstanceCapacityCatalog for AwsInstancesType {
+    fn capacity(&self) -> Option<InstanceCapacity> {
+        aws_instance_capacity(&self.to_cloud_provider_format())
+    }
+}
+
+impl InstanceCapacityCatalog for AzureInstancesType {
+    fn capacity(&self) -> Option<InstanceCapacity> {
+        azure_instance_capacity(&self.to_cloud_provider_format())
+    }
+}
+
+impl InstanceCapacityCatalog for ScwInstancesType {
+    fn capacity(&self) -> Option<InstanceCapacity> {
+        scw_instance_capacity(&self.to_cloud_provider_format())
+    }
+}
+
+/// Splits an AWS instance family into its category and generation, i.e `c6gn` => (`c`, 6)
+fn aws_family_category_and_generation(family: &str) -> (&str, Option<u32>) {
+    let category_end = family.find(|c: char| c.is_ascii_digit()).unwrap_or(family.len());
+    let generation = family[category_end..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>()
+        .parse::<u32>()
+        .ok();
+
+    (&family[..category_end], generation)
+}
+
+/// AWS instance types are named `<family>.<size>`, the size giving the number of vCPUs and the family the memory per vCPU
+pub fn aws_instance_capacity(instance_type: &str) -> Option<InstanceCapacity> {
+    let (family, size) = instance_type.split_once('.')?;
+    let (category, generation) = aws_family_category_and_generation(family);
+
+    // burstable instances do not follow the vCPU / memory ratio of other families
+    let (vcpus, memory_in_mib) = if category == "t" {
+        let vcpus = match (size, generation) {
+            ("nano" | "micro" | "small", Some(2)) => 1,
+            ("nano" | "micro" | "small" | "medium" | "large", _) => 2,
+            ("xlarge", _) => 4,
+            ("2xlarge", _) => 8,
+            _ => return None,
+        };
+        let memory_in_mib = match size {
+            "nano" => 512,
+            "micro" => 1024,
+            "small" => 2048,
+            "medium" => 4096,
+            "large" => 8192,
+            "xlarge" => 16384,
+            "2xlarge" => 32768,
+            _ => return None,
+        };
+        (vcpus, memory_in_mib)
+    } else {
+        let vcpus = match size {
+            "medium" => 1,
+            "large" => 2,
+            "xlarge" => 4,
+            _ => 4 * size.strip_suffix("xlarge")?.parse::<u32>().ok()?,
+        };
+        let memory_per_vcpu_in_mib = match family {
+            "c3" | "c4" => 1920,
+            "c5n" => 2688,
+            "i2" | "i3" | "r3" | "r4" | "d2" | "p3" => 7808,
+            "f1" => 15616,
+            "x1e" => 31232,
+            "g5g" | "vt1" => 2048,
+            "d3en" | "im4gn" => 4096,
+            "is4gen" => 6144,
+            "g6e" | "gr6" => 8192,
+            "x2gd" | "x8g" => 16384,
+            "x2iedn" | "x2iezn" => 32768,
+            _ => match category {
+                "c" | "hpc" => 2048,
+                "m" | "g" | "h" | "inf" | "trn" => 4096,
+                "r" | "i" | "z" | "d" => 8192,
+                _ => return None,
+            },
+        };
+        (vcpus, vcpus * memory_per_vcpu_in_mib)
+    };
+
+    // EKS default max pods, driven by the number of network interfaces and IPs per interface of the instance size
+    let max_pods = match size {
+        "nano" | "micro" => 4,
+        "small" => 11,
+        "medium" => 17,
+        "large" => 29,
+        "xlarge" | "2xlarge" => 58,
+        _ if vcpus < 64 => 234,
+        _ => 737,
+    };
+
+    Some(InstanceCapacity {
+        vcpus,
+        memory_in_mib,
+        max_pods,
+    })
+}
+
+static AZURE_INSTANCE_TYPE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^standard_([a-z]+)(\d+)(?:-(\d+))?([a-z]*)(?:_v(\d+))?(?:_promo)?$")
+        .expect("Invalid azure instance type regex")
+});
+
+/// Azure instance types are named `standard_<family><vCPUs>[-<constrained vCPUs>]<features>[_v<version>]`
+/// https://learn.microsoft.com/en-us/azure/virtual-machines/vm-naming-conventions
+pub fn azure_instance_capacity(instance_type: &str) -> Option<InstanceCapacity> {
+    let captures = AZURE_INSTANCE_TYPE_REGEX.captures(&instance_type.to_lowercase())?;
+    let family = captures.get(1)?.as_str().to_string();
+    let size_vcpus = captures.get(2)?.as_str().parse::<u32>().ok()?;
+    // constrained vCPU instances keep the memory of their size but run less vCPUs
+    let vcpus = match captures.get(3) {
+        Some(constrained_vcpus) => constrained_vcpus.as_str().parse::<u32>().ok()?,
+        None => size_vcpus,
+    };
+    let features = captures.get(4).map(|it| it.as_str()).unwrap_or_default().to_string();
+    let version = captures.get(5).and_then(|it| it.as_str().parse::<u32>().ok());
+
+    let memory_per_vcpu_in_mib = match (family.as_str(), version) {
+        ("a", Some(2)) if features.contains('m') => 8192,
+        ("a", Some(2)) => 2048,
+        ("b", None) => match (size_vcpus, features.as_str()) {
+            (1, "ls") => 512,
+            (1, "s") => 1024,
+            (1, "ms") | (2, "s") => 2048,
+            (_, "ms") => 4096,
+            _ => return None,
+        },
+        ("b", Some(2)) if features.contains('t') => 512,
+        ("b", Some(2)) if features.contains('l') => 2048,
+        ("b", Some(2)) => 4096,
+        ("d", Some(3..)) if features.contains('l') => 2048,
+        ("d", Some(3..)) => 4096,
+        // isolated instances have their own memory sizes
+        ("e", Some(3..)) if features.contains('i') => return None,
+        ("e", Some(3..)) => 8192,
+        ("f", None | Some(2)) => 2048,
+        ("f", Some(6)) if features.contains('l') => 2048,
+        ("f", Some(6)) if features.contains('m') => 8192,
+        ("f", Some(6)) => 4096,
+        ("l", Some(2..)) => 8192,
+        _ => return None,
+    };
+
+    Some(InstanceCapacity {
+        vcpus,
+        memory_in_mib: size_vcpus * memory_per_vcpu_in_mib,
+        max_pods: AKS_MAX_PODS,
+    })
+}
+
+static SCW_INSTANCE_TYPE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"-(\d+)c-(\d+)g").expect("Invalid scaleway instance type regex"));
+
+/// Recent Scaleway instance types hold their capacity in their name (i.e `pop2-4c-16g`), older ones do not
+pub fn scw_instance_capacity(instance_type: &str) -> Option<InstanceCapacity> {
+    let instance_type = instance_type.to_lowercase();
+    let (vcpus, memory_in_gib) = match SCW_INSTANCE_TYPE_REGEX.captures(&instance_type) {
+        Some(captures) => (
+            captures.get(1)?.as_str().parse::<u32>().ok()?,
+            captures.get(2)?.as_str().parse::<u32>().ok()?,
+        ),
+        None => match instance_type.as_str() {
+            "play2-pico" => (1, 2),
+            "dev1-s" | "start1-s" | "vc1s" => (2, 2),
+            "play2-nano" => (2, 4),
+            "dev1-m" => (3, 4),
+            "start1-m" | "vc1m" => (4, 4),
+            "dev1-l" | "play2-micro" => (4, 8),
+            "dev1-xl" => (4, 12),
+            "vc1l" => (6, 8),
+            "x64-15gb" => (6, 15),
+            "start1-l" => (8, 8),
+            "x64-30gb" => (8, 30),
+            "x64-60gb" => (10, 60),
+            "x64-120gb" => (12, 120),
+            "pro2-xxs" | "ent1-xxs" => (2, 8),
+            "gp1-xs" | "pro2-xs" | "ent1-xs" => (4, 16),
+            "gp1-s" | "pro2-s" | "ent1-s" => (8, 32),
+            "gp1-m" | "pro2-m" | "ent1-m" => (16, 64),
+            "gp1-l" | "pro2-l" | "ent1-l" => (32, 128),
+            "gp1-xl" => (48, 256),
+            "ent1-xl" => (64, 256),
+            _ => return None,
+        },
+    };
+
+    Some(InstanceCapacity {
+        vcpus,
+        memory_in_mib: memory_in_gib * 1024,
+        max_pods: KAPSULE_MAX_PODS,
+    })
+}
+
+fn karpenter_requirement_matches(requirement: &KarpenterNodePoolRequirement, value: Option<&str>) -> bool {
+    let Some(value) = value else {
+        // labels not known for an instance type are not filtering it out
+        return true;
+    };
+
+    match requirement
+        .operator
+        .as_ref()
+        .unwrap_or(&KarpenterRequirementOperator::In)
+    {
+        KarpenterRequirementOperator::In => requirement.values.iter().any(|it| it.eq_ignore_ascii_case(value)),
+        KarpenterRequirementOperator::Gt => match value.parse::<u32>() {
+            Ok(value) => requirement
+                .values
+                .iter()
+                .filter_map(|it| it.parse::<u32>().ok())
+                .all(|bound| value > bound),
+            Err(_) => false,
+        },
+    }
+}
+
+/// Capacities of the AWS instance types Karpenter can provision for the given node pool requirements
+pub fn aws_karpenter_instance_capacities(requirements: &[KarpenterNodePoolRequirement]) -> NodeCapacities {
+    AwsInstancesType::iter()
+        .filter(|instance_type| instance_type.is_instance_allowed())
+        .filter(|instance_type| {
+            let name = instance_type.to_cloud_provider_format();
+            let (family, size) = name.split_once('.').unwrap_or((name.as_str(), ""));
+            let (category, generation) = aws_family_category_and_generation(family);
+            let generation = generation.map(|it| it.to_string());
+            let arch = if instance_type.is_arm_instance() {
+                "arm64"
+            } else {
+                "amd64"
+            };
+
+            requirements.iter().all(|requirement| {
+                let value = match requirement.key {
+                    KarpenterNodePoolRequirementKey::InstanceType => Some(name.as_str()),
+                    KarpenterNodePoolRequirementKey::Arch => Some(arch),
+                    KarpenterNodePoolRequirementKey::InstanceCategory => Some(category),
+                    KarpenterNodePoolRequirementKey::InstanceFamily => Some(family),
+                    KarpenterNodePoolRequirementKey::InstanceGeneration => generation.as_deref(),
+                    KarpenterNodePoolRequirementKey::InstanceSize => Some(size),
+                    KarpenterNodePoolRequirementKey::CapacityType
+                    | KarpenterNodePoolRequirementKey::Os
+                    | KarpenterNodePoolRequirementKey::SkuFamily
+                    | KarpenterNodePoolRequirementKey::SkuName
+                    | KarpenterNodePoolRequirementKey::SkuVersion => None,
+                };
+                karpenter_requirement_matches(requirement, value)
+            })
+        })
+        .map(|instance_type| (instance_type.to_cloud_provider_format(), instance_type.capacity()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aws_instance_capacity() {
+        let capacity = |vcpus, memory_in_mib, max_pods| {
+            Some(InstanceCapacity {
+                vcpus,
+                memory_in_mib,
+                max_pods,
+            })
+        };
+
+        assert_eq!(aws_instance_capacity("t3.medium"), capacity(2, 4096, 17));
+        assert_eq!(aws_instance_capacity("t2.micro"), capacity(1, 1024, 4));
+        assert_eq!(aws_instance_capacity("m6g.medium"), capacity(1, 4096, 17));
+        assert_eq!(aws_instance_capacity("c5.large"), capacity(2, 4096, 29));
+        assert_eq!(aws_instance_capacity("m5.2xlarge"), capacity(8, 32768, 58));
+        assert_eq!(aws_instance_capacity("r6i.4xlarge"), capacity(16, 131072, 234));
+        assert_eq!(aws_instance_capacity("c5.9xlarge"), capacity(36, 73728, 234));
+        assert_eq!(aws_instance_capacity("m5.24xlarge"), capacity(96, 393216, 737));
+        assert_eq!(aws_instance_capacity("m5.metal"), None);
+        assert_eq!(aws_instance_capacity("u-6tb1.metal"), None);
+    }
+
+    #[test]
+    fn test_azure_instance_capacity() {
+        let capacity = |vcpus, memory_in_mib| {
+            Some(InstanceCapacity {
+                vcpus,
+                memory_in_mib,
+                max_pods: AKS_MAX_PODS,
+            })
+        };
+
+        assert_eq!(azure_instance_capacity("standard_d4s_v5"), capacity(4, 16384));
+        assert_eq!(azure_instance_capacity("Standard_D2ls_v5"), capacity(2, 4096));
+        assert_eq!(azure_instance_capacity("standard_e8-2s_v5"), capacity(2, 65536));
+        assert_eq!(azure_instance_capacity("standard_b2ms"), capacity(2, 8192));
+        assert_eq!(azure_instance_capacity("standard_b1s"), capacity(1, 1024));
+        assert_eq!(azure_instance_capacity("standard_f4s_v2"), capacity(4, 8192));
+        assert_eq!(azure_instance_capacity("standard_nc6s_v3"), None);
+    }
+
+    #[test]
+    fn test_scw_instance_capacity() {
+        let capacity = |vcpus, memory_in_mib| {
+            Some(InstanceCapacity {
+                vcpus,
+                memory_in_mib,
+                max_pods: KAPSULE_MAX_PODS,
+            })
+        };
+
+        assert_eq!(scw_instance_capacity("pop2-4c-16g"), capacity(4, 16384));
+        assert_eq!(scw_instance_capacity("pop2-hm-8c-64g"), capacity(8, 65536));
+        assert_eq!(scw_instance_capacity("pop2-16c-64g-win"), capacity(16, 65536));
+        assert_eq!(scw_instance_capacity("DEV1-L"), capacity(4, 8192));
+        assert_eq!(scw_instance_capacity("gpu-3070-s"), None);
+    }
+
+    #[test]
+    fn test_allocatable() {
+        let allocatable = InstanceCapacity {
+            vcpus: 2,
+            memory_in_mib: 4096,
+            max_pods: 17,
+        }
+        .allocatable();
+
+        assert_eq!(
+            allocatable,
+            NodeAllocatable {
+                cpu_in_milli: 1930,
+                memory_in_mib: 3554,
+                pods: 17,
+            }
+        );
+        assert!(allocatable.can_fit(1500, 3000));
+        assert!(!allocatable.can_fit(2000, 3000));
+        assert!(!allocatable.can_fit(1500, 4000));
+
+        assert_eq!(
+            InstanceCapacity {
+                vcpus: 8,
+                memory_in_mib: 32768,
+                max_pods: 58,
+            }
+            .allocatable()
+            .cpu_in_milli,
+            8000 - 60 - 10 - 10 - 10
+        );
+    }
+
+    #[test]
+    fn test_aws_karpenter_instance_capacities() {
+        let requirements = vec![
+            KarpenterNodePoolRequirement {
+                key: KarpenterNodePoolRequirementKey::InstanceFamily,
+                values: vec!["c5".to_string()],
+                operator: None,
+            },
+            KarpenterNodePoolRequirement {
+                key: KarpenterNodePoolRequirementKey::InstanceSize,
+                values: vec!["large".to_string(), "xlarge".to_string()],
+                operator: Some(KarpenterRequirementOperator::In),
+            },
+        ];
+
+        let node_capacities = aws_karpenter_instance_capacities(&requirements);
+        let mut capacities = node_capacities.capacities;
+        capacities.sort_by_key(|it| it.vcpus);
+        assert!(node_capacities.unknown_instance_types.is_empty());
+        assert_eq!(
+            capacities,
+            vec![
+                InstanceCapacity {
+                    vcpus: 2,
+                    memory_in_mib: 4096,
+                    max_pods: 29,
+                },
+                InstanceCapacity {
+                    vcpus: 4,
+                    memory_in_mib: 8192,
+                    max_pods: 58,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_node_capacities_keep_unknown_instance_types() {
+        let node_capacities: NodeCapacities = ["t3.medium", "m5.metal"]
+            .into_iter()
+            .map(|instance_type| (instance_type.to_string(), aws_instance_capacity(instance_type)))
+            .collect();
+
+        assert_eq!(
+            node_capacities,
+            NodeCapacities {
+                capacities: vec![InstanceCapacity {
+                    vcpus: 2,
+                    memory_in_mib: 4096,
+                    max_pods: 17,
+                }],
+                unknown_instance_types: vec!["m5.metal".to_string()],
+            }
+        );
+    }
+}
@@ -1,9 +1,11 @@
 use crate::environment::models::domain::ToHelmString;
+use crate::infrastructure::models::cloud_provider::io::InputError;
 use crate::io_models::models::{CpuArchitecture, KubernetesCpuResourceUnit, KubernetesMemoryResourceUnit};
 use duration_str::deserialize_duration;
 use itertools::Itertools;
 use serde_derive::{Deserialize, Serialize};
 use serde_with::DisplayFromStr;
+use std::collections::HashSet;
 use std::fmt;
 use std::fmt::Formatter;
 use std::time::Duration;
@@ -15,6 +17,52 @@ pub struct KarpenterParameters {
     pub disk_size_in_gib: i32,
     pub default_service_architecture: CpuArchitecture,
     pub qovery_node_pools: KarpenterNodePool,
+    #[serde(default)]
+    pub user_node_pools: Vec<KarpenterUserNodePool>,
+}
+
+impl KarpenterParameters {
+    pub fn get_user_node_pool(&self, name: &str) -> Option<&KarpenterUserNodePool> {
+        self.user_node_pools.iter().find(|node_pool| node_pool.name == name)
+    }
+
+    /// User node pools names are used as Karpenter NodePool names and in their dedicated taint key,
+    /// so they must be unique DNS labels not clashing with Qovery node pools
+    pub fn validate(&self) -> Result<(), InputError> {
+        let to_input_error = |message: String| InputError::InvalidInputFieldValue {
+            field_name: "karpenter_parameters.user_node_pools.name".to_string(),
+            message,
+        };
+
+        let mut names = HashSet::with_capacity(self.user_node_pools.len());
+        for node_pool in &self.user_node_pools {
+            let name = node_pool.name.as_str();
+            if RESERVED_NODE_POOL_NAMES.contains(&name) {
+                return Err(to_input_error(format!("`{name}` is reserved for Qovery node pools")));
+            }
+            if !is_dns_label(name) {
+                return Err(to_input_error(format!(
+                    "`{name}` must contain at most 63 lowercase alphanumeric characters or '-', and start and end with an alphanumeric character"
+                )));
+            }
+            if !names.insert(name) {
+                return Err(to_input_error(format!("`{name}` is defined more than once")));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+const RESERVED_NODE_POOL_NAMES: [&str; 2] = ["default", "stable"];
+
+fn is_dns_label(name: &str) -> bool {
+    let is_alphanumeric = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit();
+    !name.is_empty()
+        && name.len() <= 63
+        && name.chars().all(|c| is_alphanumeric(c) || c == '-')
+        && name.starts_with(is_alphanumeric)
+        && name.ends_with(is_alphanumeric)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -132,12 +180,71 @@ impl ToHelmString for Vec<KarpenterNodePoolDisruptionReason> {
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum KarpenterNodePoolDisruptionReason {
     Underutilized,
+    Drifted,
 }
 
 impl fmt::Display for KarpenterNodePoolDisruptionReason {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let output = match self {
             KarpenterNodePoolDisruptionReason::Underutilized => "Underutilized",
+            KarpenterNodePoolDisruptionReason::Drifted => "Drifted",
+        };
+        write!(f, "{output}")
+    }
+}
+
+/// Node pool defined by the user on top of Qovery ones, only services explicitly placed on it are scheduled there
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KarpenterUserNodePool {
+    pub name: String,
+    #[serde(default)]
+    pub requirements: Vec<KarpenterNodePoolRequirement>,
+    #[serde(default)]
+    pub taints: Vec<KarpenterNodePoolTaint>,
+    #[serde(default)]
+    pub limits: Option<KarpenterNodePoolLimits>,
+    #[serde(default)]
+    pub budgets: Vec<KarpenterNodePoolDisruptionBudget>,
+}
+
+impl KarpenterUserNodePool {
+    /// Taint set on every node of the pool, keeping away services not placed on it
+    pub fn dedicated_taint(&self) -> KarpenterNodePoolTaint {
+        KarpenterNodePoolTaint {
+            key: format!("nodepool/{}", self.name),
+            value: None,
+            effect: KarpenterNodePoolTaintEffect::NoSchedule,
+        }
+    }
+
+    pub fn all_taints(&self) -> Vec<KarpenterNodePoolTaint> {
+        let mut taints = vec![self.dedicated_taint()];
+        taints.extend(self.taints.iter().cloned());
+        taints
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct KarpenterNodePoolTaint {
+    pub key: String,
+    #[serde(default)]
+    pub value: Option<String>,
+    pub effect: KarpenterNodePoolTaintEffect,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum KarpenterNodePoolTaintEffect {
+    NoSchedule,
+    PreferNoSchedule,
+    NoExecute,
+}
+
+impl fmt::Display for KarpenterNodePoolTaintEffect {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let output = match self {
+            KarpenterNodePoolTaintEffect::NoSchedule => "NoSchedule",
+            KarpenterNodePoolTaintEffect::PreferNoSchedule => "PreferNoSchedule",
+            KarpenterNodePoolTaintEffect::NoExecute => "NoExecute",
         };
         write!(f, "{output}")
     }
@@ -164,6 +271,7 @@ mod tests {
         KarpenterNodePoolLimits, KarpenterParameters, KarpenterStableNodePoolOverride,
     };
     use crate::io_models::models::{KubernetesCpuResourceUnit, KubernetesMemoryResourceUnit};
+    use itertools::Itertools;
 
     #[test]
     fn should_deserialize_correctly_when_stable_node_pool_override_is_present_with_consolidation() {
@@ -542,4 +650,54 @@ mod tests {
             .expect("default_override should be present");
         assert_eq!(default_node_pool_override, KarpenterDefaultNodePoolOverride { limits: None })
     }
+
+    #[test]
+    fn test_karpenter_parameters_validate_user_node_pool_names() {
+        let karpenter_parameters_with_user_node_pools = |names: &[&str]| {
+            let user_node_pools = names.iter().map(|name| format!(r#"{{ "name": "{name}" }}"#)).join(",");
+            serde_json::from_str::<KarpenterParameters>(&format!(
+                r#"
+                {{
+                  "spot_enabled": false,
+                  "max_node_drain_time_in_secs": null,
+                  "disk_size_in_gib": 50,
+                  "default_service_architecture": "AMD64",
+                  "qovery_node_pools": {{
+                    "requirements": [],
+                    "stable_override": {{ "budgets": [] }},
+                    "default_override": null
+                  }},
+                  "user_node_pools": [{user_node_pools}]
+                }}
+                "#
+            ))
+            .expect("karpenter parameters should be valid json")
+        };
+
+        // given
+        let longest_name = "a".repeat(63);
+        let too_long_name = "a".repeat(64);
+        let test_cases = vec![
+            (vec![], true),
+            (vec!["gpu", "batch-jobs-2"], true),
+            (vec![longest_name.as_str()], true),
+            (vec!["gpu", "gpu"], false),
+            (vec!["default"], false),
+            (vec!["stable"], false),
+            (vec![""], false),
+            (vec!["GPU"], false),
+            (vec!["gpu_pool"], false),
+            (vec!["-gpu"], false),
+            (vec!["gpu-"], false),
+            (vec![too_long_name.as_str()], false),
+        ];
+
+        for (names, is_valid) in test_cases {
+            // when
+            let result = karpenter_parameters_with_user_node_pools(&names).validate();
+
+            // then
+            assert_eq!(result.is_ok(), is_valid, "user node pools: {names:?}");
+        }
+    }
 }
@@ -0,0 +1,294 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Timelike, Utc, Weekday};
+use itertools::Itertools;
+use std::collections::BTreeMap;
+
+/// Weekly recurring window, in UTC, during which control plane patch upgrades and node images updates can be applied
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaintenanceWindow {
+    /// Days the window opens, starting from monday
+    days: Vec<Weekday>,
+    start_time: NaiveTime,
+    duration: Duration,
+}
+
+impl MaintenanceWindow {
+    pub fn new(days: Vec<Weekday>, start_time: NaiveTime, duration: Duration) -> Result<Self, String> {
+        if days.is_empty() {
+            return Err("at least one day is required".to_string());
+        }
+        if duration <= Duration::zero() || duration > Duration::days(1) {
+            return Err(format!(
+                "duration must be between 1 minute and 24 hours, got {} minutes",
+                duration.num_minutes()
+            ));
+        }
+
+        Ok(MaintenanceWindow {
+            days: days
+                .into_iter()
+                .sorted_by_key(|day| day.num_days_from_monday())
+                .dedup()
+                .collect(),
+            start_time,
+            duration,
+        })
+    }
+
+    pub fn days(&self) -> &[Weekday] {
+        &self.days
+    }
+
+    pub fn start_time(&self) -> NaiveTime {
+        self.start_time
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    pub fn is_every_day(&self) -> bool {
+        self.days.len() == 7
+    }
+
+    /// For providers scheduling maintenance on a single day per week or on every day
+    pub fn check_single_day_or_every_day(&self) -> Result<(), String> {
+        match self.days.len() {
+            1 | 7 => Ok(()),
+            _ => Err(format!(
+                "the window must open on a single day or on every day, got {}",
+                self.days.iter().map(|day| weekday_name(*day)).join(", ")
+            )),
+        }
+    }
+
+    /// For providers requiring a minimum duration, in hours, for the window
+    pub fn check_min_duration_in_hours(&self, min_hours: i64) -> Result<(), String> {
+        match self.duration < Duration::hours(min_hours) {
+            true => Err(format!(
+                "the window must last at least {min_hours} hours, got {} minutes",
+                self.duration.num_minutes()
+            )),
+            false => Ok(()),
+        }
+    }
+
+    pub fn is_open_at(&self, at: DateTime<Utc>) -> bool {
+        self.openings_around(at)
+            .any(|opening| opening <= at && at < opening + self.duration)
+    }
+
+    pub fn next_opening_after(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        self.openings_around(at)
+            .find(|opening| *opening > at)
+            .expect("a weekly maintenance window always opens within the next 7 days")
+    }
+
+    /// Openings from the day before `at` (a window can still be open past midnight) up to a week after it
+    fn openings_around(&self, at: DateTime<Utc>) -> impl Iterator<Item = DateTime<Utc>> + '_ {
+        let today = at.date_naive();
+        (-1..=7)
+            .map(move |offset| today + Duration::days(offset))
+            .filter(|day| self.days.contains(&day.weekday()))
+            .map(|day| day.and_time(self.start_time).and_utc())
+    }
+
+    /// Recurrence of the window in RFC5545 format, i.e: `FREQ=WEEKLY;BYDAY=SA,SU`
+    pub fn to_rrule(&self) -> String {
+        let days = self
+            .days
+            .iter()
+            .map(|day| day.to_string()[..2].to_uppercase())
+            .join(",");
+        format!("FREQ=WEEKLY;BYDAY={days}")
+    }
+
+    /// Hours of each day covered by the window, a window going past midnight spreads on the next day
+    pub fn hours_by_day(&self) -> Vec<(Weekday, Vec<u32>)> {
+        let covered_hours = (self.start_time.minute() as i64 + self.duration.num_minutes() + 59) / 60;
+        let mut hours_by_day: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+        for day in &self.days {
+            for hour in self.start_time.hour() as i64..self.start_time.hour() as i64 + covered_hours {
+                let day = day.num_days_from_monday() + (hour / 24) as u32;
+                hours_by_day.entry(day % 7).or_default().push((hour % 24) as u32);
+            }
+        }
+
+        hours_by_day
+            .into_iter()
+            .map(|(day, hours)| {
+                (
+                    Weekday::try_from(day as u8).unwrap_or(Weekday::Mon),
+                    hours.into_iter().sorted().dedup().collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// Periods outside of the window, as a cron schedule of when the window closes and the time until it opens again
+    pub fn closed_periods(&self) -> Vec<(String, Duration)> {
+        let openings = self
+            .days
+            .iter()
+            .map(|day| reference_week_opening(*day, self.start_time))
+            .collect_vec();
+
+        openings
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, opening)| {
+                let closing = *opening + self.duration;
+                let next_opening = openings
+                    .get(idx + 1)
+                    .copied()
+                    .unwrap_or_else(|| openings[0] + Duration::weeks(1));
+                let closed_for = next_opening - closing;
+                if closed_for <= Duration::zero() {
+                    return None;
+                }
+
+                Some((
+                    format!(
+                        "{} {} * * {}",
+                        closing.minute(),
+                        closing.hour(),
+                        closing.weekday().num_days_from_sunday()
+                    ),
+                    closed_for,
+                ))
+            })
+            .collect()
+    }
+
+    /// First opening of the window after the 1st of January 2024, for providers expecting a date time to start from
+    pub fn first_opening(&self) -> DateTime<Utc> {
+        reference_week_opening(self.days[0], self.start_time)
+    }
+}
+
+/// Full english name of the day, i.e: `Monday`
+pub fn weekday_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+/// Opening of the window on `day`, during the week of monday 1st of January 2024
+fn reference_week_opening(day: Weekday, start_time: NaiveTime) -> DateTime<Utc> {
+    let monday = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap_or_default();
+    (monday + Duration::days(day.num_days_from_monday() as i64))
+        .and_time(start_time)
+        .and_utc()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn window(days: Vec<Weekday>, start_time: &str, duration_in_hours: i64) -> MaintenanceWindow {
+        MaintenanceWindow::new(
+            days,
+            NaiveTime::parse_from_str(start_time, "%H:%M").expect("valid start time"),
+            Duration::hours(duration_in_hours),
+        )
+        .expect("valid maintenance window")
+    }
+
+    #[test]
+    fn test_is_open_at() {
+        // saturday and sunday, from 22:00 to 02:00
+        let window = window(vec![Weekday::Sun, Weekday::Sat], "22:00", 4);
+
+        // 2024-01-06 is a saturday
+        assert!(window.is_open_at(Utc.with_ymd_and_hms(2024, 1, 6, 22, 0, 0).unwrap()));
+        assert!(window.is_open_at(Utc.with_ymd_and_hms(2024, 1, 7, 1, 59, 0).unwrap()));
+        assert!(window.is_open_at(Utc.with_ymd_and_hms(2024, 1, 8, 1, 0, 0).unwrap()));
+        assert!(!window.is_open_at(Utc.with_ymd_and_hms(2024, 1, 8, 2, 0, 0).unwrap()));
+        assert!(!window.is_open_at(Utc.with_ymd_and_hms(2024, 1, 6, 21, 59, 0).unwrap()));
+        assert!(!window.is_open_at(Utc.with_ymd_and_hms(2024, 1, 10, 23, 0, 0).unwrap()));
+
+        assert_eq!(
+            window.next_opening_after(Utc.with_ymd_and_hms(2024, 1, 8, 2, 0, 0).unwrap()),
+            Utc.with_ymd_and_hms(2024, 1, 13, 22, 0, 0).unwrap()
+        );
+        assert_eq!(
+            window.next_opening_after(Utc.with_ymd_and_hms(2024, 1, 6, 22, 0, 0).unwrap()),
+            Utc.with_ymd_and_hms(2024, 1, 7, 22, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_native_formats() {
+        let window = window(vec![Weekday::Sun, Weekday::Sat], "22:30", 4);
+
+        assert_eq!(window.days(), &[Weekday::Sat, Weekday::Sun]);
+        assert_eq!(window.to_rrule(), "FREQ=WEEKLY;BYDAY=SA,SU");
+        assert_eq!(
+            window.hours_by_day(),
+            vec![
+                (Weekday::Mon, vec![0, 1, 2]),
+                (Weekday::Sat, vec![22, 23]),
+                (Weekday::Sun, vec![0, 1, 2, 22, 23]),
+            ]
+        );
+        assert_eq!(
+            window.closed_periods(),
+            vec![
+                ("30 2 * * 0".to_string(), Duration::hours(20)),
+                ("30 2 * * 1".to_string(), Duration::hours(140)),
+            ]
+        );
+        assert_eq!(window.first_opening(), Utc.with_ymd_and_hms(2024, 1, 6, 22, 30, 0).unwrap());
+    }
+
+    #[test]
+    fn test_every_day_window_covering_the_whole_day_is_never_closed() {
+        let window = window(
+            vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+                Weekday::Sat,
+                Weekday::Sun,
+            ],
+            "00:00",
+            24,
+        );
+
+        assert!(window.is_every_day());
+        assert!(window.closed_periods().is_empty());
+        assert!(window.check_single_day_or_every_day().is_ok());
+        assert!(MaintenanceWindow::new(vec![], NaiveTime::MIN, Duration::hours(4)).is_err());
+        assert!(MaintenanceWindow::new(vec![Weekday::Mon], NaiveTime::MIN, Duration::hours(25)).is_err());
+    }
+
+    #[test]
+    fn test_provider_constraints() {
+        assert!(
+            window(vec![Weekday::Sat], "22:00", 4)
+                .check_single_day_or_every_day()
+                .is_ok()
+        );
+        assert_eq!(
+            window(vec![Weekday::Sun, Weekday::Sat], "22:00", 4).check_single_day_or_every_day(),
+            Err("the window must open on a single day or on every day, got Saturday, Sunday".to_string())
+        );
+        assert!(
+            window(vec![Weekday::Sat], "22:00", 4)
+                .check_min_duration_in_hours(4)
+                .is_ok()
+        );
+        assert_eq!(
+            window(vec![Weekday::Sat], "22:00", 2).check_min_duration_in_hours(4),
+            Err("the window must last at least 4 hours, got 120 minutes".to_string())
+        );
+    }
+}
@@ -2,16 +2,18 @@ pub mod aws;
 pub mod azure;
 pub mod eksanywhere;
 pub mod gcp;
+pub mod instance_capacity;
 pub mod karpenter;
+pub mod maintenance_window;
 pub mod scaleway;
 pub mod self_managed;
 
 use crate::cmd::kubectl::kubectl_delete_apiservice;
 use crate::cmd::kubectl::{
     kubectl_delete_objects_in_all_namespaces, kubectl_exec_count_all_objects, kubectl_exec_get_node,
-    kubectl_exec_version, kubernetes_get_all_pdbs,
+    kubectl_exec_get_pods, kubectl_exec_version, kubernetes_get_all_pdbs,
 };
-use crate::cmd::structs::KubernetesNodeCondition;
+use crate::cmd::structs::{KubernetesNodeCondition, KubernetesPodStatusPhase};
 use crate::environment::models::types::VersionsNumber;
 use crate::errors::{CommandError, EngineError, ErrorMessageVerbosity};
 use crate::events::Stage::Infrastructure;
@@ -21,6 +23,10 @@ use crate::infrastructure::models::cloud_provider::CloudProvider;
 use crate::infrastructure::models::cloud_provider::Kind as CloudProviderKind;
 use crate::infrastructure::models::cloud_provider::io::ClusterAdvancedSettings;
 use crate::infrastructure::models::cloud_provider::service::Action;
+use crate::infrastructure::models::cost_allocation::CostAllocationTags;
+use crate::infrastructure::models::kubernetes::instance_capacity::NodeCapacities;
+use crate::infrastructure::models::kubernetes::karpenter::KarpenterUserNodePool;
+use crate::infrastructure::models::object_storage::ObjectStorage;
 use crate::io_models::QoveryIdentifier;
 use crate::io_models::context::Context;
 use crate::io_models::models::NodeGroupsWithDesiredState;
@@ -515,6 +521,18 @@ pub trait Kubernetes: Send + Sync {
     fn logger(&self) -> &dyn Logger;
     fn is_network_managed_by_user(&self) -> bool;
     fn cpu_architectures(&self) -> Vec<CpuArchitecture>;
+    // Object storage of the cluster, if the cluster is managed with one
+    fn object_storage(&self) -> Option<&dyn ObjectStorage> {
+        None
+    }
+    // Bucket of the cluster object storage where cluster backups are stored
+    fn backups_bucket_name(&self) -> String {
+        backups_bucket_name(self.short_id())
+    }
+    // Tags set on the cloud resources of the cluster itself (node groups, buckets, etc.)
+    fn cost_allocation_tags(&self) -> CostAllocationTags {
+        CostAllocationTags::for_cluster(*self.context().organization_long_id(), *self.long_id())
+    }
     fn get_event_details(&self, stage: Stage) -> EventDetails {
         let context = self.context();
         EventDetails::new(
@@ -539,6 +557,14 @@ pub trait Kubernetes: Send + Sync {
     fn is_karpenter_enabled(&self) -> bool {
         false
     }
+    fn karpenter_user_node_pool(&self, _name: &str) -> Option<&KarpenterUserNodePool> {
+        None
+    }
+    /// Capacities of the instance types the cluster can run services on, in the given user node pool or in the default placement.
+    /// Empty when nodes are not known by the engine (i.e autopilot or self managed clusters).
+    fn node_capacities(&self, _node_pool: Option<&str>) -> NodeCapacities {
+        NodeCapacities::default()
+    }
     fn loadbalancer_l4_annotations(&self, cloud_provider_lb_name: Option<&str>) -> Vec<(String, String)>;
 
     fn as_infra_actions(&self) -> &dyn InfrastructureAction;
@@ -633,6 +659,11 @@ pub fn event_details(
     )
 }
 
+/// Bucket where backups of a cluster are stored, also used to restore them into another cluster
+pub fn backups_bucket_name(cluster_short_id: &str) -> String {
+    format!("qovery-backups-{cluster_short_id}")
+}
+
 pub fn uninstall_cert_manager<P>(
     kubernetes_config: P,
     envs: Vec<(&str, &str)>,
@@ -958,6 +989,42 @@ where
     }
 }
 
+/// For clusters whose nodes are managed by the cloud provider (i.e: GKE autopilot), there are no worker nodes to
+/// wait for, only the pods of the paused workloads
+pub fn check_workloads_pause<P>(
+    kubernetes_config: P,
+    envs: Vec<(&str, &str)>,
+    pod_selector: &str,
+) -> Result<(), CommandError>
+where
+    P: AsRef<Path>,
+{
+    let result = retry::retry(Fixed::from_millis(10000).take(60), || {
+        match kubectl_exec_get_pods(kubernetes_config.as_ref(), None, Some(pod_selector), envs.clone()) {
+            Err(e) => OperationResult::Retry(e),
+            Ok(pods) => {
+                if pods.items.iter().any(|pod| {
+                    matches!(
+                        pod.status.phase,
+                        KubernetesPodStatusPhase::Pending | KubernetesPodStatusPhase::Running
+                    )
+                }) {
+                    return OperationResult::Retry(CommandError::new_from_safe_message(
+                        "There are still not paused workloads.".to_string(),
+                    ));
+                }
+
+                OperationResult::Ok(())
+            }
+        }
+    });
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(retry::Error { error, .. }) => Err(error),
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum KubernetesNodesType {
     Masters,
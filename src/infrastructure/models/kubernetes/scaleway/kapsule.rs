@@ -3,7 +3,9 @@ use crate::events::Stage::Infrastructure;
 use crate::events::{EngineEvent, EventDetails, InfrastructureStep, Transmitter};
 use crate::infrastructure::action::kubeconfig_helper::write_kubeconfig_on_disk;
 use crate::infrastructure::models::cloud_provider::CloudProvider;
-use crate::infrastructure::models::cloud_provider::io::ClusterAdvancedSettings;
+use crate::infrastructure::models::cloud_provider::io::{ClusterAdvancedSettings, InputError};
+use crate::infrastructure::models::cost_allocation::CostAllocationTags;
+use crate::infrastructure::models::kubernetes::instance_capacity::{InstanceCapacityCatalog, NodeCapacities};
 use crate::infrastructure::models::kubernetes::scaleway::node::ScwInstancesType;
 use crate::infrastructure::models::kubernetes::{
     self, InstanceType, Kind, Kubernetes, KubernetesVersion, ProviderOptions,
@@ -19,6 +21,7 @@ use crate::environment::models::domain::ToTerraformString;
 use crate::environment::models::scaleway::ScwZone;
 use crate::infrastructure::action::InfrastructureAction;
 use crate::infrastructure::models::cloud_provider::scaleway::ScalewayCredentials;
+use crate::infrastructure::models::object_storage::ObjectStorage;
 use crate::infrastructure::models::object_storage::scaleway_object_storage::ScalewayOS;
 use crate::io_models::metrics::MetricsParameters;
 use crate::runtime::block_on;
@@ -203,6 +206,21 @@ impl Kapsule {
         }
 
         advanced_settings.validate(event_details.clone())?;
+        // Kapsule auto upgrades open on a single day per week or every day
+        if let Some(maintenance_window) = advanced_settings.maintenance_window() {
+            let to_input_error = |field_name: &str, message: String| {
+                Box::new(EngineError::new_invalid_engine_payload_invalid_field_value(
+                    event_details.clone(),
+                    InputError::InvalidInputFieldValue {
+                        field_name: field_name.to_string(),
+                        message,
+                    },
+                ))
+            };
+            maintenance_window
+                .check_single_day_or_every_day()
+                .map_err(|message| to_input_error("cluster.maintenance_window.days", message))?;
+        }
 
         let creds = cloud_provider.downcast_ref();
         let creds = creds
@@ -221,7 +239,8 @@ impl Kapsule {
             creds.access_key.to_string(),
             creds.secret_key.to_string(),
             zone,
-        );
+        )
+        .with_cost_allocation_tags(CostAllocationTags::for_cluster(*context.organization_long_id(), long_id));
 
         let cluster = Kapsule {
             context,
@@ -382,6 +401,24 @@ impl Kubernetes for Kapsule {
             .collect()
     }
 
+    fn node_capacities(&self, _node_pool: Option<&str>) -> NodeCapacities {
+        self.nodes_groups
+            .iter()
+            .map(|node_group| {
+                (
+                    node_group.instance_type.clone(),
+                    ScwInstancesType::from_str(&node_group.instance_type)
+                        .ok()
+                        .and_then(|instance_type| instance_type.capacity()),
+                )
+            })
+            .collect()
+    }
+
+    fn object_storage(&self) -> Option<&dyn ObjectStorage> {
+        Some(&self.object_storage)
+    }
+
     fn temp_dir(&self) -> &Path {
         &self.temp_dir
     }
@@ -6,7 +6,10 @@ use uuid::Uuid;
 
 use crate::cmd::docker;
 use crate::errors::EngineError;
+use crate::events::InfrastructureStep;
+use crate::events::Stage::Infrastructure;
 use crate::infrastructure::action::InfrastructureAction;
+use crate::infrastructure::action::kubeconfig_helper::write_kubeconfig_on_disk;
 use crate::infrastructure::models::cloud_provider::io::ClusterAdvancedSettings;
 use crate::infrastructure::models::kubernetes::{self, Kind, Kubernetes, KubernetesVersion};
 use crate::io_models::context::Context;
@@ -15,6 +18,7 @@ use crate::io_models::models::CpuArchitecture;
 use crate::io_models::models::CpuArchitecture::{AMD64, ARM64};
 use crate::logger::Logger;
 use crate::utilities::to_short_id;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
@@ -26,11 +30,11 @@ pub struct SelfManaged {
     name: String,
     version: KubernetesVersion,
     region: String,
-    #[allow(dead_code)] //TODO(pmavro): not yet implemented
+    created_at: DateTime<Utc>,
     options: SelfManagedOptions,
     logger: Box<dyn Logger>,
     advanced_settings: ClusterAdvancedSettings,
-    _kubeconfig: Option<String>,
+    kubeconfig: Option<String>,
     temp_dir: PathBuf,
 }
 
@@ -42,6 +46,7 @@ impl SelfManaged {
         kind: Kind,
         region: String,
         version: KubernetesVersion,
+        created_at: DateTime<Utc>,
         options: SelfManagedOptions,
         logger: Box<dyn Logger>,
         advanced_settings: ClusterAdvancedSettings,
@@ -56,15 +61,37 @@ impl SelfManaged {
             name,
             version,
             region,
+            created_at,
             options,
             logger,
             advanced_settings,
-            _kubeconfig: kubeconfig,
+            kubeconfig,
             temp_dir,
         };
 
+        // make sure to write kubeconfig file, the engine may also run within the cluster without it
+        if let Some(kubeconfig) = cluster.kubeconfig() {
+            write_kubeconfig_on_disk(
+                &cluster.kubeconfig_local_file_path(),
+                kubeconfig,
+                cluster.get_event_details(Infrastructure(InfrastructureStep::LoadConfiguration)),
+            )?;
+        }
+
         Ok(cluster)
     }
+
+    pub fn options(&self) -> &SelfManagedOptions {
+        &self.options
+    }
+
+    pub fn kubeconfig(&self) -> Option<&str> {
+        self.kubeconfig.as_deref()
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -75,6 +102,26 @@ pub struct SelfManagedOptions {
     pub qovery_engine_url: String,
     pub jwt_token: String,
     pub qovery_engine_location: EngineLocation,
+    #[serde(default)]
+    pub tls_email_report: String,
+    #[serde(default)]
+    pub services: SelfManagedServices,
+}
+
+/// Bootstrap charts Qovery deploys next to its agents, the user may already run their own ones so all are opt-in
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SelfManagedServices {
+    /// ingress-nginx
+    pub ingress: bool,
+    /// external-dns
+    pub dns: bool,
+    /// loki and promtail, logs are stored on the cluster
+    pub logging: bool,
+    /// cert-manager, its configs and the Qovery DNS webhook
+    pub certificates: bool,
+    /// metrics-server
+    pub observability: bool,
 }
 
 impl Kubernetes for SelfManaged {
@@ -1,3 +1,4 @@
+use crate::infrastructure::models::cost_allocation::CostAllocationTags;
 use crate::infrastructure::models::object_storage::errors::ObjectStorageError;
 use crate::infrastructure::models::object_storage::{Bucket, BucketDeleteStrategy, BucketObject};
 use crate::services;
@@ -31,6 +32,7 @@ pub struct AzureOS {
     name: String,
 
     service: Arc<BlobStorageService>,
+    cost_allocation_tags: CostAllocationTags,
 }
 
 impl AzureOS {
@@ -41,9 +43,15 @@ impl AzureOS {
             name: name.to_string(),
 
             service,
+            cost_allocation_tags: CostAllocationTags::default(),
         }
     }
 
+    pub fn with_cost_allocation_tags(mut self, cost_allocation_tags: CostAllocationTags) -> Self {
+        self.cost_allocation_tags = cost_allocation_tags;
+        self
+    }
+
     pub fn id(&self) -> &str {
         &self.id
     }
@@ -89,13 +97,17 @@ impl AzureOS {
                 account: storage_account.account_name.to_string(),
             },
             bucket_ttl,
-            Some(HashMap::from([
-                ("creation_date".to_string(), creation_date.timestamp().to_string()),
-                (
-                    "ttl".to_string(),
-                    format!("{}", bucket_ttl.map(|ttl| ttl.as_secs()).unwrap_or(0)),
-                ),
-            ])),
+            Some(HashMap::from_iter(
+                [
+                    ("creation_date".to_string(), creation_date.timestamp().to_string()),
+                    (
+                        "ttl".to_string(),
+                        format!("{}", bucket_ttl.map(|ttl| ttl.as_secs()).unwrap_or(0)),
+                    ),
+                ]
+                .into_iter()
+                .chain(self.cost_allocation_tags.to_tags()),
+            )),
         ) {
             Ok(o) => Ok(o),
             Err(e) => Err(ObjectStorageError::CannotCreateBucket {
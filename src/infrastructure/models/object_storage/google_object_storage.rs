@@ -1,3 +1,4 @@
+use crate::infrastructure::models::cost_allocation::CostAllocationTags;
 use crate::infrastructure::models::object_storage::errors::ObjectStorageError;
 use crate::infrastructure::models::object_storage::{Bucket, BucketDeleteStrategy, BucketObject};
 use crate::infrastructure::models::object_storage::{Kind, ObjectStorage};
@@ -17,6 +18,7 @@ pub struct GoogleOS {
     project_id: String,
     region: GcpStorageRegion,
     service: Arc<ObjectStorageService>,
+    cost_allocation_tags: CostAllocationTags,
 }
 
 impl GoogleOS {
@@ -35,8 +37,14 @@ impl GoogleOS {
             project_id: project_id.to_string(),
             region,
             service,
+            cost_allocation_tags: CostAllocationTags::default(),
         }
     }
+
+    pub fn with_cost_allocation_tags(mut self, cost_allocation_tags: CostAllocationTags) -> Self {
+        self.cost_allocation_tags = cost_allocation_tags;
+        self
+    }
 }
 
 impl ObjectStorage for GoogleOS {
@@ -81,15 +89,19 @@ impl ObjectStorage for GoogleOS {
             bucket_ttl,
             bucket_versioning_activated,
             bucket_logging_activated,
-            Some(HashMap::from([
-                // Tags keys rule: Only hyphens (-), underscores (_), lowercase characters, and numbers are allowed.
-                // Keys must start with a lowercase character. International characters are allowed.
-                ("creation_date".to_string(), creation_date.timestamp().to_string()),
-                (
-                    "ttl".to_string(),
-                    format!("{}", bucket_ttl.map(|ttl| ttl.as_secs()).unwrap_or(0)),
-                ),
-            ])),
+            Some(HashMap::from_iter(
+                [
+                    // Tags keys rule: Only hyphens (-), underscores (_), lowercase characters, and numbers are allowed.
+                    // Keys must start with a lowercase character. International characters are allowed.
+                    ("creation_date".to_string(), creation_date.timestamp().to_string()),
+                    (
+                        "ttl".to_string(),
+                        format!("{}", bucket_ttl.map(|ttl| ttl.as_secs()).unwrap_or(0)),
+                    ),
+                ]
+                .into_iter()
+                .chain(self.cost_allocation_tags.to_tags()),
+            )),
         ) {
             Ok(o) => Ok(o),
             Err(e) => Err(ObjectStorageError::CannotCreateBucket {
@@ -7,6 +7,7 @@ use crate::services::gcp::object_storage_regions::GcpStorageRegion;
 use enum_dispatch::enum_dispatch;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::io::Write;
 use std::path::Path;
 use std::time::Duration;
 use strum_macros::EnumIter;
@@ -70,6 +71,22 @@ pub trait ObjectStorage {
     ) -> Result<(), ObjectStorageError>;
     fn delete_bucket_non_blocking(&self, bucket_name: &str) -> Result<(), ObjectStorageError>;
     fn get_object(&self, bucket_name: &str, object_key: &str) -> Result<BucketObject, ObjectStorageError>;
+    /// Write the object content into `writer`, without loading the whole object in memory when the storage allows it
+    fn get_object_to_writer(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+        writer: &mut dyn Write,
+    ) -> Result<(), ObjectStorageError> {
+        let object = self.get_object(bucket_name, object_key)?;
+        writer
+            .write_all(&object.value)
+            .map_err(|e| ObjectStorageError::CannotGetObjectFile {
+                bucket_name: bucket_name.to_string(),
+                object_name: object_key.to_string(),
+                raw_error_message: format!("Cannot write object: {e}"),
+            })
+    }
     fn put_object(
         &self,
         bucket_name: &str,
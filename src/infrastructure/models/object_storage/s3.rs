@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use itertools::Itertools;
 use retry::delay::Fixed;
 use std::collections::HashMap;
-use std::io::Read;
+use std::io::{Read, Write};
 use std::path::Path;
 use std::str::FromStr;
 use std::time::Duration;
@@ -19,6 +19,7 @@ use rusoto_s3::{
 
 use crate::environment::models::ToCloudProviderFormat;
 use crate::infrastructure::models::cloud_provider::aws::{AwsCredentials, new_rusoto_creds};
+use crate::infrastructure::models::cost_allocation::CostAllocationTags;
 use crate::infrastructure::models::object_storage::errors::ObjectStorageError;
 use crate::infrastructure::models::object_storage::{
     Bucket, BucketDeleteStrategy, BucketObject, BucketRegion, Kind, ObjectStorage,
@@ -30,6 +31,7 @@ pub struct S3 {
     name: String,
     credentials: AwsCredentials,
     region: AwsRegion,
+    cost_allocation_tags: CostAllocationTags,
 }
 
 impl S3 {
@@ -39,9 +41,15 @@ impl S3 {
             name,
             credentials,
             region,
+            cost_allocation_tags: CostAllocationTags::default(),
         }
     }
 
+    pub fn with_cost_allocation_tags(mut self, cost_allocation_tags: CostAllocationTags) -> Self {
+        self.cost_allocation_tags = cost_allocation_tags;
+        self
+    }
+
     fn get_s3_client(&self) -> S3Client {
         let region = RusotoRegion::from_str(self.region.to_cloud_provider_format()).unwrap_or_else(|_| {
             panic!(
@@ -193,23 +201,33 @@ impl ObjectStorage for S3 {
         }
 
         let creation_date: DateTime<Utc> = Utc::now();
-        if let Err(e) = block_on(s3_client.put_bucket_tagging(PutBucketTaggingRequest {
-            bucket: bucket_name.to_string(),
-            expected_bucket_owner: None,
-            tagging: Tagging {
-                tag_set: vec![
-                    Tag {
-                        key: "CreationDate".to_string(),
-                        value: creation_date.to_rfc3339(),
-                    },
-                    Tag {
-                        key: "Ttl".to_string(),
-                        value: format!("{}", bucket_ttl.map(|ttl| ttl.as_secs()).unwrap_or(0)),
-                    },
-                ],
-            },
-            ..Default::default()
-        })) {
+        if let Err(e) = block_on(
+            s3_client.put_bucket_tagging(PutBucketTaggingRequest {
+                bucket: bucket_name.to_string(),
+                expected_bucket_owner: None,
+                tagging: Tagging {
+                    tag_set: vec![
+                        Tag {
+                            key: "CreationDate".to_string(),
+                            value: creation_date.to_rfc3339(),
+                        },
+                        Tag {
+                            key: "Ttl".to_string(),
+                            value: format!("{}", bucket_ttl.map(|ttl| ttl.as_secs()).unwrap_or(0)),
+                        },
+                    ]
+                    .into_iter()
+                    .chain(
+                        self.cost_allocation_tags
+                            .to_tags()
+                            .into_iter()
+                            .map(|(key, value)| Tag { key, value }),
+                    )
+                    .collect(),
+                },
+                ..Default::default()
+            }),
+        ) {
             return Err(ObjectStorageError::CannotTagBucket {
                 bucket_name: bucket_name.to_string(),
                 raw_error_message: e.to_string(),
@@ -425,6 +443,35 @@ impl ObjectStorage for S3 {
         }
     }
 
+    fn get_object_to_writer(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+        writer: &mut dyn Write,
+    ) -> Result<(), ObjectStorageError> {
+        S3::is_bucket_name_valid(bucket_name)?;
+
+        let to_error = |raw_error_message: String| ObjectStorageError::CannotGetObjectFile {
+            bucket_name: bucket_name.to_string(),
+            object_name: object_key.to_string(),
+            raw_error_message,
+        };
+        let res = block_on(self.get_s3_client().get_object(GetObjectRequest {
+            bucket: bucket_name.to_string(),
+            key: object_key.to_string(),
+            expected_bucket_owner: None,
+            ..Default::default()
+        }))
+        .map_err(|e| to_error(e.to_string()))?;
+        let mut stream = res
+            .body
+            .ok_or_else(|| to_error("Cannot get response body".to_string()))?
+            .into_blocking_read();
+        std::io::copy(&mut stream, writer).map_err(|e| to_error(format!("Cannot read response body: {e}")))?;
+
+        Ok(())
+    }
+
     fn put_object(
         &self,
         bucket_name: &str,
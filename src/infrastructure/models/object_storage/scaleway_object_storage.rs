@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
-use std::io::Read;
+use std::io::{Read, Write};
 use std::path::Path;
 use std::time::Duration;
 
@@ -9,6 +9,7 @@ use crate::infrastructure::models::object_storage::{
 };
 
 use crate::environment::models::scaleway::ScwZone;
+use crate::infrastructure::models::cost_allocation::CostAllocationTags;
 use crate::infrastructure::models::object_storage::errors::ObjectStorageError;
 use crate::runtime::block_on;
 use rusoto_core::{Client, HttpClient, Region as RusotoRegion};
@@ -28,6 +29,7 @@ pub struct ScalewayOS {
     access_key: String,
     secret_token: String,
     zone: ScwZone,
+    cost_allocation_tags: CostAllocationTags,
 }
 
 impl ScalewayOS {
@@ -38,9 +40,15 @@ impl ScalewayOS {
             access_key,
             secret_token,
             zone,
+            cost_allocation_tags: CostAllocationTags::default(),
         }
     }
 
+    pub fn with_cost_allocation_tags(mut self, cost_allocation_tags: CostAllocationTags) -> Self {
+        self.cost_allocation_tags = cost_allocation_tags;
+        self
+    }
+
     fn get_s3_client(&self) -> S3Client {
         let region = RusotoRegion::Custom {
             name: self.zone.region().to_string(),
@@ -189,24 +197,32 @@ impl ObjectStorage for ScalewayOS {
         }
 
         let creation_date: DateTime<Utc> = Utc::now();
-        if let Err(e) = block_on(s3_client.put_bucket_tagging(PutBucketTaggingRequest {
-            bucket: bucket_name.to_string(),
-            expected_bucket_owner: None,
-            // Note: SCW doesn't support key/value tags, keys should be added inside the value
-            tagging: Tagging {
-                tag_set: vec![
-                    Tag {
-                        key: "CreationDate".to_string(),
-                        value: format!("CreationDate:{}", creation_date.to_rfc3339()),
-                    },
-                    Tag {
-                        key: "Ttl".to_string(),
-                        value: format!("Ttl:{}", bucket_ttl.map(|ttl| ttl.as_secs()).unwrap_or(0)),
-                    },
-                ],
-            },
-            ..Default::default()
-        })) {
+        if let Err(e) = block_on(
+            s3_client.put_bucket_tagging(PutBucketTaggingRequest {
+                bucket: bucket_name.to_string(),
+                expected_bucket_owner: None,
+                // Note: SCW doesn't support key/value tags, keys should be added inside the value
+                tagging: Tagging {
+                    tag_set: vec![
+                        Tag {
+                            key: "CreationDate".to_string(),
+                            value: format!("CreationDate:{}", creation_date.to_rfc3339()),
+                        },
+                        Tag {
+                            key: "Ttl".to_string(),
+                            value: format!("Ttl:{}", bucket_ttl.map(|ttl| ttl.as_secs()).unwrap_or(0)),
+                        },
+                    ]
+                    .into_iter()
+                    .chain(self.cost_allocation_tags.to_tags().into_iter().map(|(key, value)| Tag {
+                        value: format!("{key}:{value}"),
+                        key,
+                    }))
+                    .collect(),
+                },
+                ..Default::default()
+            }),
+        ) {
             return Err(ObjectStorageError::CannotTagBucket {
                 bucket_name: bucket_name.to_string(),
                 raw_error_message: e.to_string(),
@@ -407,6 +423,34 @@ impl ObjectStorage for ScalewayOS {
         }
     }
 
+    fn get_object_to_writer(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+        writer: &mut dyn Write,
+    ) -> Result<(), ObjectStorageError> {
+        ScalewayOS::is_bucket_name_valid(bucket_name)?;
+
+        let to_error = |raw_error_message: String| ObjectStorageError::CannotGetObjectFile {
+            bucket_name: bucket_name.to_string(),
+            object_name: object_key.to_string(),
+            raw_error_message,
+        };
+        let res = block_on(self.get_s3_client().get_object(GetObjectRequest {
+            bucket: bucket_name.to_string(),
+            key: object_key.to_string(),
+            ..Default::default()
+        }))
+        .map_err(|e| to_error(e.to_string()))?;
+        let mut stream = res
+            .body
+            .ok_or_else(|| to_error("Cannot get response body".to_string()))?
+            .into_blocking_read();
+        std::io::copy(&mut stream, writer).map_err(|e| to_error(format!("Cannot read response body: {e}")))?;
+
+        Ok(())
+    }
+
     fn put_object(
         &self,
         bucket_name: &str,
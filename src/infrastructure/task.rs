@@ -3,9 +3,11 @@ use crate::engine_task;
 use crate::engine_task::Task;
 use crate::engine_task::qovery_api::QoveryApi;
 use crate::environment::models::abort::{Abort, AbortStatus};
+use crate::environment::sweeper_task::EnvironmentSweeperTask;
 use crate::errors::EngineError;
 use crate::events::Stage::Infrastructure;
 use crate::events::{EngineEvent, EventDetails, EventMessage, InfrastructureStep, Transmitter};
+use crate::infrastructure::action::ClusterMaintenanceOutcome;
 use crate::io_models::context::Context;
 use crate::io_models::engine_request::InfrastructureEngineRequest;
 use crate::io_models::{Action, QoveryIdentifier};
@@ -27,6 +29,8 @@ pub struct InfrastructureTask {
     span: tracing::Span,
     is_terminated: (RwLock<Option<broadcast::Sender<()>>>, broadcast::Receiver<()>),
     log_file_writer: Option<LogFileWriter>,
+    // Set when the request asks to sweep the expired environments of the cluster, the task is then delegated to it
+    environment_sweeper: Option<EnvironmentSweeperTask>,
 }
 
 impl InfrastructureTask {
@@ -58,6 +62,20 @@ impl InfrastructureTask {
             },
         );
 
+        let qovery_api: Arc<dyn QoveryApi> = Arc::from(qovery_api);
+        let environment_sweeper = match request.sweep_expired_environments {
+            true => Some(EnvironmentSweeperTask::new(
+                request.clone(),
+                workspace_root_dir.clone(),
+                lib_root_dir.clone(),
+                docker.clone(),
+                logger.clone(),
+                metrics_registry.clone(),
+                qovery_api.clone(),
+            )),
+            false => None,
+        };
+
         InfrastructureTask {
             workspace_root_dir,
             lib_root_dir,
@@ -65,13 +83,14 @@ impl InfrastructureTask {
             request,
             logger,
             metrics_registry,
-            qovery_api: Arc::from(qovery_api),
+            qovery_api,
             span,
             is_terminated: {
                 let (tx, rx) = broadcast::channel(1);
                 (RwLock::new(Some(tx)), rx)
             },
             log_file_writer,
+            environment_sweeper,
         }
     }
 
@@ -95,53 +114,72 @@ impl InfrastructureTask {
         EventDetails::clone_changing_stage(self.request.event_details(), Infrastructure(step))
     }
 
-    fn handle_transaction_result(&self, logger: Box<dyn Logger>, transaction_result: Result<(), Box<EngineError>>) {
-        match transaction_result {
-            Ok(()) => self.send_infrastructure_progress(logger.clone(), None),
-            Err(err) => self.send_infrastructure_progress(logger.clone(), Some(err)),
+    /// Step reported once the requested action is successfully done
+    fn completed_step(&self) -> InfrastructureStep {
+        match self.request.action {
+            _ if self.request.cluster_backup_restore.is_some() => InfrastructureStep::Restored,
+            _ if self.request.cluster_maintenance => InfrastructureStep::Maintained,
+            _ if self.request.environments_costs.is_some() => InfrastructureStep::EnvironmentsCostsReported,
+            Action::Create => InfrastructureStep::Created,
+            Action::Pause => InfrastructureStep::Paused,
+            Action::Delete => InfrastructureStep::Deleted,
+            Action::Restart => InfrastructureStep::RestartedError,
         }
     }
 
-    fn send_infrastructure_progress(&self, logger: Box<dyn Logger>, option_engine_error: Option<Box<EngineError>>) {
+    fn send_infrastructure_progress(
+        &self,
+        logger: Box<dyn Logger>,
+        transaction_result: Result<InfrastructureStep, Box<EngineError>>,
+    ) {
         let kubernetes = &self.request.kubernetes;
-        if let Some(engine_error) = option_engine_error {
-            let infrastructure_step = match self.request.action {
-                Action::Create => InfrastructureStep::CreateError,
-                Action::Pause => InfrastructureStep::PauseError,
-                Action::Delete => InfrastructureStep::DeleteError,
-                Action::Restart => InfrastructureStep::RestartedError,
-            };
-            let event_message =
-                EventMessage::new_from_safe(format!("Kubernetes cluster failure {}", &infrastructure_step));
-
-            let engine_event = EngineEvent::Error(
-                engine_error.clone_engine_error_with_stage(Infrastructure(infrastructure_step)),
-                Some(event_message),
-            );
-
-            logger.log(engine_event);
-        } else {
-            let infrastructure_step = match self.request.action {
-                Action::Create => InfrastructureStep::Created,
-                Action::Pause => InfrastructureStep::Paused,
-                Action::Delete => InfrastructureStep::Deleted,
-                Action::Restart => InfrastructureStep::RestartedError,
-            };
-            let event_message =
-                EventMessage::new_from_safe(format!("Kubernetes cluster successfully {}", &infrastructure_step));
-            let engine_event = EngineEvent::Info(
-                EventDetails::new(
-                    Some(self.request.cloud_provider.kind.clone()),
-                    QoveryIdentifier::new(self.request.organization_long_id),
-                    QoveryIdentifier::new(kubernetes.long_id),
-                    self.request.id.to_string(),
-                    Infrastructure(infrastructure_step),
-                    Transmitter::Kubernetes(kubernetes.long_id, kubernetes.name.to_string()),
-                ),
-                event_message,
-            );
-
-            logger.log(engine_event);
+        match transaction_result {
+            Err(engine_error) => {
+                let infrastructure_step = match self.request.action {
+                    _ if self.request.cluster_backup_restore.is_some() => InfrastructureStep::RestoreError,
+                    _ if self.request.cluster_maintenance => InfrastructureStep::MaintenanceError,
+                    _ if self.request.environments_costs.is_some() => InfrastructureStep::EnvironmentsCostsError,
+                    Action::Create => InfrastructureStep::CreateError,
+                    Action::Pause => InfrastructureStep::PauseError,
+                    Action::Delete => InfrastructureStep::DeleteError,
+                    Action::Restart => InfrastructureStep::RestartedError,
+                };
+                let event_message =
+                    EventMessage::new_from_safe(format!("Kubernetes cluster failure {}", &infrastructure_step));
+
+                let engine_event = EngineEvent::Error(
+                    engine_error.clone_engine_error_with_stage(Infrastructure(infrastructure_step)),
+                    Some(event_message),
+                );
+
+                logger.log(engine_event);
+            }
+            Ok(infrastructure_step) => {
+                let event_message = match infrastructure_step {
+                    InfrastructureStep::MaintenanceSkipped => {
+                        EventMessage::new_from_safe("Kubernetes cluster maintenance skipped".to_string())
+                    }
+                    InfrastructureStep::EnvironmentsCostsReported => {
+                        EventMessage::new_from_safe("Kubernetes cluster environments costs reported".to_string())
+                    }
+                    _ => {
+                        EventMessage::new_from_safe(format!("Kubernetes cluster successfully {}", &infrastructure_step))
+                    }
+                };
+                let engine_event = EngineEvent::Info(
+                    EventDetails::new(
+                        Some(self.request.cloud_provider.kind.clone()),
+                        QoveryIdentifier::new(self.request.organization_long_id),
+                        QoveryIdentifier::new(kubernetes.long_id),
+                        self.request.id.to_string(),
+                        Infrastructure(infrastructure_step),
+                        Transmitter::Kubernetes(kubernetes.long_id, kubernetes.name.to_string()),
+                    ),
+                    event_message,
+                );
+
+                logger.log(engine_event);
+            }
         }
     }
 }
@@ -152,6 +190,11 @@ impl Task for InfrastructureTask {
     }
 
     fn run(&self) {
+        if let Some(environment_sweeper) = &self.environment_sweeper {
+            environment_sweeper.run();
+            return;
+        }
+
         if self.request.is_self_managed() {
             engine_task::enable_log_file_writer(&self.info_context(), &self.log_file_writer);
         }
@@ -187,16 +230,34 @@ impl Task for InfrastructureTask {
         ) {
             Ok(engine) => engine,
             Err(err) => {
-                self.send_infrastructure_progress(self.logger.clone(), Some(err));
+                self.send_infrastructure_progress(self.logger.clone(), Err(err));
                 return;
             }
         };
 
-        let ret = infra_ctx
-            .kubernetes()
-            .as_infra_actions()
-            .run(&infra_ctx, self.request.action.to_service_action());
-        self.handle_transaction_result(self.logger.clone(), ret);
+        let infra_actions = infra_ctx.kubernetes().as_infra_actions();
+        let ret = match &self.request.cluster_backup_restore {
+            Some(restore) => infra_actions
+                .restore_cluster_backup(&infra_ctx, restore)
+                .map(|_| self.completed_step()),
+            None if self.request.cluster_maintenance => {
+                infra_actions
+                    .run_cluster_maintenance(&infra_ctx)
+                    .map(|outcome| match outcome {
+                        ClusterMaintenanceOutcome::Applied => self.completed_step(),
+                        ClusterMaintenanceOutcome::Skipped => InfrastructureStep::MaintenanceSkipped,
+                    })
+            }
+            None => match &self.request.environments_costs {
+                Some(environments_costs) => infra_actions
+                    .report_environments_costs(&infra_ctx, environments_costs)
+                    .map(|_| self.completed_step()),
+                None => infra_actions
+                    .run(&infra_ctx, self.request.action.to_service_action())
+                    .map(|_| self.completed_step()),
+            },
+        };
+        self.send_infrastructure_progress(self.logger.clone(), ret);
 
         // Uploading to S3 can take a lot of time, and might hit the core timeout
         // So we early drop the guard to notify core that the task is done
@@ -222,19 +283,31 @@ impl Task for InfrastructureTask {
         info!("infrastructure task {} finished", self.id());
     }
 
-    fn cancel(&self, _force_requested: bool) -> bool {
-        false
+    fn cancel(&self, force_requested: bool) -> bool {
+        match &self.environment_sweeper {
+            Some(environment_sweeper) => environment_sweeper.cancel(force_requested),
+            None => false,
+        }
     }
 
     fn cancel_checker(&self) -> Box<dyn Abort> {
-        Box::new(move || AbortStatus::None)
+        match &self.environment_sweeper {
+            Some(environment_sweeper) => environment_sweeper.cancel_checker(),
+            None => Box::new(move || AbortStatus::None),
+        }
     }
 
     fn is_terminated(&self) -> bool {
-        self.is_terminated.0.read().map(|tx| tx.is_none()).unwrap_or(true)
+        match &self.environment_sweeper {
+            Some(environment_sweeper) => environment_sweeper.is_terminated(),
+            None => self.is_terminated.0.read().map(|tx| tx.is_none()).unwrap_or(true),
+        }
     }
 
     fn await_terminated(&self) -> broadcast::Receiver<()> {
-        self.is_terminated.1.resubscribe()
+        match &self.environment_sweeper {
+            Some(environment_sweeper) => environment_sweeper.await_terminated(),
+            None => self.is_terminated.1.resubscribe(),
+        }
     }
 }
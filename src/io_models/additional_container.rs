@@ -0,0 +1,104 @@
+use crate::environment::models::additional_container as models;
+use crate::environment::models::registry_image_source::RegistryImageSource;
+use crate::infrastructure::models::container_registry::InteractWithRegistry;
+use crate::infrastructure::models::kubernetes::Kubernetes;
+use crate::io_models::application::to_environment_variable;
+use crate::io_models::container::Registry;
+use crate::io_models::models::{KubernetesCpuResourceUnit, KubernetesMemoryResourceUnit};
+use crate::io_models::probe::Probe;
+use crate::io_models::variable_utils::{VariableInfo, default_environment_vars_with_info};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AdditionalContainerKind {
+    /// Runs to completion, in declaration order, before the service container starts (i.e: database migrations)
+    Init,
+    /// Runs alongside the service container for the whole life of the pod (i.e: log shippers, cloud sql proxies)
+    Sidecar,
+}
+
+/// Empty dir volume shared by the pod containers
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct SharedVolume {
+    pub name: String,
+    /// Path the volume is mounted at in the additional container
+    pub mount_path: String,
+    /// Path the volume is mounted at in the service container, not mounted in it when `None`
+    #[serde(default)]
+    pub service_mount_path: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
+pub struct AdditionalContainer {
+    pub name: String,
+    pub kind: AdditionalContainerKind,
+    pub registry: Registry,
+    pub image: String,
+    pub tag: String,
+    #[serde(default)]
+    pub command_args: Vec<String>,
+    #[serde(default)]
+    pub entrypoint: Option<String>,
+    /// Key is a String, Value is a base64 encoded String
+    #[serde(default = "default_environment_vars_with_info")]
+    pub environment_vars_with_infos: BTreeMap<String, VariableInfo>,
+    pub cpu_request_in_milli: u32,
+    pub cpu_limit_in_milli: u32,
+    pub ram_request_in_mib: u32,
+    pub ram_limit_in_mib: u32,
+    #[serde(default)]
+    pub readiness_probe: Option<Probe>,
+    #[serde(default)]
+    pub liveness_probe: Option<Probe>,
+    #[serde(default)]
+    pub shared_volumes: Vec<SharedVolume>,
+}
+
+impl AdditionalContainer {
+    pub fn to_domain(
+        mut self,
+        default_container_registry: &dyn InteractWithRegistry,
+        cluster: &dyn Kubernetes,
+    ) -> models::AdditionalContainer {
+        // Same as for containers, the url of the default registry is only known by the engine
+        if self.registry.id() == default_container_registry.long_id() {
+            self.registry
+                .set_url(default_container_registry.get_registry_endpoint(Some(cluster.cluster_name().as_str())));
+        }
+
+        models::AdditionalContainer {
+            name: self.name,
+            kind: match self.kind {
+                AdditionalContainerKind::Init => models::AdditionalContainerKind::Init,
+                AdditionalContainerKind::Sidecar => models::AdditionalContainerKind::Sidecar,
+            },
+            source: RegistryImageSource {
+                registry: self.registry,
+                image: self.image,
+                tag: self.tag,
+                artifact: None,
+                registry_mirroring_mode: cluster.advanced_settings().registry_mirroring_mode.clone(),
+            },
+            command_args: self.command_args,
+            entrypoint: self.entrypoint,
+            environment_variables: to_environment_variable(self.environment_vars_with_infos),
+            cpu_request_in_milli: KubernetesCpuResourceUnit::MilliCpu(self.cpu_request_in_milli),
+            cpu_limit_in_milli: KubernetesCpuResourceUnit::MilliCpu(self.cpu_limit_in_milli),
+            ram_request_in_mib: KubernetesMemoryResourceUnit::MebiByte(self.ram_request_in_mib),
+            ram_limit_in_mib: KubernetesMemoryResourceUnit::MebiByte(self.ram_limit_in_mib),
+            readiness_probe: self.readiness_probe.map(|p| p.to_domain()),
+            liveness_probe: self.liveness_probe.map(|p| p.to_domain()),
+            shared_volumes: self
+                .shared_volumes
+                .into_iter()
+                .map(|volume| models::SharedVolume {
+                    name: volume.name,
+                    mount_path: volume.mount_path,
+                    service_mount_path: volume.service_mount_path,
+                })
+                .collect(),
+        }
+    }
+}
@@ -3,14 +3,13 @@ use std::str;
 use std::sync::Arc;
 use std::time::Duration;
 
-use base64::Engine;
-use base64::engine::general_purpose;
 use chrono::{DateTime, Utc};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use url::Url;
 use uuid::Uuid;
 
+use crate::cmd::docker::BuildProvenance;
 use crate::engine_task::qovery_api::QoveryApi;
 use crate::environment::models;
 use crate::environment::models::application::{ApplicationError, ApplicationService};
@@ -20,12 +19,17 @@ use crate::environment::models::gcp::GcpAppExtraSettings;
 use crate::environment::models::scaleway::ScwAppExtraSettings;
 use crate::environment::models::selfmanaged::OnPremiseAppExtraSettings;
 use crate::environment::models::types::{AWS, Azure, GCP, OnPremise, SCW};
-use crate::infrastructure::models::build_platform::{Build, GitRepository, Image, SshKey};
+use crate::infrastructure::models::build_platform::{Build, BuildCacheBackendKind, GitRepository, Image, SshKey};
 use crate::infrastructure::models::cloud_provider::io::{NginxConfigurationSnippet, NginxServerSnippet};
 use crate::infrastructure::models::cloud_provider::service::ServiceType;
 use crate::infrastructure::models::cloud_provider::{CloudProvider, Kind as CPKind};
-use crate::infrastructure::models::container_registry::{ContainerRegistryInfo, DockerRegistryInfo};
+use crate::infrastructure::models::container_registry::{
+    ContainerRegistryInfo, DockerRegistryInfo, InteractWithRegistry,
+};
+use crate::infrastructure::models::kubernetes::Kubernetes;
+use crate::io_models::additional_container::AdditionalContainer;
 use crate::io_models::annotations_group::AnnotationsGroup;
+use crate::io_models::autoscaling::EventDrivenAutoscaling;
 use crate::io_models::container::{ContainerAdvancedSettings, Registry};
 use crate::io_models::context::Context;
 use crate::io_models::labels_group::LabelsGroup;
@@ -35,8 +39,8 @@ use crate::io_models::models::{
 use crate::io_models::probe::Probe;
 use crate::io_models::variable_utils::{VariableInfo, default_environment_vars_with_info};
 use crate::io_models::{
-    Action, MountedFile, QoveryIdentifier, fetch_git_token, normalize_root_and_dockerfile_path, sanitized_git_url,
-    ssh_keys_from_env_vars,
+    Action, MountedFile, QoveryIdentifier, build_env_vars_from_env_vars, fetch_git_token, monorepo_watched_paths,
+    normalize_root_and_dockerfile_path, sanitized_git_url, ssh_keys_from_env_vars,
 };
 use crate::utilities::to_short_id;
 
@@ -114,6 +118,9 @@ pub struct ApplicationAdvancedSettings {
     pub deployment_update_strategy_rolling_update_max_surge_percent: u32,
     #[serde(alias = "deployment.affinity.node.required")]
     pub deployment_affinity_node_required: BTreeMap<String, String>,
+    #[serde(default)]
+    #[serde(alias = "deployment.node_pool")]
+    pub deployment_node_pool: Option<String>,
     #[serde(alias = "deployment.antiaffinity.pod")]
     pub deployment_antiaffinity_pod: PodAntiAffinity,
     #[serde(alias = "deployment.lifecycle.post_start_exec_command")]
@@ -131,6 +138,18 @@ pub struct ApplicationAdvancedSettings {
     #[serde(default)]
     #[serde(alias = "build.ephemeral_storage_in_gib")]
     pub build_ephemeral_storage_in_gib: Option<u32>,
+    #[serde(default)]
+    #[serde(alias = "build.monorepo_mode.enabled")]
+    pub build_monorepo_mode_enabled: bool,
+    #[serde(default)]
+    #[serde(alias = "build.monorepo_mode.watched_paths")]
+    pub build_monorepo_mode_watched_paths: Vec<String>,
+    #[serde(default)]
+    #[serde(alias = "build.cache_backend")]
+    pub build_cache_backend: Option<BuildCacheBackendKind>,
+    #[serde(default)]
+    #[serde(alias = "build.provenance")]
+    pub build_provenance: BuildProvenance,
 
     // Ingress
     #[serde(alias = "network.ingress.proxy_body_size_mb")]
@@ -213,6 +232,7 @@ impl Default for ApplicationAdvancedSettings {
             deployment_update_strategy_rolling_update_max_unavailable_percent: 25,
             deployment_update_strategy_rolling_update_max_surge_percent: 25,
             deployment_affinity_node_required: BTreeMap::new(),
+            deployment_node_pool: None,
             deployment_antiaffinity_pod: PodAntiAffinity::Preferred,
             deployment_lifecycle_post_start_exec_command: vec![],
             deployment_lifecycle_pre_stop_exec_command: vec![],
@@ -220,6 +240,10 @@ impl Default for ApplicationAdvancedSettings {
             build_cpu_max_in_milli: 4000,
             build_ram_max_in_gib: 8,
             build_ephemeral_storage_in_gib: None,
+            build_monorepo_mode_enabled: false,
+            build_monorepo_mode_watched_paths: vec![],
+            build_cache_backend: None,
+            build_provenance: BuildProvenance::default(),
             network_ingress_proxy_body_size_mb: 100,
             network_ingress_force_ssl_redirect: true,
             network_ingress_cors_enable: false,
@@ -269,6 +293,7 @@ impl ApplicationAdvancedSettings {
             deployment_update_strategy_rolling_update_max_surge_percent: self
                 .deployment_update_strategy_rolling_update_max_surge_percent,
             deployment_affinity_node_required: self.deployment_affinity_node_required.clone(),
+            deployment_node_pool: self.deployment_node_pool.clone(),
             deployment_antiaffinity_pod: self.deployment_antiaffinity_pod.clone(),
             deployment_lifecycle_post_start_exec_command: self.deployment_lifecycle_post_start_exec_command.clone(),
             deployment_lifecycle_pre_stop_exec_command: self.deployment_lifecycle_pre_stop_exec_command.clone(),
@@ -346,6 +371,11 @@ pub struct Application {
     pub liveness_probe: Option<Probe>,
     #[serde(default)]
     pub advanced_settings: ApplicationAdvancedSettings,
+    #[serde(default)]
+    pub event_driven_autoscaling: Option<EventDrivenAutoscaling>,
+    /// Init and sidecar containers running in the same pods as the application
+    #[serde(default)]
+    pub additional_containers: Vec<AdditionalContainer>,
     pub container_registries: Vec<Registry>,
     #[serde(default)]
     pub annotations_group_ids: BTreeSet<Uuid>,
@@ -369,10 +399,17 @@ impl Application {
         context: &Context,
         build: Build,
         cloud_provider: &dyn CloudProvider,
+        default_container_registry: &dyn InteractWithRegistry,
+        cluster: &dyn Kubernetes,
         annotations_group: &BTreeMap<Uuid, AnnotationsGroup>,
         labels_group: &BTreeMap<Uuid, LabelsGroup>,
     ) -> Result<Box<dyn ApplicationService>, ApplicationError> {
         let environment_variables = to_environment_variable(self.environment_vars_with_infos);
+        let additional_containers = self
+            .additional_containers
+            .into_iter()
+            .map(|c| c.to_domain(default_container_registry, cluster))
+            .collect_vec();
         let annotations_groups = self
             .annotations_group_ids
             .iter()
@@ -414,6 +451,8 @@ impl Application {
                     self.readiness_probe.map(|p| p.to_domain()),
                     self.liveness_probe.map(|p| p.to_domain()),
                     self.advanced_settings,
+                    self.event_driven_autoscaling.map(|a| a.to_domain()),
+                    additional_containers,
                     AwsAppExtraSettings {},
                     |transmitter| context.get_event_details(transmitter),
                     annotations_groups,
@@ -447,6 +486,8 @@ impl Application {
                 self.readiness_probe.map(|p| p.to_domain()),
                 self.liveness_probe.map(|p| p.to_domain()),
                 self.advanced_settings,
+                self.event_driven_autoscaling.map(|a| a.to_domain()),
+                additional_containers,
                 AzureAppExtraSettings {},
                 |transmitter| context.get_event_details(transmitter),
                 annotations_groups,
@@ -479,6 +520,8 @@ impl Application {
                 self.readiness_probe.map(|p| p.to_domain()),
                 self.liveness_probe.map(|p| p.to_domain()),
                 self.advanced_settings,
+                self.event_driven_autoscaling.map(|a| a.to_domain()),
+                additional_containers,
                 ScwAppExtraSettings {},
                 |transmitter| context.get_event_details(transmitter),
                 annotations_groups,
@@ -511,6 +554,8 @@ impl Application {
                 self.readiness_probe.map(|p| p.to_domain()),
                 self.liveness_probe.map(|p| p.to_domain()),
                 self.advanced_settings,
+                self.event_driven_autoscaling.map(|a| a.to_domain()),
+                additional_containers,
                 GcpAppExtraSettings {},
                 |transmitter| context.get_event_details(transmitter),
                 annotations_groups,
@@ -543,6 +588,8 @@ impl Application {
                 self.readiness_probe.map(|p| p.to_domain()),
                 self.liveness_probe.map(|p| p.to_domain()),
                 self.advanced_settings,
+                self.event_driven_autoscaling.map(|a| a.to_domain()),
+                additional_containers,
                 OnPremiseAppExtraSettings {},
                 |transmitter| context.get_event_details(transmitter),
                 annotations_groups,
@@ -600,7 +647,14 @@ impl Application {
         //FIXME: Return a result the function
         let url = Url::parse(&self.git_url).unwrap_or_else(|_| Url::parse("https://invalid-git-url.com").unwrap());
 
-        let mut disable_build_cache = false;
+        let build_env_vars = build_env_vars_from_env_vars(&self.environment_vars_with_infos);
+        let watched_paths = self.advanced_settings.build_monorepo_mode_enabled.then(|| {
+            monorepo_watched_paths(
+                &root_path,
+                &dockerfile_path,
+                &self.advanced_settings.build_monorepo_mode_watched_paths,
+            )
+        });
         let mut build = Build {
             git_repository: GitRepository {
                 url,
@@ -617,34 +671,22 @@ impl Application {
                 root_path,
                 extra_files_to_inject: vec![],
                 docker_target_build_stage: self.docker_target_build_stage.clone(),
+                watched_paths,
+                paths_content_hash: None,
+                commit_timestamp: None,
             },
             image: self.to_image(registry_url, cluster_id),
-            environment_variables: self
-                .environment_vars_with_infos
-                .iter()
-                .filter_map(|(k, variable_infos)| {
-                    // Remove special vars
-                    let v = String::from_utf8(
-                        general_purpose::STANDARD
-                            .decode(variable_infos.value.as_bytes())
-                            .unwrap_or_default(),
-                    )
-                    .unwrap_or_default();
-                    if k == "QOVERY_DISABLE_BUILD_CACHE" && v.to_lowercase() == "true" {
-                        disable_build_cache = true;
-                        return None;
-                    }
-
-                    Some((k.clone(), v))
-                })
-                .collect::<BTreeMap<_, _>>(),
-            disable_cache: disable_build_cache,
+            environment_variables: build_env_vars.environment_variables,
+            secret_environment_variables: build_env_vars.secret_environment_variables,
+            disable_cache: build_env_vars.disable_build_cache,
             timeout: Duration::from_secs(self.advanced_settings.build_timeout_max_sec as u64),
             architectures,
             max_cpu_in_milli: self.advanced_settings.build_cpu_max_in_milli,
             max_ram_in_gib: self.advanced_settings.build_ram_max_in_gib,
             ephemeral_storage_in_gib: self.advanced_settings.build_ephemeral_storage_in_gib,
             registries: self.container_registries.clone(),
+            cache_backend: self.advanced_settings.build_cache_backend,
+            provenance: self.advanced_settings.build_provenance,
         };
 
         build.compute_image_tag();
@@ -0,0 +1,101 @@
+use crate::environment::models::autoscaling as models;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+fn default_polling_interval_seconds() -> u32 {
+    30
+}
+
+fn default_cooldown_period_seconds() -> u32 {
+    300
+}
+
+/// Event driven autoscaling of a service, backed by KEDA (requires `keda.enabled` cluster advanced setting).
+/// When set, it replaces the cpu/memory HPA of the service and allows `min_instances` to be 0.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct EventDrivenAutoscaling {
+    pub scalers: Vec<Scaler>,
+    #[serde(default = "default_polling_interval_seconds")]
+    pub polling_interval_seconds: u32,
+    #[serde(default = "default_cooldown_period_seconds")]
+    pub cooldown_period_seconds: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Scaler {
+    /// Any KEDA queue scaler (i.e: aws-sqs-queue, rabbitmq, kafka, redis, gcp-pubsub, azure-servicebus)
+    /// `authentication` maps a KEDA trigger authentication parameter to the name of one of the service environment variables
+    Queue {
+        scaler_type: String,
+        metadata: BTreeMap<String, String>,
+        #[serde(default)]
+        authentication: BTreeMap<String, String>,
+    },
+    Prometheus {
+        server_address: Option<String>,
+        query: String,
+        threshold: String,
+        activation_threshold: Option<String>,
+    },
+    Cron {
+        timezone: String,
+        start: String,
+        end: String,
+        desired_replicas: u32,
+    },
+    HttpRequestRate {
+        requests_per_second: u32,
+    },
+}
+
+impl Scaler {
+    pub fn to_domain(&self) -> models::Scaler {
+        match self {
+            Scaler::Queue {
+                scaler_type,
+                metadata,
+                authentication,
+            } => models::Scaler::Queue {
+                scaler_type: scaler_type.clone(),
+                metadata: metadata.clone(),
+                authentication: authentication.clone(),
+            },
+            Scaler::Prometheus {
+                server_address,
+                query,
+                threshold,
+                activation_threshold,
+            } => models::Scaler::Prometheus {
+                server_address: server_address.clone(),
+                query: query.clone(),
+                threshold: threshold.clone(),
+                activation_threshold: activation_threshold.clone(),
+            },
+            Scaler::Cron {
+                timezone,
+                start,
+                end,
+                desired_replicas,
+            } => models::Scaler::Cron {
+                timezone: timezone.clone(),
+                start: start.clone(),
+                end: end.clone(),
+                desired_replicas: *desired_replicas,
+            },
+            Scaler::HttpRequestRate { requests_per_second } => models::Scaler::HttpRequestRate {
+                requests_per_second: *requests_per_second,
+            },
+        }
+    }
+}
+
+impl EventDrivenAutoscaling {
+    pub fn to_domain(&self) -> models::EventDrivenAutoscaling {
+        models::EventDrivenAutoscaling {
+            scalers: self.scalers.iter().map(|s| s.to_domain()).collect(),
+            polling_interval_seconds: self.polling_interval_seconds,
+            cooldown_period_seconds: self.cooldown_period_seconds,
+        }
+    }
+}
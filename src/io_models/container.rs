@@ -15,8 +15,10 @@ use crate::infrastructure::models::container_registry::ecr::ECR;
 use crate::infrastructure::models::container_registry::errors::ContainerRegistryError;
 use crate::infrastructure::models::container_registry::{InteractWithRegistry, azure_container_registry};
 use crate::infrastructure::models::kubernetes::Kubernetes;
+use crate::io_models::additional_container::AdditionalContainer;
 use crate::io_models::annotations_group::AnnotationsGroup;
 use crate::io_models::application::{Port, Storage, to_environment_variable};
+use crate::io_models::autoscaling::EventDrivenAutoscaling;
 use crate::io_models::context::Context;
 use crate::io_models::labels_group::LabelsGroup;
 use crate::io_models::models::{KubernetesCpuResourceUnit, KubernetesMemoryResourceUnit};
@@ -246,6 +248,48 @@ impl Registry {
     }
 }
 
+// Pre-built image (docker-archive or oci layout tarball) produced outside of Qovery.
+// When set, the image is loaded from it instead of being pulled from the registry
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageArtifact {
+    // Object in the cluster object storage
+    ObjectStorage {
+        bucket_name: String,
+        object_key: String,
+        sha256_checksum: String,
+    },
+    Http {
+        url: Url,
+        sha256_checksum: String,
+    },
+}
+
+impl ImageArtifact {
+    // Location of the artifact safe to be displayed to the user (url query may contain credentials)
+    pub fn location(&self) -> String {
+        match self {
+            ImageArtifact::ObjectStorage {
+                bucket_name,
+                object_key,
+                ..
+            } => format!("{bucket_name}/{object_key}"),
+            ImageArtifact::Http { url, .. } => {
+                format!("{}://{}{}", url.scheme(), url.host_str().unwrap_or_default(), url.path())
+            }
+        }
+    }
+
+    // Expected sha256 of the artifact content, lowercase hex without the optional `sha256:` prefix
+    pub fn sha256_checksum(&self) -> String {
+        match self {
+            ImageArtifact::ObjectStorage { sha256_checksum, .. } | ImageArtifact::Http { sha256_checksum, .. } => {
+                sha256_checksum.trim_start_matches("sha256:").to_ascii_lowercase()
+            }
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
 #[serde(default)]
 pub struct ContainerAdvancedSettings {
@@ -268,6 +312,9 @@ pub struct ContainerAdvancedSettings {
     pub deployment_update_strategy_rolling_update_max_surge_percent: u32,
     #[serde(alias = "deployment.affinity.node.required")]
     pub deployment_affinity_node_required: BTreeMap<String, String>,
+    #[serde(default)]
+    #[serde(alias = "deployment.node_pool")]
+    pub deployment_node_pool: Option<String>,
     #[serde(alias = "deployment.antiaffinity.pod")]
     pub deployment_antiaffinity_pod: PodAntiAffinity,
     #[serde(alias = "deployment.lifecycle.post_start_exec_command")]
@@ -356,6 +403,7 @@ impl Default for ContainerAdvancedSettings {
             deployment_update_strategy_rolling_update_max_unavailable_percent: 25,
             deployment_update_strategy_rolling_update_max_surge_percent: 25,
             deployment_affinity_node_required: BTreeMap::new(),
+            deployment_node_pool: None,
             deployment_antiaffinity_pod: PodAntiAffinity::Preferred,
             deployment_lifecycle_post_start_exec_command: vec![],
             deployment_lifecycle_pre_stop_exec_command: vec![],
@@ -404,6 +452,8 @@ pub struct Container {
     pub registry: Registry,
     pub image: String,
     pub tag: String,
+    #[serde(default)]
+    pub image_artifact: Option<ImageArtifact>,
     pub command_args: Vec<String>,
     pub entrypoint: Option<String>,
     pub cpu_request_in_milli: u32,
@@ -426,6 +476,11 @@ pub struct Container {
     #[serde(default)]
     pub advanced_settings: ContainerAdvancedSettings,
     #[serde(default)]
+    pub event_driven_autoscaling: Option<EventDrivenAutoscaling>,
+    /// Init and sidecar containers running in the same pods as the container
+    #[serde(default)]
+    pub additional_containers: Vec<AdditionalContainer>,
+    #[serde(default)]
     pub annotations_group_ids: BTreeSet<Uuid>,
     #[serde(default)]
     pub labels_group_ids: BTreeSet<Uuid>,
@@ -450,10 +505,16 @@ impl Container {
                 .set_url(default_container_registry.get_registry_endpoint(Some(cluster.cluster_name().as_str())));
         }
 
+        let additional_containers = self
+            .additional_containers
+            .into_iter()
+            .map(|c| c.to_domain(default_container_registry, cluster))
+            .collect_vec();
         let image_source = RegistryImageSource {
             registry: self.registry,
             image: self.image,
             tag: self.tag,
+            artifact: self.image_artifact,
             registry_mirroring_mode: cluster.advanced_settings().registry_mirroring_mode.clone(),
         };
         let annotations_groups = self
@@ -496,6 +557,8 @@ impl Container {
                 self.readiness_probe.map(|p| p.to_domain()),
                 self.liveness_probe.map(|p| p.to_domain()),
                 self.advanced_settings,
+                self.event_driven_autoscaling.map(|a| a.to_domain()),
+                additional_containers,
                 AwsAppExtraSettings {},
                 |transmitter| context.get_event_details(transmitter),
                 annotations_groups,
@@ -527,6 +590,8 @@ impl Container {
                 self.readiness_probe.map(|p| p.to_domain()),
                 self.liveness_probe.map(|p| p.to_domain()),
                 self.advanced_settings,
+                self.event_driven_autoscaling.map(|a| a.to_domain()),
+                additional_containers,
                 AzureAppExtraSettings {},
                 |transmitter| context.get_event_details(transmitter),
                 annotations_groups,
@@ -558,6 +623,8 @@ impl Container {
                 self.readiness_probe.map(|p| p.to_domain()),
                 self.liveness_probe.map(|p| p.to_domain()),
                 self.advanced_settings,
+                self.event_driven_autoscaling.map(|a| a.to_domain()),
+                additional_containers,
                 ScwAppExtraSettings {},
                 |transmitter| context.get_event_details(transmitter),
                 annotations_groups,
@@ -589,6 +656,8 @@ impl Container {
                 self.readiness_probe.map(|p| p.to_domain()),
                 self.liveness_probe.map(|p| p.to_domain()),
                 self.advanced_settings,
+                self.event_driven_autoscaling.map(|a| a.to_domain()),
+                additional_containers,
                 GcpAppExtraSettings {},
                 |transmitter| context.get_event_details(transmitter),
                 annotations_groups,
@@ -620,6 +689,8 @@ impl Container {
                 self.readiness_probe.map(|p| p.to_domain()),
                 self.liveness_probe.map(|p| p.to_domain()),
                 self.advanced_settings,
+                self.event_driven_autoscaling.map(|a| a.to_domain()),
+                additional_containers,
                 OnPremiseAppExtraSettings {},
                 |transmitter| context.get_event_details(transmitter),
                 annotations_groups,
@@ -2,23 +2,29 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Deserializer, Serialize, de};
 use serde_json::Value;
 
+use crate::cmd::docker::BuildCacheObjectStorage;
 use crate::environment::models::azure::Credentials;
 use crate::environment::models::domain::Domain;
+use crate::environment::models::environment::DnsRouting;
 use crate::environment::models::gcp::JsonCredentials;
 use crate::environment::models::gcp::io::JsonCredentials as JsonCredentialsIo;
 use crate::environment::models::scaleway::{ScwRegion, ScwZone};
+use crate::environment::report::git_status_notifier::GitStatusNotifications;
 use crate::errors::{CommandError, EngineError as IoEngineError, EngineError};
 use crate::events::{EventDetails, InfrastructureStep, Stage, Transmitter};
 use crate::fs::workspace_directory;
 use crate::infrastructure::infrastructure_context::InfrastructureContext;
 use crate::infrastructure::models::build_platform::local_docker::LocalDocker;
+use crate::infrastructure::models::build_platform::{BuildCacheBackendKind, BuildCacheConfig};
 use crate::infrastructure::models::cloud_provider::aws::regions::AwsRegion;
 use crate::infrastructure::models::cloud_provider::aws::{AWS, AwsCredentials};
 use crate::infrastructure::models::cloud_provider::azure::Azure;
 use crate::infrastructure::models::cloud_provider::azure::locations::{AzureLocation, AzureZone};
 use crate::infrastructure::models::cloud_provider::gcp::Google;
 use crate::infrastructure::models::cloud_provider::gcp::locations::GcpRegion;
-use crate::infrastructure::models::cloud_provider::io::{ClusterAdvancedSettings, CustomerHelmChartsOverrideEncoded};
+use crate::infrastructure::models::cloud_provider::io::{
+    ClusterAdvancedSettings, CustomerHelmChartsOverrideEncoded, InputError,
+};
 use crate::infrastructure::models::cloud_provider::scaleway::Scaleway;
 use crate::infrastructure::models::cloud_provider::self_managed::SelfManaged;
 use crate::infrastructure::models::container_registry::azure_container_registry::AzureContainerRegistry;
@@ -56,6 +62,7 @@ use governor::{Quota, RateLimiter};
 use nonzero_ext::nonzero;
 use rusoto_signature::Region;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
@@ -83,6 +90,25 @@ pub struct EngineRequest<T> {
     pub target_environment: T,
     pub metadata: Option<Metadata>,
     pub archive: Option<Archive>,
+    /// Other clusters the target environment must be deployed to as well, i.e: for active-active regions
+    #[serde(default)]
+    pub multi_cluster: Option<MultiClusterDeployment>,
+    /// Report the build and deployment progress of the services to the git provider of their commit
+    #[serde(default)]
+    pub git_status_notifications: Option<GitStatusNotifications>,
+    /// Restore a backup of the cluster instead of running the requested action
+    #[serde(default)]
+    pub cluster_backup_restore: Option<ClusterBackupRestore>,
+    /// Apply pending patch upgrades and node images updates if the cluster maintenance window is open,
+    /// instead of running the requested action
+    #[serde(default)]
+    pub cluster_maintenance: bool,
+    /// Pause or delete the expired ephemeral environments of the cluster, instead of running the requested action
+    #[serde(default)]
+    pub sweep_expired_environments: bool,
+    /// Compute the costs of the cluster environments and report them, instead of running the requested action
+    #[serde(default)]
+    pub environments_costs: Option<EnvironmentsCostsRequest>,
 }
 
 impl<T> EngineRequest<T> {
@@ -96,7 +122,13 @@ impl<T> EngineRequest<T> {
     ) -> Result<InfrastructureContext, Box<EngineError>> {
         let build_platform = self
             .build_platform
-            .to_engine_build_platform(context, metrics_registry.clone_dyn());
+            .to_engine_build_platform(context, metrics_registry.clone_dyn())
+            .map_err(|err| {
+                Box::new(IoEngineError::new_invalid_engine_payload_invalid_field_value(
+                    event_details.clone(),
+                    err,
+                ))
+            })?;
         let cloud_provider = self
             .cloud_provider
             .to_engine_cloud_provider(&self.kubernetes.region, self.kubernetes.kind)
@@ -205,10 +237,113 @@ impl<T> EngineRequest<T> {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MultiClusterDeploymentStrategy {
+    /// Clusters are deployed one after the other, the deployment halts at the first cluster failing
+    #[default]
+    Sequential,
+    /// All clusters are deployed at the same time
+    Parallel,
+}
+
+/// How DNS records of routers custom domains are resolved when the same hostname is served by several clusters
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DnsRoutingPolicy {
+    /// Traffic is split between clusters according to their weight (cluster long id -> weight).
+    /// Clusters absent from the map get a weight of 1
+    Weighted { weights: HashMap<Uuid, u32> },
+    /// Clients are routed to the cluster region with the lowest latency
+    Latency,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ClusterTarget {
+    pub cloud_provider: CloudProvider,
+    pub dns_provider: DnsProvider,
+    pub container_registry: ContainerRegistry,
+    pub kubernetes: KubernetesDto,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ClusterBackupRestore {
+    pub backup_name: String,
+    /// Cluster which made the backup, when restoring the backup of another cluster
+    #[serde(default)]
+    pub source_cluster_long_id: Option<Uuid>,
+    /// Region of the cluster which made the backup, required when restoring the backup of another cluster
+    #[serde(default)]
+    pub source_cluster_region: Option<String>,
+    /// Environments namespaces to restore, they must be listed explicitly
+    #[serde(default)]
+    pub namespaces: Vec<String>,
+    /// Restore namespaces under another name, i.e: to restore next to the existing ones
+    #[serde(default)]
+    pub namespace_mapping: HashMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct EnvironmentsCostsRequest {
+    /// Costs are computed over the last hours, up to now
+    pub window_in_hours: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MultiClusterDeployment {
+    pub additional_clusters: Vec<ClusterTarget>,
+    #[serde(default)]
+    pub strategy: MultiClusterDeploymentStrategy,
+    #[serde(default)]
+    pub dns_routing_policy: Option<DnsRoutingPolicy>,
+}
+
+impl DnsRoutingPolicy {
+    /// Checks the policy can be honoured on a cluster, records are only created with routing annotations understood
+    /// by Route53 and latency regions are AWS regions
+    pub fn check_cluster_support(
+        &self,
+        cloud_provider_kind: &cloud_provider::Kind,
+        dns_provider_kind: &dns_provider::Kind,
+    ) -> Result<(), String> {
+        if !dns_provider_kind.supports_routing_policies() {
+            return Err(format!(
+                "Weighted and latency based DNS routing require a Route53 DNS provider, {dns_provider_kind:?} is not supported"
+            ));
+        }
+
+        match self {
+            DnsRoutingPolicy::Weighted { .. } => Ok(()),
+            DnsRoutingPolicy::Latency if cloud_provider_kind == &cloud_provider::Kind::Aws => Ok(()),
+            DnsRoutingPolicy::Latency => Err(format!(
+                "Latency based DNS routing requires clusters running on AWS, {cloud_provider_kind} clusters are not supported"
+            )),
+        }
+    }
+
+    pub fn to_domain(&self, cluster_long_id: &Uuid, cluster_region: &str) -> DnsRouting {
+        match self {
+            DnsRoutingPolicy::Weighted { weights } => DnsRouting {
+                set_identifier: cluster_long_id.to_string(),
+                weight: Some(weights.get(cluster_long_id).copied().unwrap_or(1)),
+                region: None,
+            },
+            DnsRoutingPolicy::Latency => DnsRouting {
+                set_identifier: cluster_long_id.to_string(),
+                weight: None,
+                region: Some(cluster_region.to_string()),
+            },
+        }
+    }
+}
+
 impl InfrastructureEngineRequest {
     pub fn event_details(&self) -> EventDetails {
         let kubernetes = &self.kubernetes;
         let stage = match self.action {
+            _ if self.cluster_backup_restore.is_some() => Stage::Infrastructure(InfrastructureStep::Restore),
+            _ if self.cluster_maintenance => Stage::Infrastructure(InfrastructureStep::Maintenance),
+            _ if self.environments_costs.is_some() => Stage::Infrastructure(InfrastructureStep::EnvironmentsCosts),
             Action::Create => Stage::Infrastructure(InfrastructureStep::Create),
             Action::Pause => Stage::Infrastructure(InfrastructureStep::Pause),
             Action::Delete => Stage::Infrastructure(InfrastructureStep::Delete),
@@ -239,6 +374,25 @@ impl EnvironmentEngineRequest {
             Transmitter::Environment(self.target_environment.long_id, self.target_environment.name.clone()),
         )
     }
+
+    /// Same request, but targeting one of the additional clusters of a multi cluster deployment
+    pub fn for_cluster(&self, cluster: &ClusterTarget) -> EnvironmentEngineRequest {
+        EnvironmentEngineRequest {
+            cloud_provider: cluster.cloud_provider.clone(),
+            dns_provider: cluster.dns_provider.clone(),
+            container_registry: cluster.container_registry.clone(),
+            kubernetes: cluster.kubernetes.clone(),
+            multi_cluster: None,
+            ..self.clone()
+        }
+    }
+
+    pub fn additional_clusters(&self) -> &[ClusterTarget] {
+        self.multi_cluster
+            .as_ref()
+            .map(|multi_cluster| multi_cluster.additional_clusters.as_slice())
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -248,6 +402,120 @@ pub struct BuildPlatform {
     pub long_id: Uuid,
     pub name: String,
     pub options: CloudProviderOptions,
+    #[serde(default)]
+    pub build_cache: BuildCacheOptions,
+}
+
+/// Default cache backend of the builds, can be overridden per service with `build.cache_backend` advanced setting
+#[derive(Serialize, Deserialize, Clone, Derivative, Default)]
+#[derivative(Debug)]
+#[serde(default)]
+pub struct BuildCacheOptions {
+    pub backend: BuildCacheBackendKind,
+    pub bucket_name: Option<String>,
+    pub region: Option<String>,
+    // S3 compatible endpoint for on-premise storages, or account url of the Azure storage account
+    pub endpoint_url: Option<String>,
+    // HMAC keys, for storages not accepting the cloud provider credentials (GCS interoperability, on-premise, Azure)
+    pub access_key_id: Option<String>,
+    #[derivative(Debug = "ignore")]
+    pub secret_access_key: Option<String>,
+    pub local_directory: Option<PathBuf>,
+}
+
+impl BuildCacheOptions {
+    // No bucket means that no object storage is configured, but a bucket without the settings of its storage is an error
+    fn to_object_storage(&self, options: &CloudProviderOptions) -> Result<Option<BuildCacheObjectStorage>, InputError> {
+        let Some(bucket) = self.bucket_name.clone() else {
+            return Ok(None);
+        };
+        let required = |field_name: &str, value: &Option<String>| {
+            value.clone().ok_or_else(|| InputError::InvalidInputFieldValue {
+                field_name: format!("build_cache.{field_name}"),
+                message: "is required by the object storage build cache".to_string(),
+            })
+        };
+        let hmac_keys = || {
+            Ok::<_, InputError>((
+                required("access_key_id", &self.access_key_id)?,
+                required("secret_access_key", &self.secret_access_key)?,
+            ))
+        };
+
+        let storage = match options {
+            CloudProviderOptions::Aws {
+                access_key_id,
+                secret_access_key,
+                session_token,
+            } => BuildCacheObjectStorage::S3 {
+                bucket,
+                region: required("region", &self.region)?,
+                endpoint_url: self.endpoint_url.clone(),
+                access_key_id: access_key_id.clone(),
+                secret_access_key: secret_access_key.clone(),
+                session_token: session_token.clone(),
+            },
+            CloudProviderOptions::Scaleway {
+                scaleway_access_key,
+                scaleway_secret_key,
+                ..
+            } => {
+                let region = required("region", &self.region)?;
+                BuildCacheObjectStorage::S3 {
+                    bucket,
+                    endpoint_url: Some(
+                        self.endpoint_url
+                            .clone()
+                            .unwrap_or_else(|| format!("https://s3.{region}.scw.cloud")),
+                    ),
+                    region,
+                    access_key_id: scaleway_access_key.clone(),
+                    secret_access_key: scaleway_secret_key.clone(),
+                    session_token: None,
+                }
+            }
+            CloudProviderOptions::Gcp { .. } => {
+                let (access_key_id, secret_access_key) = hmac_keys()?;
+                BuildCacheObjectStorage::S3 {
+                    bucket,
+                    region: self.region.clone().unwrap_or_else(|| "auto".to_string()),
+                    endpoint_url: Some(
+                        self.endpoint_url
+                            .clone()
+                            .unwrap_or_else(|| "https://storage.googleapis.com".to_string()),
+                    ),
+                    access_key_id,
+                    secret_access_key,
+                    session_token: None,
+                }
+            }
+            CloudProviderOptions::Azure { .. } => {
+                if self.secret_access_key.is_some() {
+                    return Err(InputError::InvalidInputFieldValue {
+                        field_name: "build_cache.secret_access_key".to_string(),
+                        message: "is not supported for Azure, the identity of the builder is used".to_string(),
+                    });
+                }
+                BuildCacheObjectStorage::AzureBlob {
+                    account_url: required("endpoint_url", &self.endpoint_url)?,
+                    container: bucket,
+                }
+            }
+            CloudProviderOptions::OnPremise {} => {
+                let (access_key_id, secret_access_key) = hmac_keys()?;
+                BuildCacheObjectStorage::S3 {
+                    bucket,
+                    region: self.region.clone().unwrap_or_else(|| "us-east-1".to_string()),
+                    endpoint_url: Some(required("endpoint_url", &self.endpoint_url)?),
+                    access_key_id,
+                    secret_access_key,
+                    session_token: None,
+                }
+            }
+        };
+
+        Ok(Some(storage))
+    }
 }
 
 impl BuildPlatform {
@@ -255,13 +523,20 @@ impl BuildPlatform {
         &self,
         context: &Context,
         metrics_registry: Box<dyn MetricsRegistry>,
-    ) -> Box<dyn build_platform::BuildPlatform> {
-        Box::new(match self.kind {
+    ) -> Result<Box<dyn build_platform::BuildPlatform>, InputError> {
+        let build_cache = BuildCacheConfig {
+            backend: self.build_cache.backend,
+            object_storage: self.build_cache.to_object_storage(&self.options)?,
+            local_directory: self.build_cache.local_directory.clone(),
+        };
+
+        Ok(Box::new(match self.kind {
             build_platform::Kind::LocalDocker => {
                 // FIXME: Remove the unwrap by propagating errors above
-                LocalDocker::new(context.clone(), self.long_id, self.name.as_str(), metrics_registry).unwrap()
+                LocalDocker::new(context.clone(), self.long_id, self.name.as_str(), metrics_registry, build_cache)
+                    .unwrap()
             }
-        })
+        }))
     }
 }
 
@@ -631,6 +906,7 @@ impl KubernetesDto {
                     self.region.to_string(),
                     KubernetesVersion::from_str(&self.version)
                         .unwrap_or_else(|_| panic!("Kubernetes version `{}` is not supported", &self.version)),
+                    self.created_at,
                     serde_json::from_value::<kubernetes::self_managed::on_premise::SelfManagedOptions>(
                         self.options.clone(),
                     )
@@ -1038,3 +1314,248 @@ impl From<GithubCrRepoType> for RegistryType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn cluster_json(cluster_long_id: Uuid, region: &str) -> (Value, Value, Value, Value) {
+        let cloud_provider = json!({
+            "kind": "AWS",
+            "id": "aws",
+            "long_id": Uuid::new_v4(),
+            "name": "aws",
+            "zones": [format!("{region}a")],
+            "options": { "access_key_id": "key", "secret_access_key": "secret" },
+            "terraform_state_credentials": {
+                "access_key_id": "key",
+                "secret_access_key": "secret",
+                "region": region,
+                "s3_bucket": "bucket",
+                "dynamodb_table": "table",
+            },
+        });
+        let dns_provider = json!({
+            "kind": "QOVERY_DNS",
+            "long_id": Uuid::new_v4(),
+            "name": "qovery-dns",
+            "domain": format!("{region}.example.com"),
+            "options": {},
+        });
+        let container_registry = json!({
+            "kind": "ECR",
+            "long_id": Uuid::new_v4(),
+            "name": "ecr",
+            "options": { "access_key_id": "key", "secret_access_key": "secret", "region": region },
+        });
+        let kubernetes = json!({
+            "kind": "EKS",
+            "long_id": cluster_long_id,
+            "name": format!("cluster-{region}"),
+            "version": "1.31",
+            "region": region,
+            "options": {},
+            "created_at": "2025-01-01T00:00:00Z",
+            "nodes_groups": [],
+            "advanced_settings": {},
+            "customer_helm_charts_override": null,
+            "kubeconfig": null,
+            "qovery_allowed_public_access_cidrs": null,
+        });
+
+        (cloud_provider, dns_provider, container_registry, kubernetes)
+    }
+
+    fn environment_engine_request(
+        main_cluster_long_id: Uuid,
+        additional_cluster_long_id: Uuid,
+    ) -> EnvironmentEngineRequest {
+        let (cloud_provider, dns_provider, container_registry, kubernetes) =
+            cluster_json(main_cluster_long_id, "eu-west-3");
+        let (additional_cloud_provider, additional_dns_provider, additional_container_registry, additional_kubernetes) =
+            cluster_json(additional_cluster_long_id, "us-east-1");
+        let organization_long_id = Uuid::new_v4();
+
+        serde_json::from_value(json!({
+            "id": "request-id",
+            "organization_id": "organization-id",
+            "organization_long_id": organization_long_id,
+            "deployment_jwt_token": "token",
+            "created_at": "2025-01-01T00:00:00Z",
+            "action": "CREATE",
+            "features": [],
+            "test_cluster": false,
+            "build_platform": {
+                "kind": "LOCAL_DOCKER",
+                "id": "local-docker",
+                "long_id": Uuid::new_v4(),
+                "name": "local-docker",
+                "options": { "access_key_id": "key", "secret_access_key": "secret" },
+            },
+            "cloud_provider": cloud_provider,
+            "dns_provider": dns_provider,
+            "container_registry": container_registry,
+            "kubernetes": kubernetes,
+            "target_environment": {
+                "execution_id": "execution-id",
+                "long_id": Uuid::new_v4(),
+                "name": "environment",
+                "kube_name": "environment",
+                "project_long_id": Uuid::new_v4(),
+                "organization_long_id": organization_long_id,
+                "action": "CREATE",
+                "applications": [],
+                "containers": [],
+                "jobs": [],
+                "routers": [],
+                "databases": [],
+            },
+            "metadata": null,
+            "archive": null,
+            "multi_cluster": {
+                "additional_clusters": [{
+                    "cloud_provider": additional_cloud_provider,
+                    "dns_provider": additional_dns_provider,
+                    "container_registry": additional_container_registry,
+                    "kubernetes": additional_kubernetes,
+                }],
+                "strategy": "PARALLEL",
+                "dns_routing_policy": { "type": "LATENCY" },
+            },
+        }))
+        .expect("valid environment engine request")
+    }
+
+    #[test]
+    fn test_build_cache_options_to_object_storage() {
+        // setup:
+        let aws = CloudProviderOptions::Aws {
+            access_key_id: "key".to_string(),
+            secret_access_key: "secret".to_string(),
+            session_token: None,
+        };
+        let azure = CloudProviderOptions::Azure {
+            client_id: "client".to_string(),
+            client_secret: "secret".to_string(),
+            tenant_id: "tenant".to_string(),
+            subscription_id: "subscription".to_string(),
+        };
+        let options = BuildCacheOptions {
+            backend: BuildCacheBackendKind::ObjectStorage,
+            bucket_name: Some("build-cache".to_string()),
+            region: Some("eu-west-3".to_string()),
+            ..Default::default()
+        };
+
+        // execute & verify:
+        assert!(matches!(
+            options.to_object_storage(&aws),
+            Ok(Some(BuildCacheObjectStorage::S3 { region, .. })) if region == "eu-west-3"
+        ));
+        assert!(matches!(BuildCacheOptions::default().to_object_storage(&aws), Ok(None)));
+        assert!(
+            BuildCacheOptions {
+                region: None,
+                ..options.clone()
+            }
+            .to_object_storage(&aws)
+            .is_err(),
+            "a bucket without region must be rejected"
+        );
+        assert!(
+            options.to_object_storage(&CloudProviderOptions::OnPremise {}).is_err(),
+            "on-premise storages require hmac keys and an endpoint"
+        );
+        assert!(options.to_object_storage(&azure).is_err(), "azure requires the account url");
+        assert!(
+            BuildCacheOptions {
+                endpoint_url: Some("https://account.blob.core.windows.net".to_string()),
+                secret_access_key: Some("secret".to_string()),
+                ..options.clone()
+            }
+            .to_object_storage(&azure)
+            .is_err(),
+            "azure account keys can't be passed to buildkit without being exposed"
+        );
+    }
+
+    #[test]
+    fn test_dns_routing_policy_to_domain() {
+        let weighted_cluster_id = Uuid::new_v4();
+        let other_cluster_id = Uuid::new_v4();
+        let weighted = DnsRoutingPolicy::Weighted {
+            weights: HashMap::from([(weighted_cluster_id, 3)]),
+        };
+
+        assert_eq!(
+            weighted.to_domain(&weighted_cluster_id, "eu-west-3"),
+            DnsRouting {
+                set_identifier: weighted_cluster_id.to_string(),
+                weight: Some(3),
+                region: None,
+            }
+        );
+        // Clusters without weight get the default one
+        assert_eq!(weighted.to_domain(&other_cluster_id, "eu-west-3").weight, Some(1));
+
+        assert_eq!(
+            DnsRoutingPolicy::Latency.to_domain(&other_cluster_id, "us-east-1"),
+            DnsRouting {
+                set_identifier: other_cluster_id.to_string(),
+                weight: None,
+                region: Some("us-east-1".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_dns_routing_policy_requires_route53() {
+        let weighted = DnsRoutingPolicy::Weighted {
+            weights: HashMap::new(),
+        };
+
+        for dns_provider_kind in [dns_provider::Kind::Cloudflare, dns_provider::Kind::QoveryDns] {
+            assert!(
+                weighted
+                    .check_cluster_support(&cloud_provider::Kind::Aws, &dns_provider_kind)
+                    .is_err()
+            );
+            assert!(
+                DnsRoutingPolicy::Latency
+                    .check_cluster_support(&cloud_provider::Kind::Aws, &dns_provider_kind)
+                    .is_err()
+            );
+        }
+    }
+
+    #[test]
+    fn test_environment_engine_request_for_cluster() {
+        let main_cluster_long_id = Uuid::new_v4();
+        let additional_cluster_long_id = Uuid::new_v4();
+        let request = environment_engine_request(main_cluster_long_id, additional_cluster_long_id);
+
+        assert_eq!(request.additional_clusters().len(), 1);
+        let cluster_request = request.for_cluster(&request.additional_clusters()[0]);
+
+        // Cluster specific fields come from the additional cluster
+        assert_eq!(cluster_request.kubernetes.long_id, additional_cluster_long_id);
+        assert_eq!(cluster_request.kubernetes.region, "us-east-1");
+        assert_eq!(cluster_request.dns_provider.domain, "us-east-1.example.com");
+        assert_eq!(cluster_request.cloud_provider.zones, vec!["us-east-1a".to_string()]);
+        assert!(matches!(
+            &cluster_request.container_registry,
+            ContainerRegistry::Ecr { options, .. } if options.region == "us-east-1"
+        ));
+
+        // The cluster request must not fan out again
+        assert!(cluster_request.multi_cluster.is_none());
+        assert!(cluster_request.additional_clusters().is_empty());
+
+        // Everything else is shared with the original request
+        assert_eq!(cluster_request.id, request.id);
+        assert_eq!(cluster_request.organization_long_id, request.organization_long_id);
+        assert_eq!(cluster_request.target_environment.long_id, request.target_environment.long_id);
+        assert_eq!(request.kubernetes.long_id, main_cluster_long_id);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::environment::models;
 use crate::environment::models::application::{ApplicationError, ApplicationService};
 use crate::environment::models::container::{ContainerError, ContainerService};
 use crate::environment::models::database::{DatabaseError, DatabaseService};
@@ -52,6 +53,66 @@ pub struct EnvironmentRequest {
     pub annotations_groups: BTreeMap<Uuid, AnnotationsGroup>,
     #[serde(default = "default_labels_groups")]
     pub labels_groups: BTreeMap<Uuid, LabelsGroup>,
+    #[serde(default)]
+    pub lifecycle: Option<EnvironmentLifecycle>,
+    #[serde(default)]
+    pub admission_policies: Option<AdmissionPolicies>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ExpirationAction {
+    Pause,
+    Delete,
+}
+
+/// Lifecycle of an ephemeral environment (i.e: preview environment).
+/// Once expired, the environment is paused or deleted by the `EnvironmentSweeperTask`
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct EnvironmentLifecycle {
+    /// Time to live of the environment, starting from its last deployment
+    pub ttl_in_seconds: u64,
+    pub action_on_expiration: ExpirationAction,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AdmissionPolicyMode {
+    Warn,
+    Enforce,
+}
+
+/// Organization policies every manifest of the environment is evaluated against before being applied
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct AdmissionPolicies {
+    pub mode: AdmissionPolicyMode,
+    pub policies: Vec<AdmissionPolicy>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct AdmissionPolicy {
+    pub name: String,
+    /// Rego module, its `deny` and `violation` rules are violations, its `warn` rules are only reported
+    pub rego: String,
+}
+
+impl AdmissionPolicies {
+    pub fn to_domain(&self) -> models::environment::AdmissionPolicies {
+        models::environment::AdmissionPolicies {
+            mode: match self.mode {
+                AdmissionPolicyMode::Warn => models::environment::AdmissionPolicyMode::Warn,
+                AdmissionPolicyMode::Enforce => models::environment::AdmissionPolicyMode::Enforce,
+            },
+            policies: self
+                .policies
+                .iter()
+                .map(|policy| models::environment::AdmissionPolicy {
+                    name: policy.name.clone(),
+                    rego: policy.rego.clone(),
+                })
+                .collect(),
+        }
+    }
 }
 
 fn default_max_parallel_build() -> u32 {
@@ -89,6 +150,21 @@ pub enum DomainError {
 }
 
 impl EnvironmentRequest {
+    /// Same environment, with the action applied to the environment and all its services
+    pub fn with_action(mut self, action: Action) -> EnvironmentRequest {
+        self.action = action.clone();
+        self.applications.iter_mut().for_each(|x| x.action = action.clone());
+        self.containers.iter_mut().for_each(|x| x.action = action.clone());
+        self.jobs.iter_mut().for_each(|x| x.action = action.clone());
+        self.routers.iter_mut().for_each(|x| x.action = action.clone());
+        self.databases.iter_mut().for_each(|x| x.action = action.clone());
+        self.helms.iter_mut().for_each(|x| x.action = action.clone());
+        self.terraform_services
+            .iter_mut()
+            .for_each(|x| x.action = action.clone());
+        self
+    }
+
     pub fn to_environment_domain(
         &self,
         context: &Context,
@@ -107,7 +183,15 @@ impl EnvironmentRequest {
                     cluster.cpu_architectures(),
                     &QoveryIdentifier::new(*cluster.long_id()),
                 );
-                srv.to_application_domain(context, build, cloud_provider, &self.annotations_groups, &self.labels_groups)
+                srv.to_application_domain(
+                    context,
+                    build,
+                    cloud_provider,
+                    container_registry,
+                    cluster,
+                    &self.annotations_groups,
+                    &self.labels_groups,
+                )
             })
             .collect();
         let applications = applications?;
@@ -369,7 +453,7 @@ impl EnvironmentRequest {
             .collect();
         let terraform_services = terraform_services?;
 
-        Ok(Environment::new(
+        let mut environment = Environment::new(
             self.long_id,
             self.name.clone(),
             self.kube_name.clone(),
@@ -386,6 +470,13 @@ impl EnvironmentRequest {
             jobs,
             helm_charts,
             terraform_services,
-        ))
+        );
+        environment.admission_policies = self
+            .admission_policies
+            .as_ref()
+            .filter(|admission_policies| !admission_policies.policies.is_empty())
+            .map(|admission_policies| admission_policies.to_domain());
+
+        Ok(environment)
     }
 }
@@ -1,3 +1,4 @@
+use crate::cmd::docker::BuildProvenance;
 use crate::engine_task::qovery_api::QoveryApi;
 use crate::environment::models;
 use crate::environment::models::aws::AwsAppExtraSettings;
@@ -8,7 +9,7 @@ use crate::environment::models::registry_image_source::RegistryImageSource;
 use crate::environment::models::scaleway::ScwAppExtraSettings;
 use crate::environment::models::selfmanaged::OnPremiseAppExtraSettings;
 use crate::environment::models::types::{AWS, Azure, GCP, OnPremise, SCW};
-use crate::infrastructure::models::build_platform::{Build, GitRepository, Image, SshKey};
+use crate::infrastructure::models::build_platform::{Build, BuildCacheBackendKind, GitRepository, Image, SshKey};
 use crate::infrastructure::models::cloud_provider::service::ServiceType;
 use crate::infrastructure::models::cloud_provider::{CloudProvider, Kind};
 use crate::infrastructure::models::container_registry::{
@@ -17,19 +18,18 @@ use crate::infrastructure::models::container_registry::{
 use crate::infrastructure::models::kubernetes::Kubernetes;
 use crate::io_models::annotations_group::AnnotationsGroup;
 use crate::io_models::application::{GitCredentials, to_environment_variable};
-use crate::io_models::container::Registry;
+use crate::io_models::autoscaling::Scaler;
+use crate::io_models::container::{ImageArtifact, Registry};
 use crate::io_models::context::Context;
 use crate::io_models::labels_group::LabelsGroup;
 use crate::io_models::models::{CpuArchitecture, KubernetesCpuResourceUnit, KubernetesMemoryResourceUnit};
 use crate::io_models::probe::Probe;
 use crate::io_models::variable_utils::{VariableInfo, default_environment_vars_with_info};
 use crate::io_models::{
-    Action, MountedFile, QoveryIdentifier, fetch_git_token, normalize_root_and_dockerfile_path, sanitized_git_url,
-    ssh_keys_from_env_vars,
+    Action, MountedFile, QoveryIdentifier, build_env_vars_from_env_vars, fetch_git_token, monorepo_watched_paths,
+    normalize_root_and_dockerfile_path, sanitized_git_url, ssh_keys_from_env_vars,
 };
 use crate::utilities::to_short_id;
-use base64::Engine;
-use base64::engine::general_purpose;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet};
@@ -44,7 +44,19 @@ pub struct JobAdvancedSettings {
     // Job specific
     #[serde(alias = "job.delete_ttl_seconds_after_finished")]
     pub job_delete_ttl_seconds_after_finished: Option<u32>,
+    #[serde(alias = "job.completion_mode")]
+    pub job_completion_mode: JobCompletionMode,
+    #[serde(alias = "job.backoff_limit_per_index")]
+    pub job_backoff_limit_per_index: Option<u32>,
+    #[serde(alias = "job.max_failed_indexes")]
+    pub job_max_failed_indexes: Option<u32>,
+    #[serde(alias = "job.pod_failure_policy.ignore_exit_codes")]
+    pub job_pod_failure_policy_ignore_exit_codes: Vec<i32>,
+    #[serde(alias = "job.pod_failure_policy.fail_job_exit_codes")]
+    pub job_pod_failure_policy_fail_job_exit_codes: Vec<i32>,
 
+    #[serde(alias = "cronjob.suspend")]
+    pub cronjob_suspend: bool,
     #[serde(alias = "cronjob.concurrency_policy")]
     pub cronjob_concurrency_policy: String,
     #[serde(alias = "cronjob.failed_jobs_history_limit")]
@@ -57,6 +69,9 @@ pub struct JobAdvancedSettings {
     pub deployment_termination_grace_period_seconds: u32,
     #[serde(alias = "deployment.affinity.node.required")]
     pub deployment_affinity_node_required: BTreeMap<String, String>,
+    #[serde(default)]
+    #[serde(alias = "deployment.node_pool")]
+    pub deployment_node_pool: Option<String>,
 
     // Build
     #[serde(alias = "build.timeout_max_sec")]
@@ -68,6 +83,18 @@ pub struct JobAdvancedSettings {
     #[serde(default)]
     #[serde(alias = "build.ephemeral_storage_in_gib")]
     pub build_ephemeral_storage_in_gib: Option<u32>,
+    #[serde(default)]
+    #[serde(alias = "build.monorepo_mode.enabled")]
+    pub build_monorepo_mode_enabled: bool,
+    #[serde(default)]
+    #[serde(alias = "build.monorepo_mode.watched_paths")]
+    pub build_monorepo_mode_watched_paths: Vec<String>,
+    #[serde(default)]
+    #[serde(alias = "build.cache_backend")]
+    pub build_cache_backend: Option<BuildCacheBackendKind>,
+    #[serde(default)]
+    #[serde(alias = "build.provenance")]
+    pub build_provenance: BuildProvenance,
 
     #[serde(alias = "security.service_account_name")]
     pub security_service_account_name: String,
@@ -81,8 +108,15 @@ impl Default for JobAdvancedSettings {
     fn default() -> Self {
         Self {
             job_delete_ttl_seconds_after_finished: None,
+            job_completion_mode: JobCompletionMode::NonIndexed,
+            job_backoff_limit_per_index: None,
+            job_max_failed_indexes: None,
+            job_pod_failure_policy_ignore_exit_codes: vec![],
+            job_pod_failure_policy_fail_job_exit_codes: vec![],
+            cronjob_suspend: false,
             deployment_termination_grace_period_seconds: 60,
             deployment_affinity_node_required: BTreeMap::new(),
+            deployment_node_pool: None,
             cronjob_concurrency_policy: "Forbid".to_string(),
             cronjob_failed_jobs_history_limit: 1,
             cronjob_success_jobs_history_limit: 1,
@@ -90,6 +124,10 @@ impl Default for JobAdvancedSettings {
             build_cpu_max_in_milli: 4000,
             build_ram_max_in_gib: 8,
             build_ephemeral_storage_in_gib: None,
+            build_monorepo_mode_enabled: false,
+            build_monorepo_mode_watched_paths: vec![],
+            build_cache_backend: None,
+            build_provenance: BuildProvenance::default(),
             security_service_account_name: "".to_string(),
             security_read_only_root_filesystem: false,
             security_automount_service_account_token: false,
@@ -97,6 +135,16 @@ impl Default for JobAdvancedSettings {
     }
 }
 
+/// .spec.completionMode, variants are named after the kubernetes ones
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum JobCompletionMode {
+    /// The job is complete once `completions` pods succeeded, pods are interchangeable
+    NonIndexed,
+    /// Each pod gets its own index in JOB_COMPLETION_INDEX, from 0 to `completions - 1`,
+    /// and the job is complete once there is one succeeded pod per index
+    Indexed,
+}
+
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum LifecycleType {
     TERRAFORM,
@@ -108,10 +156,25 @@ pub enum LifecycleType {
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum JobSchedule {
-    OnStart { lifecycle_type: LifecycleType },
-    OnPause { lifecycle_type: LifecycleType },
-    OnDelete { lifecycle_type: LifecycleType },
-    Cron { schedule: String, timezone: String },
+    OnStart {
+        lifecycle_type: LifecycleType,
+    },
+    OnPause {
+        lifecycle_type: LifecycleType,
+    },
+    OnDelete {
+        lifecycle_type: LifecycleType,
+    },
+    Cron {
+        schedule: String,
+        timezone: String,
+    },
+    /// Jobs spawned by KEDA (ScaledJob) according to the scalers, i.e: one job per message in a queue
+    Event {
+        scalers: Vec<Scaler>,
+        max_parallel_jobs: u32,
+        polling_interval_seconds: u32,
+    },
 }
 
 impl JobSchedule {
@@ -119,8 +182,12 @@ impl JobSchedule {
         matches!(self, JobSchedule::Cron { .. })
     }
 
+    pub fn is_scaled_job(&self) -> bool {
+        matches!(self, JobSchedule::Event { .. })
+    }
+
     pub fn is_job(&self) -> bool {
-        !self.is_cronjob()
+        !self.is_cronjob() && !self.is_scaled_job()
     }
 
     pub fn lifecycle_type(&self) -> Option<LifecycleType> {
@@ -128,7 +195,7 @@ impl JobSchedule {
             JobSchedule::OnStart { lifecycle_type } => Some(*lifecycle_type),
             JobSchedule::OnPause { lifecycle_type } => Some(*lifecycle_type),
             JobSchedule::OnDelete { lifecycle_type } => Some(*lifecycle_type),
-            JobSchedule::Cron { .. } => None,
+            JobSchedule::Cron { .. } | JobSchedule::Event { .. } => None,
         }
     }
 }
@@ -140,6 +207,8 @@ pub enum JobSource {
         registry: Registry,
         image: String,
         tag: String,
+        #[serde(default)]
+        artifact: Option<ImageArtifact>,
     },
     Docker {
         git_url: String,
@@ -165,6 +234,12 @@ pub struct Job {
     // .spec.backoffLimit
     pub max_duration_in_sec: u64,
     // .spec.activeDeadlineSeconds
+    #[serde(default = "default_parallelism")]
+    pub parallelism: u32,
+    // .spec.parallelism
+    #[serde(default = "default_completions")]
+    pub completions: u32,
+    // .spec.completions
     pub default_port: Option<u16>,
     // for probes
     pub command_args: Vec<String>,
@@ -197,6 +272,14 @@ pub struct Job {
     pub output_variable_validation_pattern: String,
 }
 
+fn default_parallelism() -> u32 {
+    1u32
+}
+
+fn default_completions() -> u32 {
+    1u32
+}
+
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
 pub struct ContainerRegistries {
     pub registries: Vec<Registry>,
@@ -270,7 +353,14 @@ impl Job {
             Url::parse("https://invalid-git-url.com").expect("Error while trying to parse invalid git url")
         });
 
-        let mut disable_build_cache = false;
+        let build_env_vars = build_env_vars_from_env_vars(&self.environment_vars_with_infos);
+        let watched_paths = self.advanced_settings.build_monorepo_mode_enabled.then(|| {
+            monorepo_watched_paths(
+                &root_path,
+                &dockerfile_path,
+                &self.advanced_settings.build_monorepo_mode_watched_paths,
+            )
+        });
         let mut build = Build {
             git_repository: GitRepository {
                 url,
@@ -287,34 +377,22 @@ impl Job {
                 root_path,
                 extra_files_to_inject: vec![],
                 docker_target_build_stage: docker_target_build_stage.clone(),
+                watched_paths,
+                paths_content_hash: None,
+                commit_timestamp: None,
             },
             image: self.to_image(commit_id.to_string(), registry_url, cluster_id, git_url),
-            environment_variables: self
-                .environment_vars_with_infos
-                .iter()
-                .filter_map(|(k, variable_infos)| {
-                    // Remove special vars
-                    let v = String::from_utf8(
-                        general_purpose::STANDARD
-                            .decode(variable_infos.value.as_bytes())
-                            .unwrap_or_default(),
-                    )
-                    .unwrap_or_default();
-                    if k == "QOVERY_DISABLE_BUILD_CACHE" && v.to_lowercase() == "true" {
-                        disable_build_cache = true;
-                        return None;
-                    }
-
-                    Some((k.clone(), v))
-                })
-                .collect::<BTreeMap<_, _>>(),
-            disable_cache: disable_build_cache,
+            environment_variables: build_env_vars.environment_variables,
+            secret_environment_variables: build_env_vars.secret_environment_variables,
+            disable_cache: build_env_vars.disable_build_cache,
             timeout: Duration::from_secs(self.advanced_settings.build_timeout_max_sec as u64),
             architectures,
             max_cpu_in_milli: self.advanced_settings.build_cpu_max_in_milli,
             max_ram_in_gib: self.advanced_settings.build_ram_max_in_gib,
             ephemeral_storage_in_gib: self.advanced_settings.build_ephemeral_storage_in_gib,
             registries: self.container_registries.registries.clone(),
+            cache_backend: self.advanced_settings.build_cache_backend,
+            provenance: self.advanced_settings.build_provenance,
         };
 
         build.compute_image_tag();
@@ -388,6 +466,7 @@ impl Job {
                 mut registry,
                 image,
                 tag,
+                artifact,
             } => {
                 // Default registry is a bit special as the core does not know its url/credentials as it is retrieved by us with some tags
                 if registry.id() == default_container_registry.long_id() {
@@ -400,6 +479,7 @@ impl Job {
                         registry,
                         image,
                         tag,
+                        artifact,
                         registry_mirroring_mode: cluster.advanced_settings().registry_mirroring_mode.clone(),
                     }),
                 }
@@ -431,6 +511,8 @@ impl Job {
                 self.schedule,
                 self.max_nb_restart,
                 Duration::from_secs(self.max_duration_in_sec),
+                self.parallelism,
+                self.completions,
                 self.default_port,
                 self.command_args,
                 self.entrypoint,
@@ -464,6 +546,8 @@ impl Job {
                 self.schedule,
                 self.max_nb_restart,
                 Duration::from_secs(self.max_duration_in_sec),
+                self.parallelism,
+                self.completions,
                 self.default_port,
                 self.command_args,
                 self.entrypoint,
@@ -497,6 +581,8 @@ impl Job {
                 self.schedule,
                 self.max_nb_restart,
                 Duration::from_secs(self.max_duration_in_sec),
+                self.parallelism,
+                self.completions,
                 self.default_port,
                 self.command_args,
                 self.entrypoint,
@@ -530,6 +616,8 @@ impl Job {
                 self.schedule,
                 self.max_nb_restart,
                 Duration::from_secs(self.max_duration_in_sec),
+                self.parallelism,
+                self.completions,
                 self.default_port,
                 self.command_args,
                 self.entrypoint,
@@ -563,6 +651,8 @@ impl Job {
                 self.schedule,
                 self.max_nb_restart,
                 Duration::from_secs(self.max_duration_in_sec),
+                self.parallelism,
+                self.completions,
                 self.default_port,
                 self.command_args,
                 self.entrypoint,
@@ -14,8 +14,10 @@ use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use uuid::Uuid;
 
+pub mod additional_container;
 pub mod annotations_group;
 pub mod application;
+pub mod autoscaling;
 mod azure;
 pub mod container;
 pub mod context;
@@ -229,6 +231,45 @@ pub fn ssh_keys_from_env_vars(environment_vars: &BTreeMap<String, VariableInfo>)
     ssh_keys
 }
 
+pub struct BuildEnvironmentVariables {
+    pub environment_variables: BTreeMap<String, String>,
+    pub secret_environment_variables: BTreeMap<String, String>,
+    pub disable_build_cache: bool,
+}
+
+// Decode build time variables, values are base64 encoded.
+// Secret ones are kept apart, to be exposed as BuildKit secrets instead of build args
+pub fn build_env_vars_from_env_vars(environment_vars: &BTreeMap<String, VariableInfo>) -> BuildEnvironmentVariables {
+    let mut build_env_vars = BuildEnvironmentVariables {
+        environment_variables: BTreeMap::new(),
+        secret_environment_variables: BTreeMap::new(),
+        disable_build_cache: false,
+    };
+
+    for (k, variable_infos) in environment_vars {
+        let v = String::from_utf8(
+            general_purpose::STANDARD
+                .decode(variable_infos.value.as_bytes())
+                .unwrap_or_default(),
+        )
+        .unwrap_or_default();
+
+        // Remove special vars
+        if k == "QOVERY_DISABLE_BUILD_CACHE" && v.to_lowercase() == "true" {
+            build_env_vars.disable_build_cache = true;
+            continue;
+        }
+
+        if variable_infos.is_secret {
+            build_env_vars.secret_environment_variables.insert(k.clone(), v);
+        } else {
+            build_env_vars.environment_variables.insert(k.clone(), v);
+        }
+    }
+
+    build_env_vars
+}
+
 // Convert our root path to an relative path to be able to append them correctly
 pub fn normalize_root_and_dockerfile_path(
     root_path: &str,
@@ -252,6 +293,22 @@ pub fn normalize_root_and_dockerfile_path(
     (root_path, dockerfile_path)
 }
 
+// Paths defining the image of a service built in monorepo mode: its root path, its dockerfile and the extra watched paths
+pub fn monorepo_watched_paths(
+    root_path: &Path,
+    dockerfile_path: &Option<PathBuf>,
+    extra_watched_paths: &[String],
+) -> Vec<PathBuf> {
+    std::iter::once(root_path.to_path_buf())
+        .chain(dockerfile_path.clone())
+        .chain(
+            extra_watched_paths
+                .iter()
+                .map(|path| PathBuf::from(path.trim_start_matches('/'))),
+        )
+        .collect()
+}
+
 pub fn fetch_git_token(
     qovery_api: &dyn QoveryApi,
     service_type: ServiceType,
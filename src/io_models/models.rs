@@ -107,6 +107,31 @@ pub struct HostDataTemplate {
     pub service_port: u16,
 }
 
+#[derive(Serialize, Eq, PartialEq, Debug)]
+pub struct GatewayListenerDataTemplate {
+    pub name: String,
+    pub hostname: String,
+    pub certificate_secret_name: String,
+}
+
+#[derive(Serialize, Eq, PartialEq, Debug)]
+pub struct GatewayHttpListenerDataTemplate {
+    pub name: String,
+    pub hostname: String,
+    /// Hostname has an HTTPS listener, its HTTP requests are redirected to it
+    pub redirect_to_https: bool,
+    /// Certificate of the hostname is issued through an HTTP01 challenge served by this listener
+    pub serves_http01_challenges: bool,
+}
+
+#[derive(Serialize, Eq, PartialEq, Debug)]
+pub struct GatewayRouteDataTemplate {
+    pub hostname: String,
+    pub service_name: String,
+    pub service_port: u16,
+    pub listener_name: String,
+}
+
 pub struct Route {
     pub path: String,
     pub service_long_id: Uuid,
@@ -149,7 +174,7 @@ pub struct NodeGroups {
     pub zone: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, Hash)]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy, Hash)]
 pub enum CpuArchitecture {
     AMD64,
     ARM64,
@@ -293,6 +318,17 @@ pub enum KubernetesMemoryResourceUnit {
     GigaByte(u32),
 }
 
+impl KubernetesMemoryResourceUnit {
+    pub fn to_mib(&self) -> u32 {
+        match self {
+            KubernetesMemoryResourceUnit::MebiByte(v) => *v,
+            KubernetesMemoryResourceUnit::MegaByte(v) => (*v as u64 * 1_000_000 / 1_048_576) as u32,
+            KubernetesMemoryResourceUnit::GibiByte(v) => v * 1024,
+            KubernetesMemoryResourceUnit::GigaByte(v) => (*v as u64 * 1_000_000_000 / 1_048_576) as u32,
+        }
+    }
+}
+
 impl Display for KubernetesMemoryResourceUnit {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.write_str(
@@ -464,6 +500,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_kubernetes_memory_resource_unit_to_mib() {
+        assert_eq!(KubernetesMemoryResourceUnit::MebiByte(512).to_mib(), 512);
+        assert_eq!(KubernetesMemoryResourceUnit::GibiByte(2).to_mib(), 2048);
+        assert_eq!(KubernetesMemoryResourceUnit::MegaByte(1000).to_mib(), 953);
+        assert_eq!(KubernetesMemoryResourceUnit::GigaByte(1).to_mib(), 953);
+    }
+
     #[test]
     fn should_get_kubernetes_memory_unit_from_string() {
         // given
@@ -1,3 +1,4 @@
+use crate::cmd::docker::BuildProvenance;
 use crate::engine_task::qovery_api::QoveryApi;
 use crate::environment::models;
 use crate::environment::models::terraform_service::{TerraformServiceError, TerraformServiceTrait};
@@ -16,12 +17,10 @@ use crate::io_models::labels_group::LabelsGroup;
 use crate::io_models::models::{CpuArchitecture, KubernetesMemoryResourceUnit};
 use crate::io_models::variable_utils::{VariableInfo, default_environment_vars_with_info};
 use crate::io_models::{
-    Action, QoveryIdentifier, fetch_git_token, normalize_root_and_dockerfile_path, sanitized_git_url,
-    ssh_keys_from_env_vars,
+    Action, QoveryIdentifier, build_env_vars_from_env_vars, fetch_git_token, normalize_root_and_dockerfile_path,
+    sanitized_git_url, ssh_keys_from_env_vars,
 };
 use crate::utilities::to_short_id;
-use base64::Engine;
-use base64::engine::general_purpose;
 use itertools::Itertools;
 use serde_derive::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet, HashMap};
@@ -509,27 +508,7 @@ impl TerraformService {
 
         // Convert our root module path to a relative path to be able to append them correctly
         let (root_path, dockerfile_path) = normalize_root_and_dockerfile_path("/", dockerfile_path);
-        let mut disable_build_cache = false;
-
-        let build_env_vars = self
-            .environment_vars_with_infos
-            .iter()
-            .filter_map(|(k, variable_infos)| {
-                // Remove special vars
-                let v = String::from_utf8(
-                    general_purpose::STANDARD
-                        .decode(variable_infos.value.as_bytes())
-                        .unwrap_or_default(),
-                )
-                .unwrap_or_default();
-                if k == "QOVERY_DISABLE_BUILD_CACHE" && v.to_lowercase() == "true" {
-                    disable_build_cache = true;
-                    return None;
-                }
-
-                Some((k.clone(), v))
-            })
-            .collect::<BTreeMap<_, _>>();
+        let build_env_vars = build_env_vars_from_env_vars(&self.environment_vars_with_infos);
 
         let extra_files_to_inject = self.build_extra_files(root_module_path)?;
 
@@ -549,16 +528,22 @@ impl TerraformService {
                 root_path: root_path.clone(),
                 extra_files_to_inject,
                 docker_target_build_stage: None,
+                watched_paths: None,
+                paths_content_hash: None,
+                commit_timestamp: None,
             },
             image: self.to_image(commit_id.to_string(), registry_url, cluster_id, git_url.as_str()),
-            environment_variables: build_env_vars,
-            disable_cache: disable_build_cache,
+            environment_variables: build_env_vars.environment_variables,
+            secret_environment_variables: build_env_vars.secret_environment_variables,
+            disable_cache: build_env_vars.disable_build_cache,
             timeout: Duration::from_secs(self.advanced_settings.build_timeout_max_sec as u64),
             architectures,
             max_cpu_in_milli: self.advanced_settings.build_cpu_max_in_milli,
             max_ram_in_gib: self.advanced_settings.build_ram_max_in_gib,
             ephemeral_storage_in_gib: self.advanced_settings.build_ephemeral_storage_in_gib,
             registries: vec![],
+            cache_backend: None,
+            provenance: BuildProvenance::default(),
         };
 
         build.compute_image_tag();
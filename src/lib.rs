@@ -16,6 +16,7 @@ pub mod errors;
 pub mod events;
 pub mod fs;
 pub use cmd::git::git_initialize_opts;
+pub use cmd::git_mirror::git_initialize_mirror_cache;
 pub mod environment;
 pub mod helm;
 pub mod infrastructure;
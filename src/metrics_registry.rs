@@ -1,4 +1,5 @@
 use crate::events::{EngineMsg, EngineMsgPayload};
+use crate::io_models::models::CpuArchitecture;
 use crate::msg_publisher::{MsgPublisher, StdMsgPublisher};
 use std::collections::HashMap;
 use std::fmt::Display;
@@ -14,6 +15,10 @@ pub enum StepName {
     GitClone,
     BuildQueueing,
     Build,
+    // Build of a single architecture of a multi-architecture image
+    BuildArchitecture(CpuArchitecture),
+    BuildCacheHit,
+    BuildCacheMiss,
     MirrorImage,
     DeploymentQueueing,
     Deployment,
@@ -28,6 +33,9 @@ impl Display for StepName {
             StepName::BuildQueueing => "BuildQueueing".to_string(),
             StepName::GitClone => "GitClone".to_string(),
             StepName::Build => "Build".to_string(),
+            StepName::BuildArchitecture(arch) => format!("Build{arch}"),
+            StepName::BuildCacheHit => "BuildCacheHit".to_string(),
+            StepName::BuildCacheMiss => "BuildCacheMiss".to_string(),
             StepName::MirrorImage => "MirrorImage".to_string(),
             StepName::DeploymentQueueing => "DeploymentQueueing".to_string(),
             StepName::Deployment => "Deployment".to_string(),
@@ -0,0 +1,186 @@
+use crate::cmd::kubectl::kubectl_exec_get_raw;
+use crate::errors::ErrorMessageVerbosity;
+use crate::helm::HelmChartNamespaces;
+use crate::infrastructure::helm_charts::opencost_chart::{OPENCOST_API_PORT, OpenCostChart};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Label set by the engine on every environment workload, as sanitized by OpenCost
+const ENVIRONMENT_ID_LABEL: &str = "qovery_com_environment_id";
+
+#[derive(thiserror::Error, Clone, Debug, PartialEq)]
+pub enum CostAllocationServiceError {
+    #[error("Cannot get costs allocation from OpenCost: {raw_error_message}")]
+    CannotGetAllocation { raw_error_message: String },
+}
+
+/// Estimated cost of an environment workloads over a time window, in the currency configured in OpenCost
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnvironmentCost {
+    pub environment_id: Uuid,
+    pub window_start: DateTime<Utc>,
+    pub window_end: DateTime<Utc>,
+    pub cpu_cost: f64,
+    pub gpu_cost: f64,
+    pub ram_cost: f64,
+    pub pv_cost: f64,
+    pub network_cost: f64,
+    pub load_balancer_cost: f64,
+    pub total_cost: f64,
+}
+
+#[derive(Deserialize)]
+struct AllocationResponse {
+    data: Vec<HashMap<String, Allocation>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Allocation {
+    window: AllocationWindow,
+    #[serde(default)]
+    cpu_cost: f64,
+    #[serde(default)]
+    gpu_cost: f64,
+    #[serde(default)]
+    ram_cost: f64,
+    #[serde(default)]
+    pv_cost: f64,
+    #[serde(default)]
+    network_cost: f64,
+    #[serde(default)]
+    load_balancer_cost: f64,
+    #[serde(default)]
+    total_cost: f64,
+}
+
+#[derive(Deserialize)]
+struct AllocationWindow {
+    start: String,
+    end: String,
+}
+
+/// Computes environments costs from the OpenCost allocation API, installed with `cost.opencost.enabled`
+pub struct CostAllocationService {
+    kubeconfig_path: PathBuf,
+    envs: Vec<(String, String)>,
+}
+
+impl CostAllocationService {
+    pub fn new(kubeconfig_path: PathBuf, envs: Vec<(String, String)>) -> CostAllocationService {
+        CostAllocationService { kubeconfig_path, envs }
+    }
+
+    /// OpenCost is not exposed outside of the cluster, its API is reached through the Kubernetes API server proxy
+    fn allocation_api_path(window: Duration) -> String {
+        format!(
+            "/api/v1/namespaces/{}/services/{}:{OPENCOST_API_PORT}/proxy/allocation/compute?window={}h&aggregate=label:{ENVIRONMENT_ID_LABEL}&accumulate=true",
+            HelmChartNamespaces::OpenCost,
+            OpenCostChart::chart_name(),
+            (window.as_secs() / 3600).max(1)
+        )
+    }
+
+    /// Returns the cost of every environment of the cluster over the last `window`, rounded to the hour
+    pub fn get_environments_costs(&self, window: Duration) -> Result<Vec<EnvironmentCost>, CostAllocationServiceError> {
+        let response: AllocationResponse = kubectl_exec_get_raw(
+            &self.kubeconfig_path,
+            self.envs.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect(),
+            &Self::allocation_api_path(window),
+        )
+        .map_err(|e| CostAllocationServiceError::CannotGetAllocation {
+            raw_error_message: e.message(ErrorMessageVerbosity::FullDetailsWithoutEnvVars),
+        })?;
+
+        Ok(to_environments_costs(response))
+    }
+}
+
+fn to_environments_costs(response: AllocationResponse) -> Vec<EnvironmentCost> {
+    let mut costs: Vec<EnvironmentCost> = response
+        .data
+        .into_iter()
+        .flatten()
+        // workloads without the label are aggregated under `__unallocated__`, and unused nodes under `__idle__`
+        .filter_map(|(name, allocation)| {
+            let environment_id = Uuid::from_str(&name).ok()?;
+            Some(EnvironmentCost {
+                environment_id,
+                window_start: DateTime::parse_from_rfc3339(&allocation.window.start)
+                    .ok()?
+                    .with_timezone(&Utc),
+                window_end: DateTime::parse_from_rfc3339(&allocation.window.end)
+                    .ok()?
+                    .with_timezone(&Utc),
+                cpu_cost: allocation.cpu_cost,
+                gpu_cost: allocation.gpu_cost,
+                ram_cost: allocation.ram_cost,
+                pv_cost: allocation.pv_cost,
+                network_cost: allocation.network_cost,
+                load_balancer_cost: allocation.load_balancer_cost,
+                total_cost: allocation.total_cost,
+            })
+        })
+        .collect();
+    costs.sort_by_key(|cost| cost.environment_id);
+
+    costs
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::services::cost_allocation_service::{AllocationResponse, CostAllocationService, to_environments_costs};
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_allocation_api_path() {
+        assert_eq!(
+            CostAllocationService::allocation_api_path(Duration::from_secs(7 * 24 * 3600)),
+            "/api/v1/namespaces/opencost/services/opencost:9003/proxy/allocation/compute?window=168h&aggregate=label:qovery_com_environment_id&accumulate=true"
+        );
+        assert!(CostAllocationService::allocation_api_path(Duration::from_secs(60)).contains("window=1h"));
+    }
+
+    #[test]
+    fn test_to_environments_costs_skips_unallocated_costs() {
+        let environment_id = Uuid::new_v4();
+        let response: AllocationResponse = serde_json::from_str(&format!(
+            r#"{{
+                "code": 200,
+                "data": [{{
+                    "{environment_id}": {{
+                        "name": "{environment_id}",
+                        "window": {{ "start": "2025-01-01T00:00:00Z", "end": "2025-01-08T00:00:00Z" }},
+                        "cpuCost": 1.5,
+                        "gpuCost": 0,
+                        "ramCost": 0.5,
+                        "pvCost": 0.25,
+                        "networkCost": 0,
+                        "loadBalancerCost": 0.75,
+                        "totalCost": 3.0
+                    }},
+                    "__unallocated__": {{
+                        "name": "__unallocated__",
+                        "window": {{ "start": "2025-01-01T00:00:00Z", "end": "2025-01-08T00:00:00Z" }},
+                        "totalCost": 10.0
+                    }}
+                }}]
+            }}"#
+        ))
+        .unwrap();
+
+        let costs = to_environments_costs(response);
+
+        assert_eq!(costs.len(), 1);
+        assert_eq!(costs[0].environment_id, environment_id);
+        assert_eq!(costs[0].cpu_cost, 1.5);
+        assert_eq!(costs[0].load_balancer_cost, 0.75);
+        assert_eq!(costs[0].total_cost, 3.0);
+    }
+}
@@ -0,0 +1,420 @@
+use crate::environment::models::types::VersionsNumber;
+use crate::helm::ChartReleaseData;
+use crate::infrastructure::models::kubernetes::KubernetesVersion;
+use crate::runtime::block_on;
+use base64::Engine;
+use base64::engine::general_purpose;
+use k8s_openapi::api::core::v1::Secret;
+use kube::api::{DynamicObject, ListParams};
+use kube::core::{GroupVersion, GroupVersionKind};
+use kube::{Api, Client, discovery};
+use serde::Deserialize;
+use std::io::Read;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+
+const LAST_APPLIED_CONFIGURATION_ANNOTATION: &str = "kubectl.kubernetes.io/last-applied-configuration";
+const HELM_DEPLOYED_RELEASES_SELECTOR: &str = "owner=helm,status=deployed";
+
+/// A kubernetes API version removed from the API server in a given kubernetes version
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeprecatedApi {
+    pub api_version: &'static str,
+    pub kind: &'static str,
+    pub deprecated_since: &'static str,
+    pub replace_with: Option<&'static str>,
+}
+
+const fn deprecated_api(
+    api_version: &'static str,
+    kind: &'static str,
+    deprecated_since: &'static str,
+    replace_with: Option<&'static str>,
+) -> DeprecatedApi {
+    DeprecatedApi {
+        api_version,
+        kind,
+        deprecated_since,
+        replace_with,
+    }
+}
+
+/// APIs removed in each kubernetes version, see https://kubernetes.io/docs/reference/using-api/deprecation-guide/
+pub fn deprecated_apis_removed_in(version: &KubernetesVersion) -> &'static [DeprecatedApi] {
+    const REMOVED_IN_1_25: &[DeprecatedApi] = &[
+        deprecated_api("batch/v1beta1", "CronJob", "1.21.0", Some("batch/v1")),
+        deprecated_api(
+            "discovery.k8s.io/v1beta1",
+            "EndpointSlice",
+            "1.21.0",
+            Some("discovery.k8s.io/v1"),
+        ),
+        deprecated_api("events.k8s.io/v1beta1", "Event", "1.19.0", Some("events.k8s.io/v1")),
+        deprecated_api(
+            "autoscaling/v2beta1",
+            "HorizontalPodAutoscaler",
+            "1.23.0",
+            Some("autoscaling/v2"),
+        ),
+        deprecated_api("policy/v1beta1", "PodDisruptionBudget", "1.21.0", Some("policy/v1")),
+        deprecated_api("policy/v1beta1", "PodSecurityPolicy", "1.21.0", None),
+        deprecated_api("node.k8s.io/v1beta1", "RuntimeClass", "1.20.0", Some("node.k8s.io/v1")),
+    ];
+    const REMOVED_IN_1_26: &[DeprecatedApi] = &[
+        deprecated_api(
+            "flowcontrol.apiserver.k8s.io/v1beta1",
+            "FlowSchema",
+            "1.23.0",
+            Some("flowcontrol.apiserver.k8s.io/v1beta3"),
+        ),
+        deprecated_api(
+            "flowcontrol.apiserver.k8s.io/v1beta1",
+            "PriorityLevelConfiguration",
+            "1.23.0",
+            Some("flowcontrol.apiserver.k8s.io/v1beta3"),
+        ),
+        deprecated_api(
+            "autoscaling/v2beta2",
+            "HorizontalPodAutoscaler",
+            "1.23.0",
+            Some("autoscaling/v2"),
+        ),
+    ];
+    const REMOVED_IN_1_27: &[DeprecatedApi] = &[deprecated_api(
+        "storage.k8s.io/v1beta1",
+        "CSIStorageCapacity",
+        "1.24.0",
+        Some("storage.k8s.io/v1"),
+    )];
+    const REMOVED_IN_1_29: &[DeprecatedApi] = &[
+        deprecated_api(
+            "flowcontrol.apiserver.k8s.io/v1beta2",
+            "FlowSchema",
+            "1.26.0",
+            Some("flowcontrol.apiserver.k8s.io/v1"),
+        ),
+        deprecated_api(
+            "flowcontrol.apiserver.k8s.io/v1beta2",
+            "PriorityLevelConfiguration",
+            "1.26.0",
+            Some("flowcontrol.apiserver.k8s.io/v1"),
+        ),
+    ];
+    const REMOVED_IN_1_32: &[DeprecatedApi] = &[
+        deprecated_api(
+            "flowcontrol.apiserver.k8s.io/v1beta3",
+            "FlowSchema",
+            "1.29.0",
+            Some("flowcontrol.apiserver.k8s.io/v1"),
+        ),
+        deprecated_api(
+            "flowcontrol.apiserver.k8s.io/v1beta3",
+            "PriorityLevelConfiguration",
+            "1.29.0",
+            Some("flowcontrol.apiserver.k8s.io/v1"),
+        ),
+    ];
+
+    match version {
+        KubernetesVersion::V1_25 { .. } => REMOVED_IN_1_25,
+        KubernetesVersion::V1_26 { .. } => REMOVED_IN_1_26,
+        KubernetesVersion::V1_27 { .. } => REMOVED_IN_1_27,
+        KubernetesVersion::V1_29 { .. } => REMOVED_IN_1_29,
+        KubernetesVersion::V1_32 { .. } => REMOVED_IN_1_32,
+        KubernetesVersion::V1_23 { .. }
+        | KubernetesVersion::V1_24 { .. }
+        | KubernetesVersion::V1_28 { .. }
+        | KubernetesVersion::V1_30 { .. }
+        | KubernetesVersion::V1_31 { .. }
+        | KubernetesVersion::V1_33 { .. } => &[],
+    }
+}
+
+/// An object still using a deprecated API, found in the cluster or in a helm release
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ScannedDeprecation {
+    pub name: Option<String>,
+    pub namespace: Option<String>,
+    pub kind: Option<String>,
+    pub api_version: Option<String>,
+    pub rule_set: Option<String>,
+    pub replace_with: Option<String>,
+    pub since: Option<String>,
+}
+
+#[derive(thiserror::Error, Clone, Debug, PartialEq)]
+pub enum KubernetesApiDeprecationScannerError {
+    #[error("Cannot list `{kind}` objects served by `{api_version}`: {raw_error_message}")]
+    CannotListResources {
+        kind: String,
+        api_version: String,
+        raw_error_message: String,
+    },
+    #[error("Cannot list helm releases: {raw_error_message}")]
+    CannotListHelmReleases { raw_error_message: String },
+}
+
+#[derive(Clone, Debug)]
+struct DeprecationRule {
+    removed_in: VersionsNumber,
+    api: &'static DeprecatedApi,
+}
+
+impl DeprecationRule {
+    fn matches(&self, api_version: &str, kind: &str) -> bool {
+        self.api.api_version == api_version && self.api.kind == kind
+    }
+
+    fn to_deprecation(&self, name: Option<String>, namespace: Option<String>) -> ScannedDeprecation {
+        ScannedDeprecation {
+            name,
+            namespace,
+            kind: Some(self.api.kind.to_string()),
+            api_version: Some(self.api.api_version.to_string()),
+            rule_set: Some(format!("Deprecated APIs removed in {}", self.removed_in)),
+            replace_with: Some(self.api.replace_with.unwrap_or("<removed>").to_string()),
+            since: Some(self.api.deprecated_since.to_string()),
+        }
+    }
+}
+
+/// Rules of every kubernetes version up to `target_version`, or all of them if there is no target
+fn deprecation_rules(target_version: Option<&VersionsNumber>) -> Vec<DeprecationRule> {
+    KubernetesVersion::iter()
+        .filter_map(|version| {
+            let removed_in = VersionsNumber::from(version.clone());
+            match target_version {
+                Some(target_version) if &removed_in > target_version => None,
+                _ => Some((removed_in, deprecated_apis_removed_in(&version))),
+            }
+        })
+        .flat_map(|(removed_in, apis)| {
+            apis.iter().map(move |api| DeprecationRule {
+                removed_in: removed_in.clone(),
+                api,
+            })
+        })
+        .collect()
+}
+
+/// Finds objects using APIs removed in upcoming kubernetes versions, by looking at:
+/// * the API version objects have been applied with, for objects served by a deprecated API
+/// * the manifests of deployed helm releases
+#[cfg_attr(test, faux::create)]
+pub struct KubernetesApiDeprecationScanner {}
+
+#[cfg_attr(test, faux::methods)]
+impl Default for KubernetesApiDeprecationScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg_attr(test, faux::methods)]
+impl<'m> KubernetesApiDeprecationScanner {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn get_deprecations(
+        &'m self,
+        kube_client: &'m Client,
+        target_version: Option<&'m VersionsNumber>,
+    ) -> Result<Vec<ScannedDeprecation>, KubernetesApiDeprecationScannerError> {
+        let rules = deprecation_rules(target_version);
+        if rules.is_empty() {
+            return Ok(Vec::with_capacity(0));
+        }
+
+        let mut deprecations = block_on(served_resources_deprecations(kube_client, &rules))?;
+        for deprecation in block_on(helm_releases_deprecations(kube_client, &rules))? {
+            if !deprecations.contains(&deprecation) {
+                deprecations.push(deprecation);
+            }
+        }
+
+        Ok(deprecations)
+    }
+}
+
+async fn served_resources_deprecations(
+    kube_client: &Client,
+    rules: &[DeprecationRule],
+) -> Result<Vec<ScannedDeprecation>, KubernetesApiDeprecationScannerError> {
+    let mut deprecations = vec![];
+    for rule in rules {
+        let gvk = match GroupVersion::from_str(rule.api.api_version) {
+            Ok(gv) => GroupVersionKind::gvk(&gv.group, &gv.version, rule.api.kind),
+            Err(_) => continue,
+        };
+        // API is not served anymore by the cluster, no object can use it
+        let Ok((api_resource, _)) = discovery::pinned_kind(kube_client, &gvk).await else {
+            continue;
+        };
+
+        // objects are returned converted to the requested version, only their applied configuration tells the version they use
+        let objects = Api::<DynamicObject>::all_with(kube_client.clone(), &api_resource)
+            .list(&ListParams::default())
+            .await
+            .map_err(|e| KubernetesApiDeprecationScannerError::CannotListResources {
+                kind: rule.api.kind.to_string(),
+                api_version: rule.api.api_version.to_string(),
+                raw_error_message: e.to_string(),
+            })?;
+        for object in objects.items {
+            let applied_api_version = object
+                .metadata
+                .annotations
+                .as_ref()
+                .and_then(|annotations| annotations.get(LAST_APPLIED_CONFIGURATION_ANNOTATION))
+                .and_then(|applied| serde_json::from_str::<serde_json::Value>(applied).ok())
+                .and_then(|applied| applied.get("apiVersion").and_then(|it| it.as_str()).map(String::from));
+            if applied_api_version.as_deref() == Some(rule.api.api_version) {
+                deprecations.push(rule.to_deprecation(object.metadata.name, object.metadata.namespace));
+            }
+        }
+    }
+
+    Ok(deprecations)
+}
+
+async fn helm_releases_deprecations(
+    kube_client: &Client,
+    rules: &[DeprecationRule],
+) -> Result<Vec<ScannedDeprecation>, KubernetesApiDeprecationScannerError> {
+    let releases_secrets = Api::<Secret>::all(kube_client.clone())
+        .list(&ListParams::default().labels(HELM_DEPLOYED_RELEASES_SELECTOR))
+        .await
+        .map_err(|e| KubernetesApiDeprecationScannerError::CannotListHelmReleases {
+            raw_error_message: e.to_string(),
+        })?;
+
+    let mut deprecations = vec![];
+    for secret in releases_secrets.items {
+        let secret_name = secret.metadata.name.clone().unwrap_or_default();
+        let Some(release) = secret.data.as_ref().and_then(|data| data.get("release")) else {
+            continue;
+        };
+        match decode_helm_release(&release.0) {
+            Ok(release) => deprecations.extend(manifest_deprecations(&release.manifest, &release.namespace, rules)),
+            Err(err) => warn!("Cannot decode helm release secret `{}`: {}", secret_name, err),
+        }
+    }
+
+    Ok(deprecations)
+}
+
+/// Helm stores releases as base64 encoded gzipped json
+fn decode_helm_release(encoded_release: &[u8]) -> Result<ChartReleaseData, String> {
+    let compressed_release = general_purpose::STANDARD
+        .decode(encoded_release)
+        .map_err(|e| e.to_string())?;
+    let mut release = String::new();
+    flate2::read::GzDecoder::new(&compressed_release[..])
+        .read_to_string(&mut release)
+        .map_err(|e| e.to_string())?;
+
+    serde_json::from_str(&release).map_err(|e| e.to_string())
+}
+
+fn manifest_deprecations(
+    manifest: &str,
+    release_namespace: &str,
+    rules: &[DeprecationRule],
+) -> Vec<ScannedDeprecation> {
+    let mut deprecations = vec![];
+    for document in serde_yaml::Deserializer::from_str(manifest) {
+        let Ok(object) = serde_yaml::Value::deserialize(document) else {
+            continue;
+        };
+        let (Some(api_version), Some(kind)) = (
+            object.get("apiVersion").and_then(|it| it.as_str()),
+            object.get("kind").and_then(|it| it.as_str()),
+        ) else {
+            continue;
+        };
+        let Some(rule) = rules.iter().find(|rule| rule.matches(api_version, kind)) else {
+            continue;
+        };
+
+        let metadata = object.get("metadata");
+        let name = metadata
+            .and_then(|it| it.get("name"))
+            .and_then(|it| it.as_str())
+            .map(String::from);
+        let namespace = metadata
+            .and_then(|it| it.get("namespace"))
+            .and_then(|it| it.as_str())
+            .unwrap_or(release_namespace);
+        deprecations.push(rule.to_deprecation(name, Some(namespace.to_string())));
+    }
+
+    deprecations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environment::models::types::VersionsNumberBuilder;
+
+    #[test]
+    fn test_deprecation_rules_up_to_target_version() {
+        let rules = deprecation_rules(Some(&VersionsNumberBuilder::new().major(1).minor(26).build()));
+
+        assert!(
+            rules
+                .iter()
+                .any(|rule| rule.matches("policy/v1beta1", "PodSecurityPolicy"))
+        );
+        assert!(
+            rules
+                .iter()
+                .any(|rule| rule.matches("autoscaling/v2beta2", "HorizontalPodAutoscaler"))
+        );
+        assert!(
+            !rules
+                .iter()
+                .any(|rule| rule.matches("storage.k8s.io/v1beta1", "CSIStorageCapacity"))
+        );
+        assert!(
+            deprecation_rules(None)
+                .iter()
+                .any(|rule| rule.matches("flowcontrol.apiserver.k8s.io/v1beta3", "FlowSchema"))
+        );
+    }
+
+    #[test]
+    fn test_manifest_deprecations() {
+        let rules = deprecation_rules(None);
+        let manifest = r#"
+---
+# Source: app/templates/pdb.yaml
+apiVersion: policy/v1beta1
+kind: PodDisruptionBudget
+metadata:
+  name: app
+---
+# Source: app/templates/cronjob.yaml
+apiVersion: batch/v1
+kind: CronJob
+metadata:
+  name: app
+  namespace: jobs
+"#;
+
+        let deprecations = manifest_deprecations(manifest, "default", &rules);
+
+        assert_eq!(
+            deprecations,
+            vec![ScannedDeprecation {
+                name: Some("app".to_string()),
+                namespace: Some("default".to_string()),
+                kind: Some("PodDisruptionBudget".to_string()),
+                api_version: Some("policy/v1beta1".to_string()),
+                rule_set: Some("Deprecated APIs removed in 1.25".to_string()),
+                replace_with: Some("policy/v1".to_string()),
+                since: Some("1.21.0".to_string()),
+            }]
+        );
+    }
+}
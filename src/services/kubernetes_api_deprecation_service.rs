@@ -1,9 +1,9 @@
 use crate::environment::models::types::VersionsNumber;
+use crate::io_models::QoveryIdentifier;
 use crate::io_models::QoveryIdentifierError;
 use crate::runtime::block_on;
-use crate::{
-    cmd::kubent::{Deprecation as CmdDeprecation, Kubent, KubentError},
-    io_models::QoveryIdentifier,
+use crate::services::kubernetes_api_deprecation_scanner::{
+    KubernetesApiDeprecationScanner, KubernetesApiDeprecationScannerError, ScannedDeprecation,
 };
 use kube::api::{ApiResource, DynamicObject};
 use kube::core::{GroupVersion, GroupVersionKind};
@@ -12,13 +12,14 @@ use std::str::FromStr;
 use std::{
     fmt::{self, Formatter},
     ops::Deref,
-    path::Path,
 };
 
 #[derive(thiserror::Error, Clone, Debug, PartialEq)]
 pub enum KubernetesDeprecationServiceError {
-    #[error("Client (kubent) error: {client_error}")]
-    ClientError { client_error: KubentError },
+    #[error("Scanner error: {client_error}")]
+    ClientError {
+        client_error: KubernetesApiDeprecationScannerError,
+    },
     #[error(
         "Error while trying to parse kubernetes API version, it seems to be an invalid version: `{invalid_version}`"
     )]
@@ -171,7 +172,7 @@ impl Deprecation {
 
     pub fn try_from_with_qovery_metadata(
         kube_client: &Client,
-        deprecation: CmdDeprecation,
+        deprecation: ScannedDeprecation,
     ) -> Result<Self, KubernetesDeprecationServiceError> {
         Self::new_with_qovery_metadata(
             kube_client,
@@ -193,10 +194,10 @@ impl Deprecation {
     }
 }
 
-impl TryFrom<CmdDeprecation> for Deprecation {
+impl TryFrom<ScannedDeprecation> for Deprecation {
     type Error = KubernetesDeprecationServiceError;
 
-    fn try_from(deprecation: CmdDeprecation) -> Result<Self, Self::Error> {
+    fn try_from(deprecation: ScannedDeprecation) -> Result<Self, Self::Error> {
         Ok(Self {
             name: deprecation.name,
             namespace: deprecation.namespace,
@@ -385,23 +386,22 @@ pub enum KubernetesApiDeprecationServiceGranuality<'a> {
 
 #[derive(Default)]
 pub struct KubernetesApiDeprecationService {
-    client: Kubent,
+    scanner: KubernetesApiDeprecationScanner,
 }
 
 impl KubernetesApiDeprecationService {
-    pub fn new(client: Kubent) -> Self {
-        Self { client }
+    pub fn new(scanner: KubernetesApiDeprecationScanner) -> Self {
+        Self { scanner }
     }
 
     pub fn get_deprecated_kubernetes_apis(
         &self,
-        kubeconfig: &Path,
+        kube_client: &Client,
         target_version: Option<&VersionsNumber>,
-        envs: &[(&str, &str)],
         granularity: KubernetesApiDeprecationServiceGranuality,
     ) -> Result<Vec<Deprecation>, KubernetesDeprecationServiceError> {
-        self.client
-            .get_deprecations(kubeconfig, target_version.map(|v| v.to_string()), envs)
+        self.scanner
+            .get_deprecations(kube_client, target_version)
             .map_err(|e| KubernetesDeprecationServiceError::ClientError { client_error: e })?
             .into_iter()
             .map(|d| match granularity {
@@ -415,13 +415,12 @@ impl KubernetesApiDeprecationService {
 
     pub fn is_cluster_fully_compatible_with_kubernetes_version(
         &self,
-        kubeconfig: &Path,
+        kube_client: &Client,
         target_kubernetes_version: Option<&VersionsNumber>,
-        envs: &[(&str, &str)],
         granularity: KubernetesApiDeprecationServiceGranuality,
     ) -> Result<(), KubernetesDeprecationServiceError> {
         let deprecations = self
-            .get_deprecated_kubernetes_apis(kubeconfig, target_kubernetes_version, envs, granularity)?
+            .get_deprecated_kubernetes_apis(kube_client, target_kubernetes_version, granularity)?
             .into_iter()
             .filter(|deprecation| match target_kubernetes_version {
                 Some(tv) => match deprecation.since {
@@ -442,25 +441,24 @@ impl KubernetesApiDeprecationService {
 
 #[cfg(test)]
 mod tests {
-    use std::{fs::File, path::PathBuf};
-    use tempfile::tempdir;
-
     use super::*;
     use crate::environment::models::types::VersionsNumberBuilder;
-    use crate::{cmd::kubent, services::kubernetes_api_deprecation_service::Deprecation as ServiceDeprecation};
+    use crate::services::kubernetes_api_deprecation_service::Deprecation as ServiceDeprecation;
+
+    /// Client never reaching any cluster, the scanner being mocked
+    fn kube_client() -> Client {
+        block_on(async {
+            Client::try_from(kube::Config::new("http://127.0.0.1:1".parse().expect("Invalid cluster url")))
+        })
+        .expect("Failed to create kube client")
+    }
 
     #[test]
     fn test_get_deprecated_kubernetes_apis_with_deprecations() {
         // setup:
-        let temp_dir = tempdir().expect("Failed to create temp dir");
-        let kubeconfig = temp_dir.path().join("kubeconfig");
-        let _kubeconfig_file = File::create(&kubeconfig).expect("Failed to create kubeconfig file");
-        let _created_temp_dir_guard = scopeguard::guard((), |_| {
-            // delete temp dir after test
-            std::fs::remove_dir_all(temp_dir.path()).expect("Failed to remove temp dir");
-        });
-
-        let deprecations = vec![kubent::Deprecation {
+        let kube_client = kube_client();
+
+        let deprecations = vec![ScannedDeprecation {
             name: Some("name".to_string()),
             namespace: Some("namespace".to_string()),
             kind: Some("kind".to_string()),
@@ -469,16 +467,15 @@ mod tests {
             replace_with: Some("replace_with".to_string()),
             since: Some("1.28".to_string()),
         }];
-        let mut kubent_cmd_mock = Kubent::faux();
-        faux::when!(kubent_cmd_mock.get_deprecations(_, _, _)).then_return(Ok(deprecations.clone()));
+        let mut scanner_mock = KubernetesApiDeprecationScanner::faux();
+        faux::when!(scanner_mock.get_deprecations(_, _)).then_return(Ok(deprecations.clone()));
 
-        let service = KubernetesApiDeprecationService::new(kubent_cmd_mock);
+        let service = KubernetesApiDeprecationService::new(scanner_mock);
 
         // execute:
         let result = service.get_deprecated_kubernetes_apis(
-            &kubeconfig,
+            &kube_client,
             None,
-            &[],
             KubernetesApiDeprecationServiceGranuality::Default,
         );
 
@@ -495,25 +492,18 @@ mod tests {
     #[test]
     fn test_get_deprecated_kubernetes_apis_without_deprecations() {
         // setup:
-        let temp_dir = tempdir().expect("Failed to create temp dir");
-        let kubeconfig = temp_dir.path().join("kubeconfig");
-        let _kubeconfig_file = File::create(&kubeconfig).expect("Failed to create kubeconfig file");
-        let _created_temp_dir_guard = scopeguard::guard((), |_| {
-            // delete temp dir after test
-            std::fs::remove_dir_all(temp_dir.path()).expect("Failed to remove temp dir");
-        });
+        let kube_client = kube_client();
 
         let deprecations = vec![];
-        let mut kubent_cmd_mock = Kubent::faux();
-        faux::when!(kubent_cmd_mock.get_deprecations(_, _, _)).then_return(Ok(deprecations.clone()));
+        let mut scanner_mock = KubernetesApiDeprecationScanner::faux();
+        faux::when!(scanner_mock.get_deprecations(_, _)).then_return(Ok(deprecations.clone()));
 
-        let service = KubernetesApiDeprecationService::new(kubent_cmd_mock);
+        let service = KubernetesApiDeprecationService::new(scanner_mock);
 
         // execute:
         let result = service.get_deprecated_kubernetes_apis(
-            &kubeconfig,
+            &kube_client,
             None,
-            &[],
             KubernetesApiDeprecationServiceGranuality::Default,
         );
 
@@ -528,30 +518,31 @@ mod tests {
     }
 
     #[test]
-    fn test_get_deprecated_kubernetes_apis_with_wrong_kubeconfig() {
+    fn test_get_deprecated_kubernetes_apis_with_scanner_error() {
         // setup:
-        let kubeconfig = PathBuf::from("/tmp/kubeconfig-this-one-doesnt-exist");
+        let kube_client = kube_client();
 
-        let mut kubent_cmd_mock = Kubent::faux();
-        faux::when!(kubent_cmd_mock.get_deprecations(_, _, _)).then_return(Err(KubentError::InvalidKubeConfig {
-            kubeconfig_path: kubeconfig.display().to_string(),
-        }));
+        let mut scanner_mock = KubernetesApiDeprecationScanner::faux();
+        faux::when!(scanner_mock.get_deprecations(_, _)).then_return(Err(
+            KubernetesApiDeprecationScannerError::CannotListHelmReleases {
+                raw_error_message: "forbidden".to_string(),
+            },
+        ));
 
-        let service = KubernetesApiDeprecationService::new(kubent_cmd_mock);
+        let service = KubernetesApiDeprecationService::new(scanner_mock);
 
         // execute:
         let result = service.get_deprecated_kubernetes_apis(
-            &kubeconfig,
+            &kube_client,
             None,
-            &[],
             KubernetesApiDeprecationServiceGranuality::Default,
         );
 
         // verify:
         assert_eq!(
             KubernetesDeprecationServiceError::ClientError {
-                client_error: KubentError::InvalidKubeConfig {
-                    kubeconfig_path: kubeconfig.display().to_string(),
+                client_error: KubernetesApiDeprecationScannerError::CannotListHelmReleases {
+                    raw_error_message: "forbidden".to_string(),
                 }
             },
             result.expect_err("Should have error")
@@ -561,18 +552,12 @@ mod tests {
     #[test]
     fn test_get_deprecated_kubernetes_apis_with_wrong_kubernetes_api_version() {
         // setup:
-        let temp_dir = tempdir().expect("Failed to create temp dir");
-        let kubeconfig = temp_dir.path().join("kubeconfig");
-        let _kubeconfig_file = File::create(&kubeconfig).expect("Failed to create kubeconfig file");
-        let _created_temp_dir_guard = scopeguard::guard((), |_| {
-            // delete temp dir after test
-            std::fs::remove_dir_all(temp_dir.path()).expect("Failed to remove temp dir");
-        });
+        let kube_client = kube_client();
 
         let invalid_api_version = ""; // TODO(benjaminch): find a better invalid version,
         // VersionsNumber parsing is a bit too permissive and clunky and needs to be improved /
         // swapped with an external lib.
-        let deprecations = vec![kubent::Deprecation {
+        let deprecations = vec![ScannedDeprecation {
             name: Some("name".to_string()),
             namespace: Some("namespace".to_string()),
             kind: Some("kind".to_string()),
@@ -581,16 +566,15 @@ mod tests {
             replace_with: Some("replace_with".to_string()),
             since: Some(invalid_api_version.to_string()),
         }];
-        let mut kubent_cmd_mock = Kubent::faux();
-        faux::when!(kubent_cmd_mock.get_deprecations(_, _, _)).then_return(Ok(deprecations.clone()));
+        let mut scanner_mock = KubernetesApiDeprecationScanner::faux();
+        faux::when!(scanner_mock.get_deprecations(_, _)).then_return(Ok(deprecations.clone()));
 
-        let service = KubernetesApiDeprecationService::new(kubent_cmd_mock);
+        let service = KubernetesApiDeprecationService::new(scanner_mock);
 
         // execute:
         let result = service.get_deprecated_kubernetes_apis(
-            &kubeconfig,
+            &kube_client,
             None,
-            &[],
             KubernetesApiDeprecationServiceGranuality::Default,
         );
 
@@ -606,25 +590,18 @@ mod tests {
     #[test]
     fn test_is_cluster_fully_compatible_with_kubernetes_version_no_deprecations() {
         // setup:
-        let temp_dir = tempdir().expect("Failed to create temp dir");
-        let kubeconfig = temp_dir.path().join("kubeconfig");
-        let _kubeconfig_file = File::create(&kubeconfig).expect("Failed to create kubeconfig file");
-        let _created_temp_dir_guard = scopeguard::guard((), |_| {
-            // delete temp dir after test
-            std::fs::remove_dir_all(temp_dir.path()).expect("Failed to remove temp dir");
-        });
+        let kube_client = kube_client();
 
         let deprecations = vec![];
-        let mut kubent_cmd_mock = Kubent::faux();
-        faux::when!(kubent_cmd_mock.get_deprecations(_, _, _)).then_return(Ok(deprecations.clone()));
+        let mut scanner_mock = KubernetesApiDeprecationScanner::faux();
+        faux::when!(scanner_mock.get_deprecations(_, _)).then_return(Ok(deprecations.clone()));
 
-        let service = KubernetesApiDeprecationService::new(kubent_cmd_mock);
+        let service = KubernetesApiDeprecationService::new(scanner_mock);
 
         // execute:
         let result = service.is_cluster_fully_compatible_with_kubernetes_version(
-            &kubeconfig,
+            &kube_client,
             None,
-            &[],
             KubernetesApiDeprecationServiceGranuality::Default,
         );
 
@@ -635,15 +612,9 @@ mod tests {
     #[test]
     fn test_is_cluster_fully_compatible_with_kubernetes_version_no_deprecation_with_target_version() {
         // setup:
-        let temp_dir = tempdir().expect("Failed to create temp dir");
-        let kubeconfig = temp_dir.path().join("kubeconfig");
-        let _kubeconfig_file = File::create(&kubeconfig).expect("Failed to create kubeconfig file");
-        let _created_temp_dir_guard = scopeguard::guard((), |_| {
-            // delete temp dir after test
-            std::fs::remove_dir_all(temp_dir.path()).expect("Failed to remove temp dir");
-        });
-
-        let deprecations = vec![kubent::Deprecation {
+        let kube_client = kube_client();
+
+        let deprecations = vec![ScannedDeprecation {
             name: Some("name".to_string()),
             namespace: Some("namespace".to_string()),
             kind: Some("kind".to_string()),
@@ -652,16 +623,15 @@ mod tests {
             replace_with: Some("replace_with".to_string()),
             since: Some("1.33".to_string()),
         }];
-        let mut kubent_cmd_mock = Kubent::faux();
-        faux::when!(kubent_cmd_mock.get_deprecations(_, _, _)).then_return(Ok(deprecations.clone()));
+        let mut scanner_mock = KubernetesApiDeprecationScanner::faux();
+        faux::when!(scanner_mock.get_deprecations(_, _)).then_return(Ok(deprecations.clone()));
 
-        let service = KubernetesApiDeprecationService::new(kubent_cmd_mock);
+        let service = KubernetesApiDeprecationService::new(scanner_mock);
 
         // execute:
         let result = service.is_cluster_fully_compatible_with_kubernetes_version(
-            &kubeconfig,
+            &kube_client,
             Some(&VersionsNumberBuilder::new().major(1).minor(32).build()),
-            &[],
             KubernetesApiDeprecationServiceGranuality::Default,
         );
 
@@ -672,15 +642,9 @@ mod tests {
     #[test]
     fn test_is_cluster_fully_compatible_with_kubernetes_version_with_deprecations_on_target_version() {
         // setup:
-        let temp_dir = tempdir().expect("Failed to create temp dir");
-        let kubeconfig = temp_dir.path().join("kubeconfig");
-        let _kubeconfig_file = File::create(&kubeconfig).expect("Failed to create kubeconfig file");
-        let _created_temp_dir_guard = scopeguard::guard((), |_| {
-            // delete temp dir after test
-            std::fs::remove_dir_all(temp_dir.path()).expect("Failed to remove temp dir");
-        });
-
-        let deprecations = vec![kubent::Deprecation {
+        let kube_client = kube_client();
+
+        let deprecations = vec![ScannedDeprecation {
             name: Some("name".to_string()),
             namespace: Some("namespace".to_string()),
             kind: Some("kind".to_string()),
@@ -689,16 +653,15 @@ mod tests {
             replace_with: Some("replace_with".to_string()),
             since: Some("1.32".to_string()),
         }];
-        let mut kubent_cmd_mock = Kubent::faux();
-        faux::when!(kubent_cmd_mock.get_deprecations(_, _, _)).then_return(Ok(deprecations.clone()));
+        let mut scanner_mock = KubernetesApiDeprecationScanner::faux();
+        faux::when!(scanner_mock.get_deprecations(_, _)).then_return(Ok(deprecations.clone()));
 
-        let service = KubernetesApiDeprecationService::new(kubent_cmd_mock);
+        let service = KubernetesApiDeprecationService::new(scanner_mock);
 
         // execute:
         let result = service.is_cluster_fully_compatible_with_kubernetes_version(
-            &kubeconfig,
+            &kube_client,
             Some(&VersionsNumberBuilder::new().major(1).minor(32).build()),
-            &[],
             KubernetesApiDeprecationServiceGranuality::Default,
         );
 
@@ -717,16 +680,16 @@ mod tests {
     }
 
     #[test]
-    fn test_deprecation_from_cmd_deprecation() {
+    fn test_deprecation_from_scanned_deprecation() {
         // setup:
         struct TestCase {
-            cmd_deprecation: kubent::Deprecation,
+            scanned_deprecation: ScannedDeprecation,
             expected: Result<Deprecation, KubernetesDeprecationServiceError>,
         }
 
         let test_cases = vec![
             TestCase {
-                cmd_deprecation: kubent::Deprecation {
+                scanned_deprecation: ScannedDeprecation {
                     name: Some("name".to_string()),
                     namespace: Some("namespace".to_string()),
                     kind: Some("kind".to_string()),
@@ -741,7 +704,7 @@ mod tests {
                 }),
             },
             TestCase {
-                cmd_deprecation: kubent::Deprecation {
+                scanned_deprecation: ScannedDeprecation {
                     name: Some("name".to_string()),
                     namespace: Some("namespace".to_string()),
                     kind: Some("kind".to_string()),
@@ -762,7 +725,7 @@ mod tests {
                 }),
             },
             TestCase {
-                cmd_deprecation: kubent::Deprecation {
+                scanned_deprecation: ScannedDeprecation {
                     name: None,
                     namespace: None,
                     kind: None,
@@ -786,7 +749,7 @@ mod tests {
 
         for test_case in test_cases {
             // execute:
-            let result = Deprecation::try_from(test_case.cmd_deprecation);
+            let result = Deprecation::try_from(test_case.scanned_deprecation);
             // verify:
             assert_eq!(test_case.expected, result);
         }
@@ -1,5 +1,7 @@
 pub mod aws;
 pub mod azure;
+pub mod cost_allocation_service;
 pub mod gcp;
 pub mod kube_client;
+pub mod kubernetes_api_deprecation_scanner;
 pub mod kubernetes_api_deprecation_service;
@@ -6,6 +6,7 @@ use std::hash::{Hash, Hasher};
 use std::path::Path;
 
 use crate::infrastructure::models::build_platform::GitRepositoryExtraFile;
+use git2::{ObjectType, Oid};
 use reqwest::header::{HeaderMap, HeaderValue};
 use uuid::Uuid;
 
@@ -29,6 +30,7 @@ pub fn compute_image_tag<P: AsRef<Path> + Hash, T: AsRef<Path> + Hash>(
     dockerfile_content: &Option<String>,
     docker_extra_files_to_inject: &[GitRepositoryExtraFile],
     environment_variables: &BTreeMap<String, String>,
+    secret_environment_variables: &BTreeMap<String, String>,
     commit_id: &str,
     docker_target_build_stage: &Option<String>,
 ) -> String {
@@ -47,6 +49,11 @@ pub fn compute_image_tag<P: AsRef<Path> + Hash, T: AsRef<Path> + Hash>(
         // we redeploy an app with a env var changed with Buildpacks.
         dockerfile_path.hash(&mut hasher);
         environment_variables.hash(&mut hasher);
+        // secret values never take part in the tag, only their names do.
+        // Rotating a secret does not trigger a new build, a new commit or a forced build is required
+        secret_environment_variables
+            .keys()
+            .for_each(|key| key.hash(&mut hasher));
     }
 
     // Include docker_target_build_stage in the hash calculation only if it's Some
@@ -67,6 +74,51 @@ pub fn compute_image_tag<P: AsRef<Path> + Hash, T: AsRef<Path> + Hash>(
     tag
 }
 
+// Image tag derived from the content of the service paths instead of the commit, used by monorepo builds.
+// Unlike `compute_image_tag`, only sha1 is used so the tag remains the same across engine versions
+pub fn compute_content_addressed_image_tag<T: AsRef<Path>>(
+    paths_content_hash: &str,
+    dockerfile_path: &Option<T>,
+    dockerfile_content: &Option<String>,
+    docker_extra_files_to_inject: &[GitRepositoryExtraFile],
+    environment_variables: &BTreeMap<String, String>,
+    secret_environment_variables: &BTreeMap<String, String>,
+    docker_target_build_stage: &Option<String>,
+) -> String {
+    let sha1 = |content: &str| {
+        Oid::hash_object(ObjectType::Blob, content.as_bytes())
+            .map(|oid| oid.to_string())
+            .unwrap_or_default()
+    };
+
+    let mut hash_input = vec![format!("content {paths_content_hash}")];
+    if let Some(dockerfile_content) = dockerfile_content {
+        hash_input.push(format!("dockerfile_content {}", sha1(dockerfile_content)));
+    }
+    if let Some(dockerfile_path) = dockerfile_path {
+        hash_input.push(format!("dockerfile_path {}", dockerfile_path.as_ref().to_string_lossy()));
+        for (key, value) in environment_variables {
+            hash_input.push(format!("env {key} {}", sha1(value)));
+        }
+        // Secret values never take part in the tag, only their names do
+        for key in secret_environment_variables.keys() {
+            hash_input.push(format!("secret {key}"));
+        }
+    }
+    if let Some(build_stage) = docker_target_build_stage {
+        hash_input.push(format!("target_build_stage {build_stage}"));
+    }
+    for extra_file in docker_extra_files_to_inject {
+        hash_input.push(format!(
+            "extra_file {} {}",
+            extra_file.path.to_string_lossy(),
+            sha1(&extra_file.content)
+        ));
+    }
+
+    format!("tree-{}", sha1(&hash_input.join("\n")))
+}
+
 pub fn to_short_id(id: &Uuid) -> String {
     format!("z{}", id.to_string().split_at(8).0)
 }
@@ -96,7 +148,7 @@ pub fn envs_to_string(env_var: Vec<(&str, &str)>) -> Vec<(String, String)> {
 #[cfg(test)]
 mod tests_utilities {
     use crate::infrastructure::models::build_platform::GitRepositoryExtraFile;
-    use crate::utilities::{base64_replace_comma_to_new_line, compute_image_tag};
+    use crate::utilities::{base64_replace_comma_to_new_line, compute_content_addressed_image_tag, compute_image_tag};
     use base64::Engine;
     use base64::engine::general_purpose;
     use std::collections::BTreeMap;
@@ -110,6 +162,7 @@ mod tests_utilities {
             &None,
             &[],
             &BTreeMap::new(),
+            &BTreeMap::new(),
             "63d8c437337416a7067d3f358197ac47d003fab9",
             &None,
         );
@@ -120,6 +173,7 @@ mod tests_utilities {
             &None,
             &[],
             &BTreeMap::new(),
+            &BTreeMap::new(),
             "63d8c437337416a7067d3f358197ac47d003fab9",
             &None,
         );
@@ -132,6 +186,7 @@ mod tests_utilities {
             &None,
             &[],
             &BTreeMap::new(),
+            &BTreeMap::new(),
             "63d8c437337416a7067d3f358197ac47d003fab9",
             &None,
         );
@@ -144,6 +199,7 @@ mod tests_utilities {
             &None,
             &[],
             &BTreeMap::new(),
+            &BTreeMap::new(),
             "63d8c437337416a7067d3f358197ac47d003fab9",
             &None,
         );
@@ -156,6 +212,7 @@ mod tests_utilities {
             &None,
             &[],
             &BTreeMap::new(),
+            &BTreeMap::new(),
             "63d8c437337416a7067d3f358197ac47d003fab9",
             &None,
         );
@@ -169,6 +226,7 @@ mod tests_utilities {
             &None,
             &[],
             &env_vars_5,
+            &BTreeMap::new(),
             "63d8c437337416a7067d3f358197ac47d003fab9",
             &None,
         );
@@ -181,6 +239,7 @@ mod tests_utilities {
             &Some("FROM my-custom-dockerfile".to_string()),
             &[],
             &env_vars_5,
+            &BTreeMap::new(),
             "63d8c437337416a7067d3f358197ac47d003fab9",
             &None,
         );
@@ -195,6 +254,7 @@ mod tests_utilities {
                 content: "toto".to_string(),
             }],
             &env_vars_5,
+            &BTreeMap::new(),
             "63d8c437337416a7067d3f358197ac47d003fab9",
             &None,
         );
@@ -209,6 +269,7 @@ mod tests_utilities {
                 content: "tata".to_string(),
             }],
             &env_vars_5,
+            &BTreeMap::new(),
             "63d8c437337416a7067d3f358197ac47d003fab9",
             &None,
         );
@@ -221,6 +282,7 @@ mod tests_utilities {
             &Some("FROM my-custom-dockerfile".to_string()),
             &[],
             &env_vars_5,
+            &BTreeMap::new(),
             "63d8c437337416a7067d3f358197ac47d003fab9",
             &Some("stage1".to_string()),
         );
@@ -231,11 +293,130 @@ mod tests_utilities {
             &Some("FROM my-custom-dockerfile".to_string()),
             &[],
             &env_vars_5,
+            &BTreeMap::new(),
             "63d8c437337416a7067d3f358197ac47d003fab9",
             &Some("stage2".to_string()),
         );
 
         assert_ne!(image_tag_8, image_tag_9);
+
+        // Test that secret values are not part of the hash, only their names are
+        let mut secret_env_vars = BTreeMap::new();
+        secret_env_vars.insert("NPM_TOKEN".to_string(), "secret_v1".to_string());
+        let image_tag_10 = compute_image_tag(
+            "/".to_string(),
+            &Some("Dockerfile".to_string()),
+            &None,
+            &[],
+            &env_vars_5,
+            &secret_env_vars,
+            "63d8c437337416a7067d3f358197ac47d003fab9",
+            &None,
+        );
+        let image_tag_10_2 = compute_image_tag(
+            "/".to_string(),
+            &Some("Dockerfile".to_string()),
+            &None,
+            &[],
+            &env_vars_5,
+            &secret_env_vars,
+            "63d8c437337416a7067d3f358197ac47d003fab9",
+            &None,
+        );
+        assert_eq!(image_tag_10, image_tag_10_2);
+
+        secret_env_vars.insert("NPM_TOKEN".to_string(), "secret_v2".to_string());
+        let image_tag_11 = compute_image_tag(
+            "/".to_string(),
+            &Some("Dockerfile".to_string()),
+            &None,
+            &[],
+            &env_vars_5,
+            &secret_env_vars,
+            "63d8c437337416a7067d3f358197ac47d003fab9",
+            &None,
+        );
+        assert_eq!(image_tag_10, image_tag_11);
+
+        secret_env_vars.insert("GITHUB_TOKEN".to_string(), "secret_v1".to_string());
+        let image_tag_12 = compute_image_tag(
+            "/".to_string(),
+            &Some("Dockerfile".to_string()),
+            &None,
+            &[],
+            &env_vars_5,
+            &secret_env_vars,
+            "63d8c437337416a7067d3f358197ac47d003fab9",
+            &None,
+        );
+        assert_ne!(image_tag_11, image_tag_12);
+    }
+
+    #[test]
+    fn test_get_content_addressed_image_tag() {
+        // The tag must never change across engine versions, otherwise every monorepo service would be rebuilt
+        let image_tag = compute_content_addressed_image_tag(
+            "4b825dc642cb6eb9a060e54bf8d69288fbee4904",
+            &Some("Dockerfile"),
+            &None,
+            &[],
+            &BTreeMap::new(),
+            &BTreeMap::new(),
+            &None,
+        );
+        assert_eq!(image_tag, "tree-f954b39e2bebf69ecccb94f136a8690a6aef8a14");
+
+        let mut env_vars = BTreeMap::new();
+        env_vars.insert("toto".to_string(), "key".to_string());
+        let image_tag_2 = compute_content_addressed_image_tag(
+            "4b825dc642cb6eb9a060e54bf8d69288fbee4904",
+            &Some("Dockerfile"),
+            &None,
+            &[],
+            &env_vars,
+            &BTreeMap::new(),
+            &None,
+        );
+        assert_ne!(image_tag, image_tag_2);
+
+        let mut secret_env_vars = BTreeMap::new();
+        secret_env_vars.insert("NPM_TOKEN".to_string(), "secret_v1".to_string());
+        let image_tag_3 = compute_content_addressed_image_tag(
+            "4b825dc642cb6eb9a060e54bf8d69288fbee4904",
+            &Some("Dockerfile"),
+            &None,
+            &[],
+            &env_vars,
+            &secret_env_vars,
+            &None,
+        );
+        assert_ne!(image_tag_2, image_tag_3);
+
+        // A rotated secret does not change the tag
+        secret_env_vars.insert("NPM_TOKEN".to_string(), "secret_v2".to_string());
+        assert_eq!(
+            image_tag_3,
+            compute_content_addressed_image_tag(
+                "4b825dc642cb6eb9a060e54bf8d69288fbee4904",
+                &Some("Dockerfile"),
+                &None,
+                &[],
+                &env_vars,
+                &secret_env_vars,
+                &None,
+            )
+        );
+
+        let image_tag_4 = compute_content_addressed_image_tag(
+            "aa825dc642cb6eb9a060e54bf8d69288fbee4904",
+            &Some("Dockerfile"),
+            &None,
+            &[],
+            &env_vars,
+            &secret_env_vars,
+            &None,
+        );
+        assert_ne!(image_tag_3, image_tag_4);
     }
 
     #[test]
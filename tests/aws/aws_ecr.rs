@@ -17,6 +17,7 @@ use uuid::Uuid;
 #[test]
 fn create_ecr_repository_with_tags() {
     use qovery_engine::infrastructure::models::container_registry::RegistryTags;
+    use qovery_engine::infrastructure::models::cost_allocation::CostAllocationTags;
 
     let test_name = function_name!();
     engine_run_test(|| {
@@ -55,6 +56,7 @@ fn create_ecr_repository_with_tags() {
                 environment_id: Some(Uuid::new_v4().to_string()),
                 project_id: Some(Uuid::new_v4().to_string()),
                 resource_ttl: Some(Duration::from_secs(AWS_QUICK_RESOURCE_TTL_IN_SECONDS as u64)),
+                cost_allocation_tags: CostAllocationTags::default(),
             },
         );
         assert!(repo_creation.is_ok());
@@ -1622,6 +1622,7 @@ fn deploy_container_with_no_router_and_affinitiy_on_aws_eks() {
                 url: Url::parse("https://public.ecr.aws").unwrap(),
             },
             image: "r3m4q3r9/pub-mirror-debian".to_string(),
+            image_artifact: None,
             tag: "11.6-ci".to_string(),
             command_args: vec![
                 "/bin/sh".to_string(),
@@ -1689,6 +1690,8 @@ fn deploy_container_with_no_router_and_affinitiy_on_aws_eks() {
                 failure_threshold: 5,
             }),
             advanced_settings: Default::default(),
+            event_driven_autoscaling: None,
+            additional_containers: vec![],
             annotations_group_ids: btreeset! {},
             labels_group_ids: btreeset! {},
         }];
@@ -1851,6 +1854,7 @@ fn deploy_container_with_no_router_on_aws_eks() {
                 url: Url::parse("https://public.ecr.aws").unwrap(),
             },
             image: "r3m4q3r9/pub-mirror-debian".to_string(),
+            image_artifact: None,
             tag: "11.6-ci".to_string(),
             command_args: vec![
                 "/bin/sh".to_string(),
@@ -1918,6 +1922,8 @@ fn deploy_container_with_no_router_on_aws_eks() {
                 failure_threshold: 5,
             }),
             advanced_settings: Default::default(),
+            event_driven_autoscaling: None,
+            additional_containers: vec![],
             annotations_group_ids: btreeset! {},
             labels_group_ids: btreeset! {},
         }];
@@ -1990,6 +1996,7 @@ fn deploy_container_with_storages_on_aws_eks() {
                 url: Url::parse("https://public.ecr.aws").unwrap(),
             },
             image: "r3m4q3r9/pub-mirror-debian".to_string(),
+            image_artifact: None,
             tag: "11.6-ci".to_string(),
             command_args: vec![
                 "/bin/sh".to_string(),
@@ -2063,6 +2070,8 @@ fn deploy_container_with_storages_on_aws_eks() {
             environment_vars_with_infos: BTreeMap::default(),
             mounted_files: vec![],
             advanced_settings: Default::default(),
+            event_driven_autoscaling: None,
+            additional_containers: vec![],
             annotations_group_ids: btreeset! {},
             labels_group_ids: btreeset! {},
         }];
@@ -2135,6 +2144,7 @@ fn deploy_container_on_aws_eks_with_mounted_files_as_volume() {
                 url: Url::parse("https://public.ecr.aws").unwrap(),
             },
             image: "r3m4q3r9/pub-mirror-debian".to_string(),
+            image_artifact: None,
             tag: "11.6-ci".to_string(),
             command_args: vec![
                 "/bin/sh".to_string(),
@@ -2205,6 +2215,8 @@ fn deploy_container_on_aws_eks_with_mounted_files_as_volume() {
             environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{value: general_purpose::STANDARD.encode("my_value"), is_secret: false} },
             mounted_files: vec![mounted_file.clone()],
             advanced_settings: Default::default(),
+            event_driven_autoscaling: None,
+            additional_containers: vec![],
             annotations_group_ids: btreeset! {},
             labels_group_ids: btreeset! {},
         }];
@@ -2310,6 +2322,7 @@ fn deploy_container_with_router_on_aws_eks() {
                 credentials: None,
             },
             image: "r3m4q3r9/pub-mirror-httpd".to_string(),
+            image_artifact: None,
             tag: "2.4.56-alpine3.17".to_string(),
             command_args: vec![],
             entrypoint: None,
@@ -2369,6 +2382,8 @@ fn deploy_container_with_router_on_aws_eks() {
             environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{value: general_purpose::STANDARD.encode("my_value"), is_secret:false} },
             mounted_files: vec![],
             advanced_settings: Default::default(),
+            event_driven_autoscaling: None,
+            additional_containers: vec![],
             annotations_group_ids: btreeset! { annotations_group_id },
             labels_group_ids: btreeset! { labels_group_id },
         }];
@@ -2494,10 +2509,13 @@ fn a_working_job() -> Job {
                 url: Url::parse("https://public.ecr.aws").unwrap(),
             },
             image: "r3m4q3r9/pub-mirror-debian".to_string(),
+            artifact: None,
             tag: "11.6-ci".to_string(),
         },
         max_nb_restart: 2,
         max_duration_in_sec: 300,
+        parallelism: 1,
+        completions: 1,
         default_port: Some(8080),
         //command_args: vec![],
         command_args: vec![
@@ -2781,6 +2799,8 @@ CMD ["/bin/sh", "-c", "echo hello"]
             },
             max_nb_restart: 2,
             max_duration_in_sec: 300,
+            parallelism: 1,
+            completions: 1,
             default_port: Some(8080),
             //command_args: vec![],
             command_args: vec![
@@ -2890,10 +2910,13 @@ fn deploy_cronjob_on_aws_eks() {
                     url: Url::parse("https://public.ecr.aws").unwrap(),
                 },
                 image: "r3m4q3r9/pub-mirror-debian".to_string(),
+                artifact: None,
                 tag: "11.6-ci".to_string(),
             },
             max_nb_restart: 1,
             max_duration_in_sec: 30,
+            parallelism: 1,
+            completions: 1,
             default_port: Some(8080),
             command_args: vec![
                 "/bin/sh".to_string(),
@@ -3020,10 +3043,13 @@ fn deploy_cronjob_force_trigger_on_aws_eks() {
                     url: Url::parse("https://public.ecr.aws").unwrap(),
                 },
                 image: "r3m4q3r9/pub-mirror-debian".to_string(),
+                artifact: None,
                 tag: "11.6-ci".to_string(),
             },
             max_nb_restart: 1,
             max_duration_in_sec: 30,
+            parallelism: 1,
+            completions: 1,
             default_port: Some(8080),
             command_args: vec![
                 "/bin/sh".to_string(),
@@ -3164,6 +3190,8 @@ fn build_and_deploy_job_on_aws_eks() {
             },
             max_nb_restart: 2,
             max_duration_in_sec: 300,
+            parallelism: 1,
+            completions: 1,
             default_port: Some(8080),
             //command_args: vec![],
             command_args: vec![
@@ -3289,6 +3317,7 @@ fn test_restart_deployment() {
                 url: Url::parse("https://public.ecr.aws").unwrap(),
             },
             image: "r3m4q3r9/pub-mirror-debian".to_string(),
+            image_artifact: None,
             tag: "11.6-ci".to_string(),
             command_args: vec![
                 "/bin/sh".to_string(),
@@ -3356,6 +3385,8 @@ fn test_restart_deployment() {
             mounted_files: vec![],
             environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{value: general_purpose::STANDARD.encode("my_value"), is_secret: false} },
             advanced_settings: Default::default(),
+            event_driven_autoscaling: None,
+            additional_containers: vec![],
             annotations_group_ids: btreeset! {},
             labels_group_ids: btreeset! {},
         }];
@@ -3426,6 +3457,7 @@ fn test_restart_statefulset() {
                 url: Url::parse("https://public.ecr.aws").unwrap(),
             },
             image: "r3m4q3r9/pub-mirror-debian".to_string(),
+            image_artifact: None,
             tag: "11.6-ci".to_string(),
             command_args: vec![
                 "/bin/sh".to_string(),
@@ -3501,6 +3533,8 @@ fn test_restart_statefulset() {
             }),
             environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{value: general_purpose::STANDARD.encode("my_value"), is_secret: false} },
             advanced_settings: Default::default(),
+            event_driven_autoscaling: None,
+            additional_containers: vec![],
             annotations_group_ids: btreeset! {},
             labels_group_ids: btreeset! {},
         }];
@@ -3891,6 +3925,8 @@ fn build_and_deploy_job_on_aws_eks_with_mounted_files_as_volume() {
             },
             max_nb_restart: 2,
             max_duration_in_sec: 300,
+            parallelism: 1,
+            completions: 1,
             default_port: Some(8080),
             //command_args: vec![],
             command_args: vec![
@@ -4184,6 +4220,7 @@ fn deploy_container_with_udp_tcp_public_ports() {
                 url: Url::parse("https://public.ecr.aws").unwrap(),
             },
             image: "r3m4q3r9/pub-mirror-debian".to_string(),
+            image_artifact: None,
             tag: "11.6-ci".to_string(),
             command_args: vec![
                 "/bin/sh".to_string(),
@@ -4266,6 +4303,8 @@ fn deploy_container_with_udp_tcp_public_ports() {
                 failure_threshold: 5,
             }),
             advanced_settings: Default::default(),
+            event_driven_autoscaling: None,
+            additional_containers: vec![],
             annotations_group_ids: btreeset! {},
             labels_group_ids: btreeset! {},
         }];
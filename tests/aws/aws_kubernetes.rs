@@ -277,6 +277,7 @@ fn create_and_destroy_eks_cluster_karpenter_without_nat_gw_in_eu_west_3() {
                 }),
             }),
         },
+        user_node_pools: vec![],
     };
     create_and_destroy_eks_cluster(
         region,
@@ -332,6 +333,7 @@ fn create_pause_and_destroy_eks_cluster_arm_karpenter_with_nat_gw_in_eu_west_3()
                 }),
             }),
         },
+        user_node_pools: vec![],
     };
     create_and_destroy_eks_cluster(
         region,
@@ -385,6 +387,7 @@ fn create_upgrade_and_destroy_eks_cluster_karpenter_with_nat_gw_in_eu_west_3() {
             },
             default_override: None,
         },
+        user_node_pools: vec![],
     };
     create_and_destroy_eks_cluster(
         region,
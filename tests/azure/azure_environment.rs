@@ -640,6 +640,7 @@ fn azure_aks_deploy_container_with_router() {
                 credentials: None,
             },
             image: "r3m4q3r9/pub-mirror-httpd".to_string(),
+            image_artifact: None,
             tag: "2.4.56-alpine3.17".to_string(),
             command_args: vec![],
             entrypoint: None,
@@ -699,6 +700,8 @@ fn azure_aks_deploy_container_with_router() {
             environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{value: general_purpose::STANDARD.encode("my_value"), is_secret:false} },
             mounted_files: vec![],
             advanced_settings: Default::default(),
+            event_driven_autoscaling: None,
+            additional_containers: vec![],
             annotations_group_ids: btreeset! { annotations_group_id },
             labels_group_ids: btreeset! { labels_group_id },
         }];
@@ -818,6 +821,7 @@ fn azure_aks_deploy_container_with_storages() {
                 url: Url::parse("https://public.ecr.aws").unwrap(),
             },
             image: "r3m4q3r9/pub-mirror-debian".to_string(),
+            image_artifact: None,
             tag: "11.6-ci".to_string(),
             command_args: vec![
                 "/bin/sh".to_string(),
@@ -891,6 +895,8 @@ fn azure_aks_deploy_container_with_storages() {
             environment_vars_with_infos: BTreeMap::default(),
             mounted_files: vec![],
             advanced_settings: Default::default(),
+            event_driven_autoscaling: None,
+            additional_containers: vec![],
             annotations_group_ids: btreeset! {},
             labels_group_ids: btreeset! {},
         }];
@@ -975,6 +981,7 @@ fn azure_aks_deploy_container_with_mounted_files_as_volume() {
                 url: Url::parse("https://public.ecr.aws").unwrap(),
             },
             image: "r3m4q3r9/pub-mirror-debian".to_string(),
+            image_artifact: None,
             tag: "11.6-ci".to_string(),
             command_args: vec![
                 "/bin/sh".to_string(),
@@ -1045,6 +1052,8 @@ fn azure_aks_deploy_container_with_mounted_files_as_volume() {
             environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{value: general_purpose::STANDARD.encode("my_value"), is_secret: false} },
             mounted_files: vec![mounted_file.clone()],
             advanced_settings: Default::default(),
+            event_driven_autoscaling: None,
+            additional_containers: vec![],
             annotations_group_ids: btreeset! {},
             labels_group_ids: btreeset! {},
         }];
@@ -1157,6 +1166,7 @@ fn azure_aks_deploy_container_without_router() {
                 credentials: None,
             },
             image: "r3m4q3r9/pub-mirror-httpd".to_string(),
+            image_artifact: None,
             tag: "2.4.56-alpine3.17".to_string(),
             command_args: vec![],
             entrypoint: None,
@@ -1216,6 +1226,8 @@ fn azure_aks_deploy_container_without_router() {
             environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{value: general_purpose::STANDARD.encode("my_value"), is_secret:false} },
             mounted_files: vec![],
             advanced_settings: Default::default(),
+            event_driven_autoscaling: None,
+            additional_containers: vec![],
             annotations_group_ids: btreeset! { annotations_group_id },
             labels_group_ids: btreeset! { labels_group_id },
         }];
@@ -1325,10 +1337,13 @@ fn azure_aks_deploy_job() {
                     url: Url::parse("https://public.ecr.aws").unwrap(),
                 },
                 image: "r3m4q3r9/pub-mirror-debian".to_string(),
+                artifact: None,
                 tag: "11.6-ci".to_string(),
             },
             max_nb_restart: 2,
             max_duration_in_sec: 300,
+            parallelism: 1,
+            completions: 1,
             default_port: Some(8080),
             //command_args: vec![],
             command_args: vec![
@@ -1464,6 +1479,8 @@ CMD ["/bin/sh", "-c", "echo hello"]
             },
             max_nb_restart: 2,
             max_duration_in_sec: 300,
+            parallelism: 1,
+            completions: 1,
             default_port: Some(8080),
             //command_args: vec![],
             command_args: vec![
@@ -1585,10 +1602,13 @@ fn azure_aks_deploy_cronjob() {
                     url: Url::parse("https://public.ecr.aws").unwrap(),
                 },
                 image: "r3m4q3r9/pub-mirror-debian".to_string(),
+                artifact: None,
                 tag: "11.6-ci".to_string(),
             },
             max_nb_restart: 1,
             max_duration_in_sec: 30,
+            parallelism: 1,
+            completions: 1,
             default_port: Some(8080),
             command_args: vec![
                 "/bin/sh".to_string(),
@@ -1727,10 +1747,13 @@ fn azure_aks_deploy_cronjob_force_trigger() {
                     url: Url::parse("https://public.ecr.aws").unwrap(),
                 },
                 image: "r3m4q3r9/pub-mirror-debian".to_string(),
+                artifact: None,
                 tag: "11.6-ci".to_string(),
             },
             max_nb_restart: 1,
             max_duration_in_sec: 30,
+            parallelism: 1,
+            completions: 1,
             default_port: Some(8080),
             command_args: vec![
                 "/bin/sh".to_string(),
@@ -1883,6 +1906,8 @@ fn azure_aks_build_and_deploy_job() {
             },
             max_nb_restart: 2,
             max_duration_in_sec: 300,
+            parallelism: 1,
+            completions: 1,
             default_port: Some(8080),
             //command_args: vec![],
             command_args: vec![
@@ -14,6 +14,7 @@ use uuid::Uuid;
 #[test]
 fn test_github_cr() {
     use qovery_engine::infrastructure::models::container_registry::RegistryTags;
+    use qovery_engine::infrastructure::models::cost_allocation::CostAllocationTags;
 
     let test_name = function_name!();
     engine_run_test(|| {
@@ -46,6 +47,7 @@ fn test_github_cr() {
                 environment_id: Some(Uuid::new_v4().to_string()),
                 project_id: Some(Uuid::new_v4().to_string()),
                 resource_ttl: None,
+                cost_allocation_tags: CostAllocationTags::default(),
             },
         );
         assert!(repo_creation.is_ok());
@@ -601,6 +601,7 @@ fn gcp_gke_deploy_container_with_router() {
                 credentials: None,
             },
             image: "r3m4q3r9/pub-mirror-httpd".to_string(),
+            image_artifact: None,
             tag: "2.4.56-alpine3.17".to_string(),
             command_args: vec![],
             entrypoint: None,
@@ -660,6 +661,8 @@ fn gcp_gke_deploy_container_with_router() {
             environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{value: general_purpose::STANDARD.encode("my_value"), is_secret:false} },
             mounted_files: vec![],
             advanced_settings: Default::default(),
+            event_driven_autoscaling: None,
+            additional_containers: vec![],
             annotations_group_ids: btreeset! { annotations_group_id },
             labels_group_ids: btreeset! { labels_group_id },
         }];
@@ -3,6 +3,7 @@ use crate::helpers::utilities::{FuncTestsSecrets, context_for_cluster, logger, m
 use base64::Engine;
 use base64::engine::general_purpose;
 use chrono::Utc;
+use qovery_engine::cmd::docker::BuildProvenance;
 use qovery_engine::environment::models::abort::AbortStatus;
 use qovery_engine::environment::models::application::Application;
 use qovery_engine::environment::models::aws::{AwsAppExtraSettings, AwsRouterExtraSettings, AwsStorageType};
@@ -33,7 +34,7 @@ use qovery_engine::io_models::application::{ApplicationAdvancedSettings, Port, P
 use qovery_engine::io_models::container::{ContainerAdvancedSettings, Registry};
 use qovery_engine::io_models::database::{DatabaseMode, DatabaseOptions, DiskIOPS};
 use qovery_engine::io_models::engine_location::EngineLocation;
-use qovery_engine::io_models::job::{JobAdvancedSettings, JobSchedule};
+use qovery_engine::io_models::job::{JobAdvancedSettings, JobCompletionMode, JobSchedule};
 use qovery_engine::io_models::labels_group::{Label, LabelsGroup};
 use qovery_engine::io_models::models::{
     CpuArchitecture, CustomDomain, EnvironmentVariable, KubernetesCpuResourceUnit, KubernetesMemoryResourceUnit,
@@ -256,6 +257,9 @@ pub fn test_application(test_kube: &dyn Kubernetes, domain: &str) -> Application
                 root_path: PathBuf::from("my_root_path"),
                 extra_files_to_inject: vec![],
                 docker_target_build_stage: None,
+                watched_paths: None,
+                paths_content_hash: None,
+                commit_timestamp: None,
             },
             image: Image {
                 service_id: "my_application_id".to_string(),
@@ -273,6 +277,7 @@ pub fn test_application(test_kube: &dyn Kubernetes, domain: &str) -> Application
                 shared_image_feature_enabled: false,
             },
             environment_variables: BTreeMap::new(),
+            secret_environment_variables: BTreeMap::new(),
             disable_cache: false,
             timeout: Duration::from_secs(42),
             architectures: test_kube.cpu_architectures(),
@@ -280,6 +285,8 @@ pub fn test_application(test_kube: &dyn Kubernetes, domain: &str) -> Application
             max_ram_in_gib: 4,
             ephemeral_storage_in_gib: None,
             registries: vec![],
+            cache_backend: None,
+            provenance: BuildProvenance::default(),
         },
         vec![],
         None,
@@ -321,6 +328,10 @@ pub fn test_application(test_kube: &dyn Kubernetes, domain: &str) -> Application
             build_cpu_max_in_milli: 2000,
             build_ram_max_in_gib: 4,
             build_ephemeral_storage_in_gib: None,
+            build_monorepo_mode_enabled: false,
+            build_monorepo_mode_watched_paths: vec![],
+            build_cache_backend: None,
+            build_provenance: BuildProvenance::default(),
             network_ingress_proxy_body_size_mb: 3,
             network_ingress_force_ssl_redirect: true,
             network_ingress_cors_enable: true,
@@ -354,8 +365,10 @@ pub fn test_application(test_kube: &dyn Kubernetes, domain: &str) -> Application
             hpa_cpu_average_utilization_percent: 31,
             hpa_memory_average_utilization_percent: None,
             deployment_affinity_node_required: BTreeMap::new(),
+            deployment_node_pool: None,
             deployment_antiaffinity_pod: PodAntiAffinity::Preferred,
         },
+        None,
         AwsAppExtraSettings {},
         |transmitter| test_kube.context().get_event_details(transmitter),
         get_annotations_group_for_app(),
@@ -385,6 +398,7 @@ pub fn test_container(test_kube: &dyn Kubernetes) -> Container<AWSType> {
             },
             image: "my_image".to_string(),
             tag: "my_tag".to_string(),
+            artifact: None,
             registry_mirroring_mode: RegistryMirroringMode::Service,
         },
         vec![test_cmd_arg()],
@@ -427,6 +441,7 @@ pub fn test_container(test_kube: &dyn Kubernetes) -> Container<AWSType> {
             deployment_update_strategy_rolling_update_max_unavailable_percent: 25,
             deployment_update_strategy_rolling_update_max_surge_percent: 25,
             deployment_affinity_node_required: BTreeMap::new(),
+            deployment_node_pool: None,
             deployment_antiaffinity_pod: PodAntiAffinity::Preferred,
             deployment_lifecycle_post_start_exec_command: vec![],
             deployment_lifecycle_pre_stop_exec_command: vec![],
@@ -466,6 +481,7 @@ pub fn test_container(test_kube: &dyn Kubernetes) -> Container<AWSType> {
             security_read_only_root_filesystem: false,
             security_automount_service_account_token: false,
         },
+        None,
         AwsAppExtraSettings {},
         |transmitter| test_kube.context().get_event_details(transmitter),
         get_annotations_group_for_app(),
@@ -638,6 +654,7 @@ fn test_job(test_kube: &dyn Kubernetes) -> Job<AWSType> {
                 },
                 image: "my_image".to_string(),
                 tag: "my_tag".to_string(),
+                artifact: None,
                 registry_mirroring_mode: RegistryMirroringMode::Service,
             }),
         },
@@ -647,6 +664,8 @@ fn test_job(test_kube: &dyn Kubernetes) -> Job<AWSType> {
         },
         1,
         Duration::from_secs(2),
+        1,
+        1,
         Some(3),
         vec![test_cmd_arg()],
         None,
@@ -659,8 +678,15 @@ fn test_job(test_kube: &dyn Kubernetes) -> Job<AWSType> {
         btreeset![test_mounted_file()],
         JobAdvancedSettings {
             job_delete_ttl_seconds_after_finished: Some(8),
+            job_completion_mode: JobCompletionMode::NonIndexed,
+            job_backoff_limit_per_index: None,
+            job_max_failed_indexes: None,
+            job_pod_failure_policy_ignore_exit_codes: vec![],
+            job_pod_failure_policy_fail_job_exit_codes: vec![],
+            cronjob_suspend: false,
             deployment_termination_grace_period_seconds: 60,
             deployment_affinity_node_required: BTreeMap::new(),
+            deployment_node_pool: None,
             cronjob_concurrency_policy: "my_cronjob_concurrency_policy".to_string(),
             cronjob_failed_jobs_history_limit: 9,
             cronjob_success_jobs_history_limit: 10,
@@ -668,6 +694,10 @@ fn test_job(test_kube: &dyn Kubernetes) -> Job<AWSType> {
             build_cpu_max_in_milli: 2000,
             build_ram_max_in_gib: 4,
             build_ephemeral_storage_in_gib: None,
+            build_monorepo_mode_enabled: false,
+            build_monorepo_mode_watched_paths: vec![],
+            build_cache_backend: None,
+            build_provenance: BuildProvenance::default(),
             security_service_account_name: "".to_string(),
             security_read_only_root_filesystem: false,
             security_automount_service_account_token: false,
@@ -295,6 +295,8 @@ pub fn environment_3_apps_3_databases(
                     failure_threshold: 5,
                 }),
                 container_registries: Vec::new(),
+                event_driven_autoscaling: None,
+                additional_containers: vec![],
                 annotations_group_ids: BTreeSet::new(),
                 labels_group_ids: BTreeSet::new(),
                 should_delete_shared_registry: false,
@@ -364,6 +366,8 @@ pub fn environment_3_apps_3_databases(
                 }),
                 public_domain: format!("{app_id}.example.com"),
                 container_registries: Vec::new(),
+                event_driven_autoscaling: None,
+                additional_containers: vec![],
                 annotations_group_ids: BTreeSet::new(),
                 labels_group_ids: BTreeSet::new(),
                 should_delete_shared_registry: false,
@@ -435,6 +439,8 @@ pub fn environment_3_apps_3_databases(
                     failure_threshold: 5,
                 }),
                 container_registries: Vec::new(),
+                event_driven_autoscaling: None,
+                additional_containers: vec![],
                 annotations_group_ids: BTreeSet::new(),
                 labels_group_ids: BTreeSet::new(),
                 should_delete_shared_registry: false,
@@ -538,6 +544,8 @@ pub fn environment_3_apps_3_databases(
         terraform_services: vec![],
         annotations_groups: btreemap! {},
         labels_groups: btreemap! {},
+        lifecycle: None,
+        admission_policies: None,
     }
 }
 
@@ -584,6 +592,8 @@ pub fn database_test_environment(context: &Context) -> EnvironmentRequest {
             liveness_probe: None,
             public_domain: format!("{}.example.com", Uuid::new_v4()),
             container_registries: Vec::new(),
+            event_driven_autoscaling: None,
+            additional_containers: vec![],
             annotations_group_ids: BTreeSet::new(),
             labels_group_ids: BTreeSet::new(),
             should_delete_shared_registry: false,
@@ -598,6 +608,8 @@ pub fn database_test_environment(context: &Context) -> EnvironmentRequest {
         terraform_services: vec![],
         annotations_groups: btreemap! {},
         labels_groups: btreemap! {},
+        lifecycle: None,
+        admission_policies: None,
     }
 }
 
@@ -644,6 +656,8 @@ pub fn database_test_environment_on_upgrade(context: &Context) -> EnvironmentReq
             readiness_probe: None,
             liveness_probe: None,
             container_registries: Vec::new(),
+            event_driven_autoscaling: None,
+            additional_containers: vec![],
             annotations_group_ids: BTreeSet::new(),
             labels_group_ids: BTreeSet::new(),
             should_delete_shared_registry: false,
@@ -658,6 +672,8 @@ pub fn database_test_environment_on_upgrade(context: &Context) -> EnvironmentReq
         helms: vec![],
         annotations_groups: btreemap! {},
         labels_groups: btreemap! {},
+        lifecycle: None,
+        admission_policies: None,
     }
 }
 
@@ -106,6 +106,8 @@ pub fn working_environment(
             advanced_settings: settings,
             public_domain: format!("{}.{}", application_id.to_uuid(), test_domain),
             container_registries: Vec::new(),
+            event_driven_autoscaling: None,
+            additional_containers: vec![],
             annotations_group_ids: BTreeSet::new(),
             labels_group_ids: btreeset! {},
             should_delete_shared_registry: false,
@@ -120,6 +122,8 @@ pub fn working_environment(
         terraform_services: vec![],
         annotations_groups: btreemap! {},
         labels_groups: btreemap! {},
+        lifecycle: None,
+        admission_policies: None,
     };
 
     if with_router {
@@ -348,6 +352,8 @@ pub fn environment_2_app_2_routers_1_psql(
                 }),
                 public_domain: format!("{application_id1}.{test_domain}"),
                 container_registries: Vec::new(),
+                event_driven_autoscaling: None,
+                additional_containers: vec![],
                 annotations_group_ids: btreeset! {},
                 labels_group_ids: btreeset! {},
                 should_delete_shared_registry: false,
@@ -417,6 +423,8 @@ pub fn environment_2_app_2_routers_1_psql(
                     failure_threshold: 5,
                 }),
                 container_registries: Vec::new(),
+                event_driven_autoscaling: None,
+                additional_containers: vec![],
                 annotations_group_ids: BTreeSet::new(),
                 labels_group_ids: btreeset! {},
                 should_delete_shared_registry: false,
@@ -460,6 +468,8 @@ pub fn environment_2_app_2_routers_1_psql(
         terraform_services: vec![],
         annotations_groups: btreemap! {},
         labels_groups: btreemap! {},
+        lifecycle: None,
+        admission_policies: None,
     }
 }
 
@@ -555,6 +565,8 @@ pub fn echo_app_environment(context: &Context, test_domain: &str) -> Environment
                 failure_threshold: 5,
             }),
             container_registries: Vec::new(),
+            event_driven_autoscaling: None,
+            additional_containers: vec![],
             annotations_group_ids: BTreeSet::new(),
             labels_group_ids: btreeset! {},
             should_delete_shared_registry: false,
@@ -581,6 +593,8 @@ pub fn echo_app_environment(context: &Context, test_domain: &str) -> Environment
         terraform_services: vec![],
         annotations_groups: btreemap! {},
         labels_groups: btreemap! {},
+        lifecycle: None,
+        admission_policies: None,
     }
 }
 
@@ -671,6 +685,8 @@ pub fn environment_only_http_server(
                 failure_threshold: 5,
             }),
             container_registries: Vec::new(),
+            event_driven_autoscaling: None,
+            additional_containers: vec![],
             annotations_group_ids: BTreeSet::new(),
             labels_group_ids: btreeset! {},
             should_delete_shared_registry: false,
@@ -685,6 +701,8 @@ pub fn environment_only_http_server(
         terraform_services: vec![],
         annotations_groups: btreemap! {},
         labels_groups: btreemap! {},
+        lifecycle: None,
+        admission_policies: None,
     };
 
     if with_router {
@@ -48,6 +48,7 @@ use qovery_engine::environment::report::obfuscation_service::{ObfuscationService
 use qovery_engine::errors::CommandError;
 use qovery_engine::events::{EnvironmentStep, EventDetails, Stage, Transmitter};
 use qovery_engine::infrastructure::infrastructure_context::InfrastructureContext;
+use qovery_engine::infrastructure::models::build_platform::BuildCacheConfig;
 use qovery_engine::infrastructure::models::build_platform::local_docker::LocalDocker;
 use qovery_engine::infrastructure::models::cloud_provider::Kind;
 use qovery_engine::infrastructure::models::cloud_provider::aws::database_instance_type::AwsDatabaseInstanceType;
@@ -560,6 +561,7 @@ pub fn build_platform_local_docker(context: &Context) -> LocalDocker {
         Uuid::new_v4(),
         "qovery-local-docker",
         Box::<StdMetricsRegistry>::default(),
+        BuildCacheConfig::default(),
     )
     .unwrap()
 }
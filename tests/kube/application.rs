@@ -95,6 +95,7 @@ fn should_increase_app_storage_size() {
             resized_app.readiness_probe.clone().map(|p| p.to_domain()),
             resized_app.liveness_probe.clone().map(|p| p.to_domain()),
             resized_app.advanced_settings.clone(),
+            None,
             AwsAppExtraSettings {},
             |transmitter| infra_ctx.context().get_event_details(transmitter),
             vec![],
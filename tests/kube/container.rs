@@ -82,6 +82,7 @@ fn should_increase_container_storage_size() {
                 registry: resized_container.registry.clone(),
                 image: resized_container.image.clone(),
                 tag: resized_container.tag.clone(),
+                artifact: resized_container.image_artifact.clone(),
                 registry_mirroring_mode: RegistryMirroringMode::Service,
             },
             resized_container.command_args.clone(),
@@ -100,6 +101,7 @@ fn should_increase_container_storage_size() {
             resized_container.readiness_probe.clone().map(|p| p.to_domain()),
             resized_container.liveness_probe.clone().map(|p| p.to_domain()),
             resized_container.advanced_settings.clone(),
+            None,
             AwsAppExtraSettings {},
             |transmitter| infra_ctx.context().get_event_details(transmitter),
             vec![],
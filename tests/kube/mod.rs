@@ -76,6 +76,8 @@ pub fn kube_test_env(options: TestEnvOption) -> (InfrastructureContext, Environm
         terraform_services: vec![],
         annotations_groups: btreemap! {},
         labels_groups: btreemap! {},
+        lifecycle: None,
+        admission_policies: None,
     };
 
     match options {
@@ -129,6 +131,7 @@ pub fn kube_test_env(options: TestEnvOption) -> (InfrastructureContext, Environm
                     url: Url::parse("https://public.ecr.aws").unwrap(),
                 },
                 image: "r3m4q3r9/pub-mirror-debian".to_string(),
+                image_artifact: None,
                 tag: "11.6-ci".to_string(),
                 command_args: vec![
                     "/bin/sh".to_string(),
@@ -202,6 +205,8 @@ pub fn kube_test_env(options: TestEnvOption) -> (InfrastructureContext, Environm
                 environment_vars_with_infos: BTreeMap::default(),
                 advanced_settings: Default::default(),
                 mounted_files: vec![],
+                event_driven_autoscaling: None,
+                additional_containers: vec![],
                 annotations_group_ids: BTreeSet::new(),
                 labels_group_ids: btreeset! {},
             };
@@ -288,6 +293,8 @@ pub fn kube_test_env(options: TestEnvOption) -> (InfrastructureContext, Environm
                 advanced_settings: Default::default(),
                 mounted_files: vec![],
                 container_registries: Vec::new(),
+                event_driven_autoscaling: None,
+                additional_containers: vec![],
                 annotations_group_ids: BTreeSet::new(),
                 labels_group_ids: btreeset! {},
                 should_delete_shared_registry: false,
@@ -326,10 +333,13 @@ pub fn kube_test_env(options: TestEnvOption) -> (InfrastructureContext, Environm
                         url: Url::parse("https://public.ecr.aws").unwrap(),
                     },
                     image: "r3m4q3r9/pub-mirror-debian".to_string(),
+                    artifact: None,
                     tag: "11.6-ci".to_string(),
                 },
                 max_nb_restart: 1,
                 max_duration_in_sec: 120,
+                parallelism: 1,
+                completions: 1,
                 environment_vars_with_infos: BTreeMap::default(),
                 advanced_settings: Default::default(),
                 mounted_files: vec![],
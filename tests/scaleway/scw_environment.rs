@@ -1634,6 +1634,7 @@ fn deploy_container_with_no_router_on_scw() {
                 url: Url::parse("https://public.ecr.aws").unwrap(),
             },
             image: "r3m4q3r9/pub-mirror-debian".to_string(),
+            image_artifact: None,
             tag: "11.6-ci".to_string(),
             command_args: vec![
                 "/bin/sh".to_string(),
@@ -1701,6 +1702,8 @@ fn deploy_container_with_no_router_on_scw() {
             environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{ value: general_purpose::STANDARD.encode("my_value"), is_secret: false} },
             mounted_files: vec![],
             advanced_settings: Default::default(),
+            event_driven_autoscaling: None,
+            additional_containers: vec![],
             annotations_group_ids: BTreeSet::new(),
             labels_group_ids: btreeset! {},
         }];
@@ -1775,6 +1778,7 @@ fn deploy_container_on_scw_with_mounted_files_as_volume() {
                 url: Url::parse("https://public.ecr.aws").unwrap(),
             },
             image: "r3m4q3r9/pub-mirror-debian".to_string(),
+            image_artifact: None,
             tag: "11.6-ci".to_string(),
             command_args: vec![
                 "/bin/sh".to_string(),
@@ -1845,6 +1849,8 @@ fn deploy_container_on_scw_with_mounted_files_as_volume() {
                 success_threshold: 1,
                 failure_threshold: 5,
             }),
+            event_driven_autoscaling: None,
+            additional_containers: vec![],
             annotations_group_ids: BTreeSet::new(),
             labels_group_ids: btreeset! {},
         }];
@@ -1951,6 +1957,7 @@ fn deploy_container_with_router_on_scw() {
                 credentials: None,
             },
             image: "r3m4q3r9/pub-mirror-httpd".to_string(),
+            image_artifact: None,
             tag: "2.4.56-alpine3.17".to_string(),
             command_args: vec![],
             entrypoint: None,
@@ -2007,6 +2014,8 @@ fn deploy_container_with_router_on_scw() {
                 success_threshold: 1,
                 failure_threshold: 5,
             }),
+            event_driven_autoscaling: None,
+            additional_containers: vec![],
             annotations_group_ids: btreeset! { annotations_group_id },
             labels_group_ids: btreeset! { labels_group_id },
         }];
@@ -2114,10 +2123,13 @@ fn deploy_job_on_scw_kapsule() {
                     url: Url::parse("https://public.ecr.aws").unwrap(),
                 },
                 image: "r3m4q3r9/pub-mirror-debian".to_string(),
+                artifact: None,
                 tag: "11.6-ci".to_string(),
             },
             max_nb_restart: 2,
             max_duration_in_sec: 300,
+            parallelism: 1,
+            completions: 1,
             default_port: Some(8080),
             //command_args: vec![],
             command_args: vec![
@@ -2245,10 +2257,13 @@ fn deploy_cronjob_on_scw_kapsule() {
                     url: Url::parse("https://public.ecr.aws").unwrap(),
                 },
                 image: "r3m4q3r9/pub-mirror-debian".to_string(),
+                artifact: None,
                 tag: "11.6-ci".to_string(),
             },
             max_nb_restart: 1,
             max_duration_in_sec: 30,
+            parallelism: 1,
+            completions: 1,
             default_port: Some(8080),
             command_args: vec![
                 "/bin/sh".to_string(),
@@ -2354,10 +2369,13 @@ fn deploy_cronjob_force_trigger_on_scw_kapsule() {
                     url: Url::parse("https://public.ecr.aws").unwrap(),
                 },
                 image: "r3m4q3r9/pub-mirror-debian".to_string(),
+                artifact: None,
                 tag: "11.6-ci".to_string(),
             },
             max_nb_restart: 1,
             max_duration_in_sec: 30,
+            parallelism: 1,
+            completions: 1,
             default_port: Some(8080),
             command_args: vec![
                 "/bin/sh".to_string(),
@@ -2469,6 +2487,8 @@ fn build_and_deploy_job_on_scw_kapsule() {
             },
             max_nb_restart: 2,
             max_duration_in_sec: 300,
+            parallelism: 1,
+            completions: 1,
             default_port: Some(8080),
             //command_args: vec![],
             command_args: vec![
@@ -2589,6 +2609,8 @@ fn build_and_deploy_job_on_scw_kapsule_with_mounted_files() {
             },
             max_nb_restart: 2,
             max_duration_in_sec: 300,
+            parallelism: 1,
+            completions: 1,
             default_port: Some(8080),
             //command_args: vec![],
             command_args: vec![
@@ -2727,6 +2749,7 @@ fn deploy_container_with_tcp_public_port() {
                 url: Url::parse("https://public.ecr.aws").unwrap(),
             },
             image: "r3m4q3r9/pub-mirror-debian".to_string(),
+            image_artifact: None,
             tag: "11.6-ci".to_string(),
             command_args: vec![
                 "/bin/sh".to_string(),
@@ -2806,6 +2829,8 @@ fn deploy_container_with_tcp_public_port() {
             environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{ value: general_purpose::STANDARD.encode("my_value"), is_secret:false} },
             mounted_files: vec![],
             advanced_settings: Default::default(),
+            event_driven_autoscaling: None,
+            additional_containers: vec![],
             annotations_group_ids: BTreeSet::new(),
             labels_group_ids: btreeset! {},
         }];